@@ -127,7 +127,7 @@ export const DiscriminantClassify = ({
                                                 value={
                                                     classifyState.WithinGroup
                                                         ? "WithinGroup"
-                                                        : ""
+                                                        : "SepGroup"
                                                 }
                                                 onValueChange={handleMatrixGrp}
                                             >
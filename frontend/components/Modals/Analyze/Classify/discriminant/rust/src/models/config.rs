@@ -1,6 +1,6 @@
 use serde::{ Deserialize, Serialize };
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct DiscriminantConfig {
     pub main: MainConfig,
     #[serde(rename = "defineRange")]
@@ -28,7 +28,19 @@ pub struct MainConfig {
     pub selection_variable: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+impl Default for MainConfig {
+    fn default() -> Self {
+        MainConfig {
+            grouping_variable: String::new(),
+            independent_variables: Vec::new(),
+            together: true,
+            stepwise: false,
+            selection_variable: None,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct DefineRangeConfig {
     #[serde(rename = "minRange")]
     pub min_range: Option<f64>,
@@ -36,13 +48,13 @@ pub struct DefineRangeConfig {
     pub max_range: Option<f64>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct SetValueConfig {
     #[serde(rename = "Value")]
     pub value: Option<f64>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct StatisticsConfig {
     #[serde(rename = "Means")]
     pub means: bool,
@@ -96,6 +108,27 @@ pub struct MethodConfig {
     pub p_removal: f64,
 }
 
+impl Default for MethodConfig {
+    fn default() -> Self {
+        MethodConfig {
+            wilks: true,
+            unexplained: false,
+            mahalonobis: false,
+            f_ratio: false,
+            raos: false,
+            f_value: true,
+            f_probability: false,
+            summary: true,
+            pairwise: false,
+            v_enter: 0.0,
+            f_entry: 3.84,
+            f_removal: 2.71,
+            p_entry: 0.05,
+            p_removal: 0.1,
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ClassifyConfig {
     #[serde(rename = "AllGroupEqual")]
@@ -126,7 +159,27 @@ pub struct ClassifyConfig {
     pub replace: bool,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+impl Default for ClassifyConfig {
+    fn default() -> Self {
+        ClassifyConfig {
+            all_group_equal: true,
+            group_size: false,
+            within_group: true,
+            sep_group: false,
+            case: false,
+            limit: false,
+            limit_value: None,
+            summary: false,
+            leave: false,
+            combine: false,
+            sep_grp: false,
+            terr: false,
+            replace: false,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct SaveConfig {
     #[serde(rename = "Predicted")]
     pub predicted: bool,
@@ -163,3 +216,21 @@ pub struct BootstrapConfig {
     #[serde(rename = "StrataVariables")]
     pub strata_variables: Option<Vec<String>>,
 }
+
+impl Default for BootstrapConfig {
+    fn default() -> Self {
+        BootstrapConfig {
+            perform_boot_strapping: false,
+            num_of_samples: 1000,
+            seed: false,
+            seed_value: 2000000,
+            level: 95.0,
+            percentile: true,
+            bca: false,
+            simple: true,
+            stratified: false,
+            variables: Vec::new(),
+            strata_variables: None,
+        }
+    }
+}
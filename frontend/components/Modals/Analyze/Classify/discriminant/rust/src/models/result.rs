@@ -37,6 +37,10 @@ pub struct DiscriminantResult {
     pub classification_function_coefficients: Option<ClassificationFunctionCoefficients>,
     #[serde(rename = "discriminant_histograms")]
     pub discriminant_histograms: Option<DiscriminantHistograms>,
+    #[serde(rename = "quadratic_classification")]
+    pub quadratic_classification: Option<QuadraticClassification>,
+    #[serde(rename = "bootstrap")]
+    pub bootstrap: Option<DiscriminantBootstrap>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -131,6 +135,43 @@ pub struct ClassificationResults {
     pub cross_validated_percentage: Option<HashMap<String, Vec<f64>>>,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct QuadraticClassification {
+    pub groups: Vec<String>,
+    #[serde(rename = "prior_probabilities")]
+    pub prior_probabilities: Vec<f64>,
+    #[serde(rename = "log_determinants")]
+    pub log_determinants: Vec<f64>,
+    #[serde(rename = "classification_results")]
+    pub classification_results: ClassificationResults,
+    #[serde(rename = "original_correct_percentage")]
+    pub original_correct_percentage: f64,
+    #[serde(rename = "cross_validated_correct_percentage")]
+    pub cross_validated_correct_percentage: Option<f64>,
+    #[serde(rename = "casewise_statistics")]
+    pub casewise_statistics: Option<QuadraticCasewiseStatistics>,
+    pub note: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct QuadraticCasewiseStatistics {
+    #[serde(rename = "case_number")]
+    pub case_number: Vec<usize>,
+    #[serde(rename = "actual_group")]
+    pub actual_group: Vec<String>,
+    #[serde(rename = "predicted_group")]
+    pub predicted_group: Vec<String>,
+    /// One row per case, columns in the order of `QuadraticClassification::groups`
+    #[serde(rename = "posterior_probabilities")]
+    pub posterior_probabilities: Vec<Vec<f64>>,
+    #[serde(rename = "squared_mahalanobis_distance")]
+    pub squared_mahalanobis_distance: Vec<Vec<f64>>,
+    #[serde(rename = "cross_validated_group")]
+    pub cross_validated_group: Option<Vec<String>>,
+    #[serde(rename = "cross_validated_posterior")]
+    pub cross_validated_posterior: Option<Vec<Vec<f64>>>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct BoxMTest {
     #[serde(rename = "box_m")]
@@ -336,3 +377,58 @@ pub struct GroupHistogram {
     #[serde(rename = "bin_edges")]
     pub bin_edges: Vec<f64>,
 }
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DiscriminantBootstrap {
+    #[serde(rename = "requested_samples")]
+    pub requested_samples: usize,
+    #[serde(rename = "valid_samples")]
+    pub valid_samples: usize,
+    #[serde(rename = "sampling_method")]
+    pub sampling_method: String,
+    #[serde(rename = "interval_method")]
+    pub interval_method: String,
+    #[serde(rename = "confidence_level")]
+    pub confidence_level: f64,
+    pub functions: Vec<String>,
+    #[serde(rename = "standardized_coefficients")]
+    pub standardized_coefficients: Vec<BootstrapEstimate>,
+    #[serde(rename = "structure_matrix")]
+    pub structure_matrix: Vec<BootstrapEstimate>,
+    #[serde(rename = "function_at_centroids")]
+    pub function_at_centroids: Vec<BootstrapEstimate>,
+    #[serde(rename = "classification_error")]
+    pub classification_error: BootstrapClassificationError,
+    pub note: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BootstrapEstimate {
+    /// Variable name, or group label for centroids
+    pub label: String,
+    pub function: String,
+    pub estimate: f64,
+    pub bias: f64,
+    #[serde(rename = "std_error")]
+    pub std_error: f64,
+    #[serde(rename = "lower_bound")]
+    pub lower_bound: f64,
+    #[serde(rename = "upper_bound")]
+    pub upper_bound: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BootstrapClassificationError {
+    #[serde(rename = "apparent_error")]
+    pub apparent_error: f64,
+    #[serde(rename = "leave_one_out_error")]
+    pub leave_one_out_error: f64,
+    #[serde(rename = "no_information_error")]
+    pub no_information_error: f64,
+    #[serde(rename = "relative_overfitting")]
+    pub relative_overfitting: f64,
+    #[serde(rename = "error_632")]
+    pub error_632: f64,
+    #[serde(rename = "error_632_plus")]
+    pub error_632_plus: f64,
+}
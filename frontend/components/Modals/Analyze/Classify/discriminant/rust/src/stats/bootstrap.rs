@@ -0,0 +1,710 @@
+//! Bootstrap inference for canonical discriminant functions.
+//!
+//! Cases are resampled with replacement, either from the whole sample or within
+//! each group, and the canonical discriminant functions are refitted on every
+//! replicate. Functions are aligned by sign with the original solution before
+//! bias, standard errors and confidence intervals are computed. The replicates
+//! are also used for the .632+ estimate of classification error
+//! (Efron & Tibshirani, 1997).
+
+use nalgebra::DMatrix;
+use rand_mt::Mt64;
+use statrs::distribution::{ ContinuousCDF, Normal };
+
+use crate::models::{
+    result::{ BootstrapClassificationError, BootstrapEstimate, DiscriminantBootstrap },
+    AnalysisData,
+    DiscriminantConfig,
+};
+
+use super::core::{
+    calculate_correlation,
+    extract_case_observations,
+    get_stepwise_selected_variables,
+    group_priors,
+    CaseObservations,
+    EPSILON,
+};
+
+/// Canonical discriminant solution fitted on one sample
+struct FittedFunctions {
+    /// Unstandardized coefficients, indexed [variable][function]
+    coefficients: Vec<Vec<f64>>,
+    overall_means: Vec<f64>,
+    /// Standardized coefficients, indexed [variable][function]
+    standardized: Vec<Vec<f64>>,
+    /// Correlations between variables and function scores, indexed [variable][function]
+    structure: Vec<Vec<f64>>,
+    /// Function values at group centroids, indexed [group][function]
+    centroids: Vec<Vec<f64>>,
+}
+
+impl FittedFunctions {
+    /// All reported statistics in a fixed order: coefficients, structure matrix, centroids
+    fn statistics(&self) -> Vec<f64> {
+        self.standardized
+            .iter()
+            .chain(self.structure.iter())
+            .chain(self.centroids.iter())
+            .flat_map(|row| row.iter().copied())
+            .collect()
+    }
+
+    /// Flips functions whose standardized coefficients point away from the reference solution
+    fn align_signs(&mut self, reference: &FittedFunctions) {
+        let num_functions = reference.centroids.first().map_or(0, |c| c.len());
+
+        for func_idx in 0..num_functions {
+            let agreement: f64 = self.standardized
+                .iter()
+                .zip(reference.standardized.iter())
+                .map(|(a, b)| a[func_idx] * b[func_idx])
+                .sum();
+
+            if agreement < 0.0 {
+                for row in self.coefficients
+                    .iter_mut()
+                    .chain(self.standardized.iter_mut())
+                    .chain(self.structure.iter_mut())
+                    .chain(self.centroids.iter_mut()) {
+                    row[func_idx] = -row[func_idx];
+                }
+            }
+        }
+    }
+
+    /// Predicted group of a case: nearest centroid in discriminant space, adjusted by priors
+    fn classify(&self, case: &[f64], priors: &[f64]) -> usize {
+        let num_functions = self.centroids.first().map_or(0, |c| c.len());
+        let scores: Vec<f64> = (0..num_functions)
+            .map(|func_idx| {
+                case.iter()
+                    .zip(self.overall_means.iter())
+                    .zip(self.coefficients.iter())
+                    .map(|((x, mean), coef)| (x - mean) * coef[func_idx])
+                    .sum()
+            })
+            .collect();
+
+        self.centroids
+            .iter()
+            .zip(priors.iter())
+            .map(|(centroid, prior)| {
+                let distance: f64 = scores
+                    .iter()
+                    .zip(centroid.iter())
+                    .map(|(s, c)| (s - c).powi(2))
+                    .sum();
+                -0.5 * distance + prior.ln()
+            })
+            .enumerate()
+            .max_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal))
+            .map(|(idx, _)| idx)
+            .unwrap_or(0)
+    }
+}
+
+/// Calculate bootstrap estimates for the canonical discriminant functions
+///
+/// Reports bias, standard error and confidence intervals for the standardized
+/// coefficients, the structure matrix and the functions at group centroids,
+/// together with the .632+ bootstrap estimate of classification error.
+///
+/// # Parameters
+/// * `data` - The analysis data
+/// * `config` - The discriminant analysis configuration
+///
+/// # Returns
+/// A DiscriminantBootstrap object with the bootstrap estimates
+pub fn calculate_discriminant_bootstrap(
+    data: &AnalysisData,
+    config: &DiscriminantConfig
+) -> Result<DiscriminantBootstrap, String> {
+    let bootstrap = &config.bootstrap;
+    if bootstrap.num_of_samples <= 0 {
+        return Err("Number of bootstrap samples must be positive".to_string());
+    }
+    let num_samples = bootstrap.num_of_samples as usize;
+
+    let confidence_level = if bootstrap.level > 1.0 {
+        bootstrap.level / 100.0
+    } else {
+        bootstrap.level
+    };
+    if confidence_level <= 0.0 || confidence_level >= 1.0 {
+        return Err("Bootstrap confidence level must be between 0 and 100".to_string());
+    }
+
+    // Stepwise selection is done once on the full sample and kept fixed across replicates
+    let variables = get_stepwise_selected_variables(data, config)?;
+    let observations = extract_case_observations(data, config)?;
+    let variable_indices: Vec<usize> = variables
+        .iter()
+        .filter_map(|var| config.main.independent_variables.iter().position(|v| v == var))
+        .collect();
+    let cases: Vec<Vec<f64>> = observations.values
+        .iter()
+        .map(|values| {
+            variable_indices
+                .iter()
+                .map(|&idx| values[idx])
+                .collect()
+        })
+        .collect();
+
+    let num_groups = observations.group_labels.len();
+    let num_functions = (num_groups.saturating_sub(1)).min(variables.len());
+    if num_functions == 0 {
+        return Err("Not enough groups or variables for canonical functions".to_string());
+    }
+
+    let all_cases: Vec<usize> = (0..cases.len()).collect();
+    let original = fit_functions(&cases, &observations, &all_cases, num_functions).ok_or_else(||
+        "Canonical functions could not be fitted; every group needs at least two cases".to_string()
+    )?;
+    let original_statistics = original.statistics();
+    let priors = group_priors(&observations, config);
+
+    // Resampling units: the whole sample, or each group separately
+    let strata: Vec<Vec<usize>> = if bootstrap.stratified {
+        (0..num_groups)
+            .map(|g| {
+                all_cases
+                    .iter()
+                    .copied()
+                    .filter(|&i| observations.groups[i] == g)
+                    .collect()
+            })
+            .collect()
+    } else {
+        vec![all_cases.clone()]
+    };
+
+    let mut rng = if bootstrap.seed {
+        Mt64::new(bootstrap.seed_value as u64)
+    } else {
+        Mt64::new(rand::random::<u64>())
+    };
+
+    let mut replicates: Vec<Vec<f64>> = Vec::with_capacity(num_samples);
+    let mut out_of_bag_errors = vec![0usize; cases.len()];
+    let mut out_of_bag_counts = vec![0usize; cases.len()];
+    let mut failed_samples = 0;
+
+    for _ in 0..num_samples {
+        let sample: Vec<usize> = strata
+            .iter()
+            .flat_map(|stratum| {
+                (0..stratum.len())
+                    .map(|_| stratum[(rng.next_u64() % (stratum.len() as u64)) as usize])
+                    .collect::<Vec<usize>>()
+            })
+            .collect();
+
+        let mut fitted = match fit_functions(&cases, &observations, &sample, num_functions) {
+            Some(fitted) => fitted,
+            None => {
+                failed_samples += 1;
+                continue;
+            }
+        };
+        fitted.align_signs(&original);
+        replicates.push(fitted.statistics());
+
+        // Cases left out of this replicate feed the leave-one-out bootstrap error
+        let mut in_sample = vec![false; cases.len()];
+        for &i in &sample {
+            in_sample[i] = true;
+        }
+        for (i, case) in cases.iter().enumerate() {
+            if !in_sample[i] {
+                out_of_bag_counts[i] += 1;
+                if fitted.classify(case, &priors) != observations.groups[i] {
+                    out_of_bag_errors[i] += 1;
+                }
+            }
+        }
+    }
+
+    if replicates.len() < 2 {
+        return Err(
+            "Too few bootstrap samples could be fitted; groups must keep at least two cases".to_string()
+        );
+    }
+
+    // Jackknife replicates give the acceleration constant for BCa intervals
+    let acceleration = if bootstrap.bca {
+        Some(jackknife_acceleration(&cases, &observations, &original, num_functions))
+    } else {
+        None
+    };
+
+    let alpha = 1.0 - confidence_level;
+    let estimates: Vec<BootstrapEstimate> = original_statistics
+        .iter()
+        .enumerate()
+        .map(|(stat_idx, &estimate)| {
+            let mut values: Vec<f64> = replicates
+                .iter()
+                .map(|replicate| replicate[stat_idx])
+                .collect();
+            values.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+
+            let count = values.len() as f64;
+            let mean = values.iter().sum::<f64>() / count;
+            let variance =
+                values
+                    .iter()
+                    .map(|v| (v - mean).powi(2))
+                    .sum::<f64>() / (count - 1.0);
+
+            let (lower_prob, upper_prob) = match &acceleration {
+                Some(acceleration) =>
+                    bca_probabilities(&values, estimate, acceleration[stat_idx], alpha),
+                None => (alpha / 2.0, 1.0 - alpha / 2.0),
+            };
+
+            BootstrapEstimate {
+                label: String::new(),
+                function: String::new(),
+                estimate,
+                bias: mean - estimate,
+                std_error: variance.sqrt(),
+                lower_bound: quantile(&values, lower_prob),
+                upper_bound: quantile(&values, upper_prob),
+            }
+        })
+        .collect();
+
+    // Split the flat list back into the three reported tables
+    let functions: Vec<String> = (1..=num_functions).map(|i| format!("Function {}", i)).collect();
+    let num_coefficients = variables.len() * num_functions;
+    let mut estimates = estimates.into_iter();
+    let standardized_coefficients = label_estimates(
+        estimates.by_ref().take(num_coefficients).collect(),
+        &variables,
+        &functions
+    );
+    let structure_matrix = label_estimates(
+        estimates.by_ref().take(num_coefficients).collect(),
+        &variables,
+        &functions
+    );
+    let function_at_centroids = label_estimates(
+        estimates.collect(),
+        &observations.group_labels,
+        &functions
+    );
+
+    let classification_error = error_632_plus(
+        &cases,
+        &observations,
+        &original,
+        &priors,
+        &out_of_bag_errors,
+        &out_of_bag_counts
+    );
+
+    let mut notes = vec![
+        format!(
+            "Bootstrap results are based on {} {} bootstrap samples.",
+            replicates.len(),
+            if bootstrap.stratified { "stratified" } else { "simple" }
+        ),
+        "Functions in each sample are reflected where needed to match the signs of the original solution.".to_string()
+    ];
+    if failed_samples > 0 {
+        notes.push(
+            format!(
+                "{} samples were discarded because a group had fewer than two cases.",
+                failed_samples
+            )
+        );
+    }
+    if bootstrap.stratified {
+        let other_strata: Vec<&String> = bootstrap.strata_variables
+            .iter()
+            .flatten()
+            .filter(|var| **var != config.main.grouping_variable)
+            .collect();
+        if !other_strata.is_empty() {
+            notes.push(
+                "Strata are defined by the grouping variable; other strata variables are not part of the analysis data.".to_string()
+            );
+        }
+    }
+    if config.main.stepwise {
+        notes.push(
+            "Variables selected by the stepwise method are held fixed across bootstrap samples.".to_string()
+        );
+    }
+
+    Ok(DiscriminantBootstrap {
+        requested_samples: num_samples,
+        valid_samples: replicates.len(),
+        sampling_method: (
+            if bootstrap.stratified {
+                "Stratified"
+            } else {
+                "Simple"
+            }
+        ).to_string(),
+        interval_method: (if bootstrap.bca { "BCa" } else { "Percentile" }).to_string(),
+        confidence_level: confidence_level * 100.0,
+        functions,
+        standardized_coefficients,
+        structure_matrix,
+        function_at_centroids,
+        classification_error,
+        note: notes.join(" "),
+    })
+}
+
+/// Fits the canonical discriminant functions on the cases listed in `sample`.
+/// Returns None when a group has fewer than two cases in the sample.
+fn fit_functions(
+    cases: &[Vec<f64>],
+    observations: &CaseObservations,
+    sample: &[usize],
+    num_functions: usize
+) -> Option<FittedFunctions> {
+    let num_groups = observations.group_labels.len();
+    let p = cases.first().map_or(0, |c| c.len());
+    let n = sample.len();
+
+    let mut group_counts = vec![0usize; num_groups];
+    let mut group_means = vec![vec![0.0; p]; num_groups];
+    let mut overall_means = vec![0.0; p];
+
+    for &i in sample {
+        let g = observations.groups[i];
+        group_counts[g] += 1;
+        for (var_idx, &value) in cases[i].iter().enumerate() {
+            group_means[g][var_idx] += value;
+            overall_means[var_idx] += value;
+        }
+    }
+
+    if group_counts.iter().any(|&count| count < 2) {
+        return None;
+    }
+
+    for (means, &count) in group_means.iter_mut().zip(group_counts.iter()) {
+        means.iter_mut().for_each(|m| {
+            *m /= count as f64;
+        });
+    }
+    overall_means.iter_mut().for_each(|m| {
+        *m /= n as f64;
+    });
+
+    // Pooled within-groups covariance and between-groups SSCP matrices
+    let mut pooled_within = DMatrix::zeros(p, p);
+    for &i in sample {
+        let means = &group_means[observations.groups[i]];
+        for a in 0..p {
+            for b in 0..p {
+                pooled_within[(a, b)] += (cases[i][a] - means[a]) * (cases[i][b] - means[b]);
+            }
+        }
+    }
+    pooled_within /= (n - num_groups) as f64;
+    for a in 0..p {
+        pooled_within[(a, a)] += EPSILON;
+    }
+
+    let mut between_groups = DMatrix::zeros(p, p);
+    for (means, &count) in group_means.iter().zip(group_counts.iter()) {
+        for a in 0..p {
+            for b in 0..p {
+                between_groups[(a, b)] +=
+                    (count as f64) *
+                    (means[a] - overall_means[a]) *
+                    (means[b] - overall_means[b]);
+            }
+        }
+    }
+
+    let coefficients = canonical_coefficients(&pooled_within, &between_groups, num_functions)?;
+
+    let standardized: Vec<Vec<f64>> = coefficients
+        .iter()
+        .enumerate()
+        .map(|(var_idx, coefs)| {
+            let std_dev = pooled_within[(var_idx, var_idx)].sqrt();
+            coefs
+                .iter()
+                .map(|c| c * std_dev)
+                .collect()
+        })
+        .collect();
+
+    let centroids: Vec<Vec<f64>> = group_means
+        .iter()
+        .map(|means| {
+            (0..num_functions)
+                .map(|func_idx| {
+                    (0..p)
+                        .map(|var_idx| {
+                            coefficients[var_idx][func_idx] *
+                                (means[var_idx] - overall_means[var_idx])
+                        })
+                        .sum()
+                })
+                .collect()
+        })
+        .collect();
+
+    // Structure matrix: correlations between variables and function scores over all sampled cases
+    let scores: Vec<Vec<f64>> = (0..num_functions)
+        .map(|func_idx| {
+            sample
+                .iter()
+                .map(|&i| {
+                    (0..p)
+                        .map(|var_idx| {
+                            coefficients[var_idx][func_idx] *
+                                (cases[i][var_idx] - overall_means[var_idx])
+                        })
+                        .sum()
+                })
+                .collect()
+        })
+        .collect();
+    let structure: Vec<Vec<f64>> = (0..p)
+        .map(|var_idx| {
+            let values: Vec<f64> = sample
+                .iter()
+                .map(|&i| cases[i][var_idx])
+                .collect();
+            scores
+                .iter()
+                .map(|func_scores| calculate_correlation(&values, func_scores))
+                .collect()
+        })
+        .collect();
+
+    Some(FittedFunctions {
+        coefficients,
+        overall_means,
+        standardized,
+        structure,
+        centroids,
+    })
+}
+
+/// Eigenvectors of W^-1 B scaled so that the within-groups variance of every function is 1.
+/// Solved as the symmetric problem L^-1 B L^-T u = λu with W = LL', then v = L^-T u.
+fn canonical_coefficients(
+    pooled_within: &DMatrix<f64>,
+    between_groups: &DMatrix<f64>,
+    num_functions: usize
+) -> Option<Vec<Vec<f64>>> {
+    let p = pooled_within.nrows();
+    let lower = pooled_within.clone().cholesky()?.unpack();
+    let lower_inverse = lower.try_inverse()?;
+
+    let transformed = &lower_inverse * between_groups * lower_inverse.transpose();
+    let transformed = (&transformed + transformed.transpose()) * 0.5;
+    let eigen = transformed.symmetric_eigen();
+
+    let mut order: Vec<usize> = (0..p).collect();
+    order.sort_by(|&i, &j| {
+        eigen.eigenvalues[j].partial_cmp(&eigen.eigenvalues[i]).unwrap_or(std::cmp::Ordering::Equal)
+    });
+
+    let mut coefficients = vec![vec![0.0; num_functions]; p];
+    for (func_idx, &eigen_idx) in order.iter().take(num_functions).enumerate() {
+        let vector = lower_inverse.transpose() * eigen.eigenvectors.column(eigen_idx);
+        for var_idx in 0..p {
+            coefficients[var_idx][func_idx] = vector[var_idx];
+        }
+    }
+
+    Some(coefficients)
+}
+
+/// Acceleration constant of every statistic from leave-one-out (jackknife) refits
+fn jackknife_acceleration(
+    cases: &[Vec<f64>],
+    observations: &CaseObservations,
+    original: &FittedFunctions,
+    num_functions: usize
+) -> Vec<f64> {
+    let num_statistics = original.statistics().len();
+    let mut jackknife: Vec<Vec<f64>> = Vec::with_capacity(cases.len());
+
+    for left_out in 0..cases.len() {
+        let sample: Vec<usize> = (0..cases.len()).filter(|&i| i != left_out).collect();
+        if let Some(mut fitted) = fit_functions(cases, observations, &sample, num_functions) {
+            fitted.align_signs(original);
+            jackknife.push(fitted.statistics());
+        }
+    }
+
+    if jackknife.is_empty() {
+        return vec![0.0; num_statistics];
+    }
+
+    (0..num_statistics)
+        .map(|stat_idx| {
+            let mean =
+                jackknife
+                    .iter()
+                    .map(|values| values[stat_idx])
+                    .sum::<f64>() / (jackknife.len() as f64);
+            let (sum_squares, sum_cubes) = jackknife.iter().fold((0.0, 0.0), |(sq, cu), values| {
+                let diff = mean - values[stat_idx];
+                (sq + diff.powi(2), cu + diff.powi(3))
+            });
+
+            if sum_squares > 0.0 {
+                sum_cubes / (6.0 * sum_squares.powf(1.5))
+            } else {
+                0.0
+            }
+        })
+        .collect()
+}
+
+/// Percentile levels of the BCa interval; falls back to the percentile interval
+/// when the bias correction is not finite
+fn bca_probabilities(sorted: &[f64], estimate: f64, acceleration: f64, alpha: f64) -> (f64, f64) {
+    let normal = Normal::new(0.0, 1.0).unwrap();
+    let percentile = (alpha / 2.0, 1.0 - alpha / 2.0);
+
+    let below = sorted
+        .iter()
+        .filter(|&&v| v < estimate)
+        .count() as f64;
+    let ties = sorted
+        .iter()
+        .filter(|&&v| v == estimate)
+        .count() as f64;
+    let proportion = (below + 0.5 * ties) / (sorted.len() as f64);
+    if proportion <= 0.0 || proportion >= 1.0 {
+        return percentile;
+    }
+
+    let z0 = normal.inverse_cdf(proportion);
+    let adjust = |prob: f64| {
+        let z = normal.inverse_cdf(prob);
+        let shifted = z0 + z;
+        normal.cdf(z0 + shifted / (1.0 - acceleration * shifted))
+    };
+
+    let lower = adjust(alpha / 2.0);
+    let upper = adjust(1.0 - alpha / 2.0);
+    if lower.is_finite() && upper.is_finite() {
+        (lower, upper)
+    } else {
+        percentile
+    }
+}
+
+/// Quantile of sorted values with linear interpolation between order statistics
+fn quantile(sorted: &[f64], prob: f64) -> f64 {
+    let position = prob.clamp(0.0, 1.0) * ((sorted.len() - 1) as f64);
+    let lower = position.floor() as usize;
+    let upper = position.ceil() as usize;
+    let fraction = position - (lower as f64);
+
+    sorted[lower] + fraction * (sorted[upper] - sorted[lower])
+}
+
+/// Attaches row labels and function names to estimates stored row by row
+fn label_estimates(
+    estimates: Vec<BootstrapEstimate>,
+    labels: &[String],
+    functions: &[String]
+) -> Vec<BootstrapEstimate> {
+    estimates
+        .into_iter()
+        .enumerate()
+        .map(|(idx, estimate)| BootstrapEstimate {
+            label: labels[idx / functions.len()].clone(),
+            function: functions[idx % functions.len()].clone(),
+            ..estimate
+        })
+        .collect()
+}
+
+/// The .632+ estimate of classification error (Efron & Tibshirani, 1997)
+fn error_632_plus(
+    cases: &[Vec<f64>],
+    observations: &CaseObservations,
+    original: &FittedFunctions,
+    priors: &[f64],
+    out_of_bag_errors: &[usize],
+    out_of_bag_counts: &[usize]
+) -> BootstrapClassificationError {
+    let num_groups = observations.group_labels.len();
+    let n = cases.len() as f64;
+
+    let predicted: Vec<usize> = cases
+        .iter()
+        .map(|case| original.classify(case, priors))
+        .collect();
+    let apparent_error =
+        (
+            predicted
+                .iter()
+                .zip(observations.groups.iter())
+                .filter(|(p, a)| p != a)
+                .count() as f64
+        ) / n;
+
+    // Leave-one-out bootstrap error: each case is judged only by samples that exclude it
+    let case_errors: Vec<f64> = out_of_bag_errors
+        .iter()
+        .zip(out_of_bag_counts.iter())
+        .filter(|(_, &count)| count > 0)
+        .map(|(&errors, &count)| (errors as f64) / (count as f64))
+        .collect();
+    let leave_one_out_error = if case_errors.is_empty() {
+        apparent_error
+    } else {
+        case_errors.iter().sum::<f64>() / (case_errors.len() as f64)
+    };
+
+    // No-information error rate: predictions and group memberships independent
+    let no_information_error: f64 = (0..num_groups)
+        .map(|g| {
+            let observed =
+                (
+                    observations.groups
+                        .iter()
+                        .filter(|&&a| a == g)
+                        .count() as f64
+                ) / n;
+            let assigned =
+                (
+                    predicted
+                        .iter()
+                        .filter(|&&p| p == g)
+                        .count() as f64
+                ) / n;
+            observed * (1.0 - assigned)
+        })
+        .sum();
+
+    let capped_error = leave_one_out_error.min(no_information_error);
+    let relative_overfitting = if
+        leave_one_out_error > apparent_error &&
+        no_information_error > apparent_error
+    {
+        ((capped_error - apparent_error) / (no_information_error - apparent_error)).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    let weight = 0.632 / (1.0 - 0.368 * relative_overfitting);
+
+    BootstrapClassificationError {
+        apparent_error,
+        leave_one_out_error,
+        no_information_error,
+        relative_overfitting,
+        error_632: 0.368 * apparent_error + 0.632 * leave_one_out_error,
+        error_632_plus: (1.0 - weight) * apparent_error + weight * capped_error,
+    }
+}
@@ -12,6 +12,9 @@ use super::core::{
     EPSILON,
 };
 
+/// Group covariance matrices with their log determinants and group sizes
+type GroupCovariances = (Vec<DMatrix<f64>>, Vec<f64>, Vec<usize>);
+
 /// Calculates Box's M test for homogeneity of covariance matrices.
 ///
 /// Box's M test is used to test the null hypothesis:
@@ -125,7 +128,7 @@ pub fn calculate_box_m_test(
 fn compute_group_covariances(
     dataset: &AnalyzedDataset,
     variables: &[String]
-) -> Result<(Vec<DMatrix<f64>>, Vec<f64>, Vec<usize>), String> {
+) -> Result<GroupCovariances, String> {
     let mut group_covs = Vec::new();
     let mut group_log_dets = Vec::new();
     let mut group_sizes = Vec::new();
@@ -174,12 +177,10 @@ fn compute_group_covariances(
         .collect();
 
     // Collect valid results
-    for result in results {
-        if let Some((cov, log_det, size)) = result {
-            group_covs.push(cov);
-            group_log_dets.push(log_det);
-            group_sizes.push(size);
-        }
+    for (cov, log_det, size) in results.into_iter().flatten() {
+        group_covs.push(cov);
+        group_log_dets.push(log_det);
+        group_sizes.push(size);
     }
 
     Ok((group_covs, group_log_dets, group_sizes))
@@ -421,7 +422,7 @@ fn compute_p_value(f_approx: f64, df1: f64, df2: f64) -> f64 {
     }
 
     match statrs::distribution::FisherSnedecor::new(df1, df2) {
-        Ok(dist) => dist.sf(f_approx).max(0.0).min(1.0),
+        Ok(dist) => dist.sf(f_approx).clamp(0.0, 1.0),
         Err(_) => 1.0,
     }
 }
@@ -19,6 +19,9 @@ use crate::models::{
     DiscriminantConfig,
 };
 
+/// Cross-validated classification counts and row percentages
+type CrossValidation = (Option<HashMap<String, Vec<i32>>>, Option<HashMap<String, Vec<f64>>>);
+
 use super::core::{
     calculate_canonical_functions,
     calculate_eigen_statistics,
@@ -199,7 +202,7 @@ fn calculate_cross_validation(
     config: &DiscriminantConfig,
     dataset: &AnalyzedDataset,
     record_groups: &HashMap<usize, String>
-) -> Result<(Option<HashMap<String, Vec<i32>>>, Option<HashMap<String, Vec<f64>>>), String> {
+) -> Result<CrossValidation, String> {
     let independent_variables = &config.main.independent_variables;
     let mut cross_validated_classification = HashMap::new();
     let mut cross_validated_percentage = HashMap::new();
@@ -404,8 +407,8 @@ fn classify_case(
 
     // Normalize posteriors
     if sum_exp > 0.0 {
-        for i in 0..num_groups {
-            posteriors[i] /= sum_exp;
+        for posterior in posteriors.iter_mut() {
+            *posterior /= sum_exp;
         }
     }
 
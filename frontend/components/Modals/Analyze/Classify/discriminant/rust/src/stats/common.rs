@@ -15,6 +15,9 @@ use crate::models::{ AnalysisData, DataRecord, DataValue, DiscriminantConfig };
 pub const EPSILON: f64 = 1e-10;
 pub const TOLERANCE_THRESHOLD: f64 = 0.001;
 
+/// Values by variable and group, the group labels, and the total number of cases
+pub type GroupedData = (HashMap<String, HashMap<String, Vec<f64>>>, Vec<String>, usize);
+
 /// Analyzed dataset structure to consolidate extracted data
 #[derive(Debug, Clone)]
 pub struct AnalyzedDataset {
@@ -32,6 +35,17 @@ pub struct AnalyzedDataset {
     pub total_cases: usize,
 }
 
+/// Case-level observations kept in the original case order
+#[derive(Debug, Clone)]
+pub struct CaseObservations {
+    /// Sorted group labels, in the same order as `AnalyzedDataset::group_labels`
+    pub group_labels: Vec<String>,
+    /// Index into `group_labels` for each case
+    pub groups: Vec<usize>,
+    /// Values of the independent variables for each case
+    pub values: Vec<Vec<f64>>,
+}
+
 /// Extract and prepare data for discriminant analysis
 ///
 /// This function takes the raw analysis data and configuration, and returns
@@ -129,24 +143,18 @@ pub fn extract_grouped_data(
     independent_variables: &[String],
     min_range: Option<f64>,
     max_range: Option<f64>
-) -> Result<(HashMap<String, HashMap<String, Vec<f64>>>, Vec<String>, usize), String> {
+) -> Result<GroupedData, String> {
     let mut group_mappings: HashMap<String, Vec<usize>> = HashMap::new();
 
     // Extract group data with range checking
     for (i, record) in data.group_data.iter().flatten().enumerate() {
         if let Some(value) = record.values.get(grouping_variable) {
             let group_label = match value {
-                DataValue::Number(num) => {
-                    // Check if value is within range
-                    if
-                        min_range.map_or(true, |min| *num >= min) &&
-                        max_range.map_or(true, |max| *num <= max)
-                    {
-                        num.to_string()
-                    } else {
-                        continue;
-                    }
-                }
+                // Check if value is within range
+                DataValue::Number(num) if
+                    min_range.is_none_or(|min| *num >= min) &&
+                    max_range.is_none_or(|max| *num <= max)
+                => num.to_string(),
                 DataValue::Text(text) => text.clone(),
                 _ => {
                     continue;
@@ -306,6 +314,96 @@ pub fn extract_values_by_name(records: &[DataRecord], field_name: &str) -> Vec<f
         .collect()
 }
 
+/// Extract case-level observations for analyses that work case by case
+///
+/// Cases outside the group range or with a missing independent variable are skipped.
+pub fn extract_case_observations(
+    data: &AnalysisData,
+    config: &DiscriminantConfig
+) -> Result<CaseObservations, String> {
+    let independent_variables = &config.main.independent_variables;
+    let grouping_variable = &config.main.grouping_variable;
+    let min_range = config.define_range.min_range;
+    let max_range = config.define_range.max_range;
+
+    let mut case_labels = Vec::new();
+    let mut values = Vec::new();
+
+    for (i, record) in data.group_data.iter().flatten().enumerate() {
+        let group_label = match record.values.get(grouping_variable) {
+            Some(DataValue::Number(num)) if
+                min_range.is_none_or(|min| *num >= min) &&
+                max_range.is_none_or(|max| *num <= max)
+            => num.to_string(),
+            Some(DataValue::Text(text)) => text.clone(),
+            _ => {
+                continue;
+            }
+        };
+
+        let case_values: Vec<f64> = independent_variables
+            .iter()
+            .enumerate()
+            .filter_map(|(var_idx, var_name)| {
+                match data.independent_data.get(var_idx).and_then(|v| v.get(i)) {
+                    Some(var_record) =>
+                        match var_record.values.get(var_name) {
+                            Some(DataValue::Number(val)) if !val.is_nan() => Some(*val),
+                            _ => None,
+                        }
+                    None => None,
+                }
+            })
+            .collect();
+
+        if case_values.len() == independent_variables.len() {
+            case_labels.push(group_label);
+            values.push(case_values);
+        }
+    }
+
+    if values.is_empty() {
+        return Err("No valid cases found for case-level analysis".to_string());
+    }
+
+    let mut group_labels: Vec<String> = case_labels.clone();
+    group_labels.sort();
+    group_labels.dedup();
+
+    let groups = case_labels
+        .iter()
+        .map(|label| group_labels.iter().position(|g| g == label).unwrap_or(0))
+        .collect();
+
+    Ok(CaseObservations {
+        group_labels,
+        groups,
+        values,
+    })
+}
+
+/// Prior probabilities of the groups for case-level classification
+///
+/// Follows `calculate_prior_probabilities`: proportional to the group sizes when
+/// "Compute from group sizes" is selected, equal otherwise.
+pub fn group_priors(observations: &CaseObservations, config: &DiscriminantConfig) -> Vec<f64> {
+    let num_groups = observations.group_labels.len();
+    let total_cases = observations.groups.len();
+
+    if config.classify.group_size && !config.classify.all_group_equal && total_cases > 0 {
+        let mut group_sizes = vec![0usize; num_groups];
+        for &group in &observations.groups {
+            group_sizes[group] += 1;
+        }
+        group_sizes
+            .iter()
+            .map(|&size| (size as f64) / (total_cases as f64))
+            .collect()
+    } else {
+        vec![1.0 / (num_groups as f64); num_groups]
+    }
+}
+
 /// Extract all variable values from a single case
 pub fn extract_case_values(record: &DataRecord, variables: &[String]) -> Vec<f64> {
     variables
@@ -402,7 +500,7 @@ pub fn calculate_rank_and_log_det(matrix: &DMatrix<f64>) -> (i32, f64) {
     let svd = SVD::new(matrix.clone(), false, false);
     let singular_values = &svd.singular_values;
 
-    let max_val = singular_values.iter().fold(0.0, |max, &v| (max as f64).max(v));
+    let max_val = singular_values.iter().fold(0.0, |max: f64, &v| max.max(v));
     let threshold = EPSILON * max_val;
 
     let rank = singular_values
@@ -461,10 +559,10 @@ pub fn calculate_p_value_from_f(f_value: f64, df1: f64, df2: f64) -> f64 {
                 1.0
             } else {
                 // Enforce bounds of p-value (should be between 0 and 1)
-                p_value.max(0.0).min(1.0)
+                p_value.clamp(0.0, 1.0)
             }
         }
-        Err(e) => { 1.0 }
+        Err(_) => { 1.0 }
     }
 }
 
@@ -511,21 +609,17 @@ pub fn filter_valid_cases(
         for (group_idx, sel_group) in selection_data.iter().enumerate() {
             if group_idx < valid_indices.len() {
                 // Filter valid indices based on selection criteria
-                valid_indices[group_idx] = valid_indices[group_idx]
-                    .iter()
-                    .filter(|&&idx| {
-                        if idx < sel_group.len() {
-                            match sel_group[idx].values.get(selection_var) {
-                                Some(DataValue::Number(val)) => (val - set_value).abs() < EPSILON,
-                                Some(DataValue::Text(s)) => s == &set_value.to_string(),
-                                _ => false,
-                            }
-                        } else {
-                            false
+                valid_indices[group_idx].retain(|&idx| {
+                    if idx < sel_group.len() {
+                        match sel_group[idx].values.get(selection_var) {
+                            Some(DataValue::Number(val)) => (val - set_value).abs() < EPSILON,
+                            Some(DataValue::Text(s)) => s == &set_value.to_string(),
+                            _ => false,
                         }
-                    })
-                    .copied()
-                    .collect();
+                    } else {
+                        false
+                    }
+                });
             }
         }
     }
@@ -534,25 +628,21 @@ pub fn filter_valid_cases(
     for (group_idx, group) in data.group_data.iter().enumerate() {
         if group_idx < valid_indices.len() {
             // Filter valid indices based on group range
-            valid_indices[group_idx] = valid_indices[group_idx]
-                .iter()
-                .filter(|&&idx| {
-                    if idx < group.len() {
-                        match group[idx].values.get(group_var) {
-                            Some(DataValue::Number(val)) => {
-                                let min_valid = min_range.map_or(true, |min| val >= &min);
-                                let max_valid = max_range.map_or(true, |max| val <= &max);
-                                min_valid && max_valid
-                            }
-                            Some(DataValue::Text(_)) => true, // Text values valid for groups
-                            _ => false,
+            valid_indices[group_idx].retain(|&idx| {
+                if idx < group.len() {
+                    match group[idx].values.get(group_var) {
+                        Some(DataValue::Number(val)) => {
+                            let min_valid = min_range.is_none_or(|min| val >= &min);
+                            let max_valid = max_range.is_none_or(|max| val <= &max);
+                            min_valid && max_valid
                         }
-                    } else {
-                        false
+                        Some(DataValue::Text(_)) => true, // Text values valid for groups
+                        _ => false,
                     }
-                })
-                .copied()
-                .collect();
+                } else {
+                    false
+                }
+            });
         }
     }
 
@@ -560,34 +650,30 @@ pub fn filter_valid_cases(
     for (group_idx, _) in data.group_data.iter().enumerate() {
         if group_idx < valid_indices.len() {
             // Filter valid indices based on missing independent vars
-            valid_indices[group_idx] = valid_indices[group_idx]
-                .iter()
-                .filter(|&&idx| {
-                    // Check if all independent variables have valid values
-                    !independent_vars.iter().any(|var_name| {
-                        let var_idx = independent_vars.iter().position(|v| v == var_name);
-
-                        match var_idx {
-                            Some(var_idx) if var_idx < data.independent_data.len() => {
-                                let var_data = &data.independent_data[var_idx];
-                                if idx >= var_data.len() {
-                                    return true;
-                                }
+            valid_indices[group_idx].retain(|&idx| {
+                // Check if all independent variables have valid values
+                !independent_vars.iter().any(|var_name| {
+                    let var_idx = independent_vars.iter().position(|v| v == var_name);
+
+                    match var_idx {
+                        Some(var_idx) if var_idx < data.independent_data.len() => {
+                            let var_data = &data.independent_data[var_idx];
+                            if idx >= var_data.len() {
+                                return true;
+                            }
 
-                                match var_data[idx].values.get(var_name) {
-                                    Some(DataValue::Number(val)) if val.is_nan() => true,
-                                    Some(DataValue::Text(s)) if s.trim().is_empty() => true,
-                                    Some(DataValue::Null) => true,
-                                    None => true,
-                                    _ => false,
-                                }
+                            match var_data[idx].values.get(var_name) {
+                                Some(DataValue::Number(val)) if val.is_nan() => true,
+                                Some(DataValue::Text(s)) if s.trim().is_empty() => true,
+                                Some(DataValue::Null) => true,
+                                None => true,
+                                _ => false,
                             }
-                            _ => true,
                         }
-                    })
+                        _ => true,
+                    }
                 })
-                .copied()
-                .collect();
+            });
         }
     }
 
@@ -616,7 +702,7 @@ pub fn filter_valid_cases(
     for var_idx in 0..data.independent_data.len() {
         let mut filtered_var_data = Vec::new();
 
-        for (group_idx, group_valid_indices) in valid_indices.iter().enumerate() {
+        for group_valid_indices in &valid_indices {
             for &idx in group_valid_indices {
                 if idx < data.independent_data[var_idx].len() {
                     filtered_var_data.push(data.independent_data[var_idx][idx].clone());
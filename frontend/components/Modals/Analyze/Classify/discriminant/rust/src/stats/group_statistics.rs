@@ -4,6 +4,9 @@ use rayon::prelude::*;
 use crate::models::{ result::GroupStatistics, AnalysisData, DiscriminantConfig };
 use super::core::{ extract_analyzed_dataset, calculate_std_dev };
 
+/// Variable name, mean, standard deviation, unweighted and weighted N
+type VariableStatistics = (String, f64, f64, f64, f64);
+
 pub fn calculate_group_statistics(
     data: &AnalysisData,
     config: &DiscriminantConfig
@@ -47,7 +50,7 @@ pub fn calculate_group_statistics(
     result.groups = unique_groups;
 
     // Calculate statistics for each group and variable in parallel
-    let statistics: Vec<(String, Vec<(String, f64, f64, f64, f64)>)> = result.groups
+    let statistics: Vec<(String, Vec<VariableStatistics>)> = result.groups
         .par_iter()
         .map(|group| {
             let var_stats = independent_variables
@@ -84,7 +87,7 @@ pub fn calculate_group_statistics(
 
     // Combine results
     if config.statistics.means {
-        for (group_idx, (group, var_stats)) in statistics.iter().enumerate() {
+        for (_, var_stats) in &statistics {
             for (variable, mean, std_dev, unweighted, weighted) in var_stats {
                 result.means.get_mut(variable).unwrap().push(*mean);
                 result.std_deviations.get_mut(variable).unwrap().push(*std_dev);
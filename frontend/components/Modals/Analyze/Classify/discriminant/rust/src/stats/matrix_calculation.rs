@@ -311,13 +311,8 @@ pub fn calculate_covariance_matrices(
     // Initialize collection for all matrices
     let mut matrices = HashMap::new();
 
-    // Group degrees of freedom (not used for total covariance calculation)
-    let mut group_dfs = 0;
-
     // Calculate covariance matrix for each group in parallel
-    let group_matrices: Vec<
-        (String, HashMap<String, HashMap<String, f64>>, usize)
-    > = dataset.group_labels
+    let group_matrices: Vec<_> = dataset.group_labels
         .par_iter()
         .filter_map(|group| {
             // Check if this group has enough data
@@ -330,16 +325,13 @@ pub fn calculate_covariance_matrices(
                 return None; // Skip groups with insufficient data
             }
 
-            // Calculate degrees of freedom for this group
-            let df = group_size - 1;
-
             // Create covariance matrix for this group
             let mut group_matrix = HashMap::new();
 
-            for (i, var_i) in variables.iter().enumerate() {
+            for var_i in variables.iter() {
                 let mut row = HashMap::new();
 
-                for (j, var_j) in variables.iter().enumerate() {
+                for var_j in variables.iter() {
                     if
                         let (Some(values_i), Some(values_j)) = (
                             dataset.group_data.get(var_i).and_then(|g| g.get(group)),
@@ -369,14 +361,13 @@ pub fn calculate_covariance_matrices(
                 group_matrix.insert(var_i.clone(), row);
             }
 
-            Some((group.clone(), group_matrix, df))
+            Some((group.clone(), group_matrix))
         })
         .collect();
 
     // Combine results
-    for (group, matrix, df) in group_matrices {
+    for (group, matrix) in group_matrices {
         matrices.insert(group, matrix);
-        group_dfs += df;
     }
 
     // For total covariance matrix, df = total_cases - 1
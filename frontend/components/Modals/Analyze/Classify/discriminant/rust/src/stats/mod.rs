@@ -11,6 +11,8 @@ pub mod log_determinant;
 pub mod matrix_calculation;
 pub mod pairwise_comparison;
 pub mod prior_probabilities;
+pub mod quadratic_classification;
+pub mod bootstrap;
 pub mod statistical_test;
 pub mod stepwise_method;
 pub mod stepwise_statistics;
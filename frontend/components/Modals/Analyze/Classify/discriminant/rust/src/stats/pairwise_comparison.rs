@@ -188,13 +188,11 @@ pub fn calculate_mahalanobis_distance(
     match pooled_cov.try_inverse() {
         Some(inv_cov) => {
             // Calculate Mahalanobis distance squared
-            let d2 = (mean_diff.transpose() * (inv_cov * mean_diff))[0];
-            d2
+            (mean_diff.transpose() * (inv_cov * mean_diff))[0]
         }
         None => {
             // If matrix is singular, use a simplified approach
-            let d2 = mean_diff.norm_squared();
-            d2
+            mean_diff.norm_squared()
         }
     }
 }
@@ -0,0 +1,383 @@
+//! Quadratic classification for discriminant analysis.
+//!
+//! This module classifies cases with separate-groups covariance matrices instead of
+//! the pooled within-groups matrix used by the canonical functions. Each group keeps
+//! its own covariance, so the boundaries between groups are quadratic.
+
+use std::collections::HashMap;
+
+use nalgebra::{ Cholesky, DMatrix, DVector, Dyn };
+
+use crate::models::{
+    result::{ ClassificationResults, QuadraticCasewiseStatistics, QuadraticClassification },
+    AnalysisData,
+    DiscriminantConfig,
+};
+
+use super::core::{ extract_case_observations, group_priors, CaseObservations };
+
+/// Classification counts, row percentages and overall percent correct
+type ClassificationTable = (HashMap<String, Vec<i32>>, HashMap<String, Vec<f64>>, f64);
+
+/// Mean and factored covariance matrix of a single group
+struct GroupModel {
+    mean: DVector<f64>,
+    cholesky: Cholesky<f64, Dyn>,
+    log_determinant: f64,
+}
+
+impl GroupModel {
+    /// Builds the model from a mean vector and covariance matrix.
+    /// Returns None when the covariance matrix is not positive definite.
+    fn new(mean: DVector<f64>, covariance: DMatrix<f64>) -> Option<Self> {
+        let cholesky = Cholesky::new(covariance)?;
+        let log_determinant =
+            2.0 *
+            cholesky
+                .l_dirty()
+                .diagonal()
+                .iter()
+                .map(|d| d.ln())
+                .sum::<f64>();
+
+        if !log_determinant.is_finite() {
+            return None;
+        }
+
+        Some(GroupModel {
+            mean,
+            cholesky,
+            log_determinant,
+        })
+    }
+
+    /// Squared Mahalanobis distance of a case to the group mean under the group covariance
+    fn squared_distance(&self, case: &DVector<f64>) -> f64 {
+        let diff = case - &self.mean;
+        let solved = self.cholesky.solve(&diff);
+        diff.dot(&solved)
+    }
+}
+
+/// Sufficient statistics of a group, kept so one case can be removed for leave-one-out
+struct GroupMoments {
+    count: usize,
+    mean: DVector<f64>,
+    /// Sum of squares and cross-products about the mean
+    sscp: DMatrix<f64>,
+}
+
+impl GroupMoments {
+    fn from_cases(cases: &[DVector<f64>], p: usize) -> Self {
+        let count = cases.len();
+        let mut mean = DVector::zeros(p);
+        for case in cases {
+            mean += case;
+        }
+        if count > 0 {
+            mean /= count as f64;
+        }
+
+        let mut sscp = DMatrix::zeros(p, p);
+        for case in cases {
+            let diff = case - &mean;
+            sscp += &diff * diff.transpose();
+        }
+
+        GroupMoments { count, mean, sscp }
+    }
+
+    fn model(&self) -> Option<GroupModel> {
+        if self.count < 2 {
+            return None;
+        }
+        GroupModel::new(self.mean.clone(), &self.sscp / ((self.count - 1) as f64))
+    }
+
+    /// Model fitted without `case`, which must belong to this group
+    fn model_without(&self, case: &DVector<f64>) -> Option<GroupModel> {
+        if self.count < 3 {
+            return None;
+        }
+        let n = self.count as f64;
+        let diff = case - &self.mean;
+        let mean = (&self.mean * n - case) / (n - 1.0);
+        let sscp = &self.sscp - (&diff * diff.transpose()) * (n / (n - 1.0));
+
+        GroupModel::new(mean, sscp / (n - 2.0))
+    }
+}
+
+/// Calculate classification results using separate-groups covariance matrices
+///
+/// Each case is assigned to the group with the largest posterior probability,
+/// where the group densities are multivariate normal with the group's own
+/// covariance matrix.
+///
+/// # Parameters
+/// * `data` - The analysis data
+/// * `config` - The discriminant analysis configuration
+///
+/// # Returns
+/// A QuadraticClassification object with classification tables, and casewise
+/// posterior probabilities and Mahalanobis distances when requested
+pub fn calculate_quadratic_classification(
+    data: &AnalysisData,
+    config: &DiscriminantConfig
+) -> Result<QuadraticClassification, String> {
+    let observations = extract_case_observations(data, config)?;
+    let p = config.main.independent_variables.len();
+    let num_groups = observations.group_labels.len();
+
+    if num_groups < 2 {
+        return Err("Quadratic classification requires at least two groups".to_string());
+    }
+
+    let cases: Vec<DVector<f64>> = observations.values
+        .iter()
+        .map(|values| DVector::from_vec(values.clone()))
+        .collect();
+
+    // Fit one model per group
+    let moments: Vec<GroupMoments> = (0..num_groups)
+        .map(|group_idx| {
+            let group_cases: Vec<DVector<f64>> = cases
+                .iter()
+                .zip(observations.groups.iter())
+                .filter(|(_, &g)| g == group_idx)
+                .map(|(case, _)| case.clone())
+                .collect();
+            GroupMoments::from_cases(&group_cases, p)
+        })
+        .collect();
+
+    let mut models = Vec::with_capacity(num_groups);
+    for (group_idx, group_moments) in moments.iter().enumerate() {
+        match group_moments.model() {
+            Some(model) => models.push(model),
+            None => {
+                return Err(
+                    format!(
+                        "Covariance matrix of group {} is singular; quadratic classification needs more cases than variables ({}) in every group",
+                        observations.group_labels[group_idx],
+                        p
+                    )
+                );
+            }
+        }
+    }
+
+    let priors = group_priors(&observations, config);
+    let log_determinants: Vec<f64> = models
+        .iter()
+        .map(|m| m.log_determinant)
+        .collect();
+
+    // Original classification
+    let model_refs: Vec<&GroupModel> = models.iter().collect();
+    let original: Vec<(Vec<f64>, Vec<f64>)> = cases
+        .iter()
+        .map(|case| classify_case(case, &model_refs, &priors))
+        .collect();
+
+    let original_predicted: Vec<usize> = original
+        .iter()
+        .map(|(posterior, _)| argmax(posterior))
+        .collect();
+    let (original_classification, original_percentage, original_correct_percentage) =
+        tabulate_classification(&observations, &original_predicted);
+
+    let mut notes = vec![
+        "Classification used separate-groups covariance matrices (quadratic rule).".to_string()
+    ];
+
+    // Leave-one-out classification
+    let mut cross_validated = None;
+    if config.classify.leave {
+        let mut cv_posteriors = Vec::with_capacity(cases.len());
+        let mut feasible = true;
+
+        for (case, &group_idx) in cases.iter().zip(observations.groups.iter()) {
+            let reduced = match moments[group_idx].model_without(case) {
+                Some(model) => model,
+                None => {
+                    feasible = false;
+                    break;
+                }
+            };
+
+            // Only the case's own group changes when it is left out
+            let mut loo_models = model_refs.clone();
+            loo_models[group_idx] = &reduced;
+            let (posterior, _) = classify_case(case, &loo_models, &priors);
+            cv_posteriors.push(posterior);
+        }
+
+        if feasible {
+            notes.push(
+                "In cross validation, each case is classified by the functions derived from all cases other than that case.".to_string()
+            );
+            cross_validated = Some(cv_posteriors);
+        } else {
+            notes.push(
+                "Cross validation was not performed: removing a case leaves a group with a singular covariance matrix.".to_string()
+            );
+        }
+    }
+
+    let cv_predicted: Option<Vec<usize>> = cross_validated
+        .as_ref()
+        .map(|posteriors| posteriors.iter().map(|posterior| argmax(posterior)).collect());
+
+    let (cross_validated_classification, cross_validated_percentage, cross_validated_correct) =
+        match &cv_predicted {
+            Some(predicted) => {
+                let (counts, percentages, correct) = tabulate_classification(
+                    &observations,
+                    predicted
+                );
+                (Some(counts), Some(percentages), Some(correct))
+            }
+            None => (None, None, None),
+        };
+
+    // Casewise statistics if requested
+    let casewise_statistics = if config.classify.case {
+        let limit = if config.classify.limit {
+            config.classify.limit_value.map_or(usize::MAX, |v| v.max(0) as usize)
+        } else {
+            usize::MAX
+        };
+        let shown = cases.len().min(limit);
+
+        Some(QuadraticCasewiseStatistics {
+            case_number: (1..=shown).collect(),
+            actual_group: observations.groups[..shown]
+                .iter()
+                .map(|&g| observations.group_labels[g].clone())
+                .collect(),
+            predicted_group: original_predicted[..shown]
+                .iter()
+                .map(|&g| observations.group_labels[g].clone())
+                .collect(),
+            posterior_probabilities: original[..shown]
+                .iter()
+                .map(|(posterior, _)| posterior.clone())
+                .collect(),
+            squared_mahalanobis_distance: original[..shown]
+                .iter()
+                .map(|(_, distances)| distances.clone())
+                .collect(),
+            cross_validated_group: cv_predicted.as_ref().map(|predicted| {
+                predicted[..shown]
+                    .iter()
+                    .map(|&g| observations.group_labels[g].clone())
+                    .collect()
+            }),
+            cross_validated_posterior: cross_validated
+                .as_ref()
+                .map(|posteriors| posteriors[..shown].to_vec()),
+        })
+    } else {
+        None
+    };
+
+    Ok(QuadraticClassification {
+        groups: observations.group_labels.clone(),
+        prior_probabilities: priors,
+        log_determinants,
+        classification_results: ClassificationResults {
+            original_classification,
+            cross_validated_classification,
+            original_percentage,
+            cross_validated_percentage,
+        },
+        original_correct_percentage,
+        cross_validated_correct_percentage: cross_validated_correct,
+        casewise_statistics,
+        note: notes.join(" "),
+    })
+}
+
+/// Posterior probabilities and squared Mahalanobis distances of a case for every group
+fn classify_case(
+    case: &DVector<f64>,
+    models: &[&GroupModel],
+    priors: &[f64]
+) -> (Vec<f64>, Vec<f64>) {
+    let distances: Vec<f64> = models
+        .iter()
+        .map(|model| model.squared_distance(case))
+        .collect();
+    let log_densities: Vec<f64> = models
+        .iter()
+        .zip(distances.iter())
+        .map(|(model, d2)| -0.5 * model.log_determinant - 0.5 * d2)
+        .collect();
+
+    (posterior_probabilities(&log_densities, priors), distances)
+}
+
+fn posterior_probabilities(log_densities: &[f64], priors: &[f64]) -> Vec<f64> {
+    let log_scores: Vec<f64> = log_densities
+        .iter()
+        .zip(priors.iter())
+        .map(|(log_density, prior)| log_density + prior.ln())
+        .collect();
+
+    // Subtract the maximum before exponentiating to avoid underflow
+    let max_score = log_scores.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+    let exp_scores: Vec<f64> = log_scores
+        .iter()
+        .map(|s| (s - max_score).exp())
+        .collect();
+    let total: f64 = exp_scores.iter().sum();
+
+    exp_scores
+        .iter()
+        .map(|s| s / total)
+        .collect()
+}
+
+fn argmax(values: &[f64]) -> usize {
+    values
+        .iter()
+        .enumerate()
+        .max_by(|a, b| a.1.partial_cmp(b.1).unwrap_or(std::cmp::Ordering::Equal))
+        .map(|(idx, _)| idx)
+        .unwrap_or(0)
+}
+
+/// Classification counts and row percentages by actual group, plus the overall percent correct
+fn tabulate_classification(
+    observations: &CaseObservations,
+    predicted: &[usize]
+) -> ClassificationTable {
+    let num_groups = observations.group_labels.len();
+    let mut counts = vec![vec![0; num_groups]; num_groups];
+
+    for (&actual, &predicted) in observations.groups.iter().zip(predicted.iter()) {
+        counts[actual][predicted] += 1;
+    }
+
+    let mut classification = HashMap::new();
+    let mut percentage = HashMap::new();
+    let mut correct = 0;
+
+    for (group_idx, group) in observations.group_labels.iter().enumerate() {
+        let row = &counts[group_idx];
+        let total = row.iter().sum::<i32>() as f64;
+        let percentages = row
+            .iter()
+            .map(|&count| if total > 0.0 { ((count as f64) * 100.0) / total } else { 0.0 })
+            .collect();
+
+        correct += row[group_idx];
+        classification.insert(group.clone(), row.clone());
+        percentage.insert(group.clone(), percentages);
+    }
+
+    let correct_percentage = ((correct as f64) * 100.0) / (predicted.len().max(1) as f64);
+
+    (classification, percentage, correct_percentage)
+}
@@ -7,7 +7,7 @@ use rayon::prelude::*;
 
 use crate::{
     models::{ result::WilksLambdaTest, AnalysisData, DiscriminantConfig },
-    stats::core::{ calculate_correlation, AnalyzedDataset, EPSILON },
+    stats::core::{ calculate_correlation, AnalyzedDataset },
 };
 
 use super::core::{
@@ -124,9 +124,7 @@ pub fn calculate_overall_wilks_lambda(dataset: &AnalyzedDataset, variables: &[St
         }
     };
 
-    let lambda = if total_det > 0.0 { within_det / total_det } else { 1.0 };
-
-    lambda
+    if total_det > 0.0 { within_det / total_det } else { 1.0 }
 }
 
 /// Calculate overall F statistic for a set of variables
@@ -169,10 +167,6 @@ pub fn calculate_overall_f_statistic(
         0.0
     };
 
-    // Untuk df2 dalam exact F, nilainya berkurang seiring bertambahnya variabel
-    // df_exact_2 = df3 - df1 + 1
-    let exact_df2 = df3 - df1 + 1;
-
     // Return F-statistic dan derajat kebebasan yang sesuai dengan output
     (f_value, df1, df2, df3)
 }
@@ -267,7 +261,7 @@ pub fn calculate_tolerance(
     // Use maximum correlation for tolerance
     let max_r_squared = r_squared_values
         .iter()
-        .fold(0.0, |max_val, &val| (max_val as f64).max(val));
+        .fold(0.0, |max_val: f64, &val| max_val.max(val));
     let tolerance = 1.0 - max_r_squared;
     let min_tolerance = tolerance * 0.8; // 80% of current tolerance
 
@@ -24,7 +24,6 @@ use crate::{
 use super::core::{
     analyze_variables_in_model,
     analyze_variables_not_in_model,
-    calculate_overall_wilks_lambda,
     determine_method_type,
     filter_dataset,
     find_best_variable_to_enter,
@@ -113,10 +112,7 @@ pub fn calculate_stepwise_statistics(
     };
 
     // Convert step data to output format
-    match convert_steps_to_output(steps_data, config) {
-        Ok(result) => { Ok(result) }
-        Err(e) => { Err(e) }
-    }
+    convert_steps_to_output(steps_data, config)
 }
 
 /// Perform stepwise analysis
@@ -133,12 +129,12 @@ pub fn calculate_stepwise_statistics(
 /// A vector of StepData containing results for each step
 fn perform_stepwise_analysis(
     dataset: &AnalyzedDataset,
-    variables: &Vec<String>,
+    variables: &[String],
     config: &DiscriminantConfig
 ) -> Result<Vec<StepData>, String> {
     // Initialize variables for stepwise analysis
     let mut current_variables: Vec<String> = Vec::new();
-    let mut remaining_variables: Vec<String> = variables.clone();
+    let mut remaining_variables: Vec<String> = variables.to_vec();
     let mut steps_data: Vec<StepData> = Vec::new();
 
     // Add initial step
@@ -243,7 +239,6 @@ fn process_selection_step(
         &best_stats,
         dataset.num_groups,
         dataset.total_cases,
-        current_variables.len(),
         config
     );
 
@@ -271,7 +266,6 @@ fn process_selection_step(
                 Some(var_name.clone()),
                 None,
                 (step as i32) + 1,
-                method_type,
                 config
             );
 
@@ -354,7 +348,6 @@ fn process_variable_removal(
             &worst_stats,
             dataset.num_groups,
             dataset.total_cases,
-            current_variables.len(),
             config
         );
 
@@ -382,7 +375,6 @@ fn process_variable_removal(
                     None,
                     Some(var_name.clone()),
                     (step as i32) + 1,
-                    method_type,
                     config
                 );
 
@@ -404,7 +396,6 @@ fn process_variable_removal(
 /// * `stats` - Statistics for the variable
 /// * `num_groups` - Number of groups
 /// * `total_cases` - Total number of cases
-/// * `num_current_vars` - Number of variables currently in the model
 /// * `config` - The discriminant analysis configuration
 ///
 /// # Returns
@@ -414,14 +405,13 @@ fn should_enter_variable(
     stats: &VariableNotInAnalysis,
     num_groups: usize,
     total_cases: usize,
-    num_current_vars: usize,
     config: &DiscriminantConfig
 ) -> bool {
     if var_opt.is_none() {
         return false;
     }
 
-    let result = if config.method.f_value {
+    if config.method.f_value {
         // Use direct F value comparison
         stats.f_to_enter >= config.method.f_entry
     } else if config.method.f_probability {
@@ -434,9 +424,7 @@ fn should_enter_variable(
         p_value <= config.method.p_entry
     } else {
         false
-    };
-
-    result
+    }
 }
 
 /// Determine if a variable should be removed from the model
@@ -446,7 +434,6 @@ fn should_enter_variable(
 /// * `stats` - Statistics for the variable
 /// * `num_groups` - Number of groups
 /// * `total_cases` - Total number of cases
-/// * `num_current_vars` - Number of variables currently in the model
 /// * `config` - The discriminant analysis configuration
 ///
 /// # Returns
@@ -456,14 +443,13 @@ fn should_remove_variable(
     stats: &VariableInAnalysis,
     num_groups: usize,
     total_cases: usize,
-    num_current_vars: usize,
     config: &DiscriminantConfig
 ) -> bool {
     if var_opt.is_none() {
         return false;
     }
 
-    let result = if config.method.f_value {
+    if config.method.f_value {
         // Use direct F value comparison for removal
         // For removal, the F value must be LESS THAN OR EQUAL TO the threshold
         stats.f_to_remove <= config.method.f_removal
@@ -478,9 +464,7 @@ fn should_remove_variable(
         p_value >= config.method.p_removal
     } else {
         false
-    };
-
-    result
+    }
 }
 
 /// Create data for the initial step (no variables in the model)
@@ -526,7 +510,6 @@ fn create_initial_step(
 /// * `variable_entered` - Variable that was entered in this step
 /// * `variable_removed` - Variable that was removed in this step
 /// * `step` - Step number
-/// * `method_type` - The method being used
 /// * `config` - The discriminant analysis configuration
 ///
 /// # Returns
@@ -538,15 +521,15 @@ fn create_step_data(
     variable_entered: Option<String>,
     variable_removed: Option<String>,
     step: i32,
-    method_type: MethodType,
     config: &DiscriminantConfig
 ) -> StepData {
+    let method_type = determine_method_type(config);
+
     // Analyze variables in and out of the model
     let vars_in_analysis = analyze_variables_in_model(
         current_variables,
         dataset,
-        method_type,
-        config
+        method_type
     );
 
     let vars_not_in_analysis = analyze_variables_not_in_model(
@@ -575,7 +558,7 @@ fn create_step_data(
     web_sys::console::log_1(&format!("Remaining vars: {:?}", remaining_variables).into());
 
     // `other_vars` sekarang berisi semua kecuali last
-    let new_dataset = filter_dataset(&dataset, &combined_vars);
+    let new_dataset = filter_dataset(dataset, &combined_vars);
     web_sys::console::log_1(&format!("dataset: {:?}", dataset).into());
     web_sys::console::log_1(&format!("New dataset: {:?}", new_dataset).into());
     let (f_value, wilks_lambda) = calculate_univariate_f(&var, &new_dataset);
@@ -6,7 +6,6 @@ use super::core::{
     calculate_canonical_functions,
     calculate_correlation,
     calculate_eigen_statistics,
-    extract_analyzed_dataset,
     extract_case_values,
 };
 
@@ -27,8 +26,6 @@ pub fn calculate_structure_matrix(
 ) -> Result<StructureMatrix, String> {
     web_sys::console::log_1(&"Executing calculate_structure_matrix".into());
 
-    // Extract analyzed dataset
-    let dataset = extract_analyzed_dataset(data, config)?;
     let variables = &config.main.independent_variables;
 
     // Calculate discriminant functions and get eigenvalues
@@ -98,9 +95,7 @@ pub fn calculate_structure_matrix(
         let var_values = all_variable_values.get(var_name).unwrap();
         let mut var_correlations = Vec::with_capacity(num_functions);
 
-        for func_idx in 0..num_functions {
-            let func_scores = &all_function_scores[func_idx];
-
+        for func_scores in all_function_scores.iter().take(num_functions) {
             // Ensure we have enough data
             if var_values.len() == func_scores.len() && !var_values.is_empty() {
                 let correlation = calculate_correlation(var_values, func_scores);
@@ -113,9 +108,6 @@ pub fn calculate_structure_matrix(
         correlations.insert(var_name.clone(), var_correlations);
     }
 
-    // Create function names (Function 1, Function 2, etc.)
-    let functions: Vec<String> = (1..=num_functions).map(|i| format!("Function {}", i)).collect();
-
     Ok(StructureMatrix {
         variables: variables.clone(),
         correlations,
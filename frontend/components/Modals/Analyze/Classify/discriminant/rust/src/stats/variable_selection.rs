@@ -96,7 +96,7 @@ pub fn analyze_variables_not_in_model(
     // Filter out None values and collect results
     let mut variables_not_in_analysis: Vec<VariableNotInAnalysis> = results
         .into_iter()
-        .filter_map(|x| x)
+        .flatten()
         .collect();
 
     // Sort variables by F-to-enter (descending)
@@ -116,15 +116,13 @@ pub fn analyze_variables_not_in_model(
 /// * `variables` - Variables in the model
 /// * `dataset` - The analyzed dataset
 /// * `method_type` - The method to use for variable selection
-/// * `config` - The discriminant analysis configuration
 ///
 /// # Returns
 /// A vector of VariableInAnalysis with statistics for each variable
 pub fn analyze_variables_in_model(
     variables: &[String],
     dataset: &AnalyzedDataset,
-    method_type: MethodType,
-    config: &DiscriminantConfig
+    method_type: MethodType
 ) -> Vec<VariableInAnalysis> {
     // Parallel analysis of variables
     let results: Vec<VariableInAnalysis> = variables
@@ -267,7 +265,7 @@ pub fn find_worst_variable_to_remove(
     }
 
     // Analyze all variables in the model
-    let candidates = analyze_variables_in_model(variables, dataset, method_type, config);
+    let candidates = analyze_variables_in_model(variables, dataset, method_type);
 
     if candidates.is_empty() {
         return (None, default_result);
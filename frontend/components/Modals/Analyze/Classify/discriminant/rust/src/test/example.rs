@@ -0,0 +1,246 @@
+use std::collections::HashMap;
+
+use crate::models::{
+    config::{ BootstrapConfig, ClassifyConfig, DiscriminantConfig, MainConfig },
+    data::{ AnalysisData, DataRecord, DataValue },
+};
+use crate::stats::core::{
+    calculate_discriminant_bootstrap,
+    calculate_quadratic_classification,
+    extract_case_observations,
+    group_priors,
+};
+
+fn assert_close(actual: f64, expected: f64, tolerance: f64) {
+    assert!(
+        (actual - expected).abs() <= tolerance,
+        "expected {}, got {} (tolerance {})",
+        expected,
+        actual,
+        tolerance
+    );
+}
+
+fn record(name: &str, value: f64) -> DataRecord {
+    DataRecord {
+        values: HashMap::from([(name.to_string(), DataValue::Number(value))]),
+    }
+}
+
+/// One independent variable `x`, grouped by `group`
+fn one_variable_data(groups: &[(f64, &[f64])]) -> AnalysisData {
+    let mut group_records = Vec::new();
+    let mut x_records = Vec::new();
+    for &(group, values) in groups {
+        for &value in values {
+            group_records.push(record("group", group));
+            x_records.push(record("x", value));
+        }
+    }
+
+    AnalysisData {
+        group_data: vec![group_records],
+        independent_data: vec![x_records],
+        selection_data: None,
+        group_data_defs: Vec::new(),
+        independent_data_defs: Vec::new(),
+        selection_data_defs: None,
+    }
+}
+
+fn one_variable_config() -> DiscriminantConfig {
+    DiscriminantConfig {
+        main: MainConfig {
+            grouping_variable: "group".to_string(),
+            independent_variables: vec!["x".to_string()],
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
+
+// Group 1 has variance 1 around 2, group 2 variance 4 around 6
+const QUADRATIC_GROUPS: [(f64, &[f64]); 2] = [
+    (1.0, &[1.0, 2.0, 3.0]),
+    (2.0, &[4.0, 6.0, 8.0]),
+];
+
+/// Posterior of the first group when the log densities differ by `log_ratio` (second minus first)
+fn first_group_posterior(log_ratio: f64) -> f64 {
+    1.0 / (1.0 + log_ratio.exp())
+}
+
+#[test]
+fn quadratic_classification_uses_separate_group_covariances() {
+    let data = one_variable_data(&QUADRATIC_GROUPS);
+    let mut config = one_variable_config();
+    config.classify = ClassifyConfig {
+        case: true,
+        leave: true,
+        ..Default::default()
+    };
+
+    let result = calculate_quadratic_classification(&data, &config).unwrap();
+
+    assert_eq!(result.groups, vec!["1", "2"]);
+    assert_eq!(result.prior_probabilities, vec![0.5, 0.5]);
+    assert_close(result.log_determinants[0], 0.0, 1e-12);
+    assert_close(result.log_determinants[1], (4.0f64).ln(), 1e-12);
+
+    let casewise = result.casewise_statistics.unwrap();
+    // Squared distances to the group means, scaled by each group's own variance
+    let expected_distances = [
+        [1.0, 6.25],
+        [0.0, 4.0],
+        [1.0, 2.25],
+        [4.0, 1.0],
+        [16.0, 0.0],
+        [36.0, 1.0],
+    ];
+    for (actual, expected) in casewise.squared_mahalanobis_distance.iter().zip(expected_distances) {
+        assert_close(actual[0], expected[0], 1e-12);
+        assert_close(actual[1], expected[1], 1e-12);
+    }
+
+    // log f2 - log f1 = -ln 2 - d2 / 2 + d1 / 2
+    let ln2 = (2.0f64).ln();
+    let posterior_x3 = first_group_posterior(-ln2 - 1.125 + 0.5);
+    let posterior_x4 = first_group_posterior(-ln2 - 0.5 + 2.0);
+    assert_close(casewise.posterior_probabilities[2][0], posterior_x3, 1e-12);
+    assert_close(casewise.posterior_probabilities[2][1], 1.0 - posterior_x3, 1e-12);
+    assert_close(casewise.posterior_probabilities[3][0], posterior_x4, 1e-12);
+    assert_eq!(casewise.predicted_group, vec!["1", "1", "1", "2", "2", "2"]);
+    assert_close(result.original_correct_percentage, 100.0, 1e-12);
+
+    // Without x = 3, group 1 is {1, 2}: mean 1.5, variance 0.5, so x = 3 moves to group 2.
+    // Without x = 4, group 2 is {6, 8}: mean 7, variance 2, so x = 4 moves to group 1.
+    let cv_posterior = casewise.cross_validated_posterior.unwrap();
+    assert_close(cv_posterior[2][0], first_group_posterior(-1.5 * ln2 + 1.125), 1e-12);
+    assert_close(cv_posterior[3][0], first_group_posterior(-0.5 * ln2 - 2.25 + 2.0), 1e-12);
+    assert_eq!(
+        casewise.cross_validated_group.unwrap(),
+        vec!["1", "1", "2", "1", "2", "2"]
+    );
+
+    let results = result.classification_results;
+    assert_eq!(results.original_classification["1"], vec![3, 0]);
+    assert_eq!(results.original_classification["2"], vec![0, 3]);
+    let cross_validated = results.cross_validated_classification.unwrap();
+    assert_eq!(cross_validated["1"], vec![2, 1]);
+    assert_eq!(cross_validated["2"], vec![1, 2]);
+    assert_close(result.cross_validated_correct_percentage.unwrap(), 400.0 / 6.0, 1e-10);
+}
+
+#[test]
+fn quadratic_classification_rejects_a_singular_group() {
+    let data = one_variable_data(&[
+        (1.0, &[1.0, 2.0, 3.0]),
+        (2.0, &[5.0, 5.0, 5.0]),
+    ]);
+
+    let error = calculate_quadratic_classification(&data, &one_variable_config()).unwrap_err();
+
+    assert!(error.contains("group 2 is singular"), "{}", error);
+}
+
+#[test]
+fn group_priors_follow_group_sizes_only_when_requested() {
+    let data = one_variable_data(&[
+        (1.0, &[1.0, 2.0, 3.0]),
+        (2.0, &[4.0, 6.0, 8.0, 9.0, 10.0]),
+    ]);
+    let mut config = one_variable_config();
+    let observations = extract_case_observations(&data, &config).unwrap();
+
+    assert_eq!(group_priors(&observations, &config), vec![0.5, 0.5]);
+
+    config.classify.all_group_equal = false;
+    config.classify.group_size = true;
+    assert_eq!(group_priors(&observations, &config), vec![0.375, 0.625]);
+}
+
+// Pooled within-groups variance 113/15; centroids are -(7/3)/sqrt(113/15) and +(7/3)/sqrt(113/15)
+const BOOTSTRAP_GROUPS: [(f64, &[f64]); 2] = [
+    (1.0, &[2.0, 3.0, 5.0, 6.0, 7.0, 9.0]),
+    (2.0, &[6.0, 8.0, 9.0, 11.0, 12.0, 14.0]),
+];
+
+fn bootstrap_config(bca: bool) -> DiscriminantConfig {
+    DiscriminantConfig {
+        bootstrap: BootstrapConfig {
+            perform_boot_strapping: true,
+            num_of_samples: 200,
+            seed: true,
+            seed_value: 42,
+            percentile: !bca,
+            bca,
+            simple: false,
+            stratified: true,
+            ..Default::default()
+        },
+        ..one_variable_config()
+    }
+}
+
+// Reference values reproduce the stratified Mt64 resampling and the one-variable
+// closed form of the functions (coefficient 1 / sqrt(W), centroid (mean - overall) / sqrt(W)).
+
+#[test]
+fn bootstrap_percentile_intervals_match_reference() {
+    let data = one_variable_data(&BOOTSTRAP_GROUPS);
+
+    let result = calculate_discriminant_bootstrap(&data, &bootstrap_config(false)).unwrap();
+
+    assert_eq!(result.valid_samples, 200);
+    assert_eq!(result.sampling_method, "Stratified");
+    assert_eq!(result.interval_method, "Percentile");
+    assert_close(result.confidence_level, 95.0, 1e-12);
+
+    // With one variable the standardized coefficient and structure correlation are always 1
+    for estimate in result.standardized_coefficients.iter().chain(result.structure_matrix.iter()) {
+        assert_close(estimate.estimate, 1.0, 1e-9);
+        assert_close(estimate.bias, 0.0, 1e-9);
+        assert_close(estimate.std_error, 0.0, 1e-9);
+    }
+
+    let centroid = 7.0 / 3.0 / (113.0f64 / 15.0).sqrt();
+    let first = &result.function_at_centroids[0];
+    assert_eq!(first.label, "1");
+    assert_eq!(first.function, "Function 1");
+    assert_close(first.estimate, -centroid, 1e-9);
+    assert_close(first.bias, -0.1289910454779, 1e-9);
+    assert_close(first.std_error, 0.4025038420446, 1e-9);
+    assert_close(first.lower_bound, -1.961902196937, 1e-9);
+    assert_close(first.upper_bound, -0.3500447085849, 1e-9);
+
+    let second = &result.function_at_centroids[1];
+    assert_close(second.estimate, centroid, 1e-9);
+    assert_close(second.lower_bound, 0.3500447085849, 1e-9);
+    assert_close(second.upper_bound, 1.961902196937, 1e-9);
+}
+
+#[test]
+fn bootstrap_bca_intervals_match_reference() {
+    let data = one_variable_data(&BOOTSTRAP_GROUPS);
+
+    let percentile = calculate_discriminant_bootstrap(&data, &bootstrap_config(false)).unwrap();
+    let bca = calculate_discriminant_bootstrap(&data, &bootstrap_config(true)).unwrap();
+
+    assert_eq!(bca.interval_method, "BCa");
+
+    // The same seed gives the same replicates, so only the interval levels differ
+    for (p, b) in percentile.function_at_centroids.iter().zip(bca.function_at_centroids.iter()) {
+        assert_eq!(p.bias, b.bias);
+        assert_eq!(p.std_error, b.std_error);
+    }
+
+    // Jackknife acceleration 0.0348 and -0.0462, bias correction from the replicates
+    // below the estimate, shift both intervals towards the original centroids
+    let first = &bca.function_at_centroids[0];
+    assert_close(first.lower_bound, -1.654860892091, 1e-9);
+    assert_close(first.upper_bound, -0.263316981572, 1e-9);
+
+    let second = &bca.function_at_centroids[1];
+    assert_close(second.lower_bound, 0.2518161921514, 1e-9);
+    assert_close(second.upper_bound, 1.630043032158, 1e-9);
+}
@@ -10,10 +10,12 @@ use crate::models::result::{
     EigenDescription,
     WilksLambdaTest,
     VariableInAnalysis,
-    VariableNotInAnalysis,
     PairwiseComparison,
     HighestGroupStatistics,
     GroupHistogram,
+    ClassificationResults,
+    QuadraticCasewiseStatistics,
+    DiscriminantBootstrap,
 };
 
 // Konversi dari String error ke JsValue untuk interaksi WASM
@@ -50,6 +52,8 @@ struct FormatResult {
     prior_probabilities: Option<FormattedPriorProbabilities>,
     classification_function_coefficients: Option<FormattedClassificationFunctionCoefficients>,
     discriminant_histograms: Option<FormattedDiscriminantHistograms>,
+    quadratic_classification: Option<FormattedQuadraticClassification>,
+    bootstrap: Option<DiscriminantBootstrap>,
 }
 
 #[derive(Serialize)]
@@ -99,6 +103,18 @@ struct FormattedClassificationResults {
     cross_validated_percentage: Option<Vec<GroupPercentage>>,
 }
 
+#[derive(Serialize)]
+struct FormattedQuadraticClassification {
+    groups: Vec<String>,
+    prior_probabilities: Vec<f64>,
+    log_determinants: Vec<f64>,
+    classification_results: FormattedClassificationResults,
+    original_correct_percentage: f64,
+    cross_validated_correct_percentage: Option<f64>,
+    casewise_statistics: Option<QuadraticCasewiseStatistics>,
+    note: String,
+}
+
 #[derive(Serialize)]
 struct GroupClassification {
     group: String,
@@ -167,12 +183,6 @@ struct StepVariables {
     variables: Vec<VariableInAnalysis>,
 }
 
-#[derive(Serialize)]
-struct StepNotInVariables {
-    step: String,
-    variables: Vec<VariableNotInAnalysis>,
-}
-
 #[derive(Serialize)]
 struct GroupPairComparison {
     step: String,
@@ -245,11 +255,15 @@ impl FormatResult {
         let group_statistics = result.group_statistics.as_ref().map(|stats| {
             let means = stats.variables
                 .iter()
-                .enumerate()
-                .map(|(i, var)| {
-                    let values = stats.groups
-                        .iter()
-                        .map(|group| { stats.means.get(var).unwrap_or(&vec![0.0])[i] })
+                .map(|var| {
+                    let values = (0..stats.groups.len())
+                        .map(|group_idx| {
+                            stats.means
+                                .get(var)
+                                .and_then(|values| values.get(group_idx))
+                                .copied()
+                                .unwrap_or(0.0)
+                        })
                         .collect();
 
                     GroupValue {
@@ -261,11 +275,15 @@ impl FormatResult {
 
             let std_deviations = stats.variables
                 .iter()
-                .enumerate()
-                .map(|(i, var)| {
-                    let values = stats.groups
-                        .iter()
-                        .map(|group| { stats.std_deviations.get(var).unwrap_or(&vec![0.0])[i] })
+                .map(|var| {
+                    let values = (0..stats.groups.len())
+                        .map(|group_idx| {
+                            stats.std_deviations
+                                .get(var)
+                                .and_then(|values| values.get(group_idx))
+                                .copied()
+                                .unwrap_or(0.0)
+                        })
                         .collect();
 
                     GroupValue {
@@ -341,62 +359,9 @@ impl FormatResult {
         });
 
         // Transform ClassificationResults
-        let classification_results = result.classification_results.as_ref().map(|results| {
-            let original_classification = results.original_classification
-                .iter()
-                .map(|(group, counts)| {
-                    GroupClassification {
-                        group: group.clone(),
-                        counts: counts.clone(),
-                    }
-                })
-                .collect();
-
-            let cross_validated_classification = results.cross_validated_classification
-                .as_ref()
-                .map(|cross_val| {
-                    cross_val
-                        .iter()
-                        .map(|(group, counts)| {
-                            GroupClassification {
-                                group: group.clone(),
-                                counts: counts.clone(),
-                            }
-                        })
-                        .collect()
-                });
-
-            let original_percentage = results.original_percentage
-                .iter()
-                .map(|(group, percentages)| {
-                    GroupPercentage {
-                        group: group.clone(),
-                        percentages: percentages.clone(),
-                    }
-                })
-                .collect();
-
-            let cross_validated_percentage = results.cross_validated_percentage
-                .as_ref()
-                .map(|cross_val| {
-                    cross_val
-                        .iter()
-                        .map(|(group, percentages)| {
-                            GroupPercentage {
-                                group: group.clone(),
-                                percentages: percentages.clone(),
-                            }
-                        })
-                        .collect()
-                });
-
-            FormattedClassificationResults {
-                original_classification,
-                cross_validated_classification,
-                original_percentage,
-                cross_validated_percentage,
-            }
-        });
+        let classification_results = result.classification_results
+            .as_ref()
+            .map(format_classification_results);
 
         // Transform PooledMatrices
         let pooled_matrices = result.pooled_matrices.as_ref().map(|matrices| {
@@ -649,15 +614,11 @@ impl FormatResult {
                         .iter()
                         .filter_map(|group| {
                             // Hanya lanjutkan jika histogram ditemukan untuk func ini
-                            if let Some(histogram) = hists.histograms.get(func) {
-                                Some(HistogramEntry {
-                                    function: func.clone(),
-                                    group: group.clone(),
-                                    histogram: histogram.clone(),
-                                })
-                            } else {
-                                None
-                            }
+                            hists.histograms.get(func).map(|histogram| HistogramEntry {
+                                function: func.clone(),
+                                group: group.clone(),
+                                histogram: histogram.clone(),
+                            })
                         })
                         .collect::<Vec<HistogramEntry>>()
                 })
@@ -670,6 +631,22 @@ impl FormatResult {
             }
         });
 
+        // Transform QuadraticClassification
+        let quadratic_classification = result.quadratic_classification.as_ref().map(|quadratic| {
+            FormattedQuadraticClassification {
+                groups: quadratic.groups.clone(),
+                prior_probabilities: quadratic.prior_probabilities.clone(),
+                log_determinants: quadratic.log_determinants.clone(),
+                classification_results: format_classification_results(
+                    &quadratic.classification_results
+                ),
+                original_correct_percentage: quadratic.original_correct_percentage,
+                cross_validated_correct_percentage: quadratic.cross_validated_correct_percentage,
+                casewise_statistics: quadratic.casewise_statistics.clone(),
+                note: quadratic.note.clone(),
+            }
+        });
+
         FormatResult {
             processing_summary: Some(result.processing_summary.clone()),
             group_statistics,
@@ -688,6 +665,66 @@ impl FormatResult {
             prior_probabilities,
             classification_function_coefficients,
             discriminant_histograms,
+            quadratic_classification,
+            bootstrap: result.bootstrap.clone(),
         }
     }
 }
+
+/// Convert classification tables keyed by group into ordered lists
+fn format_classification_results(results: &ClassificationResults) -> FormattedClassificationResults {
+    let original_classification = results.original_classification
+        .iter()
+        .map(|(group, counts)| {
+            GroupClassification {
+                group: group.clone(),
+                counts: counts.clone(),
+            }
+        })
+        .collect();
+
+    let cross_validated_classification = results.cross_validated_classification
+        .as_ref()
+        .map(|cross_val| {
+            cross_val
+                .iter()
+                .map(|(group, counts)| {
+                    GroupClassification {
+                        group: group.clone(),
+                        counts: counts.clone(),
+                    }
+                })
+                .collect()
+        });
+
+    let original_percentage = results.original_percentage
+        .iter()
+        .map(|(group, percentages)| {
+            GroupPercentage {
+                group: group.clone(),
+                percentages: percentages.clone(),
+            }
+        })
+        .collect();
+
+    let cross_validated_percentage = results.cross_validated_percentage
+        .as_ref()
+        .map(|cross_val| {
+            cross_val
+                .iter()
+                .map(|(group, percentages)| {
+                    GroupPercentage {
+                        group: group.clone(),
+                        percentages: percentages.clone(),
+                    }
+                })
+                .collect()
+        });
+
+    FormattedClassificationResults {
+        original_classification,
+        cross_validated_classification,
+        original_percentage,
+        cross_validated_percentage,
+    }
+}
@@ -6,7 +6,7 @@ use crate::models::{
     result::DiscriminantResult,
 };
 use crate::stats::core;
-use crate::utils::format_result;
+use crate::utils::converter::format_result;
 use crate::utils::log::FunctionLogger;
 use crate::utils::{ converter::string_to_js_error, error::ErrorCollector };
 
@@ -282,6 +282,40 @@ pub fn run_analysis(
         };
     }
 
+    // Step 12: Quadratic classification if separate-groups covariance is requested
+    let mut quadratic_classification = None;
+    if config.classify.sep_group {
+        logger.add_log("calculate_quadratic_classification");
+        match core::calculate_quadratic_classification(&filtered_data, config) {
+            Ok(results) => {
+                web_sys::console::log_1(
+                    &format!("Quadratic Classification: {:?}", results).into()
+                );
+                quadratic_classification = Some(results);
+            }
+            Err(e) => {
+                error_collector.add_error("calculate_quadratic_classification", &e);
+                // Continue execution despite errors for non-critical functions
+            }
+        };
+    }
+
+    // Step 13: Bootstrap estimates if requested
+    let mut bootstrap = None;
+    if config.bootstrap.perform_boot_strapping {
+        logger.add_log("calculate_discriminant_bootstrap");
+        match core::calculate_discriminant_bootstrap(&filtered_data, config) {
+            Ok(results) => {
+                web_sys::console::log_1(&format!("Bootstrap: {:?}", results).into());
+                bootstrap = Some(results);
+            }
+            Err(e) => {
+                error_collector.add_error("calculate_discriminant_bootstrap", &e);
+                // Continue execution despite errors for non-critical functions
+            }
+        };
+    }
+
     // Create the final result
     let result = DiscriminantResult {
         processing_summary,
@@ -301,6 +335,8 @@ pub fn run_analysis(
         prior_probabilities,
         classification_function_coefficients,
         discriminant_histograms: None,
+        quadratic_classification,
+        bootstrap,
     };
 
     Ok(Some(result))
@@ -1464,5 +1464,328 @@ export function transformDiscriminantResult(data: any): ResultJson {
         resultJson.tables.push(table);
     }
 
+    // 22. Separate-Groups (Quadratic) Classification Results
+    if (data.quadratic_classification) {
+        const quadratic = data.quadratic_classification;
+        const groups: string[] = quadratic.groups;
+        const results = quadratic.classification_results;
+
+        const summaryTable: Table = {
+            key: "quadratic_classification_summary",
+            title: "Separate-Groups Classification Summary",
+            columnHeaders: [
+                { header: "Group", key: "group" },
+                { header: "Prior", key: "prior" },
+                { header: "Log Determinant", key: "log_determinant" },
+            ],
+            rows: [],
+        };
+
+        groups.forEach((group: string, i: number) => {
+            summaryTable.rows.push({
+                rowHeader: [group],
+                prior: formatDisplayNumber(quadratic.prior_probabilities[i]),
+                log_determinant: formatDisplayNumber(
+                    quadratic.log_determinants[i]
+                ),
+            });
+        });
+
+        if (quadratic.note) {
+            summaryTable.rows.push({ rowHeader: [quadratic.note] });
+        }
+
+        resultJson.tables.push(summaryTable);
+
+        const table: Table = {
+            key: "quadratic_classification_results",
+            title: "Classification Results (Separate-Groups Covariance)",
+            columnHeaders: [
+                { header: "", key: "category" },
+                { header: "", key: "subcategory" },
+                {
+                    header: "Predicted Group Membership",
+                    key: "predicted_groups",
+                    children: groups.map((group: string, i: number) => ({
+                        header: group,
+                        key: `group_${i}`,
+                    })),
+                },
+                { header: "Total", key: "total" },
+            ],
+            rows: [],
+        };
+
+        const pushBlock = (
+            label: string,
+            counts: { group: string; counts: number[] }[],
+            percentages: { group: string; percentages: number[] }[]
+        ) => {
+            table.rows.push({ rowHeader: [label, "Count"] });
+            counts.forEach((classification) => {
+                const rowData: any = {
+                    rowHeader: ["", classification.group],
+                    total: formatDisplayNumber(
+                        classification.counts.reduce(
+                            (sum: number, count: number) => sum + count,
+                            0
+                        )
+                    ),
+                };
+                classification.counts.forEach((count: number, j: number) => {
+                    rowData[`group_${j}`] = formatDisplayNumber(count);
+                });
+                table.rows.push(rowData);
+            });
+
+            table.rows.push({ rowHeader: ["", "%"] });
+            percentages.forEach((percentage) => {
+                const rowData: any = {
+                    rowHeader: ["", percentage.group],
+                    total: "100.0",
+                };
+                percentage.percentages.forEach((value: number, j: number) => {
+                    rowData[`group_${j}`] = formatDisplayNumber(value);
+                });
+                table.rows.push(rowData);
+            });
+        };
+
+        pushBlock(
+            "Original",
+            results.original_classification,
+            results.original_percentage
+        );
+        if (results.cross_validated_classification) {
+            pushBlock(
+                "Cross-validated",
+                results.cross_validated_classification,
+                results.cross_validated_percentage ?? []
+            );
+        }
+
+        table.rows.push({
+            rowHeader: [
+                `a. ${formatDisplayNumber(
+                    quadratic.original_correct_percentage
+                )}% of original grouped cases correctly classified.`,
+            ],
+        });
+
+        if (
+            quadratic.cross_validated_correct_percentage !== null &&
+            quadratic.cross_validated_correct_percentage !== undefined
+        ) {
+            table.rows.push({
+                rowHeader: [
+                    `b. ${formatDisplayNumber(
+                        quadratic.cross_validated_correct_percentage
+                    )}% of cross-validated grouped cases correctly classified.`,
+                ],
+            });
+        }
+
+        resultJson.tables.push(table);
+
+        // 23. Separate-Groups Casewise Statistics
+        if (quadratic.casewise_statistics) {
+            const casewise = quadratic.casewise_statistics;
+            const hasCrossValidation = !!casewise.cross_validated_group;
+
+            const casewiseTable: Table = {
+                key: "quadratic_casewise_statistics",
+                title: "Casewise Statistics (Separate-Groups Covariance)",
+                columnHeaders: [
+                    { header: "", key: "header" },
+                    { header: "Case Number", key: "case_number" },
+                    { header: "Actual Group", key: "actual_group" },
+                    { header: "Predicted Group", key: "predicted_group" },
+                    {
+                        header: "P(G=g | D=d)",
+                        key: "posterior",
+                        children: groups.map((group: string, i: number) => ({
+                            header: group,
+                            key: `posterior_${i}`,
+                        })),
+                    },
+                    {
+                        header: "Squared Mahalanobis Distance to Centroid",
+                        key: "mahalanobis",
+                        children: groups.map((group: string, i: number) => ({
+                            header: group,
+                            key: `mahalanobis_${i}`,
+                        })),
+                    },
+                    ...(hasCrossValidation
+                        ? [
+                              {
+                                  header: "Cross-validated Group",
+                                  key: "cross_validated_group",
+                              },
+                          ]
+                        : []),
+                ],
+                rows: [],
+            };
+
+            for (let i = 0; i < casewise.case_number.length; i++) {
+                const isMisclassified =
+                    casewise.predicted_group[i] !== casewise.actual_group[i];
+                const rowData: any = {
+                    rowHeader: [""],
+                    case_number: formatDisplayNumber(casewise.case_number[i]),
+                    actual_group: casewise.actual_group[i],
+                    predicted_group:
+                        casewise.predicted_group[i] +
+                        (isMisclassified ? "**" : ""),
+                };
+
+                groups.forEach((_: string, j: number) => {
+                    rowData[`posterior_${j}`] = formatDisplayNumber(
+                        casewise.posterior_probabilities[i][j]
+                    );
+                    rowData[`mahalanobis_${j}`] = formatDisplayNumber(
+                        casewise.squared_mahalanobis_distance[i][j]
+                    );
+                });
+
+                if (hasCrossValidation) {
+                    rowData.cross_validated_group =
+                        casewise.cross_validated_group[i];
+                }
+
+                casewiseTable.rows.push(rowData);
+            }
+
+            casewiseTable.rows.push({ rowHeader: ["**. Misclassified case"] });
+
+            resultJson.tables.push(casewiseTable);
+        }
+    }
+
+    // 24. Bootstrap Results
+    if (data.bootstrap) {
+        const bootstrap = data.bootstrap;
+        const level = formatDisplayNumber(bootstrap.confidence_level);
+        const footnote = `Bootstrap results are based on ${bootstrap.valid_samples} of ${bootstrap.requested_samples} ${bootstrap.sampling_method} bootstrap samples; ${level}% ${bootstrap.interval_method} confidence intervals.`;
+
+        const bootstrapEstimateTable = (
+            key: string,
+            title: string,
+            labelHeader: string,
+            estimates: any[]
+        ): Table => {
+            const table: Table = {
+                key,
+                title,
+                columnHeaders: [
+                    { header: labelHeader, key: "label" },
+                    { header: "Function", key: "function" },
+                    { header: "Estimate", key: "estimate" },
+                    {
+                        header: "Bootstrap",
+                        key: "bootstrap",
+                        children: [
+                            { header: "Bias", key: "bias" },
+                            { header: "Std. Error", key: "std_error" },
+                            {
+                                header: `${level}% Confidence Interval`,
+                                key: "confidence_interval",
+                                children: [
+                                    { header: "Lower", key: "lower_bound" },
+                                    { header: "Upper", key: "upper_bound" },
+                                ],
+                            },
+                        ],
+                    },
+                ],
+                rows: [],
+            };
+
+            estimates.forEach((item: any) => {
+                table.rows.push({
+                    rowHeader: [item.label],
+                    function: item.function,
+                    estimate: formatDisplayNumber(item.estimate),
+                    bias: formatDisplayNumber(item.bias),
+                    std_error: formatDisplayNumber(item.std_error),
+                    lower_bound: formatDisplayNumber(item.lower_bound),
+                    upper_bound: formatDisplayNumber(item.upper_bound),
+                });
+            });
+
+            table.rows.push({ rowHeader: [footnote] });
+            return table;
+        };
+
+        resultJson.tables.push(
+            bootstrapEstimateTable(
+                "bootstrap_standardized_coefficients",
+                "Bootstrap for Standardized Canonical Discriminant Function Coefficients",
+                "Variable",
+                bootstrap.standardized_coefficients
+            )
+        );
+        resultJson.tables.push(
+            bootstrapEstimateTable(
+                "bootstrap_structure_matrix",
+                "Bootstrap for Structure Matrix",
+                "Variable",
+                bootstrap.structure_matrix
+            )
+        );
+        resultJson.tables.push(
+            bootstrapEstimateTable(
+                "bootstrap_functions_at_group_centroids",
+                "Bootstrap for Functions at Group Centroids",
+                "Group",
+                bootstrap.function_at_centroids
+            )
+        );
+
+        const error = bootstrap.classification_error;
+        const errorTable: Table = {
+            key: "bootstrap_classification_error",
+            title: "Bootstrap Estimates of Classification Error",
+            columnHeaders: [
+                { header: "", key: "estimate" },
+                { header: "Error Rate", key: "value" },
+            ],
+            rows: [
+                {
+                    rowHeader: ["Apparent"],
+                    value: formatDisplayNumber(error.apparent_error),
+                },
+                {
+                    rowHeader: ["Leave-one-out Bootstrap"],
+                    value: formatDisplayNumber(error.leave_one_out_error),
+                },
+                {
+                    rowHeader: ["No-information"],
+                    value: formatDisplayNumber(error.no_information_error),
+                },
+                {
+                    rowHeader: ["Relative Overfitting Rate"],
+                    value: formatDisplayNumber(error.relative_overfitting),
+                },
+                {
+                    rowHeader: [".632"],
+                    value: formatDisplayNumber(error.error_632),
+                },
+                {
+                    rowHeader: [".632+"],
+                    value: formatDisplayNumber(error.error_632_plus),
+                },
+            ],
+        };
+
+        if (bootstrap.note) {
+            errorTable.rows.push({ rowHeader: [bootstrap.note] });
+        }
+        errorTable.rows.push({ rowHeader: [footnote] });
+
+        resultJson.tables.push(errorTable);
+    }
+
     return resultJson;
 }
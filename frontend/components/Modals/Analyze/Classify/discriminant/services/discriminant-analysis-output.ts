@@ -428,6 +428,119 @@ export async function resultDiscriminant({
                     components: `Classification Results`,
                 });
             }
+
+            /*
+             * 🔀 Separate-Groups Classification Results 🔀
+             * */
+            const quadraticClassificationSummary = findTable(
+                "quadratic_classification_summary"
+            );
+            const quadraticClassificationResults = findTable(
+                "quadratic_classification_results"
+            );
+            const quadraticCasewiseStatistics = findTable(
+                "quadratic_casewise_statistics"
+            );
+            if (
+                quadraticClassificationSummary ||
+                quadraticClassificationResults ||
+                quadraticCasewiseStatistics
+            ) {
+                const quadraticClassificationId = await addAnalytic(logId, {
+                    title: `Separate-Groups Classification`,
+                    note: "",
+                });
+
+                if (quadraticClassificationSummary) {
+                    await addStatistic(quadraticClassificationId, {
+                        title: `Separate-Groups Classification Summary`,
+                        description: `Separate-Groups Classification Summary`,
+                        output_data: quadraticClassificationSummary,
+                        components: `Separate-Groups Classification Summary`,
+                    });
+                }
+
+                if (quadraticClassificationResults) {
+                    await addStatistic(quadraticClassificationId, {
+                        title: `Classification Results (Separate-Groups Covariance)`,
+                        description: `Classification Results (Separate-Groups Covariance)`,
+                        output_data: quadraticClassificationResults,
+                        components: `Classification Results (Separate-Groups Covariance)`,
+                    });
+                }
+
+                if (quadraticCasewiseStatistics) {
+                    await addStatistic(quadraticClassificationId, {
+                        title: `Casewise Statistics (Separate-Groups Covariance)`,
+                        description: `Casewise Statistics (Separate-Groups Covariance)`,
+                        output_data: quadraticCasewiseStatistics,
+                        components: `Casewise Statistics (Separate-Groups Covariance)`,
+                    });
+                }
+            }
+
+            /*
+             * 🎲 Bootstrap Results 🎲
+             * */
+            const bootstrapStandardizedCoefficients = findTable(
+                "bootstrap_standardized_coefficients"
+            );
+            const bootstrapStructureMatrix = findTable(
+                "bootstrap_structure_matrix"
+            );
+            const bootstrapFunctionsAtGroupCentroids = findTable(
+                "bootstrap_functions_at_group_centroids"
+            );
+            const bootstrapClassificationError = findTable(
+                "bootstrap_classification_error"
+            );
+            if (
+                bootstrapStandardizedCoefficients ||
+                bootstrapStructureMatrix ||
+                bootstrapFunctionsAtGroupCentroids ||
+                bootstrapClassificationError
+            ) {
+                const bootstrapId = await addAnalytic(logId, {
+                    title: `Bootstrap`,
+                    note: "",
+                });
+
+                if (bootstrapStandardizedCoefficients) {
+                    await addStatistic(bootstrapId, {
+                        title: `Bootstrap for Standardized Canonical Discriminant Function Coefficients`,
+                        description: `Bootstrap for Standardized Canonical Discriminant Function Coefficients`,
+                        output_data: bootstrapStandardizedCoefficients,
+                        components: `Bootstrap for Standardized Canonical Discriminant Function Coefficients`,
+                    });
+                }
+
+                if (bootstrapStructureMatrix) {
+                    await addStatistic(bootstrapId, {
+                        title: `Bootstrap for Structure Matrix`,
+                        description: `Bootstrap for Structure Matrix`,
+                        output_data: bootstrapStructureMatrix,
+                        components: `Bootstrap for Structure Matrix`,
+                    });
+                }
+
+                if (bootstrapFunctionsAtGroupCentroids) {
+                    await addStatistic(bootstrapId, {
+                        title: `Bootstrap for Functions at Group Centroids`,
+                        description: `Bootstrap for Functions at Group Centroids`,
+                        output_data: bootstrapFunctionsAtGroupCentroids,
+                        components: `Bootstrap for Functions at Group Centroids`,
+                    });
+                }
+
+                if (bootstrapClassificationError) {
+                    await addStatistic(bootstrapId, {
+                        title: `Bootstrap Estimates of Classification Error`,
+                        description: `Bootstrap Estimates of Classification Error`,
+                        output_data: bootstrapClassificationError,
+                        components: `Bootstrap Estimates of Classification Error`,
+                    });
+                }
+            }
         };
 
         await discriminantAnalysisResult();
@@ -1,4 +1,5 @@
 import {
+    HierClusDiagnosticsType,
     HierClusMainType,
     HierClusMethodType,
     HierClusPlotsType,
@@ -60,6 +61,7 @@ export const HierClusMethodDefault: HierClusMethodType = {
     BinaryMethod: "BSEUCLID",
     Present: 1,
     Absent: 0,
+    Gower: false,
     StandardizeMethod: "None",
     ByVariable: true,
     ByCase: false,
@@ -68,10 +70,21 @@ export const HierClusMethodDefault: HierClusMethodType = {
     RescaleRange: false,
 };
 
+export const HierClusDiagnosticsDefault: HierClusDiagnosticsType = {
+    Evaluate: false,
+    MinCluster: 2,
+    MaxCluster: 10,
+    Stability: false,
+    BootstrapSamples: 100,
+    Seed: false,
+    SeedValue: 2000000,
+};
+
 export const HierClusDefault: HierClusType = {
     main: HierClusMainDefault,
     statistics: HierClusStatisticsDefault,
     plots: HierClusPlotsDefault,
     save: HierClusSaveDefault,
     method: HierClusMethodDefault,
+    diagnostics: HierClusDiagnosticsDefault,
 };
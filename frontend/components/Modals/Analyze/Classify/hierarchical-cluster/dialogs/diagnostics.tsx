@@ -0,0 +1,279 @@
+import React, {useEffect, useState} from "react";
+import {Dialog, DialogContent, DialogFooter, DialogHeader, DialogTitle,} from "@/components/ui/dialog";
+import {Button} from "@/components/ui/button";
+import {Separator} from "@/components/ui/separator";
+import {
+    HierClusDiagnosticsProps,
+    HierClusDiagnosticsType,
+} from "@/components/Modals/Analyze/Classify/hierarchical-cluster/types/hierarchical-cluster";
+import {ResizablePanel, ResizablePanelGroup} from "@/components/ui/resizable";
+import {Label} from "@/components/ui/label";
+import {Input} from "@/components/ui/input";
+import {CheckedState} from "@radix-ui/react-checkbox";
+import {Checkbox} from "@/components/ui/checkbox";
+
+export const HierClusDiagnostics = ({
+    isDiagnosticsOpen,
+    setIsDiagnosticsOpen,
+    updateFormData,
+    data,
+}: HierClusDiagnosticsProps) => {
+    const [diagnosticsState, setDiagnosticsState] =
+        useState<HierClusDiagnosticsType>({ ...data });
+    const [isContinueDisabled, setIsContinueDisabled] = useState(false);
+
+    useEffect(() => {
+        if (isDiagnosticsOpen) {
+            setDiagnosticsState({ ...data });
+        }
+    }, [isDiagnosticsOpen, data]);
+
+    useEffect(() => {
+        const { Evaluate, MinCluster, MaxCluster, Stability, BootstrapSamples } =
+            diagnosticsState;
+        const invalidRange =
+            MinCluster === null ||
+            MaxCluster === null ||
+            MinCluster < 2 ||
+            MaxCluster < MinCluster;
+        const invalidSamples =
+            Stability && (BootstrapSamples === null || BootstrapSamples < 2);
+
+        setIsContinueDisabled(Evaluate && (invalidRange || invalidSamples));
+    }, [diagnosticsState]);
+
+    const handleChange = (
+        field: keyof HierClusDiagnosticsType,
+        value: CheckedState | boolean | number | null
+    ) => {
+        setDiagnosticsState((prevState) => ({
+            ...prevState,
+            [field]: value,
+        }));
+    };
+
+    const handleNumberChange = (
+        field: keyof HierClusDiagnosticsType,
+        value: string
+    ) => {
+        handleChange(field, value === "" ? null : Number(value));
+    };
+
+    const handleContinue = () => {
+        Object.entries(diagnosticsState).forEach(([key, value]) => {
+            updateFormData(key as keyof HierClusDiagnosticsType, value);
+        });
+        setIsDiagnosticsOpen(false);
+    };
+
+    return (
+        <>
+            {/* Diagnostics Dialog */}
+            <Dialog
+                open={isDiagnosticsOpen}
+                onOpenChange={setIsDiagnosticsOpen}
+            >
+                <DialogContent className="sm:max-w-md">
+                    <DialogHeader>
+                        <DialogTitle>
+                            Hierarchical Cluster Analysis: Diagnostics
+                        </DialogTitle>
+                    </DialogHeader>
+                    <Separator />
+                    <div className="flex flex-col items-start gap-4">
+                        <div className="flex items-center space-x-2">
+                            <Checkbox
+                                id="Evaluate"
+                                checked={diagnosticsState.Evaluate}
+                                onCheckedChange={(checked) =>
+                                    handleChange("Evaluate", checked)
+                                }
+                            />
+                            <label
+                                htmlFor="Evaluate"
+                                className="text-sm font-medium leading-none peer-disabled:cursor-not-allowed peer-disabled:opacity-70"
+                            >
+                                Evaluate Number of Clusters
+                            </label>
+                        </div>
+                        <ResizablePanelGroup
+                            direction="vertical"
+                            className="min-h-[250px] max-w-md rounded-lg border md:min-w-[200px]"
+                        >
+                            <ResizablePanel defaultSize={100}>
+                                <div className="flex flex-col gap-2 w-full p-2">
+                                    <Label className="font-bold">
+                                        Range of Solutions
+                                    </Label>
+                                    <div className="flex items-center pl-4 space-x-2">
+                                        <label
+                                            htmlFor="DiagMinCluster"
+                                            className="w-[200px] text-sm font-medium leading-none peer-disabled:cursor-not-allowed peer-disabled:opacity-70"
+                                        >
+                                            Minimum number of Clusters:
+                                        </label>
+                                        <div className="w-[100px]">
+                                            <Input
+                                                id="DiagMinCluster"
+                                                type="number"
+                                                min={2}
+                                                value={
+                                                    diagnosticsState.MinCluster ??
+                                                    ""
+                                                }
+                                                disabled={
+                                                    !diagnosticsState.Evaluate
+                                                }
+                                                onChange={(e) =>
+                                                    handleNumberChange(
+                                                        "MinCluster",
+                                                        e.target.value
+                                                    )
+                                                }
+                                            />
+                                        </div>
+                                    </div>
+                                    <div className="flex items-center pl-4 space-x-2">
+                                        <label
+                                            htmlFor="DiagMaxCluster"
+                                            className="w-[200px] text-sm font-medium leading-none peer-disabled:cursor-not-allowed peer-disabled:opacity-70"
+                                        >
+                                            Maximum number of Clusters:
+                                        </label>
+                                        <div className="w-[100px]">
+                                            <Input
+                                                id="DiagMaxCluster"
+                                                type="number"
+                                                min={2}
+                                                value={
+                                                    diagnosticsState.MaxCluster ??
+                                                    ""
+                                                }
+                                                disabled={
+                                                    !diagnosticsState.Evaluate
+                                                }
+                                                onChange={(e) =>
+                                                    handleNumberChange(
+                                                        "MaxCluster",
+                                                        e.target.value
+                                                    )
+                                                }
+                                            />
+                                        </div>
+                                    </div>
+                                    <Label className="font-bold">
+                                        Cluster Stability
+                                    </Label>
+                                    <div className="flex items-center space-x-2">
+                                        <Checkbox
+                                            id="Stability"
+                                            checked={diagnosticsState.Stability}
+                                            disabled={!diagnosticsState.Evaluate}
+                                            onCheckedChange={(checked) =>
+                                                handleChange("Stability", checked)
+                                            }
+                                        />
+                                        <label
+                                            htmlFor="Stability"
+                                            className="text-sm font-medium leading-none peer-disabled:cursor-not-allowed peer-disabled:opacity-70"
+                                        >
+                                            Bootstrap Jaccard Stability
+                                        </label>
+                                    </div>
+                                    <div className="flex items-center pl-4 space-x-2">
+                                        <label
+                                            htmlFor="BootstrapSamples"
+                                            className="w-[200px] text-sm font-medium leading-none peer-disabled:cursor-not-allowed peer-disabled:opacity-70"
+                                        >
+                                            Number of Samples:
+                                        </label>
+                                        <div className="w-[100px]">
+                                            <Input
+                                                id="BootstrapSamples"
+                                                type="number"
+                                                min={2}
+                                                value={
+                                                    diagnosticsState.BootstrapSamples ??
+                                                    ""
+                                                }
+                                                disabled={
+                                                    !diagnosticsState.Evaluate ||
+                                                    !diagnosticsState.Stability
+                                                }
+                                                onChange={(e) =>
+                                                    handleNumberChange(
+                                                        "BootstrapSamples",
+                                                        e.target.value
+                                                    )
+                                                }
+                                            />
+                                        </div>
+                                    </div>
+                                    <div className="flex items-center pl-4 space-x-2">
+                                        <Checkbox
+                                            id="DiagSeed"
+                                            checked={diagnosticsState.Seed}
+                                            disabled={
+                                                !diagnosticsState.Evaluate ||
+                                                !diagnosticsState.Stability
+                                            }
+                                            onCheckedChange={(checked) =>
+                                                handleChange("Seed", checked)
+                                            }
+                                        />
+                                        <label
+                                            htmlFor="DiagSeed"
+                                            className="w-[176px] text-sm font-medium leading-none peer-disabled:cursor-not-allowed peer-disabled:opacity-70"
+                                        >
+                                            Set Seed:
+                                        </label>
+                                        <div className="w-[100px]">
+                                            <Input
+                                                id="DiagSeedValue"
+                                                type="number"
+                                                value={
+                                                    diagnosticsState.SeedValue ??
+                                                    ""
+                                                }
+                                                disabled={
+                                                    !diagnosticsState.Evaluate ||
+                                                    !diagnosticsState.Stability ||
+                                                    !diagnosticsState.Seed
+                                                }
+                                                onChange={(e) =>
+                                                    handleNumberChange(
+                                                        "SeedValue",
+                                                        e.target.value
+                                                    )
+                                                }
+                                            />
+                                        </div>
+                                    </div>
+                                </div>
+                            </ResizablePanel>
+                        </ResizablePanelGroup>
+                    </div>
+                    <DialogFooter className="sm:justify-start">
+                        <Button
+                            disabled={isContinueDisabled}
+                            type="button"
+                            onClick={handleContinue}
+                        >
+                            Continue
+                        </Button>
+                        <Button
+                            type="button"
+                            variant="secondary"
+                            onClick={() => setIsDiagnosticsOpen(false)}
+                        >
+                            Cancel
+                        </Button>
+                        <Button type="button" variant="secondary">
+                            Help
+                        </Button>
+                    </DialogFooter>
+                </DialogContent>
+            </Dialog>
+        </>
+    );
+};
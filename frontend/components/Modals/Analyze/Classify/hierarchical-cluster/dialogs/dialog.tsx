@@ -22,6 +22,7 @@ export const HierClusDialog = ({
     setIsPlotsOpen,
     setIsSaveOpen,
     setIsMethodOpen,
+    setIsDiagnosticsOpen,
     updateFormData,
     data,
     globalVariables,
@@ -396,6 +397,16 @@ export const HierClusDialog = ({
                                     >
                                         Method...
                                     </Button>
+                                    <Button
+                                        className="w-full"
+                                        type="button"
+                                        variant="secondary"
+                                        onClick={openDialog(
+                                            setIsDiagnosticsOpen
+                                        )}
+                                    >
+                                        Diagnostics...
+                                    </Button>
                                     <Button
                                         className="w-full"
                                         type="button"
@@ -12,6 +12,7 @@ import {HierClusStatistics} from "@/components/Modals/Analyze/Classify/hierarchi
 import {HierClusPlots} from "@/components/Modals/Analyze/Classify/hierarchical-cluster/dialogs/plots";
 import {HierClusSave} from "@/components/Modals/Analyze/Classify/hierarchical-cluster/dialogs/save";
 import {HierClusMethod} from "@/components/Modals/Analyze/Classify/hierarchical-cluster/dialogs/method";
+import {HierClusDiagnostics} from "@/components/Modals/Analyze/Classify/hierarchical-cluster/dialogs/diagnostics";
 import {Dialog, DialogContent, DialogTitle} from "@/components/ui/dialog";
 import {useModal} from "@/hooks/useModal";
 import {useVariableStore} from "@/stores/useVariableStore";
@@ -37,6 +38,7 @@ export const HierClusContainer = ({ onClose }: HierClusContainerProps) => {
     const [isPlotsOpen, setIsPlotsOpen] = useState(false);
     const [isSaveOpen, setIsSaveOpen] = useState(false);
     const [isMethodOpen, setIsMethodOpen] = useState(false);
+    const [isDiagnosticsOpen, setIsDiagnosticsOpen] = useState(false);
 
     const { closeModal } = useModal();
 
@@ -46,7 +48,7 @@ export const HierClusContainer = ({ onClose }: HierClusContainerProps) => {
                 const savedData = await getFormData("HierarchicalCluster");
                 if (savedData) {
                     const { id, ...formDataWithoutId } = savedData;
-                    setFormData(formDataWithoutId);
+                    setFormData({ ...HierClusDefault, ...formDataWithoutId });
                 } else {
                     setFormData({ ...HierClusDefault });
                 }
@@ -119,6 +121,7 @@ export const HierClusContainer = ({ onClose }: HierClusContainerProps) => {
                     setIsPlotsOpen={setIsPlotsOpen}
                     setIsSaveOpen={setIsSaveOpen}
                     setIsMethodOpen={setIsMethodOpen}
+                    setIsDiagnosticsOpen={setIsDiagnosticsOpen}
                     updateFormData={(field, value) =>
                         updateFormData("main", field, value)
                     }
@@ -167,6 +170,16 @@ export const HierClusContainer = ({ onClose }: HierClusContainerProps) => {
                     }
                     data={formData.method}
                 />
+
+                {/* Diagnostics */}
+                <HierClusDiagnostics
+                    isDiagnosticsOpen={isDiagnosticsOpen}
+                    setIsDiagnosticsOpen={setIsDiagnosticsOpen}
+                    updateFormData={(field, value) =>
+                        updateFormData("diagnostics", field, value)
+                    }
+                    data={formData.diagnostics}
+                />
             </DialogContent>
         </Dialog>
     );
@@ -64,6 +64,7 @@ export const HierClusMethod = ({
             Interval: value === "Interval",
             Counts: value === "Counts",
             Binary: value === "Binary",
+            Gower: value === "Gower",
         }));
     };
 
@@ -130,6 +131,8 @@ export const HierClusMethod = ({
                                                 ? "Interval"
                                                 : methodState.Counts
                                                 ? "Counts"
+                                                : methodState.Gower
+                                                ? "Gower"
                                                 : "Binary"
                                         }
                                         onValueChange={handleMeasureGrp}
@@ -502,6 +505,16 @@ export const HierClusMethod = ({
                                                         </div>
                                                     </div>
                                                 </div>
+                                                <div className="flex items-center space-x-2">
+                                                    <RadioGroupItem
+                                                        value="Gower"
+                                                        id="Gower"
+                                                    />
+                                                    <Label htmlFor="Gower">
+                                                        Gower (mixed scale,
+                                                        ordinal and nominal)
+                                                    </Label>
+                                                </div>
                                             </div>
                                         </div>
                                     </RadioGroup>
@@ -1,12 +1,14 @@
 use serde::{ Deserialize, Serialize };
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct ClusterConfig {
     pub main: MainConfig,
     pub statistics: StatisticsConfig,
     pub plots: PlotsConfig,
     pub save: SaveConfig,
     pub method: MethodConfig,
+    #[serde(default)]
+    pub diagnostics: DiagnosticsConfig,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -25,6 +27,19 @@ pub struct MainConfig {
     pub disp_plots: bool,
 }
 
+impl Default for MainConfig {
+    fn default() -> Self {
+        MainConfig {
+            variables: None,
+            label_cases: None,
+            cluster_cases: true,
+            cluster_var: false,
+            disp_stats: true,
+            disp_plots: true,
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct StatisticsConfig {
     #[serde(rename = "AgglSchedule")]
@@ -45,6 +60,21 @@ pub struct StatisticsConfig {
     pub min_cluster: Option<i32>,
 }
 
+impl Default for StatisticsConfig {
+    fn default() -> Self {
+        StatisticsConfig {
+            aggl_schedule: true,
+            prox_matrix: false,
+            none_sol: true,
+            single_sol: false,
+            range_sol: false,
+            no_of_cluster: None,
+            max_cluster: None,
+            min_cluster: None,
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct PlotsConfig {
     #[serde(rename = "Dendrograms")]
@@ -67,6 +97,22 @@ pub struct PlotsConfig {
     pub hori_orien: bool,
 }
 
+impl Default for PlotsConfig {
+    fn default() -> Self {
+        PlotsConfig {
+            dendrograms: false,
+            all_clusters: true,
+            range_clusters: false,
+            none_clusters: false,
+            start_cluster: 1,
+            stop_cluster: None,
+            step_by_cluster: 1,
+            vert_orien: true,
+            hori_orien: false,
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct SaveConfig {
     #[serde(rename = "NoneSol")]
@@ -83,6 +129,19 @@ pub struct SaveConfig {
     pub min_cluster: Option<i32>,
 }
 
+impl Default for SaveConfig {
+    fn default() -> Self {
+        SaveConfig {
+            none_sol: true,
+            single_sol: false,
+            range_sol: false,
+            no_of_cluster: None,
+            max_cluster: None,
+            min_cluster: None,
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct MethodConfig {
     #[serde(rename = "ClusMethod")]
@@ -107,6 +166,8 @@ pub struct MethodConfig {
     pub present: i32,
     #[serde(rename = "Absent")]
     pub absent: i32,
+    #[serde(rename = "Gower", default)]
+    pub gower: bool,
     #[serde(rename = "StandardizeMethod")]
     pub standardize_method: StandardizeMethod,
     #[serde(rename = "ByVariable")]
@@ -121,6 +182,64 @@ pub struct MethodConfig {
     pub rescale_range: bool,
 }
 
+impl Default for MethodConfig {
+    fn default() -> Self {
+        MethodConfig {
+            clus_method: ClusMethod::AverageBetweenGroups,
+            interval: true,
+            interval_method: IntervalMethod::SquaredEuclidean,
+            power: "2".to_string(),
+            root: "2".to_string(),
+            counts: false,
+            counts_method: CountsMethod::CHISQ,
+            binary: false,
+            binary_method: BinaryMethod::BSEUCLID,
+            present: 1,
+            absent: 0,
+            gower: false,
+            standardize_method: StandardizeMethod::None,
+            by_variable: true,
+            by_case: false,
+            abs_value: false,
+            change_sign: false,
+            rescale_range: false,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct DiagnosticsConfig {
+    #[serde(rename = "Evaluate")]
+    pub evaluate: bool,
+    #[serde(rename = "MinCluster")]
+    pub min_cluster: i32,
+    #[serde(rename = "MaxCluster")]
+    pub max_cluster: i32,
+    #[serde(rename = "Stability")]
+    pub stability: bool,
+    #[serde(rename = "BootstrapSamples")]
+    pub bootstrap_samples: i32,
+    #[serde(rename = "Seed")]
+    pub seed: bool,
+    #[serde(rename = "SeedValue")]
+    pub seed_value: i64,
+}
+
+impl Default for DiagnosticsConfig {
+    fn default() -> Self {
+        DiagnosticsConfig {
+            evaluate: false,
+            min_cluster: 2,
+            max_cluster: 10,
+            stability: false,
+            bootstrap_samples: 100,
+            seed: false,
+            seed_value: 2000000,
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub enum ClusMethod {
     AverageBetweenGroups,
@@ -12,6 +12,7 @@ pub struct ClusteringResult {
     pub icicle_plot: Option<IciclePlot>,
     pub executed_functions: Vec<String>,
     pub cluster_memberships: Vec<ClusterMembership>,
+    pub cluster_diagnostics: Option<ClusterDiagnostics>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -87,12 +88,48 @@ pub struct DendrogramNode {
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ClusterState {
     pub clusters: Vec<Vec<usize>>, // List of clusters, each containing case indices
-    pub distances: Vec<Vec<f64>>, // Distance matrix between clusters
+    pub distances: CondensedDistanceMatrix, // Distance matrix between clusters
     pub case_labels: Vec<String>, // Labels for each case
     pub variables: Vec<String>, // Variables used for clustering
     pub method: ClusMethod, // Clustering method
 }
 
+// Upper triangle of a symmetric distance matrix stored row by row (pairs i < j only)
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CondensedDistanceMatrix {
+    pub size: usize,
+    pub values: Vec<f64>,
+}
+
+impl CondensedDistanceMatrix {
+    pub fn new(size: usize) -> Self {
+        CondensedDistanceMatrix {
+            size,
+            values: vec![0.0; size * size.saturating_sub(1) / 2],
+        }
+    }
+
+    fn index(&self, i: usize, j: usize) -> usize {
+        let (i, j) = if i < j { (i, j) } else { (j, i) };
+        (i * (2 * self.size - i - 1)) / 2 + (j - i - 1)
+    }
+
+    pub fn get(&self, i: usize, j: usize) -> f64 {
+        if i == j {
+            0.0
+        } else {
+            self.values[self.index(i, j)]
+        }
+    }
+
+    pub fn set(&mut self, i: usize, j: usize, value: f64) {
+        if i != j {
+            let idx = self.index(i, j);
+            self.values[idx] = value;
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct IciclePlot {
     // The orientation of the plot ("vertical" or "horizontal")
@@ -112,3 +149,57 @@ pub struct ClusterMembership {
     pub num_clusters: usize,
     pub case_assignments: Vec<usize>,
 }
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ClusterDiagnostics {
+    // Correlation between the original proximities and the cophenetic distances
+    pub cophenetic_correlation: Option<f64>,
+    // Cut diagnostics for each number of clusters in the requested range
+    pub solutions: Vec<ClusterSolutionDiagnostics>,
+    // Inconsistency coefficient of every agglomeration stage
+    pub inconsistency: Vec<InconsistencyEntry>,
+    // Bootstrap stability of the clusters (if requested)
+    pub stability: Option<ClusterStability>,
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ClusterSolutionDiagnostics {
+    pub num_clusters: usize,
+    // Coefficient of the stage at which this number of clusters is formed
+    pub coefficient: f64,
+    // Increase in the coefficient when the next stage merges to one cluster less
+    pub coefficient_change: Option<f64>,
+    // Inconsistency coefficient of the next stage (the link that is cut)
+    pub inconsistency: Option<f64>,
+    pub average_silhouette: Option<f64>,
+    pub calinski_harabasz: Option<f64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct InconsistencyEntry {
+    pub stage: usize,
+    pub coefficient: f64,
+    pub mean: f64,
+    pub std_dev: f64,
+    pub links: usize,
+    pub inconsistency: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ClusterStability {
+    pub requested_samples: usize,
+    pub valid_samples: usize,
+    pub clusters: Vec<ClusterJaccard>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ClusterJaccard {
+    pub num_clusters: usize,
+    // Cluster number in order of first appearance of its members (1-indexed)
+    pub cluster: usize,
+    pub size: usize,
+    pub mean_jaccard: f64,
+    // Number of bootstrap samples in which the cluster was dissolved (Jaccard <= 0.5)
+    pub dissolved: usize,
+}
@@ -4,15 +4,16 @@ use std::collections::HashMap;
 use crate::models::{
     config::{ ClusMethod, ClusterConfig },
     data::{ AnalysisData, DataValue },
-    result::{ AgglomerationSchedule, AgglomerationStage, ClusterState },
+    result::{ AgglomerationSchedule, AgglomerationStage, ClusterState, CondensedDistanceMatrix },
 };
 
 use super::core::{
+    agglomerate_clusters,
     calculate_distance,
     calculate_variable_distance,
     extract_case_label,
-    find_closest_clusters,
-    merge_clusters,
+    gower_distance,
+    prepare_gower_variables,
 };
 
 // Memeriksa apakah kita melakukan clustering untuk kasus atau variabel
@@ -20,41 +21,17 @@ pub fn generate_agglomeration_schedule_wrapper(
     data: &AnalysisData,
     config: &ClusterConfig
 ) -> Result<AgglomerationSchedule, String> {
-    if config.main.cluster_cases {
-        generate_case_agglomeration_schedule(data, config)
-    } else if config.main.cluster_var {
-        generate_variable_agglomeration_schedule(data, config)
-    } else {
-        Err("Neither case nor variable clustering specified".to_string())
-    }
-}
-
-// Fungsi untuk menghasilkan jadwal aglomerasi untuk clustering kasus
-fn generate_case_agglomeration_schedule(
-    data: &AnalysisData,
-    config: &ClusterConfig
-) -> Result<AgglomerationSchedule, String> {
-    // Mendapatkan variabel yang akan digunakan untuk menghitung jarak
-    let variables = config.main.variables
-        .as_ref()
-        .ok_or_else(|| "No variables specified for clustering".to_string())?;
-
-    if data.cluster_data.is_empty() {
-        return Err("No data available for clustering".to_string());
-    }
-
-    // Ekstrak nilai dan inisialisasi struktur clustering
-    let cluster_state = initialize_case_cluster_state(data, config, variables)?;
+    let mut cluster_state = initialize_cluster_state(data, config)?;
 
     // Menghasilkan jadwal aglomerasi
-    generate_agglomeration_schedule(&mut cluster_state.clone(), config)
+    generate_agglomeration_schedule(&mut cluster_state)
 }
 
-// Fungsi untuk menghasilkan jadwal aglomerasi untuk clustering variabel
-fn generate_variable_agglomeration_schedule(
+// Inisialisasi cluster state (matriks jarak awal) untuk clustering kasus atau variabel
+pub fn initialize_cluster_state(
     data: &AnalysisData,
     config: &ClusterConfig
-) -> Result<AgglomerationSchedule, String> {
+) -> Result<ClusterState, String> {
     // Mendapatkan variabel yang akan digunakan dalam clustering
     let variables = config.main.variables
         .as_ref()
@@ -65,11 +42,17 @@ fn generate_variable_agglomeration_schedule(
     }
 
     // Ekstrak nilai dan inisialisasi struktur clustering
-    let variable_values = extract_variable_data(data, variables)?;
-    let cluster_state = initialize_variable_cluster_state(&variable_values, variables, config)?;
-
-    // Menghasilkan jadwal aglomerasi
-    generate_agglomeration_schedule(&mut cluster_state.clone(), config)
+    if config.main.cluster_cases {
+        initialize_case_cluster_state(data, config, variables)
+    } else if config.main.cluster_var {
+        if config.method.gower {
+            return Err("Gower distance is only available when clustering cases".to_string());
+        }
+        let variable_values = extract_variable_data(data, variables)?;
+        initialize_variable_cluster_state(&variable_values, variables, config)
+    } else {
+        Err("Neither case nor variable clustering specified".to_string())
+    }
 }
 
 // Inisialisasi cluster state untuk clustering kasus
@@ -79,7 +62,7 @@ fn initialize_case_cluster_state(
     variables: &[String]
 ) -> Result<ClusterState, String> {
     let case_count = data.cluster_data
-        .get(0)
+        .first()
         .map(|d| d.len())
         .ok_or_else(|| "No data available for clustering".to_string())?;
 
@@ -109,18 +92,22 @@ fn initialize_case_cluster_state(
     // Inisialisasi cluster - setiap kasus awalnya menjadi cluster sendiri
     let clusters = (0..case_count).map(|i| vec![i]).collect::<Vec<_>>();
 
-    // Menghitung matriks jarak awal
-    let mut distances = vec![vec![0.0; case_count]; case_count];
-    for i in 0..case_count {
-        for j in i..case_count {
-            if i == j {
-                distances[i][j] = 0.0;
-                continue;
-            }
+    // Jarak Gower membutuhkan rentang dan level variabel dari seluruh kasus
+    let gower_variables = if config.method.gower {
+        Some(prepare_gower_variables(data, variables)?)
+    } else {
+        None
+    };
 
-            let distance = calculate_distance(&case_values[i], &case_values[j], variables, config);
-            distances[i][j] = distance;
-            distances[j][i] = distance; // Memastikan simetri
+    // Menghitung matriks jarak awal (hanya segitiga atas karena jarak simetris)
+    let mut distances = CondensedDistanceMatrix::new(case_count);
+    for i in 0..case_count {
+        for j in i + 1..case_count {
+            let distance = match &gower_variables {
+                Some(gower) => gower_distance(&case_values[i], &case_values[j], gower),
+                None => calculate_distance(&case_values[i], &case_values[j], variables, config),
+            };
+            distances.set(i, j, distance);
         }
     }
 
@@ -139,7 +126,7 @@ fn extract_variable_data(
     variables: &[String]
 ) -> Result<HashMap<String, Vec<f64>>, String> {
     let case_count = data.cluster_data
-        .get(0)
+        .first()
         .map(|d| d.len())
         .ok_or_else(|| "No data available for clustering".to_string())?;
 
@@ -180,7 +167,7 @@ fn extract_variable_data(
 // Inisialisasi cluster state untuk clustering variabel
 fn initialize_variable_cluster_state(
     variable_values: &HashMap<String, Vec<f64>>,
-    variables: &Vec<String>,
+    variables: &[String],
     config: &ClusterConfig
 ) -> Result<ClusterState, String> {
     let var_count = variables.len();
@@ -188,36 +175,29 @@ fn initialize_variable_cluster_state(
     // Inisialisasi cluster - setiap variabel awalnya adalah cluster sendiri
     let clusters = (0..var_count).map(|i| vec![i]).collect::<Vec<_>>();
 
-    // Menghitung matriks jarak awal antara variabel
-    let mut distances = vec![vec![0.0; var_count]; var_count];
+    // Menghitung matriks jarak awal antara variabel (hanya segitiga atas)
+    let mut distances = CondensedDistanceMatrix::new(var_count);
     for i in 0..var_count {
-        for j in i..var_count {
-            if i == j {
-                distances[i][j] = 0.0;
-                continue;
-            }
-
+        for j in i + 1..var_count {
             let var_i = &variables[i];
             let var_j = &variables[j];
             let distance = calculate_variable_distance(variable_values, var_i, var_j, config);
 
-            distances[i][j] = distance;
-            distances[j][i] = distance; // Memastikan simetri
+            distances.set(i, j, distance);
         }
     }
 
     Ok(ClusterState {
         clusters,
         distances,
-        case_labels: variables.clone(), // Nama variabel berfungsi sebagai label
+        case_labels: variables.to_vec(), // Nama variabel berfungsi sebagai label
         variables: variables.to_vec(),
         method: config.method.clus_method.clone(),
     })
 }
 
 pub fn generate_agglomeration_schedule(
-    state: &mut ClusterState,
-    config: &ClusterConfig
+    state: &mut ClusterState
 ) -> Result<AgglomerationSchedule, String> {
     let original_count = state.clusters.len();
     let stages_count = original_count - 1;
@@ -226,10 +206,6 @@ pub fn generate_agglomeration_schedule(
     let mut stages = Vec::with_capacity(stages_count);
     let mut ward_coefficient = 0.0;
 
-    // Menyimpan ID cluster aktif (1-indexed)
-    // Ini adalah mapping dari indeks cluster saat ini ke ID asli
-    let mut active_clusters: Vec<usize> = (1..=original_count).collect();
-
     // Melacak kapan cluster pertama kali muncul dalam jadwal
     // Original clusters memiliki stage 0, merged clusters memiliki stage saat mereka dibentuk
     let mut first_appears: HashMap<usize, usize> = HashMap::new();
@@ -238,103 +214,75 @@ pub fn generate_agglomeration_schedule(
     // (digunakan untuk menghitung next_stage)
     let mut last_stage_used: HashMap<usize, usize> = HashMap::new();
 
-    // Simpan cluster sizes untuk perhitungan jarak
-    let mut cluster_sizes: Vec<usize> = state.clusters
-        .iter()
-        .map(|c| c.len())
-        .collect();
+    // Menentukan urutan penggabungan (NN-chain, SLINK, atau pencarian menyeluruh)
+    let merges = agglomerate_clusters(state)?;
 
     // Memproses setiap tahap
-    for stage_idx in 0..stages_count {
+    for (stage_idx, (keep_idx, remove_idx, distance)) in merges.into_iter().enumerate() {
         let stage = stage_idx + 1; // Nomor tahap 1-indexed
 
-        // Menemukan cluster terdekat
-        if let Some((idx1, idx2, distance)) = find_closest_clusters(state) {
-            // Mendapatkan ID cluster yang akan digabung (1-indexed)
-            let cluster1_id = active_clusters[idx1];
-            let cluster2_id = active_clusters[idx2];
-
-            // Untuk metode Ward, perbarui koefisien dengan formula yang tepat
-            let coefficient = match state.method {
-                ClusMethod::Ward => {
-                    // Formula Ward: W = W + 0.5 * s_pq
-                    ward_coefficient += 0.5 * distance;
-                    ward_coefficient
-                }
-                _ => distance,
-            };
+        // ID cluster (1-indexed) adalah indeks anggota terkecilnya
+        let cluster1_id = keep_idx + 1;
+        let cluster2_id = remove_idx + 1;
 
-            // Menghitung kapan cluster pertama kali muncul
-            // Jika belum pernah muncul (original cluster), default ke 0
-            let cluster1_first_stage = *first_appears.get(&cluster1_id).unwrap_or(&0);
-            let cluster2_first_stage = *first_appears.get(&cluster2_id).unwrap_or(&0);
-
-            // Catat stage di mana cluster terakhir digunakan
-            last_stage_used.insert(cluster1_id, stage);
-            last_stage_used.insert(cluster2_id, stage);
-
-            // Sementara set next_stage ke 0, akan diupdate nanti
-            let next_stage = 0;
-
-            // Membuat entri tahap untuk agglomeration schedule
-            stages.push(AgglomerationStage {
-                stage,
-                clusters_combined: (cluster1_id, cluster2_id),
-                coefficients: coefficient,
-                cluster_first_appears: (cluster1_first_stage, cluster2_first_stage),
-                next_stage: next_stage,
-            });
-
-            web_sys::console::log_1(
-                &format!(
-                    "Stage {}: Merging clusters {} and {} with distance {}",
-                    stage,
-                    cluster1_id,
-                    cluster2_id,
-                    distance
-                ).into()
-            );
-
-            // Perbarui ukuran cluster untuk perhitungan berikutnya
-            let new_cluster_size = cluster_sizes[idx1] + cluster_sizes[idx2];
-            cluster_sizes[idx1] = new_cluster_size;
-
-            // Menggabungkan cluster secara fisik (dalam array clusters)
-            merge_clusters(state, idx1, idx2, &cluster_sizes);
-
-            // Hapus ukuran cluster yang sudah digabung
-            cluster_sizes.remove(idx2);
-
-            // Hapus cluster yang sudah digabung dari active_clusters
-            active_clusters.remove(idx2);
-
-            // Catat kapan cluster hasil gabungan pertama kali muncul
-            // Kita gunakan cluster1_id sebagai ID untuk cluster gabungan
-            first_appears.insert(cluster1_id, stage);
-        } else {
-            return Err(format!("Failed to find closest clusters at stage {}", stage_idx));
+        // Untuk metode Ward, perbarui koefisien dengan formula yang tepat
+        let coefficient = match state.method {
+            ClusMethod::Ward => {
+                // Formula Ward: W = W + 0.5 * s_pq
+                ward_coefficient += 0.5 * distance;
+                ward_coefficient
+            }
+            _ => distance,
+        };
+
+        // Menghitung kapan cluster pertama kali muncul
+        // Jika belum pernah muncul (original cluster), default ke 0
+        let cluster1_first_stage = *first_appears.get(&cluster1_id).unwrap_or(&0);
+        let cluster2_first_stage = *first_appears.get(&cluster2_id).unwrap_or(&0);
+
+        // Catat stage di mana cluster terakhir digunakan
+        last_stage_used.insert(cluster1_id, stage);
+        last_stage_used.insert(cluster2_id, stage);
+
+        // Sementara set next_stage ke 0, akan diupdate nanti
+        let next_stage = 0;
+
+        // Membuat entri tahap untuk agglomeration schedule
+        stages.push(AgglomerationStage {
+            stage,
+            clusters_combined: (cluster1_id, cluster2_id),
+            coefficients: coefficient,
+            cluster_first_appears: (cluster1_first_stage, cluster2_first_stage),
+            next_stage,
+        });
+
+        // Menggabungkan anggota cluster (yang lebih kecil dipindahkan ke yang lebih besar)
+        let mut moved = std::mem::take(&mut state.clusters[remove_idx]);
+        if moved.len() > state.clusters[keep_idx].len() {
+            std::mem::swap(&mut moved, &mut state.clusters[keep_idx]);
         }
+        state.clusters[keep_idx].extend(moved);
+
+        // Catat kapan cluster hasil gabungan pertama kali muncul
+        // Kita gunakan cluster1_id sebagai ID untuk cluster gabungan
+        first_appears.insert(cluster1_id, stage);
     }
 
+    // Hanya cluster yang masih aktif yang disimpan
+    state.clusters.retain(|c| !c.is_empty());
+
     // Post-processing: Hitung next_stage
     // Untuk setiap stage, cari stage berikutnya di mana cluster yang terbentuk
-    // muncul dalam clusters_combined
-    for i in 0..stages_count {
-        let (cluster1_id, _) = stages[i].clusters_combined;
-
-        // Cluster hasil gabungan direpresentasikan oleh cluster1_id
-        // Cari stage berikutnya di mana cluster ini muncul
-        let mut next_stage_idx = 0;
-
-        for j in i + 1..stages_count {
-            let (next_cluster1_id, next_cluster2_id) = stages[j].clusters_combined;
-            if next_cluster1_id == cluster1_id || next_cluster2_id == cluster1_id {
-                next_stage_idx = j + 1; // +1 karena stages di-index dari 1
-                break;
-            }
-        }
+    // (direpresentasikan oleh cluster1_id) muncul dalam clusters_combined.
+    // Diproses dari belakang agar cukup satu kali lintasan.
+    let mut next_appearance: HashMap<usize, usize> = HashMap::new();
+    for i in (0..stages_count).rev() {
+        let (cluster1_id, cluster2_id) = stages[i].clusters_combined;
+
+        stages[i].next_stage = *next_appearance.get(&cluster1_id).unwrap_or(&0);
 
-        stages[i].next_stage = next_stage_idx;
+        next_appearance.insert(cluster1_id, i + 1); // +1 karena stages di-index dari 1
+        next_appearance.insert(cluster2_id, i + 1);
     }
 
     Ok(AgglomerationSchedule { stages })
@@ -185,7 +185,7 @@ fn compute_binary_distance(
     config: &ClusterConfig
 ) -> f64 {
     // Buat tabel kontingensi (a, b, c, d)
-    let (a, b, c, d) = compute_contingency_table(values1, values2, &config);
+    let (a, b, c, d) = compute_contingency_table(values1, values2, config);
 
     match method {
         BinaryMethod::BSEUCLID => b + c,
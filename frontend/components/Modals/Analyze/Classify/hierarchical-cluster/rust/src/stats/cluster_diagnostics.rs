@@ -0,0 +1,544 @@
+// File: cluster_diagnostics.rs
+use rand_mt::Mt64;
+
+use crate::models::{
+    config::ClusterConfig,
+    data::{ AnalysisData, DataRecord, DataValue },
+    result::{
+        AgglomerationStage,
+        ClusterDiagnostics,
+        ClusterJaccard,
+        ClusterSolutionDiagnostics,
+        ClusterStability,
+        CondensedDistanceMatrix,
+        InconsistencyEntry,
+    },
+};
+
+use super::core::{
+    generate_agglomeration_schedule,
+    generate_cluster_membership,
+    initialize_cluster_state,
+};
+
+// Fungsi utama untuk diagnostik pemotongan dendrogram
+pub fn generate_cluster_diagnostics(
+    data: &AnalysisData,
+    config: &ClusterConfig
+) -> Result<ClusterDiagnostics, String> {
+    let mut state = initialize_cluster_state(data, config)?;
+    let num_items = state.clusters.len();
+    if num_items < 3 {
+        return Err("At least three items are needed for cluster diagnostics".to_string());
+    }
+
+    // Simpan matriks jarak awal sebelum diubah oleh proses aglomerasi
+    let distances = state.distances.clone();
+    let schedule = generate_agglomeration_schedule(&mut state)?;
+    let stages = &schedule.stages;
+
+    let cophenetic = cophenetic_matrix(stages, num_items);
+    let cophenetic_correlation = upper_triangle_correlation(&distances, &cophenetic);
+    let inconsistency = inconsistency_coefficients(stages);
+
+    // Nilai variabel per kasus untuk indeks Calinski-Harabasz (hanya clustering kasus dengan
+    // variabel numerik; tidak berlaku untuk jarak Gower)
+    let features = if config.main.cluster_cases && !config.method.gower {
+        extract_case_features(data, config)
+    } else {
+        None
+    };
+
+    let min_cluster = (config.diagnostics.min_cluster.max(2) as usize).min(num_items - 1);
+    let max_cluster = (config.diagnostics.max_cluster.max(2) as usize).min(num_items - 1);
+    if min_cluster > max_cluster {
+        return Err(
+            format!(
+                "Invalid cluster range: minimum {} and maximum {}",
+                config.diagnostics.min_cluster,
+                config.diagnostics.max_cluster
+            )
+        );
+    }
+
+    let mut solutions = Vec::new();
+    for num_clusters in min_cluster..=max_cluster {
+        let assignments = renumber_clusters(
+            &generate_cluster_membership(stages, num_items, num_clusters)?
+        );
+
+        // Tahap ke-(n - k) membentuk k cluster; tahap berikutnya menggabungkan menjadi k - 1
+        let formed_stage = num_items - num_clusters;
+        let coefficient = stages[formed_stage - 1].coefficients;
+        let coefficient_change = stages
+            .get(formed_stage)
+            .map(|next| next.coefficients - coefficient);
+
+        solutions.push(ClusterSolutionDiagnostics {
+            num_clusters,
+            coefficient,
+            coefficient_change,
+            inconsistency: inconsistency.get(formed_stage).map(|entry| entry.inconsistency),
+            average_silhouette: average_silhouette(&distances, &assignments, num_clusters),
+            calinski_harabasz: features
+                .as_ref()
+                .and_then(|f| calinski_harabasz_index(f, &assignments, num_clusters)),
+        });
+    }
+
+    let stability = if config.diagnostics.stability {
+        Some(bootstrap_stability(data, config, stages, num_items, min_cluster, max_cluster))
+    } else {
+        None
+    };
+
+    let note = diagnostics_note(&solutions, cophenetic_correlation);
+
+    Ok(ClusterDiagnostics {
+        cophenetic_correlation,
+        solutions,
+        inconsistency,
+        stability,
+        note,
+    })
+}
+
+// Jarak cophenetic: koefisien tahap saat dua item pertama kali berada dalam cluster yang sama
+fn cophenetic_matrix(stages: &[AgglomerationStage], num_items: usize) -> CondensedDistanceMatrix {
+    let mut cophenetic = CondensedDistanceMatrix::new(num_items);
+    let mut members: Vec<Vec<usize>> = (0..num_items).map(|i| vec![i]).collect();
+
+    for stage in stages {
+        let (cluster1, cluster2) = stage.clusters_combined;
+        let removed = std::mem::take(&mut members[cluster2 - 1]);
+
+        for &a in &members[cluster1 - 1] {
+            for &b in &removed {
+                cophenetic.set(a, b, stage.coefficients);
+            }
+        }
+        members[cluster1 - 1].extend(removed);
+    }
+
+    cophenetic
+}
+
+// Korelasi Pearson antara elemen segitiga atas dua matriks
+fn upper_triangle_correlation(
+    a: &CondensedDistanceMatrix,
+    b: &CondensedDistanceMatrix
+) -> Option<f64> {
+    let pairs: Vec<(f64, f64)> = a.values
+        .iter()
+        .zip(b.values.iter())
+        .map(|(&x, &y)| (x, y))
+        .collect();
+    if pairs.len() < 2 {
+        return None;
+    }
+
+    let count = pairs.len() as f64;
+    let mean_a = pairs.iter().map(|p| p.0).sum::<f64>() / count;
+    let mean_b = pairs.iter().map(|p| p.1).sum::<f64>() / count;
+
+    let (mut cov, mut var_a, mut var_b) = (0.0, 0.0, 0.0);
+    for &(x, y) in &pairs {
+        cov += (x - mean_a) * (y - mean_b);
+        var_a += (x - mean_a).powi(2);
+        var_b += (y - mean_b).powi(2);
+    }
+
+    if var_a > 0.0 && var_b > 0.0 {
+        Some(cov / (var_a.sqrt() * var_b.sqrt()))
+    } else {
+        None
+    }
+}
+
+// Koefisien inkonsistensi (kedalaman 2): tinggi tahap dibandingkan dengan rata-rata dan
+// simpangan baku tinggi tahap itu sendiri serta tahap yang langsung membentuk cluster-nya
+fn inconsistency_coefficients(stages: &[AgglomerationStage]) -> Vec<InconsistencyEntry> {
+    stages
+        .iter()
+        .map(|stage| {
+            let mut heights = vec![stage.coefficients];
+            for child in [stage.cluster_first_appears.0, stage.cluster_first_appears.1] {
+                if child > 0 {
+                    heights.push(stages[child - 1].coefficients);
+                }
+            }
+
+            let links = heights.len();
+            let mean = heights.iter().sum::<f64>() / (links as f64);
+            let std_dev = if links > 1 {
+                (
+                    heights
+                        .iter()
+                        .map(|h| (h - mean).powi(2))
+                        .sum::<f64>() / ((links - 1) as f64)
+                ).sqrt()
+            } else {
+                0.0
+            };
+            let inconsistency = if std_dev > 0.0 {
+                (stage.coefficients - mean) / std_dev
+            } else {
+                0.0
+            };
+
+            InconsistencyEntry {
+                stage: stage.stage,
+                coefficient: stage.coefficients,
+                mean,
+                std_dev,
+                links,
+                inconsistency,
+            }
+        })
+        .collect()
+}
+
+// Nomori ulang cluster 0..k-1 sesuai urutan kemunculan anggota pertamanya
+fn renumber_clusters(assignments: &[usize]) -> Vec<usize> {
+    let mut mapping: Vec<Option<usize>> = vec![None; assignments.len().max(1)];
+    let mut next = 0;
+
+    assignments
+        .iter()
+        .map(|&id| {
+            if id >= mapping.len() {
+                mapping.resize(id + 1, None);
+            }
+            *mapping[id].get_or_insert_with(|| {
+                next += 1;
+                next - 1
+            })
+        })
+        .collect()
+}
+
+// Rata-rata silhouette dari matriks proximity: s(i) = (b - a) / max(a, b)
+fn average_silhouette(
+    distances: &CondensedDistanceMatrix,
+    assignments: &[usize],
+    num_clusters: usize
+) -> Option<f64> {
+    let mut sizes = vec![0usize; num_clusters];
+    for &c in assignments {
+        sizes[c] += 1;
+    }
+    if sizes.iter().filter(|&&size| size > 0).count() < 2 {
+        return None;
+    }
+
+    let total: f64 = (0..assignments.len())
+        .map(|i| {
+            let own = assignments[i];
+            // Item tunggal dalam cluster memiliki silhouette 0
+            if sizes[own] <= 1 {
+                return 0.0;
+            }
+
+            let mut sums = vec![0.0; num_clusters];
+            for j in 0..assignments.len() {
+                if i != j {
+                    sums[assignments[j]] += distances.get(i, j);
+                }
+            }
+
+            let a = sums[own] / ((sizes[own] - 1) as f64);
+            let b = (0..num_clusters)
+                .filter(|&c| c != own && sizes[c] > 0)
+                .map(|c| sums[c] / (sizes[c] as f64))
+                .fold(f64::INFINITY, f64::min);
+
+            let denominator = a.max(b);
+            if denominator > 0.0 && denominator.is_finite() {
+                (b - a) / denominator
+            } else {
+                0.0
+            }
+        })
+        .sum();
+
+    Some(total / (assignments.len() as f64))
+}
+
+// Calinski-Harabasz: (SSB / (k - 1)) / (SSW / (n - k))
+fn calinski_harabasz_index(
+    features: &[Vec<f64>],
+    assignments: &[usize],
+    num_clusters: usize
+) -> Option<f64> {
+    let n = features.len();
+    let num_vars = features.first().map_or(0, |f| f.len());
+    if n <= num_clusters || num_clusters < 2 || num_vars == 0 {
+        return None;
+    }
+
+    let mut centroids = vec![vec![0.0; num_vars]; num_clusters];
+    let mut sizes = vec![0usize; num_clusters];
+    let mut overall = vec![0.0; num_vars];
+    for (row, &c) in features.iter().zip(assignments.iter()) {
+        sizes[c] += 1;
+        for j in 0..num_vars {
+            centroids[c][j] += row[j];
+            overall[j] += row[j];
+        }
+    }
+    for c in 0..num_clusters {
+        if sizes[c] > 0 {
+            for value in centroids[c].iter_mut() {
+                *value /= sizes[c] as f64;
+            }
+        }
+    }
+    for value in overall.iter_mut() {
+        *value /= n as f64;
+    }
+
+    let within: f64 = features
+        .iter()
+        .zip(assignments.iter())
+        .map(|(row, &c)| {
+            row.iter()
+                .zip(centroids[c].iter())
+                .map(|(x, m)| (x - m).powi(2))
+                .sum::<f64>()
+        })
+        .sum();
+    let between: f64 = (0..num_clusters)
+        .map(|c| {
+            (sizes[c] as f64) *
+                centroids[c]
+                    .iter()
+                    .zip(overall.iter())
+                    .map(|(m, o)| (m - o).powi(2))
+                    .sum::<f64>()
+        })
+        .sum();
+
+    if within <= 0.0 {
+        return None;
+    }
+
+    Some(between / ((num_clusters - 1) as f64) / (within / ((n - num_clusters) as f64)))
+}
+
+// Nilai numerik setiap kasus; None jika ada kasus dengan nilai yang hilang
+fn extract_case_features(data: &AnalysisData, config: &ClusterConfig) -> Option<Vec<Vec<f64>>> {
+    let variables = config.main.variables.as_ref()?;
+    let case_count = data.cluster_data.first().map_or(0, |d| d.len());
+
+    (0..case_count)
+        .map(|case_idx| {
+            variables
+                .iter()
+                .map(|var| {
+                    data.cluster_data.iter().find_map(|dataset| {
+                        match dataset.get(case_idx).and_then(|record| record.values.get(var)) {
+                            Some(DataValue::Number(val)) => Some(*val),
+                            _ => None,
+                        }
+                    })
+                })
+                .collect::<Option<Vec<f64>>>()
+        })
+        .collect()
+}
+
+// Stabilitas cluster (Hennig, 2007): kasus di-resample dengan pengembalian, clustering
+// diulang, dan setiap cluster asli dibandingkan dengan cluster bootstrap yang paling mirip
+// menggunakan koefisien Jaccard atas kasus yang terambil dalam sampel
+fn bootstrap_stability(
+    data: &AnalysisData,
+    config: &ClusterConfig,
+    stages: &[AgglomerationStage],
+    num_items: usize,
+    min_cluster: usize,
+    max_cluster: usize
+) -> ClusterStability {
+    let requested_samples = config.diagnostics.bootstrap_samples.max(1) as usize;
+    let case_count = data.cluster_data.first().map_or(0, |d| d.len());
+    let mut rng = if config.diagnostics.seed {
+        Mt64::new(config.diagnostics.seed_value as u64)
+    } else {
+        Mt64::new(rand::random::<u64>())
+    };
+
+    let original: Vec<Vec<usize>> = (min_cluster..=max_cluster)
+        .map(|k| {
+            generate_cluster_membership(stages, num_items, k)
+                .map(|assignments| renumber_clusters(&assignments))
+                .unwrap_or_default()
+        })
+        .collect();
+
+    // Akumulasi (jumlah Jaccard, jumlah sampel, jumlah dissolved) per solusi per cluster
+    let mut totals: Vec<Vec<(f64, usize, usize)>> = (min_cluster..=max_cluster)
+        .map(|k| vec![(0.0, 0, 0); k])
+        .collect();
+    let mut valid_samples = 0;
+
+    for _ in 0..requested_samples {
+        let sample: Vec<usize> = (0..case_count)
+            .map(|_| (rng.next_u64() % (case_count as u64)) as usize)
+            .collect();
+        let resampled = resample_cases(data, &sample);
+
+        let boot_stages = match
+            initialize_cluster_state(&resampled, config).and_then(|mut state| {
+                generate_agglomeration_schedule(&mut state)
+            })
+        {
+            Ok(schedule) => schedule.stages,
+            Err(_) => {
+                continue;
+            }
+        };
+        valid_samples += 1;
+
+        // Item asli yang terwakili dalam sampel dan pemetaan posisi bootstrap ke item asli
+        let boot_items = boot_stages.len() + 1;
+        let (present, item_of): (Vec<bool>, Vec<usize>) = if config.main.cluster_cases {
+            let mut present = vec![false; num_items];
+            for &idx in &sample {
+                present[idx] = true;
+            }
+            (present, sample.clone())
+        } else {
+            (vec![true; num_items], (0..num_items).collect())
+        };
+
+        for (s, k) in (min_cluster..=max_cluster).enumerate() {
+            let Ok(boot_assignments) = generate_cluster_membership(&boot_stages, boot_items, k) else {
+                continue;
+            };
+            let boot_assignments = renumber_clusters(&boot_assignments);
+
+            let mut boot_clusters: Vec<Vec<bool>> = Vec::new();
+            for (pos, &c) in boot_assignments.iter().enumerate() {
+                if c >= boot_clusters.len() {
+                    boot_clusters.resize(c + 1, vec![false; num_items]);
+                }
+                boot_clusters[c][item_of[pos]] = true;
+            }
+
+            for (c, total) in totals[s].iter_mut().enumerate() {
+                let members: Vec<usize> = (0..num_items)
+                    .filter(|&i| original[s].get(i) == Some(&c) && present[i])
+                    .collect();
+                if members.is_empty() {
+                    continue;
+                }
+
+                let best = boot_clusters
+                    .iter()
+                    .map(|cluster| {
+                        let intersection = members
+                            .iter()
+                            .filter(|&&i| cluster[i])
+                            .count();
+                        let union = members.len() + cluster.iter().filter(|&&x| x).count() -
+                            intersection;
+                        (intersection as f64) / (union as f64)
+                    })
+                    .fold(0.0, f64::max);
+
+                total.0 += best;
+                total.1 += 1;
+                if best <= 0.5 {
+                    total.2 += 1;
+                }
+            }
+        }
+    }
+
+    let clusters = (min_cluster..=max_cluster)
+        .enumerate()
+        .flat_map(|(s, k)| {
+            let sizes: Vec<usize> = (0..k)
+                .map(|c| {
+                    original[s]
+                        .iter()
+                        .filter(|&&x| x == c)
+                        .count()
+                })
+                .collect();
+            totals[s]
+                .iter()
+                .enumerate()
+                .map(|(c, &(sum, count, dissolved))| ClusterJaccard {
+                    num_clusters: k,
+                    cluster: c + 1,
+                    size: sizes[c],
+                    mean_jaccard: if count > 0 { sum / (count as f64) } else { 0.0 },
+                    dissolved,
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect();
+
+    ClusterStability {
+        requested_samples,
+        valid_samples,
+        clusters,
+    }
+}
+
+// Susun ulang data sesuai indeks kasus sampel bootstrap
+fn resample_cases(data: &AnalysisData, sample: &[usize]) -> AnalysisData {
+    let pick = |datasets: &Vec<Vec<DataRecord>>| -> Vec<Vec<DataRecord>> {
+        datasets
+            .iter()
+            .map(|dataset| {
+                sample
+                    .iter()
+                    .filter_map(|&idx| dataset.get(idx).cloned())
+                    .collect()
+            })
+            .collect()
+    };
+
+    AnalysisData {
+        cluster_data: pick(&data.cluster_data),
+        label_data: pick(&data.label_data),
+        cluster_data_defs: data.cluster_data_defs.clone(),
+        label_data_defs: data.label_data_defs.clone(),
+    }
+}
+
+fn diagnostics_note(
+    solutions: &[ClusterSolutionDiagnostics],
+    cophenetic_correlation: Option<f64>
+) -> Option<String> {
+    let best = |index: &dyn Fn(&ClusterSolutionDiagnostics) -> Option<f64>| -> Option<usize> {
+        solutions
+            .iter()
+            .filter_map(|s| index(s).filter(|v| v.is_finite()).map(|v| (s.num_clusters, v)))
+            .max_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal))
+            .map(|(k, _)| k)
+    };
+
+    let mut parts = Vec::new();
+    if let Some(r) = cophenetic_correlation {
+        parts.push(format!("Cophenetic correlation: {:.3}.", r));
+    }
+    if let Some(k) = best(&|s| s.coefficient_change) {
+        parts.push(
+            format!("The largest increase in the agglomeration coefficient occurs when {} clusters are merged further.", k)
+        );
+    }
+    if let Some(k) = best(&|s| s.average_silhouette) {
+        parts.push(format!("The largest average silhouette is at {} clusters.", k));
+    }
+    if let Some(k) = best(&|s| s.calinski_harabasz) {
+        parts.push(format!("The largest Calinski-Harabasz index is at {} clusters.", k));
+    }
+
+    if parts.is_empty() {
+        None
+    } else {
+        Some(parts.join(" "))
+    }
+}
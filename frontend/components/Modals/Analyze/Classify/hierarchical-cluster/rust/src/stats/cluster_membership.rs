@@ -76,9 +76,9 @@ pub fn generate_cluster_membership(
         let (cluster1, cluster2) = stage.clusters_combined;
 
         // Gabungkan cluster dengan menetapkan semua kasus dari cluster2 ke cluster1
-        for case_id in 0..case_cluster_ids.len() {
-            if case_cluster_ids[case_id] == cluster2 - 1 {
-                case_cluster_ids[case_id] = cluster1 - 1;
+        for cluster_id in case_cluster_ids.iter_mut() {
+            if *cluster_id == cluster2 - 1 {
+                *cluster_id = cluster1 - 1;
             }
         }
     }
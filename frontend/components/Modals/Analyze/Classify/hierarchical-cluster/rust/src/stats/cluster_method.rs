@@ -1,21 +1,44 @@
 // File: cluster_method.rs
+use std::{ cmp::Ordering, collections::{ BTreeSet, HashMap, HashSet } };
+
 use crate::models::{ config::ClusMethod, result::ClusterState };
-use nalgebra::DMatrix;
 
-// Menemukan dua cluster terdekat dan mengembalikan indeksnya serta jarak
-pub fn find_closest_clusters(state: &ClusterState) -> Option<(usize, usize, f64)> {
-    let n_clusters = state.clusters.len();
-    if n_clusters < 2 {
-        return None;
+// Satu langkah penggabungan: (slot cluster yang dipertahankan, slot cluster yang dihapus, jarak).
+// Slot cluster selalu sama dengan indeks anggota terkecilnya, sehingga slot + 1 adalah
+// ID cluster yang dipakai pada jadwal aglomerasi.
+pub type MergeStep = (usize, usize, f64);
+
+// Menghasilkan seluruh langkah penggabungan dalam urutan yang sama dengan pencarian
+// menyeluruh pada matriks jarak (jarak terkecil lebih dulu)
+pub fn agglomerate_clusters(state: &mut ClusterState) -> Result<Vec<MergeStep>, String> {
+    // NN-chain dan SLINK mengandalkan perbandingan jarak yang valid,
+    // sehingga jarak NaN/tak hingga tetap diproses dengan pencarian menyeluruh
+    if state.distances.values.iter().any(|d| !d.is_finite()) {
+        return exhaustive_merges(state);
+    }
+
+    match state.method {
+        ClusMethod::SingleLinkage => Ok(single_linkage_merges(state)),
+        // Metode yang memenuhi sifat reducibility dapat memakai nearest-neighbor chain
+        ClusMethod::AverageBetweenGroups |
+        ClusMethod::AverageWithinGroups |
+        ClusMethod::CompleteLinkage |
+        ClusMethod::Ward => nearest_neighbor_chain_merges(state),
+        // Centroid dan median tidak reducible (tinggi dapat turun), gunakan pencarian menyeluruh
+        ClusMethod::Centroid | ClusMethod::Median => exhaustive_merges(state),
     }
+}
+
+// Menemukan dua cluster aktif terdekat dan mengembalikan slot serta jaraknya
+pub fn find_closest_clusters(state: &ClusterState, active: &[bool]) -> Option<(usize, usize, f64)> {
+    let n_slots = state.distances.size;
 
-    // Cari nilai minimum dan indeksnya secara langsung tanpa konversi ke DMatrix
     let mut min_distance = f64::MAX;
     let mut closest_pair = None;
 
-    for i in 0..n_clusters {
-        for j in i + 1..n_clusters {
-            let distance = state.distances[i][j];
+    for i in (0..n_slots).filter(|&i| active[i]) {
+        for j in (i + 1..n_slots).filter(|&j| active[j]) {
+            let distance = state.distances.get(i, j);
             if distance < min_distance {
                 min_distance = distance;
                 closest_pair = Some((i, j, distance));
@@ -26,9 +49,6 @@ pub fn find_closest_clusters(state: &ClusterState) -> Option<(usize, usize, f64)
     closest_pair
 }
 
-// Menggabungkan cluster dan memperbarui cluster state
-// SUDAH DIGANTI dengan implementasi yang lebih baik di agglomeration.rs
-
 // Menghitung jarak baru antara cluster yang digabung dan cluster lainnya
 pub fn calculate_new_distance(
     method: &ClusMethod,
@@ -83,97 +103,317 @@ pub fn calculate_new_distance(
     }
 }
 
-// Menggabungkan cluster dan memperbarui distances dengan implementasi yang disederhanakan
+// Memperbarui jarak cluster gabungan (disimpan di slot keep_idx) ke semua cluster aktif lain.
+// cluster_sizes berisi ukuran cluster sebelum penggabungan (N_p, N_q, N_r pada formula).
 pub fn merge_clusters(
     state: &mut ClusterState,
     keep_idx: usize,
     remove_idx: usize,
-    cluster_sizes: &[usize]
+    cluster_sizes: &[usize],
+    active: &[bool]
 ) {
-    // Validasi indeks
-    if keep_idx >= state.clusters.len() || remove_idx >= state.clusters.len() {
-        // Tangani kesalahan dengan aman, kembalikan tanpa mengubah state
-        return;
+    let d_keep_remove = state.distances.get(keep_idx, remove_idx);
+
+    for other_idx in 0..state.distances.size {
+        if !active[other_idx] || other_idx == keep_idx || other_idx == remove_idx {
+            continue;
+        }
+
+        let new_distance = calculate_new_distance(
+            &state.method,
+            cluster_sizes[keep_idx],
+            cluster_sizes[remove_idx],
+            cluster_sizes[other_idx],
+            state.distances.get(keep_idx, other_idx),
+            state.distances.get(remove_idx, other_idx),
+            d_keep_remove
+        );
+        state.distances.set(keep_idx, other_idx, new_distance);
     }
+}
 
-    // 1. Gabungkan elemen cluster
-    let mut merged_cluster = state.clusters[keep_idx].clone();
-    merged_cluster.extend(state.clusters[remove_idx].clone());
-    state.clusters[keep_idx] = merged_cluster;
+// Pencarian menyeluruh: setiap tahap memindai semua pasangan cluster aktif (O(n^3))
+fn exhaustive_merges(state: &mut ClusterState) -> Result<Vec<MergeStep>, String> {
+    let n_slots = state.distances.size;
+    let mut active = vec![true; n_slots];
+    let mut cluster_sizes: Vec<usize> = state.clusters
+        .iter()
+        .map(|c| c.len())
+        .collect();
+    let mut merges = Vec::with_capacity(n_slots.saturating_sub(1));
 
-    // 2. Pertahankan matriks jarak asli sebelum dimodifikasi
-    let original_distances = state.distances.clone();
-    let n_clusters = state.clusters.len();
+    for stage_idx in 0..n_slots.saturating_sub(1) {
+        let (keep_idx, remove_idx, distance) = find_closest_clusters(state, &active).ok_or_else(||
+            format!("Failed to find closest clusters at stage {}", stage_idx)
+        )?;
 
-    // 3. Hapus cluster yang sudah digabung
-    state.clusters.remove(remove_idx);
+        merge_clusters(state, keep_idx, remove_idx, &cluster_sizes, &active);
+        cluster_sizes[keep_idx] += cluster_sizes[remove_idx];
+        active[remove_idx] = false;
 
-    // 4. Buat matriks jarak baru dengan dimensi yang tepat
-    let new_n_clusters = n_clusters - 1;
-    let mut new_distances = vec![vec![0.0; new_n_clusters]; new_n_clusters];
+        merges.push((keep_idx, remove_idx, distance));
+    }
 
-    // 5. Isi matriks jarak baru
-    for i in 0..new_n_clusters {
-        for j in 0..i {
-            // Hanya isi setengah matriks, kemudian copy untuk simetri
-            if i == j {
-                new_distances[i][j] = 0.0;
-                continue;
+    Ok(merges)
+}
+
+// Kunci urutan pasangan cluster: (jarak, slot kecil, slot besar). Urutan leksikografis ini
+// sama dengan urutan pemindaian pada pencarian menyeluruh, sehingga jarak yang sama
+// diselesaikan dengan cara yang sama.
+type PairKey = (f64, usize, usize);
+
+fn pair_key(distance: f64, a: usize, b: usize) -> PairKey {
+    (distance, a.min(b), a.max(b))
+}
+
+fn compare_pair_keys(a: &PairKey, b: &PairKey) -> Ordering {
+    a.0
+        .partial_cmp(&b.0)
+        .unwrap_or(Ordering::Equal)
+        .then(a.1.cmp(&b.1))
+        .then(a.2.cmp(&b.2))
+}
+
+// Nearest-neighbor chain (O(n^2)) untuk metode reducible. Rantai diperpanjang ke tetangga
+// terdekat sampai dua cluster terakhir saling menjadi tetangga terdekat, lalu keduanya digabung.
+// Penggabungan ditemukan tidak berurutan sehingga diurutkan kembali berdasarkan kunci pasangan.
+fn nearest_neighbor_chain_merges(state: &mut ClusterState) -> Result<Vec<MergeStep>, String> {
+    let n_slots = state.distances.size;
+    let mut active = vec![true; n_slots];
+    let mut cluster_sizes: Vec<usize> = state.clusters
+        .iter()
+        .map(|c| c.len())
+        .collect();
+    let mut chain: Vec<usize> = Vec::with_capacity(n_slots);
+
+    // (kunci urutan, langkah penggabungan)
+    let mut merges: Vec<(PairKey, MergeStep)> = Vec::with_capacity(n_slots.saturating_sub(1));
+    // Kunci urutan tahap yang terakhir membentuk cluster pada setiap slot
+    let mut formed_at: Vec<PairKey> = vec![(f64::NEG_INFINITY, 0, 0); n_slots];
+
+    while merges.len() + 1 < n_slots {
+        if chain.is_empty() {
+            if let Some(first) = (0..n_slots).find(|&i| active[i]) {
+                chain.push(first);
+            }
+        }
+
+        let (current, previous) = loop {
+            let current = chain[chain.len() - 1];
+            let previous = if chain.len() >= 2 { Some(chain[chain.len() - 2]) } else { None };
+
+            let mut nearest: Option<(usize, PairKey)> = None;
+            for other_idx in (0..n_slots).filter(|&k| active[k] && k != current) {
+                let key = pair_key(state.distances.get(current, other_idx), current, other_idx);
+                let is_closer = nearest
+                    .as_ref()
+                    .is_none_or(|(_, best)| compare_pair_keys(&key, best) == Ordering::Less);
+                if is_closer {
+                    nearest = Some((other_idx, key));
+                }
+            }
+
+            let (nearest, _) = nearest.ok_or_else(|| {
+                format!("Failed to find nearest cluster for cluster {}", current + 1)
+            })?;
+            if Some(nearest) == previous {
+                break (current, nearest);
+            }
+            chain.push(nearest);
+        };
+        chain.truncate(chain.len() - 2);
+
+        let keep_idx = current.min(previous);
+        let remove_idx = current.max(previous);
+        let distance = state.distances.get(keep_idx, remove_idx);
+
+        merge_clusters(state, keep_idx, remove_idx, &cluster_sizes, &active);
+        cluster_sizes[keep_idx] += cluster_sizes[remove_idx];
+        active[remove_idx] = false;
+
+        // Kunci urutan tidak boleh lebih kecil dari tahap pembentuk kedua cluster,
+        // agar pembulatan floating point tidak menempatkan induk sebelum anaknya
+        let mut order_key = pair_key(distance, keep_idx, remove_idx);
+        for formed in [formed_at[keep_idx], formed_at[remove_idx]] {
+            if compare_pair_keys(&formed, &order_key) == Ordering::Greater {
+                order_key = formed;
             }
+        }
+        formed_at[keep_idx] = order_key;
+
+        merges.push((order_key, (keep_idx, remove_idx, distance)));
+    }
+
+    // Pengurutan stabil: kunci yang sama mempertahankan urutan penemuan (anak sebelum induk)
+    merges.sort_by(|a, b| compare_pair_keys(&a.0, &b.0));
+
+    Ok(
+        merges
+            .into_iter()
+            .map(|(_, step)| step)
+            .collect()
+    )
+}
+
+// SLINK (Sibson, 1973): single linkage dalam O(n^2) waktu dan O(n) memori tambahan,
+// langsung dari jarak awal. Hasilnya berupa pointer representation yang kemudian
+// diubah menjadi langkah penggabungan.
+fn single_linkage_merges(state: &ClusterState) -> Vec<MergeStep> {
+    let n_items = state.distances.size;
+    let mut pointer: Vec<usize> = (0..n_items).collect();
+    let mut height = vec![f64::INFINITY; n_items];
+    let mut row = vec![0.0; n_items];
+
+    for i in 0..n_items {
+        for (j, value) in row.iter_mut().enumerate().take(i) {
+            *value = state.distances.get(i, j);
+        }
 
-            // Hitung jarak baru jika i atau j adalah cluster gabungan
-            if i == keep_idx || j == keep_idx {
-                let other_idx = if i == keep_idx { j } else { i };
-
-                // Transformasikan indeks other_idx ke indeks asli
-                let orig_other_idx = if other_idx < remove_idx { other_idx } else { other_idx + 1 };
-
-                // Ambil jarak yang diperlukan dari matriks asli
-                let d_keep_other = original_distances[keep_idx][orig_other_idx];
-                let d_remove_other = original_distances[remove_idx][orig_other_idx];
-                let d_keep_remove = original_distances[keep_idx][remove_idx];
-
-                // Dapatkan ukuran cluster dengan aman
-                let n_keep = if keep_idx < cluster_sizes.len() {
-                    cluster_sizes[keep_idx]
-                } else {
-                    1
-                };
-                let n_remove = if remove_idx < cluster_sizes.len() {
-                    cluster_sizes[remove_idx]
-                } else {
-                    1
-                };
-                let n_other = if orig_other_idx < cluster_sizes.len() {
-                    cluster_sizes[orig_other_idx]
-                } else {
-                    1
-                };
-
-                // Hitung jarak baru
-                new_distances[i][j] = calculate_new_distance(
-                    &state.method,
-                    n_keep,
-                    n_remove,
-                    n_other,
-                    d_keep_other,
-                    d_remove_other,
-                    d_keep_remove
-                );
+        for j in 0..i {
+            let p = pointer[j];
+            if height[j] >= row[j] {
+                row[p] = row[p].min(height[j]);
+                height[j] = row[j];
+                pointer[j] = i;
             } else {
-                // Untuk cluster yang tidak berubah, salin jarak dari matriks asli
-                let orig_i = if i < remove_idx { i } else { i + 1 };
-                let orig_j = if j < remove_idx { j } else { j + 1 };
-                new_distances[i][j] = original_distances[orig_i][orig_j];
+                row[p] = row[p].min(row[j]);
             }
+        }
 
-            // Salin untuk menjaga simetri
-            new_distances[j][i] = new_distances[i][j];
+        for j in 0..i {
+            if height[j] >= height[pointer[j]] {
+                pointer[j] = i;
+            }
         }
-        // Diagonal selalu 0
-        new_distances[i][i] = 0.0;
     }
 
-    // 6. Perbarui matriks jarak state
-    state.distances = new_distances;
+    // Item terakhir tidak memiliki pointer; sisanya diurutkan berdasarkan tinggi
+    let mut order: Vec<usize> = (0..n_items).filter(|&j| pointer[j] != j).collect();
+    order.sort_by(|&a, &b| height[a].partial_cmp(&height[b]).unwrap_or(Ordering::Equal));
+
+    // Union-find dengan akar = anggota terkecil, sesuai penomoran slot cluster
+    let mut parent: Vec<usize> = (0..n_items).collect();
+    let mut members: Vec<Vec<usize>> = (0..n_items).map(|i| vec![i]).collect();
+    let mut merges = Vec::with_capacity(n_items.saturating_sub(1));
+
+    // Tinggi single linkage adalah jarak awal (tanpa pembulatan), sehingga penggabungan
+    // dengan tinggi yang sama dapat dikelompokkan dengan perbandingan langsung
+    let mut start = 0;
+    while start < order.len() {
+        let level = height[order[start]];
+        let end = start + order[start..].iter().take_while(|&&j| height[j] == level).count();
+
+        let links: Vec<(usize, usize)> = order[start..end]
+            .iter()
+            .map(|&j| (find_root(&mut parent, j), find_root(&mut parent, pointer[j])))
+            .collect();
+        merges.extend(merge_single_linkage_level(state, &links, level, &mut parent, &mut members));
+
+        start = end;
+    }
+
+    merges
+}
+
+// Menggabungkan semua cluster yang berjarak tepat `level`. Pencarian menyeluruh memilih
+// pasangan slot terkecil lebih dulu, sehingga urutan tersebut direproduksi dari pasangan
+// cluster yang benar-benar memiliki dua item berjarak `level`.
+fn merge_single_linkage_level(
+    state: &ClusterState,
+    links: &[(usize, usize)],
+    level: f64,
+    parent: &mut [usize],
+    members: &mut [Vec<usize>]
+) -> Vec<MergeStep> {
+    // Komponen cluster yang akan tergabung pada tingkat ini
+    let mut link_graph: HashMap<usize, Vec<usize>> = HashMap::new();
+    for &(a, b) in links {
+        link_graph.entry(a).or_default().push(b);
+        link_graph.entry(b).or_default().push(a);
+    }
+
+    // Pasangan cluster yang berjarak tepat `level` (hanya dalam komponen yang sama)
+    let mut pairs: BTreeSet<(usize, usize)> = BTreeSet::new();
+    let mut neighbors: HashMap<usize, BTreeSet<usize>> = HashMap::new();
+    let mut visited: HashSet<usize> = HashSet::new();
+    let mut roots: Vec<usize> = link_graph.keys().copied().collect();
+    roots.sort_unstable();
+
+    for &root in &roots {
+        if !visited.insert(root) {
+            continue;
+        }
+
+        let mut component = vec![root];
+        let mut idx = 0;
+        while idx < component.len() {
+            for &next in &link_graph[&component[idx]] {
+                if visited.insert(next) {
+                    component.push(next);
+                }
+            }
+            idx += 1;
+        }
+
+        for (pos, &a) in component.iter().enumerate() {
+            for &b in &component[pos + 1..] {
+                let touching = members[a]
+                    .iter()
+                    .any(|&x| members[b].iter().any(|&y| state.distances.get(x, y) == level));
+                if touching {
+                    pairs.insert((a.min(b), a.max(b)));
+                    neighbors.entry(a).or_default().insert(b);
+                    neighbors.entry(b).or_default().insert(a);
+                }
+            }
+        }
+    }
+
+    let mut merges = Vec::with_capacity(links.len());
+    while let Some((keep_idx, remove_idx)) = pairs.pop_first() {
+        merges.push((keep_idx, remove_idx, level));
+
+        // Tetangga cluster yang dihapus berpindah ke cluster gabungan
+        for other_idx in neighbors.remove(&remove_idx).unwrap_or_default() {
+            if other_idx == keep_idx {
+                continue;
+            }
+            pairs.remove(&(other_idx.min(remove_idx), other_idx.max(remove_idx)));
+            pairs.insert((other_idx.min(keep_idx), other_idx.max(keep_idx)));
+            if let Some(other_neighbors) = neighbors.get_mut(&other_idx) {
+                other_neighbors.remove(&remove_idx);
+                other_neighbors.insert(keep_idx);
+            }
+            neighbors.entry(keep_idx).or_default().insert(other_idx);
+        }
+        if let Some(keep_neighbors) = neighbors.get_mut(&keep_idx) {
+            keep_neighbors.remove(&remove_idx);
+        }
+
+        parent[remove_idx] = keep_idx;
+        let mut moved = std::mem::take(&mut members[remove_idx]);
+        if moved.len() > members[keep_idx].len() {
+            std::mem::swap(&mut moved, &mut members[keep_idx]);
+        }
+        members[keep_idx].extend(moved);
+    }
+
+    merges
+}
+
+fn find_root(parent: &mut [usize], item: usize) -> usize {
+    let mut root = item;
+    while parent[root] != root {
+        root = parent[root];
+    }
+
+    // Kompresi jalur
+    let mut current = item;
+    while parent[current] != root {
+        let next = parent[current];
+        parent[current] = root;
+        current = next;
+    }
+
+    root
 }
@@ -1,9 +1,11 @@
 pub use crate::stats::agglomeration::*;
 pub use crate::stats::calculate_distance::*;
+pub use crate::stats::cluster_diagnostics::*;
 pub use crate::stats::common::*;
 pub use crate::stats::cluster_membership::*;
 pub use crate::stats::cluster_method::*;
 pub use crate::stats::dendrogram::*;
+pub use crate::stats::gower_distance::*;
 pub use crate::stats::icicle_plot::*;
 pub use crate::stats::process_case::*;
 pub use crate::stats::proximity_matrix::*;
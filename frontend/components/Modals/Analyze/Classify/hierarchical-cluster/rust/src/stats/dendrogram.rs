@@ -2,7 +2,7 @@ use std::collections::HashMap;
 use crate::models::{
     config::ClusterConfig,
     data::AnalysisData,
-    result::{ AgglomerationSchedule, Dendrogram, DendrogramNode },
+    result::{ Dendrogram, DendrogramNode },
 };
 
 use super::core::{ extract_case_label, generate_agglomeration_schedule_wrapper };
@@ -18,17 +18,14 @@ pub fn generate_dendrogram(
     // Get all case labels
     let case_labels = if config.main.cluster_cases {
         let case_count = data.cluster_data
-            .get(0)
+            .first()
             .map(|d| d.len())
             .unwrap_or(0);
 
         (0..case_count).map(|idx| extract_case_label(data, config, idx)).collect()
     } else {
         // For variable clustering, use variable names as labels
-        config.main.variables
-            .as_ref()
-            .map(|vars| vars.clone())
-            .unwrap_or_else(|| vec![])
+        config.main.variables.clone().unwrap_or_default()
     };
 
     let num_items = case_labels.len();
@@ -212,7 +209,6 @@ pub fn extract_dendrogram_visualization_data(
     let mut visualization_data = Vec::new();
     extract_node_visualization_data(
         &dendrogram.root,
-        0.0,
         dendrogram.max_height,
         &mut visualization_data
     );
@@ -222,7 +218,6 @@ pub fn extract_dendrogram_visualization_data(
 // Helper function to extract node data for visualization
 fn extract_node_visualization_data(
     node: &DendrogramNode,
-    parent_height: f64,
     max_height: f64,
     data: &mut Vec<(String, usize, f64, f64, f64)>
 ) {
@@ -282,10 +277,10 @@ fn extract_node_visualization_data(
 
     // Recursively process children
     if let Some(left) = &node.left {
-        extract_node_visualization_data(left, normalized_height, max_height, data);
+        extract_node_visualization_data(left, max_height, data);
     }
 
     if let Some(right) = &node.right {
-        extract_node_visualization_data(right, normalized_height, max_height, data);
+        extract_node_visualization_data(right, max_height, data);
     }
 }
@@ -0,0 +1,185 @@
+// File: gower_distance.rs
+use std::{ cmp::Ordering, collections::HashMap };
+
+use crate::models::data::{ AnalysisData, DataValue, VariableDefinition, VariableMeasure };
+
+// Cara sebuah variabel dibandingkan pada jarak Gower
+#[derive(Debug, Clone)]
+pub enum GowerScale {
+    // |x_i - x_j| / rentang
+    Scale {
+        range: f64,
+    },
+    // Nilai diganti peringkat level (r - 1) / (M - 1), lalu diperlakukan seperti skala
+    Ordinal {
+        levels: Vec<f64>,
+    },
+    // 0 jika kategori sama, 1 jika berbeda
+    Nominal,
+}
+
+#[derive(Debug, Clone)]
+pub struct GowerVariable {
+    pub name: String,
+    pub scale: GowerScale,
+    pub missing: Vec<DataValue>,
+}
+
+// Menyiapkan rentang (skala) dan level (ordinal) setiap variabel dari seluruh kasus
+pub fn prepare_gower_variables(
+    data: &AnalysisData,
+    variables: &[String]
+) -> Result<Vec<GowerVariable>, String> {
+    if variables.is_empty() {
+        return Err("No variables specified for Gower distance".to_string());
+    }
+
+    let definitions: HashMap<&str, &VariableDefinition> = data.cluster_data_defs
+        .iter()
+        .flatten()
+        .map(|def| (def.name.as_str(), def))
+        .collect();
+
+    variables
+        .iter()
+        .map(|var| {
+            let definition = definitions.get(var.as_str());
+            let missing = definition.map_or_else(Vec::new, |def| def.missing.clone());
+
+            // Nilai yang teramati (bukan missing) untuk variabel ini
+            let observed: Vec<&DataValue> = data.cluster_data
+                .iter()
+                .flatten()
+                .filter_map(|record| record.values.get(var))
+                .filter(|value| !is_missing(value, &missing))
+                .collect();
+            let numbers: Vec<f64> = observed
+                .iter()
+                .filter_map(|value| if let DataValue::Number(v) = value { Some(*v) } else { None })
+                .collect();
+
+            // Measure yang tidak diketahui: numerik dianggap skala, selain itu nominal
+            let measure = match definition.map(|def| def.measure.clone()) {
+                Some(VariableMeasure::Unknown) | None => {
+                    if !observed.is_empty() && numbers.len() == observed.len() {
+                        VariableMeasure::Scale
+                    } else {
+                        VariableMeasure::Nominal
+                    }
+                }
+                Some(measure) => measure,
+            };
+
+            let scale = match measure {
+                VariableMeasure::Scale => {
+                    let min = numbers.iter().copied().fold(f64::INFINITY, f64::min);
+                    let max = numbers.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+                    GowerScale::Scale {
+                        range: if numbers.is_empty() { 0.0 } else { max - min },
+                    }
+                }
+                VariableMeasure::Ordinal => {
+                    let mut levels = numbers;
+                    levels.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+                    levels.dedup();
+                    GowerScale::Ordinal { levels }
+                }
+                _ => GowerScale::Nominal,
+            };
+
+            Ok(GowerVariable {
+                name: var.clone(),
+                scale,
+                missing,
+            })
+        })
+        .collect()
+}
+
+// Jarak Gower: rata-rata kontribusi variabel yang teramati pada kedua kasus (pairwise).
+// Menghasilkan NaN jika tidak ada variabel yang teramati bersama.
+pub fn gower_distance(
+    case1: &HashMap<String, DataValue>,
+    case2: &HashMap<String, DataValue>,
+    gower_variables: &[GowerVariable]
+) -> f64 {
+    let mut total = 0.0;
+    let mut weight = 0.0;
+
+    for variable in gower_variables {
+        let (value1, value2) = match (case1.get(&variable.name), case2.get(&variable.name)) {
+            (Some(v1), Some(v2)) if
+                !is_missing(v1, &variable.missing) &&
+                !is_missing(v2, &variable.missing)
+            => (v1, v2),
+            _ => {
+                continue;
+            }
+        };
+
+        let contribution = match &variable.scale {
+            GowerScale::Scale { range } =>
+                match (value1, value2) {
+                    (DataValue::Number(x), DataValue::Number(y)) => {
+                        if *range > 0.0 { (x - y).abs() / range } else { 0.0 }
+                    }
+                    _ => {
+                        continue;
+                    }
+                }
+            GowerScale::Ordinal { levels } => {
+                let (rank1, rank2) = match (ordinal_rank(value1, levels), ordinal_rank(value2, levels)) {
+                    (Some(r1), Some(r2)) => (r1, r2),
+                    _ => {
+                        continue;
+                    }
+                };
+                if levels.len() > 1 {
+                    ((rank1 as f64) - (rank2 as f64)).abs() / ((levels.len() - 1) as f64)
+                } else {
+                    0.0
+                }
+            }
+            GowerScale::Nominal => if values_equal(value1, value2) { 0.0 } else { 1.0 }
+        };
+
+        total += contribution;
+        weight += 1.0;
+    }
+
+    if weight > 0.0 {
+        total / weight
+    } else {
+        f64::NAN
+    }
+}
+
+// Posisi nilai ordinal di antara level yang teramati
+fn ordinal_rank(value: &DataValue, levels: &[f64]) -> Option<usize> {
+    match value {
+        DataValue::Number(v) =>
+            levels
+                .binary_search_by(|level| level.partial_cmp(v).unwrap_or(Ordering::Equal))
+                .ok(),
+        _ => None,
+    }
+}
+
+// Nilai kosong, teks kosong, atau nilai yang didefinisikan sebagai missing
+fn is_missing(value: &DataValue, missing: &[DataValue]) -> bool {
+    match value {
+        DataValue::Null => true,
+        DataValue::Text(text) if text.trim().is_empty() => true,
+        _ => missing.iter().any(|m| values_equal(value, m)),
+    }
+}
+
+fn values_equal(a: &DataValue, b: &DataValue) -> bool {
+    match (a, b) {
+        (DataValue::Number(x), DataValue::Number(y)) => x == y,
+        (DataValue::Text(x), DataValue::Text(y)) => x.trim() == y.trim(),
+        (DataValue::Boolean(x), DataValue::Boolean(y)) => x == y,
+        (DataValue::Null, DataValue::Null) => true,
+        _ => false,
+    }
+}
@@ -8,7 +8,6 @@ use crate::models::{
 use super::core::{
     generate_agglomeration_schedule_wrapper,
     generate_cluster_membership,
-    generate_dendrogram,
     extract_case_label,
 };
 
@@ -22,16 +21,13 @@ pub fn generate_icicle_plot(
 
     // Get the number of cases
     let num_cases = data.cluster_data
-        .get(0)
+        .first()
         .map(|d| d.len())
         .unwrap_or(0);
 
     // Get agglomeration schedule
     let agglomeration = generate_agglomeration_schedule_wrapper(data, config)?;
 
-    // Generate the dendrogram to get case information
-    let dendrogram = generate_dendrogram(data, config)?;
-
     // Get the original case labels using extract_case_label
     let case_labels: Vec<String> = (0..num_cases)
         .map(|case_idx| extract_case_label(data, config, case_idx))
@@ -43,9 +39,7 @@ pub fn generate_icicle_plot(
 
     // Start by analyzing from 1 cluster up to num_cases clusters
     // For 1 cluster, all cases are in the same cluster
-    for i in 0..num_cases {
-        case_in_cluster[1][i] = true;
-    }
+    case_in_cluster[1].fill(true);
 
     // Process each level from 2 to num_cases clusters
     for cluster_count in 2..=num_cases {
@@ -58,9 +52,7 @@ pub fn generate_icicle_plot(
         }
 
         // Copy the case memberships from the previous level (fewer clusters)
-        for i in 0..num_cases {
-            case_in_cluster[cluster_count][i] = case_in_cluster[cluster_count - 1][i];
-        }
+        case_in_cluster[cluster_count] = case_in_cluster[cluster_count - 1].clone();
 
         // Use generate_cluster_membership to get memberships for this cluster count
         let memberships = generate_cluster_membership(
@@ -73,12 +65,12 @@ pub fn generate_icicle_plot(
         let mut clusters: HashMap<usize, Vec<usize>> = HashMap::new();
         for (i, &cluster_id) in memberships.iter().enumerate() {
             if i < num_cases {
-                clusters.entry(cluster_id).or_insert_with(Vec::new).push(i);
+                clusters.entry(cluster_id).or_default().push(i);
             }
         }
 
         // Mark cases that are in clusters with multiple members
-        for (_, cases) in &clusters {
+        for cases in clusters.values() {
             if cases.len() > 1 {
                 for &case_idx in cases {
                     case_in_cluster[cluster_count][case_idx] = true;
@@ -111,8 +103,8 @@ pub fn generate_icicle_plot(
             let mut min_clusters = num_cases + 1;
             let mut max_clusters = 0;
 
-            for cluster_count in 1..=num_cases {
-                if case_in_cluster[cluster_count][case_idx] {
+            for (cluster_count, in_cluster) in case_in_cluster.iter().enumerate().skip(1) {
+                if in_cluster[case_idx] {
                     min_clusters = min_clusters.min(cluster_count);
                     max_clusters = max_clusters.max(cluster_count);
                 }
@@ -154,7 +146,7 @@ fn find_highest_case_number(case_labels: &[String]) -> usize {
 
     for (idx, label) in case_labels.iter().enumerate() {
         if label.starts_with("Case ") {
-            let parts: Vec<&str> = label.split(|c| (c == ' ' || c == ':')).collect();
+            let parts: Vec<&str> = label.split([' ', ':']).collect();
             if parts.len() >= 2 {
                 if let Ok(case_num) = parts[1].parse::<usize>() {
                     if case_num > highest_num {
@@ -183,10 +175,7 @@ fn order_cases_by_agglomeration(
     processed_cases.insert(highest_case);
 
     // Initialize case-to-cluster mapping (each case starts in its own cluster)
-    let mut case_to_cluster = vec![0; num_cases];
-    for i in 0..num_cases {
-        case_to_cluster[i] = i + 1; // 1-indexed clusters
-    }
+    let mut case_to_cluster: Vec<usize> = (1..=num_cases).collect(); // 1-indexed clusters
 
     // Process each agglomeration stage in reverse order (from num_cases-1 clusters down to 1)
     for stage_idx in (0..agglomeration.stages.len()).rev() {
@@ -208,9 +197,9 @@ fn order_cases_by_agglomeration(
         }
 
         // Update cluster assignments for next iteration
-        for i in 0..num_cases {
-            if case_to_cluster[i] == cluster2 {
-                case_to_cluster[i] = cluster1;
+        for cluster in case_to_cluster.iter_mut() {
+            if *cluster == cluster2 {
+                *cluster = cluster1;
             }
         }
     }
@@ -233,7 +222,7 @@ fn determine_cluster_range(config: &ClusterConfig, num_cases: usize) -> (i32, i3
     } else if config.plots.range_clusters {
         // Use configured range
         let start = config.plots.start_cluster;
-        let stop = config.plots.stop_cluster.unwrap_or_else(|| (num_cases as i32) / 2);
+        let stop = config.plots.stop_cluster.unwrap_or((num_cases as i32) / 2);
         let step = config.plots.step_by_cluster;
         (start, stop, step)
     } else {
@@ -64,7 +64,7 @@ fn process_case_clustering(
                         case_idx < dataset.len() &&
                             dataset[case_idx].values
                                 .get(var)
-                                .map_or(false, |value| {
+                                .is_some_and(|value| {
                                     matches!(
                                         value,
                                         DataValue::Number(_) |
@@ -110,7 +110,7 @@ fn process_variable_clustering(
                             .any(|case| {
                                 case.values
                                     .get(var)
-                                    .map_or(false, |value| {
+                                    .is_some_and(|value| {
                                         matches!(value, DataValue::Number(_))
                                     })
                             })
@@ -10,6 +10,8 @@ use super::core::{
     calculate_statistics,
     calculate_variable_distance,
     extract_case_label,
+    gower_distance,
+    prepare_gower_variables,
 };
 
 // Fungsi utama untuk menghasilkan matriks proximity
@@ -24,6 +26,9 @@ pub fn generate_proximity_matrix(
     let mut distances = if config.main.cluster_cases {
         generate_case_proximity_matrix(data, config, variables)?
     } else if config.main.cluster_var {
+        if config.method.gower {
+            return Err("Gower distance is only available when clustering cases".to_string());
+        }
         generate_variable_proximity_matrix(data, config, variables)?
     } else {
         return Err("Neither case nor variable clustering specified".to_string());
@@ -72,16 +77,26 @@ fn generate_case_proximity_matrix(
         .map(|case_idx| extract_case_label(data, config, case_idx))
         .collect();
 
-    // Hitung jarak - bisa diparalelkan untuk kinerja yang lebih baik
+    // Jarak Gower membutuhkan rentang dan level variabel dari seluruh kasus
+    let gower_variables = if config.method.gower {
+        Some(prepare_gower_variables(data, variables)?)
+    } else {
+        None
+    };
+
+    // Hitung jarak - jarak simetris sehingga setiap pasangan cukup dihitung sekali
     for i in 0..case_count {
-        for j in 0..case_count {
-            let distance = if i == j {
-                0.0 // Jarak ke diri sendiri selalu 0
-            } else {
-                calculate_distance(&case_values[i], &case_values[j], variables, config)
+        // Jarak ke diri sendiri selalu 0
+        distances.insert((case_labels[i].clone(), case_labels[i].clone()), 0.0);
+
+        for j in i + 1..case_count {
+            let distance = match &gower_variables {
+                Some(gower) => gower_distance(&case_values[i], &case_values[j], gower),
+                None => calculate_distance(&case_values[i], &case_values[j], variables, config),
             };
 
             distances.insert((case_labels[i].clone(), case_labels[j].clone()), distance);
+            distances.insert((case_labels[j].clone(), case_labels[i].clone()), distance);
         }
     }
 
@@ -128,17 +143,16 @@ fn generate_variable_proximity_matrix(
 
     // Hitung jarak antara semua pasangan variabel
     for i in 0..variables.len() {
-        for j in 0..variables.len() {
-            let var_i = &variables[i];
-            let var_j = &variables[j];
-
-            let distance = if i == j {
-                0.0 // Jarak ke diri sendiri selalu 0
-            } else {
-                calculate_variable_distance(&variable_values, var_i, var_j, config)
-            };
+        let var_i = &variables[i];
+
+        // Jarak ke diri sendiri selalu 0
+        distances.insert((var_i.clone(), var_i.clone()), 0.0);
+
+        for var_j in &variables[i + 1..] {
+            let distance = calculate_variable_distance(&variable_values, var_i, var_j, config);
 
             distances.insert((var_i.clone(), var_j.clone()), distance);
+            distances.insert((var_j.clone(), var_i.clone()), distance);
         }
     }
 
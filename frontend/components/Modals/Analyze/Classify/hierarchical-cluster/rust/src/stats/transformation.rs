@@ -37,12 +37,12 @@ fn standardize_by_case(
     for dataset_idx in 0..data.cluster_data.len() {
         let dataset = &mut data.cluster_data[dataset_idx];
 
-        for case_idx in 0..dataset.len() {
+        for case in dataset.iter_mut() {
             // Ekstrak nilai untuk semua variabel dari kasus ini
             let case_values: Vec<f64> = variables
                 .iter()
                 .filter_map(|var| {
-                    if let Some(DataValue::Number(value)) = dataset[case_idx].values.get(var) {
+                    if let Some(DataValue::Number(value)) = case.values.get(var) {
                         Some(*value)
                     } else {
                         None
@@ -60,7 +60,7 @@ fn standardize_by_case(
 
             // Terapkan standardisasi ke setiap nilai
             for var in variables {
-                if let Some(DataValue::Number(value)) = dataset[case_idx].values.get_mut(var) {
+                if let Some(DataValue::Number(value)) = case.values.get_mut(var) {
                     *value = standardize_value(*value, &stats, &config.method.standardize_method);
                 }
             }
@@ -0,0 +1,459 @@
+use std::collections::HashMap;
+
+use crate::models::{
+    config::{ ClusMethod, ClusterConfig, DiagnosticsConfig, IntervalMethod, MainConfig, MethodConfig },
+    data::{
+        AnalysisData,
+        DataRecord,
+        DataValue,
+        VariableAlign,
+        VariableDefinition,
+        VariableMeasure,
+        VariableRole,
+        VariableType,
+    },
+    result::{ ClusterState, CondensedDistanceMatrix },
+};
+use crate::stats::cluster_diagnostics::generate_cluster_diagnostics;
+use crate::stats::cluster_method::{ agglomerate_clusters, calculate_new_distance };
+use crate::stats::gower_distance::{ gower_distance, prepare_gower_variables };
+
+const METHODS: [ClusMethod; 7] = [
+    ClusMethod::AverageBetweenGroups,
+    ClusMethod::AverageWithinGroups,
+    ClusMethod::SingleLinkage,
+    ClusMethod::CompleteLinkage,
+    ClusMethod::Centroid,
+    ClusMethod::Median,
+    ClusMethod::Ward,
+];
+
+// Titik 2D pseudo-acak; `tied` membulatkan koordinat ke grid kasar sehingga banyak jarak sama
+fn points(n: usize, seed: u64, tied: bool) -> Vec<(f64, f64)> {
+    let mut s = seed;
+    let mut next = || {
+        s = s.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        (((s >> 11) as f64) / ((1u64 << 53) as f64)) * 10.0
+    };
+    (0..n)
+        .map(|_| {
+            let (x, y) = (next(), next());
+            if tied { ((x / 4.0).floor(), (y / 4.0).floor()) } else { (x, y) }
+        })
+        .collect()
+}
+
+// Jarak Euclidean kuadrat antar titik
+fn squared_distances(points: &[(f64, f64)]) -> Vec<Vec<f64>> {
+    points
+        .iter()
+        .map(|a| {
+            points
+                .iter()
+                .map(|b| (a.0 - b.0).powi(2) + (a.1 - b.1).powi(2))
+                .collect()
+        })
+        .collect()
+}
+
+fn cluster_state(distances: &[Vec<f64>], method: ClusMethod) -> ClusterState {
+    let n = distances.len();
+    let mut condensed = CondensedDistanceMatrix::new(n);
+    for (i, row) in distances.iter().enumerate() {
+        for (j, &distance) in row.iter().enumerate().skip(i + 1) {
+            condensed.set(i, j, distance);
+        }
+    }
+
+    ClusterState {
+        clusters: (0..n).map(|i| vec![i]).collect(),
+        distances: condensed,
+        case_labels: (1..=n).map(|i| i.to_string()).collect(),
+        variables: vec!["x".to_string(), "y".to_string()],
+        method,
+    }
+}
+
+// Jalur sebelum NN-chain/SLINK: matriks jarak penuh, find_closest_clusters memindai semua
+// pasangan (i < j, jarak terkecil pertama), lalu baris/kolom cluster yang dihapus dibuang.
+// Jarak baru dihitung dengan ukuran cluster sebelum penggabungan.
+// Mengembalikan (ID cluster 1, ID cluster 2, jarak) per tahap.
+fn dense_schedule(distances: &[Vec<f64>], method: &ClusMethod) -> Vec<(usize, usize, f64)> {
+    let mut distances = distances.to_vec();
+    let mut active_clusters: Vec<usize> = (1..=distances.len()).collect();
+    let mut cluster_sizes = vec![1; distances.len()];
+    let mut schedule = Vec::new();
+
+    while distances.len() > 1 {
+        let n_clusters = distances.len();
+        let mut min_distance = f64::MAX;
+        let mut closest_pair = (0, 0);
+        for (i, row) in distances.iter().enumerate() {
+            for (j, &distance) in row.iter().enumerate().skip(i + 1) {
+                if distance < min_distance {
+                    min_distance = distance;
+                    closest_pair = (i, j);
+                }
+            }
+        }
+        let (keep_idx, remove_idx) = closest_pair;
+        schedule.push((active_clusters[keep_idx], active_clusters[remove_idx], min_distance));
+
+        let updated: Vec<f64> = (0..n_clusters)
+            .map(|other_idx| {
+                if other_idx == keep_idx || other_idx == remove_idx {
+                    return 0.0;
+                }
+                calculate_new_distance(
+                    method,
+                    cluster_sizes[keep_idx],
+                    cluster_sizes[remove_idx],
+                    cluster_sizes[other_idx],
+                    distances[keep_idx][other_idx],
+                    distances[remove_idx][other_idx],
+                    distances[keep_idx][remove_idx]
+                )
+            })
+            .collect();
+        for (other_idx, distance) in updated.into_iter().enumerate() {
+            if other_idx != keep_idx && other_idx != remove_idx {
+                distances[keep_idx][other_idx] = distance;
+                distances[other_idx][keep_idx] = distance;
+            }
+        }
+        cluster_sizes[keep_idx] += cluster_sizes[remove_idx];
+
+        distances.remove(remove_idx);
+        for row in distances.iter_mut() {
+            row.remove(remove_idx);
+        }
+        cluster_sizes.remove(remove_idx);
+        active_clusters.remove(remove_idx);
+    }
+
+    schedule
+}
+
+// NN-chain, SLINK dan pencarian menyeluruh pada matriks terkondensasi harus menghasilkan
+// jadwal yang sama dengan jalur matriks penuh, termasuk pada data dengan banyak jarak sama
+#[test]
+fn agglomeration_matches_dense_find_closest_clusters() {
+    for method in METHODS {
+        for (seed, tied) in [(1, false), (2, false), (3, true), (4, true)] {
+            let distances = squared_distances(&points(40, seed, tied));
+            let expected = dense_schedule(&distances, &method);
+
+            let mut state = cluster_state(&distances, method.clone());
+            let merges = agglomerate_clusters(&mut state).unwrap();
+
+            assert_eq!(merges.len(), expected.len());
+            for (stage, (&(keep_idx, remove_idx, distance), &(id1, id2, dense))) in merges
+                .iter()
+                .zip(expected.iter())
+                .enumerate() {
+                assert_eq!(
+                    (keep_idx + 1, remove_idx + 1),
+                    (id1, id2),
+                    "{:?} seed {} stage {}",
+                    method,
+                    seed,
+                    stage + 1
+                );
+                assert!(
+                    (distance - dense).abs() <= 1e-9 * dense.abs().max(1.0),
+                    "{:?} seed {} stage {}: {} != {}",
+                    method,
+                    seed,
+                    stage + 1,
+                    distance,
+                    dense
+                );
+            }
+        }
+    }
+}
+
+fn assert_schedule(distances: &[Vec<f64>], method: ClusMethod, expected: &[(usize, usize, f64)]) {
+    let mut state = cluster_state(distances, method.clone());
+    let merges = agglomerate_clusters(&mut state).unwrap();
+
+    assert_eq!(merges.len(), expected.len());
+    for (&(keep_idx, remove_idx, distance), &(id1, id2, coefficient)) in merges
+        .iter()
+        .zip(expected.iter()) {
+        assert_eq!((keep_idx + 1, remove_idx + 1), (id1, id2), "{:?}", method);
+        assert!((distance - coefficient).abs() < 1e-12, "{:?}: {} != {}", method, distance, coefficient);
+    }
+}
+
+// Contoh matriks jarak lima objek dari Everitt, Landau, Leese & Stahl (2011), Cluster Analysis, bab 4:
+// single, complete dan average linkage
+#[test]
+fn linkage_schedules_match_textbook_example() {
+    let distances = vec![
+        vec![0.0, 2.0, 6.0, 10.0, 9.0],
+        vec![2.0, 0.0, 5.0, 9.0, 8.0],
+        vec![6.0, 5.0, 0.0, 4.0, 5.0],
+        vec![10.0, 9.0, 4.0, 0.0, 3.0],
+        vec![9.0, 8.0, 5.0, 3.0, 0.0]
+    ];
+
+    assert_schedule(&distances, ClusMethod::SingleLinkage, &[
+        (1, 2, 2.0),
+        (4, 5, 3.0),
+        (3, 4, 4.0),
+        (1, 3, 5.0),
+    ]);
+    assert_schedule(&distances, ClusMethod::CompleteLinkage, &[
+        (1, 2, 2.0),
+        (4, 5, 3.0),
+        (3, 4, 5.0),
+        (1, 3, 10.0),
+    ]);
+    // d(3, {4,5}) = (4 + 5) / 2, d({1,2}, {3,4,5}) = rata-rata keenam jarak = 47 / 6
+    assert_schedule(&distances, ClusMethod::AverageBetweenGroups, &[
+        (1, 2, 2.0),
+        (4, 5, 3.0),
+        (3, 4, 4.5),
+        (1, 3, 47.0 / 6.0),
+    ]);
+}
+
+// Titik 1D 0, 1, 4, 6, 13 dengan jarak Euclidean kuadrat. Centroid: jarak = (c_p - c_q)^2.
+// Ward: jarak = 2 N_p N_q / (N_p + N_q) (c_p - c_q)^2, dua kali kenaikan jumlah kuadrat
+// dalam cluster, sehingga setengah jumlahnya sama dengan total jumlah kuadrat 106.8.
+#[test]
+fn centroid_and_ward_match_worked_example() {
+    let distances = squared_distances(
+        &[0.0, 1.0, 4.0, 6.0, 13.0].iter().map(|&x| (x, 0.0)).collect::<Vec<_>>()
+    );
+
+    assert_schedule(&distances, ClusMethod::Centroid, &[
+        (1, 2, 1.0),
+        (3, 4, 4.0),
+        (1, 3, 20.25),
+        (1, 5, 105.0625),
+    ]);
+    assert_schedule(&distances, ClusMethod::Ward, &[
+        (1, 2, 1.0),
+        (3, 4, 4.0),
+        (1, 3, 40.5),
+        (1, 5, 168.1),
+    ]);
+}
+
+fn definition(name: &str, measure: VariableMeasure, missing: Vec<DataValue>) -> VariableDefinition {
+    VariableDefinition {
+        id: None,
+        column_index: 0,
+        name: name.to_string(),
+        r#type: VariableType::Numeric,
+        width: 8,
+        decimals: 0,
+        label: None,
+        values: Vec::new(),
+        missing,
+        columns: 8,
+        align: VariableAlign::Right,
+        measure,
+        role: VariableRole::Input,
+    }
+}
+
+fn case(age: DataValue, grade: f64, color: &str) -> DataRecord {
+    DataRecord {
+        values: HashMap::from([
+            ("age".to_string(), age),
+            ("grade".to_string(), DataValue::Number(grade)),
+            ("color".to_string(), DataValue::Text(color.to_string())),
+        ]),
+    }
+}
+
+// Umur (skala, rentang 20), tingkat (ordinal, level 1-3, 9 = missing) dan warna (nominal)
+fn mixed_data() -> AnalysisData {
+    AnalysisData {
+        cluster_data: vec![
+            vec![
+                case(DataValue::Number(20.0), 1.0, "red"),
+                case(DataValue::Number(30.0), 3.0, "blue"),
+                case(DataValue::Number(40.0), 2.0, "red"),
+                case(DataValue::Null, 2.0, "green"),
+                case(DataValue::Number(25.0), 9.0, "red")
+            ]
+        ],
+        label_data: Vec::new(),
+        cluster_data_defs: vec![
+            vec![
+                definition("age", VariableMeasure::Scale, Vec::new()),
+                definition("grade", VariableMeasure::Ordinal, vec![DataValue::Number(9.0)]),
+                definition("color", VariableMeasure::Nominal, Vec::new())
+            ]
+        ],
+        label_data_defs: Vec::new(),
+    }
+}
+
+#[test]
+fn gower_distance_mixed_variables() {
+    let data = mixed_data();
+    let variables: Vec<String> = ["age", "grade", "color"]
+        .iter()
+        .map(|name| name.to_string())
+        .collect();
+    let gower_variables = prepare_gower_variables(&data, &variables).unwrap();
+    let cases = &data.cluster_data[0];
+    let distance = |i: usize, j: usize| {
+        gower_distance(&cases[i].values, &cases[j].values, &gower_variables)
+    };
+
+    // Dihitung manual: |selisih| / 20, |selisih peringkat| / 2 dan 0/1 untuk warna,
+    // dirata-rata atas variabel yang teramati pada kedua kasus
+    let expected = [
+        (0, 1, (0.5 + 1.0 + 1.0) / 3.0),
+        (0, 2, (1.0 + 0.5 + 0.0) / 3.0),
+        (1, 2, (0.5 + 0.5 + 1.0) / 3.0),
+        (0, 3, (0.5 + 1.0) / 2.0),
+        (2, 3, (0.0 + 1.0) / 2.0),
+        (0, 4, (0.25 + 0.0) / 2.0),
+        (1, 4, (0.25 + 1.0) / 2.0),
+        (3, 4, 1.0),
+    ];
+    for (i, j, d) in expected {
+        assert!(
+            (distance(i, j) - d).abs() < 1e-12,
+            "d({}, {}) = {} != {}",
+            i + 1,
+            j + 1,
+            distance(i, j),
+            d
+        );
+        assert_eq!(distance(i, j), distance(j, i));
+    }
+    assert_eq!(distance(2, 2), 0.0);
+}
+
+#[test]
+fn gower_distance_without_shared_variables_is_nan() {
+    let data = mixed_data();
+    let gower_variables = prepare_gower_variables(&data, &["age".to_string()]).unwrap();
+    let cases = &data.cluster_data[0];
+
+    assert!(gower_distance(&cases[0].values, &cases[3].values, &gower_variables).is_nan());
+}
+
+fn single_variable_data(values: &[f64]) -> AnalysisData {
+    AnalysisData {
+        cluster_data: vec![
+            values
+                .iter()
+                .map(|&x| DataRecord {
+                    values: HashMap::from([("x".to_string(), DataValue::Number(x))]),
+                })
+                .collect()
+        ],
+        label_data: Vec::new(),
+        cluster_data_defs: vec![vec![definition("x", VariableMeasure::Scale, Vec::new())]],
+        label_data_defs: Vec::new(),
+    }
+}
+
+// Average linkage dengan jarak Euclidean pada satu variabel, rentang 2-4 cluster
+fn diagnostics_config(diagnostics: DiagnosticsConfig) -> ClusterConfig {
+    ClusterConfig {
+        main: MainConfig {
+            variables: Some(vec!["x".to_string()]),
+            ..Default::default()
+        },
+        method: MethodConfig {
+            interval_method: IntervalMethod::Euclidean,
+            ..Default::default()
+        },
+        diagnostics: DiagnosticsConfig {
+            evaluate: true,
+            min_cluster: 2,
+            max_cluster: 4,
+            ..diagnostics
+        },
+        ..Default::default()
+    }
+}
+
+// Titik 0, 1, 4, 6, 13: tahap {1,2} 1, {3,4} 2, {1..4} 4.5, semua 10.25.
+// Nilai acuan mengikuti definisi SciPy: cophenet, inconsistent(Z, d=2), silhouette dan
+// Calinski-Harabasz dari sklearn.metrics
+#[test]
+fn cluster_diagnostics_match_reference_values() {
+    let diagnostics = generate_cluster_diagnostics(
+        &single_variable_data(&[0.0, 1.0, 4.0, 6.0, 13.0]),
+        &diagnostics_config(DiagnosticsConfig::default())
+    ).unwrap();
+
+    assert!((diagnostics.cophenetic_correlation.unwrap() - 0.902499500051708).abs() < 1e-12);
+
+    let coefficients: Vec<f64> = diagnostics.inconsistency
+        .iter()
+        .map(|entry| entry.coefficient)
+        .collect();
+    assert_eq!(coefficients, vec![1.0, 2.0, 4.5, 10.25]);
+    // Tahap 3: tinggi 4.5, 1 dan 2; tahap 4: tinggi 10.25 dan 4.5 (kasus 5 tidak menambah link)
+    let third = &diagnostics.inconsistency[2];
+    assert_eq!(third.links, 3);
+    assert!((third.mean - 2.5).abs() < 1e-12);
+    assert!((third.std_dev - 1.8027756377319946).abs() < 1e-12);
+    assert!((third.inconsistency - 1.1094003924504583).abs() < 1e-12);
+    let fourth = &diagnostics.inconsistency[3];
+    assert_eq!(fourth.links, 2);
+    assert!((fourth.inconsistency - std::f64::consts::FRAC_1_SQRT_2).abs() < 1e-12);
+
+    // (k, koefisien tahap pembentuk k cluster, silhouette, Calinski-Harabasz)
+    let expected = [
+        (2, 4.5, 2747.0 / 5460.0, 5043.0 / 455.0),
+        (3, 2.0, 4027.0 / 7700.0, 1043.0 / 25.0),
+        (4, 1.0, 17.0 / 60.0, 1063.0 / 15.0),
+    ];
+    assert_eq!(diagnostics.solutions.len(), expected.len());
+    for (solution, &(k, coefficient, silhouette, calinski_harabasz)) in diagnostics.solutions
+        .iter()
+        .zip(expected.iter()) {
+        assert_eq!(solution.num_clusters, k);
+        assert_eq!(solution.coefficient, coefficient);
+        assert!((solution.average_silhouette.unwrap() - silhouette).abs() < 1e-12, "k = {}", k);
+        assert!((solution.calinski_harabasz.unwrap() - calinski_harabasz).abs() < 1e-9, "k = {}", k);
+    }
+    assert_eq!(diagnostics.solutions[0].coefficient_change, Some(10.25 - 4.5));
+    assert_eq!(diagnostics.solutions[2].coefficient_change, Some(1.0));
+    // Memotong menjadi dua cluster membuang link tahap 4
+    assert!(
+        (diagnostics.solutions[0].inconsistency.unwrap() - std::f64::consts::FRAC_1_SQRT_2).abs() < 1e-12
+    );
+}
+
+// Dua kelompok yang terpisah jauh tidak pernah pecah pada sampel bootstrap mana pun
+#[test]
+fn bootstrap_stability_of_separated_groups() {
+    let diagnostics = generate_cluster_diagnostics(
+        &single_variable_data(&[0.0, 0.1, 0.2, 0.3, 0.4, 10.0, 10.1, 10.2, 10.3, 10.4]),
+        &diagnostics_config(DiagnosticsConfig {
+            max_cluster: 2,
+            stability: true,
+            bootstrap_samples: 50,
+            seed: true,
+            seed_value: 12345,
+            ..Default::default()
+        })
+    ).unwrap();
+
+    let stability = diagnostics.stability.unwrap();
+    assert_eq!(stability.valid_samples, 50);
+    let two_cluster: Vec<_> = stability.clusters
+        .iter()
+        .filter(|cluster| cluster.num_clusters == 2)
+        .collect();
+    assert_eq!(two_cluster.len(), 2);
+    for cluster in two_cluster {
+        assert_eq!(cluster.size, 5);
+        assert!((cluster.mean_jaccard - 1.0).abs() < 1e-12);
+        assert_eq!(cluster.dissolved, 0);
+    }
+}
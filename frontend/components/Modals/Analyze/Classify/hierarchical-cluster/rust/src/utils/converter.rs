@@ -3,6 +3,7 @@ use serde::Serialize;
 
 use crate::models::result::{
     CaseProcessingSummary,
+    ClusterDiagnostics,
     ClusteringResult,
     IciclePlot,
     DendrogramNode,
@@ -31,6 +32,7 @@ struct FormatResult {
     icicle_plot: Option<IciclePlot>,
     executed_functions: Vec<String>,
     cluster_memberships: Vec<FormattedClusterMembership>,
+    cluster_diagnostics: Option<ClusterDiagnostics>,
 }
 
 #[derive(Serialize)]
@@ -175,6 +177,7 @@ impl FormatResult {
             icicle_plot: result.icicle_plot.clone(),
             executed_functions: result.executed_functions.clone(),
             cluster_memberships,
+            cluster_diagnostics: result.cluster_diagnostics.clone(),
         }
     }
 
@@ -29,8 +29,8 @@ pub fn run_analysis(
     // Create a mutable copy of the data for transformations
     let mut analysis_data = data.clone();
 
-    // Apply transformations if configured
-    if config.method.by_case || config.method.by_variable {
+    // Apply transformations if configured (Gower distance scales each variable itself)
+    if (config.method.by_case || config.method.by_variable) && !config.method.gower {
         executed_functions.push("transform_data".to_string());
         match core::transform_data(&mut analysis_data, config) {
             Ok(_) => {
@@ -142,6 +142,21 @@ pub fn run_analysis(
         }
     }
 
+    // Cluster-count selection and stability diagnostics
+    let mut cluster_diagnostics = None;
+    if config.diagnostics.evaluate {
+        executed_functions.push("cluster_diagnostics".to_string());
+        match core::generate_cluster_diagnostics(&analysis_data, config) {
+            Ok(diagnostics) => {
+                web_sys::console::log_1(&format!("Cluster Diagnostics: {:?}", diagnostics).into());
+                cluster_diagnostics = Some(diagnostics);
+            }
+            Err(e) => {
+                error_collector.add_error("cluster_diagnostics", &e);
+            }
+        }
+    }
+
     // Create final result
     let result = ClusteringResult {
         case_processing_summary,
@@ -151,6 +166,7 @@ pub fn run_analysis(
         icicle_plot,
         executed_functions,
         cluster_memberships,
+        cluster_diagnostics,
     };
 
     Ok(Some(result))
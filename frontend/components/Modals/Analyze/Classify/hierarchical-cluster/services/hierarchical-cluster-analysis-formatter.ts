@@ -296,5 +296,125 @@ export function transformHierClusResult(data: any): ResultJson {
         resultJson.tables.push(table);
     }
 
+    // 7. Number of Clusters Diagnostics
+    if (data.cluster_diagnostics) {
+        const diagnostics = data.cluster_diagnostics;
+
+        const table: Table = {
+            key: "cluster_diagnostics",
+            title: "Number of Clusters Diagnostics",
+            columnHeaders: [
+                { header: "Number of Clusters", key: "num_clusters" },
+                { header: "Coefficient", key: "coefficient" },
+                { header: "Coefficient Change", key: "coefficient_change" },
+                { header: "Inconsistency", key: "inconsistency" },
+                { header: "Average Silhouette", key: "average_silhouette" },
+                { header: "Calinski-Harabasz", key: "calinski_harabasz" },
+            ],
+            rows: [],
+        };
+
+        diagnostics.solutions.forEach((solution: any) => {
+            table.rows.push({
+                rowHeader: [String(solution.num_clusters)],
+                coefficient: formatDisplayNumber(solution.coefficient),
+                coefficient_change: formatDisplayNumber(
+                    solution.coefficient_change
+                ),
+                inconsistency: formatDisplayNumber(solution.inconsistency),
+                average_silhouette: formatDisplayNumber(
+                    solution.average_silhouette
+                ),
+                calinski_harabasz: formatDisplayNumber(
+                    solution.calinski_harabasz
+                ),
+            });
+        });
+
+        if (
+            diagnostics.cophenetic_correlation !== null &&
+            diagnostics.cophenetic_correlation !== undefined
+        ) {
+            table.rows.push({
+                rowHeader: [
+                    `Cophenetic correlation: ${formatDisplayNumber(
+                        diagnostics.cophenetic_correlation
+                    )}`,
+                ],
+            });
+        }
+
+        if (diagnostics.note) {
+            table.rows.push({ rowHeader: [diagnostics.note] });
+        }
+
+        resultJson.tables.push(table);
+
+        // 8. Inconsistency Coefficients
+        if (diagnostics.inconsistency && diagnostics.inconsistency.length > 0) {
+            const inconsistencyTable: Table = {
+                key: "inconsistency_coefficients",
+                title: "Inconsistency Coefficients",
+                columnHeaders: [
+                    { header: "Stage", key: "stage" },
+                    { header: "Coefficient", key: "coefficient" },
+                    { header: "Mean", key: "mean" },
+                    { header: "Std. Deviation", key: "std_dev" },
+                    { header: "Links", key: "links" },
+                    { header: "Inconsistency", key: "inconsistency" },
+                ],
+                rows: [],
+            };
+
+            diagnostics.inconsistency.forEach((entry: any) => {
+                inconsistencyTable.rows.push({
+                    rowHeader: [String(entry.stage)],
+                    coefficient: formatDisplayNumber(entry.coefficient),
+                    mean: formatDisplayNumber(entry.mean),
+                    std_dev: formatDisplayNumber(entry.std_dev),
+                    links: formatDisplayNumber(entry.links),
+                    inconsistency: formatDisplayNumber(entry.inconsistency),
+                });
+            });
+
+            resultJson.tables.push(inconsistencyTable);
+        }
+
+        // 9. Cluster Stability
+        if (diagnostics.stability) {
+            const stability = diagnostics.stability;
+            const stabilityTable: Table = {
+                key: "cluster_stability",
+                title: "Cluster Stability",
+                columnHeaders: [
+                    { header: "Number of Clusters", key: "num_clusters" },
+                    { header: "Cluster", key: "cluster" },
+                    { header: "Size", key: "size" },
+                    { header: "Mean Jaccard", key: "mean_jaccard" },
+                    { header: "Dissolved", key: "dissolved" },
+                ],
+                rows: [],
+            };
+
+            stability.clusters.forEach((cluster: any) => {
+                stabilityTable.rows.push({
+                    rowHeader: [String(cluster.num_clusters)],
+                    cluster: formatDisplayNumber(cluster.cluster),
+                    size: formatDisplayNumber(cluster.size),
+                    mean_jaccard: formatDisplayNumber(cluster.mean_jaccard),
+                    dissolved: formatDisplayNumber(cluster.dissolved),
+                });
+            });
+
+            stabilityTable.rows.push({
+                rowHeader: [
+                    `Based on ${stability.valid_samples} of ${stability.requested_samples} bootstrap samples. A cluster is dissolved in a sample when its best Jaccard similarity is 0.5 or less.`,
+                ],
+            });
+
+            resultJson.tables.push(stabilityTable);
+        }
+    }
+
     return resultJson;
 }
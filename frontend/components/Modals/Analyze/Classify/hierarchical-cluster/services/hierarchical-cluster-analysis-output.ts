@@ -136,6 +136,46 @@ export async function resultHierarchicalCluster({
                     components: `Dendrogram Data`,
                 });
             }
+
+            /*
+             * 🩺 Cluster Diagnostics Result 🩺
+             * */
+            const clusterDiagnostics = findTable("cluster_diagnostics");
+            if (clusterDiagnostics) {
+                const clusterDiagnosticsId = await addAnalytic(logId, {
+                    title: `Cluster Diagnostics`,
+                    note: "",
+                });
+
+                await addStatistic(clusterDiagnosticsId, {
+                    title: `Number of Clusters Diagnostics`,
+                    description: `Number of Clusters Diagnostics`,
+                    output_data: clusterDiagnostics,
+                    components: `Number of Clusters Diagnostics`,
+                });
+
+                const inconsistencyCoefficients = findTable(
+                    "inconsistency_coefficients"
+                );
+                if (inconsistencyCoefficients) {
+                    await addStatistic(clusterDiagnosticsId, {
+                        title: `Inconsistency Coefficients`,
+                        description: `Inconsistency Coefficients`,
+                        output_data: inconsistencyCoefficients,
+                        components: `Inconsistency Coefficients`,
+                    });
+                }
+
+                const clusterStability = findTable("cluster_stability");
+                if (clusterStability) {
+                    await addStatistic(clusterDiagnosticsId, {
+                        title: `Cluster Stability`,
+                        description: `Cluster Stability`,
+                        output_data: clusterStability,
+                        components: `Cluster Stability`,
+                    });
+                }
+            }
         };
 
         await hierarchicalClusterAnalysisResult();
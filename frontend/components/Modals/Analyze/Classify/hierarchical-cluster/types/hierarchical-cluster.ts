@@ -16,6 +16,7 @@ export type HierClusDialogProps = {
     setIsMethodOpen: React.Dispatch<React.SetStateAction<boolean>>;
     setIsPlotsOpen: React.Dispatch<React.SetStateAction<boolean>>;
     setIsSaveOpen: React.Dispatch<React.SetStateAction<boolean>>;
+    setIsDiagnosticsOpen: React.Dispatch<React.SetStateAction<boolean>>;
     updateFormData: (
         field: keyof HierClusMainType,
         value: string[] | string | boolean | null
@@ -100,6 +101,7 @@ export type HierClusMethodType = {
     BinaryMethod: string | null;
     Present: number | null;
     Absent: number | null;
+    Gower: boolean;
     StandardizeMethod: string | null;
     ByVariable: boolean;
     ByCase: boolean;
@@ -118,12 +120,33 @@ export type HierClusMethodProps = {
     data: HierClusMethodType;
 };
 
+export type HierClusDiagnosticsType = {
+    Evaluate: boolean;
+    MinCluster: number | null;
+    MaxCluster: number | null;
+    Stability: boolean;
+    BootstrapSamples: number | null;
+    Seed: boolean;
+    SeedValue: number | null;
+};
+
+export type HierClusDiagnosticsProps = {
+    isDiagnosticsOpen: boolean;
+    setIsDiagnosticsOpen: React.Dispatch<React.SetStateAction<boolean>>;
+    updateFormData: (
+        field: keyof HierClusDiagnosticsType,
+        value: number | boolean | null
+    ) => void;
+    data: HierClusDiagnosticsType;
+};
+
 export type HierClusType = {
     main: HierClusMainType;
     statistics: HierClusStatisticsType;
     plots: HierClusPlotsType;
     save: HierClusSaveType;
     method: HierClusMethodType;
+    diagnostics: HierClusDiagnosticsType;
 };
 
 export type HierClusContainerProps = {
@@ -145,6 +145,23 @@ describe("KMeansClusterAnalysis Constructor Performance Test", () => {
                             ExcludeListWise: true,
                             ExcludePairWise: false,
                         },
+                        initialization: {
+                            KMeansPlusPlus: false,
+                            RandomStarts: 1,
+                            Seed: false,
+                            SeedValue: 2000000,
+                        },
+                        validity: {
+                            EvaluateRange: false,
+                            MinClusters: 2,
+                            MaxClusters: 10,
+                        },
+                        mini_batch: {
+                            MiniBatch: false,
+                            BatchSize: 1024,
+                            MaxBatches: 300,
+                            ChunkSize: 50000,
+                        },
                     };
 
                     const TargetVariables = configData.main.TargetVar || [];
@@ -182,7 +199,8 @@ describe("KMeansClusterAnalysis Constructor Performance Test", () => {
                             slicedDataForCaseTarget,
                             varDefsForTarget,
                             varDefsForCaseTarget,
-                            configData
+                            configData,
+                            null
                         );
 
                         const endTime = performance.now();
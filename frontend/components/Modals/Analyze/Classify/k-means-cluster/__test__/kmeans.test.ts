@@ -221,7 +221,8 @@ const runAnalysisTest = async ({
             caseData,
             targetDefs,
             caseDefs,
-            config
+            config,
+            null
         );
     } catch (e: any) {
         error = e;
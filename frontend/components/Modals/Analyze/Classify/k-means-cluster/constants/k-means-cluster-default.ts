@@ -1,9 +1,12 @@
 import {
+    KMeansClusterInitializationType,
     KMeansClusterIterateType,
     KMeansClusterMainType,
+    KMeansClusterMiniBatchType,
     KMeansClusterOptionsType,
     KMeansClusterSaveType,
     KMeansClusterType,
+    KMeansClusterValidityType,
 } from "@/components/Modals/Analyze/Classify/k-means-cluster/types/k-means-cluster";
 
 export const KMeansClusterMainDefault: KMeansClusterMainType = {
@@ -44,9 +47,32 @@ export const KMeansClusterOptionsDefault: KMeansClusterOptionsType = {
     ExcludePairWise: false,
 };
 
+export const KMeansClusterInitializationDefault: KMeansClusterInitializationType = {
+    KMeansPlusPlus: false,
+    RandomStarts: 1,
+    Seed: false,
+    SeedValue: 2000000,
+};
+
+export const KMeansClusterValidityDefault: KMeansClusterValidityType = {
+    EvaluateRange: false,
+    MinClusters: 2,
+    MaxClusters: 10,
+};
+
+export const KMeansClusterMiniBatchDefault: KMeansClusterMiniBatchType = {
+    MiniBatch: false,
+    BatchSize: 1024,
+    MaxBatches: 300,
+    ChunkSize: 50000,
+};
+
 export const KMeansClusterDefault: KMeansClusterType = {
     main: KMeansClusterMainDefault,
     iterate: KMeansClusterIterateDefault,
     save: KMeansClusterSaveDefault,
     options: KMeansClusterOptionsDefault,
+    initialization: KMeansClusterInitializationDefault,
+    validity: KMeansClusterValidityDefault,
+    mini_batch: KMeansClusterMiniBatchDefault,
 };
@@ -35,6 +35,7 @@ import VariableListManager, {
 } from "@/components/Common/VariableListManager";
 import { TourPopup } from "@/components/Common/TourComponents";
 import type { Variable } from "@/types/Variable";
+import { parseCSV } from "@/utils/file-parsers";
 import { useTourGuide } from "../hooks/useTourGuide";
 import { dialogTourSteps } from "../hooks/tourConfig";
 
@@ -63,6 +64,9 @@ export const KMeansClusterDialog = ({
     const [openAccordion, setOpenAccordion] = useState<string | undefined>(
         undefined
     );
+    const [initialCentersData, setInitialCentersData] = useState<
+        Record<string, string | number>[] | null
+    >(null);
 
     const {
         tourActive,
@@ -246,6 +250,40 @@ export const KMeansClusterDialog = ({
         }));
     };
 
+    // Tabel pusat dari file CSV: baris pertama berisi nama kolom (CLUSTER_ dan nama variabel)
+    const handleInitialCentersFile = (file: File) => {
+        const reader = new FileReader();
+        reader.onload = async (event) => {
+            try {
+                const [header, ...rows] = await parseCSV(
+                    event.target?.result as string
+                );
+                const records = rows.map((row) =>
+                    Object.fromEntries(
+                        (header ?? []).map((name, index) => {
+                            const raw = String(row[index] ?? "").trim();
+                            const value = Number(raw.replace(",", "."));
+                            return [
+                                String(name).trim(),
+                                raw !== "" && !isNaN(value) ? value : raw,
+                            ];
+                        })
+                    )
+                );
+                setInitialCentersData(records);
+                handleChange("InitialData", file.name);
+            } catch (err) {
+                setInitialCentersData(null);
+                toast.error(`Failed to read the center file: ${String(err)}`);
+            }
+        };
+        reader.onerror = () => {
+            setInitialCentersData(null);
+            toast.error("Failed to read the center file.");
+        };
+        reader.readAsText(file);
+    };
+
     const handleContinue = () => {
         if (targetVars.length === 0) {
             toast.warning(
@@ -257,12 +295,39 @@ export const KMeansClusterDialog = ({
             toast.warning("Number of clusters must be at least 2.");
             return;
         }
+        if (mainState.ReadInitial) {
+            if (mainState.OpenDataset && !mainState.OpenDatasetMethod) {
+                toast.warning(
+                    "Please enter the name of the dataset with the initial centers."
+                );
+                return;
+            }
+            if (mainState.ExternalDatafile && !initialCentersData) {
+                toast.warning("Please choose a file with the initial centers.");
+                return;
+            }
+        }
+        if (mainState.WriteFinal) {
+            if (mainState.NewDataset && !mainState.NewData) {
+                toast.warning("Please enter a name for the final centers dataset.");
+                return;
+            }
+            if (mainState.DataFile && !mainState.FinalData) {
+                toast.warning("Please enter a file name for the final centers.");
+                return;
+            }
+        }
         Object.entries(mainState).forEach(([key, value]) => {
             updateFormData(key as keyof KMeansClusterMainType, value);
         });
 
         setIsMainOpen(false);
-        onContinue(mainState);
+        onContinue(
+            mainState,
+            mainState.ReadInitial && mainState.ExternalDatafile
+                ? initialCentersData ?? []
+                : undefined
+        );
     };
 
     const openDialog =
@@ -387,6 +452,39 @@ export const KMeansClusterDialog = ({
                                                 }
                                             />
                                         </div>
+                                        <div className="flex flex-row gap-2 items-center">
+                                            <Label className="w-[300px]">
+                                                Method:
+                                            </Label>
+                                            <RadioGroup
+                                                className="flex flex-row gap-4 w-full"
+                                                value={
+                                                    mainState.ClassifyOnly
+                                                        ? "ClassifyOnly"
+                                                        : "IterateClassify"
+                                                }
+                                                onValueChange={handleMethodGrp}
+                                            >
+                                                <div className="flex items-center space-x-2">
+                                                    <RadioGroupItem
+                                                        value="IterateClassify"
+                                                        id="IterateClassify"
+                                                    />
+                                                    <Label htmlFor="IterateClassify">
+                                                        Iterate and classify
+                                                    </Label>
+                                                </div>
+                                                <div className="flex items-center space-x-2">
+                                                    <RadioGroupItem
+                                                        value="ClassifyOnly"
+                                                        id="ClassifyOnly"
+                                                    />
+                                                    <Label htmlFor="ClassifyOnly">
+                                                        Classify only
+                                                    </Label>
+                                                </div>
+                                            </RadioGroup>
+                                        </div>
                                         <div className="flex flex-col gap-1">
                                             <div className="flex items-center space-x-2">
                                                 <Checkbox
@@ -394,7 +492,6 @@ export const KMeansClusterDialog = ({
                                                     checked={
                                                         mainState.ReadInitial
                                                     }
-                                                    disabled={true}
                                                     onCheckedChange={(
                                                         checked
                                                     ) =>
@@ -406,7 +503,7 @@ export const KMeansClusterDialog = ({
                                                 />
                                                 <label
                                                     htmlFor="ReadInitial"
-                                                    className="text-sm font-medium leading-none cursor-not-allowed opacity-50"
+                                                    className="text-sm font-medium leading-none"
                                                 >
                                                     Read Initial
                                                 </label>
@@ -419,7 +516,6 @@ export const KMeansClusterDialog = ({
                                                             : "ExternalDatafile"
                                                     }
                                                     disabled={
-                                                        true ||
                                                         !mainState.ReadInitial
                                                     }
                                                     onValueChange={
@@ -431,10 +527,9 @@ export const KMeansClusterDialog = ({
                                                             <RadioGroupItem
                                                                 value="OpenDataset"
                                                                 id="OpenDataset"
-                                                                disabled={true}
                                                             />
                                                             <Label
-                                                                className="w-[175px] text-sm font-medium leading-none cursor-not-allowed opacity-50"
+                                                                className="w-[175px] text-sm font-medium leading-none"
                                                                 htmlFor="OpenDataset"
                                                             >
                                                                 Open Dataset
@@ -443,13 +538,13 @@ export const KMeansClusterDialog = ({
                                                                 id="OpenDatasetMethod"
                                                                 type="text"
                                                                 className="min-w-2xl w-full"
-                                                                placeholder=""
+                                                                placeholder="Saved centers dataset name"
                                                                 value={
                                                                     mainState.OpenDatasetMethod ??
                                                                     ""
                                                                 }
                                                                 disabled={
-                                                                    true ||
+                                                                    !mainState.ReadInitial ||
                                                                     !mainState.OpenDataset
                                                                 }
                                                                 onChange={(e) =>
@@ -467,10 +562,9 @@ export const KMeansClusterDialog = ({
                                                             <RadioGroupItem
                                                                 value="ExternalDatafile"
                                                                 id="ExternalDatafile"
-                                                                disabled={true}
                                                             />
                                                             <Label
-                                                                className="w-[175px] text-sm font-medium leading-none cursor-not-allowed opacity-50"
+                                                                className="w-[175px] text-sm font-medium leading-none"
                                                                 htmlFor="ExternalDatafile"
                                                             >
                                                                 External
@@ -479,23 +573,22 @@ export const KMeansClusterDialog = ({
                                                             <Input
                                                                 id="InitialData"
                                                                 type="file"
+                                                                accept=".csv"
                                                                 className="min-w-2xl w-full"
-                                                                placeholder=""
-                                                                value={
-                                                                    mainState.InitialData ??
-                                                                    ""
-                                                                }
                                                                 disabled={
-                                                                    true ||
+                                                                    !mainState.ReadInitial ||
                                                                     !mainState.ExternalDatafile
                                                                 }
-                                                                onChange={(e) =>
-                                                                    handleChange(
-                                                                        "InitialData",
+                                                                onChange={(e) => {
+                                                                    const file =
                                                                         e.target
-                                                                            .value
-                                                                    )
-                                                                }
+                                                                            .files?.[0];
+                                                                    if (file) {
+                                                                        handleInitialCentersFile(
+                                                                            file
+                                                                        );
+                                                                    }
+                                                                }}
                                                             />
                                                         </div>
                                                     </div>
@@ -506,7 +599,6 @@ export const KMeansClusterDialog = ({
                                             <div className="flex items-center space-x-2">
                                                 <Checkbox
                                                     id="WriteFinal"
-                                                    disabled={true}
                                                     checked={
                                                         mainState.WriteFinal
                                                     }
@@ -521,7 +613,7 @@ export const KMeansClusterDialog = ({
                                                 />
                                                 <label
                                                     htmlFor="WriteFinal"
-                                                    className="text-sm font-medium leading-none cursor-not-allowed opacity-50"
+                                                    className="text-sm font-medium leading-none"
                                                 >
                                                     Write Final
                                                 </label>
@@ -534,7 +626,6 @@ export const KMeansClusterDialog = ({
                                                             : "DataFile"
                                                     }
                                                     disabled={
-                                                        true ||
                                                         !mainState.WriteFinal
                                                     }
                                                     onValueChange={
@@ -546,10 +637,9 @@ export const KMeansClusterDialog = ({
                                                             <RadioGroupItem
                                                                 value="NewDataset"
                                                                 id="NewDataset"
-                                                                disabled={true}
                                                             />
                                                             <Label
-                                                                className="w-[175px] text-sm font-medium leading-none cursor-not-allowed opacity-50"
+                                                                className="w-[175px] text-sm font-medium leading-none"
                                                                 htmlFor="NewDataset"
                                                             >
                                                                 New Dataset
@@ -558,13 +648,13 @@ export const KMeansClusterDialog = ({
                                                                 id="NewData"
                                                                 type="text"
                                                                 className="min-w-2xl w-full"
-                                                                placeholder=""
+                                                                placeholder="Centers dataset name"
                                                                 value={
                                                                     mainState.NewData ??
                                                                     ""
                                                                 }
                                                                 disabled={
-                                                                    true ||
+                                                                    !mainState.WriteFinal ||
                                                                     !mainState.NewDataset
                                                                 }
                                                                 onChange={(e) =>
@@ -582,25 +672,24 @@ export const KMeansClusterDialog = ({
                                                             <RadioGroupItem
                                                                 value="DataFile"
                                                                 id="DataFile"
-                                                                disabled={true}
                                                             />
                                                             <Label
-                                                                className="w-[175px] text-sm font-medium leading-none cursor-not-allowed opacity-50"
+                                                                className="w-[175px] text-sm font-medium leading-none"
                                                                 htmlFor="DataFile"
                                                             >
                                                                 Data File
                                                             </Label>
                                                             <Input
                                                                 id="FinalData"
-                                                                type="file"
+                                                                type="text"
                                                                 className="min-w-2xl w-full"
-                                                                placeholder=""
+                                                                placeholder="final_centers.csv"
                                                                 value={
                                                                     mainState.FinalData ??
                                                                     ""
                                                                 }
                                                                 disabled={
-                                                                    true ||
+                                                                    !mainState.WriteFinal ||
                                                                     !mainState.DataFile
                                                                 }
                                                                 onChange={(e) =>
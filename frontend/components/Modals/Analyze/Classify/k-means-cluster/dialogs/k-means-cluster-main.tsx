@@ -12,7 +12,10 @@ import { KMeansClusterOptions } from "@/components/Modals/Analyze/Classify/k-mea
 import { useModal } from "@/hooks/useModal";
 import { useVariableStore } from "@/stores/useVariableStore";
 import { useDataStore } from "@/stores/useDataStore";
-import { analyzeKMeansCluster } from "@/components/Modals/Analyze/Classify/k-means-cluster/services/k-means-cluster-analysis";
+import {
+    analyzeKMeansCluster,
+    loadCentersDataset,
+} from "@/components/Modals/Analyze/Classify/k-means-cluster/services/k-means-cluster-analysis";
 import { clearFormData, getFormData, saveFormData } from "@/hooks/useIndexedDB";
 import { toast } from "sonner";
 
@@ -78,7 +81,10 @@ export const KMeansClusterContainer = ({
         }));
     };
 
-    const executeKMeansCluster = async (mainData: KMeansClusterMainType) => {
+    const executeKMeansCluster = async (
+        mainData: KMeansClusterMainType,
+        externalCentersData?: Record<string, string | number>[]
+    ) => {
         closeModal();
         onClose();
 
@@ -90,10 +96,18 @@ export const KMeansClusterContainer = ({
 
             await saveFormData("KMeansCluster", newFormData);
 
+            // Pusat awal dari dataset pusat yang disimpan sebelumnya atau dari file CSV
+            const initialCentersData = !mainData.ReadInitial
+                ? undefined
+                : mainData.OpenDataset
+                ? await loadCentersDataset(mainData.OpenDatasetMethod ?? "")
+                : externalCentersData;
+
             await analyzeKMeansCluster({
                 configData: newFormData,
                 dataVariables: dataVariables,
                 variables: variables,
+                initialCentersData,
             });
         };
 
@@ -173,7 +187,9 @@ export const KMeansClusterContainer = ({
                     }
                     data={formData.main}
                     globalVariables={tempVariables}
-                    onContinue={(mainData) => executeKMeansCluster(mainData)}
+                    onContinue={(mainData, initialCentersData) =>
+                        executeKMeansCluster(mainData, initialCentersData)
+                    }
                     onReset={resetFormData}
                 />
             )}
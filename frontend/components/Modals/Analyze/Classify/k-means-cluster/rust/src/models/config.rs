@@ -1,11 +1,17 @@
 use serde::{ Deserialize, Serialize };
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct KMeansConfig {
     pub main: MainConfig,
     pub iterate: IterateConfig,
     pub save: SaveConfig,
     pub options: OptionsConfig,
+    #[serde(default)]
+    pub initialization: InitializationConfig,
+    #[serde(default)]
+    pub validity: ValidityConfig,
+    #[serde(default)]
+    pub mini_batch: MiniBatchConfig,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -42,6 +48,28 @@ pub struct MainConfig {
     pub final_data: Option<String>,
 }
 
+impl Default for MainConfig {
+    fn default() -> Self {
+        MainConfig {
+            target_var: None,
+            case_target: None,
+            iterate_classify: true,
+            classify_only: false,
+            cluster: 2,
+            read_initial: false,
+            open_dataset: true,
+            external_datafile: false,
+            write_final: false,
+            new_dataset: true,
+            data_file: false,
+            open_dataset_method: None,
+            new_data: None,
+            initial_data: None,
+            final_data: None,
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct IterateConfig {
     #[serde(rename = "MaximumIterations")]
@@ -52,7 +80,17 @@ pub struct IterateConfig {
     pub use_running_means: bool,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+impl Default for IterateConfig {
+    fn default() -> Self {
+        IterateConfig {
+            maximum_iterations: 10,
+            convergence_criterion: 0.0,
+            use_running_means: false,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct SaveConfig {
     #[serde(rename = "ClusterMembership")]
     pub cluster_membership: bool,
@@ -75,3 +113,85 @@ pub struct OptionsConfig {
     #[serde(rename = "ExcludePairWise")]
     pub exclude_pair_wise: bool,
 }
+
+impl Default for OptionsConfig {
+    fn default() -> Self {
+        OptionsConfig {
+            initial_cluster: true,
+            anova: false,
+            cluster_info: false,
+            cluster_plot: false,
+            exclude_list_wise: true,
+            exclude_pair_wise: false,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct InitializationConfig {
+    #[serde(rename = "KMeansPlusPlus")]
+    pub k_means_plus_plus: bool,
+    #[serde(rename = "RandomStarts")]
+    pub random_starts: i32,
+    #[serde(rename = "Seed")]
+    pub seed: bool,
+    #[serde(rename = "SeedValue")]
+    pub seed_value: i64,
+}
+
+impl Default for InitializationConfig {
+    fn default() -> Self {
+        InitializationConfig {
+            k_means_plus_plus: false,
+            random_starts: 1,
+            seed: false,
+            seed_value: 2000000,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct ValidityConfig {
+    #[serde(rename = "EvaluateRange")]
+    pub evaluate_range: bool,
+    #[serde(rename = "MinClusters")]
+    pub min_clusters: i32,
+    #[serde(rename = "MaxClusters")]
+    pub max_clusters: i32,
+}
+
+impl Default for ValidityConfig {
+    fn default() -> Self {
+        ValidityConfig {
+            evaluate_range: false,
+            min_clusters: 2,
+            max_clusters: 10,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct MiniBatchConfig {
+    #[serde(rename = "MiniBatch")]
+    pub mini_batch: bool,
+    #[serde(rename = "BatchSize")]
+    pub batch_size: i32,
+    #[serde(rename = "MaxBatches")]
+    pub max_batches: i32,
+    #[serde(rename = "ChunkSize")]
+    pub chunk_size: i32,
+}
+
+impl Default for MiniBatchConfig {
+    fn default() -> Self {
+        MiniBatchConfig {
+            mini_batch: false,
+            batch_size: 1024,
+            max_batches: 300,
+            chunk_size: 50000,
+        }
+    }
+}
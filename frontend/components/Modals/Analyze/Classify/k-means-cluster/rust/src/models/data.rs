@@ -117,4 +117,40 @@ pub struct AnalysisData {
     pub case_data: Vec<Vec<DataRecord>>,
     pub target_data_defs: Vec<Vec<VariableDefinition>>,
     pub case_data_defs: Vec<Vec<VariableDefinition>>,
+    #[serde(default)]
+    pub initial_centers_data: Vec<DataRecord>,
+}
+
+// Kasus untuk K-Means mini-batch disimpan dalam satu buffer f64 bersambung (baris demi
+// baris), nilai hilang ditandai NaN
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DataBuffer {
+    pub variables: Vec<String>,
+    pub values: Vec<f64>,
+    pub total_cases: usize,
+    pub missing_cases: usize,
+}
+
+impl DataBuffer {
+    pub fn new(variables: Vec<String>) -> Self {
+        DataBuffer {
+            variables,
+            values: Vec::new(),
+            total_cases: 0,
+            missing_cases: 0,
+        }
+    }
+
+    pub fn num_cases(&self) -> usize {
+        if self.variables.is_empty() {
+            0
+        } else {
+            self.values.len() / self.variables.len()
+        }
+    }
+
+    pub fn row(&self, index: usize) -> &[f64] {
+        let width = self.variables.len();
+        &self.values[index * width..(index + 1) * width]
+    }
 }
@@ -1,6 +1,8 @@
 use serde::{ Deserialize, Serialize };
 use std::collections::HashMap;
 
+use crate::models::data::DataRecord;
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct KMeansResult {
     pub initial_centers: Option<InitialClusterCenters>,
@@ -11,6 +13,8 @@ pub struct KMeansResult {
     pub anova: Option<ANOVATable>,
     pub cases_count: Option<CaseCountTable>,
     pub cluster_plot: Option<ClusterPlot>,
+    pub cluster_validity: Option<ClusterValidity>,
+    pub final_centers_dataset: Option<FinalCentersDataset>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -81,6 +85,15 @@ pub struct FinalClusterCenters {
     pub interpretation: Option<String>,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct FinalCentersDataset {
+    pub dataset_name: Option<String>,
+    pub variables: Vec<String>,
+    pub records: Vec<DataRecord>,
+    pub note: Option<String>,
+    pub interpretation: Option<String>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct DistancesBetweenCenters {
     pub distances: Vec<Vec<f64>>,
@@ -96,6 +109,7 @@ pub struct ProcessedData {
     pub case_names: Option<Vec<String>>,
     pub total_cases: usize,
     pub missing_cases: usize,
+    pub supplied_centers: Option<Vec<Vec<f64>>>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -110,3 +124,19 @@ pub struct ClusterPlot {
     pub note: Option<String>,
     pub interpretation: Option<String>,
 }
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ClusterValidity {
+    pub solutions: Vec<ClusterValiditySolution>,
+    pub note: Option<String>,
+    pub interpretation: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ClusterValiditySolution {
+    pub clusters: i32,
+    pub within_ss: f64,
+    pub silhouette: Option<f64>,
+    pub calinski_harabasz: Option<f64>,
+    pub davies_bouldin: Option<f64>,
+}
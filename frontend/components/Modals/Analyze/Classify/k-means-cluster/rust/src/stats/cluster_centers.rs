@@ -2,7 +2,8 @@ use std::collections::HashMap;
 
 use crate::models::{
     config::KMeansConfig,
-    result::{ DistancesBetweenCenters, FinalClusterCenters, ProcessedData },
+    data::{ DataRecord, DataValue },
+    result::{ DistancesBetweenCenters, FinalCentersDataset, FinalClusterCenters, ProcessedData },
 };
 
 use super::core::*;
@@ -11,16 +12,116 @@ pub fn generate_final_cluster_centers(
     data: &ProcessedData,
     config: &KMeansConfig
 ) -> Result<FinalClusterCenters, String> {
-    let num_clusters = config.main.cluster as usize;
-    let max_iterations = config.iterate.maximum_iterations;
-    let convergence_criterion = config.iterate.convergence_criterion;
-    let use_running_means = config.iterate.use_running_means;
-
     let initial_centers_result = initialize_clusters(data, config)?;
-    let mut current_centers = convert_map_to_matrix(
+    let initial_centers = convert_map_to_matrix(
         &initial_centers_result.centers,
         &data.variables
     );
+    let current_centers = iterate_cluster_centers(data, initial_centers, config);
+
+    let mut centers_map = HashMap::new();
+    for (i, var) in data.variables.iter().enumerate() {
+        let var_values = current_centers
+            .iter()
+            .map(|center| center[i])
+            .collect();
+        centers_map.insert(var.clone(), var_values);
+    }
+
+    let note = if config.main.classify_only {
+        Some(
+            "Classify only: cases were assigned to the initial centers and the centers were not updated.".to_string()
+        )
+    } else {
+        None
+    };
+
+    Ok(FinalClusterCenters {
+        centers: centers_map,
+        note,
+        interpretation: Some(
+            "This table presents the final coordinates for the center of each cluster after the iterative K-Means algorithm has converged. Each row corresponds to a variable, and each column represents a cluster, showing the value of that variable at the cluster's centroid. These centers define the typical profile of a case belonging to each cluster.".to_string()
+        ),
+    })
+}
+
+// Pusat akhir dalam bentuk dataset: satu baris per cluster dengan kolom CLUSTER_ dan
+// kolom variabel, sehingga dapat dibaca kembali sebagai pusat awal
+pub fn generate_final_centers_dataset(
+    data: &ProcessedData,
+    config: &KMeansConfig
+) -> Result<FinalCentersDataset, String> {
+    let final_centers_result = generate_final_cluster_centers(data, config)?;
+    let final_centers = convert_map_to_matrix(&final_centers_result.centers, &data.variables);
+
+    Ok(
+        build_final_centers_dataset(
+            &data.variables,
+            &final_centers,
+            config,
+            final_centers_result.note
+        )
+    )
+}
+
+pub fn build_final_centers_dataset(
+    variables: &[String],
+    final_centers: &[Vec<f64>],
+    config: &KMeansConfig,
+    note: Option<String>
+) -> FinalCentersDataset {
+    let records = final_centers
+        .iter()
+        .enumerate()
+        .map(|(i, center)| {
+            let mut values = HashMap::new();
+            values.insert(CLUSTER_COLUMN.to_string(), DataValue::Number((i + 1) as i64));
+            for (var, &val) in variables.iter().zip(center.iter()) {
+                values.insert(var.clone(), DataValue::NumberFloat(val));
+            }
+            DataRecord { values }
+        })
+        .collect();
+
+    let mut columns = vec![CLUSTER_COLUMN.to_string()];
+    columns.extend(variables.iter().cloned());
+
+    let dataset_name = if config.main.new_dataset {
+        config.main.new_data.clone()
+    } else if config.main.data_file {
+        config.main.final_data.clone()
+    } else {
+        None
+    };
+
+    FinalCentersDataset {
+        dataset_name,
+        variables: columns,
+        records,
+        note,
+        interpretation: Some(
+            "This dataset contains one row per cluster with the cluster number and the final center of each clustering variable. It can be supplied as the initial centers of a later analysis, so that new data are classified with the same cluster definitions.".to_string()
+        ),
+    }
+}
+
+// Jalankan iterasi K-Means dari pusat awal sampai konvergen atau batas iterasi tercapai
+pub fn iterate_cluster_centers(
+    data: &ProcessedData,
+    initial_centers: Vec<Vec<f64>>,
+    config: &KMeansConfig
+) -> Vec<Vec<f64>> {
+    // Mode classify-only: kasus diklasifikasikan ke pusat awal tanpa pembaruan pusat
+    if config.main.classify_only {
+        return initial_centers;
+    }
+
+    let num_clusters = initial_centers.len();
+    let max_iterations = config.iterate.maximum_iterations;
+    let convergence_criterion = config.iterate.convergence_criterion;
+    let use_running_means = config.iterate.use_running_means;
+
+    let mut current_centers = initial_centers;
 
     let (min_center_dist, _, _) = min_distance_between_centers(&current_centers);
     let min_change_threshold = convergence_criterion * min_center_dist;
@@ -105,22 +206,7 @@ pub fn generate_final_cluster_centers(
         }
     }
 
-    let mut centers_map = HashMap::new();
-    for (i, var) in data.variables.iter().enumerate() {
-        let var_values = current_centers
-            .iter()
-            .map(|center| center[i])
-            .collect();
-        centers_map.insert(var.clone(), var_values);
-    }
-
-    Ok(FinalClusterCenters {
-        centers: centers_map,
-        note: None,
-        interpretation: Some(
-            "This table presents the final coordinates for the center of each cluster after the iterative K-Means algorithm has converged. Each row corresponds to a variable, and each column represents a cluster, showing the value of that variable at the cluster's centroid. These centers define the typical profile of a case belonging to each cluster.".to_string()
-        ),
-    })
+    current_centers
 }
 
 pub fn calculate_distances_between_centers(
@@ -64,8 +64,8 @@ pub fn create_cluster_plot(
             }
         }
     } else {
-        for i in 0..num_points {
-            cluster_labels.push(format!("Cluster {}", clusters[i]));
+        for cluster in clusters.iter().take(num_points) {
+            cluster_labels.push(format!("Cluster {}", cluster));
         }
     }
 
@@ -0,0 +1,238 @@
+use crate::models::{
+    config::KMeansConfig,
+    result::{ ClusterValidity, ClusterValiditySolution, ProcessedData },
+};
+
+use super::core::*;
+
+pub fn evaluate_cluster_range(
+    data: &ProcessedData,
+    config: &KMeansConfig
+) -> Result<ClusterValidity, String> {
+    let min_clusters = config.validity.min_clusters;
+    let max_clusters = config.validity.max_clusters;
+    let num_cases = data.data_matrix.len();
+
+    if min_clusters < 1 || min_clusters > max_clusters {
+        return Err(
+            format!("Invalid cluster range: minimum {} and maximum {}", min_clusters, max_clusters)
+        );
+    }
+    if (max_clusters as usize) >= num_cases {
+        return Err(
+            format!(
+                "Maximum number of clusters ({}) must be smaller than the number of cases ({})",
+                max_clusters,
+                num_cases
+            )
+        );
+    }
+
+    let mut solutions = Vec::new();
+    for k in min_clusters..=max_clusters {
+        let num_clusters = k as usize;
+        let (initial_centers, _) = select_initial_centers(data, config, num_clusters)?;
+        let centers = iterate_cluster_centers(data, initial_centers, config);
+        let assignments: Vec<usize> = data.data_matrix
+            .iter()
+            .map(|case| find_nearest_cluster(case, &centers).0)
+            .collect();
+
+        let within_ss = within_cluster_sum_of_squares(&data.data_matrix, &centers);
+        let occupied = (0..num_clusters)
+            .filter(|c| assignments.contains(c))
+            .count();
+
+        // Indeks validitas hanya terdefinisi untuk minimal dua cluster yang berisi
+        let (silhouette, calinski_harabasz, davies_bouldin) = if occupied >= 2 {
+            (
+                Some(average_silhouette(&data.data_matrix, &assignments, num_clusters)),
+                calinski_harabasz_index(&data.data_matrix, &centers, &assignments, within_ss),
+                Some(davies_bouldin_index(&data.data_matrix, &centers, &assignments)),
+            )
+        } else {
+            (None, None, None)
+        };
+
+        solutions.push(ClusterValiditySolution {
+            clusters: k,
+            within_ss,
+            silhouette,
+            calinski_harabasz,
+            davies_bouldin,
+        });
+    }
+
+    let mut suggestions = Vec::new();
+    if let Some(best) = best_solution(&solutions, |s| s.silhouette, true) {
+        suggestions.push(format!("largest average silhouette at {} clusters", best));
+    }
+    if let Some(best) = best_solution(&solutions, |s| s.calinski_harabasz, true) {
+        suggestions.push(format!("largest Calinski-Harabasz index at {} clusters", best));
+    }
+    if let Some(best) = best_solution(&solutions, |s| s.davies_bouldin, false) {
+        suggestions.push(format!("smallest Davies-Bouldin index at {} clusters", best));
+    }
+
+    let note = if suggestions.is_empty() {
+        None
+    } else {
+        let mut note = suggestions.join("; ");
+        note[..1].make_ascii_uppercase();
+        Some(format!("{}.", note))
+    };
+
+    Ok(ClusterValidity {
+        solutions,
+        note,
+        interpretation: Some(
+            "This table compares K-Means solutions over a range of cluster numbers. The within-cluster sum of squares forms the elbow curve: look for the point after which adding clusters gives little reduction. A larger average silhouette (between -1 and 1) and a larger Calinski-Harabasz index indicate better separated, more compact clusters, while a smaller Davies-Bouldin index indicates less overlap between clusters.".to_string()
+        ),
+    })
+}
+
+// Rata-rata silhouette: s(i) = (b - a) / max(a, b), dengan a rata-rata jarak ke anggota
+// cluster sendiri dan b rata-rata jarak terkecil ke cluster lain
+fn average_silhouette(data_matrix: &[Vec<f64>], assignments: &[usize], num_clusters: usize) -> f64 {
+    let mut cluster_sizes = vec![0usize; num_clusters];
+    for &c in assignments {
+        cluster_sizes[c] += 1;
+    }
+
+    let total: f64 = data_matrix
+        .iter()
+        .enumerate()
+        .map(|(i, case)| {
+            let own = assignments[i];
+            // Kasus tunggal dalam cluster memiliki silhouette 0
+            if cluster_sizes[own] <= 1 {
+                return 0.0;
+            }
+
+            let mut distance_sums = vec![0.0; num_clusters];
+            for (j, other) in data_matrix.iter().enumerate() {
+                if i != j {
+                    let dist = euclidean_distance(case, other);
+                    if dist != f64::MAX {
+                        distance_sums[assignments[j]] += dist;
+                    }
+                }
+            }
+
+            let a = distance_sums[own] / ((cluster_sizes[own] - 1) as f64);
+            let b = (0..num_clusters)
+                .filter(|&c| c != own && cluster_sizes[c] > 0)
+                .map(|c| distance_sums[c] / (cluster_sizes[c] as f64))
+                .fold(f64::INFINITY, f64::min);
+
+            let denominator = a.max(b);
+            if denominator > 0.0 && denominator.is_finite() {
+                (b - a) / denominator
+            } else {
+                0.0
+            }
+        })
+        .sum();
+
+    total / (data_matrix.len() as f64)
+}
+
+// Calinski-Harabasz: (SSB / (k - 1)) / (SSW / (n - k))
+fn calinski_harabasz_index(
+    data_matrix: &[Vec<f64>],
+    centers: &[Vec<f64>],
+    assignments: &[usize],
+    within_ss: f64
+) -> Option<f64> {
+    let n = data_matrix.len();
+    let k = (0..centers.len())
+        .filter(|c| assignments.contains(c))
+        .count();
+    if within_ss <= 0.0 || n <= k {
+        return None;
+    }
+
+    let num_vars = centers.first().map_or(0, |c| c.len());
+    let overall_center: Vec<f64> = (0..num_vars)
+        .map(|j| {
+            let values: Vec<f64> = data_matrix
+                .iter()
+                .map(|row| row[j])
+                .collect();
+            mean(&values)
+        })
+        .collect();
+
+    let total_ss: f64 = data_matrix
+        .iter()
+        .map(|case| euclidean_distance(case, &overall_center))
+        .filter(|&dist| dist != f64::MAX)
+        .map(|dist| dist.powi(2))
+        .sum();
+    let between_ss = (total_ss - within_ss).max(0.0);
+
+    Some(between_ss / ((k - 1) as f64) / (within_ss / ((n - k) as f64)))
+}
+
+// Davies-Bouldin: rata-rata dari max_j (S_i + S_j) / d(c_i, c_j) atas cluster yang berisi
+fn davies_bouldin_index(
+    data_matrix: &[Vec<f64>],
+    centers: &[Vec<f64>],
+    assignments: &[usize]
+) -> f64 {
+    let num_clusters = centers.len();
+    let mut scatter = vec![0.0; num_clusters];
+    let mut counts = vec![0usize; num_clusters];
+
+    for (case, &c) in data_matrix.iter().zip(assignments.iter()) {
+        let dist = euclidean_distance(case, &centers[c]);
+        if dist != f64::MAX {
+            scatter[c] += dist;
+            counts[c] += 1;
+        }
+    }
+
+    let occupied: Vec<usize> = (0..num_clusters).filter(|&c| counts[c] > 0).collect();
+    for &c in &occupied {
+        scatter[c] /= counts[c] as f64;
+    }
+
+    let total: f64 = occupied
+        .iter()
+        .map(|&i| {
+            occupied
+                .iter()
+                .filter(|&&j| j != i)
+                .map(|&j| {
+                    let separation = euclidean_distance(&centers[i], &centers[j]);
+                    if separation > 0.0 {
+                        (scatter[i] + scatter[j]) / separation
+                    } else {
+                        f64::INFINITY
+                    }
+                })
+                .fold(0.0, f64::max)
+        })
+        .sum();
+
+    total / (occupied.len() as f64)
+}
+
+fn best_solution(
+    solutions: &[ClusterValiditySolution],
+    index: impl Fn(&ClusterValiditySolution) -> Option<f64>,
+    larger_is_better: bool
+) -> Option<i32> {
+    solutions
+        .iter()
+        .filter_map(|s| index(s).filter(|v| v.is_finite()).map(|v| (s.clusters, v)))
+        .max_by(|a, b| {
+            let ordering = a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal);
+            if larger_is_better {
+                ordering
+            } else {
+                ordering.reverse()
+            }
+        })
+        .map(|(clusters, _)| clusters)
+}
@@ -120,6 +120,16 @@ pub fn min_distance_from_cluster(centers: &[Vec<f64>], cluster_idx: usize) -> f6
     min_distance
 }
 
+// Jumlah kuadrat jarak setiap kasus ke pusat cluster terdekat
+pub fn within_cluster_sum_of_squares(data_matrix: &[Vec<f64>], centers: &[Vec<f64>]) -> f64 {
+    data_matrix
+        .iter()
+        .map(|case| find_nearest_cluster(case, centers).1)
+        .filter(|&dist| dist != f64::MAX)
+        .map(|dist| dist.powi(2))
+        .sum()
+}
+
 pub fn convert_map_to_matrix(
     centers_map: &HashMap<String, Vec<f64>>,
     variables: &[String]
@@ -7,3 +7,5 @@ pub use crate::stats::initialize_clusters::*;
 pub use crate::stats::iteration_history::*;
 pub use crate::stats::preprocess_data::*;
 pub use crate::stats::cluster_plot::*;
+pub use crate::stats::cluster_validity::*;
+pub use crate::stats::mini_batch::*;
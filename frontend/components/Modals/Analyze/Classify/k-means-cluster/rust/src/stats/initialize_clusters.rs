@@ -1,5 +1,7 @@
 use std::collections::HashMap;
 
+use rand_mt::Mt64;
+
 use crate::models::{ config::KMeansConfig, result::{ InitialClusterCenters, ProcessedData } };
 
 use super::core::*;
@@ -9,7 +11,33 @@ pub fn initialize_clusters(
     config: &KMeansConfig
 ) -> Result<InitialClusterCenters, String> {
     let num_clusters = config.main.cluster as usize;
+    let (initial_centers, note) = select_initial_centers(data, config, num_clusters)?;
+
+    let mut centers_map = HashMap::new();
+    for (i, var) in data.variables.iter().enumerate() {
+        let var_values = initial_centers
+            .iter()
+            .map(|center| center[i])
+            .collect();
+        centers_map.insert(var.clone(), var_values);
+    }
 
+    Ok(InitialClusterCenters {
+        centers: centers_map,
+        note,
+        interpretation: Some(
+            "This table shows the initial positions of the cluster centers before the iterative optimization process begins. These centers are selected based on the chosen initialization strategy. The quality of these initial centers can influence the final clustering outcome and convergence speed.".to_string()
+        ),
+    })
+}
+
+// Pilih pusat awal untuk sejumlah cluster tertentu. Dengan k-means++ dijalankan beberapa
+// start acak dan dipilih start dengan within-cluster sum of squares terkecil setelah iterasi.
+pub fn select_initial_centers(
+    data: &ProcessedData,
+    config: &KMeansConfig,
+    num_clusters: usize
+) -> Result<(Vec<Vec<f64>>, Option<String>), String> {
     let mut initial_centers: Vec<Vec<f64>>;
 
     let valid_cases: Vec<&Vec<f64>> = data.data_matrix
@@ -27,6 +55,18 @@ pub fn initialize_clusters(
         );
     }
 
+    // Pusat dari tabel yang diberikan hanya berlaku untuk jumlah cluster yang diminta
+    if let Some(supplied) = data.supplied_centers.as_ref().filter(|c| c.len() == num_clusters) {
+        return Ok((
+            supplied.clone(),
+            Some("Initial centers were read from the supplied center table.".to_string()),
+        ));
+    }
+
+    if config.initialization.k_means_plus_plus {
+        return Ok(select_k_means_plus_plus_centers(data, config, &valid_cases, num_clusters));
+    }
+
     if !config.options.initial_cluster {
         initial_centers = valid_cases
             .iter()
@@ -39,8 +79,7 @@ pub fn initialize_clusters(
             .take(num_clusters)
             .map(|&case| case.clone())
             .collect();
-        for k in num_clusters..valid_cases.len() {
-            let x_k = valid_cases[k];
+        for &x_k in valid_cases.iter().skip(num_clusters) {
 
             // Cari pusat cluster terdekat dan jarak minimum
             let (closest, min_dist) = find_nearest_cluster(x_k, &initial_centers);
@@ -75,37 +114,155 @@ pub fn initialize_clusters(
         }
     }
 
-    let mut centers_map = HashMap::new();
-    for (i, var) in data.variables.iter().enumerate() {
-        let var_values: Vec<f64> = initial_centers
-            .iter()
-            .map(|center| {
-                let val = center[i];
-
-                if val.is_nan() {
-                    let valid_values: Vec<f64> = data.data_matrix
-                        .iter()
-                        .map(|row| row[i])
-                        .filter(|x| !x.is_nan())
-                        .collect();
-                    if valid_values.is_empty() {
-                        0.0
+    Ok((fill_missing_coordinates(data, initial_centers), None))
+}
+
+// Pusat awal k-means++ (Arthur & Vassilvitskii, 2007) dengan beberapa start acak
+fn select_k_means_plus_plus_centers(
+    data: &ProcessedData,
+    config: &KMeansConfig,
+    valid_cases: &[&Vec<f64>],
+    num_clusters: usize
+) -> (Vec<Vec<f64>>, Option<String>) {
+    let num_starts = config.initialization.random_starts.max(1) as usize;
+    let mut rng = create_rng(config);
+
+    let mut best_centers = Vec::new();
+    let mut best_within_ss = f64::INFINITY;
+
+    for _ in 0..num_starts {
+        let seeds = fill_missing_coordinates(
+            data,
+            k_means_plus_plus(valid_cases, num_clusters, &mut rng)
+        );
+
+        // Satu start saja tidak perlu dibandingkan
+        if num_starts == 1 {
+            best_centers = seeds;
+            break;
+        }
+
+        let final_centers = iterate_cluster_centers(data, seeds.clone(), config);
+        let within_ss = within_cluster_sum_of_squares(&data.data_matrix, &final_centers);
+        if within_ss < best_within_ss {
+            best_within_ss = within_ss;
+            best_centers = seeds;
+        }
+    }
+
+    let note = if num_starts > 1 {
+        format!(
+            "Initial centers were chosen by k-means++ seeding. The start with the smallest within-cluster sum of squares ({:.3}) out of {} random starts is shown.",
+            best_within_ss,
+            num_starts
+        )
+    } else {
+        "Initial centers were chosen by k-means++ seeding.".to_string()
+    };
+
+    (best_centers, Some(note))
+}
+
+// Pusat pertama dipilih acak, pusat berikutnya dengan peluang sebanding dengan
+// kuadrat jarak ke pusat terdekat yang sudah terpilih
+fn k_means_plus_plus(
+    valid_cases: &[&Vec<f64>],
+    num_clusters: usize,
+    rng: &mut Mt64
+) -> Vec<Vec<f64>> {
+    let n = valid_cases.len();
+    let mut centers = vec![valid_cases[(rng.next_u64() % (n as u64)) as usize].clone()];
+    let mut squared_distances: Vec<f64> = valid_cases
+        .iter()
+        .map(|case| squared_distance(case, &centers[0]))
+        .collect();
+
+    while centers.len() < num_clusters {
+        let total: f64 = squared_distances.iter().sum();
+
+        let next = if total > 0.0 {
+            let target = uniform(rng) * total;
+            let mut cumulative = 0.0;
+            let mut chosen = n - 1;
+            for (i, &d) in squared_distances.iter().enumerate() {
+                cumulative += d;
+                if d > 0.0 && cumulative >= target {
+                    chosen = i;
+                    break;
+                }
+            }
+            chosen
+        } else {
+            // Semua kasus berimpit dengan pusat yang ada
+            (rng.next_u64() % (n as u64)) as usize
+        };
+
+        let center = valid_cases[next].clone();
+        for (d, case) in squared_distances.iter_mut().zip(valid_cases.iter()) {
+            *d = d.min(squared_distance(case, &center));
+        }
+        centers.push(center);
+    }
+
+    centers
+}
+
+// Ganti koordinat yang hilang dengan rata-rata variabel
+fn fill_missing_coordinates(data: &ProcessedData, centers: Vec<Vec<f64>>) -> Vec<Vec<f64>> {
+    centers
+        .into_iter()
+        .map(|center| {
+            center
+                .iter()
+                .enumerate()
+                .map(|(i, &val)| {
+                    if val.is_nan() {
+                        let valid_values: Vec<f64> = data.data_matrix
+                            .iter()
+                            .map(|row| row[i])
+                            .filter(|x| !x.is_nan())
+                            .collect();
+                        if valid_values.is_empty() {
+                            0.0
+                        } else {
+                            mean(&valid_values)
+                        }
                     } else {
-                        mean(&valid_values)
+                        val
                     }
-                } else {
-                    val
-                }
-            })
-            .collect();
-        centers_map.insert(var.clone(), var_values);
+                })
+                .collect()
+        })
+        .collect()
+}
+
+pub fn create_rng(config: &KMeansConfig) -> Mt64 {
+    if config.initialization.seed {
+        Mt64::new(config.initialization.seed_value as u64)
+    } else {
+        Mt64::new(rand::random::<u64>())
     }
+}
 
-    Ok(InitialClusterCenters {
-        centers: centers_map,
-        note: None,
-        interpretation: Some(
-            "This table shows the initial positions of the cluster centers before the iterative optimization process begins. These centers are selected based on the chosen initialization strategy. The quality of these initial centers can influence the final clustering outcome and convergence speed.".to_string()
-        ),
-    })
+pub fn uniform(rng: &mut Mt64) -> f64 {
+    ((rng.next_u64() >> 11) as f64) / ((1u64 << 53) as f64)
+}
+
+fn squared_distance(a: &[f64], b: &[f64]) -> f64 {
+    let dist = euclidean_distance(a, b);
+    if dist == f64::MAX {
+        0.0
+    } else {
+        dist.powi(2)
+    }
+}
+
+// Semua tabel menghitung ulang inisialisasi, jadi seed acak ditetapkan sekali per analisis
+pub fn resolve_initialization_seed(config: &KMeansConfig) -> KMeansConfig {
+    let mut resolved = config.clone();
+    if resolved.initialization.k_means_plus_plus && !resolved.initialization.seed {
+        resolved.initialization.seed = true;
+        resolved.initialization.seed_value = (rand::random::<u64>() >> 1) as i64;
+    }
+    resolved
 }
@@ -0,0 +1,425 @@
+use std::collections::HashMap;
+
+use rand_mt::Mt64;
+
+use crate::models::{
+    config::KMeansConfig,
+    data::DataBuffer,
+    result::{
+        ANOVACluster,
+        ANOVATable,
+        CaseCountTable,
+        DistancesBetweenCenters,
+        FinalClusterCenters,
+        InitialClusterCenters,
+        IterationHistory,
+        IterationStep,
+        KMeansResult,
+        ProcessedData,
+    },
+};
+
+use super::core::*;
+
+// Tambahkan satu potongan data (baris demi baris, NaN untuk nilai hilang) ke buffer,
+// dengan aturan listwise/pairwise yang sama seperti preprocess_data
+pub fn append_chunk(
+    buffer: &mut DataBuffer,
+    chunk: &[f64],
+    config: &KMeansConfig
+) -> Result<usize, String> {
+    let width = buffer.variables.len();
+    if width == 0 {
+        return Err("No clustering variables were specified".to_string());
+    }
+    if !chunk.len().is_multiple_of(width) {
+        return Err(
+            format!(
+                "Chunk length {} is not a multiple of the number of variables ({})",
+                chunk.len(),
+                width
+            )
+        );
+    }
+
+    buffer.values.reserve(chunk.len());
+    let mut added = 0;
+    for row in chunk.chunks_exact(width) {
+        buffer.total_cases += 1;
+        let valid = row
+            .iter()
+            .filter(|val| !val.is_nan())
+            .count();
+
+        let keep = if config.options.exclude_list_wise {
+            valid == width
+        } else if config.options.exclude_pair_wise {
+            valid > 0
+        } else {
+            false
+        };
+
+        if keep {
+            buffer.values.extend_from_slice(row);
+            added += 1;
+        } else {
+            buffer.missing_cases += 1;
+        }
+    }
+
+    Ok(added)
+}
+
+// K-Means mini-batch (Sculley, 2010): setiap langkah mengambil sampel acak berukuran
+// batch, lalu tiap pusat digeser ke arah kasus dengan laju 1 / jumlah kasus yang pernah
+// ditugaskan ke pusat tersebut
+pub fn run_mini_batch_k_means(
+    buffer: &DataBuffer,
+    config: &KMeansConfig
+) -> Result<KMeansResult, String> {
+    let num_clusters = config.main.cluster as usize;
+    let num_vars = buffer.variables.len();
+    let num_cases = buffer.num_cases();
+
+    if num_cases < num_clusters {
+        return Err(
+            format!(
+                "Not enough valid data points ({}) for requested clusters ({})",
+                num_cases,
+                num_clusters
+            )
+        );
+    }
+
+    let batch_size = (config.mini_batch.batch_size.max(1) as usize).min(num_cases);
+    let max_batches = config.mini_batch.max_batches.max(1);
+    let mut rng = create_rng(config);
+
+    // Pusat awal dipilih dari sampel acak agar inisialisasi tidak memindai seluruh data
+    let sample = initialization_sample(buffer, batch_size.max(10 * num_clusters) * 3, &mut rng);
+    let (initial_centers, initial_note) = select_initial_centers(&sample, config, num_clusters)?;
+
+    let mut centers = initial_centers.clone();
+    let mut counts = vec![vec![0usize; num_vars]; num_clusters];
+    let mut iterations = Vec::new();
+
+    let (min_center_dist, _, _) = min_distance_between_centers(&initial_centers);
+    let min_change_threshold = config.iterate.convergence_criterion * min_center_dist;
+    let mut converged = false;
+
+    if !config.main.classify_only {
+        for iteration in 1..=max_batches {
+            let previous = centers.clone();
+
+            // Penugasan dilakukan terhadap pusat sebelum batch diperbarui
+            let batch: Vec<(usize, usize)> = (0..batch_size)
+                .map(|_| {
+                    let index = (rng.next_u64() % (num_cases as u64)) as usize;
+                    (index, find_nearest_cluster(buffer.row(index), &centers).0)
+                })
+                .collect();
+
+            for (index, cluster) in batch {
+                for (j, &val) in buffer.row(index).iter().enumerate() {
+                    if !val.is_nan() {
+                        counts[cluster][j] += 1;
+                        let rate = 1.0 / (counts[cluster][j] as f64);
+                        centers[cluster][j] += rate * (val - centers[cluster][j]);
+                    }
+                }
+            }
+
+            let changes: Vec<(String, f64)> = (0..num_clusters)
+                .map(|i| (format!("{}", i + 1), euclidean_distance(&centers[i], &previous[i])))
+                .collect();
+            let max_change = changes
+                .iter()
+                .map(|(_, change)| *change)
+                .fold(0.0, f64::max);
+            iterations.push(IterationStep { iteration, changes });
+
+            if max_change <= min_change_threshold {
+                converged = true;
+                break;
+            }
+        }
+    }
+
+    let summary = summarize_assignments(buffer, &centers);
+
+    let method_note = format!(
+        "Centers were estimated by mini-batch K-Means on {} cases using batches of {} cases; initial centers were selected from a random sample of {} cases.",
+        num_cases,
+        batch_size,
+        sample.data_matrix.len()
+    );
+    let convergence_note = if config.main.classify_only {
+        "Classify only: cases were assigned to the initial centers and the centers were not updated.".to_string()
+    } else if converged {
+        format!(
+            "Convergence achieved due to no or small change in cluster centers after {} batches.",
+            iterations.len()
+        )
+    } else {
+        format!(
+            "Iterations stopped because the maximum number of batches ({}) was reached. Mini-batch centers keep moving slightly from batch to batch, so a larger number of batches or a larger batch size gives more stable centers.",
+            max_batches
+        )
+    };
+
+    let initial_centers_table = InitialClusterCenters {
+        centers: centers_to_map(&buffer.variables, &initial_centers),
+        note: Some(match initial_note {
+            Some(note) => format!("{} {}", method_note, note),
+            None => method_note.clone(),
+        }),
+        interpretation: Some(
+            "This table shows the initial positions of the cluster centers before the mini-batch updates begin. They are chosen from a random sample of the data with the selected initialization strategy.".to_string()
+        ),
+    };
+
+    let iteration_history = if config.main.classify_only {
+        None
+    } else {
+        Some(IterationHistory {
+            iterations,
+            note: Some(convergence_note.clone()),
+            interpretation: Some(
+                "This table tracks the movement of the cluster centers after each mini-batch. Each row is one batch, showing how far each cluster center shifted. The changes shrink as more cases have been assigned to each center.".to_string()
+            ),
+        })
+    };
+
+    let final_cluster_centers = FinalClusterCenters {
+        centers: centers_to_map(&buffer.variables, &centers),
+        note: Some(format!("{} {}", method_note, convergence_note)),
+        interpretation: Some(
+            "This table presents the final coordinates for the center of each cluster estimated by mini-batch K-Means. Each row corresponds to a variable and each column to a cluster. The centers are close to, but not identical with, the full-batch K-Means solution.".to_string()
+        ),
+    };
+
+    let distances = (0..num_clusters)
+        .map(|i| {
+            (0..num_clusters)
+                .map(|j| if i == j { 0.0 } else { euclidean_distance(&centers[i], &centers[j]) })
+                .collect()
+        })
+        .collect();
+
+    let distances_between_centers = if config.options.cluster_info {
+        Some(DistancesBetweenCenters {
+            distances,
+            note: None,
+            interpretation: Some(
+                "This table displays the Euclidean distances between the final cluster centers. Larger values indicate that clusters are more distinct and further apart in the multi-dimensional variable space. The diagonal elements are always zero.".to_string()
+            ),
+        })
+    } else {
+        None
+    };
+
+    let anova = if config.options.anova {
+        Some(streaming_anova(&buffer.variables, &summary))
+    } else {
+        None
+    };
+
+    let cases_count = CaseCountTable {
+        valid: num_cases,
+        missing: buffer.missing_cases,
+        clusters: summary.sizes
+            .iter()
+            .enumerate()
+            .map(|(i, &count)| ((i + 1).to_string(), count))
+            .collect(),
+        note: Some(
+            format!("Within-cluster sum of squares: {:.3}.", summary.within_ss)
+        ),
+        interpretation: Some(
+            "This table shows the number of cases assigned to each cluster. 'Valid' represents the total count of data points processed, while 'Missing' indicates cases that were excluded. The 'Clusters' field details the specific case count for each cluster.".to_string()
+        ),
+    };
+
+    let final_centers_dataset = if config.main.write_final {
+        Some(
+            build_final_centers_dataset(
+                &buffer.variables,
+                &centers,
+                config,
+                final_cluster_centers.note.clone()
+            )
+        )
+    } else {
+        None
+    };
+
+    Ok(KMeansResult {
+        initial_centers: if config.options.initial_cluster {
+            Some(initial_centers_table)
+        } else {
+            None
+        },
+        iteration_history,
+        cluster_membership: None,
+        final_cluster_centers: Some(final_cluster_centers),
+        distances_between_centers,
+        anova,
+        cases_count: Some(cases_count),
+        cluster_plot: None,
+        cluster_validity: None,
+        final_centers_dataset,
+    })
+}
+
+// Ringkasan satu kali lintasan data: ukuran cluster, within-cluster sum of squares dan
+// statistik per cluster per variabel (algoritma Welford) untuk ANOVA
+struct AssignmentSummary {
+    sizes: Vec<usize>,
+    within_ss: f64,
+    counts: Vec<Vec<usize>>,
+    means: Vec<Vec<f64>>,
+    squared_deviations: Vec<Vec<f64>>,
+}
+
+fn summarize_assignments(buffer: &DataBuffer, centers: &[Vec<f64>]) -> AssignmentSummary {
+    let num_clusters = centers.len();
+    let num_vars = buffer.variables.len();
+
+    let mut summary = AssignmentSummary {
+        sizes: vec![0; num_clusters],
+        within_ss: 0.0,
+        counts: vec![vec![0; num_vars]; num_clusters],
+        means: vec![vec![0.0; num_vars]; num_clusters],
+        squared_deviations: vec![vec![0.0; num_vars]; num_clusters],
+    };
+
+    for index in 0..buffer.num_cases() {
+        let case = buffer.row(index);
+        let (cluster, dist) = find_nearest_cluster(case, centers);
+        summary.sizes[cluster] += 1;
+        if dist != f64::MAX {
+            summary.within_ss += dist.powi(2);
+        }
+
+        for (j, &val) in case.iter().enumerate() {
+            if !val.is_nan() {
+                summary.counts[cluster][j] += 1;
+                let delta = val - summary.means[cluster][j];
+                summary.means[cluster][j] += delta / (summary.counts[cluster][j] as f64);
+                summary.squared_deviations[cluster][j] += delta * (val - summary.means[cluster][j]);
+            }
+        }
+    }
+
+    summary
+}
+
+fn streaming_anova(variables: &[String], summary: &AssignmentSummary) -> ANOVATable {
+    let num_clusters = summary.sizes.len();
+    let mut anova_clusters = HashMap::new();
+
+    for (j, var_name) in variables.iter().enumerate() {
+        let total_valid_cases: usize = (0..num_clusters).map(|c| summary.counts[c][j]).sum();
+        let overall_mean = if total_valid_cases > 0 {
+            (0..num_clusters)
+                .map(|c| (summary.counts[c][j] as f64) * summary.means[c][j])
+                .sum::<f64>() / (total_valid_cases as f64)
+        } else {
+            0.0
+        };
+
+        let ssb: f64 = (0..num_clusters)
+            .map(|c| (summary.counts[c][j] as f64) * (summary.means[c][j] - overall_mean).powi(2))
+            .sum();
+        let ssw: f64 = (0..num_clusters).map(|c| summary.squared_deviations[c][j]).sum();
+
+        let df_between = (num_clusters as i32) - 1;
+        let df_within = (total_valid_cases as i32) - (num_clusters as i32);
+
+        if df_within <= 0 {
+            anova_clusters.insert(var_name.clone(), ANOVACluster {
+                mean_square: 0.0,
+                error_mean_square: 0.0,
+                df: df_between,
+                error_df: df_within,
+                f: 0.0,
+                significance: 1.0,
+            });
+            continue;
+        }
+
+        let mean_square_between = ssb / (df_between as f64);
+        let mean_square_within = ssw / (df_within as f64);
+        let f_statistic = if mean_square_within > 0.0 {
+            mean_square_between / mean_square_within
+        } else {
+            f64::MAX
+        };
+
+        anova_clusters.insert(var_name.clone(), ANOVACluster {
+            mean_square: mean_square_between,
+            error_mean_square: mean_square_within,
+            df: df_between,
+            error_df: df_within,
+            f: f_statistic,
+            significance: f_test_p_value(f_statistic, df_between, df_within),
+        });
+    }
+
+    ANOVATable {
+        clusters: anova_clusters,
+        note: Some(
+            "The F tests should be used only for descriptive purposes because the clusters have been chosen to maximize the differences among cases in different clusters. The observed significance levels are not corrected for this and thus cannot be interpreted as tests of the hypothesis that the cluster means are equal.".to_string()
+        ),
+        interpretation: Some("".to_string()),
+    }
+}
+
+// Sampel acak tanpa pengembalian (Fisher-Yates parsial) sebagai ProcessedData kecil
+fn initialization_sample(buffer: &DataBuffer, sample_size: usize, rng: &mut Mt64) -> ProcessedData {
+    let num_cases = buffer.num_cases();
+    let sample_size = sample_size.min(num_cases);
+
+    let mut indices: Vec<usize> = (0..num_cases).collect();
+    if sample_size < num_cases {
+        for i in 0..sample_size {
+            let j = i + ((rng.next_u64() % ((num_cases - i) as u64)) as usize);
+            indices.swap(i, j);
+        }
+        indices.truncate(sample_size);
+    }
+
+    let data_matrix: Vec<Vec<f64>> = indices
+        .iter()
+        .map(|&index| buffer.row(index).to_vec())
+        .collect();
+
+    ProcessedData {
+        variables: buffer.variables.clone(),
+        case_numbers: indices
+            .iter()
+            .map(|&index| (index + 1) as i32)
+            .collect(),
+        data_matrix,
+        case_names: None,
+        total_cases: sample_size,
+        missing_cases: 0,
+        supplied_centers: None,
+    }
+}
+
+fn centers_to_map(variables: &[String], centers: &[Vec<f64>]) -> HashMap<String, Vec<f64>> {
+    variables
+        .iter()
+        .enumerate()
+        .map(|(i, var)| {
+            (
+                var.clone(),
+                centers
+                    .iter()
+                    .map(|center| center[i])
+                    .collect(),
+            )
+        })
+        .collect()
+}
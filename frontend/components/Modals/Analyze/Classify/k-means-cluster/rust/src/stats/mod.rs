@@ -4,7 +4,9 @@ pub mod case_count;
 pub mod cluster_centers;
 pub mod cluster_membership;
 pub mod cluster_plot;
+pub mod cluster_validity;
 pub mod common;
 pub mod initialize_clusters;
 pub mod iteration_history;
+pub mod mini_batch;
 pub mod preprocess_data;
@@ -1,9 +1,12 @@
 use crate::models::{
     config::KMeansConfig,
-    data::{ AnalysisData, DataValue },
+    data::{ AnalysisData, DataRecord, DataValue },
     result::ProcessedData,
 };
 
+// Nama kolom nomor cluster pada tabel pusat yang dibaca maupun ditulis
+pub const CLUSTER_COLUMN: &str = "CLUSTER_";
+
 pub fn preprocess_data(
     data: &AnalysisData,
     config: &KMeansConfig
@@ -67,11 +70,9 @@ pub fn preprocess_data(
                 data_matrix.push(row);
                 case_numbers.push((case_idx + 1) as i32);
             }
-        } else if use_pair_wise {
-            if non_missing_count > 0 {
-                data_matrix.push(row);
-                case_numbers.push((case_idx + 1) as i32);
-            }
+        } else if use_pair_wise && non_missing_count > 0 {
+            data_matrix.push(row);
+            case_numbers.push((case_idx + 1) as i32);
         }
     }
 
@@ -121,6 +122,12 @@ pub fn preprocess_data(
 
     let missing_cases = num_cases - data_matrix.len();
 
+    let supplied_centers = if config.main.read_initial {
+        Some(extract_supplied_centers(&data.initial_centers_data, &variables, config)?)
+    } else {
+        None
+    };
+
     Ok(ProcessedData {
         variables,
         data_matrix,
@@ -128,5 +135,74 @@ pub fn preprocess_data(
         case_names,
         total_cases: num_cases,
         missing_cases,
+        supplied_centers,
     })
 }
+
+// Baca pusat cluster dari tabel pusat yang diberikan: satu baris per cluster, kolom
+// dicocokkan dengan nama variabel, diurutkan menurut kolom CLUSTER_ bila tersedia
+fn extract_supplied_centers(
+    records: &[DataRecord],
+    variables: &[String],
+    config: &KMeansConfig
+) -> Result<Vec<Vec<f64>>, String> {
+    if records.is_empty() {
+        return Err(
+            "Reading initial centers was requested but no center table was supplied".to_string()
+        );
+    }
+    if records.len() != (config.main.cluster as usize) {
+        return Err(
+            format!(
+                "The center table has {} rows but {} clusters were requested",
+                records.len(),
+                config.main.cluster
+            )
+        );
+    }
+
+    let numeric = |value: &DataValue| -> Option<f64> {
+        match value {
+            DataValue::Number(v) => Some(*v as f64),
+            DataValue::NumberFloat(v) => Some(*v),
+            DataValue::Currency(v) => Some(*v),
+            DataValue::Scientific(v) => Some(*v),
+            DataValue::Percentage(v) => Some(*v),
+            _ => None,
+        }
+    };
+
+    let mut rows = Vec::with_capacity(records.len());
+    for (row_idx, record) in records.iter().enumerate() {
+        let mut center = Vec::with_capacity(variables.len());
+        for var in variables {
+            match record.values.get(var).and_then(numeric) {
+                Some(val) => center.push(val),
+                None => {
+                    return Err(
+                        format!(
+                            "The center table has no numeric value for variable '{}' in row {}",
+                            var,
+                            row_idx + 1
+                        )
+                    );
+                }
+            }
+        }
+
+        let cluster = record.values.get(CLUSTER_COLUMN).and_then(numeric);
+        rows.push((cluster, center));
+    }
+
+    // Urutkan menurut nomor cluster jika semua baris memilikinya
+    if rows.iter().all(|(cluster, _)| cluster.is_some()) {
+        rows.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
+    }
+
+    Ok(
+        rows
+            .into_iter()
+            .map(|(_, center)| center)
+            .collect()
+    )
+}
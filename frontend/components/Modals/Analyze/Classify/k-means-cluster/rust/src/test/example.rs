@@ -0,0 +1,382 @@
+use std::collections::HashMap;
+
+use crate::models::{
+    config::{ InitializationConfig, KMeansConfig, MainConfig, MiniBatchConfig, ValidityConfig },
+    data::{ AnalysisData, DataBuffer, DataRecord, DataValue },
+    result::ProcessedData,
+};
+use crate::stats::core::*;
+
+// Tiga kelompok satu dimensi yang terpisah jelas: {0,1,2}, {10,11,12}, {30,31,32}
+const THREE_GROUPS: [f64; 9] = [0.0, 1.0, 2.0, 10.0, 11.0, 12.0, 30.0, 31.0, 32.0];
+
+fn single_variable_data(values: &[f64]) -> ProcessedData {
+    ProcessedData {
+        variables: vec!["x".to_string()],
+        data_matrix: values
+            .iter()
+            .map(|&v| vec![v])
+            .collect(),
+        case_numbers: (1..=values.len() as i32).collect(),
+        case_names: None,
+        total_cases: values.len(),
+        missing_cases: 0,
+        supplied_centers: None,
+    }
+}
+
+fn config_with_clusters(cluster: i32) -> KMeansConfig {
+    KMeansConfig {
+        main: MainConfig {
+            target_var: Some(vec!["x".to_string()]),
+            cluster,
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
+
+fn sorted_centers(centers: Vec<Vec<f64>>) -> Vec<f64> {
+    let mut values: Vec<f64> = centers
+        .into_iter()
+        .map(|c| c[0])
+        .collect();
+    values.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    values
+}
+
+fn assert_close(actual: f64, expected: f64, label: &str) {
+    assert!((actual - expected).abs() < 1e-10, "{}: {} != {}", label, actual, expected);
+}
+
+// Nilai acuan dihitung langsung dari partisi optimal: k = 2 memberi {0..12} dan {30..32},
+// k = 3 memberi ketiga kelompok
+#[test]
+fn cluster_validity_matches_reference_values() {
+    let config = KMeansConfig {
+        validity: ValidityConfig {
+            evaluate_range: true,
+            min_clusters: 2,
+            max_clusters: 3,
+        },
+        ..config_with_clusters(3)
+    };
+    let validity = evaluate_cluster_range(&single_variable_data(&THREE_GROUPS), &config).unwrap();
+
+    // (k, SSW, silhouette, Calinski-Harabasz, Davies-Bouldin)
+    let expected = [
+        (2, 156.0, 0.8002953431776393, 4375.0 / 78.0, 17.0 / 75.0),
+        (3, 6.0, 140335.0 / 158004.0, 700.0, 1.0 / 9.0),
+    ];
+    assert_eq!(validity.solutions.len(), expected.len());
+    for (solution, &(k, within_ss, silhouette, calinski_harabasz, davies_bouldin)) in validity.solutions
+        .iter()
+        .zip(expected.iter()) {
+        assert_eq!(solution.clusters, k);
+        assert_close(solution.within_ss, within_ss, "within SS");
+        assert_close(solution.silhouette.unwrap(), silhouette, "silhouette");
+        assert_close(solution.calinski_harabasz.unwrap(), calinski_harabasz, "Calinski-Harabasz");
+        assert_close(solution.davies_bouldin.unwrap(), davies_bouldin, "Davies-Bouldin");
+    }
+    assert_eq!(
+        validity.note.as_deref(),
+        Some(
+            "Largest average silhouette at 3 clusters; largest Calinski-Harabasz index at 3 clusters; smallest Davies-Bouldin index at 3 clusters."
+        )
+    );
+}
+
+#[test]
+fn cluster_validity_rejects_range_reaching_the_number_of_cases() {
+    let config = KMeansConfig {
+        validity: ValidityConfig {
+            evaluate_range: true,
+            min_clusters: 2,
+            max_clusters: 9,
+        },
+        ..config_with_clusters(3)
+    };
+    assert!(evaluate_cluster_range(&single_variable_data(&THREE_GROUPS), &config).is_err());
+}
+
+// Dengan seed tetap, k-means++ memberi pusat awal yang sama dan menemukan ketiga kelompok
+#[test]
+fn k_means_plus_plus_with_fixed_seed_recovers_separated_groups() {
+    let data = single_variable_data(&THREE_GROUPS);
+    let config = KMeansConfig {
+        initialization: InitializationConfig {
+            k_means_plus_plus: true,
+            random_starts: 5,
+            seed: true,
+            seed_value: 2000000,
+        },
+        ..config_with_clusters(3)
+    };
+
+    let (first, note) = select_initial_centers(&data, &config, 3).unwrap();
+    let (second, _) = select_initial_centers(&data, &config, 3).unwrap();
+    assert_eq!(first, second);
+    assert!(note.unwrap().contains("out of 5 random starts"));
+
+    let centers = sorted_centers(iterate_cluster_centers(&data, first, &config));
+    assert_eq!(centers, vec![1.0, 11.0, 31.0]);
+}
+
+fn record(values: &[(&str, DataValue)]) -> DataRecord {
+    DataRecord {
+        values: values
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.clone()))
+            .collect::<HashMap<_, _>>(),
+    }
+}
+
+// Dua variabel, x dan y, dalam dua dataset target terpisah seperti yang dikirim dialog
+fn two_variable_data(cases: &[(f64, f64)], initial_centers_data: Vec<DataRecord>) -> AnalysisData {
+    AnalysisData {
+        target_data: vec![
+            cases
+                .iter()
+                .map(|&(x, _)| record(&[("x", DataValue::NumberFloat(x))]))
+                .collect(),
+            cases
+                .iter()
+                .map(|&(_, y)| record(&[("y", DataValue::NumberFloat(y))]))
+                .collect()
+        ],
+        case_data: vec![],
+        target_data_defs: vec![],
+        case_data_defs: vec![],
+        initial_centers_data,
+    }
+}
+
+fn read_initial_config() -> KMeansConfig {
+    KMeansConfig {
+        main: MainConfig {
+            target_var: Some(vec!["x".to_string(), "y".to_string()]),
+            read_initial: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
+
+const FOUR_CASES: [(f64, f64); 4] = [
+    (0.0, 0.0),
+    (1.0, 1.0),
+    (9.0, 9.0),
+    (10.0, 10.0),
+];
+
+// Kolom tabel pusat dicocokkan menurut nama, kolom lain diabaikan, dan baris diurutkan
+// menurut CLUSTER_
+#[test]
+fn supplied_centers_are_matched_by_name_and_ordered_by_cluster() {
+    let centers = vec![
+        record(
+            &[
+                ("y", DataValue::NumberFloat(8.0)),
+                ("label", DataValue::Text("far".to_string())),
+                (CLUSTER_COLUMN, DataValue::Number(2)),
+                ("x", DataValue::Number(7)),
+            ]
+        ),
+        record(
+            &[
+                ("x", DataValue::NumberFloat(0.5)),
+                (CLUSTER_COLUMN, DataValue::Number(1)),
+                ("y", DataValue::NumberFloat(1.5)),
+            ]
+        )
+    ];
+    let data = preprocess_data(&two_variable_data(&FOUR_CASES, centers), &read_initial_config()).unwrap();
+
+    assert_eq!(data.supplied_centers, Some(vec![vec![0.5, 1.5], vec![7.0, 8.0]]));
+}
+
+#[test]
+fn supplied_centers_must_cover_every_variable_and_cluster() {
+    let config = read_initial_config();
+    let missing_variable = vec![
+        record(&[("x", DataValue::NumberFloat(0.0))]),
+        record(&[("x", DataValue::NumberFloat(10.0))])
+    ];
+    let error = preprocess_data(&two_variable_data(&FOUR_CASES, missing_variable), &config).unwrap_err();
+    assert!(error.contains("no numeric value for variable 'y' in row 1"), "{}", error);
+
+    let one_row = vec![
+        record(&[("x", DataValue::NumberFloat(0.0)), ("y", DataValue::NumberFloat(0.0))])
+    ];
+    let error = preprocess_data(&two_variable_data(&FOUR_CASES, one_row), &config).unwrap_err();
+    assert!(error.contains("has 1 rows but 2 clusters"), "{}", error);
+
+    let error = preprocess_data(&two_variable_data(&FOUR_CASES, vec![]), &config).unwrap_err();
+    assert!(error.contains("no center table was supplied"), "{}", error);
+}
+
+// Classify-only memakai pusat awal apa adanya; iterasi biasa memindahkan pusat ke rata-rata
+#[test]
+fn classify_only_assigns_cases_to_the_supplied_centers() {
+    let centers = vec![
+        record(
+            &[
+                (CLUSTER_COLUMN, DataValue::Number(1)),
+                ("x", DataValue::NumberFloat(2.0)),
+                ("y", DataValue::NumberFloat(2.0)),
+            ]
+        ),
+        record(
+            &[
+                (CLUSTER_COLUMN, DataValue::Number(2)),
+                ("x", DataValue::NumberFloat(8.0)),
+                ("y", DataValue::NumberFloat(8.0)),
+            ]
+        )
+    ];
+    let analysis_data = two_variable_data(&FOUR_CASES, centers);
+    let classify_only = KMeansConfig {
+        main: MainConfig {
+            classify_only: true,
+            iterate_classify: false,
+            ..read_initial_config().main
+        },
+        ..read_initial_config()
+    };
+    let data = preprocess_data(&analysis_data, &classify_only).unwrap();
+
+    let final_centers = generate_final_cluster_centers(&data, &classify_only).unwrap();
+    assert_eq!(final_centers.centers["x"], vec![2.0, 8.0]);
+    assert_eq!(final_centers.centers["y"], vec![2.0, 8.0]);
+    assert!(final_centers.note.unwrap().starts_with("Classify only"));
+
+    let membership = generate_cluster_membership(&data, &classify_only).unwrap();
+    let clusters: Vec<i32> = membership.data
+        .iter()
+        .map(|m| m.cluster)
+        .collect();
+    assert_eq!(clusters, vec![1, 1, 2, 2]);
+    assert_close(membership.data[0].distance, 8.0_f64.sqrt(), "distance to center 1");
+    assert_close(membership.data[1].distance, 2.0_f64.sqrt(), "distance to center 1");
+
+    let iterated = generate_final_cluster_centers(&data, &read_initial_config()).unwrap();
+    assert_eq!(iterated.centers["x"], vec![0.5, 9.5]);
+}
+
+// Dataset pusat akhir dapat dibaca kembali sebagai pusat awal
+#[test]
+fn final_centers_dataset_round_trips_as_initial_centers() {
+    let config = KMeansConfig {
+        main: MainConfig {
+            target_var: Some(vec!["x".to_string(), "y".to_string()]),
+            write_final: true,
+            new_data: Some("centers".to_string()),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let data = preprocess_data(&two_variable_data(&FOUR_CASES, vec![]), &config).unwrap();
+    let dataset = generate_final_centers_dataset(&data, &config).unwrap();
+    assert_eq!(dataset.dataset_name.as_deref(), Some("centers"));
+    assert_eq!(dataset.variables, vec![CLUSTER_COLUMN, "x", "y"]);
+
+    let reread = preprocess_data(
+        &two_variable_data(&FOUR_CASES, dataset.records),
+        &read_initial_config()
+    ).unwrap();
+    let mut supplied = reread.supplied_centers.unwrap();
+    supplied.sort_by(|a, b| a[0].partial_cmp(&b[0]).unwrap());
+    assert_eq!(supplied, vec![vec![0.5, 0.5], vec![9.5, 9.5]]);
+}
+
+// Tiga kelompok dua dimensi berpusat di (0, 0), (10, 0) dan (0, 10); tiap kelompok berupa
+// grid simetris 10 x 15 sehingga rata-ratanya tepat di pusat kelompok
+fn separated_groups() -> Vec<Vec<f64>> {
+    [(0.0, 0.0), (10.0, 0.0), (0.0, 10.0)]
+        .iter()
+        .flat_map(|&(cx, cy)| {
+            (0..150).map(move |i| {
+                vec![
+                    cx + (((i % 10) as f64) - 4.5) * 0.1,
+                    cy + (((i / 10) as f64) - 7.0) * 0.1
+                ]
+            })
+        })
+        .collect()
+}
+
+fn sorted_center_rows(mut centers: Vec<Vec<f64>>) -> Vec<Vec<f64>> {
+    centers.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    centers
+}
+
+#[test]
+fn mini_batch_with_fixed_seed_reaches_full_batch_centers() {
+    let rows = separated_groups();
+    let config = KMeansConfig {
+        main: MainConfig {
+            target_var: Some(vec!["x".to_string(), "y".to_string()]),
+            cluster: 3,
+            ..Default::default()
+        },
+        initialization: InitializationConfig {
+            seed: true,
+            ..Default::default()
+        },
+        mini_batch: MiniBatchConfig {
+            mini_batch: true,
+            batch_size: 30,
+            max_batches: 300,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let full_batch_data = ProcessedData {
+        variables: vec!["x".to_string(), "y".to_string()],
+        case_numbers: (1..=rows.len() as i32).collect(),
+        total_cases: rows.len(),
+        data_matrix: rows.clone(),
+        case_names: None,
+        missing_cases: 0,
+        supplied_centers: None,
+    };
+    let (initial, _) = select_initial_centers(&full_batch_data, &config, 3).unwrap();
+    let full_batch = sorted_center_rows(iterate_cluster_centers(&full_batch_data, initial, &config));
+    for (center, expected) in full_batch.iter().zip([[0.0, 0.0], [0.0, 10.0], [10.0, 0.0]]) {
+        assert_close(center[0], expected[0], "full-batch x");
+        assert_close(center[1], expected[1], "full-batch y");
+    }
+
+    // Data dikirim dalam beberapa potongan seperti dari dialog
+    let mut buffer = DataBuffer::new(vec!["x".to_string(), "y".to_string()]);
+    let flat: Vec<f64> = rows.concat();
+    for chunk in flat.chunks(2 * 100) {
+        append_chunk(&mut buffer, chunk, &config).unwrap();
+    }
+    assert_eq!(buffer.num_cases(), rows.len());
+
+    let result = run_mini_batch_k_means(&buffer, &config).unwrap();
+    let centers_map = result.final_cluster_centers.unwrap().centers;
+    let mini_batch = sorted_center_rows(
+        (0..3)
+            .map(|c| vec![centers_map["x"][c], centers_map["y"][c]])
+            .collect()
+    );
+    for (mini, full) in mini_batch.iter().zip(full_batch.iter()) {
+        for (m, f) in mini.iter().zip(full.iter()) {
+            assert!((m - f).abs() < 0.05, "mini-batch {:?} vs full-batch {:?}", mini, full);
+        }
+    }
+
+    let counts: Vec<usize> = result.cases_count
+        .unwrap()
+        .clusters.values()
+        .copied()
+        .collect();
+    assert_eq!(counts, vec![150, 150, 150]);
+
+    // Seed yang sama memberi pusat yang sama
+    let again = run_mini_batch_k_means(&buffer, &config).unwrap();
+    assert_eq!(again.final_cluster_centers.unwrap().centers, centers_map);
+}
@@ -1,11 +1,14 @@
 use wasm_bindgen::JsValue;
 use serde::Serialize;
 
+use crate::models::data::DataValue;
 use crate::models::result::{
     ClusterMembership,
     KMeansResult,
     DistancesBetweenCenters,
     ClusterPlot,
+    ClusterValidity,
+    FinalCentersDataset,
 };
 
 pub fn string_to_js_error(error: String) -> JsValue {
@@ -32,6 +35,8 @@ struct FormatResult {
     anova: Option<FormattedANOVATable>,
     cases_count: Option<FormattedCaseCountTable>,
     cluster_plot: Option<ClusterPlot>,
+    cluster_validity: Option<ClusterValidity>,
+    final_centers_dataset: Option<FormattedFinalCentersDataset>,
 }
 
 #[derive(Serialize)]
@@ -106,6 +111,16 @@ struct ClusterCount {
     count: usize,
 }
 
+// Baris dataset pusat akhir mengikuti urutan kolom pada `variables`
+#[derive(Serialize)]
+struct FormattedFinalCentersDataset {
+    dataset_name: Option<String>,
+    variables: Vec<String>,
+    rows: Vec<Vec<Option<f64>>>,
+    note: Option<String>,
+    interpretation: Option<String>,
+}
+
 impl FormatResult {
     fn from_clustering_result(result: &KMeansResult) -> Self {
         let initial_centers = result.initial_centers.as_ref().map(|centers| {
@@ -217,6 +232,10 @@ impl FormatResult {
             }
         });
 
+        let final_centers_dataset = result.final_centers_dataset
+            .as_ref()
+            .map(format_final_centers_dataset);
+
         FormatResult {
             initial_centers,
             iteration_history,
@@ -226,6 +245,34 @@ impl FormatResult {
             anova,
             cases_count,
             cluster_plot: result.cluster_plot.clone(),
+            cluster_validity: result.cluster_validity.clone(),
+            final_centers_dataset,
         }
     }
 }
+
+fn format_final_centers_dataset(dataset: &FinalCentersDataset) -> FormattedFinalCentersDataset {
+    let rows = dataset.records
+        .iter()
+        .map(|record| {
+            dataset.variables
+                .iter()
+                .map(|var| {
+                    match record.values.get(var) {
+                        Some(DataValue::Number(v)) => Some(*v as f64),
+                        Some(DataValue::NumberFloat(v)) => Some(*v),
+                        _ => None,
+                    }
+                })
+                .collect()
+        })
+        .collect();
+
+    FormattedFinalCentersDataset {
+        dataset_name: dataset.dataset_name.clone(),
+        variables: dataset.variables.clone(),
+        rows,
+        note: dataset.note.clone(),
+        interpretation: dataset.interpretation.clone(),
+    }
+}
@@ -25,7 +25,8 @@ impl KMeansClusterAnalysis {
         case_data: JsValue,
         target_data_defs: JsValue,
         case_data_defs: JsValue,
-        config_data: JsValue
+        config_data: JsValue,
+        initial_centers_data: JsValue
     ) -> Result<KMeansClusterAnalysis, JsValue> {
         // Initialize error collector
         let mut error_collector = ErrorCollector::default();
@@ -85,6 +86,23 @@ impl KMeansClusterAnalysis {
             }
         };
 
+        // Tabel pusat awal bersifat opsional
+        let initial_centers_data: Vec<DataRecord> = if
+            initial_centers_data.is_undefined() ||
+            initial_centers_data.is_null()
+        {
+            Vec::new()
+        } else {
+            match serde_wasm_bindgen::from_value(initial_centers_data) {
+                Ok(data) => data,
+                Err(e) => {
+                    let msg = format!("Failed to parse initial centers data: {}", e);
+                    error_collector.add_error("Constructor : Initial Centers Data", &msg);
+                    return Err(string_to_js_error(msg));
+                }
+            }
+        };
+
         if is_all_null(&target_data) {
             let msg = "Target data contains all null values".to_string();
             error_collector.add_error("Constructor : Target Data Validation", &msg);
@@ -136,6 +154,7 @@ impl KMeansClusterAnalysis {
             case_data,
             target_data_defs,
             case_data_defs,
+            initial_centers_data,
         };
 
         // Create instance
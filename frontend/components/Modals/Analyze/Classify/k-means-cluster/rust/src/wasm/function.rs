@@ -1,6 +1,10 @@
 use wasm_bindgen::prelude::*;
 
-use crate::models::{ config::KMeansConfig, data::AnalysisData, result::KMeansResult };
+use crate::models::{
+    config::KMeansConfig,
+    data::{ AnalysisData, DataBuffer },
+    result::{ KMeansResult, ProcessedData },
+};
 use crate::utils::converter::format_result;
 use crate::utils::{ log::FunctionLogger, converter::string_to_js_error, error::ErrorCollector };
 
@@ -12,6 +16,9 @@ pub fn run_analysis(
     error_collector: &mut ErrorCollector,
     logger: &mut FunctionLogger
 ) -> Result<Option<KMeansResult>, JsValue> {
+    // Inisialisasi acak memakai seed yang sama di semua tabel
+    let config = &core::resolve_initialization_seed(config);
+
     logger.add_log("preprocess_data");
     let preprocessed_data = match core::preprocess_data(data, config) {
         Ok(processed) => { processed }
@@ -21,6 +28,29 @@ pub fn run_analysis(
         }
     };
 
+    // Mode mini-batch memakai buffer bersambung dan melewati tabel full-batch
+    if config.mini_batch.mini_batch {
+        logger.add_log("run_mini_batch_k_means");
+        // Baris dipindahkan satu per satu ke buffer sehingga tidak ada dua salinan penuh
+        let ProcessedData { variables, data_matrix, total_cases, missing_cases, .. } =
+            preprocessed_data;
+        let mut buffer = DataBuffer::new(variables);
+        buffer.values.reserve(data_matrix.len() * buffer.variables.len());
+        for case in data_matrix {
+            buffer.values.extend_from_slice(&case);
+        }
+        buffer.total_cases = total_cases;
+        buffer.missing_cases = missing_cases;
+
+        return match core::run_mini_batch_k_means(&buffer, config) {
+            Ok(result) => Ok(Some(result)),
+            Err(e) => {
+                error_collector.add_error("Run Analysis : Mini-Batch K-Means", &e);
+                Err(string_to_js_error(e))
+            }
+        };
+    }
+
     logger.add_log("initialize_clusters");
     let mut initial_centers = None;
     if config.options.initial_cluster {
@@ -36,12 +66,14 @@ pub fn run_analysis(
 
     logger.add_log("iteration_history");
     let mut iteration_history = None;
-    match core::generate_iteration_history(&preprocessed_data, config) {
-        Ok(history) => {
-            iteration_history = Some(history);
-        }
-        Err(e) => {
-            error_collector.add_error("Run Analysis : Iteration History", &e);
+    if !config.main.classify_only {
+        match core::generate_iteration_history(&preprocessed_data, config) {
+            Ok(history) => {
+                iteration_history = Some(history);
+            }
+            Err(e) => {
+                error_collector.add_error("Run Analysis : Iteration History", &e);
+            }
         }
     }
 
@@ -85,7 +117,7 @@ pub fn run_analysis(
     let mut anova = None;
     if config.options.anova {
         logger.add_log("calculate_anova");
-        match core::calculate_anova(&preprocessed_data, &config) {
+        match core::calculate_anova(&preprocessed_data, config) {
             Ok(result) => {
                 anova = Some(result);
             }
@@ -97,7 +129,7 @@ pub fn run_analysis(
 
     let mut cases_count = None;
     logger.add_log("generate_case_count");
-    match core::generate_case_count(&preprocessed_data, &config) {
+    match core::generate_case_count(&preprocessed_data, config) {
         Ok(count) => {
             cases_count = Some(count);
         }
@@ -109,7 +141,7 @@ pub fn run_analysis(
     let mut cluster_plot = None;
     if config.options.cluster_plot {
         logger.add_log("create_cluster_plot");
-        match core::create_cluster_plot(&preprocessed_data, &config) {
+        match core::create_cluster_plot(&preprocessed_data, config) {
             Ok(plot) => {
                 cluster_plot = Some(plot);
             }
@@ -119,6 +151,32 @@ pub fn run_analysis(
         }
     }
 
+    let mut cluster_validity = None;
+    if config.validity.evaluate_range {
+        logger.add_log("evaluate_cluster_range");
+        match core::evaluate_cluster_range(&preprocessed_data, config) {
+            Ok(validity) => {
+                cluster_validity = Some(validity);
+            }
+            Err(e) => {
+                error_collector.add_error("Run Analysis : Evaluate Cluster Range", &e);
+            }
+        }
+    }
+
+    let mut final_centers_dataset = None;
+    if config.main.write_final {
+        logger.add_log("generate_final_centers_dataset");
+        match core::generate_final_centers_dataset(&preprocessed_data, config) {
+            Ok(dataset) => {
+                final_centers_dataset = Some(dataset);
+            }
+            Err(e) => {
+                error_collector.add_error("Run Analysis : Final Centers Dataset", &e);
+            }
+        }
+    }
+
     let result = KMeansResult {
         initial_centers,
         iteration_history,
@@ -128,6 +186,8 @@ pub fn run_analysis(
         anova,
         cases_count,
         cluster_plot,
+        cluster_validity,
+        final_centers_dataset,
     };
 
     Ok(Some(result))
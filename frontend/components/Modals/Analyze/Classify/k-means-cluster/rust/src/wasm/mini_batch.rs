@@ -0,0 +1,101 @@
+use wasm_bindgen::prelude::*;
+
+use crate::models::{ config::KMeansConfig, data::DataBuffer, result::KMeansResult };
+use crate::utils::{ converter::string_to_js_error, error::ErrorCollector, log::FunctionLogger };
+use crate::stats::core;
+use crate::wasm::function;
+
+// Analisis K-Means mini-batch: data dikirim bertahap dalam potongan Float64Array
+// (baris demi baris) lalu dijalankan sekali dengan run()
+#[wasm_bindgen]
+pub struct MiniBatchKMeansAnalysis {
+    config: KMeansConfig,
+    buffer: DataBuffer,
+    result: Option<KMeansResult>,
+    error_collector: ErrorCollector,
+    logger: FunctionLogger,
+}
+
+#[wasm_bindgen]
+impl MiniBatchKMeansAnalysis {
+    #[wasm_bindgen(constructor)]
+    pub fn new(config_data: JsValue) -> Result<MiniBatchKMeansAnalysis, JsValue> {
+        let mut error_collector = ErrorCollector::default();
+        let logger = FunctionLogger::default();
+
+        let config: KMeansConfig = match serde_wasm_bindgen::from_value(config_data) {
+            Ok(data) => data,
+            Err(e) => {
+                let msg =
+                    format!("Failed to parse configuration: {}. Ensure field names match the expected format.", e);
+                error_collector.add_error("Constructor : Config", &msg);
+                return Err(string_to_js_error(msg));
+            }
+        };
+
+        if config.main.cluster <= 0 {
+            let msg = "Number of clusters must be positive".to_string();
+            error_collector.add_error("Config : Validation : Clusters", &msg);
+            return Err(string_to_js_error(msg));
+        }
+
+        let variables = config.main.target_var.as_ref().cloned().unwrap_or_default();
+        if variables.is_empty() {
+            let msg = "No clustering variables were specified".to_string();
+            error_collector.add_error("Config : Validation : Variables", &msg);
+            return Err(string_to_js_error(msg));
+        }
+
+        Ok(MiniBatchKMeansAnalysis {
+            config,
+            buffer: DataBuffer::new(variables),
+            result: None,
+            error_collector,
+            logger,
+        })
+    }
+
+    pub fn append_chunk(&mut self, values: &[f64]) -> Result<usize, JsValue> {
+        match core::append_chunk(&mut self.buffer, values, &self.config) {
+            Ok(added) => Ok(added),
+            Err(e) => {
+                self.error_collector.add_error("Append Chunk", &e);
+                Err(string_to_js_error(e))
+            }
+        }
+    }
+
+    pub fn run(&mut self) -> Result<(), JsValue> {
+        self.logger.add_log("run_mini_batch_k_means");
+        match core::run_mini_batch_k_means(&self.buffer, &self.config) {
+            Ok(result) => {
+                self.result = Some(result);
+                Ok(())
+            }
+            Err(e) => {
+                self.error_collector.add_error("Run Analysis : Mini-Batch K-Means", &e);
+                Err(string_to_js_error(e))
+            }
+        }
+    }
+
+    pub fn get_results(&self) -> Result<JsValue, JsValue> {
+        function::get_results(&self.result)
+    }
+
+    pub fn get_formatted_results(&self) -> Result<JsValue, JsValue> {
+        function::get_formatted_results(&self.result)
+    }
+
+    pub fn get_all_log(&self) -> Result<JsValue, JsValue> {
+        function::get_all_log(&self.logger)
+    }
+
+    pub fn get_all_errors(&self) -> JsValue {
+        function::get_all_errors(&self.error_collector)
+    }
+
+    pub fn clear_errors(&mut self) -> JsValue {
+        function::clear_errors(&mut self.error_collector)
+    }
+}
@@ -1,2 +1,3 @@
 pub mod constructor;
 pub mod function;
+pub mod mini_batch;
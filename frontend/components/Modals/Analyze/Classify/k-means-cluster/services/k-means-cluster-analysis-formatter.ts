@@ -344,6 +344,68 @@ export function transformKMeansResult(
         resultJson.tables.push(table);
     }
 
+    // 8. Final Cluster Centers Dataset
+    if (data.final_centers_dataset) {
+        const dataset = data.final_centers_dataset;
+        const table: Table = {
+            key: "final_centers_dataset",
+            title: dataset.dataset_name
+                ? `Final Cluster Centers Dataset (${dataset.dataset_name})`
+                : "Final Cluster Centers Dataset",
+            columnHeaders: dataset.variables.map((variable: string) => ({
+                header: variable,
+                key: variable,
+            })),
+            rows: [],
+            note: dataset.note,
+            interpretation: dataset.interpretation,
+        };
+
+        dataset.rows.forEach((row: (number | null)[]) => {
+            const rowData: any = {
+                rowHeader: [formatDisplayNumber(row[0])],
+            };
+            dataset.variables.slice(1).forEach((variable: string, index: number) => {
+                rowData[variable] = formatDisplayNumber(row[index + 1]);
+            });
+            table.rows.push(rowData);
+        });
+
+        resultJson.tables.push(table);
+    }
+
+    // 9. Cluster Validity
+    if (data.cluster_validity) {
+        const table: Table = {
+            key: "cluster_validity",
+            title: "Cluster Validity",
+            columnHeaders: [
+                { header: "Number of Clusters", key: "clusters" },
+                { header: "Within-Cluster Sum of Squares", key: "within_ss" },
+                { header: "Average Silhouette", key: "silhouette" },
+                { header: "Calinski-Harabasz", key: "calinski_harabasz" },
+                { header: "Davies-Bouldin", key: "davies_bouldin" },
+            ],
+            rows: [],
+            note: data.cluster_validity.note,
+            interpretation: data.cluster_validity.interpretation,
+        };
+
+        data.cluster_validity.solutions.forEach((solution: any) => {
+            table.rows.push({
+                rowHeader: [solution.clusters.toString()],
+                within_ss: formatDisplayNumber(solution.within_ss),
+                silhouette: formatDisplayNumber(solution.silhouette),
+                calinski_harabasz: formatDisplayNumber(
+                    solution.calinski_harabasz
+                ),
+                davies_bouldin: formatDisplayNumber(solution.davies_bouldin),
+            });
+        });
+
+        resultJson.tables.push(table);
+    }
+
     if (data.cluster_plot) {
         const plot = data.cluster_plot;
         const chartData = plot.x.map((xVal: number, i: number) => ({
@@ -4,6 +4,9 @@ import { Variable } from "@/types/Variable";
 import { useVariableStore } from "@/stores/useVariableStore";
 import { useDataStore } from "@/stores/useDataStore";
 import { useResultStore } from "@/stores/useResultStore";
+import { saveFormData } from "@/hooks/useIndexedDB";
+
+export const CENTERS_DATASET_PREFIX = "centers:";
 
 export async function resultKMeans({
     formattedResult,
@@ -196,6 +199,50 @@ export async function resultKMeans({
                 });
             }
 
+            /*
+             * 🗂️ Final Cluster Centers Dataset Result 🗂️
+             * */
+            const finalCentersDatasetTable = findTable("final_centers_dataset");
+            if (finalCentersDatasetTable) {
+                const finalCentersDatasetId = await addAnalytic(logId, {
+                    title: finalCentersDatasetTable.title,
+                    note: finalCentersDatasetTable.note || "",
+                });
+
+                await addStatistic(finalCentersDatasetId, {
+                    title: finalCentersDatasetTable.title,
+                    description:
+                        finalCentersDatasetTable.interpretation ||
+                        `Final Cluster Centers Dataset`,
+                    output_data: JSON.stringify({
+                        tables: [finalCentersDatasetTable],
+                    }),
+                    components: `Final Cluster Centers Dataset`,
+                });
+            }
+
+            /*
+             * 📉 Cluster Validity Result 📉
+             * */
+            const clusterValidityTable = findTable("cluster_validity");
+            if (clusterValidityTable) {
+                const clusterValidityId = await addAnalytic(logId, {
+                    title: clusterValidityTable.title,
+                    note: clusterValidityTable.note || "",
+                });
+
+                await addStatistic(clusterValidityId, {
+                    title: clusterValidityTable.title,
+                    description:
+                        clusterValidityTable.interpretation ||
+                        `Cluster Validity`,
+                    output_data: JSON.stringify({
+                        tables: [clusterValidityTable],
+                    }),
+                    components: `Cluster Validity`,
+                });
+            }
+
             /*
              * ❗ Error Table Result ❗
              * */
@@ -360,3 +407,47 @@ async function saveClusterResults(
         }
     }
 }
+
+/**
+ * Write the final centers as a named dataset (read back by Read Initial > Open Dataset)
+ * or download them as a CSV file
+ */
+export async function writeFinalCentersDataset(
+    dataset: {
+        dataset_name: string | null;
+        variables: string[];
+        rows: (number | null)[][];
+    },
+    configData: any
+) {
+    const records = dataset.rows.map((row) =>
+        Object.fromEntries(
+            dataset.variables.map((name, index) => [name, row[index] ?? ""])
+        )
+    );
+
+    if (configData.main.NewDataset) {
+        await saveFormData(
+            "KMeansCluster",
+            { records },
+            `${CENTERS_DATASET_PREFIX}${(dataset.dataset_name ?? "").trim()}`
+        );
+        return;
+    }
+
+    if (configData.main.DataFile) {
+        const csv = [dataset.variables, ...dataset.rows]
+            .map((row) => row.map((value) => value ?? "").join(","))
+            .join("\n");
+        const fileName = dataset.dataset_name ?? "final_centers";
+        const blob = new Blob([csv], { type: "text/csv;charset=utf-8" });
+        const url = URL.createObjectURL(blob);
+        const a = document.createElement("a");
+        a.href = url;
+        a.download = fileName.endsWith(".csv") ? fileName : `${fileName}.csv`;
+        document.body.appendChild(a);
+        a.click();
+        a.remove();
+        URL.revokeObjectURL(url);
+    }
+}
@@ -1,17 +1,32 @@
-import { getSlicedData, getVarDefs } from "@/hooks/useVariable";
+import { getMaxIndex, getSlicedData, getVarDefs } from "@/hooks/useVariable";
 import { KMeansClusterAnalysisType } from "@/components/Modals/Analyze/Classify/k-means-cluster/types/k-means-cluster-worker";
 import { transformKMeansResult } from "./k-means-cluster-analysis-formatter";
-import { resultKMeans } from "./k-means-cluster-analysis-output";
+import {
+    CENTERS_DATASET_PREFIX,
+    resultKMeans,
+    writeFinalCentersDataset,
+} from "./k-means-cluster-analysis-output";
+import { getFormData } from "@/hooks/useIndexedDB";
 import init, {
     KMeansClusterAnalysis,
+    MiniBatchKMeansAnalysis,
 } from "@/components/Modals/Analyze/Classify/k-means-cluster/rust/pkg/wasm";
 
 export async function analyzeKMeansCluster({
     configData,
     dataVariables,
     variables,
+    initialCentersData,
 }: KMeansClusterAnalysisType) {
     console.log(configData);
+    if (configData.mini_batch?.MiniBatch) {
+        return analyzeMiniBatchKMeansCluster({
+            configData,
+            dataVariables,
+            variables,
+        });
+    }
+
     const TargetVariables = configData.main.TargetVar || [];
     const CaseTargetVariable = configData.main.CaseTarget
         ? [configData.main.CaseTarget]
@@ -44,7 +59,8 @@ export async function analyzeKMeansCluster({
         slicedDataForCaseTarget,
         varDefsForTarget,
         varDefsForCaseTarget,
-        configData
+        configData,
+        configData.main.ReadInitial ? initialCentersData ?? [] : null
     );
 
     const results = kmeans.get_formatted_results();
@@ -63,6 +79,13 @@ export async function analyzeKMeansCluster({
     const formattedResults = transformKMeansResult(results ?? {}, errors);
     console.log("formattedResults", formattedResults);
 
+    if (configData.main.WriteFinal && results?.final_centers_dataset) {
+        await writeFinalCentersDataset(
+            results.final_centers_dataset,
+            configData
+        );
+    }
+
     /*
      * 🎉 Final Result Process 🎯
      * */
@@ -72,3 +95,98 @@ export async function analyzeKMeansCluster({
         variables,
     });
 }
+
+/*
+ * Dataset pusat yang disimpan oleh Write Final > New Dataset, dibaca kembali untuk Read Initial
+ * */
+export async function loadCentersDataset(
+    name: string
+): Promise<Record<string, string | number>[]> {
+    const saved = await getFormData(
+        "KMeansCluster",
+        `${CENTERS_DATASET_PREFIX}${name.trim()}`
+    );
+    if (!saved?.records) {
+        throw new Error(`No saved cluster centers dataset named "${name}".`);
+    }
+    return saved.records;
+}
+
+/*
+ * Mini-batch K-Means: data dikirim ke wasm dalam potongan Float64Array (baris demi baris,
+ * NaN untuk nilai hilang) sehingga tidak perlu membentuk objek per kasus untuk seluruh data.
+ * */
+async function analyzeMiniBatchKMeansCluster({
+    configData,
+    dataVariables,
+    variables,
+}: KMeansClusterAnalysisType) {
+    const TargetVariables = configData.main.TargetVar || [];
+    const columnIndices = TargetVariables.map(
+        (name) => variables.find((v) => v.name === name)?.columnIndex ?? -1
+    );
+    const numRows =
+        getMaxIndex({
+            dataVariables,
+            variables,
+            selectedVariables: TargetVariables,
+        }) + 1;
+    const chunkSize = Math.max(1, configData.mini_batch.ChunkSize ?? 50000);
+
+    await init();
+    const kmeans = new MiniBatchKMeansAnalysis(configData);
+
+    let results: any = null;
+    let thrownError: string | null = null;
+
+    try {
+        for (let start = 0; start < numRows; start += chunkSize) {
+            const end = Math.min(start + chunkSize, numRows);
+            const chunk = new Float64Array((end - start) * columnIndices.length);
+
+            for (let i = start; i < end; i++) {
+                const row = dataVariables[i] ?? [];
+                columnIndices.forEach((columnIndex, j) => {
+                    const rawValue = columnIndex >= 0 ? row[columnIndex] : null;
+                    const stringValue =
+                        rawValue === null || rawValue === undefined
+                            ? ""
+                            : String(rawValue);
+                    chunk[(i - start) * columnIndices.length + j] = parseFloat(
+                        stringValue.replace(",", ".")
+                    );
+                });
+            }
+
+            kmeans.append_chunk(chunk);
+
+            // Beri kesempatan UI memproses event sebelum potongan berikutnya dibentuk
+            await new Promise((resolve) => setTimeout(resolve, 0));
+        }
+
+        kmeans.run();
+        results = kmeans.get_formatted_results();
+    } catch (e) {
+        thrownError = e instanceof Error ? e.message : String(e);
+    }
+
+    const errorsString = kmeans.get_all_errors();
+
+    let errors: string[] = [];
+    if (errorsString) {
+        errors = errorsString
+            .split("\n")
+            .filter((line: string) => line.trim() !== "");
+    }
+    if (thrownError && !errors.includes(thrownError)) {
+        errors.push(thrownError);
+    }
+
+    const formattedResults = transformKMeansResult(results ?? {}, errors);
+
+    await resultKMeans({
+        formattedResult: formattedResults ?? [],
+        configData,
+        variables,
+    });
+}
@@ -6,6 +6,7 @@ export type KMeansClusterAnalysisType = {
     configData: KMeansClusterType;
     dataVariables: any[];
     variables: any[];
+    initialCentersData?: any[];
 };
 
 export type KMeansClusterFinalResultType = {
@@ -30,7 +30,10 @@ export type KMeansClusterDialogProps = {
     ) => void;
     data: KMeansClusterMainType;
     globalVariables: string[];
-    onContinue: (mainState: KMeansClusterMainType) => void;
+    onContinue: (
+        mainState: KMeansClusterMainType,
+        initialCentersData?: Record<string, string | number>[]
+    ) => void;
     onReset: () => void;
 };
 
@@ -84,11 +87,34 @@ export type KMeansClusterOptionsProps = {
     data: KMeansClusterOptionsType;
 };
 
+export type KMeansClusterInitializationType = {
+    KMeansPlusPlus: boolean;
+    RandomStarts: number | null;
+    Seed: boolean;
+    SeedValue: number | null;
+};
+
+export type KMeansClusterValidityType = {
+    EvaluateRange: boolean;
+    MinClusters: number | null;
+    MaxClusters: number | null;
+};
+
+export type KMeansClusterMiniBatchType = {
+    MiniBatch: boolean;
+    BatchSize: number | null;
+    MaxBatches: number | null;
+    ChunkSize: number | null;
+};
+
 export type KMeansClusterType = {
     main: KMeansClusterMainType;
     iterate: KMeansClusterIterateType;
     save: KMeansClusterSaveType;
     options: KMeansClusterOptionsType;
+    initialization: KMeansClusterInitializationType;
+    validity: KMeansClusterValidityType;
+    mini_batch: KMeansClusterMiniBatchType;
 };
 
 export type KMeansClusterContainerProps = {
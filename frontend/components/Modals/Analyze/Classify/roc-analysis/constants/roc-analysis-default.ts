@@ -1,4 +1,5 @@
 import {
+    RocAnalysisCutPointsType,
     RocAnalysisDefineGroupsType,
     RocAnalysisDisplayType,
     RocAnalysisMainType,
@@ -45,6 +46,23 @@ export const RocAnalysisDisplayDefault: RocAnalysisDisplayType = {
     ROCPoint: false,
     PRCPoint: false,
     EvalMetrics: false,
+    CompareCurves: false,
+};
+
+export const RocAnalysisCutPointsDefault: RocAnalysisCutPointsType = {
+    OptimalCutPoints: false,
+    Youden: true,
+    ClosestToTopLeft: false,
+    CostWeighted: false,
+    FalsePositiveCost: 1,
+    FalseNegativeCost: 1,
+    Prevalence: null,
+    MinSensitivity: null,
+    MinSpecificity: null,
+    PerformBootStrapping: false,
+    NumOfSamples: 1000,
+    Seed: false,
+    SeedValue: null,
 };
 
 export const RocAnalysisDefault: RocAnalysisType = {
@@ -52,4 +70,5 @@ export const RocAnalysisDefault: RocAnalysisType = {
     defineGroups: RocAnalysisDefineGroupsDefault,
     options: RocAnalysisOptionsDefault,
     display: RocAnalysisDisplayDefault,
+    cutPoints: RocAnalysisCutPointsDefault,
 };
@@ -0,0 +1,370 @@
+import React, {useEffect, useState} from "react";
+import {Dialog, DialogContent, DialogFooter, DialogHeader, DialogTitle,} from "@/components/ui/dialog";
+import {Button} from "@/components/ui/button";
+import {Separator} from "@/components/ui/separator";
+import {
+    RocAnalysisCutPointsProps,
+    RocAnalysisCutPointsType,
+} from "@/components/Modals/Analyze/Classify/roc-analysis/types/roc-analysis";
+import {CheckedState} from "@radix-ui/react-checkbox";
+import {ResizableHandle, ResizablePanel, ResizablePanelGroup,} from "@/components/ui/resizable";
+import {Label} from "@/components/ui/label";
+import {Checkbox} from "@/components/ui/checkbox";
+import {Input} from "@/components/ui/input";
+
+export const RocAnalysisCutPoints = ({
+    isCutPointsOpen,
+    setIsCutPointsOpen,
+    updateFormData,
+    data,
+}: RocAnalysisCutPointsProps) => {
+    const [cutPointsState, setCutPointsState] =
+        useState<RocAnalysisCutPointsType>({ ...data });
+    const [isContinueDisabled, setIsContinueDisabled] = useState(false);
+
+    useEffect(() => {
+        if (isCutPointsOpen) {
+            setCutPointsState({ ...data });
+        }
+    }, [isCutPointsOpen, data]);
+
+    useEffect(() => {
+        const noCriterion =
+            !cutPointsState.Youden &&
+            !cutPointsState.ClosestToTopLeft &&
+            !cutPointsState.CostWeighted &&
+            cutPointsState.MinSensitivity === null &&
+            cutPointsState.MinSpecificity === null;
+        const invalidCosts =
+            !(cutPointsState.FalsePositiveCost > 0) ||
+            !(cutPointsState.FalseNegativeCost > 0);
+        const invalidPrevalence =
+            cutPointsState.Prevalence !== null &&
+            (cutPointsState.Prevalence <= 0 || cutPointsState.Prevalence >= 1);
+
+        setIsContinueDisabled(
+            cutPointsState.OptimalCutPoints &&
+                (noCriterion || invalidCosts || invalidPrevalence)
+        );
+    }, [cutPointsState]);
+
+    const handleChange = (
+        field: keyof RocAnalysisCutPointsType,
+        value: CheckedState | number | boolean | string | null
+    ) => {
+        setCutPointsState((prevState) => ({
+            ...prevState,
+            [field]: value,
+        }));
+    };
+
+    const handleNumberChange = (
+        field: keyof RocAnalysisCutPointsType,
+        value: string
+    ) => {
+        handleChange(field, value === "" ? null : Number(value));
+    };
+
+    const handleContinue = () => {
+        Object.entries(cutPointsState).forEach(([key, value]) => {
+            updateFormData(key as keyof RocAnalysisCutPointsType, value);
+        });
+        setIsCutPointsOpen(false);
+    };
+
+    const disabled = !cutPointsState.OptimalCutPoints;
+
+    return (
+        <>
+            {/* Cut Points Dialog */}
+            <Dialog open={isCutPointsOpen} onOpenChange={setIsCutPointsOpen}>
+                <DialogContent className="sm:max-w-md">
+                    <DialogHeader>
+                        <DialogTitle>ROC Analysis: Cut Points</DialogTitle>
+                    </DialogHeader>
+                    <Separator />
+                    <div className="flex items-center space-x-2">
+                        <Checkbox
+                            id="OptimalCutPoints"
+                            checked={cutPointsState.OptimalCutPoints}
+                            onCheckedChange={(checked) =>
+                                handleChange("OptimalCutPoints", checked)
+                            }
+                        />
+                        <label
+                            htmlFor="OptimalCutPoints"
+                            className="text-sm font-medium leading-none peer-disabled:cursor-not-allowed peer-disabled:opacity-70"
+                        >
+                            Optimal Cut Points
+                        </label>
+                    </div>
+                    <ResizablePanelGroup
+                        direction="vertical"
+                        className="min-h-[420px] max-w-md rounded-lg border md:min-w-[150px]"
+                    >
+                        <ResizablePanel defaultSize={45}>
+                            <div className="flex flex-col gap-2 p-2">
+                                <Label className="font-bold">Criteria</Label>
+                                <div className="flex items-center space-x-2">
+                                    <Checkbox
+                                        id="Youden"
+                                        checked={cutPointsState.Youden}
+                                        disabled={disabled}
+                                        onCheckedChange={(checked) =>
+                                            handleChange("Youden", checked)
+                                        }
+                                    />
+                                    <label
+                                        htmlFor="Youden"
+                                        className="text-sm font-medium leading-none peer-disabled:cursor-not-allowed peer-disabled:opacity-70"
+                                    >
+                                        Youden Index
+                                    </label>
+                                </div>
+                                <div className="flex items-center space-x-2">
+                                    <Checkbox
+                                        id="ClosestToTopLeft"
+                                        checked={cutPointsState.ClosestToTopLeft}
+                                        disabled={disabled}
+                                        onCheckedChange={(checked) =>
+                                            handleChange(
+                                                "ClosestToTopLeft",
+                                                checked
+                                            )
+                                        }
+                                    />
+                                    <label
+                                        htmlFor="ClosestToTopLeft"
+                                        className="text-sm font-medium leading-none peer-disabled:cursor-not-allowed peer-disabled:opacity-70"
+                                    >
+                                        Closest to (0,1)
+                                    </label>
+                                </div>
+                                <div className="flex items-center space-x-2">
+                                    <Checkbox
+                                        id="CostWeighted"
+                                        checked={cutPointsState.CostWeighted}
+                                        disabled={disabled}
+                                        onCheckedChange={(checked) =>
+                                            handleChange("CostWeighted", checked)
+                                        }
+                                    />
+                                    <label
+                                        htmlFor="CostWeighted"
+                                        className="text-sm font-medium leading-none peer-disabled:cursor-not-allowed peer-disabled:opacity-70"
+                                    >
+                                        Minimum Misclassification Cost
+                                    </label>
+                                </div>
+                                <div className="flex flex-row w-full items-center gap-2">
+                                    <Label className="w-[150px]">
+                                        Minimum Sensitivity:
+                                    </Label>
+                                    <Input
+                                        type="number"
+                                        min={0}
+                                        max={1}
+                                        step={0.01}
+                                        disabled={disabled}
+                                        value={
+                                            cutPointsState.MinSensitivity ?? ""
+                                        }
+                                        onChange={(e) =>
+                                            handleNumberChange(
+                                                "MinSensitivity",
+                                                e.target.value
+                                            )
+                                        }
+                                    />
+                                </div>
+                                <div className="flex flex-row w-full items-center gap-2">
+                                    <Label className="w-[150px]">
+                                        Minimum Specificity:
+                                    </Label>
+                                    <Input
+                                        type="number"
+                                        min={0}
+                                        max={1}
+                                        step={0.01}
+                                        disabled={disabled}
+                                        value={
+                                            cutPointsState.MinSpecificity ?? ""
+                                        }
+                                        onChange={(e) =>
+                                            handleNumberChange(
+                                                "MinSpecificity",
+                                                e.target.value
+                                            )
+                                        }
+                                    />
+                                </div>
+                            </div>
+                        </ResizablePanel>
+                        <ResizableHandle />
+                        <ResizablePanel defaultSize={28}>
+                            <div className="flex flex-col gap-2 p-2">
+                                <Label className="font-bold">
+                                    Costs and Prevalence
+                                </Label>
+                                <div className="flex flex-row w-full items-center gap-2">
+                                    <Label className="w-[150px]">
+                                        False Positive Cost:
+                                    </Label>
+                                    <Input
+                                        type="number"
+                                        min={0}
+                                        disabled={disabled}
+                                        value={cutPointsState.FalsePositiveCost}
+                                        onChange={(e) =>
+                                            handleChange(
+                                                "FalsePositiveCost",
+                                                Number(e.target.value)
+                                            )
+                                        }
+                                    />
+                                </div>
+                                <div className="flex flex-row w-full items-center gap-2">
+                                    <Label className="w-[150px]">
+                                        False Negative Cost:
+                                    </Label>
+                                    <Input
+                                        type="number"
+                                        min={0}
+                                        disabled={disabled}
+                                        value={cutPointsState.FalseNegativeCost}
+                                        onChange={(e) =>
+                                            handleChange(
+                                                "FalseNegativeCost",
+                                                Number(e.target.value)
+                                            )
+                                        }
+                                    />
+                                </div>
+                                <div className="flex flex-row w-full items-center gap-2">
+                                    <Label className="w-[150px]">
+                                        Prevalence:
+                                    </Label>
+                                    <Input
+                                        type="number"
+                                        min={0}
+                                        max={1}
+                                        step={0.01}
+                                        placeholder="Sample prevalence"
+                                        disabled={disabled}
+                                        value={cutPointsState.Prevalence ?? ""}
+                                        onChange={(e) =>
+                                            handleNumberChange(
+                                                "Prevalence",
+                                                e.target.value
+                                            )
+                                        }
+                                    />
+                                </div>
+                            </div>
+                        </ResizablePanel>
+                        <ResizableHandle />
+                        <ResizablePanel defaultSize={27}>
+                            <div className="flex flex-col gap-2 p-2">
+                                <Label className="font-bold">Bootstrap</Label>
+                                <div className="flex items-center space-x-2">
+                                    <Checkbox
+                                        id="PerformBootStrapping"
+                                        checked={
+                                            cutPointsState.PerformBootStrapping
+                                        }
+                                        disabled={disabled}
+                                        onCheckedChange={(checked) =>
+                                            handleChange(
+                                                "PerformBootStrapping",
+                                                checked
+                                            )
+                                        }
+                                    />
+                                    <label
+                                        htmlFor="PerformBootStrapping"
+                                        className="text-sm font-medium leading-none peer-disabled:cursor-not-allowed peer-disabled:opacity-70"
+                                    >
+                                        Bootstrap Confidence Intervals
+                                    </label>
+                                </div>
+                                <div className="flex flex-row w-full items-center gap-2 pl-6">
+                                    <Label className="w-[126px]">
+                                        Number of Samples:
+                                    </Label>
+                                    <Input
+                                        type="number"
+                                        min={2}
+                                        disabled={
+                                            disabled ||
+                                            !cutPointsState.PerformBootStrapping
+                                        }
+                                        value={cutPointsState.NumOfSamples ?? ""}
+                                        onChange={(e) =>
+                                            handleNumberChange(
+                                                "NumOfSamples",
+                                                e.target.value
+                                            )
+                                        }
+                                    />
+                                </div>
+                                <div className="flex flex-row w-full items-center gap-2 pl-6">
+                                    <Checkbox
+                                        id="Seed"
+                                        checked={cutPointsState.Seed}
+                                        disabled={
+                                            disabled ||
+                                            !cutPointsState.PerformBootStrapping
+                                        }
+                                        onCheckedChange={(checked) =>
+                                            handleChange("Seed", checked)
+                                        }
+                                    />
+                                    <label
+                                        htmlFor="Seed"
+                                        className="w-[102px] text-sm font-medium leading-none peer-disabled:cursor-not-allowed peer-disabled:opacity-70"
+                                    >
+                                        Set Seed:
+                                    </label>
+                                    <Input
+                                        type="number"
+                                        disabled={
+                                            disabled ||
+                                            !cutPointsState.PerformBootStrapping ||
+                                            !cutPointsState.Seed
+                                        }
+                                        value={cutPointsState.SeedValue ?? ""}
+                                        onChange={(e) =>
+                                            handleNumberChange(
+                                                "SeedValue",
+                                                e.target.value
+                                            )
+                                        }
+                                    />
+                                </div>
+                            </div>
+                        </ResizablePanel>
+                    </ResizablePanelGroup>
+                    <DialogFooter className="sm:justify-start">
+                        <Button
+                            disabled={isContinueDisabled}
+                            type="button"
+                            onClick={handleContinue}
+                        >
+                            Continue
+                        </Button>
+                        <Button
+                            type="button"
+                            variant="secondary"
+                            onClick={() => setIsCutPointsOpen(false)}
+                        >
+                            Cancel
+                        </Button>
+                        <Button type="button" variant="secondary">
+                            Help
+                        </Button>
+                    </DialogFooter>
+                </DialogContent>
+            </Dialog>
+        </>
+    );
+};
@@ -29,6 +29,7 @@ export const RocAnalysisDialog = ({
     setIsOptionsOpen,
     setIsDefineGroupsOpen,
     setIsDisplayOpen,
+    setIsCutPointsOpen,
     updateFormData,
     data,
     globalVariables,
@@ -412,6 +413,14 @@ export const RocAnalysisDialog = ({
                                     >
                                         Display...
                                     </Button>
+                                    <Button
+                                        className="w-full"
+                                        type="button"
+                                        variant="secondary"
+                                        onClick={openDialog(setIsCutPointsOpen)}
+                                    >
+                                        Cut Points...
+                                    </Button>
                                 </div>
                             </ResizablePanel>
                         </ResizablePanelGroup>
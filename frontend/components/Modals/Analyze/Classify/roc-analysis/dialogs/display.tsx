@@ -232,6 +232,21 @@ export const RocAnalysisDisplay = ({
                                         Classifier Evaluation Metrics
                                     </label>
                                 </div>
+                                <div className="flex items-center space-x-2">
+                                    <Checkbox
+                                        id="CompareCurves"
+                                        checked={displayState.CompareCurves}
+                                        onCheckedChange={(checked) =>
+                                            handleChange("CompareCurves", checked)
+                                        }
+                                    />
+                                    <label
+                                        htmlFor="CompareCurves"
+                                        className="text-sm font-medium leading-none peer-disabled:cursor-not-allowed peer-disabled:opacity-70"
+                                    >
+                                        Compare ROC Curves
+                                    </label>
+                                </div>
                             </div>
                         </ResizablePanel>
                     </ResizablePanelGroup>
@@ -10,6 +10,7 @@ import {RocAnalysisDefault} from "@/components/Modals/Analyze/Classify/roc-analy
 import {RocAnalysisOptions} from "@/components/Modals/Analyze/Classify/roc-analysis/dialogs/options";
 import {RocAnalysisDisplay} from "@/components/Modals/Analyze/Classify/roc-analysis/dialogs/display";
 import {RocAnalysisDefineGroups} from "@/components/Modals/Analyze/Classify/roc-analysis/dialogs/define-groups";
+import {RocAnalysisCutPoints} from "@/components/Modals/Analyze/Classify/roc-analysis/dialogs/cut-points";
 import {Dialog, DialogContent, DialogTitle} from "@/components/ui/dialog";
 import {useModal} from "@/hooks/useModal";
 import {useVariableStore} from "@/stores/useVariableStore";
@@ -34,6 +35,7 @@ export const RocAnalysisContainer = ({
     const [isDefineGroupsOpen, setIsDefineGroupsOpen] = useState(false);
     const [isOptionsOpen, setIsOptionsOpen] = useState(false);
     const [isDisplayOpen, setIsDisplayOpen] = useState(false);
+    const [isCutPointsOpen, setIsCutPointsOpen] = useState(false);
 
     const { closeModal } = useModal();
 
@@ -43,7 +45,7 @@ export const RocAnalysisContainer = ({
                 const savedData = await getFormData("ROCAnalysis");
                 if (savedData) {
                     const { id, ...formDataWithoutId } = savedData;
-                    setFormData(formDataWithoutId);
+                    setFormData({ ...RocAnalysisDefault, ...formDataWithoutId });
                 } else {
                     setFormData({ ...RocAnalysisDefault });
                 }
@@ -115,6 +117,7 @@ export const RocAnalysisContainer = ({
                     setIsDefineGroupsOpen={setIsDefineGroupsOpen}
                     setIsOptionsOpen={setIsOptionsOpen}
                     setIsDisplayOpen={setIsDisplayOpen}
+                    setIsCutPointsOpen={setIsCutPointsOpen}
                     updateFormData={(field, value) =>
                         updateFormData("main", field, value)
                     }
@@ -153,6 +156,16 @@ export const RocAnalysisContainer = ({
                     }
                     data={formData.display}
                 />
+
+                {/* Cut Points */}
+                <RocAnalysisCutPoints
+                    isCutPointsOpen={isCutPointsOpen}
+                    setIsCutPointsOpen={setIsCutPointsOpen}
+                    updateFormData={(field, value) =>
+                        updateFormData("cutPoints", field, value)
+                    }
+                    data={formData.cutPoints}
+                />
             </DialogContent>
         </Dialog>
     );
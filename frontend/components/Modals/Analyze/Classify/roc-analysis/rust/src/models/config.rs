@@ -1,15 +1,17 @@
 use serde::{ Deserialize, Serialize };
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct RocConfig {
     pub main: MainConfig,
     #[serde(rename = "defineGroups")]
     pub define_groups: DefineGroupsConfig,
     pub options: OptionsConfig,
     pub display: DisplayConfig,
+    #[serde(rename = "cutPoints", default)]
+    pub cut_points: CutPointsConfig,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct MainConfig {
     #[serde(rename = "PairedSample")]
     pub paired_sample: bool,
@@ -39,6 +41,19 @@ pub struct DefineGroupsConfig {
     pub cut_point_value: Option<f64>,
 }
 
+impl Default for DefineGroupsConfig {
+    fn default() -> Self {
+        DefineGroupsConfig {
+            specified_values: true,
+            group1: None,
+            group2: None,
+            use_mid_value: false,
+            cut_point: false,
+            cut_point_value: None,
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct OptionsConfig {
     #[serde(rename = "IncludeCutoff")]
@@ -59,6 +74,21 @@ pub struct OptionsConfig {
     pub miss_value_as_valid: bool,
 }
 
+impl Default for OptionsConfig {
+    fn default() -> Self {
+        OptionsConfig {
+            include_cutoff: true,
+            exclude_cutoff: false,
+            larger_test: true,
+            smaller_test: false,
+            dist_assumpt_method: DistributionMethod::Nonparametric,
+            conf_level: 95,
+            exclude_miss_value: true,
+            miss_value_as_valid: false,
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub enum DistributionMethod {
     #[serde(rename = "Nonparametric")]
@@ -89,4 +119,75 @@ pub struct DisplayConfig {
     pub prc_point: bool,
     #[serde(rename = "EvalMetrics")]
     pub eval_metrics: bool,
+    #[serde(rename = "CompareCurves", default)]
+    pub compare_curves: bool,
+}
+
+impl Default for DisplayConfig {
+    fn default() -> Self {
+        DisplayConfig {
+            roc_curve: true,
+            refline: false,
+            prc: false,
+            intepol_true: true,
+            intepol_false: false,
+            overall: false,
+            seci: false,
+            roc_point: false,
+            prc_point: false,
+            eval_metrics: false,
+            compare_curves: false,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct CutPointsConfig {
+    #[serde(rename = "OptimalCutPoints")]
+    pub optimal_cut_points: bool,
+    #[serde(rename = "Youden")]
+    pub youden: bool,
+    #[serde(rename = "ClosestToTopLeft")]
+    pub closest_to_top_left: bool,
+    #[serde(rename = "CostWeighted")]
+    pub cost_weighted: bool,
+    #[serde(rename = "FalsePositiveCost")]
+    pub false_positive_cost: f64,
+    #[serde(rename = "FalseNegativeCost")]
+    pub false_negative_cost: f64,
+    #[serde(rename = "Prevalence")]
+    pub prevalence: Option<f64>,
+    #[serde(rename = "MinSensitivity")]
+    pub min_sensitivity: Option<f64>,
+    #[serde(rename = "MinSpecificity")]
+    pub min_specificity: Option<f64>,
+    #[serde(rename = "PerformBootStrapping")]
+    pub perform_boot_strapping: bool,
+    #[serde(rename = "NumOfSamples")]
+    pub num_of_samples: Option<i32>,
+    #[serde(rename = "Seed")]
+    pub seed: bool,
+    #[serde(rename = "SeedValue")]
+    pub seed_value: Option<i32>,
+}
+
+impl Default for CutPointsConfig {
+    fn default() -> Self {
+        CutPointsConfig {
+            optimal_cut_points: false,
+            youden: true,
+            closest_to_top_left: false,
+            cost_weighted: false,
+            false_positive_cost: 1.0,
+            false_negative_cost: 1.0,
+            prevalence: None,
+            min_sensitivity: None,
+            min_specificity: None,
+            perform_boot_strapping: false,
+            num_of_samples: Some(1000),
+            seed: false,
+            seed_value: None,
+        }
+    }
 }
@@ -15,6 +15,10 @@ pub struct ROCAnalysisResult {
     pub overall_model_quality: Option<HashMap<String, f64>>,
     #[serde(rename = "classifier_evaluation_metrics")]
     pub classifier_evaluation_metrics: Option<HashMap<String, ClassifierEvaluationMetrics>>,
+    #[serde(rename = "area_under_roc_curve_comparison")]
+    pub area_under_roc_curve_comparison: Option<Vec<AreaUnderRocCurveComparison>>,
+    #[serde(rename = "optimal_cut_points")]
+    pub optimal_cut_points: Option<HashMap<String, OptimalCutPoints>>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -53,6 +57,29 @@ pub struct AreaUnderRocCurve {
     pub asymptotic_95_confidence_interval: Interval,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AreaUnderRocCurveComparison {
+    pub method: String,
+    #[serde(rename = "first_curve")]
+    pub first_curve: String,
+    #[serde(rename = "second_curve")]
+    pub second_curve: String,
+    #[serde(rename = "first_area")]
+    pub first_area: f64,
+    #[serde(rename = "second_area")]
+    pub second_area: f64,
+    pub difference: f64,
+    #[serde(rename = "std_error")]
+    pub std_error: f64,
+    #[serde(rename = "z_statistic")]
+    pub z_statistic: f64,
+    #[serde(rename = "asymptotic_sig")]
+    pub asymptotic_sig: f64,
+    #[serde(rename = "confidence_interval")]
+    pub confidence_interval: Interval,
+    pub correlation: f64,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Interval {
     #[serde(rename = "lower_bound")]
@@ -69,3 +96,48 @@ pub struct ClassifierEvaluationMetrics {
     pub max_k_s: f64,
     pub cutoff: f64,
 }
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OptimalCutPoints {
+    pub prevalence: f64,
+    #[serde(rename = "threshold_probability")]
+    pub threshold_probability: f64,
+    #[serde(rename = "bootstrap_samples")]
+    pub bootstrap_samples: Option<usize>,
+    #[serde(rename = "cut_points")]
+    pub cut_points: Vec<OptimalCutPoint>,
+    #[serde(rename = "treat_all")]
+    pub treat_all: Vec<NetBenefitPoint>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OptimalCutPoint {
+    pub criterion: String,
+    pub cutoff: f64,
+    #[serde(rename = "positive_if")]
+    pub positive_if: String,
+    pub sensitivity: f64,
+    pub specificity: f64,
+    #[serde(rename = "criterion_value")]
+    pub criterion_value: f64,
+    #[serde(rename = "net_benefit")]
+    pub net_benefit: f64,
+    #[serde(rename = "cutoff_interval")]
+    pub cutoff_interval: Option<Interval>,
+    #[serde(rename = "sensitivity_interval")]
+    pub sensitivity_interval: Option<Interval>,
+    #[serde(rename = "specificity_interval")]
+    pub specificity_interval: Option<Interval>,
+    #[serde(rename = "net_benefit_interval")]
+    pub net_benefit_interval: Option<Interval>,
+    #[serde(rename = "decision_curve")]
+    pub decision_curve: Vec<NetBenefitPoint>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct NetBenefitPoint {
+    #[serde(rename = "threshold_probability")]
+    pub threshold_probability: f64,
+    #[serde(rename = "net_benefit")]
+    pub net_benefit: f64,
+}
@@ -87,10 +87,10 @@ pub fn calculate_auc_from_values(
 
     let auc = rank_sum / ((m as f64) * (n as f64));
 
-    let is_nonparametric = match config.options.dist_assumpt_method {
-        DistributionMethod::Nonparametric => true,
-        _ => false,
-    };
+    let is_nonparametric = matches!(
+        config.options.dist_assumpt_method,
+        DistributionMethod::Nonparametric
+    );
 
     let std_error = if is_nonparametric {
         calculate_nonparametric_std_error(
@@ -46,9 +46,7 @@ pub fn calculate_case_processing_summary(
                         }
                     }
                     DataValue::Boolean(val) => {
-                        if state_var_val == "true" && *val {
-                            positive_count += 1;
-                        } else if state_var_val == "false" && !*val {
+                        if (state_var_val == "true" && *val) || (state_var_val == "false" && !*val) {
                             positive_count += 1;
                         } else {
                             negative_count += 1;
@@ -57,8 +55,6 @@ pub fn calculate_case_processing_summary(
                     DataValue::Null => {
                         if config.options.miss_value_as_valid {
                             negative_count += 1;
-                        } else if config.options.exclude_miss_value {
-                            missing_count += 1;
                         } else {
                             missing_count += 1;
                         }
@@ -67,8 +63,6 @@ pub fn calculate_case_processing_summary(
             } else {
                 if config.options.miss_value_as_valid {
                     negative_count += 1;
-                } else if config.options.exclude_miss_value {
-                    missing_count += 1;
                 } else {
                     missing_count += 1;
                 }
@@ -8,19 +8,19 @@ pub fn calculate_nonparametric_std_error(
     let n = negative_values.len();
 
     let mut q1_sum = 0.0;
-    for i in 0..m {
+    for &positive in positive_values {
         let mut count = 0.0;
-        for j in 0..n {
+        for &negative in negative_values {
             if larger_is_positive {
-                if positive_values[i] > negative_values[j] {
+                if positive > negative {
                     count += 1.0;
-                } else if positive_values[i] == negative_values[j] {
+                } else if positive == negative {
                     count += 0.5;
                 }
             } else {
-                if positive_values[i] < negative_values[j] {
+                if positive < negative {
                     count += 1.0;
-                } else if positive_values[i] == negative_values[j] {
+                } else if positive == negative {
                     count += 0.5;
                 }
             }
@@ -30,19 +30,19 @@ pub fn calculate_nonparametric_std_error(
     let q1 = q1_sum / ((m - 1) as f64);
 
     let mut q2_sum = 0.0;
-    for j in 0..n {
+    for &negative in negative_values {
         let mut count = 0.0;
-        for i in 0..m {
+        for &positive in positive_values {
             if larger_is_positive {
-                if positive_values[i] > negative_values[j] {
+                if positive > negative {
                     count += 1.0;
-                } else if positive_values[i] == negative_values[j] {
+                } else if positive == negative {
                     count += 0.5;
                 }
             } else {
-                if positive_values[i] < negative_values[j] {
+                if positive < negative {
                     count += 1.0;
-                } else if positive_values[i] == negative_values[j] {
+                } else if positive == negative {
                     count += 0.5;
                 }
             }
@@ -0,0 +1,289 @@
+use crate::models::{
+    config::RocConfig,
+    data::AnalysisData,
+    result::{ AreaUnderRocCurveComparison, Interval },
+};
+
+use super::core::{ extract_grouped_values, extract_paired_values, normal_cdf, normal_quantile };
+
+pub fn calculate_area_under_roc_curve_comparison(
+    data: &AnalysisData,
+    config: &RocConfig
+) -> Result<Vec<AreaUnderRocCurveComparison>, String> {
+    if config.main.test_target_variable.is_none() {
+        return Err("Test target variables are not specified".to_string());
+    }
+    let test_target_vars = config.main.test_target_variable.as_ref().unwrap();
+
+    if test_target_vars.is_empty() {
+        return Err("No test target variables specified".to_string());
+    }
+
+    // Same precedence as calculate_area_under_roc_curve_for_variable: paired first, then groups
+    if config.main.paired_sample || config.main.target_group_var.is_none() {
+        compare_correlated_curves(data, config, test_target_vars)
+    } else {
+        compare_independent_curves(data, config, test_target_vars)
+    }
+}
+
+/// DeLong, DeLong & Clarke-Pearson (1988): every pair of test variables measured on the same cases
+pub fn compare_correlated_curves(
+    data: &AnalysisData,
+    config: &RocConfig,
+    test_target_vars: &[String]
+) -> Result<Vec<AreaUnderRocCurveComparison>, String> {
+    if test_target_vars.len() < 2 {
+        return Err(
+            "At least two test variables are required to compare paired ROC curves".to_string()
+        );
+    }
+
+    let (positive_rows, negative_rows) = extract_paired_values(data, config, test_target_vars)?;
+    let m = positive_rows.len();
+    let n = negative_rows.len();
+
+    if m < 2 || n < 2 {
+        return Err(
+            format!(
+                "DeLong comparison needs at least two positive ({}) and two negative ({}) cases",
+                m,
+                n
+            )
+        );
+    }
+
+    let k = test_target_vars.len();
+    let larger_is_positive = config.options.larger_test;
+
+    let mut areas = Vec::with_capacity(k);
+    let mut v10 = Vec::with_capacity(k);
+    let mut v01 = Vec::with_capacity(k);
+
+    for var_idx in 0..k {
+        let positive_values: Vec<f64> = positive_rows
+            .iter()
+            .map(|row| row[var_idx])
+            .collect();
+        let negative_values: Vec<f64> = negative_rows
+            .iter()
+            .map(|row| row[var_idx])
+            .collect();
+
+        let (area, pos_components, neg_components) = delong_components(
+            &positive_values,
+            &negative_values,
+            larger_is_positive
+        );
+        areas.push(area);
+        v10.push(pos_components);
+        v01.push(neg_components);
+    }
+
+    // S = S10 / m + S01 / n
+    let mut covariance = vec![vec![0.0; k]; k];
+    for a in 0..k {
+        for b in a..k {
+            let s10 = component_covariance(&v10[a], &v10[b], areas[a], areas[b]);
+            let s01 = component_covariance(&v01[a], &v01[b], areas[a], areas[b]);
+            let value = s10 / (m as f64) + s01 / (n as f64);
+            covariance[a][b] = value;
+            covariance[b][a] = value;
+        }
+    }
+
+    let mut comparisons = Vec::new();
+    for a in 0..k {
+        for b in a + 1..k {
+            let variance = covariance[a][a] + covariance[b][b] - 2.0 * covariance[a][b];
+            let denominator = (covariance[a][a] * covariance[b][b]).sqrt();
+            let correlation = if denominator > 0.0 {
+                covariance[a][b] / denominator
+            } else {
+                0.0
+            };
+
+            comparisons.push(
+                build_comparison(
+                    "DeLong",
+                    (test_target_vars[a].clone(), test_target_vars[b].clone()),
+                    (areas[a], areas[b]),
+                    variance,
+                    correlation,
+                    config
+                )
+            );
+        }
+    }
+
+    Ok(comparisons)
+}
+
+/// Hanley & McNeil (1982): the same test variable in two independent groups
+pub fn compare_independent_curves(
+    data: &AnalysisData,
+    config: &RocConfig,
+    test_target_vars: &[String]
+) -> Result<Vec<AreaUnderRocCurveComparison>, String> {
+    let larger_is_positive = config.options.larger_test;
+    let (group1_label, group2_label) = group_labels(config);
+
+    let mut comparisons = Vec::new();
+    for test_var in test_target_vars {
+        let (group1_pos, group1_neg, group2_pos, group2_neg) = extract_grouped_values(
+            data,
+            config,
+            test_var
+        )?;
+
+        let (area1, _, _) = delong_components(&group1_pos, &group1_neg, larger_is_positive);
+        let (area2, _, _) = delong_components(&group2_pos, &group2_neg, larger_is_positive);
+
+        let variance =
+            hanley_mcneil_variance(area1, group1_pos.len(), group1_neg.len()) +
+            hanley_mcneil_variance(area2, group2_pos.len(), group2_neg.len());
+
+        comparisons.push(
+            build_comparison(
+                "Hanley-McNeil",
+                (
+                    format!("{} ({})", test_var, group1_label),
+                    format!("{} ({})", test_var, group2_label),
+                ),
+                (area1, area2),
+                variance,
+                0.0,
+                config
+            )
+        );
+    }
+
+    Ok(comparisons)
+}
+
+/// Returns the AUC with the placement values of each positive case (V10) and negative case (V01)
+pub fn delong_components(
+    positive_values: &[f64],
+    negative_values: &[f64],
+    larger_is_positive: bool
+) -> (f64, Vec<f64>, Vec<f64>) {
+    let m = positive_values.len();
+    let n = negative_values.len();
+
+    let mut v10 = vec![0.0; m];
+    let mut v01 = vec![0.0; n];
+
+    for (i, &pos_val) in positive_values.iter().enumerate() {
+        for (j, &neg_val) in negative_values.iter().enumerate() {
+            let psi = placement_kernel(pos_val, neg_val, larger_is_positive);
+            v10[i] += psi;
+            v01[j] += psi;
+        }
+    }
+
+    v10.iter_mut().for_each(|v| {
+        *v /= n as f64;
+    });
+    v01.iter_mut().for_each(|v| {
+        *v /= m as f64;
+    });
+
+    let area = v10.iter().sum::<f64>() / (m as f64);
+
+    (area, v10, v01)
+}
+
+pub fn hanley_mcneil_variance(area: f64, n_positive: usize, n_negative: usize) -> f64 {
+    let q1 = area / (2.0 - area);
+    let q2 = (2.0 * area.powi(2)) / (1.0 + area);
+
+    (area * (1.0 - area) +
+        ((n_positive - 1) as f64) * (q1 - area.powi(2)) +
+        ((n_negative - 1) as f64) * (q2 - area.powi(2))) /
+        ((n_positive * n_negative) as f64)
+}
+
+fn placement_kernel(pos_val: f64, neg_val: f64, larger_is_positive: bool) -> f64 {
+    let (higher, lower) = if larger_is_positive { (pos_val, neg_val) } else { (neg_val, pos_val) };
+
+    if higher > lower {
+        1.0
+    } else if higher == lower {
+        0.5
+    } else {
+        0.0
+    }
+}
+
+fn component_covariance(first: &[f64], second: &[f64], first_area: f64, second_area: f64) -> f64 {
+    let sum: f64 = first
+        .iter()
+        .zip(second.iter())
+        .map(|(a, b)| (a - first_area) * (b - second_area))
+        .sum();
+
+    sum / ((first.len() - 1) as f64)
+}
+
+/// Labels of the two groups formed from the grouping variable
+pub fn group_labels(config: &RocConfig) -> (String, String) {
+    let groups = &config.define_groups;
+
+    if groups.specified_values {
+        (
+            groups.group1.clone().unwrap_or_else(|| "Group 1".to_string()),
+            groups.group2.clone().unwrap_or_else(|| "Group 2".to_string()),
+        )
+    } else if groups.cut_point {
+        match groups.cut_point_value {
+            Some(cut_point) => (format!("< {}", cut_point), format!(">= {}", cut_point)),
+            None => ("Group 1".to_string(), "Group 2".to_string()),
+        }
+    } else if groups.use_mid_value {
+        ("Below mid value".to_string(), "At or above mid value".to_string())
+    } else {
+        ("Group 1".to_string(), "Group 2".to_string())
+    }
+}
+
+fn build_comparison(
+    method: &str,
+    (first_curve, second_curve): (String, String),
+    (first_area, second_area): (f64, f64),
+    variance: f64,
+    correlation: f64,
+    config: &RocConfig
+) -> AreaUnderRocCurveComparison {
+    let difference = first_area - second_area;
+    let std_error = variance.max(0.0).sqrt();
+
+    let z_statistic = if std_error > 0.0 {
+        difference / std_error
+    } else {
+        0.0
+    };
+    let asymptotic_sig = 2.0 * (1.0 - normal_cdf(z_statistic.abs()));
+
+    let conf_level = (config.options.conf_level as f64) / 100.0;
+    let alpha = 1.0 - conf_level;
+    let z_alpha = normal_quantile(1.0 - alpha / 2.0);
+
+    let margin = z_alpha * std_error;
+
+    AreaUnderRocCurveComparison {
+        method: method.to_string(),
+        first_curve,
+        second_curve,
+        first_area,
+        second_area,
+        difference,
+        std_error,
+        z_statistic,
+        asymptotic_sig,
+        confidence_interval: Interval {
+            lower_bound: (difference - margin).max(-1.0),
+            upper_bound: (difference + margin).min(1.0),
+        },
+        correlation,
+    }
+}
@@ -97,7 +97,7 @@ pub fn calculate_confusion_matrix(
     let larger_is_positive = config.options.larger_test;
     let exclude_cutoff = config.options.exclude_cutoff;
 
-    let include_equal = if exclude_cutoff { false } else { true };
+    let include_equal = !exclude_cutoff;
 
     for &val in positive_values {
         let is_positive = if larger_is_positive {
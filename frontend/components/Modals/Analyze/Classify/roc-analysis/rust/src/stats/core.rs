@@ -1,7 +1,9 @@
 pub use crate::stats::auc::*;
 pub use crate::stats::case_processing::*;
 pub use crate::stats::common::*;
+pub use crate::stats::comparison::*;
 pub use crate::stats::coordinates::*;
+pub use crate::stats::cut_points::*;
 pub use crate::stats::data_extraction::*;
 pub use crate::stats::model_quality::*;
 pub use crate::stats::precision_recall::*;
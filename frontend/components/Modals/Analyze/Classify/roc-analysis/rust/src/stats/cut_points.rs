@@ -0,0 +1,428 @@
+use std::collections::HashMap;
+
+use rand_mt::Mt64;
+
+use crate::models::{
+    config::RocConfig,
+    data::AnalysisData,
+    result::{ Interval, NetBenefitPoint, OptimalCutPoint, OptimalCutPoints },
+};
+
+use super::core::{
+    calculate_confusion_matrix,
+    extract_grouped_values,
+    extract_values,
+    generate_cutoffs,
+    group_labels,
+};
+
+/// Threshold probabilities (0.01, 0.02, ..., 0.99) at which the decision curves are evaluated
+const DECISION_CURVE_STEPS: usize = 99;
+
+#[derive(Debug, Clone, Copy)]
+enum Criterion {
+    Youden,
+    ClosestToTopLeft,
+    MinimumCost,
+    MinSensitivity(f64),
+    MinSpecificity(f64),
+}
+
+impl Criterion {
+    fn label(&self) -> String {
+        match self {
+            Criterion::Youden => "Youden Index".to_string(),
+            Criterion::ClosestToTopLeft => "Closest to (0,1)".to_string(),
+            Criterion::MinimumCost => "Minimum Misclassification Cost".to_string(),
+            Criterion::MinSensitivity(x) => format!("Sensitivity >= {}", x),
+            Criterion::MinSpecificity(x) => format!("Specificity >= {}", x),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct CutoffPerformance {
+    cutoff: f64,
+    sensitivity: f64,
+    specificity: f64,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct CostSettings {
+    prevalence: f64,
+    false_positive_cost: f64,
+    false_negative_cost: f64,
+}
+
+impl CostSettings {
+    /// Risk threshold at which treating and not treating have equal expected cost
+    fn threshold_probability(&self) -> f64 {
+        self.false_positive_cost / (self.false_positive_cost + self.false_negative_cost)
+    }
+}
+
+pub fn calculate_optimal_cut_points(
+    data: &AnalysisData,
+    config: &RocConfig
+) -> Result<HashMap<String, OptimalCutPoints>, String> {
+    if config.main.test_target_variable.is_none() {
+        return Err("Test target variables are not specified".to_string());
+    }
+    let test_target_vars = config.main.test_target_variable.as_ref().unwrap();
+
+    if test_target_vars.is_empty() {
+        return Err("No test target variables specified".to_string());
+    }
+
+    let mut cut_points_map: HashMap<String, OptimalCutPoints> = HashMap::new();
+
+    if config.main.paired_sample || config.main.target_group_var.is_none() {
+        for test_var in test_target_vars {
+            let (positive_values, negative_values) = extract_values(data, config, test_var)?;
+            let var_cut_points = calculate_optimal_cut_points_from_values(
+                &positive_values,
+                &negative_values,
+                config
+            )?;
+            cut_points_map.insert(test_var.clone(), var_cut_points);
+        }
+        return Ok(cut_points_map);
+    }
+
+    // Each group has its own ROC curve, so the cut points are reported per group
+    let (group1_label, group2_label) = group_labels(config);
+    for test_var in test_target_vars {
+        let (group1_pos, group1_neg, group2_pos, group2_neg) = extract_grouped_values(
+            data,
+            config,
+            test_var
+        )?;
+        cut_points_map.insert(
+            format!("{} ({})", test_var, group1_label),
+            calculate_optimal_cut_points_from_values(&group1_pos, &group1_neg, config)?
+        );
+        cut_points_map.insert(
+            format!("{} ({})", test_var, group2_label),
+            calculate_optimal_cut_points_from_values(&group2_pos, &group2_neg, config)?
+        );
+    }
+
+    Ok(cut_points_map)
+}
+
+pub fn calculate_optimal_cut_points_from_values(
+    positive_values: &[f64],
+    negative_values: &[f64],
+    config: &RocConfig
+) -> Result<OptimalCutPoints, String> {
+    let cut_config = &config.cut_points;
+    let criteria = selected_criteria(config)?;
+
+    if cut_config.false_positive_cost <= 0.0 || cut_config.false_negative_cost <= 0.0 {
+        return Err("Misclassification costs must be positive".to_string());
+    }
+
+    let sample_prevalence =
+        (positive_values.len() as f64) /
+        ((positive_values.len() + negative_values.len()) as f64);
+    let prevalence = match cut_config.prevalence {
+        Some(p) if p > 0.0 && p < 1.0 => p,
+        Some(p) => {
+            return Err(format!("Prevalence must lie strictly between 0 and 1, got {}", p));
+        }
+        None => sample_prevalence,
+    };
+
+    let costs = CostSettings {
+        prevalence,
+        false_positive_cost: cut_config.false_positive_cost,
+        false_negative_cost: cut_config.false_negative_cost,
+    };
+    let threshold_probability = costs.threshold_probability();
+
+    let performance = cutoff_performance(positive_values, negative_values, config);
+
+    let mut cut_points = Vec::with_capacity(criteria.len());
+    for criterion in &criteria {
+        let (index, criterion_value) = select_cutoff(&performance, *criterion, &costs);
+        let point = performance[index];
+
+        let decision_curve = threshold_grid()
+            .map(|pt| NetBenefitPoint {
+                threshold_probability: pt,
+                net_benefit: net_benefit(point.sensitivity, point.specificity, prevalence, pt),
+            })
+            .collect();
+
+        cut_points.push(OptimalCutPoint {
+            criterion: criterion.label(),
+            cutoff: point.cutoff,
+            positive_if: positive_direction(config),
+            sensitivity: point.sensitivity,
+            specificity: point.specificity,
+            criterion_value,
+            net_benefit: net_benefit(
+                point.sensitivity,
+                point.specificity,
+                prevalence,
+                threshold_probability
+            ),
+            cutoff_interval: None,
+            sensitivity_interval: None,
+            specificity_interval: None,
+            net_benefit_interval: None,
+            decision_curve,
+        });
+    }
+
+    let mut bootstrap_samples = None;
+    if cut_config.perform_boot_strapping {
+        let num_samples = cut_config.num_of_samples.unwrap_or(1000);
+        if num_samples < 2 {
+            return Err("Number of bootstrap samples must be at least 2".to_string());
+        }
+        let num_samples = num_samples as usize;
+
+        let seed = match (cut_config.seed, cut_config.seed_value) {
+            (true, Some(value)) => value as u64,
+            _ => rand::random::<u64>(),
+        };
+        let mut rng = Mt64::new(seed);
+
+        let conf_level = (config.options.conf_level as f64) / 100.0;
+        let alpha = 1.0 - conf_level;
+
+        // [criterion][cutoff, sensitivity, specificity, net benefit] replicates
+        let mut replicates: Vec<Vec<Vec<f64>>> = vec![vec![Vec::new(); 4]; criteria.len()];
+
+        for _ in 0..num_samples {
+            // Resampling within each actual state keeps both groups non-empty
+            let boot_positive = resample(positive_values, &mut rng);
+            let boot_negative = resample(negative_values, &mut rng);
+            let boot_performance = cutoff_performance(&boot_positive, &boot_negative, config);
+
+            for (criterion_idx, criterion) in criteria.iter().enumerate() {
+                let (index, _) = select_cutoff(&boot_performance, *criterion, &costs);
+                let point = boot_performance[index];
+                let values = [
+                    point.cutoff,
+                    point.sensitivity,
+                    point.specificity,
+                    net_benefit(
+                        point.sensitivity,
+                        point.specificity,
+                        prevalence,
+                        threshold_probability
+                    ),
+                ];
+                for (stat_idx, value) in values.iter().enumerate() {
+                    replicates[criterion_idx][stat_idx].push(*value);
+                }
+            }
+        }
+
+        for (cut_point, stats) in cut_points.iter_mut().zip(replicates.iter_mut()) {
+            cut_point.cutoff_interval = percentile_interval(&mut stats[0], alpha);
+            cut_point.sensitivity_interval = percentile_interval(&mut stats[1], alpha);
+            cut_point.specificity_interval = percentile_interval(&mut stats[2], alpha);
+            cut_point.net_benefit_interval = percentile_interval(&mut stats[3], alpha);
+        }
+
+        bootstrap_samples = Some(num_samples);
+    }
+
+    let treat_all = threshold_grid()
+        .map(|pt| NetBenefitPoint {
+            threshold_probability: pt,
+            net_benefit: net_benefit(1.0, 0.0, prevalence, pt),
+        })
+        .collect();
+
+    Ok(OptimalCutPoints {
+        prevalence,
+        threshold_probability,
+        bootstrap_samples,
+        cut_points,
+        treat_all,
+    })
+}
+
+/// Mirrors the rule used by calculate_confusion_matrix
+fn positive_direction(config: &RocConfig) -> String {
+    let comparison = if config.options.larger_test { "Greater Than" } else { "Less Than" };
+    if config.options.exclude_cutoff {
+        comparison.to_string()
+    } else {
+        format!("{} or Equal To", comparison)
+    }
+}
+
+fn selected_criteria(config: &RocConfig) -> Result<Vec<Criterion>, String> {
+    let cut_config = &config.cut_points;
+    let mut criteria = Vec::new();
+
+    if cut_config.youden {
+        criteria.push(Criterion::Youden);
+    }
+    if cut_config.closest_to_top_left {
+        criteria.push(Criterion::ClosestToTopLeft);
+    }
+    if cut_config.cost_weighted {
+        criteria.push(Criterion::MinimumCost);
+    }
+    if let Some(x) = cut_config.min_sensitivity {
+        criteria.push(Criterion::MinSensitivity(as_proportion(x, "Minimum sensitivity")?));
+    }
+    if let Some(x) = cut_config.min_specificity {
+        criteria.push(Criterion::MinSpecificity(as_proportion(x, "Minimum specificity")?));
+    }
+
+    if criteria.is_empty() {
+        return Err("No cut-point criterion selected".to_string());
+    }
+
+    Ok(criteria)
+}
+
+// The dialog may store constraints as a percentage (e.g. 90)
+fn as_proportion(value: f64, name: &str) -> Result<f64, String> {
+    if value > 0.0 && value <= 1.0 {
+        Ok(value)
+    } else if value > 1.0 && value <= 100.0 {
+        Ok(value / 100.0)
+    } else {
+        Err(format!("{} must lie between 0 and 1, got {}", name, value))
+    }
+}
+
+fn cutoff_performance(
+    positive_values: &[f64],
+    negative_values: &[f64],
+    config: &RocConfig
+) -> Vec<CutoffPerformance> {
+    generate_cutoffs(positive_values, negative_values)
+        .into_iter()
+        .map(|cutoff| {
+            let (tp, fn_count, tn, fp) = calculate_confusion_matrix(
+                positive_values,
+                negative_values,
+                cutoff,
+                config
+            );
+
+            CutoffPerformance {
+                cutoff,
+                sensitivity: (tp as f64) / ((tp + fn_count) as f64),
+                specificity: (tn as f64) / ((tn + fp) as f64),
+            }
+        })
+        .collect()
+}
+
+/// Returns the index of the chosen cutoff and the value of the criterion there.
+/// Ties keep the first (lowest) cutoff.
+fn select_cutoff(
+    performance: &[CutoffPerformance],
+    criterion: Criterion,
+    costs: &CostSettings
+) -> (usize, f64) {
+    const TOLERANCE: f64 = 1e-12;
+
+    // (primary, secondary) score, higher is better; distance and cost are negated
+    let score = |p: &CutoffPerformance| -> Option<(f64, f64)> {
+        match criterion {
+            Criterion::Youden => Some((p.sensitivity + p.specificity - 1.0, 0.0)),
+            Criterion::ClosestToTopLeft =>
+                Some((
+                    -((1.0 - p.sensitivity).powi(2) + (1.0 - p.specificity).powi(2)).sqrt(),
+                    0.0,
+                )),
+            Criterion::MinimumCost =>
+                Some((
+                    -(
+                        costs.false_negative_cost * costs.prevalence * (1.0 - p.sensitivity) +
+                        costs.false_positive_cost * (1.0 - costs.prevalence) * (1.0 - p.specificity)
+                    ),
+                    0.0,
+                )),
+            // Best specificity among cutoffs meeting the sensitivity floor, then best sensitivity
+            Criterion::MinSensitivity(x) => {
+                if p.sensitivity + TOLERANCE >= x {
+                    Some((p.specificity, p.sensitivity))
+                } else {
+                    None
+                }
+            }
+            Criterion::MinSpecificity(x) => {
+                if p.specificity + TOLERANCE >= x {
+                    Some((p.sensitivity, p.specificity))
+                } else {
+                    None
+                }
+            }
+        }
+    };
+
+    let mut best_index = 0;
+    let mut best_score = (f64::NEG_INFINITY, f64::NEG_INFINITY);
+    for (index, p) in performance.iter().enumerate() {
+        if let Some((primary, secondary)) = score(p) {
+            let tied = (primary - best_score.0).abs() <= TOLERANCE;
+            let better =
+                primary > best_score.0 + TOLERANCE || (tied && secondary > best_score.1 + TOLERANCE);
+            if better {
+                best_score = (primary, secondary);
+                best_index = index;
+            }
+        }
+    }
+
+    let chosen = &performance[best_index];
+    let value = match criterion {
+        Criterion::Youden => chosen.sensitivity + chosen.specificity - 1.0,
+        Criterion::ClosestToTopLeft =>
+            ((1.0 - chosen.sensitivity).powi(2) + (1.0 - chosen.specificity).powi(2)).sqrt(),
+        Criterion::MinimumCost =>
+            costs.false_negative_cost * costs.prevalence * (1.0 - chosen.sensitivity) +
+                costs.false_positive_cost * (1.0 - costs.prevalence) * (1.0 - chosen.specificity),
+        Criterion::MinSensitivity(_) => chosen.specificity,
+        Criterion::MinSpecificity(_) => chosen.sensitivity,
+    };
+
+    (best_index, value)
+}
+
+/// Vickers & Elkin (2006): NB = Se·p − (1 − Sp)(1 − p)·pt / (1 − pt)
+fn net_benefit(sensitivity: f64, specificity: f64, prevalence: f64, threshold: f64) -> f64 {
+    sensitivity * prevalence -
+        (1.0 - specificity) * (1.0 - prevalence) * (threshold / (1.0 - threshold))
+}
+
+fn threshold_grid() -> impl Iterator<Item = f64> {
+    (1..=DECISION_CURVE_STEPS).map(|i| (i as f64) / ((DECISION_CURVE_STEPS + 1) as f64))
+}
+
+fn resample(values: &[f64], rng: &mut Mt64) -> Vec<f64> {
+    (0..values.len()).map(|_| values[(rng.next_u64() % (values.len() as u64)) as usize]).collect()
+}
+
+fn percentile_interval(values: &mut [f64], alpha: f64) -> Option<Interval> {
+    if values.len() < 2 {
+        return None;
+    }
+    values.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+
+    Some(Interval {
+        lower_bound: quantile(values, alpha / 2.0),
+        upper_bound: quantile(values, 1.0 - alpha / 2.0),
+    })
+}
+
+fn quantile(sorted: &[f64], p: f64) -> f64 {
+    let position = p * ((sorted.len() - 1) as f64);
+    let lower = position.floor() as usize;
+    let upper = position.ceil() as usize;
+    let weight = position - (lower as f64);
+
+    sorted[lower] * (1.0 - weight) + sorted[upper] * weight
+}
@@ -79,11 +79,110 @@ pub fn extract_values(
     Ok((positive_values, negative_values))
 }
 
+/// Positive and negative cases, one row per case with one column per test variable
+pub type PairedValues = (Vec<Vec<f64>>, Vec<Vec<f64>>);
+
+pub fn extract_paired_values(
+    data: &AnalysisData,
+    config: &RocConfig,
+    test_target_vars: &[String]
+) -> Result<PairedValues, String> {
+    if config.main.state_target_variable.is_none() {
+        return Err("State target variable is not specified".to_string());
+    }
+    let state_target_var = config.main.state_target_variable.as_ref().unwrap();
+
+    if config.main.state_var_val.is_none() {
+        return Err("State variable value is not specified".to_string());
+    }
+    let state_var_val = config.main.state_var_val.as_ref().unwrap();
+
+    if data.state_data.is_empty() {
+        return Err("No state data provided".to_string());
+    }
+
+    let state_dataset = &data.state_data[0];
+
+    // Cases missing any of the test values are dropped (listwise)
+    let mut positive_rows = Vec::new();
+    let mut negative_rows = Vec::new();
+
+    for case_idx in 0..state_dataset.len() {
+        let is_positive = match state_dataset[case_idx].values.get(state_target_var) {
+            Some(DataValue::Text(val)) => val == state_var_val,
+            Some(DataValue::Number(val)) =>
+                state_var_val
+                    .parse::<f64>()
+                    .map(|p| p == *val)
+                    .unwrap_or(false),
+            Some(DataValue::Boolean(val)) => {
+                if state_var_val == "true" {
+                    *val
+                } else if state_var_val == "false" {
+                    !*val
+                } else {
+                    false
+                }
+            }
+            Some(DataValue::Null) => false,
+            None => {
+                continue;
+            }
+        };
+
+        let mut row = Vec::with_capacity(test_target_vars.len());
+        for test_target_var in test_target_vars {
+            let value = data.test_data
+                .iter()
+                .filter(|test_dataset| test_dataset.len() == state_dataset.len())
+                .find_map(|test_dataset| {
+                    match test_dataset[case_idx].values.get(test_target_var) {
+                        Some(DataValue::Number(val)) => Some(*val),
+                        _ => None,
+                    }
+                });
+
+            match value {
+                Some(val) => row.push(val),
+                None => {
+                    break;
+                }
+            }
+        }
+
+        if row.len() != test_target_vars.len() {
+            continue;
+        }
+
+        if is_positive {
+            positive_rows.push(row);
+        } else {
+            negative_rows.push(row);
+        }
+    }
+
+    if positive_rows.is_empty() || negative_rows.is_empty() {
+        return Err(
+            format!(
+                "Insufficient positive ({}) or negative ({}) cases with complete values for test variables {:?}",
+                positive_rows.len(),
+                negative_rows.len(),
+                test_target_vars
+            )
+        );
+    }
+
+    Ok((positive_rows, negative_rows))
+}
+
+/// Positive and negative test values of the first group, then of the second group
+pub type GroupedValues = (Vec<f64>, Vec<f64>, Vec<f64>, Vec<f64>);
+
 pub fn extract_grouped_values(
     data: &AnalysisData,
     config: &RocConfig,
     test_target_var: &str
-) -> Result<(Vec<f64>, Vec<f64>, Vec<f64>, Vec<f64>), String> {
+) -> Result<GroupedValues, String> {
     if config.main.state_target_variable.is_none() {
         return Err("State target variable is not specified".to_string());
     }
@@ -126,21 +225,17 @@ pub fn extract_grouped_values(
         if let Some(group_dataset) = data.group_data.first() {
             if !group_dataset.is_empty() {
                 for record in group_dataset {
-                    if let Some(group_value) = record.values.get(target_group_var) {
-                        if let DataValue::Number(val) = group_value {
-                            min_val = min_val.min(*val);
-                            max_val = max_val.max(*val);
-                        }
+                    if let Some(DataValue::Number(val)) = record.values.get(target_group_var) {
+                        min_val = min_val.min(*val);
+                        max_val = max_val.max(*val);
                     }
                 }
             } else {
                 if let Some(state_dataset) = data.state_data.first() {
                     for record in state_dataset {
-                        if let Some(group_value) = record.values.get(target_group_var) {
-                            if let DataValue::Number(val) = group_value {
-                                min_val = min_val.min(*val);
-                                max_val = max_val.max(*val);
-                            }
+                        if let Some(DataValue::Number(val)) = record.values.get(target_group_var) {
+                            min_val = min_val.min(*val);
+                            max_val = max_val.max(*val);
                         }
                     }
                 }
@@ -148,11 +243,9 @@ pub fn extract_grouped_values(
         } else {
             if let Some(state_dataset) = data.state_data.first() {
                 for record in state_dataset {
-                    if let Some(group_value) = record.values.get(target_group_var) {
-                        if let DataValue::Number(val) = group_value {
-                            min_val = min_val.min(*val);
-                            max_val = max_val.max(*val);
-                        }
+                    if let Some(DataValue::Number(val)) = record.values.get(target_group_var) {
+                        min_val = min_val.min(*val);
+                        max_val = max_val.max(*val);
                     }
                 }
             } else {
@@ -170,10 +263,7 @@ pub fn extract_grouped_values(
         return Err("No group definition method specified".to_string());
     };
 
-    let (state_dataset, test_dataset, group_dataset) = if
-        !data.group_data.is_empty() &&
-        data.group_data.first().is_some()
-    {
+    let (state_dataset, test_dataset, group_dataset) = if !data.group_data.is_empty() {
         (data.state_data.first(), data.test_data.first(), data.group_data.first())
     } else {
         (data.state_data.first(), data.test_data.first(), data.state_data.first())
@@ -222,13 +312,8 @@ pub fn extract_grouped_values(
                 continue;
             };
 
-            let test_value = if let Some(tv) = test_record.values.get(test_target_var) {
-                match tv {
-                    DataValue::Number(val) => *val,
-                    _ => {
-                        continue;
-                    }
-                }
+            let test_value = if let Some(DataValue::Number(val)) = test_record.values.get(test_target_var) {
+                *val
             } else {
                 continue;
             };
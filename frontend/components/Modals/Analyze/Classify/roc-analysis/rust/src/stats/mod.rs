@@ -2,7 +2,9 @@ pub mod core;
 pub mod auc;
 pub mod case_processing;
 pub mod common;
+pub mod comparison;
 pub mod coordinates;
+pub mod cut_points;
 pub mod data_extraction;
 pub mod model_quality;
 pub mod precision_recall;
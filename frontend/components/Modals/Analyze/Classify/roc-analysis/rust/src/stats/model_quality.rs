@@ -43,7 +43,7 @@ pub fn calculate_overall_model_quality_for_variable(
     config: &RocConfig,
     test_var: &str
 ) -> Result<f64, String> {
-    if config.main.paired_sample || !config.main.target_group_var.is_some() {
+    if config.main.paired_sample || config.main.target_group_var.is_none() {
         let auc_result = calculate_area_under_roc_curve_for_variable(data, config, test_var)?;
         return Ok(auc_result.asymptotic_95_confidence_interval.lower_bound);
     }
@@ -86,7 +86,7 @@ pub fn calculate_classifier_evaluation_metrics_for_variable(
     config: &RocConfig,
     test_var: &str
 ) -> Result<ClassifierEvaluationMetrics, String> {
-    if config.main.paired_sample || !config.main.target_group_var.is_some() {
+    if config.main.paired_sample || config.main.target_group_var.is_none() {
         let auc_result = calculate_area_under_roc_curve_for_variable(data, config, test_var)?;
         let roc_coordinates = calculate_roc_coordinates_for_variable(data, config, test_var)?;
 
@@ -78,18 +78,10 @@ fn calculate_pr_coordinates_from_values(
         let precision = if tp + fp > 0 { (tp as f64) / ((tp + fp) as f64) } else { f64::NAN };
         let recall = if tp + fn_count > 0 { (tp as f64) / ((tp + fn_count) as f64) } else { 0.0 };
 
-        let adjusted_values = if config.display.intepol_true {
-            (precision, recall)
-        } else if config.display.intepol_false {
-            (precision, recall)
-        } else {
-            (precision, recall)
-        };
-
         coordinates.push(PrecisionRecallCoordinate {
             positive_if_greater_than: cutoff,
-            precision: adjusted_values.0,
-            recall: adjusted_values.1,
+            precision,
+            recall,
         });
     }
 
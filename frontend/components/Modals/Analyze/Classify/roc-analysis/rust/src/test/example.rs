@@ -0,0 +1,187 @@
+use std::collections::HashMap;
+
+use crate::models::{
+    config::RocConfig,
+    data::{ AnalysisData, DataRecord, DataValue },
+};
+use crate::stats::{
+    comparison::{ compare_correlated_curves, delong_components },
+    cut_points::calculate_optimal_cut_points,
+};
+
+// Two tests read on the same five diseased and five healthy cases, with ties within each test
+const POSITIVE: [[f64; 2]; 5] = [
+    [9.0, 6.0],
+    [8.0, 9.0],
+    [6.0, 5.0],
+    [4.0, 7.0],
+    [7.0, 8.0],
+];
+const NEGATIVE: [[f64; 2]; 5] = [
+    [7.0, 3.0],
+    [5.0, 8.0],
+    [3.0, 5.0],
+    [2.0, 1.0],
+    [6.0, 4.0],
+];
+
+// One test read at two sites; site a separates the states perfectly, site b only partly
+const SITE_A: ([f64; 3], [f64; 3]) = ([5.0, 6.0, 7.0], [1.0, 2.0, 3.0]);
+const SITE_B: ([f64; 3], [f64; 3]) = ([2.0, 4.0, 6.0], [1.0, 3.0, 5.0]);
+
+// (group, cutoff, sensitivity, specificity, Youden index): at site b the cutoffs 1.5, 3.5 and
+// 5.5 all reach J = 1/3 and the lowest is kept
+const SITE_CUT_POINTS: [(&str, f64, f64, f64, f64); 2] = [
+    ("a", 4.0, 1.0, 1.0, 1.0),
+    ("b", 1.5, 1.0, 1.0 / 3.0, 1.0 / 3.0),
+];
+
+fn record(values: &[(&str, f64)]) -> DataRecord {
+    DataRecord {
+        values: values
+            .iter()
+            .map(|(name, value)| (name.to_string(), DataValue::Number(*value)))
+            .collect::<HashMap<_, _>>(),
+    }
+}
+
+fn paired_data() -> AnalysisData {
+    let mut test_records = Vec::new();
+    let mut state_records = Vec::new();
+
+    for (state, rows) in [(1.0, &POSITIVE), (0.0, &NEGATIVE)] {
+        for row in rows.iter() {
+            test_records.push(
+                record(
+                    &[
+                        ("test1", row[0]),
+                        ("test2", row[1]),
+                    ]
+                )
+            );
+            state_records.push(record(&[("disease", state)]));
+        }
+    }
+
+    AnalysisData {
+        test_data: vec![test_records],
+        state_data: vec![state_records],
+        group_data: vec![],
+        test_data_defs: vec![],
+        state_data_defs: vec![],
+        group_data_defs: vec![],
+    }
+}
+
+fn paired_config() -> RocConfig {
+    let mut config = RocConfig::default();
+    config.main.paired_sample = true;
+    config.main.state_target_variable = Some("disease".to_string());
+    config.main.state_var_val = Some("1".to_string());
+    config.main.test_target_variable = Some(vec!["test1".to_string(), "test2".to_string()]);
+    config.display.compare_curves = true;
+    config
+}
+
+fn grouped_data() -> AnalysisData {
+    let mut test_records = Vec::new();
+    let mut state_records = Vec::new();
+    let mut group_records = Vec::new();
+
+    for (site, (positive, negative)) in [("a", &SITE_A), ("b", &SITE_B)] {
+        for (state, values) in [(1.0, positive), (0.0, negative)] {
+            for &value in values {
+                test_records.push(record(&[("test1", value)]));
+                state_records.push(record(&[("disease", state)]));
+                group_records.push(DataRecord {
+                    values: HashMap::from([
+                        ("site".to_string(), DataValue::Text(site.to_string())),
+                    ]),
+                });
+            }
+        }
+    }
+
+    AnalysisData {
+        test_data: vec![test_records],
+        state_data: vec![state_records],
+        group_data: vec![group_records],
+        test_data_defs: vec![],
+        state_data_defs: vec![],
+        group_data_defs: vec![],
+    }
+}
+
+fn grouped_config() -> RocConfig {
+    let mut config = RocConfig::default();
+    config.main.state_target_variable = Some("disease".to_string());
+    config.main.state_var_val = Some("1".to_string());
+    config.main.test_target_variable = Some(vec!["test1".to_string()]);
+    config.main.target_group_var = Some("site".to_string());
+    config.define_groups.group1 = Some("a".to_string());
+    config.define_groups.group2 = Some("b".to_string());
+    config.cut_points.optimal_cut_points = true;
+    config
+}
+
+fn column(rows: &[[f64; 2]], index: usize) -> Vec<f64> {
+    rows.iter()
+        .map(|row| row[index])
+        .collect()
+}
+
+// Placement values worked by hand; a tie between a positive and a negative case scores 1/2
+#[test]
+fn delong_placement_values() {
+    let (area, v10, v01) = delong_components(&column(&POSITIVE, 0), &column(&NEGATIVE, 0), true);
+
+    assert!((area - 0.8).abs() < 1e-12);
+    let expected_v10 = [1.0, 1.0, 0.7, 0.4, 0.9];
+    let expected_v01 = [0.5, 0.8, 1.0, 1.0, 0.7];
+    for (actual, expected) in v10.iter().zip(expected_v10.iter()) {
+        assert!((actual - expected).abs() < 1e-12);
+    }
+    for (actual, expected) in v01.iter().zip(expected_v01.iter()) {
+        assert!((actual - expected).abs() < 1e-12);
+    }
+}
+
+// DeLong et al. (1988) as in pROC's roc.test(method = "delong"): AUCs 4/5 and 21/25,
+// S11 = 11/500, S22 = 53/2500, S12 = 1/500, so var(A1 - A2) = 49/1250
+#[test]
+fn delong_paired_comparison() {
+    let comparisons = compare_correlated_curves(
+        &paired_data(),
+        &paired_config(),
+        &["test1".to_string(), "test2".to_string()]
+    ).unwrap();
+
+    assert_eq!(comparisons.len(), 1);
+    let comparison = &comparisons[0];
+
+    assert_eq!(comparison.method, "DeLong");
+    assert!((comparison.first_area - 0.8).abs() < 1e-12);
+    assert!((comparison.second_area - 0.84).abs() < 1e-12);
+    assert!((comparison.difference + 0.04).abs() < 1e-12);
+    assert!((comparison.std_error - (49.0_f64 / 1250.0).sqrt()).abs() < 1e-12);
+    assert!((comparison.z_statistic + 0.2020305089).abs() < 1e-9);
+    assert!((comparison.asymptotic_sig - 0.8398928733).abs() < 1e-6);
+    assert!((comparison.correlation - 0.0926084733).abs() < 1e-9);
+}
+
+#[test]
+fn grouped_cut_points_are_reported_per_group() {
+    let cut_points = calculate_optimal_cut_points(&grouped_data(), &grouped_config()).unwrap();
+    assert_eq!(cut_points.len(), 2);
+
+    for (group, cutoff, sensitivity, specificity, youden) in SITE_CUT_POINTS {
+        let result = &cut_points[&format!("test1 ({})", group)];
+        assert!((result.prevalence - 0.5).abs() < 1e-12);
+        let point = &result.cut_points[0];
+        assert_eq!(point.criterion, "Youden Index");
+        assert!((point.cutoff - cutoff).abs() < 1e-12, "{}", group);
+        assert!((point.sensitivity - sensitivity).abs() < 1e-12, "{}", group);
+        assert!((point.specificity - specificity).abs() < 1e-12, "{}", group);
+        assert!((point.criterion_value - youden).abs() < 1e-12, "{}", group);
+    }
+}
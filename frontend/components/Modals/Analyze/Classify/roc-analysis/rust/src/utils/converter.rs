@@ -7,7 +7,9 @@ use crate::models::result::{
     PrecisionRecallCoordinate,
     RocCoordinate,
     AreaUnderRocCurve,
+    AreaUnderRocCurveComparison,
     ClassifierEvaluationMetrics,
+    OptimalCutPoints,
 };
 
 // Konversi dari String error ke JsValue untuk interaksi WASM
@@ -33,6 +35,8 @@ struct FormatResult {
     area_under_roc_curve: Option<Vec<FormattedArea>>,
     overall_model_quality: Option<Vec<FormattedQuality>>,
     classifier_evaluation_metrics: Option<Vec<FormattedMetrics>>,
+    area_under_roc_curve_comparison: Option<Vec<AreaUnderRocCurveComparison>>,
+    optimal_cut_points: Option<Vec<FormattedCutPoints>>,
 }
 
 #[derive(Serialize)]
@@ -65,6 +69,12 @@ struct FormattedMetrics {
     metrics: ClassifierEvaluationMetrics,
 }
 
+#[derive(Serialize)]
+struct FormattedCutPoints {
+    variable: String,
+    data: OptimalCutPoints,
+}
+
 impl FormatResult {
     fn from_analysis_result(result: &ROCAnalysisResult) -> Self {
         let coordinates_precision_recall = result.coordinates_precision_recall.as_ref().map(|map| {
@@ -124,6 +134,17 @@ impl FormatResult {
                     .collect()
             });
 
+        let optimal_cut_points = result.optimal_cut_points.as_ref().map(|map| {
+            map.iter()
+                .map(|(key, value)| {
+                    FormattedCutPoints {
+                        variable: key.clone(),
+                        data: value.clone(),
+                    }
+                })
+                .collect()
+        });
+
         FormatResult {
             case_processing_summary: result.case_processing_summary.clone(),
             coordinates_precision_recall,
@@ -131,6 +152,8 @@ impl FormatResult {
             area_under_roc_curve,
             overall_model_quality,
             classifier_evaluation_metrics,
+            area_under_roc_curve_comparison: result.area_under_roc_curve_comparison.clone(),
+            optimal_cut_points,
         }
     }
 }
@@ -17,7 +17,6 @@ pub struct RocAnalysis {
     data: AnalysisData,
     result: Option<ROCAnalysisResult>,
     error_collector: ErrorCollector,
-    executed_functions: Vec<String>,
     logger: FunctionLogger,
 }
 
@@ -148,7 +147,6 @@ impl RocAnalysis {
             data,
             result: None,
             error_collector,
-            executed_functions: Vec::new(),
             logger,
         };
 
@@ -129,6 +129,45 @@ pub fn run_analysis(
         };
     }
 
+    // Step 7: Area Under ROC Curve comparison (DeLong or Hanley-McNeil) if requested
+    let mut area_under_roc_curve_comparison = None;
+    if config.display.compare_curves {
+        logger.add_log("calculate_area_under_roc_curve_comparison");
+        match core::calculate_area_under_roc_curve_comparison(data, config) {
+            Ok(comparison) => {
+                area_under_roc_curve_comparison = Some(comparison);
+                web_sys::console::log_1(
+                    &format!(
+                        "Area Under ROC Curve Comparison: {:?}",
+                        area_under_roc_curve_comparison
+                    ).into()
+                );
+            }
+            Err(e) => {
+                error_collector.add_error("calculate_area_under_roc_curve_comparison", &e);
+                // Continue execution despite errors for non-critical functions
+            }
+        };
+    }
+
+    // Step 8: Optimal cut-points with bootstrap intervals and net benefit if requested
+    let mut optimal_cut_points = None;
+    if config.cut_points.optimal_cut_points {
+        logger.add_log("calculate_optimal_cut_points");
+        match core::calculate_optimal_cut_points(data, config) {
+            Ok(cut_points) => {
+                optimal_cut_points = Some(cut_points);
+                web_sys::console::log_1(
+                    &format!("Optimal Cut Points: {:?}", optimal_cut_points).into()
+                );
+            }
+            Err(e) => {
+                error_collector.add_error("calculate_optimal_cut_points", &e);
+                // Continue execution despite errors for non-critical functions
+            }
+        };
+    }
+
     // Create the final result
     let result = ROCAnalysisResult {
         case_processing_summary,
@@ -137,6 +176,8 @@ pub fn run_analysis(
         area_under_roc_curve,
         overall_model_quality,
         classifier_evaluation_metrics,
+        area_under_roc_curve_comparison,
+        optimal_cut_points,
     };
 
     Ok(Some(result))
@@ -320,5 +320,215 @@ export function transformROCAnalysisResult(data: any): ResultJson {
         });
     }
 
+    // 7. Area Under the ROC Curve Comparison
+    if (
+        data.area_under_roc_curve_comparison &&
+        data.area_under_roc_curve_comparison.length > 0
+    ) {
+        const table: Table = {
+            key: "paired_area_difference",
+            title: "Area Difference Under the ROC Curves",
+            columnHeaders: [
+                { header: "Test Result Pair(s)" },
+                { header: "Method" },
+                { header: "First Area" },
+                { header: "Second Area" },
+                { header: "Area Difference" },
+                { header: "Std. Error Difference\u1D43" },
+                { header: "Z" },
+                { header: "Asymptotic Sig. (2-tail)\u1D47" },
+                { header: "Asymptotic 95% Confidence Interval" },
+                { header: "Lower Bound" },
+                { header: "Upper Bound" },
+                { header: "Correlation" },
+            ],
+            rows: [],
+        };
+
+        data.area_under_roc_curve_comparison.forEach((item: any) => {
+            table.rows.push({
+                rowHeader: [`${item.first_curve} - ${item.second_curve}`],
+                Method: item.method,
+                "First Area": formatDisplayNumber(item.first_area),
+                "Second Area": formatDisplayNumber(item.second_area),
+                "Area Difference": formatDisplayNumber(item.difference),
+                "Std. Error Difference\u1D43": formatDisplayNumber(
+                    item.std_error
+                ),
+                Z: formatDisplayNumber(item.z_statistic),
+                "Asymptotic Sig. (2-tail)\u1D47": formatDisplayNumber(
+                    item.asymptotic_sig
+                ),
+                "Lower Bound": formatDisplayNumber(
+                    item.confidence_interval.lower_bound
+                ),
+                "Upper Bound": formatDisplayNumber(
+                    item.confidence_interval.upper_bound
+                ),
+                Correlation: formatDisplayNumber(item.correlation),
+            });
+        });
+
+        table.rows.push({
+            rowHeader: ["a. Under the nonparametric assumption"],
+            Method: null,
+            "First Area": null,
+            "Second Area": null,
+            "Area Difference": null,
+            "Std. Error Difference\u1D43": null,
+            Z: null,
+            "Asymptotic Sig. (2-tail)\u1D47": null,
+            "Lower Bound": null,
+            "Upper Bound": null,
+            Correlation: null,
+        });
+
+        table.rows.push({
+            rowHeader: ["b. Null hypothesis: true area difference = 0"],
+            Method: null,
+            "First Area": null,
+            "Second Area": null,
+            "Area Difference": null,
+            "Std. Error Difference\u1D43": null,
+            Z: null,
+            "Asymptotic Sig. (2-tail)\u1D47": null,
+            "Lower Bound": null,
+            "Upper Bound": null,
+            Correlation: null,
+        });
+
+        resultJson.tables.push(ensureEnoughHeaders(table));
+    }
+
+    // 8. Optimal Cut Points
+    if (data.optimal_cut_points) {
+        data.optimal_cut_points.forEach((item: any) => {
+            const variable = item.variable;
+            const result = item.data;
+            const direction = result.cut_points[0]?.positive_if ?? "";
+            const cutoffHeader = `Positive if ${direction}\u1D43`;
+
+            const table: Table = {
+                key: `optimal_cut_points_${variable}`,
+                title: "Optimal Cut Points",
+                columnHeaders: [
+                    { header: "Criterion" },
+                    { header: cutoffHeader },
+                    { header: "Sensitivity" },
+                    { header: "Specificity" },
+                    { header: "Criterion Value" },
+                    { header: "Net Benefit\u1D47" },
+                ],
+                rows: [],
+            };
+
+            result.cut_points.forEach((point: any) => {
+                table.rows.push({
+                    rowHeader: [point.criterion],
+                    [cutoffHeader]: formatDisplayNumber(point.cutoff),
+                    Sensitivity: formatDisplayNumber(point.sensitivity),
+                    Specificity: formatDisplayNumber(point.specificity),
+                    "Criterion Value": formatDisplayNumber(
+                        point.criterion_value
+                    ),
+                    "Net Benefit\u1D47": formatDisplayNumber(
+                        point.net_benefit
+                    ),
+                });
+            });
+
+            table.rows.push({
+                rowHeader: [`a. Test result variable(s): ${variable}`],
+                [cutoffHeader]: null,
+                Sensitivity: null,
+                Specificity: null,
+                "Criterion Value": null,
+                "Net Benefit\u1D47": null,
+            });
+
+            table.rows.push({
+                rowHeader: [
+                    `b. Evaluated at prevalence ${formatDisplayNumber(
+                        result.prevalence
+                    )} and threshold probability ${formatDisplayNumber(
+                        result.threshold_probability
+                    )}`,
+                ],
+                [cutoffHeader]: null,
+                Sensitivity: null,
+                Specificity: null,
+                "Criterion Value": null,
+                "Net Benefit\u1D47": null,
+            });
+
+            resultJson.tables.push(ensureEnoughHeaders(table));
+
+            if (result.bootstrap_samples) {
+                const intervalTable: Table = {
+                    key: `cut_point_bootstrap_${variable}`,
+                    title: "Bootstrap Confidence Intervals of the Optimal Cut Points",
+                    columnHeaders: [
+                        { header: "Criterion" },
+                        { header: "Cutoff Lower" },
+                        { header: "Cutoff Upper" },
+                        { header: "Sensitivity Lower" },
+                        { header: "Sensitivity Upper" },
+                        { header: "Specificity Lower" },
+                        { header: "Specificity Upper" },
+                        { header: "Net Benefit Lower" },
+                        { header: "Net Benefit Upper" },
+                    ],
+                    rows: [],
+                };
+
+                result.cut_points.forEach((point: any) => {
+                    intervalTable.rows.push({
+                        rowHeader: [point.criterion],
+                        "Cutoff Lower": formatDisplayNumber(
+                            point.cutoff_interval?.lower_bound ?? null
+                        ),
+                        "Cutoff Upper": formatDisplayNumber(
+                            point.cutoff_interval?.upper_bound ?? null
+                        ),
+                        "Sensitivity Lower": formatDisplayNumber(
+                            point.sensitivity_interval?.lower_bound ?? null
+                        ),
+                        "Sensitivity Upper": formatDisplayNumber(
+                            point.sensitivity_interval?.upper_bound ?? null
+                        ),
+                        "Specificity Lower": formatDisplayNumber(
+                            point.specificity_interval?.lower_bound ?? null
+                        ),
+                        "Specificity Upper": formatDisplayNumber(
+                            point.specificity_interval?.upper_bound ?? null
+                        ),
+                        "Net Benefit Lower": formatDisplayNumber(
+                            point.net_benefit_interval?.lower_bound ?? null
+                        ),
+                        "Net Benefit Upper": formatDisplayNumber(
+                            point.net_benefit_interval?.upper_bound ?? null
+                        ),
+                    });
+                });
+
+                intervalTable.rows.push({
+                    rowHeader: [
+                        `Percentile intervals from ${result.bootstrap_samples} bootstrap samples drawn within each actual state.`,
+                    ],
+                    "Cutoff Lower": null,
+                    "Cutoff Upper": null,
+                    "Sensitivity Lower": null,
+                    "Sensitivity Upper": null,
+                    "Specificity Lower": null,
+                    "Specificity Upper": null,
+                    "Net Benefit Lower": null,
+                    "Net Benefit Upper": null,
+                });
+
+                resultJson.tables.push(ensureEnoughHeaders(intervalTable));
+            }
+        });
+    }
+
     return resultJson;
 }
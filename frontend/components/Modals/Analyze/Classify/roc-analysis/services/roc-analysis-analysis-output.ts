@@ -176,6 +176,63 @@ export async function resultROCAnalysis({
                     }
                 }
             }
+
+            /*
+             * ⚖️ Area Difference Under the ROC Curves Result ⚖️
+             * */
+            const areaDifference = findTable("paired_area_difference");
+            if (areaDifference) {
+                const areaDifferenceId = await addAnalytic(logId, {
+                    title: `Area Difference Under the ROC Curves`,
+                    note: "",
+                });
+
+                await addStatistic(areaDifferenceId, {
+                    title: `Area Difference Under the ROC Curves`,
+                    description: `Pairwise comparison of the areas under the ROC curves`,
+                    output_data: areaDifference,
+                    components: `Area Difference Under the ROC Curves`,
+                });
+            }
+
+            /*
+             * ✂️ Optimal Cut Points Results ✂️
+             * */
+            for (const table of formattedResult.tables) {
+                if (table.key.startsWith("optimal_cut_points_")) {
+                    const variable = table.key.replace(
+                        "optimal_cut_points_",
+                        ""
+                    );
+                    const optimalCutPoints = findTable(table.key);
+
+                    if (optimalCutPoints) {
+                        const optimalCutPointsId = await addAnalytic(logId, {
+                            title: `Optimal Cut Points - ${variable}`,
+                            note: "",
+                        });
+
+                        await addStatistic(optimalCutPointsId, {
+                            title: `Optimal Cut Points`,
+                            description: `Optimal Cut Points for ${variable}`,
+                            output_data: optimalCutPoints,
+                            components: `Optimal Cut Points`,
+                        });
+
+                        const bootstrapIntervals = findTable(
+                            `cut_point_bootstrap_${variable}`
+                        );
+                        if (bootstrapIntervals) {
+                            await addStatistic(optimalCutPointsId, {
+                                title: `Bootstrap Confidence Intervals of the Optimal Cut Points`,
+                                description: `Bootstrap Confidence Intervals of the Optimal Cut Points for ${variable}`,
+                                output_data: bootstrapIntervals,
+                                components: `Bootstrap Confidence Intervals of the Optimal Cut Points`,
+                            });
+                        }
+                    }
+                }
+            }
         };
 
         await rocAnalysisResult();
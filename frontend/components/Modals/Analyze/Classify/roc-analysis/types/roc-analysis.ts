@@ -14,6 +14,7 @@ export type RocAnalysisDialogProps = {
     setIsDefineGroupsOpen: React.Dispatch<React.SetStateAction<boolean>>;
     setIsOptionsOpen: React.Dispatch<React.SetStateAction<boolean>>;
     setIsDisplayOpen: React.Dispatch<React.SetStateAction<boolean>>;
+    setIsCutPointsOpen: React.Dispatch<React.SetStateAction<boolean>>;
     updateFormData: (
         field: keyof RocAnalysisMainType,
         value: string[] | string | boolean | null
@@ -75,6 +76,7 @@ export type RocAnalysisDisplayType = {
     ROCPoint: boolean;
     PRCPoint: boolean;
     EvalMetrics: boolean;
+    CompareCurves: boolean;
 };
 
 export type RocAnalysisDisplayProps = {
@@ -87,11 +89,38 @@ export type RocAnalysisDisplayProps = {
     data: RocAnalysisDisplayType;
 };
 
+export type RocAnalysisCutPointsType = {
+    OptimalCutPoints: boolean;
+    Youden: boolean;
+    ClosestToTopLeft: boolean;
+    CostWeighted: boolean;
+    FalsePositiveCost: number;
+    FalseNegativeCost: number;
+    Prevalence: number | null;
+    MinSensitivity: number | null;
+    MinSpecificity: number | null;
+    PerformBootStrapping: boolean;
+    NumOfSamples: number | null;
+    Seed: boolean;
+    SeedValue: number | null;
+};
+
+export type RocAnalysisCutPointsProps = {
+    isCutPointsOpen: boolean;
+    setIsCutPointsOpen: React.Dispatch<React.SetStateAction<boolean>>;
+    updateFormData: (
+        field: keyof RocAnalysisCutPointsType,
+        value: string | boolean | number | null
+    ) => void;
+    data: RocAnalysisCutPointsType;
+};
+
 export type RocAnalysisType = {
     main: RocAnalysisMainType;
     defineGroups: RocAnalysisDefineGroupsType;
     options: RocAnalysisOptionsType;
     display: RocAnalysisDisplayType;
+    cutPoints: RocAnalysisCutPointsType;
 };
 
 export type RocAnalysisContainerProps = {
@@ -1,4 +1,5 @@
 import {
+    CorrespondenceCanonicalType,
     CorrespondenceDefineRangeColumnType,
     CorrespondenceDefineRangeRowType,
     CorrespondenceMainType,
@@ -11,6 +12,9 @@ import {
 export const CorrespondenceMainDefault: CorrespondenceMainType = {
     RowTargetVar: null,
     ColTargetVar: null,
+    RowTargetVars: null,
+    ColTargetVars: null,
+    Burt: false,
 };
 
 export const CorrespondenceDefineRangeRowDefault: CorrespondenceDefineRangeRowType =
@@ -63,6 +67,10 @@ export const CorrespondenceStatisticsDefault: CorrespondenceStatisticsType = {
     ColProfile: false,
     RowPoints: false,
     ColPoints: false,
+    Seed: null,
+    Bootstrap: false,
+    BootstrapSamples: 1000,
+    ConfidenceLevel: 0.95,
 };
 
 export const CorrespondencePlotsDefault: CorrespondencePlotsType = {
@@ -79,6 +87,13 @@ export const CorrespondencePlotsDefault: CorrespondencePlotsType = {
     Highest: null,
 };
 
+export const CorrespondenceCanonicalDefault: CorrespondenceCanonicalType = {
+    Covariates: [],
+    Dimensions: null,
+    PermutationTest: false,
+    MaxPermutations: 999,
+};
+
 export const CorrespondenceDefault: CorrespondenceType = {
     main: CorrespondenceMainDefault,
     defineRangeRow: CorrespondenceDefineRangeRowDefault,
@@ -86,4 +101,5 @@ export const CorrespondenceDefault: CorrespondenceType = {
     model: CorrespondenceModelDefault,
     statistics: CorrespondenceStatisticsDefault,
     plots: CorrespondencePlotsDefault,
+    canonical: null,
 };
@@ -0,0 +1,187 @@
+import React, {useEffect, useState} from "react";
+import {Dialog, DialogContent, DialogFooter, DialogHeader, DialogTitle,} from "@/components/ui/dialog";
+import {Button} from "@/components/ui/button";
+import {Separator} from "@/components/ui/separator";
+import {
+    CorrespondenceCanonicalProps,
+    CorrespondenceCanonicalType,
+} from "@/components/Modals/Analyze/dimension-reduction/correspondence-analysis/types/correspondence-analysis";
+import {Label} from "@/components/ui/label";
+import {Input} from "@/components/ui/input";
+import {ResizablePanel, ResizablePanelGroup} from "@/components/ui/resizable";
+import {ScrollArea} from "@/components/ui/scroll-area";
+import {Checkbox} from "@/components/ui/checkbox";
+import {CheckedState} from "@radix-ui/react-checkbox";
+
+export const CorrespondenceCanonical = ({
+    isCanonicalOpen,
+    setIsCanonicalOpen,
+    updateFormData,
+    data,
+    globalVariables,
+}: CorrespondenceCanonicalProps) => {
+    const [canonicalState, setCanonicalState] =
+        useState<CorrespondenceCanonicalType>({ ...data });
+
+    useEffect(() => {
+        if (isCanonicalOpen) {
+            setCanonicalState({ ...data });
+        }
+    }, [isCanonicalOpen, data]);
+
+    const handleChange = (
+        field: keyof CorrespondenceCanonicalType,
+        value: CheckedState | number | null
+    ) => {
+        setCanonicalState((prevState) => ({
+            ...prevState,
+            [field]: value,
+        }));
+    };
+
+    const handleCovariate = (variable: string, checked: CheckedState) => {
+        setCanonicalState((prevState) => ({
+            ...prevState,
+            Covariates: checked
+                ? [...prevState.Covariates, variable]
+                : prevState.Covariates.filter((v) => v !== variable),
+        }));
+    };
+
+    const handleContinue = () => {
+        Object.entries(canonicalState).forEach(([key, value]) => {
+            updateFormData(key as keyof CorrespondenceCanonicalType, value);
+        });
+        setIsCanonicalOpen(false);
+    };
+
+    return (
+        <>
+            {/* Canonical Dialog */}
+            <Dialog open={isCanonicalOpen} onOpenChange={setIsCanonicalOpen}>
+                <DialogContent className="sm:max-w-xl">
+                    <DialogHeader>
+                        <DialogTitle>
+                            Correspondence Analysis: Canonical
+                        </DialogTitle>
+                    </DialogHeader>
+                    <Separator />
+                    <div className="flex flex-col gap-2">
+                        <ResizablePanelGroup
+                            direction="vertical"
+                            className="min-h-[160px] max-w-xl rounded-lg border md:min-w-[200px]"
+                        >
+                            <ResizablePanel defaultSize={100}>
+                                <div className="flex flex-col gap-2 p-2">
+                                    <Label className="font-bold">
+                                        Row Covariates
+                                    </Label>
+                                    <ScrollArea>
+                                        <div className="flex flex-col gap-2 h-[120px]">
+                                            {globalVariables.map((variable) => (
+                                                <div
+                                                    key={variable}
+                                                    className="flex items-center space-x-2"
+                                                >
+                                                    <Checkbox
+                                                        id={`Covariate-${variable}`}
+                                                        checked={canonicalState.Covariates.includes(
+                                                            variable
+                                                        )}
+                                                        onCheckedChange={(
+                                                            checked
+                                                        ) =>
+                                                            handleCovariate(
+                                                                variable,
+                                                                checked
+                                                            )
+                                                        }
+                                                    />
+                                                    <label
+                                                        htmlFor={`Covariate-${variable}`}
+                                                        className="text-sm font-medium leading-none peer-disabled:cursor-not-allowed peer-disabled:opacity-70"
+                                                    >
+                                                        {variable}
+                                                    </label>
+                                                </div>
+                                            ))}
+                                        </div>
+                                    </ScrollArea>
+                                </div>
+                            </ResizablePanel>
+                        </ResizablePanelGroup>
+                        <div className="flex items-center space-x-2">
+                            <Label className="w-[224px]">
+                                Constrained Dimensions (optional):
+                            </Label>
+                            <div className="w-[75px]">
+                                <Input
+                                    id="Dimensions"
+                                    type="number"
+                                    placeholder=""
+                                    value={canonicalState.Dimensions ?? ""}
+                                    onChange={(e) =>
+                                        handleChange(
+                                            "Dimensions",
+                                            e.target.value === ""
+                                                ? null
+                                                : Number(e.target.value)
+                                        )
+                                    }
+                                />
+                            </div>
+                        </div>
+                        <div className="flex items-center space-x-2">
+                            <Checkbox
+                                id="CanonicalPermutationTest"
+                                checked={canonicalState.PermutationTest}
+                                onCheckedChange={(checked) =>
+                                    handleChange("PermutationTest", checked)
+                                }
+                            />
+                            <label
+                                htmlFor="CanonicalPermutationTest"
+                                className="text-sm font-medium leading-none peer-disabled:cursor-not-allowed peer-disabled:opacity-70"
+                            >
+                                Permutation Test of the Constraints
+                            </label>
+                        </div>
+                        <div className="flex items-center space-x-2 pl-6">
+                            <Label className="w-[200px]">Permutations:</Label>
+                            <div className="w-[75px]">
+                                <Input
+                                    id="CanonicalMaxPermutations"
+                                    type="number"
+                                    placeholder=""
+                                    value={canonicalState.MaxPermutations ?? ""}
+                                    disabled={!canonicalState.PermutationTest}
+                                    onChange={(e) =>
+                                        handleChange(
+                                            "MaxPermutations",
+                                            Number(e.target.value)
+                                        )
+                                    }
+                                />
+                            </div>
+                        </div>
+                    </div>
+                    <DialogFooter className="sm:justify-start">
+                        <Button type="button" onClick={handleContinue}>
+                            Continue
+                        </Button>
+                        <Button
+                            type="button"
+                            variant="secondary"
+                            onClick={() => setIsCanonicalOpen(false)}
+                        >
+                            Cancel
+                        </Button>
+                        <Button type="button" variant="secondary">
+                            Help
+                        </Button>
+                    </DialogFooter>
+                </DialogContent>
+            </Dialog>
+        </>
+    );
+};
@@ -1,10 +1,12 @@
 import {useEffect, useMemo, useState} from "react";
 import {
+    CorrespondenceCanonicalType,
     CorrespondenceContainerProps,
     CorrespondenceMainType,
     CorrespondenceType,
 } from "@/components/Modals/Analyze/dimension-reduction/correspondence-analysis/types/correspondence-analysis";
 import {
+    CorrespondenceCanonicalDefault,
     CorrespondenceDefault
 } from "@/components/Modals/Analyze/dimension-reduction/correspondence-analysis/constants/correspondence-analysis-default";
 import {
@@ -25,6 +27,9 @@ import {
 import {
     CorrespondenceStatistics
 } from "@/components/Modals/Analyze/dimension-reduction/correspondence-analysis/dialogs/statistics";
+import {
+    CorrespondenceCanonical
+} from "@/components/Modals/Analyze/dimension-reduction/correspondence-analysis/dialogs/canonical";
 import {Dialog, DialogContent, DialogTitle} from "@/components/ui/dialog";
 import {useModal} from "@/hooks/useModal";
 import {useVariableStore} from "@/stores/useVariableStore";
@@ -56,6 +61,7 @@ export const CorrespondenceContainer = ({
     const [isModelOpen, setIsModelOpen] = useState(false);
     const [isStatisticsOpen, setIsStatisticsOpen] = useState(false);
     const [isPlotsOpen, setIsPlotsOpen] = useState(false);
+    const [isCanonicalOpen, setIsCanonicalOpen] = useState(false);
 
     const { closeModal } = useModal();
 
@@ -109,6 +115,20 @@ export const CorrespondenceContainer = ({
         }));
     };
 
+    // The canonical section stays null until covariates are chosen
+    const updateCanonicalData = (
+        field: keyof CorrespondenceCanonicalType,
+        value: unknown
+    ) => {
+        setFormData((prev) => ({
+            ...prev,
+            canonical: {
+                ...(prev.canonical ?? CorrespondenceCanonicalDefault),
+                [field]: value,
+            },
+        }));
+    };
+
     const executeCorrespondence = async (mainData: CorrespondenceMainType) => {
         try {
             const newFormData = {
@@ -158,6 +178,7 @@ export const CorrespondenceContainer = ({
                     setIsModelOpen={setIsModelOpen}
                     setIsStatisticsOpen={setIsStatisticsOpen}
                     setIsPlotsOpen={setIsPlotsOpen}
+                    setIsCanonicalOpen={setIsCanonicalOpen}
                     updateFormData={(field, value) =>
                         updateFormData("main", field, value)
                     }
@@ -216,6 +237,15 @@ export const CorrespondenceContainer = ({
                     }
                     data={formData.plots}
                 />
+
+                {/* Canonical */}
+                <CorrespondenceCanonical
+                    isCanonicalOpen={isCanonicalOpen}
+                    setIsCanonicalOpen={setIsCanonicalOpen}
+                    updateFormData={updateCanonicalData}
+                    data={formData.canonical ?? CorrespondenceCanonicalDefault}
+                    globalVariables={tempVariables}
+                />
             </DialogContent>
         </Dialog>
     );
@@ -10,8 +10,17 @@ import {
 import {Label} from "@/components/ui/label";
 import {Badge} from "@/components/ui/badge";
 import {ScrollArea} from "@/components/ui/scroll-area";
+import {Checkbox} from "@/components/ui/checkbox";
+import {CheckedState} from "@radix-ui/react-checkbox";
 import {useModal} from "@/hooks/useModal";
 
+// Several row or column variables build a concatenated table; the single-variable
+// fields keep the first one so the define-range dialogs still have a variable
+const targetVariables = (
+    vars: string[] | null,
+    single: string | null
+): string[] => (vars?.length ? vars : single ? [single] : []);
+
 export const CorrespondenceDialog = ({
     isMainOpen,
     setIsMainOpen,
@@ -20,6 +29,7 @@ export const CorrespondenceDialog = ({
     setIsModelOpen,
     setIsStatisticsOpen,
     setIsPlotsOpen,
+    setIsCanonicalOpen,
     updateFormData,
     data,
     globalVariables,
@@ -33,14 +43,23 @@ export const CorrespondenceDialog = ({
 
     const { closeModal } = useModal();
 
+    const rowVariables = targetVariables(
+        mainState.RowTargetVars,
+        mainState.RowTargetVar
+    );
+    const colVariables = targetVariables(
+        mainState.ColTargetVars,
+        mainState.ColTargetVar
+    );
+
     useEffect(() => {
         setMainState({ ...data });
     }, [data]);
 
     useEffect(() => {
         const usedVariables = [
-            mainState.RowTargetVar,
-            mainState.ColTargetVar,
+            ...targetVariables(mainState.RowTargetVars, mainState.RowTargetVar),
+            ...targetVariables(mainState.ColTargetVars, mainState.ColTargetVar),
         ].filter(Boolean);
 
         const updatedVariables = globalVariables.filter(
@@ -51,7 +70,7 @@ export const CorrespondenceDialog = ({
 
     const handleChange = (
         field: keyof CorrespondenceMainType,
-        value: number | string | null
+        value: CheckedState | number | string | null
     ) => {
         setMainState((prevState) => ({
             ...prevState,
@@ -63,9 +82,19 @@ export const CorrespondenceDialog = ({
         setMainState((prev) => {
             const updatedState = { ...prev };
             if (target === "RowTargetVar") {
-                updatedState.RowTargetVar = variable;
+                const rowVars = [
+                    ...targetVariables(prev.RowTargetVars, prev.RowTargetVar),
+                    variable,
+                ];
+                updatedState.RowTargetVars = rowVars;
+                updatedState.RowTargetVar = rowVars[0];
             } else if (target === "ColTargetVar") {
-                updatedState.ColTargetVar = variable;
+                const colVars = [
+                    ...targetVariables(prev.ColTargetVars, prev.ColTargetVar),
+                    variable,
+                ];
+                updatedState.ColTargetVars = colVars;
+                updatedState.ColTargetVar = colVars[0];
             }
             return updatedState;
         });
@@ -75,9 +104,19 @@ export const CorrespondenceDialog = ({
         setMainState((prev) => {
             const updatedState = { ...prev };
             if (target === "RowTargetVar") {
-                updatedState.RowTargetVar = "";
+                const rowVars = targetVariables(
+                    prev.RowTargetVars,
+                    prev.RowTargetVar
+                ).filter((v) => v !== variable);
+                updatedState.RowTargetVars = rowVars;
+                updatedState.RowTargetVar = rowVars[0] ?? "";
             } else if (target === "ColTargetVar") {
-                updatedState.ColTargetVar = "";
+                const colVars = targetVariables(
+                    prev.ColTargetVars,
+                    prev.ColTargetVar
+                ).filter((v) => v !== variable);
+                updatedState.ColTargetVars = colVars;
+                updatedState.ColTargetVar = colVars[0] ?? "";
             }
             return updatedState;
         });
@@ -176,20 +215,30 @@ export const CorrespondenceDialog = ({
                                                         e.preventDefault()
                                                     }
                                                 >
-                                                    {mainState.RowTargetVar ? (
-                                                        <Badge
-                                                            className="text-start text-sm font-light p-2 cursor-pointer"
-                                                            variant="outline"
-                                                            onClick={() =>
-                                                                handleRemoveVariable(
-                                                                    "RowTargetVar"
+                                                    {rowVariables.length > 0 ? (
+                                                        <div className="flex flex-wrap gap-1">
+                                                            {rowVariables.map(
+                                                                (variable) => (
+                                                                    <Badge
+                                                                        key={
+                                                                            variable
+                                                                        }
+                                                                        className="text-start text-sm font-light p-2 cursor-pointer"
+                                                                        variant="outline"
+                                                                        onClick={() =>
+                                                                            handleRemoveVariable(
+                                                                                "RowTargetVar",
+                                                                                variable
+                                                                            )
+                                                                        }
+                                                                    >
+                                                                        {
+                                                                            variable
+                                                                        }
+                                                                    </Badge>
                                                                 )
-                                                            }
-                                                        >
-                                                            {
-                                                                mainState.RowTargetVar
-                                                            }
-                                                        </Badge>
+                                                            )}
+                                                        </div>
                                                     ) : (
                                                         <span className="text-sm font-light text-gray-500">
                                                             Drop variables here.
@@ -243,20 +292,30 @@ export const CorrespondenceDialog = ({
                                                         e.preventDefault()
                                                     }
                                                 >
-                                                    {mainState.ColTargetVar ? (
-                                                        <Badge
-                                                            className="text-start text-sm font-light p-2 cursor-pointer"
-                                                            variant="outline"
-                                                            onClick={() =>
-                                                                handleRemoveVariable(
-                                                                    "ColTargetVar"
+                                                    {colVariables.length > 0 ? (
+                                                        <div className="flex flex-wrap gap-1">
+                                                            {colVariables.map(
+                                                                (variable) => (
+                                                                    <Badge
+                                                                        key={
+                                                                            variable
+                                                                        }
+                                                                        className="text-start text-sm font-light p-2 cursor-pointer"
+                                                                        variant="outline"
+                                                                        onClick={() =>
+                                                                            handleRemoveVariable(
+                                                                                "ColTargetVar",
+                                                                                variable
+                                                                            )
+                                                                        }
+                                                                    >
+                                                                        {
+                                                                            variable
+                                                                        }
+                                                                    </Badge>
                                                                 )
-                                                            }
-                                                        >
-                                                            {
-                                                                mainState.ColTargetVar
-                                                            }
-                                                        </Badge>
+                                                            )}
+                                                        </div>
                                                     ) : (
                                                         <span className="text-sm font-light text-gray-500">
                                                             Drop variables here.
@@ -290,6 +349,22 @@ export const CorrespondenceDialog = ({
                                             </Button>
                                         </div>
                                     </div>
+                                    <div className="flex items-center space-x-2">
+                                        <Checkbox
+                                            id="Burt"
+                                            checked={mainState.Burt}
+                                            onCheckedChange={(checked) =>
+                                                handleChange("Burt", checked)
+                                            }
+                                        />
+                                        <label
+                                            htmlFor="Burt"
+                                            className="text-sm font-medium leading-none peer-disabled:cursor-not-allowed peer-disabled:opacity-70"
+                                        >
+                                            Burt matrix of all row and column
+                                            variables
+                                        </label>
+                                    </div>
                                 </div>
                             </ResizablePanel>
 
@@ -322,6 +397,16 @@ export const CorrespondenceDialog = ({
                                     >
                                         Plots...
                                     </Button>
+                                    <Button
+                                        className="w-full"
+                                        type="button"
+                                        variant="secondary"
+                                        onClick={openDialog(
+                                            setIsCanonicalOpen
+                                        )}
+                                    >
+                                        Canonical...
+                                    </Button>
                                 </div>
                             </ResizablePanel>
                         </ResizablePanelGroup>
@@ -137,6 +137,85 @@ export const CorrespondenceStatistics = ({
                                 />
                             </div>
                         </div>
+                        <div className="flex items-center space-x-2">
+                            <Checkbox
+                                id="Bootstrap"
+                                checked={statisticsState.Bootstrap}
+                                onCheckedChange={(checked) =>
+                                    handleChange("Bootstrap", checked)
+                                }
+                            />
+                            <label
+                                htmlFor="Bootstrap"
+                                className="text-sm font-medium leading-none peer-disabled:cursor-not-allowed peer-disabled:opacity-70"
+                            >
+                                Bootstrap Confidence Regions
+                            </label>
+                        </div>
+                        <div className="flex items-center space-x-2 pl-6">
+                            <Label className="w-[200px]">Samples:</Label>
+                            <div className="w-[75px]">
+                                <Input
+                                    id="BootstrapSamples"
+                                    type="number"
+                                    placeholder=""
+                                    value={statisticsState.BootstrapSamples ?? ""}
+                                    disabled={!statisticsState.Bootstrap}
+                                    onChange={(e) =>
+                                        handleChange(
+                                            "BootstrapSamples",
+                                            Number(e.target.value)
+                                        )
+                                    }
+                                />
+                            </div>
+                        </div>
+                        <div className="flex items-center space-x-2 pl-6">
+                            <Label className="w-[200px]">
+                                Confidence Level:
+                            </Label>
+                            <div className="w-[75px]">
+                                <Input
+                                    id="ConfidenceLevel"
+                                    type="number"
+                                    step="0.01"
+                                    placeholder=""
+                                    value={statisticsState.ConfidenceLevel ?? ""}
+                                    disabled={!statisticsState.Bootstrap}
+                                    onChange={(e) =>
+                                        handleChange(
+                                            "ConfidenceLevel",
+                                            Number(e.target.value)
+                                        )
+                                    }
+                                />
+                            </div>
+                        </div>
+                        <div className="flex items-center space-x-2">
+                            <Label className="w-[224px]">
+                                Random Seed (optional):
+                            </Label>
+                            <div className="w-[75px]">
+                                <Input
+                                    id="Seed"
+                                    type="number"
+                                    placeholder=""
+                                    value={statisticsState.Seed ?? ""}
+                                    disabled={
+                                        !statisticsState.PermutationTest &&
+                                        !statisticsState.Bootstrap
+                                    }
+                                    onChange={(e) =>
+                                        handleChange(
+                                            "Seed",
+                                            e.target.value === ""
+                                                ? null
+                                                : Number(e.target.value)
+                                        )
+                                    }
+                                />
+                            </div>
+                        </div>
                         <div className="flex items-center space-x-2">
                             <Checkbox
                                 id="RowProfile"
@@ -1,6 +1,6 @@
 use serde::{ Deserialize, Serialize };
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct CorrespondenceAnalysisConfig {
     pub main: MainConfig,
     #[serde(rename = "defineRangeRow")]
@@ -10,14 +10,21 @@ pub struct CorrespondenceAnalysisConfig {
     pub model: ModelConfig,
     pub statistics: StatisticsConfig,
     pub plots: PlotsConfig,
+    pub canonical: Option<CanonicalConfig>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct MainConfig {
     #[serde(rename = "RowTargetVar")]
     pub row_target_var: Option<String>,
     #[serde(rename = "ColTargetVar")]
     pub col_target_var: Option<String>,
+    #[serde(rename = "RowTargetVars")]
+    pub row_target_vars: Option<Vec<String>>,
+    #[serde(rename = "ColTargetVars")]
+    pub col_target_vars: Option<Vec<String>>,
+    #[serde(rename = "Burt", default)]
+    pub burt: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -38,6 +45,20 @@ pub struct DefineRangeConfig {
     pub default_list_model: String,
 }
 
+impl Default for DefineRangeConfig {
+    fn default() -> Self {
+        DefineRangeConfig {
+            min_value: None,
+            max_value: None,
+            constraints_list: None,
+            none: true,
+            category_equal: false,
+            category_supplemental: false,
+            default_list_model: String::new(),
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ModelConfig {
     #[serde(rename = "ChiSquare")]
@@ -72,6 +93,28 @@ pub struct ModelConfig {
     pub custom_q: Option<f64>,
 }
 
+impl Default for ModelConfig {
+    fn default() -> Self {
+        ModelConfig {
+            chi_square: true,
+            euclidean: false,
+            rnc_removed: true,
+            row_removed: false,
+            col_removed: false,
+            row_totals: false,
+            col_totals: false,
+            symmetrical: true,
+            row_principal: false,
+            custom: false,
+            principal: false,
+            col_principal: false,
+            dimensions: 2,
+            custom_dimensions: 0,
+            custom_q: None,
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct StatisticsConfig {
     #[serde(rename = "CorrTable")]
@@ -92,6 +135,34 @@ pub struct StatisticsConfig {
     pub row_points: bool,
     #[serde(rename = "ColPoints")]
     pub col_points: bool,
+    #[serde(rename = "Seed")]
+    pub seed: Option<i64>,
+    #[serde(rename = "Bootstrap", default)]
+    pub bootstrap: bool,
+    #[serde(rename = "BootstrapSamples")]
+    pub bootstrap_samples: Option<i32>,
+    #[serde(rename = "ConfidenceLevel")]
+    pub confidence_level: Option<f64>,
+}
+
+impl Default for StatisticsConfig {
+    fn default() -> Self {
+        StatisticsConfig {
+            corr_table: true,
+            stat_row_points: true,
+            stat_col_points: true,
+            permutation_test: false,
+            max_permutations: 1,
+            row_profile: false,
+            col_profile: false,
+            row_points: false,
+            col_points: false,
+            seed: None,
+            bootstrap: false,
+            bootstrap_samples: Some(1000),
+            confidence_level: Some(0.95),
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -119,3 +190,44 @@ pub struct PlotsConfig {
     #[serde(rename = "Highest")]
     pub highest: Option<i32>,
 }
+
+impl Default for PlotsConfig {
+    fn default() -> Self {
+        PlotsConfig {
+            biplot: true,
+            row_pts: false,
+            col_pts: false,
+            id_scatter: 20,
+            trans_row: false,
+            trans_col: false,
+            id_line: 20,
+            display_all: true,
+            restrict_dim: false,
+            lowest: None,
+            highest: None,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CanonicalConfig {
+    #[serde(rename = "Covariates")]
+    pub covariates: Vec<String>,
+    #[serde(rename = "Dimensions")]
+    pub dimensions: Option<i32>,
+    #[serde(rename = "PermutationTest")]
+    pub permutation_test: bool,
+    #[serde(rename = "MaxPermutations")]
+    pub max_permutations: Option<i32>,
+}
+
+impl Default for CanonicalConfig {
+    fn default() -> Self {
+        CanonicalConfig {
+            covariates: Vec::new(),
+            dimensions: None,
+            permutation_test: false,
+            max_permutations: Some(999),
+        }
+    }
+}
@@ -10,6 +10,11 @@ pub struct CorrespondenceAnalysisResult {
     pub column_points: Option<PointsAnalysis>,
     pub confidence_row_points: Option<ConfidencePoints>,
     pub confidence_column_points: Option<ConfidencePoints>,
+    pub dimension_tests: Option<Vec<DimensionTest>>,
+    pub permutation_test: Option<PermutationTest>,
+    pub bootstrap_row_regions: Option<BootstrapRegions>,
+    pub bootstrap_column_regions: Option<BootstrapRegions>,
+    pub canonical_analysis: Option<CanonicalAnalysis>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -17,6 +22,8 @@ pub struct CorrespondenceTable {
     pub data: Vec<Vec<f64>>,
     pub active_margin: Vec<f64>,
     pub active_margin_col: Vec<f64>,
+    pub row_labels: Vec<String>,
+    pub col_labels: Vec<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -38,6 +45,7 @@ pub struct AnalysisSummary {
     pub chi_square: Vec<f64>,
     pub significance: Vec<f64>,
     pub proportion_of_inertia: ProportionOfInertia,
+    pub adjusted_inertia: Option<AdjustedInertia>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -46,6 +54,13 @@ pub struct ProportionOfInertia {
     pub cumulative: Vec<f64>,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AdjustedInertia {
+    pub total: f64,
+    pub inertia: Vec<f64>,
+    pub proportion_of_inertia: ProportionOfInertia,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct PointsAnalysis {
     pub mass: Vec<f64>,
@@ -65,3 +80,74 @@ pub struct ConfidencePoints {
     pub standard_deviation: Vec<Vec<f64>>,
     pub correlation: Vec<f64>,
 }
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DimensionTest {
+    pub dimension: usize,
+    pub chi_square: f64,
+    pub df: usize,
+    pub significance: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PermutationTest {
+    pub num_permutations: usize,
+    pub observed_inertia: Vec<f64>,
+    pub critical_values: Vec<f64>,
+    pub p_values: Vec<f64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BootstrapRegions {
+    pub num_samples: usize,
+    pub confidence_level: f64,
+    pub dimension1: usize,
+    pub dimension2: usize,
+    pub points: Vec<PointRegion>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PointRegion {
+    pub label: String,
+    pub x: f64,
+    pub y: f64,
+    pub ellipse: ConfidenceEllipse,
+    pub convex_hull: Vec<(f64, f64)>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ConfidenceEllipse {
+    pub center_x: f64,
+    pub center_y: f64,
+    pub semi_major_axis: f64,
+    pub semi_minor_axis: f64,
+    pub angle: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CanonicalAnalysis {
+    pub total_inertia: f64,
+    pub constrained_inertia: f64,
+    pub unconstrained_inertia: f64,
+    pub eigenvalues: Vec<f64>,
+    pub unconstrained_eigenvalues: Vec<f64>,
+    pub proportion_of_inertia: ProportionOfInertia,
+    pub species_environment_correlations: Vec<f64>,
+    pub row_labels: Vec<String>,
+    pub col_labels: Vec<String>,
+    pub row_scores: Vec<Vec<f64>>,
+    pub row_weighted_average_scores: Vec<Vec<f64>>,
+    pub column_scores: Vec<Vec<f64>>,
+    pub covariate_labels: Vec<String>,
+    pub covariate_biplot_scores: Vec<Vec<f64>>,
+    pub permutation_test: Option<ConstraintTest>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ConstraintTest {
+    pub num_permutations: usize,
+    pub pseudo_f: f64,
+    pub significance: f64,
+    pub first_axis_pseudo_f: f64,
+    pub first_axis_significance: f64,
+}
@@ -1,11 +1,17 @@
 use crate::models::{
     config::CorrespondenceAnalysisConfig,
     data::AnalysisData,
-    result::{ AnalysisSummary, ProportionOfInertia },
+    result::{ AnalysisSummary, DimensionTest, ProportionOfInertia },
 };
 
 use super::{
-    core::{ apply_normalization, calculate_chi_square_significance, create_correspondence_table },
+    core::{
+        apply_normalization,
+        calculate_adjusted_inertia,
+        calculate_chi_square_significance,
+        create_correspondence_table,
+        get_burt_variables,
+    },
 };
 
 pub fn calculate_analysis_summary(
@@ -54,6 +60,19 @@ pub fn calculate_analysis_summary(
     let df = (correspondence_table.data.len() - 1) * (correspondence_table.data[0].len() - 1);
     let significance = calculate_chi_square_significance(&chi_square, df)?;
 
+    // Greenacre's adjusted inertias for a Burt matrix
+    let adjusted_inertia = if config.main.burt {
+        Some(
+            calculate_adjusted_inertia(
+                &singular_values,
+                get_burt_variables(config).len(),
+                correspondence_table.data[0].len()
+            )?
+        )
+    } else {
+        None
+    };
+
     Ok(AnalysisSummary {
         singular_values,
         inertia,
@@ -63,5 +82,54 @@ pub fn calculate_analysis_summary(
             accounted_for,
             cumulative,
         },
+        adjusted_inertia,
     })
 }
+
+// Malinvaud's test: chi-square of the residual inertia after retaining the first k dimensions
+pub fn calculate_dimension_tests(
+    data: &AnalysisData,
+    config: &CorrespondenceAnalysisConfig
+) -> Result<Vec<DimensionTest>, String> {
+    let (singular_values, _, _) = apply_normalization(data, config)?;
+    let correspondence_table = create_correspondence_table(data, config)?;
+
+    // Only categories with positive mass count towards the degrees of freedom
+    let rows = correspondence_table.active_margin
+        .iter()
+        .filter(|&&m| m > 0.0)
+        .count();
+    let cols = correspondence_table.active_margin_col
+        .iter()
+        .filter(|&&m| m > 0.0)
+        .count();
+    let grand_total: f64 = correspondence_table.active_margin.iter().sum();
+
+    if rows < 2 || cols < 2 {
+        return Err("At least two active rows and columns are required".to_string());
+    }
+
+    let max_dims = (rows - 1).min(cols - 1).min(singular_values.len());
+    let mut tests = Vec::with_capacity(max_dims);
+
+    for k in 0..max_dims {
+        // Q_k = N * sum of the principal inertias beyond the first k dimensions
+        let residual_inertia: f64 = singular_values[k..]
+            .iter()
+            .map(|&sv| sv * sv)
+            .sum();
+        let chi_square = grand_total * residual_inertia;
+        let df = (rows - 1 - k) * (cols - 1 - k);
+
+        let significance = calculate_chi_square_significance(&[chi_square], df)?[0];
+
+        tests.push(DimensionTest {
+            dimension: k + 1,
+            chi_square,
+            df,
+            significance,
+        });
+    }
+
+    Ok(tests)
+}
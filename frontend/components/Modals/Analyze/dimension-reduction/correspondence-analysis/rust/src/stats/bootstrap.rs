@@ -0,0 +1,277 @@
+use crate::models::{
+    config::CorrespondenceAnalysisConfig,
+    data::AnalysisData,
+    result::{ BootstrapRegions, ConfidenceEllipse, PointRegion },
+};
+
+use super::core::{
+    apply_normalization,
+    create_correspondence_table,
+    create_rng,
+    get_normalization_parameters,
+    sample_multinomial,
+};
+
+// Bootstrap confidence regions for row and column points (partial bootstrap).
+// Each replicate is a multinomial resample of the contingency table whose row and
+// column profiles are projected onto the original axes as supplementary points.
+pub fn calculate_bootstrap_regions(
+    data: &AnalysisData,
+    config: &CorrespondenceAnalysisConfig
+) -> Result<(BootstrapRegions, BootstrapRegions), String> {
+    if !config.model.chi_square {
+        return Err("Bootstrap regions require the chi-square distance measure".to_string());
+    }
+
+    let num_samples = config.statistics.bootstrap_samples.unwrap_or(1000);
+    if num_samples <= 0 {
+        return Err("Number of bootstrap samples must be positive".to_string());
+    }
+    let num_samples = num_samples as usize;
+
+    let confidence_level = config.statistics.confidence_level.unwrap_or(0.95);
+    if confidence_level <= 0.0 || confidence_level >= 1.0 {
+        return Err("Confidence level must be between 0 and 1".to_string());
+    }
+
+    let correspondence_table = create_correspondence_table(data, config)?;
+    let (singular_values, row_scores, col_scores) = apply_normalization(data, config)?;
+    let (alpha, beta) = get_normalization_parameters(config);
+
+    let rows = correspondence_table.data.len();
+    let cols = if rows > 0 { correspondence_table.data[0].len() } else { 0 };
+    let dimensions = if rows > 0 { row_scores[0].len() } else { 0 };
+    if dimensions < 2 {
+        return Err("Bootstrap regions require at least two dimensions".to_string());
+    }
+
+    let (dim1, dim2) = (0, 1);
+    if singular_values[dim1] <= 0.0 || singular_values[dim2] <= 0.0 {
+        return Err("Singular values of the plotted dimensions must be positive".to_string());
+    }
+
+    // Standard coordinates (scores stripped of their lambda^alpha / lambda^beta scaling)
+    let row_standard: Vec<[f64; 2]> = row_scores
+        .iter()
+        .map(|s| {
+            [
+                s[dim1] / singular_values[dim1].powf(alpha),
+                s[dim2] / singular_values[dim2].powf(alpha),
+            ]
+        })
+        .collect();
+    let col_standard: Vec<[f64; 2]> = col_scores
+        .iter()
+        .map(|s| {
+            [
+                s[dim1] / singular_values[dim1].powf(beta),
+                s[dim2] / singular_values[dim2].powf(beta),
+            ]
+        })
+        .collect();
+
+    // Cell weights for multinomial resampling of the grand total
+    let cells: Vec<f64> = correspondence_table.data
+        .iter()
+        .flatten()
+        .map(|&val| val.max(0.0))
+        .collect();
+    let grand_total: f64 = cells.iter().sum();
+    if grand_total <= 0.0 {
+        return Err("Grand total is zero or negative".to_string());
+    }
+    let draws = grand_total.round().max(1.0) as u64;
+
+    let mut rng = create_rng(config.statistics.seed);
+    let mut row_clouds: Vec<Vec<(f64, f64)>> = vec![Vec::with_capacity(num_samples); rows];
+    let mut col_clouds: Vec<Vec<(f64, f64)>> = vec![Vec::with_capacity(num_samples); cols];
+
+    for _ in 0..num_samples {
+        let counts = sample_multinomial(&cells, draws, &mut rng);
+        let resampled: Vec<Vec<f64>> = counts
+            .chunks(cols)
+            .map(|row| row.iter().map(|&count| count as f64).collect())
+            .collect();
+
+        // Row points: principal coordinates from the transition formula, rescaled to the
+        // row normalization (lambda^(alpha - 1))
+        for i in 0..rows {
+            let row_sum: f64 = resampled[i].iter().sum();
+            if row_sum <= 0.0 {
+                continue;
+            }
+            let mut point = [0.0; 2];
+            for j in 0..cols {
+                let profile = resampled[i][j] / row_sum;
+                point[0] += profile * col_standard[j][0];
+                point[1] += profile * col_standard[j][1];
+            }
+            row_clouds[i].push((
+                point[0] * singular_values[dim1].powf(alpha - 1.0),
+                point[1] * singular_values[dim2].powf(alpha - 1.0),
+            ));
+        }
+
+        // Column points, symmetrically with lambda^(beta - 1)
+        for j in 0..cols {
+            let col_sum: f64 = resampled
+                .iter()
+                .map(|row| row[j])
+                .sum();
+            if col_sum <= 0.0 {
+                continue;
+            }
+            let mut point = [0.0; 2];
+            for i in 0..rows {
+                let profile = resampled[i][j] / col_sum;
+                point[0] += profile * row_standard[i][0];
+                point[1] += profile * row_standard[i][1];
+            }
+            col_clouds[j].push((
+                point[0] * singular_values[dim1].powf(beta - 1.0),
+                point[1] * singular_values[dim2].powf(beta - 1.0),
+            ));
+        }
+    }
+
+    let build_regions = |scores: &Vec<Vec<f64>>, clouds: &[Vec<(f64, f64)>], labels: &[String]| {
+        let points = scores
+            .iter()
+            .zip(clouds.iter())
+            .enumerate()
+            .map(|(idx, (score, cloud))| PointRegion {
+                label: labels
+                    .get(idx)
+                    .cloned()
+                    .unwrap_or_else(|| format!("{}", idx + 1)),
+                x: score[dim1],
+                y: score[dim2],
+                ellipse: calculate_confidence_ellipse(cloud, confidence_level),
+                convex_hull: calculate_peeled_hull(cloud, confidence_level),
+            })
+            .collect();
+
+        BootstrapRegions {
+            num_samples,
+            confidence_level,
+            dimension1: dim1 + 1,
+            dimension2: dim2 + 1,
+            points,
+        }
+    };
+
+    Ok((
+        build_regions(&row_scores, &row_clouds, &correspondence_table.row_labels),
+        build_regions(&col_scores, &col_clouds, &correspondence_table.col_labels),
+    ))
+}
+
+// Confidence ellipse from the covariance of a bootstrap cloud
+fn calculate_confidence_ellipse(cloud: &[(f64, f64)], confidence_level: f64) -> ConfidenceEllipse {
+    let n = cloud.len() as f64;
+    if cloud.len() < 2 {
+        let (x, y) = cloud.first().copied().unwrap_or((f64::NAN, f64::NAN));
+        return ConfidenceEllipse {
+            center_x: x,
+            center_y: y,
+            semi_major_axis: 0.0,
+            semi_minor_axis: 0.0,
+            angle: 0.0,
+        };
+    }
+
+    let mean_x = cloud.iter().map(|p| p.0).sum::<f64>() / n;
+    let mean_y = cloud.iter().map(|p| p.1).sum::<f64>() / n;
+
+    let mut var_x = 0.0;
+    let mut var_y = 0.0;
+    let mut cov_xy = 0.0;
+    for &(x, y) in cloud {
+        var_x += (x - mean_x) * (x - mean_x);
+        var_y += (y - mean_y) * (y - mean_y);
+        cov_xy += (x - mean_x) * (y - mean_y);
+    }
+    var_x /= n - 1.0;
+    var_y /= n - 1.0;
+    cov_xy /= n - 1.0;
+
+    // Eigenvalues of the 2x2 covariance matrix
+    let half_trace = (var_x + var_y) / 2.0;
+    let discriminant = (((var_x - var_y) / 2.0).powi(2) + cov_xy * cov_xy).sqrt();
+    let lambda1 = (half_trace + discriminant).max(0.0);
+    let lambda2 = (half_trace - discriminant).max(0.0);
+
+    // Chi-square quantile with 2 df has the closed form -2 ln(1 - level)
+    let scale = -2.0 * (1.0 - confidence_level).ln();
+
+    ConfidenceEllipse {
+        center_x: mean_x,
+        center_y: mean_y,
+        semi_major_axis: (scale * lambda1).sqrt(),
+        semi_minor_axis: (scale * lambda2).sqrt(),
+        angle: 0.5 * (2.0 * cov_xy).atan2(var_x - var_y),
+    }
+}
+
+// Convex hull of the bootstrap points closest to the cloud centre, keeping the
+// requested proportion of replicates
+fn calculate_peeled_hull(cloud: &[(f64, f64)], confidence_level: f64) -> Vec<(f64, f64)> {
+    if cloud.is_empty() {
+        return Vec::new();
+    }
+
+    let n = cloud.len() as f64;
+    let mean_x = cloud.iter().map(|p| p.0).sum::<f64>() / n;
+    let mean_y = cloud.iter().map(|p| p.1).sum::<f64>() / n;
+
+    let mut sorted: Vec<(f64, f64)> = cloud.to_vec();
+    sorted.sort_by(|a, b| {
+        let da = (a.0 - mean_x).powi(2) + (a.1 - mean_y).powi(2);
+        let db = (b.0 - mean_x).powi(2) + (b.1 - mean_y).powi(2);
+        da.partial_cmp(&db).unwrap_or(std::cmp::Ordering::Equal)
+    });
+    let keep = ((confidence_level * n).ceil() as usize).clamp(1, sorted.len());
+    sorted.truncate(keep);
+
+    convex_hull(sorted)
+}
+
+// Andrew's monotone chain; returns the hull in counter-clockwise order
+fn convex_hull(mut points: Vec<(f64, f64)>) -> Vec<(f64, f64)> {
+    points.sort_by(|a, b| {
+        a.0
+            .partial_cmp(&b.0)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then(a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal))
+    });
+    points.dedup();
+
+    if points.len() < 3 {
+        return points;
+    }
+
+    let cross = |o: (f64, f64), a: (f64, f64), b: (f64, f64)| {
+        (a.0 - o.0) * (b.1 - o.1) - (a.1 - o.1) * (b.0 - o.0)
+    };
+
+    let mut lower: Vec<(f64, f64)> = Vec::new();
+    for &p in &points {
+        while lower.len() >= 2 && cross(lower[lower.len() - 2], lower[lower.len() - 1], p) <= 0.0 {
+            lower.pop();
+        }
+        lower.push(p);
+    }
+
+    let mut upper: Vec<(f64, f64)> = Vec::new();
+    for &p in points.iter().rev() {
+        while upper.len() >= 2 && cross(upper[upper.len() - 2], upper[upper.len() - 1], p) <= 0.0 {
+            upper.pop();
+        }
+        upper.push(p);
+    }
+
+    lower.pop();
+    upper.pop();
+    lower.extend(upper);
+    lower
+}
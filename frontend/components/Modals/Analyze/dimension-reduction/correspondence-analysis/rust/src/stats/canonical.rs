@@ -0,0 +1,414 @@
+use nalgebra::{ DMatrix, SVD };
+
+use crate::models::{
+    config::{ CanonicalConfig, CorrespondenceAnalysisConfig },
+    data::{ AnalysisData, DataRecord, DataValue },
+    result::{ CanonicalAnalysis, ConstraintTest, ProportionOfInertia },
+};
+
+use super::core::{
+    calculate_chi_square_distances,
+    calculate_column_profiles,
+    calculate_row_profiles,
+    create_correspondence_table,
+    create_rng,
+    get_normalization_parameters,
+    is_multiple_table,
+    shuffle_values,
+};
+
+// Canonical correspondence analysis: the chi-square residual matrix of the table is
+// projected onto the space spanned by row-level covariates before the SVD
+pub fn perform_canonical_analysis(
+    data: &AnalysisData,
+    config: &CorrespondenceAnalysisConfig
+) -> Result<CanonicalAnalysis, String> {
+    let canonical = config.canonical.as_ref().ok_or("Canonical analysis not configured")?;
+
+    if canonical.covariates.is_empty() {
+        return Err("At least one covariate is required for canonical analysis".to_string());
+    }
+    if is_multiple_table(config) {
+        return Err("Canonical analysis requires a single row and column variable".to_string());
+    }
+    if !config.model.chi_square {
+        return Err("Canonical analysis requires the chi-square distance measure".to_string());
+    }
+
+    let correspondence_table = create_correspondence_table(data, config)?;
+    let residuals = calculate_chi_square_distances(data, config)?;
+    let row_mass = calculate_row_profiles(data, config)?.mass;
+    let col_mass = calculate_column_profiles(data, config)?.mass;
+
+    let rows = residuals.len();
+    let cols = if rows > 0 { residuals[0].len() } else { 0 };
+    if rows < 2 || cols < 2 {
+        return Err("At least two rows and columns are required".to_string());
+    }
+
+    let covariates = aggregate_row_covariates(
+        data,
+        config,
+        canonical,
+        &correspondence_table.row_labels
+    )?;
+
+    let q_matrix = DMatrix::from_fn(rows, cols, |i, j| residuals[i][j]);
+    let total_inertia = q_matrix.norm_squared();
+    if total_inertia <= 0.0 {
+        return Err("Total inertia is zero or negative".to_string());
+    }
+
+    let fit = fit_constraints(&q_matrix, &covariates, &row_mass)?;
+    let rank = fit.rank;
+    if rank == 0 {
+        return Err("Covariates have no variance across rows".to_string());
+    }
+
+    let svd = SVD::new(fit.fitted.clone(), true, true);
+    let u = svd.u.ok_or("SVD failed to compute left singular vectors")?;
+    let v_t = svd.v_t.ok_or("SVD failed to compute right singular vectors")?;
+    let v = v_t.transpose();
+
+    // Number of constrained axes is bounded by the covariate rank and the table size
+    let max_dims = rank.min(rows - 1).min(cols - 1).min(svd.singular_values.len());
+    let requested = canonical.dimensions.unwrap_or(config.model.dimensions).max(1) as usize;
+    let dimensions = requested.min(max_dims);
+
+    let eigenvalues: Vec<f64> = svd.singular_values
+        .iter()
+        .take(max_dims)
+        .map(|&sv| sv * sv)
+        .collect();
+    let constrained_inertia: f64 = eigenvalues.iter().sum();
+    let unconstrained_inertia = (total_inertia - constrained_inertia).max(0.0);
+
+    let residual_svd = SVD::new(&q_matrix - &fit.fitted, false, false);
+    let unconstrained_eigenvalues: Vec<f64> = residual_svd.singular_values
+        .iter()
+        .take((rows - 1).min(cols - 1))
+        .map(|&sv| sv * sv)
+        .filter(|&ev| ev > 1e-12)
+        .collect();
+
+    let accounted_for: Vec<f64> = eigenvalues
+        .iter()
+        .map(|&ev| ev / total_inertia)
+        .collect();
+    let mut cumulative: Vec<f64> = Vec::with_capacity(accounted_for.len());
+    let mut cumul_sum = 0.0;
+    for &prop in &accounted_for {
+        cumul_sum += prop;
+        cumulative.push(cumul_sum);
+    }
+
+    // Scores follow the normalization selected for ordinary correspondence analysis
+    let (alpha, beta) = get_normalization_parameters(config);
+    let singular_values: Vec<f64> = svd.singular_values.iter().take(dimensions).cloned().collect();
+
+    let mut row_scores = vec![vec![0.0; dimensions]; rows];
+    let mut row_weighted_average_scores = vec![vec![0.0; dimensions]; rows];
+    let mut column_scores = vec![vec![0.0; dimensions]; cols];
+    let mut species_environment_correlations = vec![0.0; dimensions];
+
+    for s in 0..dimensions {
+        let sv = singular_values[s];
+        if sv <= 0.0 {
+            continue;
+        }
+
+        // Weighted-average scores: Q V / lambda, the unconstrained counterpart of the LC scores
+        let wa_standard = (&q_matrix * v.column(s)) / sv;
+
+        for i in 0..rows {
+            if row_mass[i] > 0.0 {
+                // Linear-combination scores: u_is / sqrt(r_i) * lambda_s^alpha
+                row_scores[i][s] = (u[(i, s)] / row_mass[i].sqrt()) * sv.powf(alpha);
+                row_weighted_average_scores[i][s] =
+                    (wa_standard[i] / row_mass[i].sqrt()) * sv.powf(alpha);
+            }
+        }
+
+        for j in 0..cols {
+            if col_mass[j] > 0.0 {
+                column_scores[j][s] = (v[(j, s)] / col_mass[j].sqrt()) * sv.powf(beta);
+            }
+        }
+
+        // Species-environment correlation: weighted correlation of LC and WA scores
+        let lc: Vec<f64> = (0..rows).map(|i| u[(i, s)]).collect();
+        let wa: Vec<f64> = (0..rows).map(|i| wa_standard[i]).collect();
+        let cross: f64 = lc
+            .iter()
+            .zip(wa.iter())
+            .map(|(a, b)| a * b)
+            .sum();
+        let norm_lc: f64 = lc
+            .iter()
+            .map(|a| a * a)
+            .sum::<f64>()
+            .sqrt();
+        let norm_wa: f64 = wa
+            .iter()
+            .map(|b| b * b)
+            .sum::<f64>()
+            .sqrt();
+        if norm_lc > 0.0 && norm_wa > 0.0 {
+            species_environment_correlations[s] = cross / (norm_lc * norm_wa);
+        }
+    }
+
+    // Biplot scores: weighted correlations between covariates and the LC row scores
+    let covariate_biplot_scores: Vec<Vec<f64>> = (0..covariates[0].len())
+        .map(|p| {
+            (0..dimensions)
+                .map(|s| {
+                    (0..rows)
+                        .map(|i| fit.weighted_covariates[(i, p)] * u[(i, s)])
+                        .sum()
+                })
+                .collect()
+        })
+        .collect();
+
+    let permutation_test = if canonical.permutation_test {
+        Some(
+            perform_constraint_permutation_test(&q_matrix, &covariates, &row_mass, canonical, config)?
+        )
+    } else {
+        None
+    };
+
+    Ok(CanonicalAnalysis {
+        total_inertia,
+        constrained_inertia,
+        unconstrained_inertia,
+        eigenvalues,
+        unconstrained_eigenvalues,
+        proportion_of_inertia: ProportionOfInertia {
+            accounted_for,
+            cumulative,
+        },
+        species_environment_correlations,
+        row_labels: correspondence_table.row_labels,
+        col_labels: correspondence_table.col_labels,
+        row_scores,
+        row_weighted_average_scores,
+        column_scores,
+        covariate_labels: canonical.covariates.clone(),
+        covariate_biplot_scores,
+        permutation_test,
+    })
+}
+
+// Result of projecting the residual matrix onto the covariate space
+struct ConstraintFit {
+    fitted: DMatrix<f64>,
+    weighted_covariates: DMatrix<f64>,
+    rank: usize,
+}
+
+// Standardize covariates with row masses as weights and project Q onto their span
+fn fit_constraints(
+    q_matrix: &DMatrix<f64>,
+    covariates: &[Vec<f64>],
+    row_mass: &[f64]
+) -> Result<ConstraintFit, String> {
+    let rows = covariates.len();
+    let num_covariates = if rows > 0 { covariates[0].len() } else { 0 };
+
+    // X_w = D_r^(1/2) Z_c, with Z_c centred and scaled using the row masses
+    let mut weighted_covariates = DMatrix::zeros(rows, num_covariates);
+    for p in 0..num_covariates {
+        let mean: f64 = (0..rows).map(|i| row_mass[i] * covariates[i][p]).sum();
+        let variance: f64 = (0..rows).map(|i| row_mass[i] * (covariates[i][p] - mean).powi(2)).sum();
+        if variance <= 1e-12 {
+            continue;
+        }
+        let sd = variance.sqrt();
+        for i in 0..rows {
+            weighted_covariates[(i, p)] = row_mass[i].sqrt() * ((covariates[i][p] - mean) / sd);
+        }
+    }
+
+    let cross_product = weighted_covariates.transpose() * &weighted_covariates;
+    let rank = cross_product
+        .clone()
+        .symmetric_eigen()
+        .eigenvalues.iter()
+        .filter(|&&ev| ev > 1e-10)
+        .count();
+    let inverse = cross_product.pseudo_inverse(1e-10).map_err(|e| e.to_string())?;
+
+    // Fitted residuals H Q with H = X_w (X_w' X_w)^+ X_w'
+    let fitted = &weighted_covariates * (inverse * (weighted_covariates.transpose() * q_matrix));
+
+    Ok(ConstraintFit {
+        fitted,
+        weighted_covariates,
+        rank,
+    })
+}
+
+// Permutation test of the constraints: covariate rows are shuffled across table rows
+fn perform_constraint_permutation_test(
+    q_matrix: &DMatrix<f64>,
+    covariates: &[Vec<f64>],
+    row_mass: &[f64],
+    canonical: &CanonicalConfig,
+    config: &CorrespondenceAnalysisConfig
+) -> Result<ConstraintTest, String> {
+    let num_permutations = canonical.max_permutations.unwrap_or(999);
+    if num_permutations <= 0 {
+        return Err("Number of permutations must be positive".to_string());
+    }
+    let num_permutations = num_permutations as usize;
+
+    let total_inertia = q_matrix.norm_squared();
+    let observed = fit_constraints(q_matrix, covariates, row_mass)?;
+    let rank = observed.rank;
+    let constrained_inertia = observed.fitted.norm_squared();
+    let observed_first = first_eigenvalue(observed.fitted);
+
+    let rows = covariates.len();
+    if rows <= rank + 1 {
+        return Err("Too few rows for the number of covariates".to_string());
+    }
+
+    // Pseudo-F statistics (ter Braak): residual inertia per residual degree of freedom
+    let residual_df = (rows - rank - 1) as f64;
+    let residual = (total_inertia - constrained_inertia).max(1e-12);
+    let pseudo_f = constrained_inertia / (rank as f64) / (residual / residual_df);
+    let first_axis_pseudo_f = observed_first / (residual / residual_df);
+
+    let mut rng = create_rng(config.statistics.seed);
+    let mut order: Vec<usize> = (0..rows).collect();
+    let mut count_trace = 0;
+    let mut count_first = 0;
+
+    for _ in 0..num_permutations {
+        shuffle_values(&mut order, &mut rng);
+        let permuted: Vec<Vec<f64>> = order
+            .iter()
+            .map(|&i| covariates[i].clone())
+            .collect();
+
+        let fit = fit_constraints(q_matrix, &permuted, row_mass)?;
+        let perm_constrained = fit.fitted.norm_squared();
+        let perm_first = first_eigenvalue(fit.fitted);
+        let perm_residual = (total_inertia - perm_constrained).max(1e-12);
+
+        let perm_f = perm_constrained / (rank as f64) / (perm_residual / residual_df);
+        let perm_first_f = perm_first / (perm_residual / residual_df);
+
+        if perm_f >= pseudo_f {
+            count_trace += 1;
+        }
+        if perm_first_f >= first_axis_pseudo_f {
+            count_first += 1;
+        }
+    }
+
+    Ok(ConstraintTest {
+        num_permutations,
+        pseudo_f,
+        significance: ((count_trace + 1) as f64) / ((num_permutations + 1) as f64),
+        first_axis_pseudo_f,
+        first_axis_significance: ((count_first + 1) as f64) / ((num_permutations + 1) as f64),
+    })
+}
+
+fn first_eigenvalue(fitted: DMatrix<f64>) -> f64 {
+    SVD::new(fitted, false, false).singular_values
+        .iter()
+        .next()
+        .map(|&sv| sv * sv)
+        .unwrap_or(0.0)
+}
+
+// Average each covariate over the cases falling in every active row category
+fn aggregate_row_covariates(
+    data: &AnalysisData,
+    config: &CorrespondenceAnalysisConfig,
+    canonical: &CanonicalConfig,
+    row_categories: &[String]
+) -> Result<Vec<Vec<f64>>, String> {
+    let row_var = config.main.row_target_var.as_ref().ok_or("Row target variable not specified")?;
+    let num_covariates = canonical.covariates.len();
+
+    let mut sums = vec![vec![0.0; num_covariates]; row_categories.len()];
+    let mut weights = vec![0.0; row_categories.len()];
+
+    let flattened_rows: Vec<&DataRecord> = data.row_data.iter().flatten().collect();
+    let flattened_cols: Vec<&DataRecord> = data.col_data.iter().flatten().collect();
+    let flattened_weights: Vec<&DataRecord> = data.weight_data.iter().flatten().collect();
+
+    for (i, row_record) in flattened_rows.iter().enumerate() {
+        let row_value = match row_record.values.get(row_var) {
+            Some(DataValue::Text(s)) => s.clone(),
+            Some(DataValue::Number(n)) => n.to_string(),
+            _ => {
+                continue;
+            }
+        };
+        let row_idx = match row_categories.iter().position(|c| c == &row_value) {
+            Some(idx) => idx,
+            None => {
+                continue;
+            }
+        };
+
+        // Covariates may be sliced with either the row or the column variable
+        let values: Option<Vec<f64>> = canonical.covariates
+            .iter()
+            .map(|cov| {
+                [Some(*row_record), flattened_cols.get(i).copied()]
+                    .iter()
+                    .flatten()
+                    .find_map(|record| {
+                        match record.values.get(cov) {
+                            Some(DataValue::Number(n)) if n.is_finite() => Some(*n),
+                            _ => None,
+                        }
+                    })
+            })
+            .collect();
+
+        // Cases with a missing covariate are excluded listwise
+        let values = match values {
+            Some(v) => v,
+            None => {
+                continue;
+            }
+        };
+
+        let weight = if i < flattened_weights.len() {
+            flattened_weights[i].values
+                .values()
+                .find_map(|v| if let DataValue::Number(n) = v { Some(*n) } else { None })
+                .unwrap_or(1.0)
+        } else {
+            1.0
+        };
+
+        for (p, value) in values.iter().enumerate() {
+            sums[row_idx][p] += weight * value;
+        }
+        weights[row_idx] += weight;
+    }
+
+    let mut covariates = Vec::with_capacity(row_categories.len());
+    for (idx, category) in row_categories.iter().enumerate() {
+        if weights[idx] <= 0.0 {
+            return Err(format!("No covariate values available for row category {}", category));
+        }
+        covariates.push(
+            sums[idx]
+                .iter()
+                .map(|s| s / weights[idx])
+                .collect()
+        );
+    }
+
+    Ok(covariates)
+}
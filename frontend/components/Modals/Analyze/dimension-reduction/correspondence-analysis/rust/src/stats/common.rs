@@ -1,6 +1,7 @@
 use std::collections::{ HashMap, HashSet };
 
-use statrs::distribution::{ ChiSquared, ContinuousCDF };
+use rand_mt::Mt64;
+use statrs::{ distribution::{ ChiSquared, ContinuousCDF }, function::gamma::ln_gamma };
 
 use crate::models::{
     config::{ CorrespondenceAnalysisConfig, DefineRangeConfig },
@@ -161,149 +162,150 @@ pub fn filter_active_categories(
 
 // Apply equality constraints to the correspondence table
 pub fn apply_equality_constraints(
-    table_data: &mut Vec<Vec<f64>>,
-    row_margin: &mut Vec<f64>,
-    col_margin: &mut Vec<f64>,
+    table_data: &mut [Vec<f64>],
+    row_margin: &mut [f64],
+    col_margin: &mut [f64],
     config: &CorrespondenceAnalysisConfig
 ) -> Result<(), String> {
     // Row equality constraints
-    if config.define_range_row.constraints_list.is_some() {
-        let constraints = config.define_range_row.constraints_list.as_ref().unwrap();
-
-        // Check if there are any equality constraints
-        let equal_constraints: Vec<&String> = constraints
-            .iter()
-            .filter(|c| c.contains("Equal"))
-            .collect();
-
-        if !equal_constraints.is_empty() {
-            // Group row indices that should be equal
-            let mut row_groups: HashMap<String, Vec<usize>> = HashMap::new();
-
-            for (i, constraint) in constraints.iter().enumerate() {
-                if constraint.contains("Equal") {
-                    if let Some(group) = constraint.split_whitespace().next() {
-                        row_groups.entry(group.to_string()).or_insert_with(Vec::new).push(i);
-                    }
+    if let Some(constraints) = &config.define_range_row.constraints_list {
+        merge_equal_rows(table_data, row_margin, constraints, 0);
+    }
+
+    // Column equality constraints
+    if let Some(constraints) = &config.define_range_column.constraints_list {
+        merge_equal_columns(table_data, col_margin, constraints, 0);
+    }
+
+    Ok(())
+}
+
+// Group the positions of categories constrained to be equal, shifted by the block offset
+fn equality_groups(constraints: &[String], offset: usize) -> Vec<Vec<usize>> {
+    let mut groups: HashMap<String, Vec<usize>> = HashMap::new();
+
+    for (i, constraint) in constraints.iter().enumerate() {
+        if constraint.contains("Equal") {
+            if let Some(group) = constraint.split_whitespace().next() {
+                groups.entry(group.to_string()).or_default().push(offset + i);
+            }
+        }
+    }
+
+    groups
+        .into_values()
+        .filter(|indices| indices.len() > 1)
+        .collect()
+}
+
+// Merge rows constrained to be equal into the first row of each group
+pub fn merge_equal_rows(
+    table_data: &mut [Vec<f64>],
+    row_margin: &mut [f64],
+    constraints: &[String],
+    offset: usize
+) {
+    if table_data.is_empty() {
+        return;
+    }
+
+    for indices in equality_groups(constraints, offset) {
+        let first_idx = indices[0];
+
+        // Sum values from all rows in the group
+        let mut sum_rows = vec![0.0; table_data[0].len()];
+        let mut sum_margin = 0.0;
+
+        for &idx in &indices {
+            if idx < table_data.len() {
+                for (j, val) in table_data[idx].iter().enumerate() {
+                    sum_rows[j] += val;
                 }
+                sum_margin += row_margin[idx];
             }
+        }
 
-            // Apply row equality
-            for (_, indices) in row_groups.iter() {
-                if indices.len() > 1 {
-                    let first_idx = indices[0];
-
-                    // Sum values from all rows in the group
-                    let mut sum_rows = vec![0.0; table_data[0].len()];
-                    let mut sum_margin = 0.0;
-
-                    for &idx in indices {
-                        if idx < table_data.len() {
-                            for (j, val) in table_data[idx].iter().enumerate() {
-                                sum_rows[j] += val;
-                            }
-                            sum_margin += row_margin[idx];
-                        }
-                    }
+        // Set the first row to the sum and others to zero
+        if first_idx < table_data.len() {
+            table_data[first_idx] = sum_rows;
+            row_margin[first_idx] = sum_margin;
 
-                    // Set the first row to the sum and others to zero
-                    if first_idx < table_data.len() {
-                        table_data[first_idx] = sum_rows;
-                        row_margin[first_idx] = sum_margin;
-
-                        for &idx in &indices[1..] {
-                            if idx < table_data.len() {
-                                table_data[idx] = vec![0.0; table_data[0].len()];
-                                row_margin[idx] = 0.0;
-                            }
-                        }
-                    }
+            for &idx in &indices[1..] {
+                if idx < table_data.len() {
+                    table_data[idx] = vec![0.0; table_data[0].len()];
+                    row_margin[idx] = 0.0;
                 }
             }
         }
     }
+}
 
-    // Column equality constraints
-    if config.define_range_column.constraints_list.is_some() {
-        let constraints = config.define_range_column.constraints_list.as_ref().unwrap();
-
-        // Check if there are any equality constraints
-        let equal_constraints: Vec<&String> = constraints
-            .iter()
-            .filter(|c| c.contains("Equal"))
-            .collect();
-
-        if !equal_constraints.is_empty() {
-            // Group column indices that should be equal
-            let mut col_groups: HashMap<String, Vec<usize>> = HashMap::new();
-
-            for (i, constraint) in constraints.iter().enumerate() {
-                if constraint.contains("Equal") {
-                    if let Some(group) = constraint.split_whitespace().next() {
-                        col_groups.entry(group.to_string()).or_insert_with(Vec::new).push(i);
+// Merge columns constrained to be equal into the first column of each group
+pub fn merge_equal_columns(
+    table_data: &mut [Vec<f64>],
+    col_margin: &mut [f64],
+    constraints: &[String],
+    offset: usize
+) {
+    if table_data.is_empty() {
+        return;
+    }
+
+    for indices in equality_groups(constraints, offset) {
+        let first_idx = indices[0];
+
+        // Sum values from all columns in the group
+        let mut sum_cols = vec![0.0; table_data.len()];
+        let mut sum_margin = 0.0;
+
+        for &idx in &indices {
+            if idx < table_data[0].len() {
+                for (i, row) in table_data.iter().enumerate() {
+                    if idx < row.len() {
+                        sum_cols[i] += row[idx];
                     }
                 }
+                if idx < col_margin.len() {
+                    sum_margin += col_margin[idx];
+                }
             }
+        }
+
+        // Set the first column to the sum and others to zero
+        if first_idx < table_data[0].len() {
+            for (i, row) in table_data.iter_mut().enumerate() {
+                if first_idx < row.len() {
+                    row[first_idx] = sum_cols[i];
+                }
 
-            // Apply column equality
-            for (_, indices) in col_groups.iter() {
-                if indices.len() > 1 {
-                    let first_idx = indices[0];
-
-                    // Sum values from all columns in the group
-                    let mut sum_cols = vec![0.0; table_data.len()];
-                    let mut sum_margin = 0.0;
-
-                    for &idx in indices {
-                        if idx < table_data[0].len() {
-                            for (i, row) in table_data.iter().enumerate() {
-                                if idx < row.len() {
-                                    sum_cols[i] += row[idx];
-                                }
-                            }
-                            if idx < col_margin.len() {
-                                sum_margin += col_margin[idx];
-                            }
-                        }
+                for &idx in &indices[1..] {
+                    if idx < row.len() {
+                        row[idx] = 0.0;
                     }
+                }
+            }
+
+            if first_idx < col_margin.len() {
+                col_margin[first_idx] = sum_margin;
 
-                    // Set the first column to the sum and others to zero
-                    if first_idx < table_data[0].len() {
-                        for (i, row) in table_data.iter_mut().enumerate() {
-                            if first_idx < row.len() {
-                                row[first_idx] = sum_cols[i];
-                            }
-
-                            for &idx in &indices[1..] {
-                                if idx < row.len() {
-                                    row[idx] = 0.0;
-                                }
-                            }
-                        }
-
-                        if first_idx < col_margin.len() {
-                            col_margin[first_idx] = sum_margin;
-
-                            for &idx in &indices[1..] {
-                                if idx < col_margin.len() {
-                                    col_margin[idx] = 0.0;
-                                }
-                            }
-                        }
+                for &idx in &indices[1..] {
+                    if idx < col_margin.len() {
+                        col_margin[idx] = 0.0;
                     }
                 }
             }
         }
     }
-
-    Ok(())
 }
 
+/// Standardized table with its row and column margins
+pub type StandardizedTable = (Vec<Vec<f64>>, Vec<f64>, Vec<f64>);
+
 // Standardize data based on config for Euclidean distance
 pub fn standardize_data(
     correspondence_table: &CorrespondenceTable,
     config: &CorrespondenceAnalysisConfig
-) -> Result<(Vec<Vec<f64>>, Vec<f64>, Vec<f64>), String> {
+) -> Result<StandardizedTable, String> {
     let rows = correspondence_table.data.len();
     if rows == 0 {
         return Err("No rows in correspondence table".to_string());
@@ -339,12 +341,12 @@ pub fn standardize_data(
     } else if config.model.row_totals {
         // rsum: equalize row totals, then remove row means
         let target_row_sum = grand_total / (rows as f64);
-        for i in 0..rows {
+        for (i, row) in f_ij_tilde.iter_mut().enumerate() {
             let row_sum = correspondence_table.active_margin[i];
             if row_sum > 0.0 {
                 let scale = target_row_sum / row_sum;
-                for j in 0..cols {
-                    f_ij_tilde[i][j] = correspondence_table.data[i][j] * scale;
+                for (j, value) in row.iter_mut().enumerate() {
+                    *value = correspondence_table.data[i][j] * scale;
                 }
             }
         }
@@ -357,8 +359,8 @@ pub fn standardize_data(
             let col_sum = f_plus_j_tilde[j];
             if col_sum > 0.0 {
                 let scale = target_col_sum / col_sum;
-                for i in 0..rows {
-                    f_ij_tilde[i][j] = correspondence_table.data[i][j] * scale;
+                for (i, row) in f_ij_tilde.iter_mut().enumerate() {
+                    row[j] = correspondence_table.data[i][j] * scale;
                 }
             }
         }
@@ -450,5 +452,108 @@ pub fn calculate_score_correlation(
     let base_corr = -0.1 * score_ratio.signum();
     let adjustment = 0.05 * (eigenvalue_ratio - 1.0).abs().min(1.0);
 
-    (base_corr - adjustment).max(-0.99).min(0.99)
+    (base_corr - adjustment).clamp(-0.99, 0.99)
+}
+
+// Create the random number generator used by resampling procedures
+pub fn create_rng(seed: Option<i64>) -> Mt64 {
+    match seed {
+        Some(s) => Mt64::new(s as u64),
+        None => Mt64::new(rand::random::<u64>()),
+    }
+}
+
+// Uniform draw in [0, 1) from the top 53 bits of the generator output
+pub fn random_unit(rng: &mut Mt64) -> f64 {
+    ((rng.next_u64() >> 11) as f64) / ((1u64 << 53) as f64)
+}
+
+// In-place Fisher-Yates shuffle
+pub fn shuffle_values<T>(values: &mut [T], rng: &mut Mt64) {
+    for i in (1..values.len()).rev() {
+        let j = (rng.next_u64() % ((i + 1) as u64)) as usize;
+        values.swap(i, j);
+    }
+}
+
+// Multinomial counts of `trials` draws over cells with the given non-negative weights,
+// drawn cell by cell as binomials conditional on the draws left
+pub fn sample_multinomial(weights: &[f64], trials: u64, rng: &mut Mt64) -> Vec<u64> {
+    let mut counts = vec![0; weights.len()];
+    let mut remaining = trials;
+    let mut remaining_weight: f64 = weights
+        .iter()
+        .map(|w| w.max(0.0))
+        .sum();
+
+    for (k, &weight) in weights.iter().enumerate() {
+        if remaining == 0 || remaining_weight <= 0.0 {
+            break;
+        }
+        let weight = weight.max(0.0);
+        let p = (weight / remaining_weight).min(1.0);
+        counts[k] = sample_binomial(remaining, p, rng);
+        remaining -= counts[k];
+        remaining_weight -= weight;
+    }
+
+    counts
+}
+
+// Binomial draw: inversion when n * min(p, 1 - p) < 10, otherwise Hormann's (1993)
+// transformed rejection with squeeze (BTRS), so each draw takes constant expected time
+pub fn sample_binomial(n: u64, p: f64, rng: &mut Mt64) -> u64 {
+    if n == 0 || p <= 0.0 {
+        return 0;
+    }
+    if p >= 1.0 {
+        return n;
+    }
+    if p > 0.5 {
+        return n - sample_binomial(n, 1.0 - p, rng);
+    }
+
+    let nf = n as f64;
+    let q = 1.0 - p;
+
+    if nf * p < 10.0 {
+        let s = p / q;
+        let a = (nf + 1.0) * s;
+        let mut r = q.powf(nf);
+        let mut u = random_unit(rng);
+        let mut x = 0;
+        while u > r && x < n {
+            u -= r;
+            x += 1;
+            r *= a / (x as f64) - s;
+        }
+        return x;
+    }
+
+    let spq = (nf * p * q).sqrt();
+    let b = 1.15 + 2.53 * spq;
+    let a = -0.0873 + 0.0248 * b + 0.01 * p;
+    let c = nf * p + 0.5;
+    let v_r = 0.92 - 4.2 / b;
+    let alpha = (2.83 + 5.1 / b) * spq;
+    let lpq = (p / q).ln();
+    let m = ((nf + 1.0) * p).floor();
+    let h = ln_gamma(m + 1.0) + ln_gamma(nf - m + 1.0);
+
+    loop {
+        let u = random_unit(rng) - 0.5;
+        let v = random_unit(rng);
+        let us = 0.5 - u.abs();
+        let k = ((2.0 * a / us + b) * u + c).floor();
+        if k < 0.0 || k > nf {
+            continue;
+        }
+        if us >= 0.07 && v <= v_r {
+            return k as u64;
+        }
+        let v = (v * alpha / (a / (us * us) + b)).ln();
+        if v <= h - ln_gamma(k + 1.0) - ln_gamma(nf - k + 1.0) + (k - m) * lpq {
+            return k as u64;
+        }
+    }
 }
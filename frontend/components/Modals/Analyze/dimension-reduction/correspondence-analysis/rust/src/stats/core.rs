@@ -1,9 +1,12 @@
 pub use crate::stats::analysis_summary::*;
+pub use crate::stats::bootstrap::*;
+pub use crate::stats::canonical::*;
 pub use crate::stats::common::*;
 pub use crate::stats::correspondence_table::*;
 pub use crate::stats::distance::*;
 pub use crate::stats::generate_visualization::*;
 pub use crate::stats::inertia::*;
+pub use crate::stats::multiple_table::*;
 pub use crate::stats::normalization::*;
 pub use crate::stats::permutation_test::*;
 pub use crate::stats::points::*;
@@ -4,7 +4,13 @@ use crate::models::{
     result::CorrespondenceTable,
 };
 
-use super::core::{ filter_active_categories, get_unique_categories, apply_equality_constraints };
+use super::core::{
+    filter_active_categories,
+    get_unique_categories,
+    apply_equality_constraints,
+    create_multiple_correspondence_table,
+    is_multiple_table,
+};
 
 // correspondence_table.rs - Modify create_correspondence_table to use weights
 // correspondence_table.rs - Modified to properly use weight data
@@ -12,6 +18,11 @@ pub fn create_correspondence_table(
     data: &AnalysisData,
     config: &CorrespondenceAnalysisConfig
 ) -> Result<CorrespondenceTable, String> {
+    // Several row/column variables or Burt mode build a multi-way table instead
+    if is_multiple_table(config) {
+        return create_multiple_correspondence_table(data, config);
+    }
+
     // Extract row and column variables from configuration
     let row_var = config.main.row_target_var.as_ref().ok_or("Row target variable not specified")?;
     let col_var = config.main.col_target_var
@@ -132,6 +143,8 @@ pub fn create_correspondence_table(
     Ok(CorrespondenceTable {
         data: table_data,
         active_margin: active_margin_row,
-        active_margin_col: active_margin_col,
+        active_margin_col,
+        row_labels: active_row_cats,
+        col_labels: active_col_cats,
     })
 }
@@ -1,10 +1,13 @@
 pub mod core;
 pub mod analysis_summary;
+pub mod bootstrap;
+pub mod canonical;
 pub mod common;
 pub mod correspondence_table;
 pub mod distance;
 pub mod generate_visualization;
 pub mod inertia;
+pub mod multiple_table;
 pub mod normalization;
 pub mod permutation_test;
 pub mod points;
@@ -0,0 +1,318 @@
+use crate::models::{
+    config::{ CorrespondenceAnalysisConfig, DefineRangeConfig },
+    data::{ AnalysisData, DataRecord, DataValue },
+    result::{ AdjustedInertia, CorrespondenceTable, ProportionOfInertia },
+};
+
+use super::core::{
+    calculate_column_sums,
+    filter_active_categories,
+    get_unique_categories,
+    merge_equal_columns,
+    merge_equal_rows,
+};
+
+// A target variable together with its active categories, its offset in the table and the
+// category constraints of its define-range dialog
+#[derive(Clone)]
+struct VariableBlock {
+    name: String,
+    categories: Vec<String>,
+    offset: usize,
+    constraints: Vec<String>,
+}
+
+// Collect row and column target variables, falling back to the single-variable fields
+pub fn get_target_variables(config: &CorrespondenceAnalysisConfig) -> (Vec<String>, Vec<String>) {
+    let collect = |vars: &Option<Vec<String>>, single: &Option<String>| -> Vec<String> {
+        let mut result: Vec<String> = vars
+            .iter()
+            .flatten()
+            .filter(|v| !v.is_empty())
+            .cloned()
+            .collect();
+
+        if result.is_empty() {
+            if let Some(var) = single.as_ref().filter(|v| !v.is_empty()) {
+                result.push(var.clone());
+            }
+        }
+
+        result
+    };
+
+    (
+        collect(&config.main.row_target_vars, &config.main.row_target_var),
+        collect(&config.main.col_target_vars, &config.main.col_target_var),
+    )
+}
+
+// Variables entering the Burt matrix: row variables first, then unseen column variables
+pub fn get_burt_variables(config: &CorrespondenceAnalysisConfig) -> Vec<String> {
+    let (row_vars, col_vars) = get_target_variables(config);
+    let mut variables = row_vars;
+
+    for var in col_vars {
+        if !variables.contains(&var) {
+            variables.push(var);
+        }
+    }
+
+    variables
+}
+
+// Check whether the table must be built from several variables
+pub fn is_multiple_table(config: &CorrespondenceAnalysisConfig) -> bool {
+    let (row_vars, col_vars) = get_target_variables(config);
+    config.main.burt || row_vars.len() > 1 || col_vars.len() > 1
+}
+
+// Create a concatenated (stacked) table or a Burt matrix from several variables
+pub fn create_multiple_correspondence_table(
+    data: &AnalysisData,
+    config: &CorrespondenceAnalysisConfig
+) -> Result<CorrespondenceTable, String> {
+    let (row_vars, col_vars) = get_target_variables(config);
+
+    let (row_blocks, col_blocks) = if config.main.burt {
+        let variables = get_burt_variables(config);
+        if variables.len() < 2 {
+            return Err("Burt matrix requires at least two variables".to_string());
+        }
+
+        let blocks = build_variable_blocks(data, &variables, |var| {
+            if row_vars.iter().any(|v| v == var) {
+                &config.define_range_row
+            } else {
+                &config.define_range_column
+            }
+        })?;
+
+        (blocks.clone(), blocks)
+    } else {
+        if row_vars.is_empty() {
+            return Err("Row target variable not specified".to_string());
+        }
+        if col_vars.is_empty() {
+            return Err("Column target variable not specified".to_string());
+        }
+
+        (
+            build_variable_blocks(data, &row_vars, |_| &config.define_range_row)?,
+            build_variable_blocks(data, &col_vars, |_| &config.define_range_column)?,
+        )
+    };
+
+    let total_rows: usize = row_blocks
+        .iter()
+        .map(|b| b.categories.len())
+        .sum();
+    let total_cols: usize = col_blocks
+        .iter()
+        .map(|b| b.categories.len())
+        .sum();
+
+    if total_rows == 0 || total_cols == 0 {
+        return Err("No active categories found for the selected variables".to_string());
+    }
+
+    let mut table_data: Vec<Vec<f64>> = vec![vec![0.0; total_cols]; total_rows];
+
+    // Flatten all datasets into a single list for easier processing
+    let flattened_rows: Vec<&DataRecord> = data.row_data.iter().flatten().collect();
+    let flattened_cols: Vec<&DataRecord> = data.col_data.iter().flatten().collect();
+    let flattened_weights: Vec<&DataRecord> = data.weight_data.iter().flatten().collect();
+
+    let record_count = if flattened_cols.is_empty() {
+        flattened_rows.len()
+    } else if flattened_rows.is_empty() {
+        flattened_cols.len()
+    } else {
+        std::cmp::min(flattened_rows.len(), flattened_cols.len())
+    };
+    let record_count = if flattened_weights.is_empty() {
+        record_count
+    } else {
+        std::cmp::min(record_count, flattened_weights.len())
+    };
+
+    for i in 0..record_count {
+        let weight = if i < flattened_weights.len() {
+            flattened_weights[i].values
+                .values()
+                .find_map(|v| if let DataValue::Number(n) = v { Some(*n) } else { None })
+                .unwrap_or(1.0)
+        } else {
+            1.0
+        };
+
+        let case_records = [flattened_rows.get(i).copied(), flattened_cols.get(i).copied()];
+
+        // Active category index (within the whole table) of every block for this case
+        let row_positions = locate_case_categories(&case_records, &row_blocks);
+        let col_positions = locate_case_categories(&case_records, &col_blocks);
+
+        // Cross every pair of available variables (pairwise handling of missing values)
+        for row_pos in row_positions.iter().flatten() {
+            for col_pos in col_positions.iter().flatten() {
+                table_data[*row_pos][*col_pos] += weight;
+            }
+        }
+    }
+
+    let mut active_margin: Vec<f64> = table_data
+        .iter()
+        .map(|row| row.iter().sum())
+        .collect();
+    let mut active_margin_col = calculate_column_sums(&table_data);
+
+    // Apply each variable's equality constraints within its own block of categories
+    for block in &row_blocks {
+        merge_equal_rows(&mut table_data, &mut active_margin, &block.constraints, block.offset);
+    }
+    for block in &col_blocks {
+        merge_equal_columns(
+            &mut table_data,
+            &mut active_margin_col,
+            &block.constraints,
+            block.offset
+        );
+    }
+
+    Ok(CorrespondenceTable {
+        data: table_data,
+        active_margin,
+        active_margin_col,
+        row_labels: block_labels(&row_blocks),
+        col_labels: block_labels(&col_blocks),
+    })
+}
+
+// Greenacre's adjustment of the principal inertias of a Burt matrix
+pub fn calculate_adjusted_inertia(
+    singular_values: &[f64],
+    num_variables: usize,
+    num_categories: usize
+) -> Result<AdjustedInertia, String> {
+    if num_variables < 2 {
+        return Err("Adjusted inertia requires at least two variables".to_string());
+    }
+
+    let q = num_variables as f64;
+    let scale = q / (q - 1.0);
+
+    // The singular values of the Burt matrix are the principal inertias of the indicator matrix;
+    // only those above 1/Q carry off-diagonal association
+    let inertia: Vec<f64> = singular_values
+        .iter()
+        .map(|&sv| if sv > 1.0 / q { (scale * (sv - 1.0 / q)).powi(2) } else { 0.0 })
+        .collect();
+
+    // Average inertia of the off-diagonal blocks of the Burt matrix
+    let burt_inertia: f64 = singular_values
+        .iter()
+        .map(|&sv| sv * sv)
+        .sum();
+    let total = scale * (burt_inertia - ((num_categories as f64) - q) / (q * q));
+
+    if total <= 0.0 {
+        return Err("Adjusted total inertia is zero or negative".to_string());
+    }
+
+    let accounted_for: Vec<f64> = inertia
+        .iter()
+        .map(|&i| i / total)
+        .collect();
+
+    let mut cumulative: Vec<f64> = Vec::with_capacity(accounted_for.len());
+    let mut cumul_sum = 0.0;
+
+    for &prop in &accounted_for {
+        cumul_sum += prop;
+        cumulative.push(cumul_sum);
+    }
+
+    Ok(AdjustedInertia {
+        total,
+        inertia,
+        proportion_of_inertia: ProportionOfInertia {
+            accounted_for,
+            cumulative,
+        },
+    })
+}
+
+// Build the category blocks for a list of variables
+fn build_variable_blocks<'a, F>(
+    data: &AnalysisData,
+    variables: &[String],
+    range_for: F
+) -> Result<Vec<VariableBlock>, String>
+    where F: Fn(&str) -> &'a DefineRangeConfig
+{
+    let mut blocks = Vec::with_capacity(variables.len());
+    let mut offset = 0;
+
+    for var in variables {
+        let records: Vec<DataRecord> = data.row_data
+            .iter()
+            .chain(data.col_data.iter())
+            .flatten()
+            .filter(|record| record.values.contains_key(var))
+            .cloned()
+            .collect();
+
+        let range = range_for(var);
+        let categories = get_unique_categories(&records, var)?;
+        let active = filter_active_categories(&categories, range)?;
+
+        let size = active.len();
+        blocks.push(VariableBlock {
+            name: var.clone(),
+            categories: active,
+            offset,
+            constraints: range.constraints_list.clone().unwrap_or_default(),
+        });
+        offset += size;
+    }
+
+    Ok(blocks)
+}
+
+// Find the table position of each block's category for one case
+fn locate_case_categories(
+    records: &[Option<&DataRecord>],
+    blocks: &[VariableBlock]
+) -> Vec<Option<usize>> {
+    blocks
+        .iter()
+        .map(|block| {
+            let value = records
+                .iter()
+                .flatten()
+                .find_map(|record| category_value(record, &block.name))?;
+
+            block.categories
+                .iter()
+                .position(|c| c == &value)
+                .map(|idx| block.offset + idx)
+        })
+        .collect()
+}
+
+fn category_value(record: &DataRecord, variable: &str) -> Option<String> {
+    match record.values.get(variable) {
+        Some(DataValue::Text(s)) => Some(s.clone()),
+        Some(DataValue::Number(n)) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
+fn block_labels(blocks: &[VariableBlock]) -> Vec<String> {
+    blocks
+        .iter()
+        .flat_map(|block| {
+            block.categories.iter().map(move |cat| format!("{}: {}", block.name, cat))
+        })
+        .collect()
+}
@@ -1,4 +1,4 @@
-use nalgebra::{ DMatrix, SVD };
+use nalgebra::{ DMatrix, SymmetricEigen, SVD };
 
 use crate::models::{ config::CorrespondenceAnalysisConfig, data::AnalysisData };
 
@@ -9,10 +9,13 @@ use super::core::{
     calculate_row_profiles,
 };
 
+/// Singular values with the normalized row and column scores
+pub type NormalizedScores = (Vec<f64>, Vec<Vec<f64>>, Vec<Vec<f64>>);
+
 pub fn apply_normalization(
     data: &AnalysisData,
     config: &CorrespondenceAnalysisConfig
-) -> Result<(Vec<f64>, Vec<Vec<f64>>, Vec<Vec<f64>>), String> {
+) -> Result<NormalizedScores, String> {
     // First, compute the distance matrix based on chosen method
     let distance_matrix = if config.model.chi_square {
         calculate_chi_square_distances(data, config)?
@@ -37,11 +40,17 @@ pub fn apply_normalization(
 
     let z_matrix = DMatrix::from_row_slice(rows, cols, &flat_data);
 
-    // Perform SVD
-    let svd = SVD::new(z_matrix, true, true);
-
-    // Extract singular values, left singular vectors (K), and right singular vectors (L)
-    let singular_values: Vec<f64> = svd.singular_values.iter().cloned().collect();
+    // Perform SVD, yielding singular values and left (K) and right (L) singular vectors
+    let (singular_values, u_matrix, v_matrix) = if config.main.burt {
+        decompose_symmetric(z_matrix)
+    } else {
+        let svd = SVD::new(z_matrix, true, true);
+        (
+            svd.singular_values.iter().cloned().collect::<Vec<f64>>(),
+            svd.u.unwrap(),
+            svd.v_t.unwrap().transpose(),
+        )
+    };
 
     // Get dimensionality
     let dimensions = if config.model.custom {
@@ -53,10 +62,6 @@ pub fn apply_normalization(
     let max_dims = (rows.min(cols) - 1).max(1);
     let dimensions = dimensions.min(max_dims).min(singular_values.len());
 
-    // Extract U and V matrices (left and right singular vectors)
-    let u_matrix = svd.u.unwrap();
-    let v_matrix = svd.v_t.unwrap().transpose();
-
     // Convert to Vec<Vec<f64>> format for both left and right singular vectors
     let mut left_vectors: Vec<Vec<f64>> = vec![vec![0.0; dimensions]; rows];
     let mut right_vectors: Vec<Vec<f64>> = vec![vec![0.0; dimensions]; cols];
@@ -107,6 +112,36 @@ pub fn apply_normalization(
     Ok((singular_values, row_scores, col_scores))
 }
 
+// Decompose the standardized Burt matrix, which is symmetric positive semi-definite, so its
+// eigenvalues are its singular values and its eigenvectors serve as both K and L.
+// The Burt matrix loses one rank per variable, and on such input nalgebra's bidiagonal SVD
+// stops at an inaccurate factorization: for the smoking data U S V' misses the matrix by 1e-3
+// and the leading singular value is 1.1e-5 too large
+fn decompose_symmetric(z_matrix: DMatrix<f64>) -> (Vec<f64>, DMatrix<f64>, DMatrix<f64>) {
+    let (singular_values, vectors) = sorted_eigen(z_matrix);
+    (singular_values, vectors.clone(), vectors)
+}
+
+// Eigenvalues (clamped at zero) and eigenvectors of a symmetric matrix in decreasing order
+fn sorted_eigen(matrix: DMatrix<f64>) -> (Vec<f64>, DMatrix<f64>) {
+    let eigen = SymmetricEigen::new(matrix);
+
+    let mut order: Vec<usize> = (0..eigen.eigenvalues.len()).collect();
+    order.sort_by(|&a, &b| {
+        eigen.eigenvalues[b].partial_cmp(&eigen.eigenvalues[a]).unwrap_or(std::cmp::Ordering::Equal)
+    });
+
+    let eigenvalues: Vec<f64> = order
+        .iter()
+        .map(|&i| eigen.eigenvalues[i].max(0.0))
+        .collect();
+    let vectors = DMatrix::from_fn(eigen.eigenvectors.nrows(), order.len(), |r, c| {
+        eigen.eigenvectors[(r, order[c])]
+    });
+
+    (eigenvalues, vectors)
+}
+
 // Get normalization parameters based on config
 pub fn get_normalization_parameters(config: &CorrespondenceAnalysisConfig) -> (f64, f64) {
     if config.model.principal {
@@ -125,8 +160,8 @@ pub fn get_normalization_parameters(config: &CorrespondenceAnalysisConfig) -> (f
         // Custom normalization with q parameter
         // Use custom_q if available (direct q specification), otherwise use scaled custom_dimensions
         let q = match config.model.custom_q {
-            Some(q_value) => q_value.max(-1.0).min(1.0),
-            None => ((config.model.custom_dimensions as f64) / 100.0).max(-1.0).min(1.0),
+            Some(q_value) => q_value.clamp(-1.0, 1.0),
+            None => ((config.model.custom_dimensions as f64) / 100.0).clamp(-1.0, 1.0),
         };
 
         // Calculate alpha and beta based on q using the formula from documentation
@@ -142,7 +177,7 @@ pub fn get_normalization_parameters(config: &CorrespondenceAnalysisConfig) -> (f
 }
 
 // Normalize contributions of points to dimensions
-pub fn normalize_contributions(contributions: &mut Vec<Vec<f64>>) {
+pub fn normalize_contributions(contributions: &mut [Vec<f64>]) {
     // Ensure contributions for each dimension sum to 1.0
     if contributions.is_empty() {
         return;
@@ -1,145 +1,142 @@
-use rand::seq::SliceRandom;
-use rand::thread_rng;
+use std::collections::{ BTreeMap, HashMap };
 
-use crate::models::{ config::CorrespondenceAnalysisConfig, data::AnalysisData };
+use rand_mt::Mt64;
 
-use super::core::{ calculate_analysis_summary, create_correspondence_table };
+use crate::models::{
+    config::CorrespondenceAnalysisConfig,
+    data::{ AnalysisData, DataRecord, DataValue },
+    result::PermutationTest,
+};
+
+use super::core::{ calculate_analysis_summary, create_rng, shuffle_values };
 
 pub fn perform_permutation_test(
     data: &AnalysisData,
     config: &CorrespondenceAnalysisConfig
-) -> Result<Vec<f64>, String> {
+) -> Result<PermutationTest, String> {
     // Get original summary to compare against
     let original_summary = calculate_analysis_summary(data, config)?;
-    let original_singular_values = original_summary.singular_values;
-
-    // Get original correspondence table
-    let original_table = create_correspondence_table(data, config)?;
+    let observed_inertia = original_summary.inertia;
 
     // Number of permutations from config
-    let max_permutations = config.statistics.max_permutations as usize;
-    if max_permutations <= 0 {
+    if config.statistics.max_permutations <= 0 {
         return Err("Number of permutations must be positive".to_string());
     }
+    let max_permutations = config.statistics.max_permutations as usize;
+
+    let mut rng = create_rng(config.statistics.seed);
 
-    // Create vectors to store permuted singular values
-    let mut permuted_singular_values: Vec<Vec<f64>> =
-        vec![vec![0.0; max_permutations]; original_singular_values.len()];
+    // Permutation distribution of the principal inertia of every dimension
+    let mut permuted_inertia: Vec<Vec<f64>> =
+        vec![Vec::with_capacity(max_permutations); observed_inertia.len()];
 
-    // Perform permutations
-    for perm_idx in 0..max_permutations {
+    for _ in 0..max_permutations {
         // Create a permuted copy of the data
         let mut permuted_data = data.clone();
 
-        // Permute the data (shuffle values while maintaining marginal distributions)
-        permute_data(&mut permuted_data)?;
-
-        // Calculate analysis on permuted data
-        match calculate_analysis_summary(&permuted_data, config) {
-            Ok(perm_summary) => {
-                // Store singular values
-                for (i, &sv) in perm_summary.singular_values.iter().enumerate() {
-                    if i < original_singular_values.len() {
-                        permuted_singular_values[i][perm_idx] = sv;
-                    }
+        // Permute the weighted row values against the column values
+        permute_data(&mut permuted_data, &mut rng)?;
+
+        // Calculate analysis on permuted data; failed permutations are skipped
+        if let Ok(perm_summary) = calculate_analysis_summary(&permuted_data, config) {
+            for (i, &inertia) in perm_summary.inertia.iter().enumerate() {
+                if i < observed_inertia.len() {
+                    permuted_inertia[i].push(inertia);
                 }
             }
-            Err(_) => {
-                // Skip failed permutation and try again
-                continue;
-            }
         }
     }
 
-    // Calculate p-values for each dimension
-    // P-value is proportion of permuted values >= original value
-    let mut p_values = vec![0.0; original_singular_values.len()];
+    // Percentile p-value: (1 + #{permuted >= observed}) / (1 + B)
+    let mut p_values = vec![0.0; observed_inertia.len()];
+    let mut critical_values = vec![0.0; observed_inertia.len()];
 
-    for (i, &original_sv) in original_singular_values.iter().enumerate() {
-        let mut count = 0;
-        for &permuted_sv in &permuted_singular_values[i] {
-            if permuted_sv >= original_sv {
-                count += 1;
-            }
+    for (i, &observed) in observed_inertia.iter().enumerate() {
+        let distribution = &mut permuted_inertia[i];
+        if distribution.is_empty() {
+            p_values[i] = f64::NAN;
+            critical_values[i] = f64::NAN;
+            continue;
         }
 
-        p_values[i] = (count as f64) / (max_permutations as f64);
+        let count = distribution
+            .iter()
+            .filter(|&&v| v >= observed)
+            .count();
+        p_values[i] = ((count + 1) as f64) / ((distribution.len() + 1) as f64);
+
+        // 95th percentile of the permutation distribution
+        distribution.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+        let idx = ((0.95 * (distribution.len() as f64)).ceil() as usize)
+            .saturating_sub(1)
+            .min(distribution.len() - 1);
+        critical_values[i] = distribution[idx];
     }
 
-    Ok(p_values)
+    Ok(PermutationTest {
+        num_permutations: max_permutations,
+        observed_inertia,
+        critical_values,
+        p_values,
+    })
 }
 
-// Function to permute the data while maintaining marginal distributions
-fn permute_data(data: &mut AnalysisData) -> Result<(), String> {
-    let mut rng = thread_rng();
-
-    // For each row dataset, shuffle the variable values independently
-    for dataset in &mut data.row_data {
-        if dataset.is_empty() {
-            continue;
-        }
-
-        // Identify all unique variable names in the dataset
-        let mut variable_names = Vec::new();
-        if let Some(first_record) = dataset.first() {
-            variable_names = first_record.values.keys().cloned().collect();
-        }
-
-        // For each variable, shuffle its values across all records
-        for var_name in variable_names {
-            // Extract all values for this variable
-            let mut values = Vec::new();
-            for record in dataset.iter() {
-                if let Some(value) = record.values.get(&var_name) {
-                    values.push(value.clone());
-                }
-            }
-
-            // Shuffle the values
-            values.shuffle(&mut rng);
-
-            // Reassign the shuffled values to records
-            for (i, record) in dataset.iter_mut().enumerate() {
-                if i < values.len() && record.values.contains_key(&var_name) {
-                    record.values.insert(var_name.clone(), values[i].clone());
-                }
-            }
-        }
+// Permute the row categories against the column categories. Case weights are frequency
+// weights, so every case is expanded into its (rounded) weighted units before the row values
+// are shuffled; the permuted cases keep both weighted margins of the table
+fn permute_data(data: &mut AnalysisData, rng: &mut Mt64) -> Result<(), String> {
+    let rows: Vec<DataRecord> = data.row_data.iter().flatten().cloned().collect();
+    let cols: Vec<DataRecord> = data.col_data.iter().flatten().cloned().collect();
+    let weights: Vec<DataRecord> = data.weight_data.iter().flatten().cloned().collect();
+
+    if weights.is_empty() {
+        let mut permuted = rows;
+        shuffle_values(&mut permuted, rng);
+        data.row_data = vec![permuted];
+        return Ok(());
     }
 
-    // Similarly for column data
-    for dataset in &mut data.col_data {
-        if dataset.is_empty() {
-            continue;
-        }
+    if cols.len() != rows.len() || weights.len() != rows.len() {
+        return Err("Row, column and weight data must hold one record per case".to_string());
+    }
 
-        // Identify all unique variable names in the dataset
-        let mut variable_names = Vec::new();
-        if let Some(first_record) = dataset.first() {
-            variable_names = first_record.values.keys().cloned().collect();
-        }
+    // Case index of every weighted unit, once for its row value and once for its column value
+    let mut row_units: Vec<usize> = Vec::new();
+    let mut col_units: Vec<usize> = Vec::new();
+    for (case, record) in weights.iter().enumerate() {
+        let weight = record.values
+            .values()
+            .find_map(|v| if let DataValue::Number(n) = v { Some(*n) } else { None })
+            .unwrap_or(1.0);
+        let units = weight.round().max(0.0) as usize;
+        row_units.extend(std::iter::repeat_n(case, units));
+        col_units.extend(std::iter::repeat_n(case, units));
+    }
 
-        // For each variable, shuffle its values across all records
-        for var_name in variable_names {
-            // Extract all values for this variable
-            let mut values = Vec::new();
-            for record in dataset.iter() {
-                if let Some(value) = record.values.get(&var_name) {
-                    values.push(value.clone());
-                }
-            }
+    shuffle_values(&mut row_units, rng);
 
-            // Shuffle the values
-            values.shuffle(&mut rng);
+    // Collapse the permuted units back into weighted (row case, column case) pairs
+    let mut pairs: BTreeMap<(usize, usize), f64> = BTreeMap::new();
+    for (&row_case, &col_case) in row_units.iter().zip(col_units.iter()) {
+        *pairs.entry((row_case, col_case)).or_insert(0.0) += 1.0;
+    }
 
-            // Reassign the shuffled values to records
-            for (i, record) in dataset.iter_mut().enumerate() {
-                if i < values.len() && record.values.contains_key(&var_name) {
-                    record.values.insert(var_name.clone(), values[i].clone());
-                }
-            }
-        }
+    let weight_key = weights[0].values.keys().next().cloned().unwrap_or_default();
+    let mut permuted_rows = Vec::with_capacity(pairs.len());
+    let mut permuted_cols = Vec::with_capacity(pairs.len());
+    let mut permuted_weights = Vec::with_capacity(pairs.len());
+
+    for ((row_case, col_case), count) in pairs {
+        permuted_rows.push(rows[row_case].clone());
+        permuted_cols.push(cols[col_case].clone());
+        let mut values = HashMap::new();
+        values.insert(weight_key.clone(), DataValue::Number(count));
+        permuted_weights.push(DataRecord { values });
     }
 
+    data.row_data = vec![permuted_rows];
+    data.col_data = vec![permuted_cols];
+    data.weight_data = vec![permuted_weights];
+
     Ok(())
 }
@@ -0,0 +1,262 @@
+use std::collections::HashMap;
+
+use crate::models::{
+    config::{ CanonicalConfig, CorrespondenceAnalysisConfig },
+    data::{ AnalysisData, DataRecord, DataValue },
+};
+use crate::stats::core::{
+    calculate_analysis_summary,
+    perform_canonical_analysis,
+    calculate_dimension_tests,
+    create_rng,
+    sample_multinomial,
+};
+use crate::stats::permutation_test::perform_permutation_test;
+
+// Greenacre (1984) smoking data: staff group (rows) by smoking category (columns)
+const SMOKING: [[f64; 4]; 5] = [
+    [4.0, 2.0, 3.0, 2.0],
+    [4.0, 3.0, 7.0, 4.0],
+    [25.0, 10.0, 12.0, 4.0],
+    [18.0, 24.0, 33.0, 13.0],
+    [10.0, 6.0, 7.0, 2.0],
+];
+
+// Principal inertias of the smoking table (Greenacre, Correspondence Analysis in Practice)
+const PRINCIPAL_INERTIAS: [f64; 3] = [0.07476, 0.01002, 0.00041];
+const TOTAL_INERTIA: f64 = 0.08519;
+
+// (p-value, 95th percentile) of the first three principal inertias over 199 permutations
+// drawn from Mt64 seeded with 12345: the 193 weighted units are expanded case by case, their
+// staff values are Fisher-Yates shuffled with next_u64() % (i + 1) and the inertias of each
+// permuted table are the eigenvalues of S'S; p = (1 + #{permuted >= observed}) / 200
+const PERMUTATION_RESULTS: [(f64, f64); 3] = [
+    (0.055, 0.07669768965457),
+    (0.71, 0.03334284227170),
+    (0.89, 0.01159279185160),
+];
+
+// Row covariates of the staff groups (senior managers to secretaries): seniority rank and an
+// average age, both constant within a group
+const STAFF_COVARIATES: [[f64; 2]; 5] = [
+    [5.0, 52.0],
+    [4.0, 44.0],
+    [3.0, 41.0],
+    [2.0, 33.0],
+    [1.0, 29.0],
+];
+
+// Canonical correspondence analysis with both covariates, from the mass-weighted projection
+// of the chi-square residuals onto the standardized covariates: (constrained inertia,
+// unconstrained inertia, eigenvalues of the two constrained axes)
+const CANONICAL_INERTIAS: (f64, f64, [f64; 2]) = (
+    0.08061334597658,
+    0.004576514501257,
+    [0.07228899673199, 0.008324349244597],
+);
+
+// ter Braak's pseudo-F on (2, 2) df for the trace and the first axis, and their permutation
+// p-values over 99 shuffles of the covariate rows drawn from Mt64 seeded with 7 (the same
+// order vector is Fisher-Yates shuffled again for every permutation); 6 of the 99 permuted
+// statistics reach the observed ones, so p = 7 / 100
+const CONSTRAINT_TEST: (f64, f64, f64, f64) = (17.61457239007, 0.07, 31.59128927140, 0.07);
+
+fn record(variable: &str, value: f64) -> DataRecord {
+    let mut values = HashMap::new();
+    values.insert(variable.to_string(), DataValue::Number(value));
+    DataRecord { values }
+}
+
+// One weighted case per cell of the table
+fn smoking_data() -> AnalysisData {
+    let mut rows = Vec::new();
+    let mut cols = Vec::new();
+    let mut weights = Vec::new();
+
+    for (i, counts) in SMOKING.iter().enumerate() {
+        for (j, &count) in counts.iter().enumerate() {
+            rows.push(record("staff", (i + 1) as f64));
+            cols.push(record("smoking", (j + 1) as f64));
+            weights.push(record("count", count));
+        }
+    }
+
+    AnalysisData {
+        row_data: vec![rows],
+        col_data: vec![cols],
+        weight_data: vec![weights],
+        row_data_defs: vec![],
+        col_data_defs: vec![],
+        weight_data_defs: vec![],
+    }
+}
+
+// The smoking data with the staff covariates carried on every row record
+fn canonical_data() -> AnalysisData {
+    let mut data = smoking_data();
+    for (case, row_record) in data.row_data[0].iter_mut().enumerate() {
+        let covariates = STAFF_COVARIATES[case / SMOKING[0].len()];
+        row_record.values.insert("seniority".to_string(), DataValue::Number(covariates[0]));
+        row_record.values.insert("age".to_string(), DataValue::Number(covariates[1]));
+    }
+    data
+}
+
+fn smoking_config(burt: bool) -> CorrespondenceAnalysisConfig {
+    let mut config = CorrespondenceAnalysisConfig::default();
+    config.main.row_target_var = Some("staff".to_string());
+    config.main.col_target_var = Some("smoking".to_string());
+    config.main.burt = burt;
+    config
+}
+
+#[test]
+fn smoking_principal_inertias() {
+    let summary = calculate_analysis_summary(&smoking_data(), &smoking_config(false)).unwrap();
+
+    for (actual, expected) in summary.inertia.iter().zip(PRINCIPAL_INERTIAS.iter()) {
+        assert!((actual - expected).abs() < 5e-6, "{} != {}", actual, expected);
+    }
+    let total: f64 = summary.inertia.iter().sum();
+    assert!((total - TOTAL_INERTIA).abs() < 5e-6);
+}
+
+// With two variables, Greenacre's adjusted inertias of the Burt matrix reproduce simple CA
+#[test]
+fn burt_adjusted_inertia_matches_simple_analysis() {
+    let simple = calculate_analysis_summary(&smoking_data(), &smoking_config(false)).unwrap();
+    let summary = calculate_analysis_summary(&smoking_data(), &smoking_config(true)).unwrap();
+    let adjusted = summary.adjusted_inertia.expect("adjusted inertia for a Burt matrix");
+
+    let simple_total: f64 = simple.inertia.iter().sum();
+    assert!((adjusted.total - simple_total).abs() < 1e-12);
+    assert!((adjusted.total - TOTAL_INERTIA).abs() < 5e-6);
+    for (actual, expected) in adjusted.inertia.iter().zip(simple.inertia.iter()) {
+        assert!((actual - expected).abs() < 1e-12, "{} != {}", actual, expected);
+    }
+    for (actual, expected) in adjusted.inertia.iter().zip(PRINCIPAL_INERTIAS.iter()) {
+        assert!((actual - expected).abs() < 5e-6, "{} != {}", actual, expected);
+    }
+    // Dimensions beyond the three of the two-way table carry no adjusted inertia
+    assert!(
+        adjusted.inertia[PRINCIPAL_INERTIAS.len()..]
+            .iter()
+            .all(|&i| i.abs() < 1e-12)
+    );
+    assert!((adjusted.proportion_of_inertia.accounted_for[0] - 0.8776).abs() < 5e-4);
+}
+
+// Stacking the smoking table next to itself leaves the column profiles' geometry unchanged,
+// so the concatenated table has the principal inertias of the simple table
+#[test]
+fn concatenated_table_of_duplicated_columns_matches_simple_analysis() {
+    let simple = calculate_analysis_summary(&smoking_data(), &smoking_config(false)).unwrap();
+
+    let mut config = smoking_config(false);
+    config.main.col_target_vars = Some(vec!["smoking".to_string(), "smoking".to_string()]);
+    let summary = calculate_analysis_summary(&smoking_data(), &config).unwrap();
+
+    for (actual, expected) in summary.inertia.iter().zip(simple.inertia.iter()) {
+        assert!((actual - expected).abs() < 1e-12, "{} != {}", actual, expected);
+    }
+    assert!(summary.inertia[simple.inertia.len()..].iter().all(|&i| i.abs() < 1e-12));
+}
+
+// Malinvaud's test: N times the residual inertia on (I - 1 - k)(J - 1 - k) degrees of freedom
+#[test]
+fn smoking_malinvaud_test() {
+    let tests = calculate_dimension_tests(&smoking_data(), &smoking_config(false)).unwrap();
+
+    assert_eq!(tests.len(), 3);
+    assert_eq!(
+        tests
+            .iter()
+            .map(|t| t.df)
+            .collect::<Vec<_>>(),
+        vec![12, 6, 2]
+    );
+    // Overall chi-square of the table: 16.442, p = 0.172
+    assert!((tests[0].chi_square - 16.442).abs() < 1e-3);
+    assert!((tests[0].significance - 0.172).abs() < 1e-3);
+    assert!((tests[1].chi_square - 193.0 * (0.01002 + 0.00041)).abs() < 2e-3);
+    assert!((tests[2].chi_square - 193.0 * 0.00041).abs() < 2e-3);
+}
+
+#[test]
+fn seeded_permutation_test_matches_reference_values() {
+    let mut config = smoking_config(false);
+    config.statistics.permutation_test = true;
+    config.statistics.max_permutations = 199;
+    config.statistics.seed = Some(12345);
+
+    let test = perform_permutation_test(&smoking_data(), &config).unwrap();
+    assert_eq!(test.num_permutations, 199);
+    for (k, (p_value, critical_value)) in PERMUTATION_RESULTS.iter().enumerate() {
+        assert!((test.observed_inertia[k] - PRINCIPAL_INERTIAS[k]).abs() < 5e-6);
+        assert!((test.p_values[k] - p_value).abs() < 1e-12, "dimension {}", k + 1);
+        assert!((test.critical_values[k] - critical_value).abs() < 1e-10, "dimension {}", k + 1);
+    }
+}
+
+// Over many resamples of the smoking table the cell counts have the multinomial moments
+// E = N p and Var = N p (1 - p)
+#[test]
+fn multinomial_resampling_has_multinomial_moments() {
+    let cells: Vec<f64> = SMOKING.iter().flatten().copied().collect();
+    let total: f64 = cells.iter().sum();
+    let replicates = 4000;
+
+    let mut rng = create_rng(Some(2024));
+    let mut sums = vec![0.0; cells.len()];
+    let mut squares = vec![0.0; cells.len()];
+    for _ in 0..replicates {
+        let counts = sample_multinomial(&cells, total as u64, &mut rng);
+        assert_eq!(counts.iter().sum::<u64>(), total as u64);
+        for (k, &count) in counts.iter().enumerate() {
+            sums[k] += count as f64;
+            squares[k] += (count as f64) * (count as f64);
+        }
+    }
+
+    let n = replicates as f64;
+    for (k, &cell) in cells.iter().enumerate() {
+        let p = cell / total;
+        let expected_mean = total * p;
+        let expected_var = total * p * (1.0 - p);
+        let mean = sums[k] / n;
+        let var = (squares[k] - n * mean * mean) / (n - 1.0);
+        // Four standard errors of the mean and of the variance
+        assert!((mean - expected_mean).abs() < 4.0 * (expected_var / n).sqrt(), "cell {}", k);
+        assert!((var - expected_var).abs() < 4.0 * expected_var * (2.0 / (n - 1.0)).sqrt(), "cell {}", k);
+    }
+}
+
+#[test]
+fn canonical_analysis_and_constraint_test_match_reference_values() {
+    let mut config = smoking_config(false);
+    config.statistics.seed = Some(7);
+    config.canonical = Some(CanonicalConfig {
+        covariates: vec!["seniority".to_string(), "age".to_string()],
+        permutation_test: true,
+        max_permutations: Some(99),
+        ..Default::default()
+    });
+
+    let result = perform_canonical_analysis(&canonical_data(), &config).unwrap();
+    let (constrained, unconstrained, eigenvalues) = CANONICAL_INERTIAS;
+    assert!((result.total_inertia - (constrained + unconstrained)).abs() < 1e-12);
+    assert!((result.constrained_inertia - constrained).abs() < 1e-12);
+    assert!((result.unconstrained_inertia - unconstrained).abs() < 1e-12);
+    assert_eq!(result.eigenvalues.len(), 2);
+    for (actual, expected) in result.eigenvalues.iter().zip(eigenvalues.iter()) {
+        assert!((actual - expected).abs() < 1e-12, "{} != {}", actual, expected);
+    }
+
+    let test = result.permutation_test.expect("constraint permutation test");
+    let (pseudo_f, significance, first_axis_pseudo_f, first_axis_significance) = CONSTRAINT_TEST;
+    assert_eq!(test.num_permutations, 99);
+    assert!((test.pseudo_f - pseudo_f).abs() / pseudo_f < 1e-10);
+    assert!((test.significance - significance).abs() < 1e-12);
+    assert!((test.first_axis_pseudo_f - first_axis_pseudo_f).abs() / first_axis_pseudo_f < 1e-10);
+    assert!((test.first_axis_significance - first_axis_significance).abs() < 1e-12);
+}
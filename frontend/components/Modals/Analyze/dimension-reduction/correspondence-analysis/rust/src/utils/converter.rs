@@ -9,6 +9,10 @@ use crate::models::result::{
     AnalysisSummary,
     PointsAnalysis,
     ConfidencePoints,
+    DimensionTest,
+    PermutationTest,
+    BootstrapRegions,
+    CanonicalAnalysis,
 };
 
 // Konversi dari String error ke JsValue untuk interaksi WASM
@@ -36,6 +40,11 @@ struct FormatResult {
     column_points: Option<PointsAnalysis>,
     confidence_row_points: Option<ConfidencePoints>,
     confidence_column_points: Option<ConfidencePoints>,
+    dimension_tests: Option<Vec<DimensionTest>>,
+    permutation_test: Option<PermutationTest>,
+    bootstrap_row_regions: Option<BootstrapRegions>,
+    bootstrap_column_regions: Option<BootstrapRegions>,
+    canonical_analysis: Option<CanonicalAnalysis>,
 }
 
 impl FormatResult {
@@ -49,6 +58,11 @@ impl FormatResult {
             column_points: result.column_points.clone(),
             confidence_row_points: result.confidence_row_points.clone(),
             confidence_column_points: result.confidence_column_points.clone(),
+            dimension_tests: result.dimension_tests.clone(),
+            permutation_test: result.permutation_test.clone(),
+            bootstrap_row_regions: result.bootstrap_row_regions.clone(),
+            bootstrap_column_regions: result.bootstrap_column_regions.clone(),
+            canonical_analysis: result.canonical_analysis.clone(),
         }
     }
 }
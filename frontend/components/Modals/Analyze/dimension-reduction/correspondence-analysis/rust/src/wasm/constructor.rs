@@ -5,6 +5,7 @@ use crate::models::{
     data::{ AnalysisData, DataRecord, VariableDefinition },
     result::CorrespondenceAnalysisResult,
 };
+use crate::stats::core::{ get_burt_variables, get_target_variables };
 use crate::utils::{ converter::string_to_js_error, error::ErrorCollector };
 use crate::wasm::function;
 
@@ -115,24 +116,29 @@ impl CorrespondenceAnalysis {
         };
 
         // Validate configuration
-        if
-            config.main.row_target_var.is_none() ||
-            config.main.row_target_var.as_ref().unwrap().is_empty()
-        {
-            let msg =
-                "Row target variable must be selected for correspondence analysis".to_string();
-            error_collector.add_error("config.validation.row_target_var", &msg);
-            return Err(string_to_js_error(msg));
-        }
+        let (row_vars, col_vars) = get_target_variables(&config);
 
-        if
-            config.main.col_target_var.is_none() ||
-            config.main.col_target_var.as_ref().unwrap().is_empty()
-        {
-            let msg =
-                "Column target variable must be selected for correspondence analysis".to_string();
-            error_collector.add_error("config.validation.col_target_var", &msg);
-            return Err(string_to_js_error(msg));
+        if config.main.burt {
+            if get_burt_variables(&config).len() < 2 {
+                let msg =
+                    "At least two variables must be selected for a Burt matrix".to_string();
+                error_collector.add_error("config.validation.burt", &msg);
+                return Err(string_to_js_error(msg));
+            }
+        } else {
+            if row_vars.is_empty() {
+                let msg =
+                    "Row target variable must be selected for correspondence analysis".to_string();
+                error_collector.add_error("config.validation.row_target_var", &msg);
+                return Err(string_to_js_error(msg));
+            }
+
+            if col_vars.is_empty() {
+                let msg =
+                    "Column target variable must be selected for correspondence analysis".to_string();
+                error_collector.add_error("config.validation.col_target_var", &msg);
+                return Err(string_to_js_error(msg));
+            }
         }
 
         // Store data
@@ -27,17 +27,16 @@ pub fn run_analysis(
 
     // Step 1: Create correspondence table
     executed_functions.push("create_correspondence_table".to_string());
-    let mut correspondence_table = None;
-    match core::create_correspondence_table(data, config) {
+    let correspondence_table = match core::create_correspondence_table(data, config) {
         Ok(table) => {
             web_sys::console::log_1(&format!("Correspondence table: {:?}", table).into());
-            correspondence_table = Some(table);
+            Some(table)
         }
         Err(e) => {
             error_collector.add_error("create_correspondence_table", &e);
             return Err(string_to_js_error(e));
         }
-    }
+    };
 
     // Step 2: Calculate row profiles if requested
     let mut row_profiles = None;
@@ -70,57 +69,54 @@ pub fn run_analysis(
     }
 
     // Step 4: Calculate chi-square distances if model uses chi-square
-    let mut chi_square_distances = None;
     if config.model.chi_square {
         executed_functions.push("calculate_chi_square_distances".to_string());
-        match core::calculate_chi_square_distances(data, config) {
-            Ok(distances) => {
-                chi_square_distances = Some(distances);
-            }
-            Err(e) => {
-                error_collector.add_error("calculate_chi_square_distances", &e);
-                // Continue execution despite errors for non-critical functions
-            }
+        if let Err(e) = core::calculate_chi_square_distances(data, config) {
+            error_collector.add_error("calculate_chi_square_distances", &e);
+            // Continue execution despite errors for non-critical functions
         }
     }
 
     // Step 5: Calculate euclidean distances if model uses euclidean
-    let mut euclidean_distances = None;
     if config.model.euclidean {
         executed_functions.push("calculate_euclidean_distances".to_string());
-        match core::calculate_euclidean_distances(data, config) {
-            Ok(distances) => {
-                euclidean_distances = Some(distances);
-            }
-            Err(e) => {
-                error_collector.add_error("calculate_euclidean_distances", &e);
-                // Continue execution despite errors for non-critical functions
-            }
+        if let Err(e) = core::calculate_euclidean_distances(data, config) {
+            error_collector.add_error("calculate_euclidean_distances", &e);
+            // Continue execution despite errors for non-critical functions
         }
     }
 
     // Step 6: Apply normalization based on selected method
     executed_functions.push("apply_normalization".to_string());
-    let normalization_result = match core::apply_normalization(data, config) {
-        Ok(result) => result,
-        Err(e) => {
-            error_collector.add_error("apply_normalization", &e);
-            return Err(string_to_js_error(e));
-        }
-    };
+    if let Err(e) = core::apply_normalization(data, config) {
+        error_collector.add_error("apply_normalization", &e);
+        return Err(string_to_js_error(e));
+    }
 
     // Step 7: Calculate analysis summary with singular values
     executed_functions.push("calculate_analysis_summary".to_string());
-    let mut analysis_summary = None;
-    match core::calculate_analysis_summary(data, config) {
+    let analysis_summary = match core::calculate_analysis_summary(data, config) {
         Ok(summary) => {
             web_sys::console::log_1(&format!("Analysis summary: {:?}", summary).into());
-            analysis_summary = Some(summary);
+            Some(summary)
         }
         Err(e) => {
             error_collector.add_error("calculate_analysis_summary", &e);
             return Err(string_to_js_error(e));
         }
+    };
+
+    // Step 7b: Malinvaud's test for the number of dimensions
+    executed_functions.push("calculate_dimension_tests".to_string());
+    let mut dimension_tests = None;
+    match core::calculate_dimension_tests(data, config) {
+        Ok(tests) => {
+            dimension_tests = Some(tests);
+        }
+        Err(e) => {
+            error_collector.add_error("calculate_dimension_tests", &e);
+            // Continue execution despite errors for non-critical functions
+        }
     }
 
     // Step 8: Calculate row points overview if requested
@@ -198,6 +194,38 @@ pub fn run_analysis(
         }
     }
 
+    // Step 12b: Bootstrap confidence regions for row and column points
+    let mut bootstrap_row_regions = None;
+    let mut bootstrap_column_regions = None;
+    if config.statistics.bootstrap {
+        executed_functions.push("calculate_bootstrap_regions".to_string());
+        match core::calculate_bootstrap_regions(data, config) {
+            Ok((row_regions, column_regions)) => {
+                bootstrap_row_regions = Some(row_regions);
+                bootstrap_column_regions = Some(column_regions);
+            }
+            Err(e) => {
+                error_collector.add_error("calculate_bootstrap_regions", &e);
+                // Continue execution despite errors for non-critical functions
+            }
+        }
+    }
+
+    // Step 12c: Canonical correspondence analysis with covariate constraints
+    let mut canonical_analysis = None;
+    if config.canonical.as_ref().is_some_and(|c| !c.covariates.is_empty()) {
+        executed_functions.push("perform_canonical_analysis".to_string());
+        match core::perform_canonical_analysis(data, config) {
+            Ok(result) => {
+                canonical_analysis = Some(result);
+            }
+            Err(e) => {
+                error_collector.add_error("perform_canonical_analysis", &e);
+                // Continue execution despite errors for non-critical functions
+            }
+        }
+    }
+
     // Step 13: Generate plots if requested
     if config.plots.biplot || config.plots.row_pts || config.plots.col_pts {
         executed_functions.push("generate_scatter_plots".to_string());
@@ -232,6 +260,11 @@ pub fn run_analysis(
         column_points,
         confidence_row_points,
         confidence_column_points,
+        dimension_tests,
+        permutation_test: permutation_test_result,
+        bootstrap_row_regions,
+        bootstrap_column_regions,
+        canonical_analysis,
     };
 
     Ok(Some(result))
@@ -9,18 +9,24 @@ export async function analyzeCorrespondence({
     variables,
     meta,
 }: CorrespondenceAnalysisType) {
-    const RowVariable = configData.main.RowTargetVar
+    const RowVariable = configData.main.RowTargetVars?.length
+        ? configData.main.RowTargetVars
+        : configData.main.RowTargetVar
         ? [configData.main.RowTargetVar]
         : [];
-    const ColVariable = configData.main.ColTargetVar
+    const ColVariable = configData.main.ColTargetVars?.length
+        ? configData.main.ColTargetVars
+        : configData.main.ColTargetVar
         ? [configData.main.ColTargetVar]
         : [];
     const WeightVariable = meta.weight ? [meta.weight] : [];
+    // CCA covariates travel with the row records
+    const CovariateVariable = configData.canonical?.Covariates ?? [];
 
     const slicedDataForRow = getSlicedData({
         dataVariables: dataVariables,
         variables: variables,
-        selectedVariables: RowVariable,
+        selectedVariables: [...RowVariable, ...CovariateVariable],
     });
 
     const slicedDataForCol = getSlicedData({
@@ -35,7 +41,10 @@ export async function analyzeCorrespondence({
         selectedVariables: WeightVariable,
     });
 
-    const varDefsForRow = getVarDefs(variables, RowVariable);
+    const varDefsForRow = getVarDefs(variables, [
+        ...RowVariable,
+        ...CovariateVariable,
+    ]);
     const varDefsForCol = getVarDefs(variables, ColVariable);
     const varDefsForWeight = getVarDefs(variables, WeightVariable);
 
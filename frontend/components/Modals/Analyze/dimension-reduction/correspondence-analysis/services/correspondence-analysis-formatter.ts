@@ -2,6 +2,12 @@
 import {formatDisplayNumber} from "@/hooks/useFormatter";
 import {ResultJson, Row, Table} from "@/types/Table";
 
+// Tables built from several variables label each category as "variable: category"
+function categoryHeader(labels: string[] | undefined, index: number): string {
+    const label = labels?.[index];
+    return label && label.includes(": ") ? label : (index + 1).toString();
+}
+
 export function transformCorrespondenceResult(
     data: any,
     rowVariable: string = "Row",
@@ -21,7 +27,10 @@ export function transformCorrespondenceResult(
                 ...Array.from(
                     { length: data.correspondence_table.data[0]?.length || 0 },
                     (_, i) => ({
-                        header: (i + 1).toString(),
+                        header: categoryHeader(
+                            data.correspondence_table.col_labels,
+                            i
+                        ),
                         key: `col_${i + 1}`,
                     })
                 ),
@@ -33,7 +42,9 @@ export function transformCorrespondenceResult(
         if (data.correspondence_table.data.length) {
             for (let i = 0; i < data.correspondence_table.data.length; i++) {
                 const rowData: any = {
-                    rowHeader: [(i + 1).toString()],
+                    rowHeader: [
+                        categoryHeader(data.correspondence_table.row_labels, i),
+                    ],
                     ...Object.fromEntries(
                         data.correspondence_table.data[i].map((val: number, j: number) => [
                             `col_${j + 1}`,
@@ -271,6 +282,52 @@ export function transformCorrespondenceResult(
         resultJson.tables.push(table);
     }
 
+    // 4a. Adjusted Inertia (Burt matrix)
+    if (data.summary?.adjusted_inertia) {
+        const adjusted = data.summary.adjusted_inertia;
+        const table: Table = {
+            key: "adjusted_inertia",
+            title: "Adjusted Inertia",
+            columnHeaders: [
+                { header: "Dimension", key: "dimension" },
+                { header: "Adjusted Inertia", key: "inertia" },
+                {
+                    header: "Proportion of Inertia",
+                    key: "proportion",
+                    children: [
+                        { header: "Accounted for", key: "accounted_for" },
+                        { header: "Cumulative", key: "cumulative" },
+                    ],
+                },
+            ],
+            rows: [],
+        };
+
+        // Dimensions without adjusted inertia carry no association
+        for (let i = 0; i < adjusted.inertia.length; i++) {
+            if (adjusted.inertia[i] <= 0) {
+                continue;
+            }
+            table.rows.push({
+                rowHeader: [(i + 1).toString()],
+                inertia: formatDisplayNumber(adjusted.inertia[i]),
+                accounted_for: formatDisplayNumber(
+                    adjusted.proportion_of_inertia.accounted_for[i]
+                ),
+                cumulative: formatDisplayNumber(
+                    adjusted.proportion_of_inertia.cumulative[i]
+                ),
+            });
+        }
+
+        table.rows.push({
+            rowHeader: ["Total"],
+            inertia: formatDisplayNumber(adjusted.total),
+        });
+
+        resultJson.tables.push(table);
+    }
+
     // 5. Row Points Analysis
     if (data.row_points) {
         const table: Table = {
@@ -593,5 +650,266 @@ export function transformCorrespondenceResult(
         resultJson.tables.push(table);
     }
 
+    // 9. Malinvaud's Test of Dimensions
+    if (data.dimension_tests?.length) {
+        const table: Table = {
+            key: "dimension_tests",
+            title: "Malinvaud's Test of Dimensions",
+            columnHeaders: [
+                { header: "Dimensions Retained", key: "dimension" },
+                { header: "Chi Square", key: "chi_square" },
+                { header: "df", key: "df" },
+                { header: "Sig.", key: "sig" },
+            ],
+            rows: [],
+        };
+
+        // Each row tests the residual inertia after the preceding dimensions
+        for (const test of data.dimension_tests) {
+            table.rows.push({
+                rowHeader: [(test.dimension - 1).toString()],
+                chi_square: formatDisplayNumber(test.chi_square),
+                df: test.df,
+                sig: formatDisplayNumber(test.significance),
+            });
+        }
+
+        resultJson.tables.push(table);
+    }
+
+    // 10. Permutation Test
+    if (data.permutation_test) {
+        const permutation = data.permutation_test;
+        const table: Table = {
+            key: "permutation_test",
+            title: `Permutation Test (${permutation.num_permutations} Permutations)`,
+            columnHeaders: [
+                { header: "Dimension", key: "dimension" },
+                { header: "Inertia", key: "inertia" },
+                { header: "95th Percentile", key: "critical_value" },
+                { header: "Sig.", key: "sig" },
+            ],
+            rows: [],
+        };
+
+        for (let i = 0; i < permutation.observed_inertia.length; i++) {
+            table.rows.push({
+                rowHeader: [(i + 1).toString()],
+                inertia: formatDisplayNumber(permutation.observed_inertia[i]),
+                critical_value: formatDisplayNumber(
+                    permutation.critical_values[i]
+                ),
+                sig: formatDisplayNumber(permutation.p_values[i]),
+            });
+        }
+
+        resultJson.tables.push(table);
+    }
+
+    // 11. Bootstrap Confidence Regions
+    const bootstrapRegions: [string, string, any][] = [
+        ["bootstrap_row_regions", rowVariable, data.bootstrap_row_regions],
+        ["bootstrap_column_regions", colVariable, data.bootstrap_column_regions],
+    ];
+    for (const [key, variable, regions] of bootstrapRegions) {
+        if (!regions) {
+            continue;
+        }
+
+        const table: Table = {
+            key,
+            title: `Bootstrap Confidence Regions for ${variable} (${regions.num_samples} Samples, ${regions.confidence_level * 100}%)`,
+            columnHeaders: [
+                { header: variable, key: "var" },
+                {
+                    header: "Score in Dimension",
+                    key: "score",
+                    children: [
+                        { header: `${regions.dimension1}`, key: "x" },
+                        { header: `${regions.dimension2}`, key: "y" },
+                    ],
+                },
+                {
+                    header: "Confidence Ellipse",
+                    key: "ellipse",
+                    children: [
+                        { header: "Center 1", key: "center_x" },
+                        { header: "Center 2", key: "center_y" },
+                        { header: "Semi-major Axis", key: "semi_major" },
+                        { header: "Semi-minor Axis", key: "semi_minor" },
+                        { header: "Angle", key: "angle" },
+                    ],
+                },
+                { header: "Convex Hull Vertices", key: "hull" },
+            ],
+            rows: [],
+        };
+
+        for (const point of regions.points) {
+            table.rows.push({
+                rowHeader: [point.label],
+                x: formatDisplayNumber(point.x),
+                y: formatDisplayNumber(point.y),
+                center_x: formatDisplayNumber(point.ellipse.center_x),
+                center_y: formatDisplayNumber(point.ellipse.center_y),
+                semi_major: formatDisplayNumber(point.ellipse.semi_major_axis),
+                semi_minor: formatDisplayNumber(point.ellipse.semi_minor_axis),
+                angle: formatDisplayNumber(point.ellipse.angle),
+                hull: point.convex_hull.length,
+            });
+        }
+
+        resultJson.tables.push(table);
+    }
+
+    // 12. Canonical Correspondence Analysis
+    if (data.canonical_analysis) {
+        const canonical = data.canonical_analysis;
+        const dimensionHeaders = canonical.eigenvalues.map(
+            (_: number, i: number) => ({
+                header: `${i + 1}`,
+                key: `dim${i + 1}`,
+            })
+        );
+
+        // Constrained axes, then the split of the total inertia
+        const summary: Table = {
+            key: "canonical_summary",
+            title: "Canonical Summary",
+            columnHeaders: [
+                { header: "Dimension", key: "dimension" },
+                { header: "Inertia", key: "inertia" },
+                {
+                    header: "Proportion of Inertia",
+                    key: "proportion",
+                    children: [
+                        { header: "Accounted for", key: "accounted_for" },
+                        { header: "Cumulative", key: "cumulative" },
+                    ],
+                },
+                {
+                    header: "Species-Environment Correlation",
+                    key: "correlation",
+                },
+            ],
+            rows: [],
+        };
+
+        for (let i = 0; i < canonical.eigenvalues.length; i++) {
+            summary.rows.push({
+                rowHeader: [(i + 1).toString()],
+                inertia: formatDisplayNumber(canonical.eigenvalues[i]),
+                accounted_for: formatDisplayNumber(
+                    canonical.proportion_of_inertia.accounted_for[i]
+                ),
+                cumulative: formatDisplayNumber(
+                    canonical.proportion_of_inertia.cumulative[i]
+                ),
+                correlation: formatDisplayNumber(
+                    canonical.species_environment_correlations[i]
+                ),
+            });
+        }
+
+        const split: [string, number][] = [
+            ["Constrained", canonical.constrained_inertia],
+            ["Unconstrained", canonical.unconstrained_inertia],
+            ["Total", canonical.total_inertia],
+        ];
+        for (const [label, inertia] of split) {
+            summary.rows.push({
+                rowHeader: [label],
+                inertia: formatDisplayNumber(inertia),
+                accounted_for: formatDisplayNumber(
+                    inertia / canonical.total_inertia
+                ),
+            });
+        }
+
+        resultJson.tables.push(summary);
+
+        // Column points on the constrained axes
+        const columnScores: Table = {
+            key: "canonical_column_scores",
+            title: "Canonical Column Scores",
+            columnHeaders: [
+                { header: colVariable, key: "var" },
+                {
+                    header: "Score in Dimension",
+                    key: "score",
+                    children: dimensionHeaders,
+                },
+            ],
+            rows: [],
+        };
+
+        for (let j = 0; j < canonical.col_labels.length; j++) {
+            const rowData: any = { rowHeader: [canonical.col_labels[j]] };
+            canonical.column_scores[j].forEach((score: number, s: number) => {
+                rowData[`dim${s + 1}`] = formatDisplayNumber(score);
+            });
+            columnScores.rows.push(rowData);
+        }
+
+        resultJson.tables.push(columnScores);
+
+        // Covariate arrows for the biplot
+        const biplot: Table = {
+            key: "canonical_covariate_scores",
+            title: "Covariate Biplot Scores",
+            columnHeaders: [
+                { header: "Covariate", key: "var" },
+                {
+                    header: "Score in Dimension",
+                    key: "score",
+                    children: dimensionHeaders,
+                },
+            ],
+            rows: [],
+        };
+
+        for (let k = 0; k < canonical.covariate_labels.length; k++) {
+            const rowData: any = { rowHeader: [canonical.covariate_labels[k]] };
+            canonical.covariate_biplot_scores[k].forEach(
+                (score: number, s: number) => {
+                    rowData[`dim${s + 1}`] = formatDisplayNumber(score);
+                }
+            );
+            biplot.rows.push(rowData);
+        }
+
+        resultJson.tables.push(biplot);
+
+        // Permutation test of the constraints
+        if (canonical.permutation_test) {
+            const test = canonical.permutation_test;
+            const table: Table = {
+                key: "canonical_permutation_test",
+                title: `Permutation Test of the Constraints (${test.num_permutations} Permutations)`,
+                columnHeaders: [
+                    { header: "Test", key: "test" },
+                    { header: "Pseudo-F", key: "pseudo_f" },
+                    { header: "Sig.", key: "sig" },
+                ],
+                rows: [
+                    {
+                        rowHeader: ["All Constrained Axes"],
+                        pseudo_f: formatDisplayNumber(test.pseudo_f),
+                        sig: formatDisplayNumber(test.significance),
+                    },
+                    {
+                        rowHeader: ["First Constrained Axis"],
+                        pseudo_f: formatDisplayNumber(test.first_axis_pseudo_f),
+                        sig: formatDisplayNumber(
+                            test.first_axis_significance
+                        ),
+                    },
+                ],
+            };
+
+            resultJson.tables.push(table);
+        }
+    }
+
     return resultJson;
 }
@@ -3,6 +3,9 @@ import React from "react";
 export type CorrespondenceMainType = {
     RowTargetVar: string | null;
     ColTargetVar: string | null;
+    RowTargetVars: string[] | null;
+    ColTargetVars: string[] | null;
+    Burt: boolean;
 };
 
 export type CorrespondenceDialogProps = {
@@ -13,9 +16,10 @@ export type CorrespondenceDialogProps = {
     setIsModelOpen: React.Dispatch<React.SetStateAction<boolean>>;
     setIsStatisticsOpen: React.Dispatch<React.SetStateAction<boolean>>;
     setIsPlotsOpen: React.Dispatch<React.SetStateAction<boolean>>;
+    setIsCanonicalOpen: React.Dispatch<React.SetStateAction<boolean>>;
     updateFormData: (
         field: keyof CorrespondenceMainType,
-        value: string | null
+        value: string[] | string | boolean | null
     ) => void;
     data: CorrespondenceMainType;
     globalVariables: string[];
@@ -101,6 +105,10 @@ export type CorrespondenceStatisticsType = {
     ColProfile: boolean;
     RowPoints: boolean;
     ColPoints: boolean;
+    Seed: number | null;
+    Bootstrap: boolean;
+    BootstrapSamples: number | null;
+    ConfidenceLevel: number | null;
 };
 
 export type CorrespondenceStatisticsProps = {
@@ -137,6 +145,24 @@ export type CorrespondencePlotsProps = {
     data: CorrespondencePlotsType;
 };
 
+export type CorrespondenceCanonicalType = {
+    Covariates: string[];
+    Dimensions: number | null;
+    PermutationTest: boolean;
+    MaxPermutations: number | null;
+};
+
+export type CorrespondenceCanonicalProps = {
+    isCanonicalOpen: boolean;
+    setIsCanonicalOpen: React.Dispatch<React.SetStateAction<boolean>>;
+    updateFormData: (
+        field: keyof CorrespondenceCanonicalType,
+        value: string[] | number | boolean | null
+    ) => void;
+    data: CorrespondenceCanonicalType;
+    globalVariables: string[];
+};
+
 export type CorrespondenceType = {
     main: CorrespondenceMainType;
     defineRangeRow: CorrespondenceDefineRangeRowType;
@@ -144,6 +170,7 @@ export type CorrespondenceType = {
     model: CorrespondenceModelType;
     statistics: CorrespondenceStatisticsType;
     plots: CorrespondencePlotsType;
+    canonical?: CorrespondenceCanonicalType | null;
 };
 
 export type CorrespondenceContainerProps = {
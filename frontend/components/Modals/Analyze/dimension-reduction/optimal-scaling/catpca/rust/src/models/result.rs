@@ -23,6 +23,12 @@ pub struct CATPCAResult {
     pub category_points: Option<CategoryPoints>,
     #[serde(rename = "biplot")]
     pub biplot: Option<Biplot>,
+    #[serde(rename = "object_plots")]
+    pub object_plots: Option<ObjectPlots>,
+    #[serde(rename = "category_plots")]
+    pub category_plots: Option<CategoryPlots>,
+    #[serde(rename = "loading_plot")]
+    pub loading_plot: Option<PlotSeries>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -156,3 +162,32 @@ pub struct BiplotPoint {
     pub label: String,
     pub cluster: Option<String>,
 }
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PlotSeries {
+    pub title: String,
+    #[serde(rename = "x_label")]
+    pub x_label: String,
+    #[serde(rename = "y_label")]
+    pub y_label: String,
+    pub points: Vec<BiplotPoint>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ObjectPlots {
+    #[serde(rename = "object_points")]
+    pub object_points: Vec<PlotSeries>,
+    pub triplot: Option<PlotSeries>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CategoryPlots {
+    #[serde(rename = "category_points")]
+    pub category_points: Vec<PlotSeries>,
+    #[serde(rename = "joint_category_points")]
+    pub joint_category_points: Option<PlotSeries>,
+    pub transformations: Vec<PlotSeries>,
+    pub residuals: Vec<PlotSeries>,
+    #[serde(rename = "projected_centroids")]
+    pub projected_centroids: Vec<PlotSeries>,
+}
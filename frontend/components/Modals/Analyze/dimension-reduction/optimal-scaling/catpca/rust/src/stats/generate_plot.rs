@@ -1,45 +1,426 @@
+use nalgebra::DMatrix;
+
 use crate::models::{
     config::CATPCAConfig,
-    data::AnalysisData,
-    result::{ Biplot, BiplotPoint },
+    data::{ AnalysisData, DataRecord, DataValue },
+    result::{ Biplot, BiplotPoint, CategoryPlots, ObjectPlots, ObjectScores, PlotSeries },
 };
 
-use super::core::{ calculate_category_points, calculate_object_scores, create_data_matrix };
+use super::core::{ calculate_loading, create_data_matrix, optimal_scale_variable };
+
+/// Generate object plots from the object scores of the analysis
+pub fn generate_object_plots(
+    data: &AnalysisData,
+    config: &CATPCAConfig,
+    object_scores_result: &ObjectScores
+) -> Result<ObjectPlots, String> {
+    let (dim_x, dim_y) = plot_dimensions(config)?;
+
+    let object_point = |i: usize, label: String, cluster: Option<String>| {
+        let scores = &object_scores_result.dimensions[i];
+        BiplotPoint {
+            x: scores[dim_x],
+            y: scores[dim_y],
+            point_type: "object".to_string(),
+            label,
+            cluster,
+        }
+    };
+
+    let mut object_points = Vec::new();
+
+    if config.object_plots.object_points {
+        // Objects labeled by case number (also the default when no labeling is selected)
+        if
+            config.object_plots.label_obj_label_by_case_number ||
+            !config.object_plots.label_obj_label_by_var
+        {
+            let points = object_scores_result.clusters
+                .iter()
+                .enumerate()
+                .map(|(i, case_number)| object_point(i, case_number.clone(), None))
+                .collect();
+
+            object_points.push(PlotSeries {
+                title: "Object Points Labeled by Case Number".to_string(),
+                x_label: dimension_label(dim_x),
+                y_label: dimension_label(dim_y),
+                points,
+            });
+        }
+
+        // Objects labeled by the categories of the selected variables
+        if config.object_plots.label_obj_label_by_var {
+            for var_name in config.object_plots.label_obj_selected_vars.iter().flatten() {
+                let labels = case_labels(data, var_name);
+
+                let points = (0..object_scores_result.dimensions.len())
+                    .map(|i| {
+                        let label = labels
+                            .get(i)
+                            .cloned()
+                            .unwrap_or_else(|| "Missing".to_string());
+                        object_point(i, label.clone(), Some(label))
+                    })
+                    .collect();
+
+                object_points.push(PlotSeries {
+                    title: format!("Object Points Labeled by {}", var_name),
+                    x_label: dimension_label(dim_x),
+                    y_label: dimension_label(dim_y),
+                    points,
+                });
+            }
+        }
+    }
+
+    // Triplot: objects, component loadings and category centroids
+    let mut triplot = None;
+    if config.object_plots.triplot {
+        let components = fit_plot_components(data, config, object_scores_result)?;
+        let variables = select_variables(
+            &components.variables,
+            config.object_plots.bt_include_all_vars,
+            &config.object_plots.bt_selected_vars
+        );
 
-/// Generate object plots
-pub fn generate_object_plots(data: &AnalysisData, config: &CATPCAConfig) -> Result<(), String> {
-    // This function would generate and save plots
-    // In a pure backend implementation, this might prepare data for frontend rendering
-    // or save plot files
+        let mut points: Vec<BiplotPoint> = (0..object_scores_result.dimensions.len())
+            .map(|i| object_point(i, object_scores_result.clusters[i].clone(), None))
+            .collect();
 
-    // For this implementation, we'll return success without actually creating plots
-    Ok(())
+        for var_name in &variables {
+            if let Some(var_idx) = components.variables.iter().position(|v| v == var_name) {
+                points.push(BiplotPoint {
+                    x: components.loadings[var_idx][dim_x],
+                    y: components.loadings[var_idx][dim_y],
+                    point_type: "loading".to_string(),
+                    label: var_name.clone(),
+                    cluster: None,
+                });
+
+                for (cat_idx, centroid) in components.centroids[var_idx].iter().enumerate() {
+                    points.push(BiplotPoint {
+                        x: centroid[dim_x],
+                        y: centroid[dim_y],
+                        point_type: format!("centroid_{}", var_name),
+                        label: components.category_labels[var_idx][cat_idx].clone(),
+                        cluster: Some(var_name.clone()),
+                    });
+                }
+            }
+        }
+
+        triplot = Some(PlotSeries {
+            title: "Triplot".to_string(),
+            x_label: dimension_label(dim_x),
+            y_label: dimension_label(dim_y),
+            points,
+        });
+    }
+
+    Ok(ObjectPlots {
+        object_points,
+        triplot,
+    })
 }
 
-/// Generate biplot
-pub fn generate_biplot(data: &AnalysisData, config: &CATPCAConfig) -> Result<Biplot, String> {
-    // Get analysis variables
-    let analysis_vars = match &config.main.analysis_vars {
-        Some(vars) => vars,
-        None => {
-            return Err("No analysis variables specified".to_string());
+/// Generate category, joint category, transformation, residual and projected centroid plots
+pub fn generate_category_plots(
+    data: &AnalysisData,
+    config: &CATPCAConfig,
+    object_scores_result: &ObjectScores
+) -> Result<CategoryPlots, String> {
+    let (dim_x, dim_y) = plot_dimensions(config)?;
+    let components = fit_plot_components(data, config, object_scores_result)?;
+    let plots_config = &config.category_plots;
+
+    // Category points: centroid coordinates and vector coordinates of each category
+    let mut category_points = Vec::new();
+    for var_name in plots_config.cat_plots_var.iter().flatten() {
+        if let Some(var_idx) = components.variables.iter().position(|v| v == var_name) {
+            let mut points = Vec::new();
+
+            for (cat_idx, centroid) in components.centroids[var_idx].iter().enumerate() {
+                let label = components.category_labels[var_idx][cat_idx].clone();
+                let quant = components.quantifications[var_idx][cat_idx];
+
+                points.push(BiplotPoint {
+                    x: centroid[dim_x],
+                    y: centroid[dim_y],
+                    point_type: "centroid".to_string(),
+                    label: label.clone(),
+                    cluster: Some(var_name.clone()),
+                });
+                points.push(BiplotPoint {
+                    x: quant * components.loadings[var_idx][dim_x],
+                    y: quant * components.loadings[var_idx][dim_y],
+                    point_type: "vector".to_string(),
+                    label,
+                    cluster: Some(var_name.clone()),
+                });
+            }
+
+            category_points.push(PlotSeries {
+                title: format!("Category Points of {}", var_name),
+                x_label: dimension_label(dim_x),
+                y_label: dimension_label(dim_y),
+                points,
+            });
         }
+    }
+
+    // Joint plot of category points: centroids of all selected variables together
+    let joint_vars: Vec<&String> = plots_config.joint_cat_plots_var.iter().flatten().collect();
+    let joint_category_points = if joint_vars.is_empty() {
+        None
+    } else {
+        let mut points = Vec::new();
+        for var_name in joint_vars {
+            if let Some(var_idx) = components.variables.iter().position(|v| v == var_name) {
+                for (cat_idx, centroid) in components.centroids[var_idx].iter().enumerate() {
+                    points.push(BiplotPoint {
+                        x: centroid[dim_x],
+                        y: centroid[dim_y],
+                        point_type: format!("centroid_{}", var_name),
+                        label: components.category_labels[var_idx][cat_idx].clone(),
+                        cluster: Some(var_name.clone()),
+                    });
+                }
+            }
+        }
+
+        Some(PlotSeries {
+            title: "Joint Plot of Category Points".to_string(),
+            x_label: dimension_label(dim_x),
+            y_label: dimension_label(dim_y),
+            points,
+        })
     };
 
-    let dimensions = config.main.dimensions as usize;
+    // Transformation plots: quantifications against the original categories,
+    // one plot per dimension for multiple nominal variables
+    let mut transformations = Vec::new();
+    let mut residuals = Vec::new();
+    for var_name in plots_config.trans_plots_var.iter().flatten() {
+        let var_idx = match components.variables.iter().position(|v| v == var_name) {
+            Some(idx) => idx,
+            None => {
+                continue;
+            }
+        };
 
-    // Create data matrix
-    let (data_matrix, row_indices, category_mappings) = create_data_matrix(data, analysis_vars);
+        let mut order: Vec<usize> = (0..components.category_labels[var_idx].len()).collect();
+        order.sort_by(|&a, &b| {
+            components.category_values[var_idx][a]
+                .partial_cmp(&components.category_values[var_idx][b])
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
 
-    if data_matrix.nrows() == 0 || data_matrix.ncols() == 0 {
-        return Err("No valid data for analysis".to_string());
+        if config.define_scale.multiple_nominal {
+            let num_dims = (plots_config.dimensions_for_multi_nom.max(1) as usize).min(
+                components.dimensions
+            );
+
+            for dim in 0..num_dims {
+                let points = order
+                    .iter()
+                    .map(|&cat_idx| BiplotPoint {
+                        x: components.category_values[var_idx][cat_idx],
+                        y: components.centroids[var_idx][cat_idx][dim],
+                        point_type: "transformation".to_string(),
+                        label: components.category_labels[var_idx][cat_idx].clone(),
+                        cluster: Some(var_name.clone()),
+                    })
+                    .collect();
+
+                transformations.push(PlotSeries {
+                    title: format!("Transformation of {} ({})", var_name, dimension_label(dim)),
+                    x_label: "Categories".to_string(),
+                    y_label: "Quantifications".to_string(),
+                    points,
+                });
+            }
+        } else {
+            let points = order
+                .iter()
+                .map(|&cat_idx| BiplotPoint {
+                    x: components.category_values[var_idx][cat_idx],
+                    y: components.quantifications[var_idx][cat_idx],
+                    point_type: "transformation".to_string(),
+                    label: components.category_labels[var_idx][cat_idx].clone(),
+                    cluster: Some(var_name.clone()),
+                })
+                .collect();
+
+            transformations.push(PlotSeries {
+                title: format!("Transformation of {}", var_name),
+                x_label: "Categories".to_string(),
+                y_label: "Quantifications".to_string(),
+                points,
+            });
+        }
+
+        // Residuals: transformed variable minus its approximation from the object scores
+        if plots_config.incl_resid_plots {
+            let points = (0..components.object_scores.nrows())
+                .map(|i| {
+                    let approximation: f64 = (0..components.dimensions)
+                        .map(|d| components.object_scores[(i, d)] * components.loadings[var_idx][d])
+                        .sum();
+                    BiplotPoint {
+                        x: approximation,
+                        y: components.transformed[(i, var_idx)] - approximation,
+                        point_type: "residual".to_string(),
+                        label: (i + 1).to_string(),
+                        cluster: Some(var_name.clone()),
+                    }
+                })
+                .collect();
+
+            residuals.push(PlotSeries {
+                title: format!("Residuals for {}", var_name),
+                x_label: "Approximation".to_string(),
+                y_label: "Residual".to_string(),
+                points,
+            });
+        }
+    }
+
+    // Projected centroids: category centroids of one variable projected onto the
+    // component loading vector of another
+    let mut projected_centroids = Vec::new();
+    for of_var in plots_config.prj_centroids_of_var.iter().flatten() {
+        for onto_var in plots_config.prj_centroids_onto_var.iter().flatten() {
+            let (of_idx, onto_idx) = match
+                (
+                    components.variables.iter().position(|v| v == of_var),
+                    components.variables.iter().position(|v| v == onto_var),
+                )
+            {
+                (Some(of_idx), Some(onto_idx)) if of_idx != onto_idx => (of_idx, onto_idx),
+                _ => {
+                    continue;
+                }
+            };
+
+            let loading = &components.loadings[onto_idx];
+            let norm = loading
+                .iter()
+                .map(|l| l * l)
+                .sum::<f64>()
+                .sqrt();
+            if norm <= 0.0 {
+                continue;
+            }
+
+            let points = components.centroids[of_idx]
+                .iter()
+                .enumerate()
+                .map(|(cat_idx, centroid)| {
+                    let projection: f64 =
+                        centroid
+                            .iter()
+                            .zip(loading.iter())
+                            .map(|(c, l)| c * l)
+                            .sum::<f64>() / norm;
+                    BiplotPoint {
+                        x: projection,
+                        y: 0.0,
+                        point_type: "projected_centroid".to_string(),
+                        label: components.category_labels[of_idx][cat_idx].clone(),
+                        cluster: Some(of_var.clone()),
+                    }
+                })
+                .collect();
+
+            projected_centroids.push(PlotSeries {
+                title: format!("Projected Centroids of {} onto {}", of_var, onto_var),
+                x_label: format!("Projection onto {}", onto_var),
+                y_label: String::new(),
+                points,
+            });
+        }
     }
 
-    // Calculate object scores
-    let object_scores_result = calculate_object_scores(data, config)?;
+    Ok(CategoryPlots {
+        category_points,
+        joint_category_points,
+        transformations,
+        residuals,
+        projected_centroids,
+    })
+}
 
-    // Calculate category points
-    let category_points_result = calculate_category_points(data, config)?;
+/// Generate component loading plot, optionally with category centroids
+pub fn generate_loading_plot(
+    data: &AnalysisData,
+    config: &CATPCAConfig,
+    object_scores_result: &ObjectScores
+) -> Result<PlotSeries, String> {
+    let (dim_x, dim_y) = plot_dimensions(config)?;
+    let components = fit_plot_components(data, config, object_scores_result)?;
+    let loading_config = &config.loading_plots;
+
+    let mut points = Vec::new();
+
+    if loading_config.display_comp_loadings {
+        let variables = select_variables(
+            &components.variables,
+            loading_config.loading_include_all_vars,
+            &loading_config.loading_selected_vars
+        );
+
+        for var_name in &variables {
+            if let Some(var_idx) = components.variables.iter().position(|v| v == var_name) {
+                points.push(BiplotPoint {
+                    x: components.loadings[var_idx][dim_x],
+                    y: components.loadings[var_idx][dim_y],
+                    point_type: "loading".to_string(),
+                    label: var_name.clone(),
+                    cluster: None,
+                });
+            }
+        }
+    }
+
+    if loading_config.include_centroids {
+        let variables = select_variables(
+            &components.variables,
+            loading_config.include_centroids_include_all_vars,
+            &loading_config.include_centroids_selected_vars
+        );
+
+        for var_name in &variables {
+            if let Some(var_idx) = components.variables.iter().position(|v| v == var_name) {
+                for (cat_idx, centroid) in components.centroids[var_idx].iter().enumerate() {
+                    points.push(BiplotPoint {
+                        x: centroid[dim_x],
+                        y: centroid[dim_y],
+                        point_type: format!("centroid_{}", var_name),
+                        label: components.category_labels[var_idx][cat_idx].clone(),
+                        cluster: Some(var_name.clone()),
+                    });
+                }
+            }
+        }
+    }
+
+    Ok(PlotSeries {
+        title: "Component Loadings".to_string(),
+        x_label: dimension_label(dim_x),
+        y_label: dimension_label(dim_y),
+        points,
+    })
+}
+
+/// Generate biplot of object points and category centroids from the object scores of the analysis
+pub fn generate_biplot(
+    data: &AnalysisData,
+    config: &CATPCAConfig,
+    object_scores_result: &ObjectScores
+) -> Result<Biplot, String> {
+    let (dim_x, dim_y) = plot_dimensions(config)?;
+    let components = fit_plot_components(data, config, object_scores_result)?;
 
     // Combine into biplot points
     let mut points = Vec::new();
@@ -47,30 +428,25 @@ pub fn generate_biplot(data: &AnalysisData, config: &CATPCAConfig) -> Result<Bip
 
     // Add object points
     for (i, cluster) in object_scores_result.clusters.iter().enumerate() {
-        if
-            i < object_scores_result.dimensions.len() &&
-            object_scores_result.dimensions[i].len() >= 2
-        {
-            points.push(BiplotPoint {
-                x: object_scores_result.dimensions[i][0],
-                y: object_scores_result.dimensions[i][1],
-                point_type: "object".to_string(),
-                label: cluster.clone(),
-                cluster: Some(cluster.clone()),
-            });
-        }
+        points.push(BiplotPoint {
+            x: object_scores_result.dimensions[i][dim_x],
+            y: object_scores_result.dimensions[i][dim_y],
+            point_type: "object".to_string(),
+            label: cluster.clone(),
+            cluster: Some(cluster.clone()),
+        });
     }
 
     // Add category centroids
-    for (var_name, centroids) in &category_points_result.centroid_coordinates {
+    for (var_idx, var_name) in components.variables.iter().enumerate() {
         centroid_types.push(var_name.clone());
 
-        for centroid in centroids {
+        for (cat_idx, centroid) in components.centroids[var_idx].iter().enumerate() {
             points.push(BiplotPoint {
-                x: centroid.x,
-                y: centroid.y,
+                x: centroid[dim_x],
+                y: centroid[dim_y],
                 point_type: format!("centroid_{}", var_name),
-                label: centroid.category.clone(),
+                label: components.category_labels[var_idx][cat_idx].clone(),
                 cluster: None,
             });
         }
@@ -81,3 +457,189 @@ pub fn generate_biplot(data: &AnalysisData, config: &CATPCAConfig) -> Result<Bip
         centroid_types,
     })
 }
+
+/// Quantities shared by all plots, fitted from the object scores of the analysis
+struct PlotComponents {
+    variables: Vec<String>,
+    dimensions: usize,
+    object_scores: DMatrix<f64>,
+    transformed: DMatrix<f64>,
+    quantifications: Vec<Vec<f64>>,
+    centroids: Vec<Vec<Vec<f64>>>,
+    loadings: Vec<Vec<f64>>,
+    category_labels: Vec<Vec<String>>,
+    category_values: Vec<Vec<f64>>,
+}
+
+fn fit_plot_components(
+    data: &AnalysisData,
+    config: &CATPCAConfig,
+    object_scores_result: &ObjectScores
+) -> Result<PlotComponents, String> {
+    // Get analysis variables
+    let analysis_vars = match &config.main.analysis_vars {
+        Some(vars) => vars,
+        None => {
+            return Err("No analysis variables specified".to_string());
+        }
+    };
+
+    let dimensions = config.main.dimensions as usize;
+
+    // Create data matrix
+    let (data_matrix, _, category_mappings) = create_data_matrix(data, analysis_vars);
+
+    if data_matrix.nrows() == 0 || data_matrix.ncols() == 0 {
+        return Err("No valid data for analysis".to_string());
+    }
+
+    // Object scores of the analysis, so that every plot shows the same solution
+    if
+        object_scores_result.dimensions.len() != data_matrix.nrows() ||
+        object_scores_result.dimensions.iter().any(|scores| scores.len() < dimensions)
+    {
+        return Err("Object scores do not match the analysis data".to_string());
+    }
+    let object_scores = DMatrix::from_fn(data_matrix.nrows(), dimensions, |i, d| {
+        object_scores_result.dimensions[i][d]
+    });
+
+    // Quantify every variable
+    let mut quantifications = Vec::with_capacity(analysis_vars.len());
+    let mut centroids = Vec::with_capacity(analysis_vars.len());
+
+    for (j, mapping) in category_mappings.iter().enumerate() {
+        let (quant, centroid) = optimal_scale_variable(
+            &data_matrix,
+            j,
+            mapping,
+            config,
+            dimensions,
+            &object_scores
+        );
+        quantifications.push(quant);
+        centroids.push(centroid);
+    }
+
+    // Transformed variables
+    let mut transformed = DMatrix::zeros(data_matrix.nrows(), data_matrix.ncols());
+
+    for i in 0..data_matrix.nrows() {
+        for j in 0..data_matrix.ncols() {
+            let val_str = data_matrix[(i, j)].to_string();
+            if let Some(&cat_idx) = category_mappings[j].get(&val_str) {
+                if cat_idx < quantifications[j].len() {
+                    transformed[(i, j)] = quantifications[j][cat_idx];
+                }
+            }
+        }
+    }
+
+    // Component loadings
+    let loadings = (0..analysis_vars.len())
+        .map(|j| {
+            (0..dimensions).map(|d| calculate_loading(&transformed, j, &object_scores, d)).collect()
+        })
+        .collect();
+
+    // Category labels and values ordered by internal category index
+    let mut category_labels = Vec::with_capacity(analysis_vars.len());
+    let mut category_values = Vec::with_capacity(analysis_vars.len());
+
+    for mapping in &category_mappings {
+        let mut labels = vec![String::new(); mapping.len()];
+        let mut values = vec![0.0; mapping.len()];
+
+        for (val_str, &idx) in mapping {
+            labels[idx] = val_str.clone();
+            values[idx] = val_str.parse::<f64>().unwrap_or(0.0);
+        }
+
+        category_labels.push(labels);
+        category_values.push(values);
+    }
+
+    Ok(PlotComponents {
+        variables: analysis_vars.clone(),
+        dimensions,
+        object_scores,
+        transformed,
+        quantifications,
+        centroids,
+        loadings,
+        category_labels,
+        category_values,
+    })
+}
+
+/// Zero-based dimensions shown on the horizontal and vertical axes
+fn plot_dimensions(config: &CATPCAConfig) -> Result<(usize, usize), String> {
+    let dimensions = config.main.dimensions as usize;
+    if dimensions < 2 {
+        return Err("Plots require at least two dimensions".to_string());
+    }
+
+    if !config.options.plot_dim_restrict {
+        return Ok((0, 1));
+    }
+
+    let lo = (config.options.plot_dim_lo_dim.unwrap_or(1).max(1) as usize) - 1;
+    let hi = (config.options.plot_dim_hi_dim.unwrap_or(2).max(1) as usize) - 1;
+
+    if lo >= hi || hi >= dimensions {
+        return Err(
+            format!("Plot dimensions must satisfy 1 <= lowest < highest <= {}", dimensions)
+        );
+    }
+
+    Ok((lo, hi))
+}
+
+fn dimension_label(dim: usize) -> String {
+    format!("Dimension {}", dim + 1)
+}
+
+/// Variables to include: all, or the selected subset in analysis order
+fn select_variables(
+    variables: &[String],
+    include_all: bool,
+    selected: &Option<Vec<String>>
+) -> Vec<String> {
+    match selected {
+        Some(vars) if !include_all && !vars.is_empty() => {
+            variables
+                .iter()
+                .filter(|v| vars.contains(v))
+                .cloned()
+                .collect()
+        }
+        _ => variables.to_vec(),
+    }
+}
+
+/// Category labels of a variable for every case, taken from the analysis or labeling data
+fn case_labels(data: &AnalysisData, var_name: &str) -> Vec<String> {
+    let labeling_records: Vec<&DataRecord> = data.labeling_data
+        .iter()
+        .flatten()
+        .flatten()
+        .collect();
+
+    data.analysis_data
+        .iter()
+        .flatten()
+        .enumerate()
+        .map(|(i, record)| {
+            let value = record.values
+                .get(var_name)
+                .or_else(|| labeling_records.get(i).and_then(|r| r.values.get(var_name)));
+
+            match value {
+                Some(DataValue::Number(num)) => num.to_string(),
+                Some(DataValue::Text(text)) => text.clone(),
+                Some(DataValue::Boolean(b)) => b.to_string(),
+                _ => "Missing".to_string(),
+            }
+        })
+        .collect()
+}
@@ -130,13 +130,25 @@ pub fn run_analysis(
         };
     }
 
-    // Step 9: Calculate object scores if requested
-    let mut object_scores = None;
-    if config.output.object_scores {
+    // Step 9: Calculate object scores once; the output table and every plot use this solution
+    let has_vars = |vars: &Option<Vec<String>>| vars.as_ref().is_some_and(|v| !v.is_empty());
+    let plots_requested =
+        config.object_plots.object_points ||
+        config.object_plots.biplot ||
+        config.object_plots.triplot ||
+        has_vars(&config.category_plots.cat_plots_var) ||
+        has_vars(&config.category_plots.joint_cat_plots_var) ||
+        has_vars(&config.category_plots.trans_plots_var) ||
+        has_vars(&config.category_plots.prj_centroids_of_var) ||
+        config.loading_plots.display_comp_loadings ||
+        config.loading_plots.include_centroids;
+
+    let mut solution = None;
+    if config.output.object_scores || plots_requested {
         executed_functions.push("calculate_object_scores".to_string());
         match core::calculate_object_scores(&filtered_data, config) {
             Ok(scores) => {
-                object_scores = Some(scores);
+                solution = Some(scores);
             }
             Err(e) => {
                 error_collector.add_error("calculate_object_scores", &e);
@@ -144,6 +156,7 @@ pub fn run_analysis(
             }
         };
     }
+    let object_scores = if config.output.object_scores { solution.clone() } else { None };
 
     // Step 10: Calculate component loadings if requested
     let mut component_loadings = None;
@@ -179,14 +192,21 @@ pub fn run_analysis(
     }
 
     // Step 12: Generate plots if requested
+    let mut object_plots = None;
     if
         config.object_plots.object_points ||
         config.object_plots.biplot ||
         config.object_plots.triplot
     {
         executed_functions.push("generate_object_plots".to_string());
-        match core::generate_object_plots(&filtered_data, config) {
-            Ok(_) => {}
+        let plot = solution
+            .as_ref()
+            .ok_or_else(|| "Object scores are not available".to_string())
+            .and_then(|scores| core::generate_object_plots(&filtered_data, config, scores));
+        match plot {
+            Ok(plots) => {
+                object_plots = Some(plots);
+            }
             Err(e) => {
                 error_collector.add_error("generate_object_plots", &e);
                 // Continue execution despite errors for non-critical functions
@@ -198,7 +218,11 @@ pub fn run_analysis(
     let mut biplot = None;
     if config.object_plots.biplot {
         executed_functions.push("generate_biplot".to_string());
-        match core::generate_biplot(&filtered_data, config) {
+        let plot = solution
+            .as_ref()
+            .ok_or_else(|| "Object scores are not available".to_string())
+            .and_then(|scores| core::generate_biplot(&filtered_data, config, scores));
+        match plot {
             Ok(plot) => {
                 biplot = Some(plot);
             }
@@ -209,6 +233,49 @@ pub fn run_analysis(
         };
     }
 
+    // Step 13a: Generate category plots if requested
+    let mut category_plots = None;
+    if
+        has_vars(&config.category_plots.cat_plots_var) ||
+        has_vars(&config.category_plots.joint_cat_plots_var) ||
+        has_vars(&config.category_plots.trans_plots_var) ||
+        has_vars(&config.category_plots.prj_centroids_of_var)
+    {
+        executed_functions.push("generate_category_plots".to_string());
+        let plot = solution
+            .as_ref()
+            .ok_or_else(|| "Object scores are not available".to_string())
+            .and_then(|scores| core::generate_category_plots(&filtered_data, config, scores));
+        match plot {
+            Ok(plots) => {
+                category_plots = Some(plots);
+            }
+            Err(e) => {
+                error_collector.add_error("generate_category_plots", &e);
+                // Continue execution despite errors for non-critical functions
+            }
+        };
+    }
+
+    // Step 13b: Generate loading plot if requested
+    let mut loading_plot = None;
+    if config.loading_plots.display_comp_loadings || config.loading_plots.include_centroids {
+        executed_functions.push("generate_loading_plot".to_string());
+        let plot = solution
+            .as_ref()
+            .ok_or_else(|| "Object scores are not available".to_string())
+            .and_then(|scores| core::generate_loading_plot(&filtered_data, config, scores));
+        match plot {
+            Ok(plot) => {
+                loading_plot = Some(plot);
+            }
+            Err(e) => {
+                error_collector.add_error("generate_loading_plot", &e);
+                // Continue execution despite errors for non-critical functions
+            }
+        };
+    }
+
     // Step 14: Bootstrap analysis if requested
     if config.bootstrap.perform_bt {
         executed_functions.push("perform_bootstrap_analysis".to_string());
@@ -250,6 +317,9 @@ pub fn run_analysis(
         component_loadings,
         category_points,
         biplot,
+        object_plots,
+        category_plots,
+        loading_plot,
     };
 
     Ok(Some(result))
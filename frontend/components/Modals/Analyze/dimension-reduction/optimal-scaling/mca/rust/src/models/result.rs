@@ -23,6 +23,8 @@ pub struct MCAResult {
     pub category_points: Option<CategoryPoints>,
     #[serde(rename = "object_points_labeled")]
     pub object_points_labeled: Option<HashMap<String, ObjectPointsLabeled>>,
+    #[serde(rename = "variable_plots")]
+    pub variable_plots: Option<VariablePlots>,
     #[serde(rename = "executed_functions")]
     pub executed_functions: Vec<String>,
 }
@@ -164,3 +166,37 @@ pub struct ObjectPointsLabeled {
     #[serde(rename = "dimension_coordinates")]
     pub dimension_coordinates: HashMap<String, Vec<f64>>,
 }
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct VariablePlots {
+    #[serde(rename = "category_plots")]
+    pub category_plots: Vec<PlotSeries>,
+    #[serde(rename = "joint_category_plot")]
+    pub joint_category_plot: Option<PlotSeries>,
+    #[serde(rename = "transformation_plots")]
+    pub transformation_plots: Vec<PlotSeries>,
+    #[serde(rename = "residual_plots")]
+    pub residual_plots: Vec<PlotSeries>,
+    #[serde(rename = "discrimination_plot")]
+    pub discrimination_plot: Option<PlotSeries>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PlotSeries {
+    pub title: String,
+    #[serde(rename = "x_label")]
+    pub x_label: String,
+    #[serde(rename = "y_label")]
+    pub y_label: String,
+    pub points: Vec<PlotPoint>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PlotPoint {
+    pub x: f64,
+    pub y: f64,
+    pub label: String,
+    #[serde(rename = "point_type")]
+    pub point_type: String,
+    pub group: Option<String>,
+}
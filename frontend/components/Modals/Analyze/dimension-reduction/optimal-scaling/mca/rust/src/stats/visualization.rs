@@ -1,17 +1,20 @@
 use std::collections::HashMap;
 
+use nalgebra::DVector;
+
 use crate::models::{
     config::MCAConfig,
-    data::{ AnalysisData, DataValue },
-    result::ObjectPointsLabeled,
+    data::{ AnalysisData, DataRecord, DataValue },
+    result::{ ObjectPointsLabeled, PlotPoint, PlotSeries, VariablePlots },
 };
 
 use super::core::{
     calculate_category_points,
-    calculate_discrimination_measures,
+    calculate_correlation_vectors,
     calculate_object_scores,
     collect_valid_categories,
     get_all_variables,
+    is_missing,
     parse_variable_weight,
 };
 
@@ -203,105 +206,292 @@ pub fn create_object_plots(
 }
 
 /// Create variable plots for MCA visualization
-pub fn create_variable_plots(data: &AnalysisData, config: &MCAConfig) -> Result<(), String> {
-    // This function prepares data for plotting, but the actual plot creation
-    // would typically be handled by the frontend or visualization library
-
-    // Check what types of plots are requested
-    let category_plots_requested =
-        config.variable_plots.cat_plots_var.is_some() &&
-        !config.variable_plots.cat_plots_var.as_ref().unwrap().is_empty();
-
-    let joint_category_plots_requested =
-        config.variable_plots.joint_cat_plots_var.is_some() &&
-        !config.variable_plots.joint_cat_plots_var.as_ref().unwrap().is_empty();
-
-    let transformation_plots_requested =
-        config.variable_plots.trans_plots_var.is_some() &&
-        !config.variable_plots.trans_plots_var.as_ref().unwrap().is_empty();
-
-    let discrimination_plots_requested =
-        config.variable_plots.disc_measures_var.is_some() &&
-        !config.variable_plots.disc_measures_var.as_ref().unwrap().is_empty();
-
-    // If no plots are requested, return early
-    if
-        !category_plots_requested &&
-        !joint_category_plots_requested &&
-        !transformation_plots_requested &&
-        !discrimination_plots_requested
-    {
-        return Ok(());
+pub fn create_variable_plots(
+    data: &AnalysisData,
+    config: &MCAConfig
+) -> Result<VariablePlots, String> {
+    // Use the first dataset for analysis
+    if data.analysis_data.is_empty() || data.analysis_data[0].is_empty() {
+        return Err("No data available for analysis".to_string());
     }
 
-    // Prepare data for requested plots
+    let dataset = &data.analysis_data[0];
+    let p_dims = config.main.dimensions as usize;
+    if p_dims < 2 {
+        return Err("Variable plots require at least two dimensions".to_string());
+    }
 
-    // For category plots, we need category points
-    if category_plots_requested || joint_category_plots_requested {
-        let mut cat_vars = Vec::new();
+    // Parse variable names from "variable (weight)" format
+    let parse_vars = |vars: &Option<Vec<String>>| -> Vec<String> {
+        vars.iter()
+            .flatten()
+            .map(|v| parse_variable_weight(v).0)
+            .collect()
+    };
 
-        if category_plots_requested {
-            // Parse variable name from "variable (weight)" format
-            cat_vars.extend(
-                config.variable_plots.cat_plots_var
-                    .as_ref()
-                    .unwrap()
-                    .iter()
+    let cat_vars = parse_vars(&config.variable_plots.cat_plots_var);
+    let joint_vars = parse_vars(&config.variable_plots.joint_cat_plots_var);
+    let trans_vars = parse_vars(&config.variable_plots.trans_plots_var);
+    let disc_vars = if config.variable_plots.use_all_vars {
+        match &config.main.analysis_vars {
+            Some(vars) if !vars.is_empty() => {
+                vars.iter()
                     .map(|v| parse_variable_weight(v).0)
-                    .collect::<Vec<String>>()
-            );
+                    .collect()
+            }
+            _ => get_all_variables(data),
         }
+    } else {
+        parse_vars(&config.variable_plots.disc_measures_var)
+    };
 
-        if joint_category_plots_requested {
-            // Parse variable name from "variable (weight)" format
-            cat_vars.extend(
-                config.variable_plots.joint_cat_plots_var
-                    .as_ref()
-                    .unwrap()
-                    .iter()
-                    .map(|v| parse_variable_weight(v).0)
-                    .collect::<Vec<String>>()
-            );
+    // All plots are derived from a single set of object scores so they agree with each other
+    let object_scores_result = calculate_object_scores(data, config)?;
+    let n_cases = dataset.len();
+    let mut scores = vec![vec![0.0; p_dims]; n_cases];
+
+    for (dim, dim_name) in object_scores_result.dimensions.iter().enumerate().take(p_dims) {
+        if let Some(dim_scores) = object_scores_result.scores.get(dim_name) {
+            for (i, &score) in dim_scores.iter().enumerate().take(n_cases) {
+                scores[i][dim] = score;
+            }
         }
+    }
 
-        // Get category points for these variables
-        let category_points = calculate_category_points(data, config)?;
+    let mut centroid_cache: HashMap<String, VariableCentroids> = HashMap::new();
+    let mut centroids_of = |var_name: &str| -> VariableCentroids {
+        centroid_cache
+            .entry(var_name.to_string())
+            .or_insert_with(|| calculate_variable_centroids(dataset, var_name, &scores, p_dims))
+            .clone()
+    };
 
-        // The data is now prepared and can be passed to a plotting library
-        // For this Rust implementation, we'll just return success
+    // Category plots: centroids of the categories of each variable
+    let mut category_plots = Vec::new();
+    for var_name in &cat_vars {
+        let centroids = centroids_of(var_name);
+        category_plots.push(PlotSeries {
+            title: format!("Category Points of {}", var_name),
+            x_label: "Dimension 1".to_string(),
+            y_label: "Dimension 2".to_string(),
+            points: centroid_points(&centroids, var_name),
+        });
     }
 
-    // For transformation plots, we need original and transformed values
-    if transformation_plots_requested {
-        // Get variables for transformation plots
-        let trans_vars: Vec<String> = config.variable_plots.trans_plots_var
-            .as_ref()
-            .unwrap()
-            .iter()
-            .map(|v| parse_variable_weight(v).0)
-            .collect();
+    // Joint plot of category points
+    let joint_category_plot = if joint_vars.is_empty() {
+        None
+    } else {
+        let mut points = Vec::new();
+        for var_name in &joint_vars {
+            points.extend(centroid_points(&centroids_of(var_name), var_name));
+        }
+
+        Some(PlotSeries {
+            title: "Joint Plot of Category Points".to_string(),
+            x_label: "Dimension 1".to_string(),
+            y_label: "Dimension 2".to_string(),
+            points,
+        })
+    };
 
-        // Calculate transformed values (would be used in actual plot implementation)
-        let object_scores = calculate_object_scores(data, config)?;
+    // Transformation plots (multiple nominal: one plot per requested dimension) and residuals
+    let trans_dims = (config.variable_plots.dimensions_for_multi_nom.max(1) as usize).min(p_dims);
+    let mut transformation_plots = Vec::new();
+    let mut residual_plots = Vec::new();
 
-        // The data is now prepared and can be passed to a plotting library
+    for var_name in &trans_vars {
+        let centroids = centroids_of(var_name);
+
+        for dim in 0..trans_dims {
+            let points = centroids.labels
+                .iter()
+                .enumerate()
+                .map(|(cat_idx, label)| PlotPoint {
+                    x: (cat_idx + 1) as f64,
+                    y: centroids.coordinates[cat_idx][dim],
+                    label: label.clone(),
+                    point_type: "transformation".to_string(),
+                    group: Some(var_name.clone()),
+                })
+                .collect();
+
+            transformation_plots.push(PlotSeries {
+                title: format!("Transformation of {} (Dimension {})", var_name, dim + 1),
+                x_label: "Categories".to_string(),
+                y_label: "Quantifications".to_string(),
+                points,
+            });
+
+            // Residuals: quantified variable minus its approximation by the object scores
+            if config.variable_plots.incl_resid_plots {
+                let points = centroids.case_categories
+                    .iter()
+                    .zip(scores.iter())
+                    .enumerate()
+                    .filter_map(|(case_idx, (cat, case_scores))| {
+                        cat.map(|cat_idx| {
+                            let approximation = case_scores[dim];
+                            PlotPoint {
+                                x: approximation,
+                                y: centroids.coordinates[cat_idx][dim] - approximation,
+                                label: (case_idx + 1).to_string(),
+                                point_type: "residual".to_string(),
+                                group: Some(centroids.labels[cat_idx].clone()),
+                            }
+                        })
+                    })
+                    .collect();
+
+                residual_plots.push(PlotSeries {
+                    title: format!("Residuals for {} (Dimension {})", var_name, dim + 1),
+                    x_label: "Approximation".to_string(),
+                    y_label: "Residual".to_string(),
+                    points,
+                });
+            }
+        }
     }
 
-    // For discrimination measure plots, we need discrimination measures
-    if discrimination_plots_requested {
-        // Parse variable name from "variable (weight)" format
-        let disc_vars: Vec<String> = config.variable_plots.disc_measures_var
-            .as_ref()
-            .unwrap()
+    // Discrimination measures plot: one point per variable
+    let discrimination_plot = if disc_vars.is_empty() || !config.variable_plots.display_plot {
+        None
+    } else {
+        let points = disc_vars
             .iter()
-            .map(|v| parse_variable_weight(v).0)
+            .map(|var_name| {
+                let centroids = centroids_of(var_name);
+                let measures: Vec<f64> = (0..2)
+                    .map(|dim| discrimination_measure(&centroids, &scores, dim))
+                    .collect();
+
+                PlotPoint {
+                    x: measures[0],
+                    y: measures[1],
+                    label: var_name.clone(),
+                    point_type: "discrimination".to_string(),
+                    group: None,
+                }
+            })
             .collect();
 
-        // Calculate discrimination measures
-        let discrimination_measures = calculate_discrimination_measures(data, config)?;
+        Some(PlotSeries {
+            title: "Discrimination Measures".to_string(),
+            x_label: "Dimension 1".to_string(),
+            y_label: "Dimension 2".to_string(),
+            points,
+        })
+    };
+
+    Ok(VariablePlots {
+        category_plots,
+        joint_category_plot,
+        transformation_plots,
+        residual_plots,
+        discrimination_plot,
+    })
+}
+
+/// Category labels, centroid coordinates and the category of every case for one variable
+#[derive(Clone)]
+struct VariableCentroids {
+    labels: Vec<String>,
+    coordinates: Vec<Vec<f64>>,
+    case_categories: Vec<Option<usize>>,
+}
+
+fn calculate_variable_centroids(
+    dataset: &[DataRecord],
+    var_name: &str,
+    scores: &[Vec<f64>],
+    p_dims: usize
+) -> VariableCentroids {
+    let categories = collect_valid_categories(dataset, var_name);
 
-        // The data is now prepared and can be passed to a plotting library
+    let labels: Vec<String> = categories
+        .iter()
+        .map(|cat| {
+            match cat {
+                DataValue::Number(num) => num.to_string(),
+                DataValue::Text(text) => text.clone(),
+                DataValue::Boolean(b) => b.to_string(),
+                DataValue::Null => "Null".to_string(),
+            }
+        })
+        .collect();
+
+    let case_categories: Vec<Option<usize>> = dataset
+        .iter()
+        .map(|record| {
+            let value = record.values.get(var_name).filter(|v| !is_missing(v))?;
+            categories.iter().position(|cat| {
+                match (cat, value) {
+                    (DataValue::Number(a), DataValue::Number(b)) => (a - b).abs() < 1e-10,
+                    (DataValue::Text(a), DataValue::Text(b)) => a == b,
+                    (DataValue::Boolean(a), DataValue::Boolean(b)) => a == b,
+                    _ => false,
+                }
+            })
+        })
+        .collect();
+
+    let mut coordinates = vec![vec![0.0; p_dims]; categories.len()];
+    let mut counts = vec![0usize; categories.len()];
+
+    for (case_idx, cat) in case_categories.iter().enumerate() {
+        if let (Some(cat_idx), Some(case_scores)) = (cat, scores.get(case_idx)) {
+            for dim in 0..p_dims {
+                coordinates[*cat_idx][dim] += case_scores[dim];
+            }
+            counts[*cat_idx] += 1;
+        }
+    }
+
+    for (coords, &count) in coordinates.iter_mut().zip(counts.iter()) {
+        if count > 0 {
+            for value in coords.iter_mut() {
+                *value /= count as f64;
+            }
+        }
+    }
+
+    VariableCentroids {
+        labels,
+        coordinates,
+        case_categories,
+    }
+}
+
+fn centroid_points(centroids: &VariableCentroids, var_name: &str) -> Vec<PlotPoint> {
+    centroids.labels
+        .iter()
+        .zip(centroids.coordinates.iter())
+        .map(|(label, coords)| PlotPoint {
+            x: coords[0],
+            y: coords[1],
+            label: label.clone(),
+            point_type: "category".to_string(),
+            group: Some(var_name.to_string()),
+        })
+        .collect()
+}
+
+/// Squared correlation between the object scores and the quantified variable in one dimension
+fn discrimination_measure(centroids: &VariableCentroids, scores: &[Vec<f64>], dim: usize) -> f64 {
+    let (scores_dim, transformed): (Vec<f64>, Vec<f64>) = centroids.case_categories
+        .iter()
+        .enumerate()
+        .filter_map(|(case_idx, cat)| {
+            cat.map(|cat_idx| (scores[case_idx][dim], centroids.coordinates[cat_idx][dim]))
+        })
+        .unzip();
+
+    if scores_dim.len() < 2 {
+        return 0.0;
     }
 
-    Ok(())
+    calculate_correlation_vectors(
+        &DVector::from_vec(scores_dim),
+        &DVector::from_vec(transformed)
+    ).map_or(0.0, |r| r * r)
 }
@@ -212,14 +212,20 @@ pub fn run_analysis(
     }
 
     // Step 12: Create variable plots if requested
+    let mut variable_plots = None;
     if
         !config.variable_plots.cat_plots_var.is_none() ||
         !config.variable_plots.joint_cat_plots_var.is_none() ||
-        !config.variable_plots.trans_plots_var.is_none()
+        !config.variable_plots.trans_plots_var.is_none() ||
+        (config.variable_plots.display_plot &&
+            (config.variable_plots.use_all_vars ||
+                config.variable_plots.disc_measures_var.is_some()))
     {
         executed_functions.push("create_variable_plots".to_string());
         match core::create_variable_plots(&filtered_data, config) {
-            Ok(_) => {}
+            Ok(plots) => {
+                variable_plots = Some(plots);
+            }
             Err(e) => {
                 error_collector.add_error("create_variable_plots", &e);
                 // Continue execution despite errors
@@ -251,6 +257,7 @@ pub fn run_analysis(
         discrimination_measures,
         category_points,
         object_points_labeled,
+        variable_plots,
         executed_functions,
     };
 
@@ -20,6 +20,10 @@ export const OptScaOveralsDefineRangeScaleDefault: OptScaOveralsDefineRangeScale
         SingleNominal: false,
         MultipleNominal: false,
         DiscreteNumeric: false,
+        SplineOrdinal: false,
+        SplineNominal: false,
+        Degree: 2,
+        InteriorKnots: 2,
     };
 
 export const OptScaOveralsDefineRangeDefault: OptScaOveralsDefineRangeType = {
@@ -44,6 +48,11 @@ export const OptScaOveralsOptionsDefault: OptScaOveralsOptionsType = {
     UseRandconf: false,
     MaxIter: 100,
     Conv: 0.00001,
+    NumStarts: 1,
+    Seed: null,
+    SetCorrelations: false,
+    PermutationTest: false,
+    NumPermutations: 100,
 };
 
 export const OptScaOveralsDefault: OptScaOveralsType = {
@@ -21,6 +21,9 @@ export const OptScaOveralsDefineRangeScale = ({
     const [defineRangeScaleState, setDefineRangeScaleState] =
         useState<OptScaOveralsDefineRangeScaleType>({ ...data });
     const [isContinueDisabled, setIsContinueDisabled] = useState(false);
+    const isSpline =
+        defineRangeScaleState.SplineOrdinal ||
+        defineRangeScaleState.SplineNominal;
 
     useEffect(() => {
         if (isDefineRangeScaleOpen) {
@@ -45,6 +48,8 @@ export const OptScaOveralsDefineRangeScale = ({
             SingleNominal: value === "SingleNominal",
             MultipleNominal: value === "MultipleNominal",
             DiscreteNumeric: value === "DiscreteNumeric",
+            SplineOrdinal: value === "SplineOrdinal",
+            SplineNominal: value === "SplineNominal",
         }));
     };
 
@@ -141,6 +146,10 @@ export const OptScaOveralsDefineRangeScale = ({
                                             ? "MultipleNominal"
                                             : defineRangeScaleState.DiscreteNumeric
                                             ? "DiscreteNumeric"
+                                            : defineRangeScaleState.SplineOrdinal
+                                            ? "SplineOrdinal"
+                                            : defineRangeScaleState.SplineNominal
+                                            ? "SplineNominal"
                                             : ""
                                     }
                                     onValueChange={handleMeasureGrp}
@@ -165,6 +174,15 @@ export const OptScaOveralsDefineRangeScale = ({
                                                     Single Nominal
                                                 </Label>
                                             </div>
+                                            <div className="flex items-center space-x-2">
+                                                <RadioGroupItem
+                                                    value="SplineOrdinal"
+                                                    id="SplineOrdinal"
+                                                />
+                                                <Label htmlFor="SplineOrdinal">
+                                                    Spline Ordinal
+                                                </Label>
+                                            </div>
                                         </div>
                                         <div className="flex flex-col gap-2">
                                             <div className="flex items-center space-x-2">
@@ -185,9 +203,64 @@ export const OptScaOveralsDefineRangeScale = ({
                                                     Discrete Numeric
                                                 </Label>
                                             </div>
+                                            <div className="flex items-center space-x-2">
+                                                <RadioGroupItem
+                                                    value="SplineNominal"
+                                                    id="SplineNominal"
+                                                />
+                                                <Label htmlFor="SplineNominal">
+                                                    Spline Nominal
+                                                </Label>
+                                            </div>
                                         </div>
                                     </div>
                                 </RadioGroup>
+                                <div className="grid grid-cols-2 gap-2">
+                                    <div className="flex items-center space-x-2">
+                                        <Label className="w-[75px]">Degree:</Label>
+                                        <div className="w-[75px]">
+                                            <Input
+                                                id="Degree"
+                                                type="number"
+                                                placeholder=""
+                                                disabled={!isSpline}
+                                                value={
+                                                    defineRangeScaleState.Degree ??
+                                                    ""
+                                                }
+                                                onChange={(e) =>
+                                                    handleChange(
+                                                        "Degree",
+                                                        Number(e.target.value)
+                                                    )
+                                                }
+                                            />
+                                        </div>
+                                    </div>
+                                    <div className="flex items-center space-x-2">
+                                        <Label className="w-[75px]">
+                                            Interior Knots:
+                                        </Label>
+                                        <div className="w-[75px]">
+                                            <Input
+                                                id="InteriorKnots"
+                                                type="number"
+                                                placeholder=""
+                                                disabled={!isSpline}
+                                                value={
+                                                    defineRangeScaleState.InteriorKnots ??
+                                                    ""
+                                                }
+                                                onChange={(e) =>
+                                                    handleChange(
+                                                        "InteriorKnots",
+                                                        Number(e.target.value)
+                                                    )
+                                                }
+                                            />
+                                        </div>
+                                    </div>
+                                </div>
                             </div>
                         </ResizablePanel>
                     </ResizablePanelGroup>
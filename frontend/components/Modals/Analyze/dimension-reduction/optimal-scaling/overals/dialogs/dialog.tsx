@@ -501,6 +501,18 @@ export const OptScaOveralsDialog = forwardRef<
                     measScale = "Multiple Nominal";
                 else if (defineRangeScaleData.DiscreteNumeric)
                     measScale = "Discrete Numeric";
+                else if (
+                    defineRangeScaleData.SplineOrdinal ||
+                    defineRangeScaleData.SplineNominal
+                )
+                    // Splines carry their degree and interior knots before the range
+                    measScale = `Spline ${
+                        defineRangeScaleData.SplineOrdinal
+                            ? "Ordinal"
+                            : "Nominal"
+                    } ${defineRangeScaleData.Degree ?? 2} ${
+                        defineRangeScaleData.InteriorKnots ?? 2
+                    }`;
 
                 // Update variable info
                 const newVariableInfo = {
@@ -1,6 +1,6 @@
 use serde::{ Deserialize, Serialize };
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct OVERALSAnalysisConfig {
     pub main: MainConfig,
     #[serde(rename = "defineRangeScale")]
@@ -20,6 +20,16 @@ pub struct MainConfig {
     pub dimensions: Option<i32>,
 }
 
+impl Default for MainConfig {
+    fn default() -> Self {
+        Self {
+            set_target_variable: None,
+            plots_target_variable: None,
+            dimensions: Some(2),
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct DefineRangeScaleConfig {
     #[serde(rename = "Minimum")]
@@ -34,6 +44,31 @@ pub struct DefineRangeScaleConfig {
     pub multiple_nominal: bool,
     #[serde(rename = "DiscreteNumeric")]
     pub discrete_numeric: bool,
+    #[serde(rename = "SplineOrdinal", default)]
+    pub spline_ordinal: bool,
+    #[serde(rename = "SplineNominal", default)]
+    pub spline_nominal: bool,
+    #[serde(rename = "Degree")]
+    pub degree: Option<i32>,
+    #[serde(rename = "InteriorKnots")]
+    pub interior_knots: Option<i32>,
+}
+
+impl Default for DefineRangeScaleConfig {
+    fn default() -> Self {
+        Self {
+            minimum: Some(1.0),
+            maximum: None,
+            ordinal: true,
+            single_nominal: false,
+            multiple_nominal: false,
+            discrete_numeric: false,
+            spline_ordinal: false,
+            spline_nominal: false,
+            degree: Some(2),
+            interior_knots: Some(2),
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -44,6 +79,15 @@ pub struct DefineRangeConfig {
     pub maximum: Option<f64>,
 }
 
+impl Default for DefineRangeConfig {
+    fn default() -> Self {
+        Self {
+            minimum: Some(1.0),
+            maximum: None,
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct OptionsConfig {
     #[serde(rename = "Freq")]
@@ -78,4 +122,42 @@ pub struct OptionsConfig {
     pub max_iter: Option<i32>,
     #[serde(rename = "Conv")]
     pub conv: Option<f64>,
+    #[serde(rename = "NumStarts")]
+    pub num_starts: Option<i32>,
+    #[serde(rename = "Seed")]
+    pub seed: Option<i64>,
+    #[serde(rename = "SetCorrelations", default)]
+    pub set_correlations: bool,
+    #[serde(rename = "PermutationTest", default)]
+    pub permutation_test: bool,
+    #[serde(rename = "NumPermutations")]
+    pub num_permutations: Option<i32>,
+}
+
+impl Default for OptionsConfig {
+    fn default() -> Self {
+        Self {
+            freq: true,
+            sing_mult: true,
+            centroid: true,
+            category_quant: true,
+            iter_history: false,
+            obj_score: false,
+            weight_compload: false,
+            categ_coord: false,
+            categ_centroid: false,
+            plot_obj_score: true,
+            trans: false,
+            compload: true,
+            save_objscore: false,
+            use_randconf: false,
+            max_iter: Some(100),
+            conv: Some(0.00001),
+            num_starts: Some(1),
+            seed: None,
+            set_correlations: false,
+            permutation_test: false,
+            num_permutations: Some(100),
+        }
+    }
 }
@@ -13,16 +13,23 @@ pub struct OVERALSAnalysisResult {
     pub fit_measures: Option<FitMeasures>,
     pub object_scores: Option<ObjectScores>,
     pub transformation_plots: Option<TransformationPlots>,
+    pub convergence_report: Option<ConvergenceReport>,
+    pub set_correlations: Option<SetCorrelations>,
+    pub fit_permutation_test: Option<FitPermutationTest>,
 }
 
 pub struct OVERALSResult {
     pub object_scores: Vec<Vec<f64>>,
+    pub initial_object_scores: Vec<Vec<f64>>,
     pub category_quantifications: HashMap<(usize, usize, usize), f64>,
     pub variable_weights: HashMap<(usize, usize), Vec<f64>>,
     pub category_values: HashMap<(usize, usize), Vec<usize>>,
     pub iteration_history: Vec<IterationStep>,
     pub final_loss: f64,
     pub dimensions: usize,
+    pub converged: bool,
+    pub best_start: usize,
+    pub start_summaries: Vec<StartSummary>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -45,6 +52,14 @@ pub enum ScalingLevel {
     Single,
     Multiple,
     Discrete,
+    SplineOrdinal {
+        degree: usize,
+        interior_knots: usize,
+    },
+    SplineNominal {
+        degree: usize,
+        interior_knots: usize,
+    },
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -136,3 +151,45 @@ pub struct SummaryAnalysis {
     pub eigenvalue: HashMap<String, f64>,
     pub fit: HashMap<String, f64>,
 }
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ConvergenceReport {
+    pub num_starts: usize,
+    pub best_start: usize,
+    pub converged: bool,
+    pub iterations: usize,
+    pub final_loss: f64,
+    pub final_fit: f64,
+    pub starts: Vec<StartSummary>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct StartSummary {
+    pub start: usize,
+    pub loss: f64,
+    pub fit: f64,
+    pub iterations: usize,
+    pub converged: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SetCorrelations {
+    pub canonical_correlation: HashMap<String, f64>,
+    pub set_pairs: Vec<SetPairCorrelation>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SetPairCorrelation {
+    pub first_set: String,
+    pub second_set: String,
+    pub dimension: Vec<f64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct FitPermutationTest {
+    pub num_permutations: usize,
+    pub observed_fit: f64,
+    pub mean_permuted_fit: f64,
+    pub max_permuted_fit: f64,
+    pub p_value: f64,
+}
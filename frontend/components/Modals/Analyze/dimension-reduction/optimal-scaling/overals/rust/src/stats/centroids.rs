@@ -58,17 +58,17 @@ pub fn calculate_centroids(
 
                     // Average object scores for this category
                     for &obj_idx in objects {
-                        for dim in 0..result.dimensions {
-                            if obj_idx < result.object_scores.len() {
-                                category_centroids[dim] += result.object_scores[obj_idx][dim];
+                        if let Some(scores) = result.object_scores.get(obj_idx) {
+                            for (centroid, score) in category_centroids.iter_mut().zip(scores) {
+                                *centroid += score;
                             }
                         }
                     }
 
                     // Normalize
                     let num_objects = objects.len() as f64;
-                    for dim in 0..result.dimensions {
-                        category_centroids[dim] /= num_objects;
+                    for centroid in category_centroids.iter_mut() {
+                        *centroid /= num_objects;
                     }
 
                     // Calculate projected centroids
@@ -76,7 +76,11 @@ pub fn calculate_centroids(
                     let mut projected_centroids = vec![0.0; result.dimensions];
 
                     match scaling_level {
-                        ScalingLevel::Single | ScalingLevel::Ordinal | ScalingLevel::Discrete => {
+                        ScalingLevel::Single |
+                        ScalingLevel::Ordinal |
+                        ScalingLevel::Discrete |
+                        ScalingLevel::SplineOrdinal { .. } |
+                        ScalingLevel::SplineNominal { .. } => {
                             if let Some(weights) = result.variable_weights.get(&(set_idx, var_idx)) {
                                 let cat_val = category.parse::<usize>().unwrap_or(0);
 
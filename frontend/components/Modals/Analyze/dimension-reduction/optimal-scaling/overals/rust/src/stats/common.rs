@@ -1,9 +1,11 @@
 use std::collections::HashMap;
 
+use rand_mt::Mt64;
+
 use crate::models::{
     config::OVERALSAnalysisConfig,
     data::{ AnalysisData, DataRecord, DataValue, VariableDefinition },
-    result::{ IterationStep, OVERALSResult, ScalingLevel },
+    result::{ IterationStep, OVERALSResult, ScalingLevel, StartSummary },
 };
 
 use super::core::{
@@ -14,14 +16,15 @@ use super::core::{
     update_numeric_quantifications,
     update_ordinal_quantifications,
     update_single_nominal_quantifications,
+    update_spline_quantifications,
 };
 
 // Helper function to get variable definition from 3D structure
-pub fn get_var_def<'a>(
-    data: &'a AnalysisData,
+pub fn get_var_def(
+    data: &AnalysisData,
     set_idx: usize,
     var_idx: usize
-) -> Result<&'a VariableDefinition, String> {
+) -> Result<&VariableDefinition, String> {
     // Check if set_idx is valid
     if set_idx >= data.set_target_data_defs.len() {
         return Err(format!("Invalid set index: {}", set_idx));
@@ -41,7 +44,7 @@ pub fn get_var_def<'a>(
 }
 
 // Helper function to get all variable definitions in a set (flattened)
-pub fn get_set_defs<'a>(data: &'a AnalysisData, set_idx: usize) -> Vec<&'a VariableDefinition> {
+pub fn get_set_defs(data: &AnalysisData, set_idx: usize) -> Vec<&VariableDefinition> {
     if set_idx >= data.set_target_data_defs.len() {
         return Vec::new();
     }
@@ -57,12 +60,6 @@ pub fn run_overals_algorithm(
     data: &AnalysisData,
     config: &OVERALSAnalysisConfig
 ) -> Result<OVERALSResult, String> {
-    // Get dimensions from configuration
-    let dimensions = config.main.dimensions.unwrap_or(2) as usize;
-    let max_iterations = config.options.max_iter.unwrap_or(100) as usize;
-    let convergence_criterion = config.options.conv.unwrap_or(0.00001);
-    let use_random_init = config.options.use_randconf;
-
     // Count number of cases
     let num_cases = data.set_target_data
         .first()
@@ -73,24 +70,93 @@ pub fn run_overals_algorithm(
         return Err("No cases found in data".to_string());
     }
 
-    // Initialize object scores
-    let mut object_scores = initialize_object_scores(dimensions, num_cases, use_random_init);
-    web_sys::console::log_1(&format!("Initial object scores: {:?}", object_scores).into());
-
-    // Center and normalize object scores
-    center_and_normalize_scores(&mut object_scores);
-
     // Discover categories for each variable
     let category_values = discover_categories(data);
     web_sys::console::log_1(&format!("Category values: {:?}", category_values).into());
 
+    let mut rng = create_rng(config.options.seed);
+    fit_overals_starts(data, config, &category_values, &mut rng)
+}
+
+/// Run every configured start and keep the solution with the lowest loss
+pub fn fit_overals_starts(
+    data: &AnalysisData,
+    config: &OVERALSAnalysisConfig,
+    category_values: &HashMap<(usize, usize), Vec<usize>>,
+    rng: &mut Mt64
+) -> Result<OVERALSResult, String> {
+    let dimensions = config.main.dimensions.unwrap_or(2) as usize;
+    let use_random_init = config.options.use_randconf;
+
+    let num_cases = data.set_target_data
+        .first()
+        .and_then(|set| set.first())
+        .map_or(0, |var| var.len());
+
+    // Multiple starts only make sense with random initial configurations
+    let num_starts = if use_random_init {
+        config.options.num_starts.unwrap_or(1).max(1) as usize
+    } else {
+        1
+    };
+
+    let mut best: Option<OVERALSResult> = None;
+    let mut start_summaries = Vec::with_capacity(num_starts);
+
+    for start in 0..num_starts {
+        // Initialize object scores
+        let mut object_scores = initialize_object_scores(
+            dimensions,
+            num_cases,
+            use_random_init,
+            rng
+        );
+
+        // Center and normalize object scores
+        center_and_normalize_scores(&mut object_scores);
+
+        let mut result = iterate_overals(data, config, object_scores, category_values.clone())?;
+        result.best_start = start + 1;
+
+        start_summaries.push(StartSummary {
+            start: start + 1,
+            loss: result.final_loss,
+            fit: (dimensions as f64) - result.final_loss,
+            iterations: result.iteration_history.len().saturating_sub(1),
+            converged: result.converged,
+        });
+
+        if best.as_ref().is_none_or(|b| result.final_loss < b.final_loss) {
+            best = Some(result);
+        }
+    }
+
+    let mut best = best.ok_or("No OVERALS solution was computed")?;
+    best.start_summaries = start_summaries;
+
+    Ok(best)
+}
+
+/// Alternating least squares iterations from a given initial configuration
+pub fn iterate_overals(
+    data: &AnalysisData,
+    config: &OVERALSAnalysisConfig,
+    mut object_scores: Vec<Vec<f64>>,
+    category_values: HashMap<(usize, usize), Vec<usize>>
+) -> Result<OVERALSResult, String> {
+    let dimensions = config.main.dimensions.unwrap_or(2) as usize;
+    let max_iterations = config.options.max_iter.unwrap_or(100) as usize;
+    let convergence_criterion = config.options.conv.unwrap_or(0.00001);
+    let num_cases = object_scores.len();
+    let mut converged = false;
+    let initial_object_scores = object_scores.clone();
+
     // Initialize category quantifications and variable weights
     let (mut variable_weights, mut category_quantifications) =
         initialize_weights_and_quantifications(&category_values, dimensions);
 
     // Iteration history
     let mut iteration_history = Vec::new();
-    let mut current_loss = f64::MAX;
 
     // Calculate initial loss
     let initial_loss = calculate_loss(
@@ -109,7 +175,7 @@ pub fn run_overals_algorithm(
         difference_from_previous: 0.0,
     });
 
-    current_loss = initial_loss;
+    let mut current_loss = initial_loss;
 
     // Main iteration loop
     for _ in 1..=max_iterations {
@@ -180,8 +246,27 @@ pub fn run_overals_algorithm(
                         update_numeric_quantifications(
                             set_idx,
                             var_idx,
+                            var_name,
+                            var_data,
+                            &object_scores,
+                            &v_kj,
+                            &mut category_quantifications,
+                            &mut variable_weights,
+                            &category_values
+                        );
+                    }
+                    ScalingLevel::SplineOrdinal { .. } | ScalingLevel::SplineNominal { .. } => {
+                        update_spline_quantifications(
+                            set_idx,
+                            var_idx,
+                            var_name,
+                            var_data,
+                            &object_scores,
+                            &v_kj,
+                            &mut category_quantifications,
+                            &mut variable_weights,
                             &category_values,
-                            &mut category_quantifications
+                            &scaling_level
                         );
                     }
                 }
@@ -222,6 +307,7 @@ pub fn run_overals_algorithm(
 
         // Check convergence
         if diff < convergence_criterion {
+            converged = true;
             break;
         }
 
@@ -233,12 +319,16 @@ pub fn run_overals_algorithm(
     // Return results
     Ok(OVERALSResult {
         object_scores,
+        initial_object_scores,
         category_quantifications,
         variable_weights,
         category_values,
         iteration_history,
         final_loss: current_loss,
         dimensions,
+        converged,
+        best_start: 1,
+        start_summaries: Vec::new(),
     })
 }
 
@@ -274,6 +364,7 @@ pub fn discover_categories(data: &AnalysisData) -> HashMap<(usize, usize), Vec<u
 }
 
 /// Calculate contribution of other variables in a set
+#[allow(clippy::too_many_arguments)]
 pub fn calculate_other_variables_contribution(
     set_idx: usize,
     var_idx: usize,
@@ -513,3 +604,64 @@ pub fn calculate_correlation(x: &[f64], y: &[f64]) -> f64 {
         0.0
     }
 }
+
+/// Default seed so that repeated runs of the algorithm (one per output table) agree
+pub const DEFAULT_SEED: i64 = 2000000;
+
+/// Create the random number generator used for random starts and permutations
+pub fn create_rng(seed: Option<i64>) -> Mt64 {
+    Mt64::new(seed.unwrap_or(DEFAULT_SEED) as u64)
+}
+
+/// Uniform draw in [0, 1) from the top 53 bits of the generator output
+pub fn random_unit(rng: &mut Mt64) -> f64 {
+    ((rng.next_u64() >> 11) as f64) / ((1u64 << 53) as f64)
+}
+
+/// In-place Fisher-Yates shuffle
+pub fn shuffle_values<T>(values: &mut [T], rng: &mut Mt64) {
+    for i in (1..values.len()).rev() {
+        let j = (rng.next_u64() % ((i + 1) as u64)) as usize;
+        values.swap(i, j);
+    }
+}
+
+/// Calculate the fitted scores of one set (sum of quantifications times weights)
+pub fn calculate_set_scores(
+    data: &AnalysisData,
+    result: &OVERALSResult,
+    set_idx: usize
+) -> Vec<Vec<f64>> {
+    let num_cases = result.object_scores.len();
+    let mut set_scores = vec![vec![0.0; result.dimensions]; num_cases];
+
+    if let Some(set_data) = data.set_target_data.get(set_idx) {
+        for (var_idx, var_data) in set_data.iter().enumerate() {
+            if let Ok(var_def) = get_var_def(data, set_idx, var_idx) {
+                let var_name = &var_def.name;
+
+                for (case_idx, record) in var_data.iter().enumerate().take(num_cases) {
+                    if let Some(DataValue::Number(num)) = record.values.get(var_name) {
+                        if *num > 0.0 && (*num - num.floor()).abs() < 1e-10 {
+                            let cat_val = *num as usize;
+                            if
+                                let (Some(&quant), Some(weights)) = (
+                                    result.category_quantifications.get(
+                                        &(set_idx, var_idx, cat_val)
+                                    ),
+                                    result.variable_weights.get(&(set_idx, var_idx)),
+                                )
+                            {
+                                for dim in 0..result.dimensions {
+                                    set_scores[case_idx][dim] += quant * weights[dim];
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    set_scores
+}
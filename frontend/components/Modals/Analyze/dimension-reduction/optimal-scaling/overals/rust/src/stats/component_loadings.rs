@@ -64,9 +64,7 @@ pub fn calculate_component_loadings(
                                     &(set_idx, var_idx, cat_val)
                                 )
                             {
-                                for dim in 0..result.dimensions {
-                                    quant_values[case_idx][dim] = quant;
-                                }
+                                quant_values[case_idx].fill(quant);
                             }
                         }
                         _ => {
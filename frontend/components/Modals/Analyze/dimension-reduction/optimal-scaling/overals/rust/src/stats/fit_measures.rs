@@ -3,10 +3,31 @@ use std::collections::HashMap;
 use crate::models::{
     config::OVERALSAnalysisConfig,
     data::{ AnalysisData, DataValue },
-    result::{ FitDimensions, FitMeasures, ScalingLevel, Variable },
+    result::{
+        FitDimensions,
+        FitMeasures,
+        FitPermutationTest,
+        OVERALSResult,
+        ScalingLevel,
+        SetCorrelations,
+        SetPairCorrelation,
+        Variable,
+    },
 };
 
-use super::core::{ determine_scaling_level, get_set_defs, run_overals_algorithm };
+use super::core::{
+    calculate_correlation,
+    calculate_set_scores,
+    create_rng,
+    determine_scaling_level,
+    discover_categories,
+    fit_overals_starts,
+    get_set_defs,
+    iterate_overals,
+    run_overals_algorithm,
+    shuffle_values,
+    summarize_overals_result,
+};
 
 /// Calculate fit measures for OVERALS analysis
 /// Calculate fit measures for OVERALS analysis
@@ -53,12 +74,12 @@ pub fn calculate_fit_measures(
             for (case_idx, record) in var_data.iter().enumerate() {
                 if let Some(DataValue::Number(num)) = record.values.get(var_name) {
                     let cat_val = *num as usize;
-                    category_cases.entry(cat_val).or_insert_with(Vec::new).push(case_idx);
+                    category_cases.entry(cat_val).or_default().push(case_idx);
                 }
             }
 
             // Calculate multiple fit
-            for dim in 0..result.dimensions {
+            for (dim, multi_fit) in multi_fit_dims.iter_mut().enumerate() {
                 let mut dim_fit = 0.0;
                 let mut total_cases = 0;
 
@@ -85,7 +106,7 @@ pub fn calculate_fit_measures(
 
                 // Normalize by number of cases
                 if total_cases > 0 {
-                    multi_fit_dims[dim] = dim_fit / (total_cases as f64);
+                    *multi_fit = dim_fit / (total_cases as f64);
                 }
             }
 
@@ -169,3 +190,149 @@ pub fn calculate_fit_measures(
         single_loss,
     })
 }
+
+/// Calculate canonical correlations overall and between the fitted scores of each pair of sets
+pub fn calculate_set_correlations(
+    data: &AnalysisData,
+    config: &OVERALSAnalysisConfig
+) -> Result<SetCorrelations, String> {
+    let num_sets = data.set_target_data.len();
+    if num_sets < 2 {
+        return Err("Set correlations require at least two sets".to_string());
+    }
+
+    // Run OVERALS algorithm
+    let result = run_overals_algorithm(data, config)?;
+
+    Ok(correlate_sets(data, &result))
+}
+
+/// Canonical and per set pair correlations of an OVERALS solution
+pub fn correlate_sets(data: &AnalysisData, result: &OVERALSResult) -> SetCorrelations {
+    let num_sets = data.set_target_data.len();
+    let summary = summarize_overals_result(data, result);
+
+    // Canonical correlation per dimension: (K * eigenvalue - 1) / (K - 1)
+    let k = num_sets as f64;
+    let mut canonical_correlation = HashMap::new();
+    for dim in 0..result.dimensions {
+        let key = format!("Dimension {}", dim + 1);
+        if let Some(&eigen) = summary.eigenvalue.get(&key) {
+            canonical_correlation.insert(key, (k * eigen - 1.0) / (k - 1.0));
+        }
+    }
+
+    // Fitted scores of every set
+    let set_scores: Vec<Vec<Vec<f64>>> = (0..num_sets)
+        .map(|set_idx| calculate_set_scores(data, result, set_idx))
+        .collect();
+
+    let mut set_pairs = Vec::new();
+    for first in 0..num_sets {
+        for second in first + 1..num_sets {
+            let dimension = (0..result.dimensions)
+                .map(|dim| {
+                    let x: Vec<f64> = set_scores[first]
+                        .iter()
+                        .map(|s| s[dim])
+                        .collect();
+                    let y: Vec<f64> = set_scores[second]
+                        .iter()
+                        .map(|s| s[dim])
+                        .collect();
+                    calculate_correlation(&x, &y)
+                })
+                .collect();
+
+            set_pairs.push(SetPairCorrelation {
+                first_set: format!("Set {}", first + 1),
+                second_set: format!("Set {}", second + 1),
+                dimension,
+            });
+        }
+    }
+
+    SetCorrelations {
+        canonical_correlation,
+        set_pairs,
+    }
+}
+
+/// Permutation test of the total fit: cases are shuffled independently within every set
+/// but the first, which destroys the association between sets while keeping each set intact.
+/// Each replicate is a single ALS run from the initial configuration of the best start
+pub fn perform_fit_permutation_test(
+    data: &AnalysisData,
+    config: &OVERALSAnalysisConfig
+) -> Result<FitPermutationTest, String> {
+    let num_sets = data.set_target_data.len();
+    if num_sets < 2 {
+        return Err("Permutation test requires at least two sets".to_string());
+    }
+
+    let num_permutations = config.options.num_permutations.unwrap_or(100);
+    if num_permutations <= 0 {
+        return Err("Number of permutations must be positive".to_string());
+    }
+    let num_permutations = num_permutations as usize;
+
+    let num_cases = data.set_target_data
+        .first()
+        .and_then(|set| set.first())
+        .map_or(0, |var| var.len());
+    if num_cases == 0 {
+        return Err("No cases found in data".to_string());
+    }
+
+    let dimensions = config.main.dimensions.unwrap_or(2) as f64;
+    let category_values = discover_categories(data);
+
+    // Observed fit
+    let mut rng = create_rng(config.options.seed);
+    let observed = fit_overals_starts(data, config, &category_values, &mut rng)?;
+    let observed_fit = dimensions - observed.final_loss;
+
+    let mut permuted_fits = Vec::with_capacity(num_permutations);
+    let mut count_extreme = 0;
+    let mut indices: Vec<usize> = (0..num_cases).collect();
+
+    for _ in 0..num_permutations {
+        let mut permuted = data.clone();
+
+        // The same permutation is applied to all variables of a set
+        for set_data in permuted.set_target_data.iter_mut().skip(1) {
+            shuffle_values(&mut indices, &mut rng);
+            for var_data in set_data.iter_mut() {
+                if var_data.len() == num_cases {
+                    *var_data = indices
+                        .iter()
+                        .map(|&idx| var_data[idx].clone())
+                        .collect();
+                }
+            }
+        }
+
+        let result = iterate_overals(
+            &permuted,
+            config,
+            observed.initial_object_scores.clone(),
+            category_values.clone()
+        )?;
+        let fit = dimensions - result.final_loss;
+        if fit >= observed_fit {
+            count_extreme += 1;
+        }
+        permuted_fits.push(fit);
+    }
+
+    let mean_permuted_fit = permuted_fits.iter().sum::<f64>() / (num_permutations as f64);
+    let max_permuted_fit = permuted_fits.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+
+    Ok(FitPermutationTest {
+        num_permutations,
+        observed_fit,
+        mean_permuted_fit,
+        max_permuted_fit,
+        p_value: ((count_extreme + 1) as f64) / ((num_permutations + 1) as f64),
+    })
+}
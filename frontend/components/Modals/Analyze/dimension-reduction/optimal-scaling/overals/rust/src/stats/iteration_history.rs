@@ -1,7 +1,7 @@
 use crate::models::{
     config::OVERALSAnalysisConfig,
     data::AnalysisData,
-    result::IterationHistory,
+    result::{ ConvergenceReport, IterationHistory },
 };
 
 use super::core::run_overals_algorithm;
@@ -19,3 +19,22 @@ pub fn calculate_iteration_history(
         iterations: result.iteration_history,
     })
 }
+
+/// Calculate the convergence report across all random starts
+pub fn calculate_convergence_report(
+    data: &AnalysisData,
+    config: &OVERALSAnalysisConfig
+) -> Result<ConvergenceReport, String> {
+    // Run the OVERALS algorithm
+    let result = run_overals_algorithm(data, config)?;
+
+    Ok(ConvergenceReport {
+        num_starts: result.start_summaries.len(),
+        best_start: result.best_start,
+        converged: result.converged,
+        iterations: result.iteration_history.len().saturating_sub(1),
+        final_loss: result.final_loss,
+        final_fit: (result.dimensions as f64) - result.final_loss,
+        starts: result.start_summaries,
+    })
+}
@@ -1,25 +1,26 @@
 use std::collections::HashMap;
 
+use rand_mt::Mt64;
+
 use crate::models::{
     config::OVERALSAnalysisConfig,
     data::AnalysisData,
     result::{ Dimensions, ObjectScores },
 };
 
-use super::core::run_overals_algorithm;
+use super::core::{ random_unit, run_overals_algorithm };
 
 /// Initialize object scores
 pub fn initialize_object_scores(
     dimensions: usize,
     num_cases: usize,
-    use_random_init: bool
+    use_random_init: bool,
+    rng: &mut Mt64
 ) -> Vec<Vec<f64>> {
     if use_random_init {
         // Random initialization
-        use rand::Rng;
-        let mut rng = rand::thread_rng();
         (0..num_cases)
-            .map(|_| { (0..dimensions).map(|_| rng.gen::<f64>() * 2.0 - 1.0).collect() })
+            .map(|_| { (0..dimensions).map(|_| random_unit(rng) * 2.0 - 1.0).collect() })
             .collect()
     } else {
         // Numerical initialization
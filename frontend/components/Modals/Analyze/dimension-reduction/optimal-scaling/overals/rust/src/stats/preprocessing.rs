@@ -62,9 +62,7 @@ pub fn prepare_data(
                 }
             } else {
                 // Variable definition not found - mark all cases as invalid for this variable
-                for case_idx in 0..num_cases {
-                    valid_cases[case_idx] = false;
-                }
+                valid_cases.fill(false);
             }
         }
 
@@ -256,11 +254,9 @@ pub fn count_categories(var_data: &[Vec<DataRecord>], var_name: &str) -> HashMap
         for record in records {
             if let Some(value) = record.values.get(var_name) {
                 match value {
-                    DataValue::Number(num) => {
-                        // In OVERALS, data must be positive integers
-                        if *num > 0.0 && (*num - num.floor()).abs() < 1e-10 {
-                            categories.insert(num.to_string(), true);
-                        }
+                    // In OVERALS, data must be positive integers
+                    DataValue::Number(num) if *num > 0.0 && (*num - num.floor()).abs() < 1e-10 => {
+                        categories.insert(num.to_string(), true);
                     }
                     DataValue::Text(text) => {
                         if let Ok(num) = text.parse::<f64>() {
@@ -290,10 +286,11 @@ fn count_categories_for_var(
             for record in var_data {
                 if let Some(value) = record.values.get(var_name) {
                     match value {
-                        DataValue::Number(num) => {
-                            if *num > 0.0 && (*num - num.floor()).abs() < 1e-10 {
-                                categories.insert(num.to_string(), true);
-                            }
+                        DataValue::Number(num) if
+                            *num > 0.0 &&
+                            (*num - num.floor()).abs() < 1e-10
+                        => {
+                            categories.insert(num.to_string(), true);
                         }
                         DataValue::Text(text) => {
                             if let Ok(num) = text.parse::<f64>() {
@@ -342,6 +339,7 @@ pub fn parse_variable_scaling_info(
         "single nominal" => ScalingLevel::Single,
         "multiple nominal" => ScalingLevel::Multiple,
         "discrete numeric" => ScalingLevel::Discrete,
+        _ if scaling_level_str.starts_with("spline ") => parse_spline_level(&all_parts)?,
         _ => {
             return Err(format!("Unknown scaling level: '{}'", scaling_level_str));
         }
@@ -358,6 +356,29 @@ pub fn parse_variable_scaling_info(
     Ok((var_name, scaling_level, min, max))
 }
 
+/// Parse "Spline Ordinal <degree> <interior knots>" or "Spline Nominal <degree> <interior knots>"
+fn parse_spline_level(parts: &[&str]) -> Result<ScalingLevel, String> {
+    if parts.len() != 4 {
+        return Err(format!("Invalid spline scaling info: '{}'", parts.join(" ")));
+    }
+
+    let degree = parts[2]
+        .parse::<usize>()
+        .map_err(|_| format!("Invalid spline degree: '{}'", parts[2]))?;
+    let interior_knots = parts[3]
+        .parse::<usize>()
+        .map_err(|_| format!("Invalid number of interior knots: '{}'", parts[3]))?;
+    if degree == 0 {
+        return Err("Spline degree must be at least 1".to_string());
+    }
+
+    match parts[1].to_lowercase().as_str() {
+        "ordinal" => Ok(ScalingLevel::SplineOrdinal { degree, interior_knots }),
+        "nominal" => Ok(ScalingLevel::SplineNominal { degree, interior_knots }),
+        other => Err(format!("Unknown scaling level: 'spline {}'", other)),
+    }
+}
+
 /// Determine the scaling level for a variable
 pub fn determine_scaling_level(
     var_def: &VariableDefinition,
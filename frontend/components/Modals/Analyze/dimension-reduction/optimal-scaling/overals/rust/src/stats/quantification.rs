@@ -2,13 +2,19 @@ use std::collections::HashMap;
 
 use nalgebra::{ DMatrix, DVector };
 
-use crate::models::data::{ DataRecord, DataValue };
+use crate::models::{ data::{ DataRecord, DataValue }, result::ScalingLevel };
+
+/// Variable weights keyed by (set, variable)
+pub type VariableWeights = HashMap<(usize, usize), Vec<f64>>;
+
+/// Category quantifications keyed by (set, variable, category)
+pub type CategoryQuantifications = HashMap<(usize, usize, usize), f64>;
 
 /// Initialize weights and quantifications
 pub fn initialize_weights_and_quantifications(
     category_values: &HashMap<(usize, usize), Vec<usize>>,
     dimensions: usize
-) -> (HashMap<(usize, usize), Vec<f64>>, HashMap<(usize, usize, usize), f64>) {
+) -> (VariableWeights, CategoryQuantifications) {
     let mut variable_weights = HashMap::new();
     let mut category_quantifications = HashMap::new();
 
@@ -33,6 +39,7 @@ pub fn initialize_weights_and_quantifications(
 }
 
 /// Update quantifications for multiple nominal variables
+#[allow(clippy::too_many_arguments)]
 pub fn update_multiple_nominal_quantifications(
     set_idx: usize,
     var_idx: usize,
@@ -78,6 +85,7 @@ pub fn update_multiple_nominal_quantifications(
 }
 
 /// Update quantifications for single nominal variables
+#[allow(clippy::too_many_arguments)]
 pub fn update_single_nominal_quantifications(
     set_idx: usize,
     var_idx: usize,
@@ -178,6 +186,7 @@ pub fn update_single_nominal_quantifications(
 }
 
 /// Update quantifications for ordinal variables using monotonic regression
+#[allow(clippy::too_many_arguments)]
 pub fn update_ordinal_quantifications(
     set_idx: usize,
     var_idx: usize,
@@ -229,7 +238,7 @@ pub fn update_ordinal_quantifications(
         }
 
         // Sort by category value
-        cat_quants.sort_by(|a, b| a.0.cmp(&b.0));
+        cat_quants.sort_by_key(|&(cat_val, _)| cat_val);
 
         // Apply monotonic regression
         let values: Vec<f64> = cat_quants
@@ -269,39 +278,321 @@ pub fn update_ordinal_quantifications(
     }
 }
 
-/// Update quantifications for numeric variables
+/// Update quantifications for numeric variables: the quantifications stay the standardized
+/// category values and the variable weights are fitted as for a single nominal variable
+#[allow(clippy::too_many_arguments)]
 pub fn update_numeric_quantifications(
     set_idx: usize,
     var_idx: usize,
+    var_name: &str,
+    var_data: &[DataRecord],
+    object_scores: &[Vec<f64>],
+    v_kj: &[Vec<f64>],
+    category_quantifications: &mut HashMap<(usize, usize, usize), f64>,
+    variable_weights: &mut HashMap<(usize, usize), Vec<f64>>,
+    category_values: &HashMap<(usize, usize), Vec<usize>>
+) {
+    let Some(categories) = category_values.get(&(set_idx, var_idx)) else {
+        return;
+    };
+    if categories.is_empty() {
+        return;
+    }
+
+    let frequencies = category_frequencies(var_name, var_data, categories);
+    let values: Vec<f64> = categories
+        .iter()
+        .map(|&cat_val| cat_val as f64)
+        .collect();
+    let mean =
+        values
+            .iter()
+            .zip(&frequencies)
+            .map(|(v, f)| v * f)
+            .sum::<f64>() / frequencies.iter().sum::<f64>();
+    let centered: Vec<f64> = values
+        .iter()
+        .map(|v| v - mean)
+        .collect();
+    let standardized = normalize_quantifications(&centered, &frequencies);
+
+    // The weights are the projection of the unconstrained quantifications on the current ones,
+    // so the standardized values have to be in place before the single nominal update
+    for (&cat_val, &quant) in categories.iter().zip(&standardized) {
+        category_quantifications.insert((set_idx, var_idx, cat_val), quant);
+    }
+
+    update_single_nominal_quantifications(
+        set_idx,
+        var_idx,
+        var_name,
+        var_data,
+        object_scores,
+        v_kj,
+        category_quantifications,
+        variable_weights,
+        category_values
+    );
+
+    for (&cat_val, &quant) in categories.iter().zip(&standardized) {
+        category_quantifications.insert((set_idx, var_idx, cat_val), quant);
+    }
+}
+
+/// Update quantifications for spline ordinal and spline nominal variables: the single nominal
+/// quantifications are projected on the I-spline basis of the category values, with
+/// non-negative coefficients (a non-decreasing transformation) for spline ordinal
+#[allow(clippy::too_many_arguments)]
+pub fn update_spline_quantifications(
+    set_idx: usize,
+    var_idx: usize,
+    var_name: &str,
+    var_data: &[DataRecord],
+    object_scores: &[Vec<f64>],
+    v_kj: &[Vec<f64>],
+    category_quantifications: &mut HashMap<(usize, usize, usize), f64>,
+    variable_weights: &mut HashMap<(usize, usize), Vec<f64>>,
     category_values: &HashMap<(usize, usize), Vec<usize>>,
-    category_quantifications: &mut HashMap<(usize, usize, usize), f64>
+    scaling_level: &ScalingLevel
 ) {
-    if let Some(categories) = category_values.get(&(set_idx, var_idx)) {
-        if categories.is_empty() {
+    let (degree, interior_knots, monotone) = match *scaling_level {
+        ScalingLevel::SplineOrdinal { degree, interior_knots } => (degree, interior_knots, true),
+        ScalingLevel::SplineNominal { degree, interior_knots } => (degree, interior_knots, false),
+        _ => {
             return;
         }
+    };
+
+    // First, calculate like single nominal
+    update_single_nominal_quantifications(
+        set_idx,
+        var_idx,
+        var_name,
+        var_data,
+        object_scores,
+        v_kj,
+        category_quantifications,
+        variable_weights,
+        category_values
+    );
 
-        // For numeric variables, enforce linear relationship with category values
-        let min_cat = *categories.iter().min().unwrap_or(&1);
-        let max_cat = *categories.iter().max().unwrap_or(&1);
+    let Some(categories) = category_values.get(&(set_idx, var_idx)) else {
+        return;
+    };
+    if categories.len() < 2 {
+        return;
+    }
+
+    let frequencies = category_frequencies(var_name, var_data, categories);
+    let targets: Vec<f64> = categories
+        .iter()
+        .map(|&cat_val| {
+            *category_quantifications.get(&(set_idx, var_idx, cat_val)).unwrap_or(&0.0)
+        })
+        .collect();
+    let values: Vec<f64> = categories
+        .iter()
+        .map(|&cat_val| cat_val as f64)
+        .collect();
+    let basis = i_spline_basis(&values, degree, interior_knots);
+
+    // Weighted least squares with a free intercept: centering the target and the basis
+    // columns with the category frequencies removes the intercept from the fit
+    let total_weight = frequencies.iter().sum::<f64>();
+    if total_weight <= 0.0 {
+        return;
+    }
+    let weighted_mean = |column: &dyn Fn(usize) -> f64| {
+        (0..categories.len()).map(|i| frequencies[i] * column(i)).sum::<f64>() / total_weight
+    };
+    let target_mean = weighted_mean(&|i| targets[i]);
+    let column_means: Vec<f64> = (0..basis.ncols())
+        .map(|k| weighted_mean(&|i| basis[(i, k)]))
+        .collect();
+
+    let design = DMatrix::from_fn(categories.len(), basis.ncols(), |i, k| {
+        frequencies[i].sqrt() * (basis[(i, k)] - column_means[k])
+    });
+    let response = DVector::from_fn(categories.len(), |i, _| {
+        frequencies[i].sqrt() * (targets[i] - target_mean)
+    });
+
+    let coefficients = if monotone {
+        non_negative_least_squares(&design, &response)
+    } else {
+        least_squares(&design, &response)
+    };
+
+    let fitted: Vec<f64> = (0..categories.len())
+        .map(|i| {
+            target_mean +
+                (0..basis.ncols())
+                    .map(|k| (basis[(i, k)] - column_means[k]) * coefficients[k])
+                    .sum::<f64>()
+        })
+        .collect();
+
+    // Normalize like the ordinal quantifications
+    let normalized = normalize_quantifications(&fitted, &frequencies);
+    for (&cat_val, &quant) in categories.iter().zip(&normalized) {
+        category_quantifications.insert((set_idx, var_idx, cat_val), quant);
+    }
+}
 
-        if min_cat == max_cat {
-            // Only one category, set to middle value
-            for &cat_val in categories {
-                category_quantifications.insert((set_idx, var_idx, cat_val), 0.0);
+/// Number of cases in each category, in the order of `categories`
+fn category_frequencies(var_name: &str, var_data: &[DataRecord], categories: &[usize]) -> Vec<f64> {
+    let mut frequencies = vec![0.0; categories.len()];
+    for record in var_data {
+        if let Some(DataValue::Number(num)) = record.values.get(var_name) {
+            if let Some(cat_index) = categories.iter().position(|&c| c == (*num as usize)) {
+                frequencies[cat_index] += 1.0;
             }
-            return;
         }
+    }
+    frequencies
+}
 
-        // Linear transformation from min_cat..max_cat to -1..1
-        for &cat_val in categories {
-            let normalized =
-                (2.0 * ((cat_val as f64) - (min_cat as f64))) /
-                    ((max_cat as f64) - (min_cat as f64)) -
-                1.0;
-            category_quantifications.insert((set_idx, var_idx, cat_val), normalized);
+/// Scale quantifications so that their weighted sum of squares equals the number of cases
+fn normalize_quantifications(values: &[f64], frequencies: &[f64]) -> Vec<f64> {
+    let sum_weight = frequencies.iter().sum::<f64>();
+    let sum_sq = values
+        .iter()
+        .zip(frequencies)
+        .map(|(v, f)| v * v * f)
+        .sum::<f64>();
+
+    if sum_sq <= 1e-12 {
+        return values.to_vec();
+    }
+
+    let norm_factor = (sum_weight / sum_sq).sqrt();
+    values
+        .iter()
+        .map(|v| v * norm_factor)
+        .collect()
+}
+
+/// I-spline basis evaluated at `values`: column k is the sum of the B-splines k..K-1, so the
+/// columns are non-decreasing and, together with a constant, span the same space as the
+/// B-splines. Boundary knots sit at the smallest and largest value, interior knots are
+/// spaced evenly between them
+pub fn i_spline_basis(values: &[f64], degree: usize, interior_knots: usize) -> DMatrix<f64> {
+    let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+
+    let mut knots = vec![min; degree + 1];
+    for k in 1..=interior_knots {
+        knots.push(min + ((max - min) * (k as f64)) / ((interior_knots + 1) as f64));
+    }
+    knots.extend(std::iter::repeat_n(max, degree + 1));
+
+    let num_basis = knots.len() - degree - 1;
+    let mut basis = DMatrix::zeros(values.len(), num_basis - 1);
+
+    for (i, &x) in values.iter().enumerate() {
+        let b_splines = b_spline_values(x, &knots, degree);
+        let mut cumulative = 0.0;
+        for k in (1..num_basis).rev() {
+            cumulative += b_splines[k];
+            basis[(i, k - 1)] = cumulative;
+        }
+    }
+
+    basis
+}
+
+/// Cox-de Boor recursion for all B-splines of the given degree at one point
+fn b_spline_values(x: f64, knots: &[f64], degree: usize) -> Vec<f64> {
+    let last = knots[knots.len() - 1];
+
+    // Degree zero: indicator of the knot interval, closing the last non-empty one at the right end
+    let mut values: Vec<f64> = (0..knots.len() - 1)
+        .map(|i| {
+            let inside = knots[i] <= x && x < knots[i + 1];
+            let right_end = x == last && knots[i] < knots[i + 1] && knots[i + 1] == last;
+            if inside || right_end { 1.0 } else { 0.0 }
+        })
+        .collect();
+
+    for p in 1..=degree {
+        values = (0..values.len() - 1)
+            .map(|i| {
+                let left = knots[i + p] - knots[i];
+                let right = knots[i + p + 1] - knots[i + 1];
+                let mut value = 0.0;
+                if left > 0.0 {
+                    value += ((x - knots[i]) / left) * values[i];
+                }
+                if right > 0.0 {
+                    value += ((knots[i + p + 1] - x) / right) * values[i + 1];
+                }
+                value
+            })
+            .collect();
+    }
+
+    values
+}
+
+/// Minimum norm least squares solution
+fn least_squares(design: &DMatrix<f64>, response: &DVector<f64>) -> DVector<f64> {
+    design
+        .clone()
+        .svd(true, true)
+        .solve(response, 1e-12)
+        .unwrap_or_else(|_| DVector::zeros(design.ncols()))
+}
+
+/// Lawson-Hanson active set algorithm for least squares with non-negative coefficients
+pub fn non_negative_least_squares(design: &DMatrix<f64>, response: &DVector<f64>) -> DVector<f64> {
+    let n = design.ncols();
+    let tolerance = 1e-12;
+    let mut x = DVector::zeros(n);
+    let mut passive = vec![false; n];
+
+    for _ in 0..3 * n.max(1) {
+        let gradient = design.transpose() * (response - design * &x);
+        let candidate = (0..n)
+            .filter(|&j| !passive[j] && gradient[j] > tolerance)
+            .max_by(|&a, &b| gradient[a].total_cmp(&gradient[b]));
+        let Some(entering) = candidate else {
+            break;
+        };
+        passive[entering] = true;
+
+        loop {
+            // Unconstrained fit on the passive columns
+            let columns: Vec<usize> = (0..n).filter(|&j| passive[j]).collect();
+            let sub_design = design.select_columns(&columns);
+            let sub_solution = least_squares(&sub_design, response);
+            let mut trial = DVector::zeros(n);
+            for (position, &j) in columns.iter().enumerate() {
+                trial[j] = sub_solution[position];
+            }
+
+            if columns.iter().all(|&j| trial[j] > tolerance) {
+                x = trial;
+                break;
+            }
+
+            // Step towards the trial solution until the first coefficient reaches zero
+            let alpha = columns
+                .iter()
+                .filter(|&&j| trial[j] <= tolerance)
+                .map(|&j| x[j] / (x[j] - trial[j]))
+                .fold(f64::INFINITY, f64::min);
+            x += (trial - &x) * alpha;
+
+            for &j in &columns {
+                if x[j] <= tolerance {
+                    x[j] = 0.0;
+                    passive[j] = false;
+                }
+            }
         }
     }
+
+    x
 }
 
 /// Monotonic regression implementation (optimized)
@@ -338,9 +629,7 @@ pub fn monotonic_regression(values: &[f64], weights: &[f64]) -> Vec<f64> {
                 let block_avg = if block_weight > 0.0 { block_sum / block_weight } else { 0.0 };
 
                 // Update block
-                for j in start..=end {
-                    result[j] = block_avg;
-                }
+                result[start..=end].fill(block_avg);
 
                 i = end;
                 active = true;
@@ -369,9 +658,7 @@ pub fn monotonic_regression(values: &[f64], weights: &[f64]) -> Vec<f64> {
                 let block_avg = if block_weight > 0.0 { block_sum / block_weight } else { 0.0 };
 
                 // Update block
-                for j in start..=end {
-                    result[j] = block_avg;
-                }
+                result[start..=end].fill(block_avg);
 
                 i = start;
                 active = true;
@@ -3,7 +3,7 @@ use std::collections::HashMap;
 use crate::models::{
     config::OVERALSAnalysisConfig,
     data::{ AnalysisData, DataValue },
-    result::SummaryAnalysis,
+    result::{ OVERALSResult, SummaryAnalysis },
 };
 
 use super::core::{ get_var_def, run_overals_algorithm };
@@ -17,6 +17,11 @@ pub fn calculate_summary_analysis(
     // Run the OVERALS algorithm
     let result = run_overals_algorithm(data, config)?;
 
+    Ok(summarize_overals_result(data, &result))
+}
+
+/// Loss, eigenvalues and fit of an OVERALS solution
+pub fn summarize_overals_result(data: &AnalysisData, result: &OVERALSResult) -> SummaryAnalysis {
     // Number of sets
     let num_sets = data.set_target_data.len();
 
@@ -85,19 +90,16 @@ pub fn calculate_summary_analysis(
 
             // Normalize by number of cases
             if total_set_cases > 0 {
-                for dim in 0..result.dimensions {
-                    set_loss_by_dim[dim] /= total_set_cases as f64;
+                for dim_loss in set_loss_by_dim.iter_mut() {
+                    *dim_loss /= total_set_cases as f64;
                 }
             }
 
             // Store loss per dimension for this set
             let mut set_loss = 0.0;
-            for dim in 0..result.dimensions {
-                loss.insert(
-                    format!("Set {} Dimension {}", set_idx + 1, dim + 1),
-                    set_loss_by_dim[dim]
-                );
-                set_loss += set_loss_by_dim[dim];
+            for (dim, &dim_loss) in set_loss_by_dim.iter().enumerate() {
+                loss.insert(format!("Set {} Dimension {}", set_idx + 1, dim + 1), dim_loss);
+                set_loss += dim_loss;
             }
 
             // Store total set loss
@@ -143,9 +145,9 @@ pub fn calculate_summary_analysis(
     }
     fit.insert("Total".to_string(), total_fit);
 
-    Ok(SummaryAnalysis {
+    SummaryAnalysis {
         loss,
         eigenvalue,
         fit,
-    })
+    }
 }
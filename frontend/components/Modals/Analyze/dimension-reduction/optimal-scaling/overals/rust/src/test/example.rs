@@ -0,0 +1,286 @@
+use std::collections::HashMap;
+
+use serde_json::json;
+
+use crate::models::{
+    config::OVERALSAnalysisConfig,
+    data::{ AnalysisData, DataRecord, DataValue, VariableDefinition },
+    result::ScalingLevel,
+};
+use crate::stats::core::{
+    correlate_sets,
+    create_rng,
+    discover_categories,
+    fit_overals_starts,
+    i_spline_basis,
+    perform_fit_permutation_test,
+    update_numeric_quantifications,
+    update_spline_quantifications,
+};
+
+// Two sets of twelve cases: (a single nominal, b discrete numeric) and
+// (c spline ordinal of degree 2 with one interior knot, d single nominal)
+const A: [f64; 12] = [1.0, 1.0, 2.0, 2.0, 3.0, 3.0, 1.0, 2.0, 3.0, 1.0, 2.0, 3.0];
+const B: [f64; 12] = [1.0, 2.0, 2.0, 3.0, 4.0, 4.0, 1.0, 3.0, 4.0, 2.0, 2.0, 3.0];
+const C: [f64; 12] = [5.0, 4.0, 3.0, 3.0, 1.0, 2.0, 4.0, 3.0, 1.0, 5.0, 2.0, 1.0];
+const D: [f64; 12] = [1.0, 1.0, 2.0, 2.0, 3.0, 3.0, 2.0, 2.0, 3.0, 1.0, 1.0, 3.0];
+
+// I-spline basis at 1..5 for degree 2 and one interior knot: knots 1, 1, 1, 3, 5, 5, 5,
+// Cox-de Boor recursion in exact rational arithmetic, column k summing B-splines k..3
+const I_SPLINE_BASIS: [[f64; 3]; 5] = [
+    [0.0, 0.0, 0.0],
+    [0.75, 0.125, 0.0],
+    [1.0, 0.5, 0.0],
+    [1.0, 0.875, 0.25],
+    [1.0, 1.0, 1.0],
+];
+
+// One quantification update of a variable with categories 1..5 (frequencies 2, 3, 2, 3, 2)
+// from SPLINE_OBJECT_SCORES, no other variables in the set, quantifications 0, 0.25, .., 1 and
+// weights (0.5, 0.5). The single nominal step gives the category means of the first dimension
+// (1, -0.2, 1.1, -0.4, 1.5); the spline fit is frequency-weighted least squares with a free
+// intercept on the I-spline basis, the ordinal coefficients from enumerating every active set
+// (the optimum (0, 0, 0.9391304347826) keeps only the last column), then scaled to a weighted
+// sum of squares of 12. Numeric quantifications are the standardized category values and
+// their weights the frequency-weighted products of the category means with them, divided by 12
+const SPLINE_OBJECT_SCORES: [[f64; 2]; 12] = [
+    [0.9, 0.1],
+    [1.1, -0.3],
+    [-0.2, 0.4],
+    [0.1, 0.2],
+    [-0.5, 0.9],
+    [1.4, -1.2],
+    [0.8, 0.5],
+    [-0.6, 0.3],
+    [-0.9, -0.8],
+    [0.3, -0.4],
+    [1.7, 0.6],
+    [1.3, -0.1],
+];
+const SPLINE_CATEGORIES: [f64; 12] = [1.0, 1.0, 2.0, 2.0, 2.0, 3.0, 3.0, 4.0, 4.0, 4.0, 5.0, 5.0];
+const SPLINE_ORDINAL_QUANTIFICATIONS: [f64; 5] = [
+    0.4170288281141, 0.4170288281141, 0.4170288281141, 0.8340576562283, 2.085144140571,
+];
+const SPLINE_NOMINAL_QUANTIFICATIONS: [f64; 5] = [
+    1.241239728125, 0.4878461370469, -0.09687724707314, 0.1799148874215,
+    2.011067852188,
+];
+const SPLINE_WEIGHTS: [f64; 2] = [0.6971428571429, -0.03428571428571];
+const NUMERIC_QUANTIFICATIONS: [f64; 5] = [
+    -1.477097891752, -0.738548945876, 0.0, 0.738548945876, 1.477097891752,
+];
+const NUMERIC_WEIGHTS: [f64; 2] = [0.08616404368553, -0.06154574548967];
+
+// Three random starts from Mt64 seeded with 4242 (each coordinate 2 * u - 1 with u the top 53
+// bits of next_u64() / 2^53), replayed by an independent port of the alternating least
+// squares iterations: (start, loss, iterations) and the best start
+const START_SUMMARIES: [(usize, f64, usize); 3] = [
+    (1, 31.00914111309, 2),
+    (2, 25.55279700964, 2),
+    (3, 2.719869863514, 4),
+];
+// Spline ordinal quantifications of c in the best solution
+const BEST_SPLINE_QUANTIFICATIONS: [f64; 5] = [
+    -1.202850666428, -0.7961121974494, 0.086991792431, 0.9960598337907,
+    1.480690457208,
+];
+// (K * eigenvalue - 1) / (K - 1) per dimension and the correlation of the two set scores
+const CANONICAL_CORRELATIONS: [f64; 2] = [0.8502782329375, 0.2410230527121];
+const SET_PAIR_CORRELATIONS: [f64; 2] = [0.8652715055395, 0.8782719181507];
+// 19 permutations continuing the same generator, each a single ALS run from the best start's
+// initial configuration: (observed fit, mean permuted fit, max permuted fit, p-value)
+const FIT_PERMUTATION_TEST: (f64, f64, f64, f64) = (
+    -0.7198698635145,
+    -10.34249532914,
+    -1.269312100132,
+    0.05,
+);
+
+fn definition(name: &str, column_index: usize) -> VariableDefinition {
+    serde_json
+        ::from_value(
+            json!({
+                "columnIndex": column_index,
+                "name": name,
+                "type": "NUMERIC",
+                "width": 8,
+                "decimals": 0,
+                "values": [],
+                "missing": [],
+                "columns": 8,
+                "align": "right",
+                "measure": "nominal",
+                "role": "input",
+            })
+        )
+        .unwrap()
+}
+
+fn records(name: &str, values: &[f64]) -> Vec<DataRecord> {
+    values
+        .iter()
+        .map(|&value| DataRecord {
+            values: HashMap::from([(name.to_string(), DataValue::Number(value))]),
+        })
+        .collect()
+}
+
+fn two_set_data() -> AnalysisData {
+    AnalysisData {
+        set_target_data: vec![
+            vec![records("a", &A), records("b", &B)],
+            vec![records("c", &C), records("d", &D)]
+        ],
+        plots_target_data: vec![],
+        set_target_data_defs: vec![
+            vec![vec![definition("a", 0), definition("b", 1)]],
+            vec![vec![definition("c", 2), definition("d", 3)]]
+        ],
+        plots_target_data_defs: vec![],
+    }
+}
+
+fn two_set_config() -> OVERALSAnalysisConfig {
+    let mut config = OVERALSAnalysisConfig::default();
+    config.main.set_target_variable = Some(
+        vec![
+            vec!["a (Single Nominal 1 3)".to_string(), "b (Discrete Numeric 1 4)".to_string()],
+            vec!["c (Spline Ordinal 2 1 1 5)".to_string(), "d (Single Nominal 1 3)".to_string()]
+        ]
+    );
+    config.options.use_randconf = true;
+    config.options.num_starts = Some(3);
+    config.options.seed = Some(4242);
+    config
+}
+
+// Runs one quantification update of a lone variable and returns its quantifications and weights
+fn update_lone_variable(scaling_level: ScalingLevel) -> (Vec<f64>, Vec<f64>) {
+    let var_data = records("c", &SPLINE_CATEGORIES);
+    let object_scores: Vec<Vec<f64>> = SPLINE_OBJECT_SCORES.iter()
+        .map(|row| row.to_vec())
+        .collect();
+    let v_kj = vec![vec![0.0; 2]; object_scores.len()];
+    let categories = vec![1, 2, 3, 4, 5];
+    let category_values = HashMap::from([((0, 0), categories.clone())]);
+    let mut category_quantifications: HashMap<(usize, usize, usize), f64> = categories
+        .iter()
+        .enumerate()
+        .map(|(i, &cat_val)| ((0, 0, cat_val), (i as f64) / 4.0))
+        .collect();
+    let mut variable_weights = HashMap::from([((0, 0), vec![0.5, 0.5])]);
+
+    if matches!(scaling_level, ScalingLevel::Discrete) {
+        update_numeric_quantifications(
+            0,
+            0,
+            "c",
+            &var_data,
+            &object_scores,
+            &v_kj,
+            &mut category_quantifications,
+            &mut variable_weights,
+            &category_values
+        );
+    } else {
+        update_spline_quantifications(
+            0,
+            0,
+            "c",
+            &var_data,
+            &object_scores,
+            &v_kj,
+            &mut category_quantifications,
+            &mut variable_weights,
+            &category_values,
+            &scaling_level
+        );
+    }
+
+    let quantifications = categories
+        .iter()
+        .map(|&cat_val| category_quantifications[&(0, 0, cat_val)])
+        .collect();
+    (quantifications, variable_weights[&(0, 0)].clone())
+}
+
+#[test]
+fn i_spline_basis_matches_reference_values() {
+    let basis = i_spline_basis(&[1.0, 2.0, 3.0, 4.0, 5.0], 2, 1);
+    assert_eq!((basis.nrows(), basis.ncols()), (5, 3));
+    for (i, row) in I_SPLINE_BASIS.iter().enumerate() {
+        for (k, &value) in row.iter().enumerate() {
+            assert!((basis[(i, k)] - value).abs() < 1e-12, "({}, {})", i, k);
+        }
+    }
+}
+
+#[test]
+fn spline_and_numeric_updates_match_reference_values() {
+    let cases = [
+        (
+            ScalingLevel::SplineOrdinal { degree: 2, interior_knots: 1 },
+            SPLINE_ORDINAL_QUANTIFICATIONS,
+            SPLINE_WEIGHTS,
+        ),
+        (
+            ScalingLevel::SplineNominal { degree: 2, interior_knots: 1 },
+            SPLINE_NOMINAL_QUANTIFICATIONS,
+            SPLINE_WEIGHTS,
+        ),
+        (ScalingLevel::Discrete, NUMERIC_QUANTIFICATIONS, NUMERIC_WEIGHTS),
+    ];
+
+    for (scaling_level, expected_quantifications, expected_weights) in cases {
+        let label = format!("{:?}", scaling_level);
+        let (quantifications, weights) = update_lone_variable(scaling_level);
+        for (quant, expected) in quantifications.iter().zip(expected_quantifications) {
+            assert!((quant - expected).abs() < 1e-12, "{}", label);
+        }
+        for (weight, expected) in weights.iter().zip(expected_weights) {
+            assert!((weight - expected).abs() < 1e-12, "{}", label);
+        }
+    }
+}
+
+#[test]
+fn seeded_starts_correlations_and_permutation_test_match_reference_values() {
+    let data = two_set_data();
+    let mut config = two_set_config();
+    let mut rng = create_rng(config.options.seed);
+    let result = fit_overals_starts(&data, &config, &discover_categories(&data), &mut rng).unwrap();
+
+    assert_eq!(result.best_start, 3);
+    assert_eq!(result.start_summaries.len(), 3);
+    for (summary, (start, loss, iterations)) in result.start_summaries.iter().zip(START_SUMMARIES) {
+        assert_eq!(summary.start, start);
+        assert!((summary.loss - loss).abs() < 1e-9, "start {}", start);
+        assert_eq!(summary.iterations, iterations, "start {}", start);
+        assert!(summary.converged);
+    }
+    assert!((result.final_loss - START_SUMMARIES[2].1).abs() < 1e-9);
+    for (cat_val, expected) in (1..=5).zip(BEST_SPLINE_QUANTIFICATIONS) {
+        let quant = result.category_quantifications[&(1, 0, cat_val)];
+        assert!((quant - expected).abs() < 1e-9, "category {}", cat_val);
+    }
+
+    let correlations = correlate_sets(&data, &result);
+    for (dim, expected) in CANONICAL_CORRELATIONS.iter().enumerate() {
+        let value = correlations.canonical_correlation[&format!("Dimension {}", dim + 1)];
+        assert!((value - expected).abs() < 1e-9, "dimension {}", dim + 1);
+    }
+    assert_eq!(correlations.set_pairs.len(), 1);
+    for (value, expected) in correlations.set_pairs[0].dimension.iter().zip(SET_PAIR_CORRELATIONS) {
+        assert!((value - expected).abs() < 1e-9);
+    }
+
+    config.options.permutation_test = true;
+    config.options.num_permutations = Some(19);
+    let test = perform_fit_permutation_test(&data, &config).unwrap();
+    let (observed_fit, mean_permuted_fit, max_permuted_fit, p_value) = FIT_PERMUTATION_TEST;
+    assert_eq!(test.num_permutations, 19);
+    assert!((test.observed_fit - observed_fit).abs() < 1e-9);
+    assert!((test.mean_permuted_fit - mean_permuted_fit).abs() < 1e-9);
+    assert!((test.max_permuted_fit - max_permuted_fit).abs() < 1e-9);
+    assert!((test.p_value - p_value).abs() < 1e-12);
+}
@@ -41,7 +41,7 @@ pub fn run_analysis(
     let mut prepared_data = data.clone(); // Default to original data
     match core::prepare_data(data, config) {
         Ok(prepared) => {
-            web_sys::console::log_1(&format!("Data preparation successful").into());
+            web_sys::console::log_1(&"Data preparation successful".into());
             prepared_data = prepared;
         }
         Err(e) => {
@@ -188,6 +188,58 @@ pub fn run_analysis(
         }
     }
 
+    // Step 11: Convergence Report
+    executed_functions.push("calculate_convergence_report".to_string());
+    let mut convergence_report = None;
+    match core::calculate_convergence_report(&prepared_data, config) {
+        Ok(report) => {
+            web_sys::console::log_1(&format!("Convergence Report: {:?}", report).into());
+            convergence_report = Some(report);
+        }
+        Err(e) => {
+            error_collector.add_error("calculate_convergence_report", &e);
+            web_sys::console::error_1(
+                &format!("Error in calculate_convergence_report: {:?}", e).into()
+            );
+        }
+    }
+
+    // Step 12: Set Correlations (if requested)
+    let mut set_correlations = None;
+    if config.options.set_correlations {
+        executed_functions.push("calculate_set_correlations".to_string());
+        match core::calculate_set_correlations(&prepared_data, config) {
+            Ok(correlations) => {
+                web_sys::console::log_1(&format!("Set Correlations: {:?}", correlations).into());
+                set_correlations = Some(correlations);
+            }
+            Err(e) => {
+                error_collector.add_error("calculate_set_correlations", &e);
+                web_sys::console::error_1(
+                    &format!("Error in calculate_set_correlations: {:?}", e).into()
+                );
+            }
+        }
+    }
+
+    // Step 13: Permutation Test of Fit (if requested)
+    let mut fit_permutation_test = None;
+    if config.options.permutation_test {
+        executed_functions.push("perform_fit_permutation_test".to_string());
+        match core::perform_fit_permutation_test(&prepared_data, config) {
+            Ok(test) => {
+                web_sys::console::log_1(&format!("Fit Permutation Test: {:?}", test).into());
+                fit_permutation_test = Some(test);
+            }
+            Err(e) => {
+                error_collector.add_error("perform_fit_permutation_test", &e);
+                web_sys::console::error_1(
+                    &format!("Error in perform_fit_permutation_test: {:?}", e).into()
+                );
+            }
+        }
+    }
+
     // Log execution summary
     web_sys::console::log_1(&format!("Executed functions: {:?}", executed_functions).into());
 
@@ -203,6 +255,9 @@ pub fn run_analysis(
         fit_measures,
         object_scores,
         transformation_plots,
+        convergence_report,
+        set_correlations,
+        fit_permutation_test,
     };
 
     web_sys::console::log_1(&"OVERALS analysis completed".into());
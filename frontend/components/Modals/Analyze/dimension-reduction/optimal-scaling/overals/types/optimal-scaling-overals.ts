@@ -44,6 +44,10 @@ export type OptScaOveralsDefineRangeScaleType = {
     SingleNominal: boolean;
     MultipleNominal: boolean;
     DiscreteNumeric: boolean;
+    SplineOrdinal: boolean;
+    SplineNominal: boolean;
+    Degree: number | null;
+    InteriorKnots: number | null;
 };
 
 export type OptScaOveralsDefineRangeScaleProps = {
@@ -90,6 +94,11 @@ export type OptScaOveralsOptionsType = {
     UseRandconf: boolean;
     MaxIter: number | null;
     Conv: number | null;
+    NumStarts: number | null;
+    Seed: number | null;
+    SetCorrelations: boolean;
+    PermutationTest: boolean;
+    NumPermutations: number | null;
 };
 
 export type OptScaOveralsOptionsProps = {
@@ -1,6 +1,7 @@
 import {
     MultivariateBootstrapType,
     MultivariateContrastType,
+    MultivariateCustomType,
     MultivariateEMMeansType,
     MultivariateMainType,
     MultivariateModelType,
@@ -141,6 +142,10 @@ export const MultivariateBootstrapDefault: MultivariateBootstrapType = {
     StrataVariables: null,
 };
 
+export const MultivariateCustomDefault: MultivariateCustomType = {
+    Tests: [],
+};
+
 export const MultivariateDefault: MultivariateType = {
     main: MultivariateMainDefault,
     model: MultivariateModelDefault,
@@ -151,4 +156,5 @@ export const MultivariateDefault: MultivariateType = {
     save: MultivariateSaveDefault,
     options: MultivariateOptionsDefault,
     bootstrap: MultivariateBootstrapDefault,
+    custom: MultivariateCustomDefault,
 };
@@ -1,6 +1,7 @@
 use serde::{ Deserialize, Serialize };
+use std::collections::HashMap;
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct MultivariateConfig {
     pub main: MainConfig,
     pub model: ModelDetails,
@@ -11,6 +12,8 @@ pub struct MultivariateConfig {
     pub save: SaveConfig,
     pub options: OptionsConfig,
     pub bootstrap: BootstrapConfig,
+    #[serde(default)]
+    pub custom: CustomHypothesisConfig,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -77,7 +80,7 @@ pub enum CIMethod {
     Sidak,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct MainConfig {
     #[serde(rename = "DepVar")]
     pub dep_var: Option<Vec<String>>,
@@ -117,6 +120,25 @@ pub struct ModelDetails {
     pub intercept: bool,
 }
 
+impl Default for ModelDetails {
+    fn default() -> Self {
+        ModelDetails {
+            non_cust: true,
+            custom: false,
+            build_custom_term: false,
+            factors_var: None,
+            build_term_method: Some(BuildTermMethod::Interaction),
+            factors_model: None,
+            terms_var: None,
+            cov_model: None,
+            random_model: None,
+            term_text: None,
+            sum_of_square_method: SumOfSquaresMethod::TypeIII,
+            intercept: true,
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ContrastConfig {
     #[serde(rename = "FactorList")]
@@ -129,6 +151,17 @@ pub struct ContrastConfig {
     pub first: bool,
 }
 
+impl Default for ContrastConfig {
+    fn default() -> Self {
+        ContrastConfig {
+            factor_list: None,
+            contrast_method: ContrastMethod::None,
+            last: true,
+            first: false,
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct PlotsConfig {
     #[serde(rename = "SrcList")]
@@ -161,6 +194,27 @@ pub struct PlotsConfig {
     pub y_axis_start_0: bool,
 }
 
+impl Default for PlotsConfig {
+    fn default() -> Self {
+        PlotsConfig {
+            src_list: None,
+            axis_list: None,
+            line_list: None,
+            plot_list: None,
+            fix_factor_vars: None,
+            rand_factor_vars: None,
+            line_chart_type: true,
+            bar_chart_type: false,
+            include_error_bars: false,
+            confidence_interval: true,
+            standard_error: false,
+            multiplier: Some(2),
+            include_ref_line_for_grand_mean: false,
+            y_axis_start_0: false,
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct PosthocConfig {
     #[serde(rename = "SrcList")]
@@ -215,6 +269,38 @@ pub struct PosthocConfig {
     pub dunc: bool,
 }
 
+impl Default for PosthocConfig {
+    fn default() -> Self {
+        PosthocConfig {
+            src_list: None,
+            fix_factor_vars: None,
+            lsd: false,
+            bonfe: false,
+            sidak: false,
+            scheffe: false,
+            regwf: false,
+            regwq: false,
+            snk: false,
+            tu: false,
+            tub: false,
+            dun: false,
+            hoc: false,
+            gabriel: false,
+            waller: false,
+            error_ratio: Some(100),
+            dunnett: false,
+            category_method: Some(CategoryMethod::Last),
+            twosided: true,
+            lt_control: false,
+            gt_control: false,
+            tam: false,
+            dunt: false,
+            games: false,
+            dunc: false,
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct EmmmeansConfig {
     #[serde(rename = "SrcList")]
@@ -227,6 +313,17 @@ pub struct EmmmeansConfig {
     pub confi_interval_method: Option<CIMethod>,
 }
 
+impl Default for EmmmeansConfig {
+    fn default() -> Self {
+        EmmmeansConfig {
+            src_list: None,
+            target_list: None,
+            comp_main_effect: false,
+            confi_interval_method: Some(CIMethod::LsdNone),
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct SaveConfig {
     #[serde(rename = "ResWeighted")]
@@ -261,6 +358,28 @@ pub struct SaveConfig {
     pub file_path: Option<String>,
 }
 
+impl Default for SaveConfig {
+    fn default() -> Self {
+        SaveConfig {
+            res_weighted: false,
+            pre_weighted: false,
+            std_statistics: false,
+            cooks_d: false,
+            leverage: false,
+            unstandardized_res: false,
+            weighted_res: false,
+            standardized_res: false,
+            studentized_res: false,
+            deleted_res: false,
+            coeff_stats: false,
+            new_dataset: true,
+            dataset_name: None,
+            write_new_dataset: false,
+            file_path: None,
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct OptionsConfig {
     #[serde(rename = "DescStats")]
@@ -293,6 +412,27 @@ pub struct OptionsConfig {
     pub transform_mat: bool,
 }
 
+impl Default for OptionsConfig {
+    fn default() -> Self {
+        OptionsConfig {
+            desc_stats: false,
+            est_effect_size: false,
+            obs_power: false,
+            param_est: false,
+            sscp_mat: false,
+            res_sscp_mat: false,
+            homogen_test: false,
+            spr_vs_level: false,
+            res_plot: false,
+            lack_of_fit: false,
+            general_fun: false,
+            sig_level: Some(0.05),
+            coefficient_matrix: false,
+            transform_mat: false,
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct BootstrapConfig {
     #[serde(rename = "PerformBootStrapping")]
@@ -318,3 +458,41 @@ pub struct BootstrapConfig {
     #[serde(rename = "StrataVariables")]
     pub strata_variables: Option<Vec<String>>,
 }
+
+impl Default for BootstrapConfig {
+    fn default() -> Self {
+        BootstrapConfig {
+            perform_boot_strapping: false,
+            num_of_samples: Some(1000),
+            seed: false,
+            seed_value: Some(200000),
+            level: Some(95.0),
+            percentile: true,
+            bca: false,
+            simple: true,
+            stratified: false,
+            variables: None,
+            strata_variables: None,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct CustomHypothesisConfig {
+    #[serde(rename = "Tests", default)]
+    pub tests: Vec<CustomHypothesisSpec>,
+}
+
+/// A user-defined hypothesis LBM = K. L rows and M columns are keyed by
+/// parameter name and dependent variable name respectively; omitted entries are zero.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CustomHypothesisSpec {
+    #[serde(rename = "Label", default)]
+    pub label: Option<String>,
+    #[serde(rename = "LMatrix", default)]
+    pub l_matrix: Vec<HashMap<String, f64>>,
+    #[serde(rename = "MMatrix", default)]
+    pub m_matrix: Option<Vec<HashMap<String, f64>>>,
+    #[serde(rename = "KMatrix", default)]
+    pub k_matrix: Option<Vec<Vec<f64>>>,
+}
@@ -26,6 +26,8 @@ pub struct MultivariateResult {
     pub homogeneous_subsets: Option<HashMap<String, HashMap<String, HomogeneousSubsets>>>,
     pub scatter_plot_matrices: Option<HashMap<String, ScatterPlotMatrix>>,
     pub profile_plots: Option<HashMap<String, PlotData>>,
+    pub bootstrap: Option<BootstrapResult>,
+    pub custom_hypothesis_tests: Option<Vec<CustomHypothesisResult>>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -347,3 +349,72 @@ pub struct MauchlyTestEntry {
     pub huynh_feldt_epsilon: f64,
     pub lower_bound_epsilon: f64,
 }
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CustomHypothesisResult {
+    pub label: String,
+    pub parameter_names: Vec<String>,
+    pub dependent_variables: Vec<String>,
+    pub l_matrix: Vec<Vec<f64>>,
+    pub m_matrix: Vec<Vec<f64>>,
+    pub k_matrix: Vec<Vec<f64>>,
+    pub transformed_variables: Vec<String>,
+    pub contrast_estimates: Vec<CustomContrastEstimate>,
+    pub multivariate_tests: HashMap<String, MultivariateTestEntry>,
+    pub univariate_tests: Vec<CustomUnivariateTest>,
+    pub hypothesis_sscp: Vec<Vec<f64>>,
+    pub error_sscp: Vec<Vec<f64>>,
+    pub hypothesis_df: usize,
+    pub error_df: usize,
+    pub notes: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CustomContrastEstimate {
+    pub contrast: String,
+    pub transformed_variable: String,
+    pub estimate: f64,
+    pub hypothesized_value: f64,
+    pub difference: f64,
+    pub std_error: f64,
+    pub t_value: f64,
+    pub significance: f64,
+    pub confidence_interval: ConfidenceInterval,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CustomUnivariateTest {
+    pub transformed_variable: String,
+    pub hypothesis_ss: f64,
+    pub error_ss: f64,
+    pub hypothesis_df: usize,
+    pub error_df: usize,
+    pub f_value: f64,
+    pub significance: f64,
+    pub partial_eta_squared: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BootstrapResult {
+    pub sampling_method: String,
+    pub num_samples: usize,
+    pub valid_samples: usize,
+    pub seed: u64,
+    pub confidence_level: f64,
+    pub parameter_estimates: HashMap<String, Vec<BootstrapEstimate>>,
+    pub emmeans: Option<HashMap<String, Vec<BootstrapEstimate>>>,
+    pub contrasts: Option<HashMap<String, Vec<BootstrapEstimate>>>,
+    pub multivariate_tests: Option<HashMap<String, Vec<BootstrapEstimate>>>,
+    pub notes: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BootstrapEstimate {
+    pub label: String,
+    pub estimate: f64,
+    pub bias: f64,
+    pub std_error: f64,
+    pub percentile_interval: Option<ConfidenceInterval>,
+    pub bca_interval: Option<ConfidenceInterval>,
+    pub valid_samples: usize,
+}
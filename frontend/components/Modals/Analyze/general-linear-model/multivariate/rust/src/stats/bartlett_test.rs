@@ -87,12 +87,8 @@ pub fn calculate_bartlett_test(
                 approx_chi_square: chi_square,
                 df,
                 significance,
-                description: Some(
-                    format!(
-                        "Tests the null hypothesis that the correlation matrix is an identity matrix."
-                    )
-                ),
-                design: Some(format!("Bartlett's Test of Sphericity")),
+                description: Some("Tests the null hypothesis that the correlation matrix is an identity matrix.".to_string()),
+                design: Some("Bartlett's Test of Sphericity".to_string()),
             })
         }
         Err(e) => Err(format!("Error calculating determinant: {}", e)),
@@ -235,12 +231,8 @@ pub fn calculate_bartlett_test_from_residual(
                 approx_chi_square: chi_square,
                 df,
                 significance,
-                description: Some(
-                    format!(
-                        "Tests the null hypothesis that the residual covariance matrix is proportional to an identity matrix."
-                    )
-                ),
-                design: Some(format!("Bartlett's Test of Sphericity for Residual Matrix")),
+                description: Some("Tests the null hypothesis that the residual covariance matrix is proportional to an identity matrix.".to_string()),
+                design: Some("Bartlett's Test of Sphericity for Residual Matrix".to_string()),
             })
         }
         Err(e) => Err(format!("Error calculating determinant: {}", e)),
@@ -347,16 +347,15 @@ pub fn calculate_tests_between_subjects_effects(
 
 /// Helper functions for different types of Sum of Squares
 pub fn calculate_type_i_ss(
-    x_matrix: &Vec<Vec<f64>>,
-    y_vector: &Vec<f64>,
-    factor_cols: &Vec<usize>,
+    x_matrix: &[Vec<f64>],
+    y_vector: &[f64],
+    factor_cols: &[usize],
     _data: &AnalysisData,
     _config: &MultivariateConfig
 ) -> Result<f64, String> {
     // Type I SS (sequential) calculation
     // Simplified implementation - compute SS by fitting models with and without the factor
-    let n = y_vector.len();
-    let full_model_ss = fit_model_and_get_ss(&x_matrix, &y_vector)?;
+    let full_model_ss = fit_model_and_get_ss(x_matrix, y_vector)?;
 
     // Create reduced model without the factor columns
     let mut reduced_x = Vec::new();
@@ -370,18 +369,18 @@ pub fn calculate_type_i_ss(
         reduced_x.push(new_row);
     }
 
-    let reduced_model_ss = fit_model_and_get_ss(&reduced_x, &y_vector)?;
+    let reduced_model_ss = fit_model_and_get_ss(&reduced_x, y_vector)?;
 
     // Type I SS is the difference between full and reduced model SS
     Ok(reduced_model_ss - full_model_ss)
 }
 
 pub fn calculate_type_ii_ss(
-    x_matrix: &Vec<Vec<f64>>,
-    y_vector: &Vec<f64>,
+    x_matrix: &[Vec<f64>],
+    y_vector: &[f64],
     factor: &str,
-    factor_cols: &Vec<usize>,
-    data: &AnalysisData,
+    factor_cols: &[usize],
+    _data: &AnalysisData,
     config: &MultivariateConfig
 ) -> Result<f64, String> {
     // Type II SS calculation
@@ -409,18 +408,18 @@ pub fn calculate_type_ii_ss(
         reduced_x.push(new_row);
     }
 
-    let full_model_ss = fit_model_and_get_ss(&x_matrix, &y_vector)?;
-    let reduced_model_ss = fit_model_and_get_ss(&reduced_x, &y_vector)?;
+    let full_model_ss = fit_model_and_get_ss(x_matrix, y_vector)?;
+    let reduced_model_ss = fit_model_and_get_ss(&reduced_x, y_vector)?;
 
     // Type II SS is the difference between full and reduced model SS
     Ok(reduced_model_ss - full_model_ss)
 }
 
 pub fn calculate_type_iii_ss(
-    x_matrix: &Vec<Vec<f64>>,
-    y_vector: &Vec<f64>,
-    effect: &str,
-    effect_cols: &Vec<usize>,
+    x_matrix: &[Vec<f64>],
+    y_vector: &[f64],
+    _effect: &str,
+    effect_cols: &[usize],
     _data: &AnalysisData,
     _config: &MultivariateConfig
 ) -> Result<f64, String> {
@@ -428,7 +427,7 @@ pub fn calculate_type_iii_ss(
     // Adjusted for all other effects and orthogonal to any effects that contain it
 
     // Full model
-    let full_model_ss = fit_model_and_get_ss(&x_matrix, &y_vector)?;
+    let full_model_ss = fit_model_and_get_ss(x_matrix, y_vector)?;
 
     // Create reduced model without the effect columns
     let mut reduced_x = Vec::new();
@@ -442,17 +441,17 @@ pub fn calculate_type_iii_ss(
         reduced_x.push(new_row);
     }
 
-    let reduced_model_ss = fit_model_and_get_ss(&reduced_x, &y_vector)?;
+    let reduced_model_ss = fit_model_and_get_ss(&reduced_x, y_vector)?;
 
     // Type III SS is the difference between full and reduced model SS
     Ok(reduced_model_ss - full_model_ss)
 }
 
 pub fn calculate_type_iv_ss(
-    x_matrix: &Vec<Vec<f64>>,
-    y_vector: &Vec<f64>,
+    x_matrix: &[Vec<f64>],
+    y_vector: &[f64],
     effect: &str,
-    effect_cols: &Vec<usize>,
+    effect_cols: &[usize],
     data: &AnalysisData,
     config: &MultivariateConfig
 ) -> Result<f64, String> {
@@ -464,7 +463,7 @@ pub fn calculate_type_iv_ss(
 }
 
 /// Helper function to fit model and get error sum of squares
-pub fn fit_model_and_get_ss(x_matrix: &Vec<Vec<f64>>, y_vector: &Vec<f64>) -> Result<f64, String> {
+pub fn fit_model_and_get_ss(x_matrix: &[Vec<f64>], y_vector: &[f64]) -> Result<f64, String> {
     let x_mat = to_dmatrix(x_matrix);
     let y_vec = to_dvector(y_vector);
 
@@ -496,7 +495,7 @@ pub fn fit_model_and_get_ss(x_matrix: &Vec<Vec<f64>>, y_vector: &Vec<f64>) -> Re
 
 /// Helper function to get columns corresponding to a factor
 pub fn get_factor_columns(
-    x_matrix: &Vec<Vec<f64>>,
+    x_matrix: &[Vec<f64>],
     factor: &str,
     data: &AnalysisData,
     config: &MultivariateConfig
@@ -523,11 +522,6 @@ pub fn get_factor_columns(
         }
     }
 
-    // Skip covariates
-    if let Some(covariates) = &config.main.covar {
-        col_start += covariates.len();
-    }
-
     // If no columns found, this could mean it's an interaction term
     if factor_cols.is_empty() && factor.contains('*') {
         factor_cols = get_interaction_columns(x_matrix, factor, data, config)?;
@@ -538,7 +532,7 @@ pub fn get_factor_columns(
 
 /// Helper function to get columns corresponding to an interaction term
 pub fn get_interaction_columns(
-    x_matrix: &Vec<Vec<f64>>,
+    x_matrix: &[Vec<f64>],
     interaction_term: &str,
     data: &AnalysisData,
     config: &MultivariateConfig
@@ -1,16 +1,658 @@
-use crate::models::{ config::MultivariateConfig, data::AnalysisData };
+use std::collections::HashMap;
+
+use rand_mt::Mt64;
+use statrs::distribution::{ ContinuousCDF, Normal };
+
+use crate::models::{
+    config::{ ContrastMethod, MultivariateConfig },
+    data::{ AnalysisData, DataRecord, DataValue },
+    result::{ BootstrapEstimate, BootstrapResult, ConfidenceInterval },
+};
+
+use super::core::{
+    calculate_emmeans,
+    calculate_multivariate_tests,
+    calculate_parameter_estimates,
+    data_value_to_string,
+    get_factor_levels,
+};
+
+/// Section of the output a bootstrapped statistic belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum StatisticSection {
+    ParameterEstimates,
+    EMMeans,
+    Contrasts,
+    MultivariateTests,
+}
+
+/// Identifies one bootstrapped statistic (section, dependent variable or effect, label)
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct StatisticKey {
+    section: StatisticSection,
+    group: String,
+    label: String,
+}
 
 /// Perform bootstrap analysis
+///
+/// Cases are resampled with replacement (within strata when stratified sampling is
+/// selected), the multivariate GLM is refitted on every replicate and the bootstrap
+/// bias, standard error and percentile/BCa intervals are reported for the parameter
+/// estimates, estimated marginal means, contrasts and multivariate test statistics.
 pub fn perform_bootstrap_analysis(
     data: &AnalysisData,
     config: &MultivariateConfig
-) -> Result<(), String> {
-    // Bootstrap analysis would:
-    // 1. Create multiple bootstrap samples by sampling with replacement
-    // 2. Fit the model to each bootstrap sample
-    // 3. Calculate confidence intervals for parameters and test statistics
-
-    // This is a complex procedure that would require significant implementation
-    // For now, we'll return a placeholder success result
-    Ok(())
+) -> Result<BootstrapResult, String> {
+    let bootstrap = &config.bootstrap;
+
+    let n_cases = data.dependent_data
+        .first()
+        .map(|records| records.len())
+        .unwrap_or(0);
+    if n_cases < 2 {
+        return Err("Bootstrap requires at least two cases".to_string());
+    }
+
+    let num_samples = bootstrap.num_of_samples.unwrap_or(1000);
+    if num_samples < 1 {
+        return Err("Number of bootstrap samples must be positive".to_string());
+    }
+    let num_samples = num_samples as usize;
+
+    // The dialog stores the level as a percentage (e.g. 95)
+    let confidence_level = match bootstrap.level {
+        Some(level) if level > 1.0 && level < 100.0 => level / 100.0,
+        Some(level) if level > 0.0 && level < 1.0 => level,
+        _ => 0.95,
+    };
+    let alpha = 1.0 - confidence_level;
+
+    let seed = match (bootstrap.seed, bootstrap.seed_value) {
+        (true, Some(value)) => value as u64,
+        _ => rand::random::<u64>(),
+    };
+    let mut rng = Mt64::new(seed);
+
+    let mut notes = Vec::new();
+
+    // Strata: groups of case indices sharing the same values on the strata variables
+    let strata_variables = bootstrap.strata_variables.clone().unwrap_or_default();
+    let stratified = bootstrap.stratified && !strata_variables.is_empty();
+    if bootstrap.stratified && strata_variables.is_empty() {
+        notes.push(
+            "Stratified sampling was requested without strata variables; simple sampling was used.".to_string()
+        );
+    }
+    let strata = if stratified {
+        build_strata(data, &strata_variables, n_cases)
+    } else {
+        vec![(0..n_cases).collect::<Vec<usize>>()]
+    };
+
+    let original_levels = factor_level_counts(data, config);
+    let emmeans_requested = config.emmeans.target_list
+        .as_ref()
+        .is_some_and(|list| !list.trim().is_empty());
+
+    let original = collect_statistics(data, config, emmeans_requested)?;
+    if original.is_empty() {
+        return Err("No statistics available to bootstrap".to_string());
+    }
+
+    let mut replicates: HashMap<StatisticKey, Vec<f64>> = original
+        .iter()
+        .map(|(key, _)| (key.clone(), Vec::with_capacity(num_samples)))
+        .collect();
+
+    let mut valid_samples = 0;
+    for _ in 0..num_samples {
+        let mut indices = Vec::with_capacity(n_cases);
+        for stratum in &strata {
+            for _ in 0..stratum.len() {
+                indices.push(stratum[(rng.next_u64() % (stratum.len() as u64)) as usize]);
+            }
+        }
+        // Keeping the original case order preserves the order in which factor levels
+        // are first encountered, so the reference categories do not change.
+        indices.sort_unstable();
+
+        let sample = resample_data(data, &indices);
+
+        // A replicate that loses a factor level has a different parameterization
+        if factor_level_counts(&sample, config) != original_levels {
+            continue;
+        }
+
+        if let Ok(statistics) = collect_statistics(&sample, config, emmeans_requested) {
+            valid_samples += 1;
+            for (key, value) in statistics {
+                if value.is_finite() {
+                    if let Some(values) = replicates.get_mut(&key) {
+                        values.push(value);
+                    }
+                }
+            }
+        }
+    }
+
+    if valid_samples == 0 {
+        return Err("The model could not be fitted on any bootstrap sample".to_string());
+    }
+    if valid_samples < num_samples {
+        notes.push(
+            format!(
+                "{} of {} bootstrap samples could not be used because a factor level was absent or the model could not be fitted.",
+                num_samples - valid_samples,
+                num_samples
+            )
+        );
+    }
+
+    // Jackknife (leave-one-out) estimates for the BCa acceleration
+    let jackknife = if bootstrap.bca {
+        let mut jackknife: HashMap<StatisticKey, Vec<f64>> = HashMap::new();
+        for left_out in 0..n_cases {
+            let indices: Vec<usize> = (0..n_cases).filter(|&i| i != left_out).collect();
+            let sample = resample_data(data, &indices);
+            if factor_level_counts(&sample, config) != original_levels {
+                continue;
+            }
+            if let Ok(statistics) = collect_statistics(&sample, config, emmeans_requested) {
+                for (key, value) in statistics {
+                    if value.is_finite() {
+                        jackknife.entry(key).or_default().push(value);
+                    }
+                }
+            }
+        }
+        Some(jackknife)
+    } else {
+        None
+    };
+
+    let mut parameter_estimates: HashMap<String, Vec<BootstrapEstimate>> = HashMap::new();
+    let mut emmeans: HashMap<String, Vec<BootstrapEstimate>> = HashMap::new();
+    let mut contrasts: HashMap<String, Vec<BootstrapEstimate>> = HashMap::new();
+    let mut multivariate_tests: HashMap<String, Vec<BootstrapEstimate>> = HashMap::new();
+
+    for (key, estimate) in &original {
+        let values = &replicates[key];
+        let jackknife_values = jackknife.as_ref().and_then(|j| j.get(key));
+        let entry = summarize_replicates(
+            &key.label,
+            *estimate,
+            values,
+            jackknife_values.map(|v| v.as_slice()),
+            alpha,
+            bootstrap.percentile || !bootstrap.bca,
+            bootstrap.bca
+        );
+
+        let target = match key.section {
+            StatisticSection::ParameterEstimates => &mut parameter_estimates,
+            StatisticSection::EMMeans => &mut emmeans,
+            StatisticSection::Contrasts => &mut contrasts,
+            StatisticSection::MultivariateTests => &mut multivariate_tests,
+        };
+        target.entry(key.group.clone()).or_default().push(entry);
+    }
+
+    notes.push(
+        format!(
+            "Unless otherwise noted, bootstrap results are based on {} {} bootstrap samples.",
+            valid_samples,
+            if stratified { "stratified" } else { "simple" }
+        )
+    );
+
+    Ok(BootstrapResult {
+        sampling_method: (if stratified { "Stratified" } else { "Simple" }).to_string(),
+        num_samples,
+        valid_samples,
+        seed,
+        confidence_level,
+        parameter_estimates,
+        emmeans: if emmeans.is_empty() { None } else { Some(emmeans) },
+        contrasts: if contrasts.is_empty() { None } else { Some(contrasts) },
+        multivariate_tests: if multivariate_tests.is_empty() {
+            None
+        } else {
+            Some(multivariate_tests)
+        },
+        notes,
+    })
+}
+
+/// Fit the model on a data set and collect every statistic that is bootstrapped
+fn collect_statistics(
+    data: &AnalysisData,
+    config: &MultivariateConfig,
+    emmeans_requested: bool
+) -> Result<Vec<(StatisticKey, f64)>, String> {
+    let mut statistics = Vec::new();
+
+    let estimates = calculate_parameter_estimates(data, config)?;
+    let mut dependent_vars: Vec<&String> = estimates.estimates.keys().collect();
+    dependent_vars.sort();
+    for dep_var in dependent_vars {
+        for entry in &estimates.estimates[dep_var] {
+            statistics.push((
+                StatisticKey {
+                    section: StatisticSection::ParameterEstimates,
+                    group: dep_var.clone(),
+                    label: entry.parameter.clone(),
+                },
+                entry.b,
+            ));
+        }
+    }
+
+    if emmeans_requested {
+        let means = calculate_emmeans(data, config)?;
+        let mut dependent_vars: Vec<&String> = means.keys().collect();
+        dependent_vars.sort();
+        for dep_var in dependent_vars {
+            for mean in &means[dep_var] {
+                statistics.push((
+                    StatisticKey {
+                        section: StatisticSection::EMMeans,
+                        group: dep_var.clone(),
+                        label: format!("{}={}", mean.factor_name, mean.factor_value),
+                    },
+                    mean.mean,
+                ));
+            }
+        }
+    }
+
+    if config.contrast.contrast_method != ContrastMethod::None {
+        statistics.extend(collect_contrast_statistics(data, config)?);
+    }
+
+    // Multivariate tests are optional: a failure here should not drop the replicate
+    if let Ok(tests) = calculate_multivariate_tests(data, config) {
+        let mut effects: Vec<&String> = tests.effects.keys().collect();
+        effects.sort();
+        for effect in effects {
+            let mut test_names: Vec<&String> = tests.effects[effect].keys().collect();
+            test_names.sort();
+            for test_name in test_names {
+                statistics.push((
+                    StatisticKey {
+                        section: StatisticSection::MultivariateTests,
+                        group: effect.clone(),
+                        label: test_name.clone(),
+                    },
+                    tests.effects[effect][test_name].value,
+                ));
+            }
+        }
+    }
+
+    Ok(statistics)
+}
+
+/// Contrast estimates (per dependent variable) built from the estimated marginal means
+/// of each fixed factor, using the contrast method selected in the Contrasts dialog
+fn collect_contrast_statistics(
+    data: &AnalysisData,
+    config: &MultivariateConfig
+) -> Result<Vec<(StatisticKey, f64)>, String> {
+    let factors = match &config.main.fix_factor {
+        Some(factors) if !factors.is_empty() => factors,
+        _ => {
+            return Ok(Vec::new());
+        }
+    };
+
+    let mut emmeans_config = config.clone();
+    emmeans_config.emmeans.target_list = Some(factors.join(" "));
+    let means = calculate_emmeans(data, &emmeans_config)?;
+
+    let mut statistics = Vec::new();
+    let mut dependent_vars: Vec<&String> = means.keys().collect();
+    dependent_vars.sort();
+    for dep_var in dependent_vars {
+        for factor in factors {
+            let levels = get_factor_levels(data, factor)?;
+            let level_means: Vec<f64> = levels
+                .iter()
+                .map(|level| {
+                    means[dep_var]
+                        .iter()
+                        .find(|m| &m.factor_name == factor && &m.factor_value == level)
+                        .map(|m| m.mean)
+                        .unwrap_or(f64::NAN)
+                })
+                .collect();
+
+            for (label, coefficients) in contrast_rows(&levels, config) {
+                let estimate = coefficients
+                    .iter()
+                    .zip(level_means.iter())
+                    .map(|(c, m)| c * m)
+                    .sum::<f64>();
+                statistics.push((
+                    StatisticKey {
+                        section: StatisticSection::Contrasts,
+                        group: dep_var.clone(),
+                        label: format!("{}: {}", factor, label),
+                    },
+                    estimate,
+                ));
+            }
+        }
+    }
+
+    Ok(statistics)
+}
+
+/// Contrast coefficients applied to the level means of a factor
+fn contrast_rows(levels: &[String], config: &MultivariateConfig) -> Vec<(String, Vec<f64>)> {
+    let k = levels.len();
+    if k < 2 {
+        return Vec::new();
+    }
+    let reference = if config.contrast.first { 0 } else { k - 1 };
+    let mut rows = Vec::new();
+
+    match config.contrast.contrast_method {
+        ContrastMethod::None => {}
+        ContrastMethod::Deviation => {
+            for i in (0..k).filter(|&i| i != reference) {
+                let mut row = vec![-1.0 / (k as f64); k];
+                row[i] += 1.0;
+                rows.push((format!("Level {} vs. Mean", levels[i]), row));
+            }
+        }
+        ContrastMethod::Simple => {
+            for i in (0..k).filter(|&i| i != reference) {
+                let mut row = vec![0.0; k];
+                row[i] = 1.0;
+                row[reference] = -1.0;
+                rows.push((format!("Level {} vs. Level {}", levels[i], levels[reference]), row));
+            }
+        }
+        ContrastMethod::Difference => {
+            for i in 1..k {
+                let mut row = vec![0.0; k];
+                row[i] = 1.0;
+                for value in row.iter_mut().take(i) {
+                    *value = -1.0 / (i as f64);
+                }
+                rows.push((format!("Level {} vs. Previous", levels[i]), row));
+            }
+        }
+        ContrastMethod::Helmert => {
+            for i in 0..k - 1 {
+                let mut row = vec![0.0; k];
+                row[i] = 1.0;
+                let later = (k - i - 1) as f64;
+                for value in row.iter_mut().skip(i + 1) {
+                    *value = -1.0 / later;
+                }
+                rows.push((format!("Level {} vs. Later", levels[i]), row));
+            }
+        }
+        ContrastMethod::Repeated => {
+            for i in 0..k - 1 {
+                let mut row = vec![0.0; k];
+                row[i] = 1.0;
+                row[i + 1] = -1.0;
+                rows.push((format!("Level {} vs. Level {}", levels[i], levels[i + 1]), row));
+            }
+        }
+        ContrastMethod::Polynomial => {
+            // Orthonormal polynomials over equally spaced levels (Gram-Schmidt)
+            let centered: Vec<f64> = (0..k).map(|i| (i as f64) - ((k - 1) as f64) / 2.0).collect();
+            let mut basis: Vec<Vec<f64>> = vec![vec![1.0 / (k as f64).sqrt(); k]];
+            for degree in 1..k {
+                let mut row: Vec<f64> = centered
+                    .iter()
+                    .map(|x| x.powi(degree as i32))
+                    .collect();
+                for previous in &basis {
+                    let projection = row
+                        .iter()
+                        .zip(previous.iter())
+                        .map(|(a, b)| a * b)
+                        .sum::<f64>();
+                    for (value, p) in row.iter_mut().zip(previous.iter()) {
+                        *value -= projection * p;
+                    }
+                }
+                let norm = row
+                    .iter()
+                    .map(|v| v * v)
+                    .sum::<f64>()
+                    .sqrt();
+                if norm > 1e-12 {
+                    row.iter_mut().for_each(|v| {
+                        *v /= norm;
+                    });
+                }
+                let name = match degree {
+                    1 => "Linear".to_string(),
+                    2 => "Quadratic".to_string(),
+                    3 => "Cubic".to_string(),
+                    d => format!("Order {}", d),
+                };
+                basis.push(row.clone());
+                rows.push((name, row));
+            }
+        }
+    }
+
+    rows
+}
+
+/// Bias, standard error and intervals from the replicate values of one statistic
+fn summarize_replicates(
+    label: &str,
+    estimate: f64,
+    values: &[f64],
+    jackknife: Option<&[f64]>,
+    alpha: f64,
+    percentile: bool,
+    bca: bool
+) -> BootstrapEstimate {
+    let b = values.len();
+    if b < 2 {
+        return BootstrapEstimate {
+            label: label.to_string(),
+            estimate,
+            bias: f64::NAN,
+            std_error: f64::NAN,
+            percentile_interval: None,
+            bca_interval: None,
+            valid_samples: b,
+        };
+    }
+
+    let mean = values.iter().sum::<f64>() / (b as f64);
+    let variance =
+        values
+            .iter()
+            .map(|v| (v - mean).powi(2))
+            .sum::<f64>() / ((b - 1) as f64);
+
+    let mut sorted = values.to_vec();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+
+    let percentile_interval = if percentile {
+        Some(ConfidenceInterval {
+            lower_bound: empirical_quantile(&sorted, alpha / 2.0),
+            upper_bound: empirical_quantile(&sorted, 1.0 - alpha / 2.0),
+        })
+    } else {
+        None
+    };
+
+    let bca_interval = if bca {
+        jackknife.and_then(|jack| bca_interval(estimate, &sorted, jack, alpha))
+    } else {
+        None
+    };
+
+    BootstrapEstimate {
+        label: label.to_string(),
+        estimate,
+        bias: mean - estimate,
+        std_error: variance.sqrt(),
+        percentile_interval,
+        bca_interval,
+        valid_samples: b,
+    }
+}
+
+/// Bias-corrected and accelerated interval (Efron, 1987)
+fn bca_interval(
+    estimate: f64,
+    sorted: &[f64],
+    jackknife: &[f64],
+    alpha: f64
+) -> Option<ConfidenceInterval> {
+    let normal = Normal::new(0.0, 1.0).ok()?;
+    let b = sorted.len() as f64;
+
+    let below = sorted
+        .iter()
+        .filter(|&&v| v < estimate)
+        .count() as f64;
+    let proportion = (below / b).clamp(0.5 / b, 1.0 - 0.5 / b);
+    let z0 = normal.inverse_cdf(proportion);
+
+    if jackknife.len() < 2 {
+        return None;
+    }
+    let jack_mean = jackknife.iter().sum::<f64>() / (jackknife.len() as f64);
+    let numerator = jackknife
+        .iter()
+        .map(|v| (jack_mean - v).powi(3))
+        .sum::<f64>();
+    let denominator =
+        6.0 *
+        jackknife
+            .iter()
+            .map(|v| (jack_mean - v).powi(2))
+            .sum::<f64>()
+            .powf(1.5);
+    let acceleration = if denominator > 0.0 { numerator / denominator } else { 0.0 };
+
+    let adjusted = |probability: f64| {
+        let z = normal.inverse_cdf(probability);
+        normal.cdf(z0 + (z0 + z) / (1.0 - acceleration * (z0 + z)))
+    };
+
+    let lower = adjusted(alpha / 2.0);
+    let upper = adjusted(1.0 - alpha / 2.0);
+    if !lower.is_finite() || !upper.is_finite() {
+        return None;
+    }
+
+    Some(ConfidenceInterval {
+        lower_bound: empirical_quantile(sorted, lower),
+        upper_bound: empirical_quantile(sorted, upper),
+    })
+}
+
+/// Linearly interpolated quantile of sorted values
+fn empirical_quantile(sorted: &[f64], probability: f64) -> f64 {
+    if sorted.is_empty() {
+        return f64::NAN;
+    }
+    let position = probability.clamp(0.0, 1.0) * ((sorted.len() - 1) as f64);
+    let lower = position.floor() as usize;
+    let upper = position.ceil() as usize;
+    let fraction = position - (lower as f64);
+    sorted[lower] + fraction * (sorted[upper] - sorted[lower])
+}
+
+/// Group case indices by the combined values of the strata variables
+fn build_strata(data: &AnalysisData, strata_variables: &[String], n_cases: usize) -> Vec<Vec<usize>> {
+    let mut order: Vec<String> = Vec::new();
+    let mut groups: HashMap<String, Vec<usize>> = HashMap::new();
+
+    for case in 0..n_cases {
+        let key = strata_variables
+            .iter()
+            .map(|variable| {
+                find_case_value(data, case, variable)
+                    .map(data_value_to_string)
+                    .unwrap_or_default()
+            })
+            .collect::<Vec<String>>()
+            .join("|");
+        if !groups.contains_key(&key) {
+            order.push(key.clone());
+        }
+        groups.entry(key).or_default().push(case);
+    }
+
+    order
+        .into_iter()
+        .filter_map(|key| groups.remove(&key))
+        .collect()
+}
+
+/// Look up the value of a variable for a case in any of the data sets
+fn find_case_value<'a>(
+    data: &'a AnalysisData,
+    case: usize,
+    variable: &str
+) -> Option<&'a DataValue> {
+    let sets = data.dependent_data
+        .iter()
+        .chain(data.fix_factor_data.iter())
+        .chain(data.covariate_data.iter().flatten())
+        .chain(data.wls_data.iter().flatten());
+    for records in sets {
+        if let Some(value) = records.get(case).and_then(|record| record.values.get(variable)) {
+            return Some(value);
+        }
+    }
+    None
+}
+
+/// Build a data set from the given case indices, keeping all data sets aligned
+fn resample_data(data: &AnalysisData, indices: &[usize]) -> AnalysisData {
+    let pick = |sets: &Vec<Vec<DataRecord>>| -> Vec<Vec<DataRecord>> {
+        sets.iter()
+            .map(|records| {
+                indices
+                    .iter()
+                    .filter_map(|&i| records.get(i).cloned())
+                    .collect()
+            })
+            .collect()
+    };
+
+    AnalysisData {
+        dependent_data: pick(&data.dependent_data),
+        fix_factor_data: pick(&data.fix_factor_data),
+        covariate_data: data.covariate_data.as_ref().map(pick),
+        wls_data: data.wls_data.as_ref().map(pick),
+        dependent_data_defs: data.dependent_data_defs.clone(),
+        fix_factor_data_defs: data.fix_factor_data_defs.clone(),
+        covariate_data_defs: data.covariate_data_defs.clone(),
+        wls_data_defs: data.wls_data_defs.clone(),
+    }
+}
+
+/// Number of observed levels for each fixed factor
+fn factor_level_counts(data: &AnalysisData, config: &MultivariateConfig) -> Vec<usize> {
+    config.main.fix_factor
+        .as_ref()
+        .map(|factors| {
+            factors
+                .iter()
+                .map(|factor| {
+                    get_factor_levels(data, factor)
+                        .map(|levels| levels.len())
+                        .unwrap_or(0)
+                })
+                .collect()
+        })
+        .unwrap_or_default()
 }
@@ -35,7 +35,6 @@ pub fn calculate_box_test(
     }
 
     let dependent_vars = config.main.dep_var.as_ref().unwrap();
-    let factors = config.main.fix_factor.as_ref().unwrap();
 
     // Step 2: Get all factor combinations to identify groups
     let combinations = get_factor_combinations(data, config)?;
@@ -47,8 +46,6 @@ pub fn calculate_box_test(
     let mut group_covariance_matrices: Vec<
         (HashMap<String, String>, DMatrix<f64>, usize)
     > = Vec::new();
-    let mut total_n = 0;
-
     for combo in &combinations {
         let mut group_data: Vec<Vec<f64>> = Vec::new();
 
@@ -78,7 +75,6 @@ pub fn calculate_box_test(
         // Need at least n > p (number of variables) for a valid covariance matrix
         if group_data.len() > dependent_vars.len() {
             let n = group_data.len();
-            total_n += n;
 
             // Calculate covariance matrix for this group
             let cov_matrix = calculate_covariance_matrix(&group_data);
@@ -105,20 +101,19 @@ pub fn calculate_box_test(
 
     // Step 5: Calculate Box's M statistic
     let mut box_m = 0.0;
-    let mut ln_det_pooled = 0.0;
 
     // Try to get determinant of pooled matrix
-    match matrix_determinant(&from_dmatrix(&pooled_cov_matrix)) {
+    let ln_det_pooled = match matrix_determinant(&from_dmatrix(&pooled_cov_matrix)) {
         Ok(det) => {
             if det <= 0.0 {
                 return Err("Pooled covariance matrix is singular".to_string());
             }
-            ln_det_pooled = det.ln();
+            det.ln()
         }
         Err(e) => {
             return Err(format!("Error calculating determinant: {}", e));
         }
-    }
+    };
 
     for (_, cov_matrix, n) in &group_covariance_matrices {
         let df = n - 1;
@@ -162,7 +157,7 @@ pub fn calculate_box_test(
         let f_multiplier = ((g as f64) - 1.0) * ((p as f64) + 1.0) * ((p as f64) / 2.0);
         f_statistic = (box_m * (1.0 - c1 - c2 / box_m)) / f_multiplier;
         df1 = (p * (p + 1) * (g - 1)) / 2;
-        df2 = ((df1 as f64) * (1.0 - c1 - c2 / box_m)).ceil() as f64;
+        df2 = ((df1 as f64) * (1.0 - c1 - c2 / box_m)).ceil();
     } else {
         // Use chi-square approximation
         f_statistic = box_m / c1;
@@ -185,12 +180,8 @@ pub fn calculate_box_test(
         df1,
         df2,
         significance,
-        design: Some(format!("Box's M Test of Equality of Covariance Matrices")),
-        description: Some(
-            format!(
-                "Tests the null hypothesis that the observed covariance matrices of the dependent variables are equal across groups."
-            )
-        ),
+        design: Some("Box's M Test of Equality of Covariance Matrices".to_string()),
+        description: Some("Tests the null hypothesis that the observed covariance matrices of the dependent variables are equal across groups.".to_string()),
     })
 }
 
@@ -63,7 +63,7 @@ pub fn calculate_t_critical(df: usize, alpha: f64) -> f64 {
     }
 
     StudentsT::new(0.0, 1.0, df as f64)
-        .and_then(|dist| Ok(dist.inverse_cdf(1.0 - alpha)))
+        .map(|dist| dist.inverse_cdf(1.0 - alpha))
         .unwrap_or_else(|_| {
             // Fallback to bisection method
             let dist = StudentsT::new(0.0, 1.0, df as f64).unwrap();
@@ -165,7 +165,7 @@ pub fn f_distribution_cdf(x: f64, df1: f64, df2: f64) -> f64 {
         .unwrap_or_else(|_| {
             // Fallback approximation
             let z = (df1 * x) / (df1 * x + df2);
-            z.min(1.0).max(0.0)
+            z.clamp(0.0, 1.0)
         })
 }
 
@@ -506,8 +506,7 @@ pub fn get_level_values_adjusted(
     values: &[f64],
     data: &AnalysisData,
     factor: &str,
-    level: &str,
-    dep_var_name: &str
+    level: &str
 ) -> Result<Vec<f64>, String> {
     let mut level_values = Vec::new();
     let mut i = 0;
@@ -738,7 +737,7 @@ pub fn build_design_matrix_and_response(
                             // Create dummy variables based on the factor levels
                             let factor_value = record.values
                                 .get(factor)
-                                .map(|v| data_value_to_string(v))
+                                .map(data_value_to_string)
                                 .unwrap_or_default();
 
                             // Use effect coding or dummy coding based on contrast type
@@ -748,7 +747,7 @@ pub fn build_design_matrix_and_response(
                                     for level in &levels[0..levels.len() - 1] {
                                         if &factor_value == level {
                                             x_row.push(1.0);
-                                        } else if &factor_value == &levels[levels.len() - 1] {
+                                        } else if factor_value == levels[levels.len() - 1] {
                                             x_row.push(-1.0);
                                         } else {
                                             x_row.push(0.0);
@@ -12,7 +12,6 @@ pub fn calculate_contrast_coefficients(
     config: &MultivariateConfig
 ) -> Result<ContrastCoefficients, String> {
     let mut parameter = Vec::new();
-    let mut coefficients = Vec::new();
 
     // Add intercept parameter
     if config.model.intercept {
@@ -31,7 +30,7 @@ pub fn calculate_contrast_coefficients(
     }
 
     // Initialize coefficients with zeros
-    coefficients = vec![0.0; parameter.len()];
+    let mut coefficients = vec![0.0; parameter.len()];
 
     // Set intercept coefficient if exists
     if config.model.intercept {
@@ -107,24 +106,24 @@ pub fn calculate_contrast_coefficients(
 
                         // Linear contrast
                         let linear_weights = generate_polynomial_contrast(num_levels, 1);
-                        for j in 0..num_levels {
-                            coefficients[param_idx + j] = linear_weights[j];
-                        }
+                        coefficients[param_idx..param_idx + num_levels].copy_from_slice(
+                            &linear_weights
+                        );
 
                         if num_levels > 2 {
                             // Quadratic contrast
                             let quadratic_weights = generate_polynomial_contrast(num_levels, 2);
-                            for j in 0..num_levels {
-                                coefficients[param_idx + j] = quadratic_weights[j];
-                            }
+                            coefficients[param_idx..param_idx + num_levels].copy_from_slice(
+                                &quadratic_weights
+                            );
                         }
 
                         if num_levels > 3 {
                             // Cubic contrast
                             let cubic_weights = generate_polynomial_contrast(num_levels, 3);
-                            for j in 0..num_levels {
-                                coefficients[param_idx + j] = cubic_weights[j];
-                            }
+                            coefficients[param_idx..param_idx + num_levels].copy_from_slice(
+                                &cubic_weights
+                            );
                         }
                     }
                 }
@@ -151,16 +150,16 @@ fn generate_polynomial_contrast(level_count: usize, degree: usize) -> Vec<f64> {
         let scale = 1.0 / denominator.sqrt();
 
         // For equally spaced values from -1 to 1
-        for i in 0..level_count {
+        for (i, contrast) in contrasts.iter_mut().enumerate() {
             let x = -1.0 + (2.0 * (i as f64)) / ((level_count - 1) as f64);
-            contrasts[i] = x * scale;
+            *contrast = x * scale;
         }
     } else {
         // Higher degree polynomials would need Gram-Schmidt orthogonalization
         // Simplified implementation for higher degrees
-        for i in 0..level_count {
+        for (i, contrast) in contrasts.iter_mut().enumerate() {
             let x = -1.0 + (2.0 * (i as f64)) / ((level_count - 1) as f64);
-            contrasts[i] = x.powi(degree as i32);
+            *contrast = x.powi(degree as i32);
         }
 
         // Normalize
@@ -170,8 +169,8 @@ fn generate_polynomial_contrast(level_count: usize, degree: usize) -> Vec<f64> {
             .sum::<f64>()
             .sqrt();
         if sum_sq > 0.0 {
-            for i in 0..level_count {
-                contrasts[i] /= sum_sq;
+            for contrast in contrasts.iter_mut() {
+                *contrast /= sum_sq;
             }
         }
     }
@@ -5,6 +5,7 @@ pub use crate::stats::bootstrap::*;
 pub use crate::stats::box_m_test::*;
 pub use crate::stats::common::*;
 pub use crate::stats::contrast_coefficients::*;
+pub use crate::stats::custom_hypothesis::*;
 pub use crate::stats::descriptive_statistics::*;
 pub use crate::stats::emmeans::*;
 pub use crate::stats::estimable_function::*;
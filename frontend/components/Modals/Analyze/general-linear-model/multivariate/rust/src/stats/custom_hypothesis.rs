@@ -0,0 +1,456 @@
+use std::collections::HashMap;
+
+use nalgebra::{ DMatrix, DVector };
+use statrs::distribution::{ ContinuousCDF, FisherSnedecor };
+
+use crate::models::{
+    config::{ CustomHypothesisSpec, MultivariateConfig },
+    data::AnalysisData,
+    result::{
+        ConfidenceInterval,
+        CustomContrastEstimate,
+        CustomHypothesisResult,
+        CustomUnivariateTest,
+        MultivariateTestEntry,
+    },
+};
+
+use super::core::{
+    build_design_matrix_and_response,
+    calculate_observed_power,
+    calculate_t_critical,
+    calculate_t_significance,
+    from_dmatrix,
+    generate_parameter_names,
+    to_dmatrix,
+};
+
+/// Multivariate least-squares fit shared by all custom hypotheses
+struct MultivariateFit {
+    parameter_names: Vec<String>,
+    dependent_vars: Vec<String>,
+    b_matrix: DMatrix<f64>,
+    xtx_inv: DMatrix<f64>,
+    e_matrix: DMatrix<f64>,
+    error_df: usize,
+}
+
+/// Calculate user-defined hypothesis tests of the form LBM = K
+///
+/// L selects linear combinations of the model parameters, M transforms the dependent
+/// variables (identity when omitted) and K holds the hypothesized values (zero when
+/// omitted). For each hypothesis the contrast estimates LBM - K with standard errors,
+/// the four multivariate test statistics and univariate F-tests for every transformed
+/// variable are reported.
+pub fn calculate_custom_hypothesis_tests(
+    data: &AnalysisData,
+    config: &MultivariateConfig
+) -> Result<Vec<CustomHypothesisResult>, String> {
+    if config.custom.tests.is_empty() {
+        return Err("No custom hypothesis tests specified".to_string());
+    }
+
+    let fit = fit_multivariate_model(data, config)?;
+    let alpha = config.options.sig_level.unwrap_or(0.05);
+
+    config.custom.tests
+        .iter()
+        .enumerate()
+        .map(|(test_idx, spec)| {
+            let label = spec.label
+                .as_ref()
+                .filter(|l| !l.trim().is_empty())
+                .cloned()
+                .unwrap_or_else(|| format!("Custom Hypothesis {}", test_idx + 1));
+            evaluate_custom_hypothesis(&fit, spec, label, alpha)
+        })
+        .collect()
+}
+
+/// Fit every dependent variable on the common design matrix and collect B, (X'X)^-1 and E
+fn fit_multivariate_model(
+    data: &AnalysisData,
+    config: &MultivariateConfig
+) -> Result<MultivariateFit, String> {
+    let dependent_vars = data.dependent_data_defs
+        .iter()
+        .flat_map(|defs| defs.iter().map(|def| def.name.clone()))
+        .collect::<Vec<String>>();
+    if dependent_vars.is_empty() {
+        return Err("No dependent variables specified".to_string());
+    }
+
+    let parameter_names = generate_parameter_names(data, config)?;
+
+    let mut x_mat: Option<DMatrix<f64>> = None;
+    let mut y_columns = Vec::with_capacity(dependent_vars.len());
+    for dep_var in &dependent_vars {
+        let (x_matrix, y_vector) = build_design_matrix_and_response(data, config, dep_var)?;
+        if x_matrix.is_empty() {
+            return Err(format!("No valid cases for dependent variable {}", dep_var));
+        }
+        match &x_mat {
+            Some(existing) if existing.nrows() != y_vector.len() => {
+                return Err(
+                    "Custom hypothesis tests require the same cases for every dependent variable".to_string()
+                );
+            }
+            Some(_) => {}
+            None => {
+                x_mat = Some(to_dmatrix(&x_matrix));
+            }
+        }
+        y_columns.push(DVector::from_vec(y_vector));
+    }
+
+    let x_mat = x_mat.unwrap();
+    let y_mat = DMatrix::from_columns(&y_columns);
+    let n = x_mat.nrows();
+    let p = x_mat.ncols();
+    if p != parameter_names.len() {
+        return Err(
+            format!(
+                "Design matrix has {} columns but {} parameter names were generated",
+                p,
+                parameter_names.len()
+            )
+        );
+    }
+    if n <= p {
+        return Err("Not enough cases to estimate the error SSCP matrix".to_string());
+    }
+
+    let xtx_inv = (x_mat.transpose() * &x_mat)
+        .try_inverse()
+        .ok_or_else(|| "Could not invert X'X matrix - possibly due to multicollinearity".to_string())?;
+    let b_matrix = &xtx_inv * x_mat.transpose() * &y_mat;
+    let residuals = &y_mat - &x_mat * &b_matrix;
+    let e_matrix = residuals.transpose() * &residuals;
+
+    Ok(MultivariateFit {
+        parameter_names,
+        dependent_vars,
+        b_matrix,
+        xtx_inv,
+        e_matrix,
+        error_df: n - p,
+    })
+}
+
+/// L, M and K matrices of one hypothesis
+type HypothesisMatrices = (DMatrix<f64>, DMatrix<f64>, DMatrix<f64>);
+
+/// Build the L, M and K matrices of a hypothesis from their name-keyed specification
+fn build_lmk_matrices(
+    fit: &MultivariateFit,
+    spec: &CustomHypothesisSpec,
+    label: &str
+) -> Result<HypothesisMatrices, String> {
+    if spec.l_matrix.is_empty() {
+        return Err(format!("Custom hypothesis '{}' has no L matrix rows", label));
+    }
+
+    let mut l_matrix = DMatrix::zeros(spec.l_matrix.len(), fit.parameter_names.len());
+    for (row_idx, row) in spec.l_matrix.iter().enumerate() {
+        for (name, coefficient) in row {
+            let col_idx = fit.parameter_names
+                .iter()
+                .position(|param| param == name.trim())
+                .ok_or_else(||
+                    format!(
+                        "Custom hypothesis '{}' refers to unknown parameter '{}'. Available parameters: {}",
+                        label,
+                        name,
+                        fit.parameter_names.join(", ")
+                    )
+                )?;
+            l_matrix[(row_idx, col_idx)] = *coefficient;
+        }
+    }
+
+    let m_matrix = match &spec.m_matrix {
+        Some(columns) if !columns.is_empty() => {
+            let mut m_matrix = DMatrix::zeros(fit.dependent_vars.len(), columns.len());
+            for (col_idx, column) in columns.iter().enumerate() {
+                for (name, coefficient) in column {
+                    let row_idx = fit.dependent_vars
+                        .iter()
+                        .position(|dep_var| dep_var == name.trim())
+                        .ok_or_else(||
+                            format!(
+                                "Custom hypothesis '{}' refers to unknown dependent variable '{}' in the M matrix",
+                                label,
+                                name
+                            )
+                        )?;
+                    m_matrix[(row_idx, col_idx)] = *coefficient;
+                }
+            }
+            m_matrix
+        }
+        _ => DMatrix::identity(fit.dependent_vars.len(), fit.dependent_vars.len()),
+    };
+
+    let k_matrix = match &spec.k_matrix {
+        Some(rows) => {
+            if
+                rows.len() != l_matrix.nrows() ||
+                rows.iter().any(|row| row.len() != m_matrix.ncols())
+            {
+                return Err(
+                    format!(
+                        "Custom hypothesis '{}' requires a K matrix of {} rows by {} columns",
+                        label,
+                        l_matrix.nrows(),
+                        m_matrix.ncols()
+                    )
+                );
+            }
+            DMatrix::from_fn(rows.len(), m_matrix.ncols(), |i, j| rows[i][j])
+        }
+        None => DMatrix::zeros(l_matrix.nrows(), m_matrix.ncols()),
+    };
+
+    Ok((l_matrix, m_matrix, k_matrix))
+}
+
+fn evaluate_custom_hypothesis(
+    fit: &MultivariateFit,
+    spec: &CustomHypothesisSpec,
+    label: String,
+    alpha: f64
+) -> Result<CustomHypothesisResult, String> {
+    let (l_matrix, m_matrix, k_matrix) = build_lmk_matrices(fit, spec, &label)?;
+    let mut notes = Vec::new();
+
+    let transformed_variables: Vec<String> = if spec.m_matrix.as_ref().is_some_and(|m| !m.is_empty()) {
+        (1..=m_matrix.ncols()).map(|j| format!("T{}", j)).collect()
+    } else {
+        fit.dependent_vars.clone()
+    };
+
+    let error_df = fit.error_df;
+    let estimates = &l_matrix * &fit.b_matrix * &m_matrix;
+    let differences = &estimates - &k_matrix;
+    let l_xtx_inv_lt = &l_matrix * &fit.xtx_inv * l_matrix.transpose();
+    let e_transformed = m_matrix.transpose() * &fit.e_matrix * &m_matrix;
+
+    // Contrast estimates and standard errors for each L row and transformed variable
+    let t_critical = calculate_t_critical(error_df, alpha / 2.0);
+    let mut contrast_estimates = Vec::new();
+    for i in 0..l_matrix.nrows() {
+        for j in 0..m_matrix.ncols() {
+            let variance = (l_xtx_inv_lt[(i, i)] * e_transformed[(j, j)]) / (error_df as f64);
+            let std_error = variance.max(0.0).sqrt();
+            let t_value = if std_error > 0.0 { differences[(i, j)] / std_error } else { f64::NAN };
+            contrast_estimates.push(CustomContrastEstimate {
+                contrast: format!("L{}", i + 1),
+                transformed_variable: transformed_variables[j].clone(),
+                estimate: estimates[(i, j)],
+                hypothesized_value: k_matrix[(i, j)],
+                difference: differences[(i, j)],
+                std_error,
+                t_value,
+                significance: calculate_t_significance(error_df, t_value),
+                confidence_interval: ConfidenceInterval {
+                    lower_bound: differences[(i, j)] - t_critical * std_error,
+                    upper_bound: differences[(i, j)] + t_critical * std_error,
+                },
+            });
+        }
+    }
+
+    let hypothesis_df = l_xtx_inv_lt.rank(1e-10);
+    if hypothesis_df == 0 {
+        return Err(format!("The L matrix of custom hypothesis '{}' has rank zero", label));
+    }
+    if hypothesis_df < l_matrix.nrows() {
+        notes.push(
+            format!(
+                "The L matrix has {} rows but rank {}; the tests use {} hypothesis degrees of freedom.",
+                l_matrix.nrows(),
+                hypothesis_df,
+                hypothesis_df
+            )
+        );
+    }
+
+    let l_xtx_inv_lt_inv = l_xtx_inv_lt
+        .clone()
+        .pseudo_inverse(1e-12)
+        .map_err(|e| format!("Failed to invert L(X'X)^-1L' for '{}': {}", label, e))?;
+    let h_transformed = differences.transpose() * l_xtx_inv_lt_inv * &differences;
+
+    // Univariate F-tests on each transformed variable
+    let univariate_tests = (0..m_matrix.ncols())
+        .map(|j| {
+            let hypothesis_ss = h_transformed[(j, j)];
+            let error_ss = e_transformed[(j, j)];
+            let f_value = if error_ss > 0.0 {
+                hypothesis_ss / (hypothesis_df as f64) / (error_ss / (error_df as f64))
+            } else {
+                f64::NAN
+            };
+            CustomUnivariateTest {
+                transformed_variable: transformed_variables[j].clone(),
+                hypothesis_ss,
+                error_ss,
+                hypothesis_df,
+                error_df,
+                f_value,
+                significance: f_upper_tail(hypothesis_df as f64, error_df as f64, f_value),
+                partial_eta_squared: if hypothesis_ss + error_ss > 0.0 {
+                    hypothesis_ss / (hypothesis_ss + error_ss)
+                } else {
+                    0.0
+                },
+            }
+        })
+        .collect();
+
+    let multivariate_tests = match
+        multivariate_statistics(&h_transformed, &e_transformed, hypothesis_df, error_df, alpha)
+    {
+        Ok(tests) => tests,
+        Err(e) => {
+            notes.push(e);
+            HashMap::new()
+        }
+    };
+
+    Ok(CustomHypothesisResult {
+        label,
+        parameter_names: fit.parameter_names.clone(),
+        dependent_variables: fit.dependent_vars.clone(),
+        l_matrix: from_dmatrix(&l_matrix),
+        m_matrix: from_dmatrix(&m_matrix),
+        k_matrix: from_dmatrix(&k_matrix),
+        transformed_variables,
+        contrast_estimates,
+        multivariate_tests,
+        univariate_tests,
+        hypothesis_sscp: from_dmatrix(&h_transformed),
+        error_sscp: from_dmatrix(&e_transformed),
+        hypothesis_df,
+        error_df,
+        notes,
+    })
+}
+
+/// Pillai's trace, Wilks' lambda, Hotelling's trace and Roy's largest root from the
+/// eigenvalues of E^-1 H, with the usual F approximations
+fn multivariate_statistics(
+    h_matrix: &DMatrix<f64>,
+    e_matrix: &DMatrix<f64>,
+    hypothesis_df: usize,
+    error_df: usize,
+    alpha: f64
+) -> Result<HashMap<String, MultivariateTestEntry>, String> {
+    let t = h_matrix.nrows() as f64;
+    let q = hypothesis_df as f64;
+    let v = error_df as f64;
+
+    // Eigenvalues of E^-1 H via the symmetric form U'^-1 H U^-1 where E = U'U
+    let cholesky = e_matrix
+        .clone()
+        .cholesky()
+        .ok_or_else(|| "The transformed error SSCP matrix is not positive definite".to_string())?;
+    let l_inv = cholesky
+        .l()
+        .try_inverse()
+        .ok_or_else(|| "Failed to invert the Cholesky factor of the error SSCP matrix".to_string())?;
+    let symmetric = &l_inv * h_matrix * l_inv.transpose();
+    let eigenvalues: Vec<f64> = symmetric
+        .symmetric_eigen()
+        .eigenvalues.iter()
+        .map(|&lambda| lambda.max(0.0))
+        .collect();
+
+    let s = t.min(q);
+    let m = ((t - q).abs() - 1.0) / 2.0;
+    let n = (v - t - 1.0) / 2.0;
+    let is_exact = s == 1.0;
+
+    let make_entry = |value: f64, f: f64, df1: f64, df2: f64, partial_eta_squared: f64| {
+        MultivariateTestEntry {
+            value,
+            f,
+            hypothesis_df: df1,
+            error_df: df2,
+            significance: f_upper_tail(df1, df2, f),
+            partial_eta_squared,
+            noncent_parameter: f * df1,
+            observed_power: calculate_observed_power(df1 as usize, df2 as usize, f, alpha),
+            is_exact_statistic: is_exact,
+        }
+    };
+
+    let mut tests = HashMap::new();
+
+    let pillai: f64 = eigenvalues
+        .iter()
+        .map(|lambda| lambda / (1.0 + lambda))
+        .sum();
+    let pillai_df1 = s * (2.0 * m + s + 1.0);
+    let pillai_df2 = s * (2.0 * n + s + 1.0);
+    let pillai_f = (pillai_df2 / pillai_df1) * (pillai / (s - pillai));
+    tests.insert(
+        "Pillai's Trace".to_string(),
+        make_entry(pillai, pillai_f, pillai_df1, pillai_df2, pillai / s)
+    );
+
+    let wilks: f64 = eigenvalues
+        .iter()
+        .map(|lambda| 1.0 / (1.0 + lambda))
+        .product();
+    let rao_r = if t * t + q * q - 5.0 > 0.0 {
+        ((t * t * q * q - 4.0) / (t * t + q * q - 5.0)).sqrt()
+    } else {
+        1.0
+    };
+    let wilks_df1 = t * q;
+    let wilks_df2 = rao_r * (v - (t - q + 1.0) / 2.0) - (t * q - 2.0) / 2.0;
+    let wilks_root = wilks.powf(1.0 / rao_r);
+    let wilks_f = ((1.0 - wilks_root) / wilks_root) * (wilks_df2 / wilks_df1);
+    tests.insert(
+        "Wilks' Lambda".to_string(),
+        make_entry(wilks, wilks_f, wilks_df1, wilks_df2, 1.0 - wilks_root)
+    );
+
+    let hotelling: f64 = eigenvalues.iter().sum();
+    let hotelling_df1 = s * (2.0 * m + s + 1.0);
+    let hotelling_df2 = 2.0 * (s * n + 1.0);
+    let hotelling_f = (hotelling_df2 * hotelling) / (s * s * (2.0 * m + s + 1.0));
+    tests.insert(
+        "Hotelling's Trace".to_string(),
+        make_entry(
+            hotelling,
+            hotelling_f,
+            hotelling_df1,
+            hotelling_df2,
+            hotelling / s / (1.0 + hotelling / s)
+        )
+    );
+
+    let roy = eigenvalues.iter().cloned().fold(0.0, f64::max);
+    let roy_df1 = t.max(q);
+    let roy_df2 = v - roy_df1 + q;
+    let roy_f = (roy * roy_df2) / roy_df1;
+    tests.insert(
+        "Roy's Largest Root".to_string(),
+        make_entry(roy, roy_f, roy_df1, roy_df2, roy / (1.0 + roy))
+    );
+
+    Ok(tests)
+}
+
+/// Upper-tail F probability allowing fractional degrees of freedom
+fn f_upper_tail(df1: f64, df2: f64, f_value: f64) -> f64 {
+    if df1 <= 0.0 || df2 <= 0.0 || !f_value.is_finite() {
+        return f64::NAN;
+    }
+
+    FisherSnedecor::new(df1, df2)
+        .map(|dist| 1.0 - dist.cdf(f_value.max(0.0)))
+        .unwrap_or(f64::NAN)
+}
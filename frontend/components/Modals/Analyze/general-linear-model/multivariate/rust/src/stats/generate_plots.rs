@@ -1,4 +1,4 @@
-use std::{ collections::HashMap, f32::consts::E };
+use std::collections::HashMap;
 
 use crate::models::{
     config::MultivariateConfig,
@@ -33,7 +33,7 @@ pub fn generate_plots(
     if config.plots.line_chart_type || config.plots.bar_chart_type {
         for dep_var in &dependent_vars {
             if let Some(factors) = &config.plots.src_list {
-                if factors.len() >= 1 {
+                if !factors.is_empty() {
                     // Create a profile plot for factor1 * factor2 (if available)
                     let x_factor = &factors[0];
                     let line_factor = if factors.len() >= 2 { Some(&factors[1]) } else { None };
@@ -54,7 +54,7 @@ pub fn generate_plots(
                             } else {
                                 Some("Bar".to_string())
                             },
-                            groups_label: line_factor.map(|f| f.clone()),
+                            groups_label: line_factor.cloned(),
                             model: Some(
                                 format!(
                                     "Intercept + {}",
@@ -99,12 +99,12 @@ pub fn generate_plots(
                                             for record in records {
                                                 let x_factor_value = record.values
                                                     .get(x_factor)
-                                                    .map(|v| data_value_to_string(v))
+                                                    .map(data_value_to_string)
                                                     .unwrap_or_default();
 
                                                 let line_factor_value = record.values
                                                     .get(line_factor_name)
-                                                    .map(|v| data_value_to_string(v))
+                                                    .map(data_value_to_string)
                                                     .unwrap_or_default();
 
                                                 if
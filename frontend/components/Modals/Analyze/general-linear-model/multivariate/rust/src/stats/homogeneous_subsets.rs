@@ -1,5 +1,5 @@
 use std::collections::HashMap;
-use statrs::distribution::{ ContinuousCDF, FisherSnedecor, StudentsT, ChiSquared };
+use statrs::distribution::{ ContinuousCDF, FisherSnedecor, ChiSquared };
 use crate::models::{
     config::MultivariateConfig,
     data::AnalysisData,
@@ -7,13 +7,9 @@ use crate::models::{
 };
 use super::core::{
     extract_dependent_value,
-    data_value_to_string,
     get_factor_levels,
     get_level_values,
     calculate_mean,
-    calculate_std_deviation,
-    calculate_t_significance,
-    calculate_f_significance,
     calculate_t_critical,
 };
 
@@ -286,7 +282,7 @@ fn calculate_mean_square_error(
     let df_total = all_values.len() - 1;
     let df_error = df_total - df_factors_total;
 
-    if df_error <= 0 {
+    if df_error == 0 {
         return Err("Insufficient degrees of freedom for error term".to_string());
     }
 
@@ -357,8 +353,6 @@ fn calculate_tukey_hsd_subsets(
         groups.push(group);
     }
 
-    // Calculate significance values for each subset
-    let significances = calculate_subset_significances(&subsets, level_means, mse, df_error);
 
     // Create the final HomogeneousSubsets structure
     let result = HomogeneousSubsets {
@@ -404,7 +398,7 @@ fn calculate_snk_subsets(
     }
 
     // Create subsets using the SNK step-down procedure
-    let subsets = create_snk_subsets(level_means, &range_msds, mse, harmonic_mean);
+    let subsets = create_snk_subsets(level_means, &range_msds);
 
     // Format the results similar to Tukey HSD
     let mut groups = Vec::new();
@@ -427,8 +421,6 @@ fn calculate_snk_subsets(
         groups.push(group);
     }
 
-    // Calculate significance values for each subset
-    let significances = calculate_subset_significances(&subsets, level_means, mse, df_error);
 
     // Create the final HomogeneousSubsets structure
     let result = HomogeneousSubsets {
@@ -476,7 +468,7 @@ fn calculate_duncan_subsets(
     }
 
     // Create subsets using Duncan's procedure
-    let subsets = create_snk_subsets(level_means, &range_msds, mse, harmonic_mean);
+    let subsets = create_snk_subsets(level_means, &range_msds);
 
     // Format the results similar to previous tests
     let mut groups = Vec::new();
@@ -499,8 +491,6 @@ fn calculate_duncan_subsets(
         groups.push(group);
     }
 
-    // Calculate significance values for each subset
-    let significances = calculate_subset_significances(&subsets, level_means, mse, df_error);
 
     // Create the final HomogeneousSubsets structure
     let result = HomogeneousSubsets {
@@ -553,7 +543,7 @@ fn calculate_regwf_subsets(
     }
 
     // Create subsets using the REGW step-down procedure
-    let subsets = create_snk_subsets(level_means, &range_msds, mse, harmonic_mean);
+    let subsets = create_snk_subsets(level_means, &range_msds);
 
     // Format the results
     let mut groups = Vec::new();
@@ -576,8 +566,6 @@ fn calculate_regwf_subsets(
         groups.push(group);
     }
 
-    // Calculate significance values for each subset
-    let significances = calculate_subset_significances(&subsets, level_means, mse, df_error);
 
     // Create the final HomogeneousSubsets structure
     let result = HomogeneousSubsets {
@@ -626,7 +614,7 @@ fn calculate_regwq_subsets(
     }
 
     // Create subsets using the REGW step-down procedure
-    let subsets = create_snk_subsets(level_means, &range_msds, mse, harmonic_mean);
+    let subsets = create_snk_subsets(level_means, &range_msds);
 
     // Format the results
     let mut groups = Vec::new();
@@ -649,8 +637,6 @@ fn calculate_regwq_subsets(
         groups.push(group);
     }
 
-    // Calculate significance values for each subset
-    let significances = calculate_subset_significances(&subsets, level_means, mse, df_error);
 
     // Create the final HomogeneousSubsets structure
     let result = HomogeneousSubsets {
@@ -689,7 +675,7 @@ fn calculate_tukey_b_subsets(
     let mut range_msds = Vec::new();
 
     // Calculate critical values for each range
-    for p in 2..=k {
+    for _ in 2..=k {
         // Tukey's b uses the same critical value for all ranges (from the studentized range)
         let q_critical = approximate_studentized_range(alpha, k, df_error);
         let msd = q_critical * (mse / harmonic_mean).sqrt();
@@ -697,7 +683,7 @@ fn calculate_tukey_b_subsets(
     }
 
     // Create subsets using the SNK step-down procedure
-    let subsets = create_snk_subsets(level_means, &range_msds, mse, harmonic_mean);
+    let subsets = create_snk_subsets(level_means, &range_msds);
 
     // Format the results
     let mut groups = Vec::new();
@@ -720,8 +706,6 @@ fn calculate_tukey_b_subsets(
         groups.push(group);
     }
 
-    // Calculate significance values for each subset
-    let significances = calculate_subset_significances(&subsets, level_means, mse, df_error);
 
     // Create the final HomogeneousSubsets structure
     let result = HomogeneousSubsets {
@@ -757,8 +741,6 @@ fn calculate_waller_duncan_subsets(
     }
 
     // Waller-Duncan uses a Bayesian approach with k-ratio
-    let k = level_means.len();
-
     // Calculate the critical value for Waller-Duncan
     // This requires the F distribution and error ratio (k-ratio)
     let f_value = f_critical_value(alpha / 2.0, 1, df_error);
@@ -791,8 +773,6 @@ fn calculate_waller_duncan_subsets(
         groups.push(group);
     }
 
-    // Calculate significance values for each subset
-    let significances = calculate_subset_significances(&subsets, level_means, mse, df_error);
 
     // Create the final HomogeneousSubsets structure
     let result = HomogeneousSubsets {
@@ -819,8 +799,6 @@ fn calculate_waller_duncan_subsets(
 fn approximate_studentized_range(alpha: f64, k: usize, df: usize) -> f64 {
     // Use an approximation based on the relationship between
     // studentized range (q) and the t-distribution
-    let p = 1.0 - alpha;
-
     // For k=2, q-distribution is related to t-distribution by q = t * sqrt(2)
     if k == 2 {
         return calculate_t_critical(df, alpha / 2.0) * (2.0_f64).sqrt();
@@ -852,21 +830,15 @@ fn f_critical_value(alpha: f64, df1: usize, df2: usize) -> f64 {
 
             for _ in 0..50 {
                 let mid = (low + high) / 2.0;
-                match dist.cdf(mid) {
-                    cdf => {
-                        if (cdf - p).abs() < tol {
-                            return mid;
-                        }
+                let cdf = dist.cdf(mid);
+                if (cdf - p).abs() < tol {
+                    return mid;
+                }
 
-                        if cdf < p {
-                            low = mid;
-                        } else {
-                            high = mid;
-                        }
-                    }
-                    _ => {
-                        break;
-                    }
+                if cdf < p {
+                    low = mid;
+                } else {
+                    high = mid;
                 }
             }
 
@@ -909,21 +881,15 @@ fn chi2_critical_value(alpha: f64, df: usize) -> f64 {
 
             for _ in 0..50 {
                 let mid = (low + high) / 2.0;
-                match dist.cdf(mid) {
-                    cdf => {
-                        if (cdf - p).abs() < tol {
-                            return mid;
-                        }
+                let cdf = dist.cdf(mid);
+                if (cdf - p).abs() < tol {
+                    return mid;
+                }
 
-                        if cdf < p {
-                            low = mid;
-                        } else {
-                            high = mid;
-                        }
-                    }
-                    _ => {
-                        break;
-                    }
+                if cdf < p {
+                    low = mid;
+                } else {
+                    high = mid;
                 }
             }
 
@@ -1020,17 +986,17 @@ fn create_homogeneous_subsets(level_means: &[(String, f64, usize)], msd: f64) ->
 
     // Optimization: merge overlapping subsets
     let mut optimized_subsets: Vec<Vec<usize>> = Vec::new();
-    for i in 0..subsets.len() {
+    for subset in &subsets {
         let mut merged = false;
 
         // Try to merge with existing optimized subsets
         for opt_subset in &mut optimized_subsets {
             // Check if they share any elements
-            let has_common = subsets[i].iter().any(|&item| opt_subset.contains(&item));
+            let has_common = subset.iter().any(|&item| opt_subset.contains(&item));
 
             if has_common {
                 // Merge the subsets
-                for &item in &subsets[i] {
+                for &item in subset {
                     if !opt_subset.contains(&item) {
                         opt_subset.push(item);
                     }
@@ -1042,7 +1008,7 @@ fn create_homogeneous_subsets(level_means: &[(String, f64, usize)], msd: f64) ->
 
         if !merged {
             // Add as a new subset
-            optimized_subsets.push(subsets[i].clone());
+            optimized_subsets.push(subset.clone());
         }
     }
 
@@ -1052,9 +1018,7 @@ fn create_homogeneous_subsets(level_means: &[(String, f64, usize)], msd: f64) ->
 /// Create homogeneous subsets using the SNK step-down procedure
 fn create_snk_subsets(
     level_means: &[(String, f64, usize)],
-    range_msds: &[f64],
-    mse: f64,
-    harmonic_mean: f64
+    range_msds: &[f64]
 ) -> Vec<Vec<usize>> {
     let k = level_means.len();
 
@@ -1115,42 +1079,3 @@ fn create_snk_subsets(
     subsets
 }
 
-/// Calculate significance values for subsets
-fn calculate_subset_significances(
-    subsets: &[Vec<usize>],
-    level_means: &[(String, f64, usize)],
-    mse: f64,
-    df_error: usize
-) -> Vec<f64> {
-    let mut significances = Vec::new();
-
-    for subset in subsets {
-        if subset.len() <= 1 {
-            // A subset with one element has no variability to test
-            significances.push(1.0);
-            continue;
-        }
-
-        // Calculate the variance within this subset
-        let subset_means: Vec<f64> = subset
-            .iter()
-            .map(|&idx| level_means[idx].1)
-            .collect();
-
-        let subset_mean = calculate_mean(&subset_means);
-        let subset_variance =
-            subset_means
-                .iter()
-                .map(|&mean| (mean - subset_mean).powi(2))
-                .sum::<f64>() / ((subset_means.len() - 1) as f64);
-
-        // Calculate F-ratio
-        let f_ratio = subset_variance / mse;
-
-        // Calculate significance
-        let significance = calculate_f_significance(subset.len() - 1, df_error, f_ratio);
-        significances.push(significance);
-    }
-
-    significances
-}
@@ -3,7 +3,7 @@ use statrs::distribution::{ ContinuousCDF, FisherSnedecor };
 
 use crate::models::{
     config::MultivariateConfig,
-    data::{ AnalysisData, DataValue },
+    data::AnalysisData,
     result::{ LeveneTest, LeveneResult },
 };
 
@@ -24,7 +24,7 @@ fn calculate_median(values: &[f64]) -> f64 {
     sorted_values.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
 
     let n = sorted_values.len();
-    if n % 2 == 0 {
+    if n.is_multiple_of(2) {
         // Even number of elements, take average of middle two
         (sorted_values[n / 2 - 1] + sorted_values[n / 2]) / 2.0
     } else {
@@ -194,8 +194,8 @@ pub fn calculate_levene_test(
                 all_abs_deviations[3].push((*value - group_trimmed_mean).abs()); // Based on trimmed mean
 
                 // Store group indices for all tests
-                for i in 0..4 {
-                    all_group_indices[i].push(idx);
+                for group_indices in &mut all_group_indices {
+                    group_indices.push(idx);
                 }
             }
             group_keys.push(group.clone());
@@ -6,6 +6,7 @@ pub mod bootstrap;
 pub mod box_m_test;
 pub mod common;
 pub mod contrast_coefficients;
+pub mod custom_hypothesis;
 pub mod descriptive_statistics;
 pub mod emmeans;
 pub mod estimable_function;
@@ -16,7 +16,6 @@ use super::{
         matrix_determinant,
         matrix_inverse,
         matrix_multiply,
-        matrix_transpose,
     },
     core::{ data_value_to_string, extract_dependent_value, parse_interaction_term },
 };
@@ -66,7 +65,6 @@ pub fn calculate_multivariate_tests(
     let (h_matrix, e_matrix, hypothesis_df, error_df) = match
         calculate_hypothesis_error_matrices(
             data,
-            config,
             "Intercept",
             dependent_vars,
             &all_values,
@@ -86,7 +84,6 @@ pub fn calculate_multivariate_tests(
             &e_matrix,
             hypothesis_df,
             error_df,
-            alpha,
             dependent_vars.len()
         )
     {
@@ -103,7 +100,6 @@ pub fn calculate_multivariate_tests(
         let (h_matrix, e_matrix, hypothesis_df, error_df) = match
             calculate_hypothesis_error_matrices(
                 data,
-                config,
                 factor,
                 dependent_vars,
                 &all_values,
@@ -122,7 +118,6 @@ pub fn calculate_multivariate_tests(
                 &e_matrix,
                 hypothesis_df,
                 error_df,
-                alpha,
                 dependent_vars.len()
             )
         {
@@ -147,7 +142,6 @@ pub fn calculate_multivariate_tests(
             let (h_matrix, e_matrix, hypothesis_df, error_df) = match
                 calculate_hypothesis_error_matrices(
                     data,
-                    config,
                     &term,
                     dependent_vars,
                     &all_values,
@@ -168,7 +162,6 @@ pub fn calculate_multivariate_tests(
                     &e_matrix,
                     hypothesis_df,
                     error_df,
-                    alpha,
                     dependent_vars.len()
                 )
             {
@@ -196,15 +189,17 @@ pub fn calculate_multivariate_tests(
     })
 }
 
+/// Hypothesis matrix, error matrix, hypothesis df and error df
+type HypothesisErrorMatrices = (Vec<Vec<f64>>, Vec<Vec<f64>>, f64, f64);
+
 /// Calculate hypothesis and error matrices for a given effect
 fn calculate_hypothesis_error_matrices(
     data: &AnalysisData,
-    config: &MultivariateConfig,
     effect: &str,
     dependent_vars: &[String],
     all_values: &[Vec<f64>],
     factors_in_effect: Option<&[String]>
-) -> Result<(Vec<Vec<f64>>, Vec<Vec<f64>>, f64, f64), String> {
+) -> Result<HypothesisErrorMatrices, String> {
     let p = dependent_vars.len();
     let n_obs = all_values[0].len();
 
@@ -244,7 +239,7 @@ fn calculate_hypothesis_error_matrices(
             }
         }
 
-        return Ok((h_matrix, e_matrix, 1.0, (n_obs - 1) as f64));
+        Ok((h_matrix, e_matrix, 1.0, (n_obs - 1) as f64))
     } else if factors_in_effect.is_none() || factors_in_effect.unwrap().is_empty() {
         // Main effect
         let factor_levels = get_factor_levels(data, effect)?;
@@ -259,7 +254,6 @@ fn calculate_hypothesis_error_matrices(
 
             // Find all records with this factor level
             for (i, dep_var) in dependent_vars.iter().enumerate() {
-                let mut record_idx = 0;
                 for records in &data.dependent_data {
                     for record in records {
                         if let Some(value) = extract_dependent_value(record, dep_var) {
@@ -286,7 +280,6 @@ fn calculate_hypothesis_error_matrices(
                                 level_values[i].push(value);
                             }
                         }
-                        record_idx += 1;
                     }
                 }
             }
@@ -328,7 +321,7 @@ fn calculate_hypothesis_error_matrices(
                 let mut record_idx = 0;
 
                 for records in &data.dependent_data {
-                    for record in records {
+                    for _ in records {
                         if record_idx >= n_obs {
                             continue;
                         }
@@ -374,7 +367,7 @@ fn calculate_hypothesis_error_matrices(
         let hypothesis_df = (level_count - 1) as f64;
         let error_df = (n_obs - level_count) as f64;
 
-        return Ok((h_matrix, e_matrix, hypothesis_df, error_df));
+        Ok((h_matrix, e_matrix, hypothesis_df, error_df))
     } else {
         // Interaction effect
         let interaction_factors = factors_in_effect.unwrap();
@@ -419,7 +412,6 @@ fn calculate_hypothesis_error_matrices(
 
             // Find all records with this combination of factor levels
             for (i, dep_var) in dependent_vars.iter().enumerate() {
-                let mut record_idx = 0;
                 for records in &data.dependent_data {
                     for record in records {
                         if let Some(value) = extract_dependent_value(record, dep_var) {
@@ -455,7 +447,6 @@ fn calculate_hypothesis_error_matrices(
                                 combo_values[i].push(value);
                             }
                         }
-                        record_idx += 1;
                     }
                 }
             }
@@ -488,7 +479,6 @@ fn calculate_hypothesis_error_matrices(
 
                 // Find all records with this factor level
                 for (i, dep_var) in dependent_vars.iter().enumerate() {
-                    let mut record_idx = 0;
                     for records in &data.dependent_data {
                         for record in records {
                             if let Some(value) = extract_dependent_value(record, dep_var) {
@@ -515,7 +505,6 @@ fn calculate_hypothesis_error_matrices(
                                     level_values[i].push(value);
                                 }
                             }
-                            record_idx += 1;
                         }
                     }
                 }
@@ -579,7 +568,7 @@ fn calculate_hypothesis_error_matrices(
                 let mut record_idx = 0;
 
                 for records in &data.dependent_data {
-                    for record in records {
+                    for _ in records {
                         if record_idx >= n_obs {
                             continue;
                         }
@@ -611,7 +600,7 @@ fn calculate_hypothesis_error_matrices(
                                 .iter()
                                 .position(|c| {
                                     c.iter().all(|(f, l)| {
-                                        record_combo.get(f).map_or(false, |rl| rl == l)
+                                        record_combo.get(f).is_some_and(|rl| rl == l)
                                     })
                                 })
                         {
@@ -642,7 +631,7 @@ fn calculate_hypothesis_error_matrices(
         let total_combinations = level_combinations.len();
         let error_df = (n_obs - total_combinations) as f64;
 
-        return Ok((h_matrix, e_matrix, hypothesis_df, error_df));
+        Ok((h_matrix, e_matrix, hypothesis_df, error_df))
     }
 }
 
@@ -652,7 +641,6 @@ fn calculate_multivariate_test_statistics(
     e_matrix: &[Vec<f64>],
     hypothesis_df: f64,
     error_df: f64,
-    alpha: f64,
     p: usize
 ) -> Result<HashMap<String, MultivariateTestEntry>, String> {
     let mut test_results = HashMap::new();
@@ -676,29 +664,15 @@ fn calculate_multivariate_test_statistics(
     // However, computing eigenvalues is complex, so we'll use a simpler approach
     // to estimate the multivariate test statistics
 
-    // Calculate determinants
-    let det_h = match matrix_determinant(h_matrix) {
-        Ok(d) => d,
-        Err(_) => 0.0001, // Fallback value if determinant calculation fails
-    };
-
-    let det_e = match matrix_determinant(e_matrix) {
-        Ok(d) => d,
-        Err(_) => 0.0001, // Fallback value
-    };
-
-    let det_he = match matrix_determinant(&he_inv) {
-        Ok(d) => d,
-        Err(_) => 0.0001, // Fallback value
-    };
+    // Calculate determinant, with a fallback value if determinant calculation fails
+    let det_he = matrix_determinant(&he_inv).unwrap_or(0.0001);
 
     // Compute trace of HE^-1
-    let mut trace_he = 0.0;
-    for i in 0..he_inv.len() {
-        if i < he_inv[i].len() {
-            trace_he += he_inv[i][i];
-        }
-    }
+    let trace_he: f64 = he_inv
+        .iter()
+        .enumerate()
+        .filter_map(|(i, row)| row.get(i))
+        .sum();
 
     // Calculate largest eigenvalue (approximation)
     let max_eigenvalue = trace_he / (p as f64);
@@ -738,7 +712,6 @@ fn calculate_multivariate_test_statistics(
     } else {
         // General approximation (Rao's)
         let r = error_df - ((p as f64) - hypothesis_df + 1.0) / 2.0;
-        let u = ((p as f64) * hypothesis_df - 2.0) / 4.0;
         let t = if (p as f64) * hypothesis_df > 2.0 {
             ((p as f64) * hypothesis_df).sqrt() - 1.0
         } else {
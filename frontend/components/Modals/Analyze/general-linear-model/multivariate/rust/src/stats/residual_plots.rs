@@ -100,7 +100,7 @@ pub fn calculate_residual_plots(
             // Convert the f64 level to an i64 with a scaling factor to maintain precision
             let level_key = (level * 1000.0).round() as i64;
 
-            level_groups.entry(level_key).or_insert_with(Vec::new).push(spread);
+            level_groups.entry(level_key).or_default().push(spread);
         }
 
         // Calculate mean spread for each level
@@ -8,14 +8,11 @@ use crate::models::{
 
 use super::common::{
     build_design_matrix_and_response,
-    calculate_mean,
     extract_dependent_value,
-    get_factor_levels,
     matrix_determinant,
     matrix_inverse,
     matrix_multiply,
     matrix_transpose,
-    data_value_to_string,
 };
 
 /// Calculate the residual SSCP matrix
@@ -32,9 +29,6 @@ pub fn calculate_residual_matrix(
     // Get the list of dependent variables
     let dependent_vars = config.main.dep_var.as_ref().unwrap();
 
-    // Get the list of factors (if any)
-    let factors = config.main.fix_factor.as_ref().map_or(Vec::new(), |f| f.clone());
-
     // Extract values for all dependent variables and organize by record
     let mut all_values: Vec<HashMap<usize, f64>> = Vec::new();
     let n_dep_vars = dependent_vars.len();
@@ -82,7 +76,7 @@ pub fn calculate_residual_matrix(
     // Calculate model parameters and residuals for each dependent variable
     let mut residuals = Vec::new();
 
-    for (i, (x_matrix, y_vector)) in design_matrices.iter().zip(y_vectors.iter()).enumerate() {
+    for (x_matrix, y_vector) in design_matrices.iter().zip(y_vectors.iter()) {
         // Calculate X'X
         let x_transpose = matrix_transpose(x_matrix);
         let xtx = match matrix_multiply(&x_transpose, x_matrix) {
@@ -285,10 +279,7 @@ pub fn calculate_bartlett_sphericity_test(
     };
 
     // Calculate trace of the covariance matrix
-    let mut trace = 0.0;
-    for i in 0..p {
-        trace += cov_matrix[i][i];
-    }
+    let trace: f64 = (0..p).map(|i| cov_matrix[i][i]).sum();
 
     // Calculate Bartlett's test statistic
     let n = df + p; // Number of observations
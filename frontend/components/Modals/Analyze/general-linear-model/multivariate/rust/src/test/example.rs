@@ -0,0 +1,179 @@
+use std::collections::HashMap;
+
+use serde_json::json;
+
+use crate::models::{
+    config::{ CustomHypothesisSpec, MultivariateConfig },
+    data::{ AnalysisData, DataRecord, DataValue, VariableDefinition },
+};
+use crate::stats::{
+    bootstrap::perform_bootstrap_analysis,
+    custom_hypothesis::calculate_custom_hypothesis_tests,
+};
+
+// Two straight-line responses on a shared covariate
+const X: [f64; 10] = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0];
+const Y1: [f64; 10] = [2.1, 3.9, 6.2, 7.8, 10.3, 11.7, 14.4, 15.6, 18.5, 19.9];
+const Y2: [f64; 10] = [5.0, 4.1, 4.4, 3.2, 3.5, 2.1, 2.6, 1.4, 1.9, 0.3];
+
+// (dependent, parameter, estimate, bias, std. error, lower, upper) over 20 simple bootstrap
+// samples drawn from Mt64 seeded with 200000: each draw takes next_u64() % 10 as the case,
+// the sorted sample is refitted by least squares and the 95% interval interpolates the
+// 0.025 and 0.975 quantiles of the 20 replicates
+const BOOTSTRAP_ESTIMATES: [(&str, &str, f64, f64, f64, f64, f64); 4] = [
+    ("y1", "Intercept", 0.02, 0.009922745624788, 0.141894289395, -0.2789693695303, 0.256966494616),
+    ("y1", "x", 2.003636363636, -0.001268248564191, 0.03917687311195, 1.929245238943, 2.075324043254),
+    ("y2", "Intercept", 5.38, -0.00780852199218, 0.1834494744511, 4.996417842472, 5.677211698585),
+    ("y2", "x", -0.46, 0.004729638810037, 0.04886521057186, -0.5241430131004, -0.3607869911835),
+];
+
+// Custom hypotheses on the same fit, with Sxx = 82.5 and error SSCP
+// E = [[0.8429090909091, 1.148], [1.148, 1.808]] on 8 df.
+// Both slopes zero: Wilks' lambda = det(E) / det(E + H) with exact F on (2, 7) df
+const CUSTOM_SLOPES_WILKS: (f64, f64) = (0.0002614126205547, 13385.29504966);
+// (dependent, hypothesis SS, F) for the univariate tests of the zero-slope hypothesis
+const CUSTOM_SLOPES_UNIVARIATE: [(&str, f64, f64); 2] = [
+    ("y1", 331.2010909091, 3143.409836066),
+    ("y2", 17.457, 77.24336283186),
+];
+// Slope of y1 - y2 equal to 2.5: (estimate, difference, std. error, Sig., lower, upper)
+// from the transformed error SS 0.3549090909091 and t on 8 df
+const CUSTOM_SLOPE_DIFFERENCE: (f64, f64, f64, f64, f64, f64) = (
+    2.463636363636,
+    -0.03636363636364,
+    0.02318924420571,
+    0.1554889547821,
+    -0.08983812939426,
+    0.01711085666699,
+);
+
+fn definition(name: &str, column_index: usize) -> VariableDefinition {
+    serde_json
+        ::from_value(
+            json!({
+                "columnIndex": column_index,
+                "name": name,
+                "type": "NUMERIC",
+                "width": 8,
+                "decimals": 2,
+                "values": [],
+                "missing": [],
+                "columns": 8,
+                "align": "right",
+                "measure": "scale",
+                "role": "input",
+            })
+        )
+        .unwrap()
+}
+
+fn regression_data() -> AnalysisData {
+    let records = (0..X.len())
+        .map(|i| {
+            let mut values = HashMap::new();
+            values.insert("y1".to_string(), DataValue::Number(Y1[i]));
+            values.insert("y2".to_string(), DataValue::Number(Y2[i]));
+            values.insert("x".to_string(), DataValue::Number(X[i]));
+            DataRecord { values }
+        })
+        .collect();
+
+    AnalysisData {
+        dependent_data: vec![records],
+        fix_factor_data: vec![],
+        covariate_data: None,
+        wls_data: None,
+        dependent_data_defs: vec![vec![definition("y1", 0), definition("y2", 1)]],
+        fix_factor_data_defs: vec![],
+        covariate_data_defs: Some(vec![vec![definition("x", 2)]]),
+        wls_data_defs: None,
+    }
+}
+
+fn regression_config() -> MultivariateConfig {
+    let mut config = MultivariateConfig::default();
+    config.main.dep_var = Some(vec!["y1".to_string(), "y2".to_string()]);
+    config.main.covar = Some(vec!["x".to_string()]);
+    config
+}
+
+fn slope_hypothesis(
+    label: &str,
+    m_matrix: Option<Vec<HashMap<String, f64>>>,
+    k_matrix: Option<Vec<Vec<f64>>>
+) -> CustomHypothesisSpec {
+    CustomHypothesisSpec {
+        label: Some(label.to_string()),
+        l_matrix: vec![HashMap::from([("x".to_string(), 1.0)])],
+        m_matrix,
+        k_matrix,
+    }
+}
+
+#[test]
+fn seeded_bootstrap_matches_reference_replicates() {
+    let mut config = regression_config();
+    config.bootstrap.perform_boot_strapping = true;
+    config.bootstrap.num_of_samples = Some(20);
+    config.bootstrap.seed = true;
+    config.bootstrap.seed_value = Some(200000);
+
+    let result = perform_bootstrap_analysis(&regression_data(), &config).unwrap();
+    assert_eq!(result.valid_samples, 20);
+    assert!((result.confidence_level - 0.95).abs() < 1e-12);
+
+    for (dep_var, parameter, estimate, bias, std_error, lower, upper) in BOOTSTRAP_ESTIMATES {
+        let entry = result.parameter_estimates[dep_var]
+            .iter()
+            .find(|entry| entry.label == parameter)
+            .unwrap();
+        let interval = entry.percentile_interval.as_ref().unwrap();
+        assert!((entry.estimate - estimate).abs() < 1e-9, "{} {}", dep_var, parameter);
+        assert!((entry.bias - bias).abs() < 1e-9, "{} {}", dep_var, parameter);
+        assert!((entry.std_error - std_error).abs() < 1e-9, "{} {}", dep_var, parameter);
+        assert!((interval.lower_bound - lower).abs() < 1e-9, "{} {}", dep_var, parameter);
+        assert!((interval.upper_bound - upper).abs() < 1e-9, "{} {}", dep_var, parameter);
+    }
+}
+
+#[test]
+fn custom_hypotheses_match_reference_values() {
+    let mut config = regression_config();
+    config.custom.tests = vec![
+        slope_hypothesis("slopes", None, None),
+        slope_hypothesis(
+            "difference",
+            Some(vec![HashMap::from([("y1".to_string(), 1.0), ("y2".to_string(), -1.0)])]),
+            Some(vec![vec![2.5]])
+        )
+    ];
+
+    let results = calculate_custom_hypothesis_tests(&regression_data(), &config).unwrap();
+    assert_eq!(results.len(), 2);
+
+    let slopes = &results[0];
+    assert_eq!((slopes.hypothesis_df, slopes.error_df), (1, 8));
+    let wilks = &slopes.multivariate_tests["Wilks' Lambda"];
+    let (lambda, f) = CUSTOM_SLOPES_WILKS;
+    assert!((wilks.value - lambda).abs() < 1e-12);
+    assert!((wilks.f - f).abs() / f < 1e-9);
+    assert_eq!((wilks.hypothesis_df, wilks.error_df), (2.0, 7.0));
+    assert!(wilks.is_exact_statistic);
+    for (test, (dep_var, hypothesis_ss, f)) in slopes.univariate_tests
+        .iter()
+        .zip(CUSTOM_SLOPES_UNIVARIATE) {
+        assert_eq!(test.transformed_variable, dep_var);
+        assert!((test.hypothesis_ss - hypothesis_ss).abs() < 1e-8, "{}", dep_var);
+        assert!((test.f_value - f).abs() / f < 1e-9, "{}", dep_var);
+    }
+
+    let difference = &results[1].contrast_estimates[0];
+    let (estimate, diff, std_error, significance, lower, upper) = CUSTOM_SLOPE_DIFFERENCE;
+    assert_eq!(difference.transformed_variable, "T1");
+    assert!((difference.estimate - estimate).abs() < 1e-9);
+    assert!((difference.difference - diff).abs() < 1e-9);
+    assert!((difference.std_error - std_error).abs() < 1e-9);
+    assert!((difference.significance - significance).abs() < 1e-9);
+    assert!((difference.confidence_interval.lower_bound - lower).abs() < 1e-9);
+    assert!((difference.confidence_interval.upper_bound - upper).abs() < 1e-9);
+}
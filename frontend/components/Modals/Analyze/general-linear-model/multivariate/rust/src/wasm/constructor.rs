@@ -20,6 +20,8 @@ pub struct MultivariateAnalysis {
 
 #[wasm_bindgen]
 impl MultivariateAnalysis {
+    // Each dataset and its variable definitions arrive as separate JS values
+    #[allow(clippy::too_many_arguments)]
     #[wasm_bindgen(constructor)]
     pub fn new(
         dep_data: JsValue,
@@ -147,24 +149,24 @@ impl MultivariateAnalysis {
         }
 
         // Validate fixed factors if using post-hoc tests
-        if config.posthoc.src_list.as_ref().map_or(false, |list| !list.is_empty()) {
-            if config.main.fix_factor.as_ref().map_or(true, |list| list.is_empty()) {
-                let msg = "Fixed factors must be specified for post-hoc tests".to_string();
-                error_collector.add_error("config.validation.posthoc", &msg);
-                return Err(string_to_js_error(msg));
-            }
+        if
+            config.posthoc.src_list.as_ref().is_some_and(|list| !list.is_empty()) &&
+            config.main.fix_factor.as_ref().is_none_or(|list| list.is_empty())
+        {
+            let msg = "Fixed factors must be specified for post-hoc tests".to_string();
+            error_collector.add_error("config.validation.posthoc", &msg);
+            return Err(string_to_js_error(msg));
         }
 
         // Validate bootstrap settings
-        if config.bootstrap.perform_boot_strapping {
-            if
-                config.bootstrap.stratified &&
-                config.bootstrap.strata_variables.as_ref().map_or(true, |list| list.is_empty())
-            {
-                let msg = "Strata variables must be specified for stratified bootstrap".to_string();
-                error_collector.add_error("config.validation.bootstrap.strata", &msg);
-                return Err(string_to_js_error(msg));
-            }
+        if
+            config.bootstrap.perform_boot_strapping &&
+            config.bootstrap.stratified &&
+            config.bootstrap.strata_variables.as_ref().is_none_or(|list| list.is_empty())
+        {
+            let msg = "Strata variables must be specified for stratified bootstrap".to_string();
+            error_collector.add_error("config.validation.bootstrap.strata", &msg);
+            return Err(string_to_js_error(msg));
         }
 
         // Store data
@@ -231,6 +231,17 @@ export type MultivariateBootstrapProps = {
     data: MultivariateBootstrapType;
 };
 
+export type MultivariateCustomHypothesisType = {
+    Label: string | null;
+    LMatrix: Record<string, number>[];
+    MMatrix: Record<string, number>[] | null;
+    KMatrix: number[][] | null;
+};
+
+export type MultivariateCustomType = {
+    Tests: MultivariateCustomHypothesisType[];
+};
+
 export type MultivariateType = {
     main: MultivariateMainType;
     model: MultivariateModelType;
@@ -241,6 +252,7 @@ export type MultivariateType = {
     save: MultivariateSaveType;
     options: MultivariateOptionsType;
     bootstrap: MultivariateBootstrapType;
+    custom: MultivariateCustomType;
 };
 
 export type MultivariateContainerProps = {
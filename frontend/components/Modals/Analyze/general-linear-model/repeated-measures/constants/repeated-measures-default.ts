@@ -1,7 +1,9 @@
 import {
     RepeatedMeasuresContrastType,
+    RepeatedMeasuresCustomType,
     RepeatedMeasuresEMMeansType,
     RepeatedMeasuresMainType,
+    RepeatedMeasuresMixedType,
     RepeatedMeasuresModelType,
     RepeatedMeasuresOptionsType,
     RepeatedMeasuresPlotsType,
@@ -126,6 +128,22 @@ export const RepeatedMeasuresOptionsDefault: RepeatedMeasuresOptionsType = {
     SigLevel: 0.05,
 };
 
+export const RepeatedMeasuresCustomDefault: RepeatedMeasuresCustomType = {
+    Tests: [],
+};
+
+export const RepeatedMeasuresMixedDefault: RepeatedMeasuresMixedType = {
+    Enabled: false,
+    CovStructure: null,
+    DfMethod: null,
+    Measure: null,
+    SubjectVar: null,
+    OccasionVar: null,
+    DepVar: null,
+    MaxIter: null,
+    Converge: null,
+};
+
 export const RepeatedMeasuresDefault: RepeatedMeasuresType = {
     main: RepeatedMeasuresMainDefault,
     model: RepeatedMeasuresModelDefault,
@@ -135,4 +153,6 @@ export const RepeatedMeasuresDefault: RepeatedMeasuresType = {
     emmeans: RepeatedMeasuresEMMeansDefault,
     save: RepeatedMeasuresSaveDefault,
     options: RepeatedMeasuresOptionsDefault,
+    custom: RepeatedMeasuresCustomDefault,
+    mixed: RepeatedMeasuresMixedDefault,
 };
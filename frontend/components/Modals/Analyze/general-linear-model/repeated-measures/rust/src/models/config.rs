@@ -1,6 +1,7 @@
 use serde::{ Deserialize, Serialize };
+use std::collections::HashMap;
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct RepeatedMeasuresConfig {
     pub main: MainConfig,
     pub model: ModelConfig,
@@ -10,9 +11,13 @@ pub struct RepeatedMeasuresConfig {
     pub emmeans: EmmeanConfig,
     pub save: SaveConfig,
     pub options: OptionsConfig,
+    #[serde(default)]
+    pub custom: CustomHypothesisConfig,
+    #[serde(default)]
+    pub mixed: MixedModelConfig,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct MainConfig {
     #[serde(rename = "SubVar")]
     pub sub_var: Option<Vec<String>>,
@@ -80,6 +85,26 @@ pub struct ModelConfig {
     pub term_text: Option<String>,
 }
 
+impl Default for ModelConfig {
+    fn default() -> Self {
+        ModelConfig {
+            non_cust: true,
+            custom: false,
+            build_custom_term: false,
+            bet_sub_var: None,
+            bet_sub_model: None,
+            with_sub_var: None,
+            with_sub_model: None,
+            def_factors: None,
+            bet_factors: None,
+            cov_model: None,
+            build_term_method: Some(BuildTermMethod::Interaction),
+            sum_of_square_method: SumOfSquaresMethod::TypeIII,
+            term_text: None,
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub enum ContrastMethod {
     #[serde(rename = "none")]
@@ -110,6 +135,17 @@ pub struct ContrastConfig {
     pub first: bool,
 }
 
+impl Default for ContrastConfig {
+    fn default() -> Self {
+        ContrastConfig {
+            factor_list: None,
+            contrast_method: ContrastMethod::None,
+            last: true,
+            first: false,
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct PlotsConfig {
     #[serde(rename = "SrcList")]
@@ -142,6 +178,27 @@ pub struct PlotsConfig {
     pub multiplier: Option<f64>,
 }
 
+impl Default for PlotsConfig {
+    fn default() -> Self {
+        PlotsConfig {
+            src_list: None,
+            axis_list: None,
+            line_list: None,
+            plot_list: None,
+            fix_factor_vars: None,
+            rand_factor_vars: None,
+            line_chart_type: true,
+            bar_chart_type: false,
+            include_error_bars: false,
+            confidence_interval: true,
+            standard_error: false,
+            include_ref_line_for_grand_mean: false,
+            y_axis_start_0: false,
+            multiplier: Some(2.0),
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct PostHocConfig {
     #[serde(rename = "SrcList")]
@@ -196,6 +253,38 @@ pub struct PostHocConfig {
     pub dunc: bool,
 }
 
+impl Default for PostHocConfig {
+    fn default() -> Self {
+        PostHocConfig {
+            src_list: None,
+            fix_factor_vars: None,
+            error_ratio: Some(100.0),
+            twosided: true,
+            lt_control: false,
+            gt_control: false,
+            category_method: Some(CategoryMethod::Last),
+            waller: false,
+            dunnett: false,
+            lsd: false,
+            bonfe: false,
+            sidak: false,
+            scheffe: false,
+            regwf: false,
+            regwq: false,
+            snk: false,
+            tu: false,
+            tub: false,
+            dun: false,
+            hoc: false,
+            gabriel: false,
+            tam: false,
+            dunt: false,
+            games: false,
+            dunc: false,
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub enum CategoryMethod {
     #[serde(rename = "last")]
@@ -226,6 +315,17 @@ pub struct EmmeanConfig {
     pub confi_interval_method: Option<CIMethod>,
 }
 
+impl Default for EmmeanConfig {
+    fn default() -> Self {
+        EmmeanConfig {
+            src_list: None,
+            target_list: None,
+            comp_main_effect: false,
+            confi_interval_method: Some(CIMethod::LsdNone),
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct SaveConfig {
     #[serde(rename = "ResWeighted")]
@@ -260,6 +360,28 @@ pub struct SaveConfig {
     pub write_new_dataset: bool,
 }
 
+impl Default for SaveConfig {
+    fn default() -> Self {
+        SaveConfig {
+            res_weighted: false,
+            pre_weighted: false,
+            std_statistics: false,
+            cooks_d: false,
+            leverage: false,
+            unstandardized_res: false,
+            weighted_res: false,
+            standardized_res: false,
+            studentized_res: false,
+            deleted_res: false,
+            coeff_stats: false,
+            new_dataset: true,
+            file_path: None,
+            dataset_name: None,
+            write_new_dataset: false,
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct OptionsConfig {
     #[serde(rename = "DescStats")]
@@ -291,3 +413,84 @@ pub struct OptionsConfig {
     #[serde(rename = "SigLevel")]
     pub sig_level: Option<f64>,
 }
+
+impl Default for OptionsConfig {
+    fn default() -> Self {
+        OptionsConfig {
+            desc_stats: false,
+            homogen_test: false,
+            est_effect_size: false,
+            spr_vs_level: false,
+            obs_power: false,
+            res_plot: false,
+            param_est: false,
+            lack_of_fit: false,
+            sscp_mat: false,
+            general_fun: false,
+            res_sscp_mat: false,
+            coefficient_matrix: false,
+            transform_mat: false,
+            sig_level: Some(0.05),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct CustomHypothesisConfig {
+    #[serde(rename = "Tests", default)]
+    pub tests: Vec<CustomHypothesisSpec>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CustomHypothesisSpec {
+    #[serde(rename = "Label", default)]
+    pub label: Option<String>,
+    #[serde(rename = "LMatrix", default)]
+    pub l_matrix: Vec<HashMap<String, f64>>,
+    #[serde(rename = "MMatrix", default)]
+    pub m_matrix: Option<Vec<HashMap<String, f64>>>,
+    #[serde(rename = "KMatrix", default)]
+    pub k_matrix: Option<Vec<Vec<f64>>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub enum CovarianceStructure {
+    #[serde(rename = "compoundSymmetry")]
+    CompoundSymmetry,
+    #[serde(rename = "ar1")]
+    AR1,
+    #[serde(rename = "unstructured")]
+    Unstructured,
+    #[serde(rename = "toeplitz")]
+    Toeplitz,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub enum DenominatorDfMethod {
+    #[serde(rename = "satterthwaite")]
+    Satterthwaite,
+    #[serde(rename = "kenwardRoger")]
+    KenwardRoger,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct MixedModelConfig {
+    #[serde(rename = "Enabled", default)]
+    pub enabled: bool,
+    #[serde(rename = "CovStructure", default)]
+    pub cov_structure: Option<CovarianceStructure>,
+    #[serde(rename = "DfMethod", default)]
+    pub df_method: Option<DenominatorDfMethod>,
+    #[serde(rename = "Measure", default)]
+    pub measure: Option<String>,
+    #[serde(rename = "SubjectVar", default)]
+    pub subject_var: Option<String>,
+    #[serde(rename = "OccasionVar", default)]
+    pub occasion_var: Option<String>,
+    #[serde(rename = "DepVar", default)]
+    pub dep_var: Option<String>,
+    #[serde(rename = "MaxIter", default)]
+    pub max_iter: Option<usize>,
+    #[serde(rename = "Converge", default)]
+    pub converge: Option<f64>,
+}
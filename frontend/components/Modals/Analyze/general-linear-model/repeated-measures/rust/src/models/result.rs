@@ -20,6 +20,9 @@ pub struct RepeatedMeasureResult {
     pub univariate_tests: Option<UnivariateTests>,
     pub posthoc_tests: Option<HashMap<String, Vec<PostHocTest>>>,
     pub emmeans: Option<HashMap<String, Vec<EstimatedMarginalMean>>>,
+    pub custom_hypothesis_tests: Option<Vec<CustomHypothesisResult>>,
+    pub doubly_multivariate: Option<DoublyMultivariateResult>,
+    pub mixed_model: Option<MixedModelResult>,
     pub executed_functions: Vec<String>,
 }
 
@@ -85,6 +88,136 @@ pub struct MultivariateTestEntry {
     pub is_exact_statistic: bool,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CustomHypothesisResult {
+    pub label: String,
+    pub parameter_names: Vec<String>,
+    pub measures: Vec<String>,
+    pub l_matrix: Vec<Vec<f64>>,
+    pub m_matrix: Vec<Vec<f64>>,
+    pub k_matrix: Vec<Vec<f64>>,
+    pub transformed_variables: Vec<String>,
+    pub contrast_estimates: Vec<CustomContrastEstimate>,
+    pub multivariate_tests: HashMap<String, MultivariateTestEntry>,
+    pub univariate_tests: Vec<CustomUnivariateTest>,
+    pub hypothesis_sscp: Vec<Vec<f64>>,
+    pub error_sscp: Vec<Vec<f64>>,
+    pub hypothesis_df: usize,
+    pub error_df: usize,
+    pub notes: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CustomContrastEstimate {
+    pub contrast: String,
+    pub transformed_variable: String,
+    pub estimate: f64,
+    pub hypothesized_value: f64,
+    pub difference: f64,
+    pub std_error: f64,
+    pub t_value: f64,
+    pub significance: f64,
+    pub confidence_interval: ConfidenceInterval,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CustomUnivariateTest {
+    pub transformed_variable: String,
+    pub hypothesis_ss: f64,
+    pub error_ss: f64,
+    pub hypothesis_df: usize,
+    pub error_df: usize,
+    pub f_value: f64,
+    pub significance: f64,
+    pub partial_eta_squared: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DoublyMultivariateResult {
+    pub design: WithinSubjectsFactors,
+    pub measures: Vec<String>,
+    pub within_factors: Vec<String>,
+    pub effects: Vec<String>,
+    pub multivariate_tests: HashMap<String, HashMap<String, MultivariateTestEntry>>,
+    pub univariate_tests: HashMap<String, Vec<DoublyMultivariateUnivariateTest>>,
+    pub emmeans: HashMap<String, Vec<EstimatedMarginalMean>>,
+    pub error_df: usize,
+    pub notes: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DoublyMultivariateUnivariateTest {
+    pub source: String,
+    pub sum_of_squares: f64,
+    pub df: f64,
+    pub mean_square: f64,
+    pub f: f64,
+    pub significance: f64,
+    pub error_sum_of_squares: f64,
+    pub error_df: f64,
+    pub partial_eta_squared: f64,
+    pub greenhouse_geisser_epsilon: Option<f64>,
+    pub greenhouse_geisser_significance: Option<f64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MixedModelResult {
+    pub dependent_variable: String,
+    pub covariance_structure: String,
+    pub df_method: String,
+    pub within_factors: Vec<String>,
+    pub occasions: Vec<String>,
+    pub subjects: usize,
+    pub observations: usize,
+    pub incomplete_subjects: usize,
+    pub fixed_effect_parameters: usize,
+    pub information_criteria: MixedModelInformationCriteria,
+    pub covariance_parameters: Vec<CovarianceParameterEstimate>,
+    pub residual_covariance: Vec<Vec<f64>>,
+    pub fixed_effects_tests: Vec<MixedModelFixedEffectTest>,
+    pub emmeans: Vec<MixedModelEstimatedMean>,
+    pub iterations: usize,
+    pub converged: bool,
+    pub notes: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MixedModelInformationCriteria {
+    pub restricted_log_likelihood: f64,
+    pub aic: f64,
+    pub aicc: f64,
+    pub bic: f64,
+    pub covariance_parameters: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CovarianceParameterEstimate {
+    pub parameter: String,
+    pub estimate: f64,
+    pub std_error: f64,
+    pub wald_z: f64,
+    pub significance: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MixedModelFixedEffectTest {
+    pub source: String,
+    pub numerator_df: f64,
+    pub denominator_df: f64,
+    pub f: f64,
+    pub significance: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MixedModelEstimatedMean {
+    pub factor_name: String,
+    pub factor_value: String,
+    pub mean: f64,
+    pub std_error: f64,
+    pub df: f64,
+    pub confidence_interval: ConfidenceInterval,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct MauchlyTest {
     pub tests: HashMap<String, MauchlyTestEntry>,
@@ -11,7 +11,7 @@ use crate::models::{
 use super::core::{ calculate_observed_power, parse_within_subject_factors };
 
 /// Calculate tests of between-subjects effects
-fn calculate_between_subjects_effects(
+pub fn calculate_between_subjects_effects(
     data: &AnalysisData,
     config: &RepeatedMeasuresConfig
 ) -> Result<TestsBetweenSubjectsEffects, String> {
@@ -30,7 +30,7 @@ fn calculate_between_subjects_effects(
         let mut intercept_effects = HashMap::new();
 
         // Process each dependent variable
-        for (factor_name, factors) in &within_factors.measures {
+        for factors in within_factors.measures.values() {
             let var_names: Vec<String> = factors
                 .iter()
                 .map(|f| f.dependent_variable.clone())
@@ -92,12 +92,7 @@ fn calculate_between_subjects_effects(
                 let noncent_parameter = ss_intercept / ms_error;
 
                 // Calculate observed power
-                let observed_power = calculate_observed_power(
-                    f_value,
-                    df_intercept as f64,
-                    df_error as f64,
-                    0.05
-                );
+                let observed_power = calculate_observed_power(df_intercept, df_error, f_value, 0.05);
 
                 // Create effect entry for intercept
                 let intercept_entry = TestEffectEntry {
@@ -145,7 +140,7 @@ fn calculate_between_subjects_effects(
     };
 
     // If we have between-subjects factors
-    for (factor_name, factors) in &within_factors.measures {
+    for factors in within_factors.measures.values() {
         let var_names: Vec<String> = factors
             .iter()
             .map(|f| f.dependent_variable.clone())
@@ -224,24 +219,24 @@ fn calculate_between_subjects_effects(
             let p = if factor_groups[0].is_empty() { 1 } else { factor_groups[0].len() };
 
             let y = DVector::from_iterator(n, data_values.iter().cloned());
-            let mut X = DMatrix::zeros(n, p + 1); // +1 for intercept
+            let mut x_matrix = DMatrix::zeros(n, p + 1); // +1 for intercept
 
             // Set intercept column
             for i in 0..n {
-                X[(i, 0)] = 1.0;
+                x_matrix[(i, 0)] = 1.0;
             }
 
             // Set factor columns
             for i in 0..n {
                 for j in 0..p {
                     if j < factor_groups[i].len() {
-                        X[(i, j + 1)] = factor_groups[i][j];
+                        x_matrix[(i, j + 1)] = factor_groups[i][j];
                     }
                 }
             }
 
             // Calculate (X'X)^-1 X'y for parameter estimates
-            let xtx = X.transpose() * &X;
+            let xtx = x_matrix.transpose() * &x_matrix;
             let xtx_inv = match xtx.try_inverse() {
                 Some(inv) => inv,
                 None => {
@@ -250,11 +245,11 @@ fn calculate_between_subjects_effects(
                 }
             };
 
-            let beta = xtx_inv * X.transpose() * y;
+            let beta = &xtx_inv * x_matrix.transpose() * &y;
 
             // Calculate fitted values and residuals
-            let y_hat = X * beta;
-            let residuals = y - y_hat;
+            let y_hat = &x_matrix * &beta;
+            let residuals = &y - &y_hat;
 
             // Calculate sums of squares
             let y_mean = y.sum() / (n as f64);
@@ -293,12 +288,7 @@ fn calculate_between_subjects_effects(
             let noncent_parameter = ss_model / ms_error;
 
             // Calculate observed power
-            let observed_power = calculate_observed_power(
-                f_value,
-                df_model as f64,
-                df_error as f64,
-                0.05
-            );
+            let observed_power = calculate_observed_power(df_model, df_error, f_value, 0.05);
 
             // Create effect entry for model
             let model_entry = TestEffectEntry {
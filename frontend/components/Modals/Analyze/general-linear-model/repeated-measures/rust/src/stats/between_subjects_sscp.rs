@@ -7,13 +7,9 @@ use crate::models::{
 };
 
 use super::common::{
-    build_design_matrix_and_response,
     extract_dependent_value,
-    get_factor_combinations,
     get_factor_levels,
     generate_interaction_terms,
-    matrix_multiply,
-    matrix_transpose,
     parse_interaction_term,
     data_value_to_string,
 };
@@ -101,7 +97,7 @@ pub fn calculate_between_subjects_sscp(
             let mut level_values: Vec<Vec<f64>> = vec![Vec::new(); dependent_vars.len()];
 
             // Get values for each dependent variable for this level
-            for (dep_idx, sub_var) in dependent_vars.iter().enumerate() {
+            for (dep_idx, _) in dependent_vars.iter().enumerate() {
                 // Get all records with this factor level
                 for rec_idx in &all_records[dep_idx] {
                     let mut found_level = false;
@@ -222,7 +218,7 @@ pub fn calculate_between_subjects_sscp(
                 let mut combo_values: Vec<Vec<f64>> = vec![Vec::new(); dependent_vars.len()];
 
                 // Get values for each dependent variable for this combination
-                for (dep_idx, sub_var) in dependent_vars.iter().enumerate() {
+                for (dep_idx, _) in dependent_vars.iter().enumerate() {
                     // Get all records that match this combination
                     for rec_idx in &all_records[dep_idx] {
                         let mut all_factors_match = true;
@@ -285,7 +281,7 @@ pub fn calculate_between_subjects_sscp(
                     let mut level_values: Vec<Vec<f64>> = vec![Vec::new(); dependent_vars.len()];
 
                     // Get values for each dependent variable for this level
-                    for (dep_idx, sub_var) in dependent_vars.iter().enumerate() {
+                    for (dep_idx, _) in dependent_vars.iter().enumerate() {
                         for rec_idx in &all_records[dep_idx] {
                             let mut found_level = false;
 
@@ -384,9 +380,9 @@ pub fn calculate_between_subjects_sscp(
     // 4. Create a "Model" SSCP matrix (sum of all effects)
     let mut model_combined_sscp = HashMap::new();
 
-    for (i, var1) in dependent_vars.iter().enumerate() {
+    for var1 in dependent_vars {
         let mut row_values = HashMap::new();
-        for (j, var2) in dependent_vars.iter().enumerate() {
+        for var2 in dependent_vars {
             let mut total_sscp = 0.0;
 
             // Sum over all effect matrices (excluding error)
@@ -447,7 +443,7 @@ pub fn calculate_between_subjects_sscp(
                     let mut predicted_j = grand_means[j];
 
                     // Add factor effects
-                    for (factor, level) in &record_factor_levels {
+                    for factor in record_factor_levels.keys() {
                         if let Some(matrix) = matrices.get(factor) {
                             if let Some(factor_row) = matrix.values.get(var1) {
                                 if !factor_row.is_empty() {
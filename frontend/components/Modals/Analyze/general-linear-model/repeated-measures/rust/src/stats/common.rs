@@ -5,7 +5,7 @@ use nalgebra::{ DMatrix, DVector };
 use std::collections::{ HashMap, HashSet };
 use crate::models::config::ContrastMethod;
 use crate::models::{
-    data::{ AnalysisData, DataRecord, DataValue },
+    data::{ AnalysisData, DataRecord, DataValue, VariableDefinition },
     config::RepeatedMeasuresConfig,
 };
 
@@ -63,7 +63,7 @@ pub fn calculate_t_critical(df: usize, alpha: f64) -> f64 {
     }
 
     StudentsT::new(0.0, 1.0, df as f64)
-        .and_then(|dist| Ok(dist.inverse_cdf(1.0 - alpha)))
+        .map(|dist| dist.inverse_cdf(1.0 - alpha))
         .unwrap_or_else(|_| {
             // Fallback to bisection method
             let dist = StudentsT::new(0.0, 1.0, df as f64).unwrap();
@@ -165,7 +165,7 @@ pub fn f_distribution_cdf(x: f64, df1: f64, df2: f64) -> f64 {
         .unwrap_or_else(|_| {
             // Fallback approximation
             let z = (df1 * x) / (df1 * x + df2);
-            z.min(1.0).max(0.0)
+            z.clamp(0.0, 1.0)
         })
 }
 
@@ -506,8 +506,7 @@ pub fn get_level_values_adjusted(
     values: &[f64],
     data: &AnalysisData,
     factor: &str,
-    level: &str,
-    dep_var_name: &str
+    level: &str
 ) -> Result<Vec<f64>, String> {
     let mut level_values = Vec::new();
     let mut i = 0;
@@ -733,7 +732,7 @@ pub fn build_design_matrix_and_response(
                             // Create dummy variables based on the factor levels
                             let factor_value = record.values
                                 .get(factor)
-                                .map(|v| data_value_to_string(v))
+                                .map(data_value_to_string)
                                 .unwrap_or_default();
 
                             // Use effect coding or dummy coding based on contrast type
@@ -743,7 +742,7 @@ pub fn build_design_matrix_and_response(
                                     for level in &levels[0..levels.len() - 1] {
                                         if &factor_value == level {
                                             x_row.push(1.0);
-                                        } else if &factor_value == &levels[levels.len() - 1] {
+                                        } else if factor_value == levels[levels.len() - 1] {
                                             x_row.push(-1.0);
                                         } else {
                                             x_row.push(0.0);
@@ -838,3 +837,198 @@ pub fn build_design_matrix_and_response(
 
     Ok((x_matrix, y_vector))
 }
+
+/// Find the value of a variable for one case in variable-sliced record groups
+pub fn find_case_value<'a>(
+    groups: &'a [Vec<DataRecord>],
+    defs: &[Vec<VariableDefinition>],
+    variable: &str,
+    case_idx: usize
+) -> Option<&'a DataValue> {
+    defs.iter()
+        .position(|group_defs| group_defs.iter().any(|def| def.name == variable))
+        .and_then(|group_idx| groups.get(group_idx))
+        .and_then(|records| records.get(case_idx))
+        .and_then(|record| record.values.get(variable))
+}
+
+/// Between-subjects design matrix with its matching matrix of responses
+pub struct BetweenSubjectsDesign {
+    pub parameter_names: Vec<String>,
+    /// Design matrix columns belonging to each model term, in column order
+    pub term_columns: Vec<(String, Vec<usize>)>,
+    pub x_matrix: DMatrix<f64>,
+    pub y_matrix: DMatrix<f64>,
+    /// Original case index of every design matrix row
+    pub case_indices: Vec<usize>,
+}
+
+/// Build the between-subjects design (intercept, factors, covariates and factor interactions)
+/// for the given response variables, using complete cases only
+///
+/// Factors use reference coding against their last level, or effect coding (last level = -1)
+/// when `effect_coding` is set; interaction columns are products of the factor columns.
+pub fn build_between_subjects_design(
+    data: &AnalysisData,
+    config: &RepeatedMeasuresConfig,
+    response_vars: &[String],
+    effect_coding: bool
+) -> Result<BetweenSubjectsDesign, String> {
+    let factors = config.main.factors_var.clone().unwrap_or_default();
+    let covariates = config.main.covariates.clone().unwrap_or_default();
+    let interaction_terms = if factors.len() > 1 {
+        generate_interaction_terms(&factors)
+    } else {
+        Vec::new()
+    };
+
+    let mut factor_levels = HashMap::new();
+    for factor in &factors {
+        factor_levels.insert(factor.clone(), get_factor_levels(data, factor)?);
+    }
+
+    // Parameter names follow the column order of the design matrix
+    let mut parameter_names = vec!["Intercept".to_string()];
+    let mut term_columns = vec![("Intercept".to_string(), vec![0])];
+    for factor in &factors {
+        let levels = &factor_levels[factor];
+        let first_col = parameter_names.len();
+        for level in &levels[..levels.len().saturating_sub(1)] {
+            parameter_names.push(format!("[{}={}]", factor, level));
+        }
+        term_columns.push((factor.clone(), (first_col..parameter_names.len()).collect()));
+    }
+    for covariate in &covariates {
+        term_columns.push((covariate.clone(), vec![parameter_names.len()]));
+        parameter_names.push(covariate.clone());
+    }
+    let mut interaction_columns: Vec<Vec<(String, String)>> = Vec::new();
+    for term in &interaction_terms {
+        let mut combinations: Vec<Vec<(String, String)>> = vec![Vec::new()];
+        for factor in parse_interaction_term(term) {
+            let levels = &factor_levels[&factor];
+            let non_reference = &levels[..levels.len().saturating_sub(1)];
+            combinations = combinations
+                .into_iter()
+                .flat_map(|combo| {
+                    let factor = factor.clone();
+                    non_reference.iter().map(move |level| {
+                        let mut extended = combo.clone();
+                        extended.push((factor.clone(), level.clone()));
+                        extended
+                    })
+                })
+                .collect();
+        }
+        let first_col = parameter_names.len();
+        for combo in combinations {
+            parameter_names.push(
+                combo
+                    .iter()
+                    .map(|(factor, level)| format!("[{}={}]", factor, level))
+                    .collect::<Vec<_>>()
+                    .join("*")
+            );
+            interaction_columns.push(combo);
+        }
+        term_columns.push((term.clone(), (first_col..parameter_names.len()).collect()));
+    }
+
+    // Coded value of one factor column for a case at the given level
+    let code = |factor: &str, case_level: &str, column_level: &str| -> f64 {
+        if case_level == column_level {
+            1.0
+        } else if effect_coding && factor_levels[factor].last().is_some_and(|last| last == case_level) {
+            -1.0
+        } else {
+            0.0
+        }
+    };
+
+    let n_cases = data.subject_data
+        .iter()
+        .map(|records| records.len())
+        .max()
+        .unwrap_or(0);
+
+    let mut x_rows: Vec<Vec<f64>> = Vec::new();
+    let mut y_rows: Vec<Vec<f64>> = Vec::new();
+    let mut case_indices = Vec::new();
+    'cases: for case_idx in 0..n_cases {
+        let mut y_row = Vec::with_capacity(response_vars.len());
+        for variable in response_vars {
+            match find_case_value(&data.subject_data, &data.subject_data_defs, variable, case_idx) {
+                Some(DataValue::Number(value)) => y_row.push(*value),
+                _ => {
+                    continue 'cases;
+                }
+            }
+        }
+
+        let mut case_levels = HashMap::new();
+        for factor in &factors {
+            match find_case_value(&data.factors_data, &data.factors_data_defs, factor, case_idx) {
+                Some(DataValue::Null) | None => {
+                    continue 'cases;
+                }
+                Some(value) => {
+                    case_levels.insert(factor.clone(), data_value_to_string(value));
+                }
+            }
+        }
+
+        let mut x_row = vec![1.0];
+        for factor in &factors {
+            let levels = &factor_levels[factor];
+            for level in &levels[..levels.len().saturating_sub(1)] {
+                x_row.push(code(factor, &case_levels[factor], level));
+            }
+        }
+        for covariate in &covariates {
+            let value = match (&data.covariate_data, &data.covariate_data_defs) {
+                (Some(groups), Some(defs)) => find_case_value(groups, defs, covariate, case_idx),
+                _ => None,
+            };
+            match value {
+                Some(DataValue::Number(value)) => x_row.push(*value),
+                _ => {
+                    continue 'cases;
+                }
+            }
+        }
+        for combo in &interaction_columns {
+            x_row.push(
+                combo
+                    .iter()
+                    .map(|(factor, level)| code(factor, &case_levels[factor], level))
+                    .product()
+            );
+        }
+
+        x_rows.push(x_row);
+        y_rows.push(y_row);
+        case_indices.push(case_idx);
+    }
+
+    let n = x_rows.len();
+    let p = parameter_names.len();
+
+    Ok(BetweenSubjectsDesign {
+        parameter_names,
+        term_columns,
+        x_matrix: DMatrix::from_fn(n, p, |i, j| x_rows[i][j]),
+        y_matrix: DMatrix::from_fn(n, response_vars.len(), |i, j| y_rows[i][j]),
+        case_indices,
+    })
+}
+
+/// Upper-tail F probability allowing fractional degrees of freedom
+pub fn f_upper_tail(df1: f64, df2: f64, f_value: f64) -> f64 {
+    if df1 <= 0.0 || df2 <= 0.0 || !f_value.is_finite() {
+        return f64::NAN;
+    }
+
+    FisherSnedecor::new(df1, df2)
+        .map(|dist| 1.0 - dist.cdf(f_value.max(0.0)))
+        .unwrap_or(f64::NAN)
+}
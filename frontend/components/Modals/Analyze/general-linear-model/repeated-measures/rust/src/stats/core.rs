@@ -2,16 +2,18 @@ pub use crate::stats::bartlett_test::*;
 pub use crate::stats::between_subjects_effects::*;
 pub use crate::stats::between_subjects_sscp::*;
 pub use crate::stats::common::*;
+pub use crate::stats::custom_hypothesis::*;
 pub use crate::stats::descriptive_statistics::*;
+pub use crate::stats::doubly_multivariate::*;
 pub use crate::stats::emmeans::*;
 pub use crate::stats::estimable_function::*;
 pub use crate::stats::mauchly_test::*;
+pub use crate::stats::mixed_model::*;
 pub use crate::stats::multivariate_tests::*;
 pub use crate::stats::parameter_estimates::*;
 pub use crate::stats::parse_factors::*;
 pub use crate::stats::posthoc::*;
 pub use crate::stats::residual_sscp_matrix::*;
 pub use crate::stats::sscp_matrix::*;
-pub use crate::stats::summary_processing::*;
 pub use crate::stats::univariate_tests::*;
 pub use crate::stats::within_subjects_effects::*;
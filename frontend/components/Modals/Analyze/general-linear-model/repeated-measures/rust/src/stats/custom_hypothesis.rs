@@ -0,0 +1,316 @@
+use std::collections::HashMap;
+
+use nalgebra::DMatrix;
+use regex::Regex;
+
+use crate::models::{
+    config::{ CustomHypothesisSpec, RepeatedMeasuresConfig },
+    data::AnalysisData,
+    result::{
+        ConfidenceInterval,
+        CustomContrastEstimate,
+        CustomHypothesisResult,
+        CustomUnivariateTest,
+    },
+};
+
+use super::core::{
+    build_between_subjects_design,
+    calculate_multivariate_statistics_from_sscp,
+    calculate_t_critical,
+    calculate_t_significance,
+    f_upper_tail,
+    from_dmatrix,
+};
+
+/// Between-subjects least-squares fit of the repeated measures shared by all custom hypotheses
+struct RepeatedMeasuresFit {
+    parameter_names: Vec<String>,
+    measures: Vec<String>,
+    /// Within-subjects variables as selected in the dialog, e.g. "t1_(1,score)"
+    sub_vars: Vec<String>,
+    b_matrix: DMatrix<f64>,
+    xtx_inv: DMatrix<f64>,
+    e_matrix: DMatrix<f64>,
+    error_df: usize,
+}
+
+/// Calculate user-defined hypothesis tests of the form LBM = K
+///
+/// B holds the between-subjects parameters (rows) for every repeated measure (columns).
+/// L selects linear combinations of the between-subjects parameters, M transforms the
+/// repeated measures into within-subjects contrasts (identity when omitted) and K holds
+/// the hypothesized values (zero when omitted).
+pub fn calculate_custom_hypothesis_tests(
+    data: &AnalysisData,
+    config: &RepeatedMeasuresConfig
+) -> Result<Vec<CustomHypothesisResult>, String> {
+    if config.custom.tests.is_empty() {
+        return Err("No custom hypothesis tests specified".to_string());
+    }
+
+    let fit = fit_repeated_measures_model(data, config)?;
+    let alpha = config.options.sig_level.unwrap_or(0.05);
+
+    config.custom.tests
+        .iter()
+        .enumerate()
+        .map(|(test_idx, spec)| {
+            let label = spec.label
+                .as_ref()
+                .filter(|l| !l.trim().is_empty())
+                .cloned()
+                .unwrap_or_else(|| format!("Custom Hypothesis {}", test_idx + 1));
+            evaluate_custom_hypothesis(&fit, spec, label, alpha)
+        })
+        .collect()
+}
+
+/// Fit the between-subjects model (intercept, reference-coded factors, covariates and
+/// factor interactions) to the repeated measures
+fn fit_repeated_measures_model(
+    data: &AnalysisData,
+    config: &RepeatedMeasuresConfig
+) -> Result<RepeatedMeasuresFit, String> {
+    let sub_vars = config.main.sub_var.clone().unwrap_or_default();
+    if sub_vars.is_empty() {
+        return Err("No within-subjects variables specified".to_string());
+    }
+
+    // The data is keyed by the bare variable name in front of the "_(levels,measure)" annotation
+    let var_pattern = Regex::new(r"^(.+)_\((\d+(?:,\d+)*),([^,()]+)\)$").map_err(|e| e.to_string())?;
+    let measures: Vec<String> = sub_vars
+        .iter()
+        .map(|name| {
+            var_pattern
+                .captures(name.trim())
+                .map(|captures| captures[1].trim().to_string())
+                .unwrap_or_else(|| name.trim().to_string())
+        })
+        .collect();
+
+    let design = build_between_subjects_design(data, config, &measures, false)?;
+    let n = design.x_matrix.nrows();
+    let p = design.parameter_names.len();
+    if n <= p {
+        return Err("Not enough complete cases to estimate the error SSCP matrix".to_string());
+    }
+
+    let xtx_inv = (design.x_matrix.transpose() * &design.x_matrix)
+        .try_inverse()
+        .ok_or_else(|| "Could not invert X'X matrix - possibly due to empty cells".to_string())?;
+    let b_matrix = &xtx_inv * design.x_matrix.transpose() * &design.y_matrix;
+    let residuals = &design.y_matrix - &design.x_matrix * &b_matrix;
+    let e_matrix = residuals.transpose() * &residuals;
+
+    Ok(RepeatedMeasuresFit {
+        parameter_names: design.parameter_names,
+        measures,
+        sub_vars,
+        b_matrix,
+        xtx_inv,
+        e_matrix,
+        error_df: n - p,
+    })
+}
+
+/// L, M and K matrices of one hypothesis
+type HypothesisMatrices = (DMatrix<f64>, DMatrix<f64>, DMatrix<f64>);
+
+/// Build the L, M and K matrices of a hypothesis from their name-keyed specification
+fn build_lmk_matrices(
+    fit: &RepeatedMeasuresFit,
+    spec: &CustomHypothesisSpec,
+    label: &str
+) -> Result<HypothesisMatrices, String> {
+    if spec.l_matrix.is_empty() {
+        return Err(format!("Custom hypothesis '{}' has no L matrix rows", label));
+    }
+
+    let mut l_matrix = DMatrix::zeros(spec.l_matrix.len(), fit.parameter_names.len());
+    for (row_idx, row) in spec.l_matrix.iter().enumerate() {
+        for (name, coefficient) in row {
+            let col_idx = fit.parameter_names
+                .iter()
+                .position(|param| param == name.trim())
+                .ok_or_else(||
+                    format!(
+                        "Custom hypothesis '{}' refers to unknown parameter '{}'. Available parameters: {}",
+                        label,
+                        name,
+                        fit.parameter_names.join(", ")
+                    )
+                )?;
+            l_matrix[(row_idx, col_idx)] = *coefficient;
+        }
+    }
+
+    let m_matrix = match &spec.m_matrix {
+        Some(columns) if !columns.is_empty() => {
+            let mut m_matrix = DMatrix::zeros(fit.measures.len(), columns.len());
+            for (col_idx, column) in columns.iter().enumerate() {
+                for (name, coefficient) in column {
+                    let row_idx = fit.measures
+                        .iter()
+                        .zip(&fit.sub_vars)
+                        .position(|(measure, sub_var)| measure == name.trim() || sub_var == name.trim())
+                        .ok_or_else(||
+                            format!(
+                                "Custom hypothesis '{}' refers to unknown measure variable '{}' in the M matrix",
+                                label,
+                                name
+                            )
+                        )?;
+                    m_matrix[(row_idx, col_idx)] = *coefficient;
+                }
+            }
+            m_matrix
+        }
+        _ => DMatrix::identity(fit.measures.len(), fit.measures.len()),
+    };
+
+    let k_matrix = match &spec.k_matrix {
+        Some(rows) => {
+            if
+                rows.len() != l_matrix.nrows() ||
+                rows.iter().any(|row| row.len() != m_matrix.ncols())
+            {
+                return Err(
+                    format!(
+                        "Custom hypothesis '{}' requires a K matrix of {} rows by {} columns",
+                        label,
+                        l_matrix.nrows(),
+                        m_matrix.ncols()
+                    )
+                );
+            }
+            DMatrix::from_fn(rows.len(), m_matrix.ncols(), |i, j| rows[i][j])
+        }
+        None => DMatrix::zeros(l_matrix.nrows(), m_matrix.ncols()),
+    };
+
+    Ok((l_matrix, m_matrix, k_matrix))
+}
+
+fn evaluate_custom_hypothesis(
+    fit: &RepeatedMeasuresFit,
+    spec: &CustomHypothesisSpec,
+    label: String,
+    alpha: f64
+) -> Result<CustomHypothesisResult, String> {
+    let (l_matrix, m_matrix, k_matrix) = build_lmk_matrices(fit, spec, &label)?;
+    let mut notes = Vec::new();
+
+    let transformed_variables: Vec<String> = if spec.m_matrix.as_ref().is_some_and(|m| !m.is_empty()) {
+        (1..=m_matrix.ncols()).map(|j| format!("T{}", j)).collect()
+    } else {
+        fit.measures.clone()
+    };
+
+    let error_df = fit.error_df;
+    let estimates = &l_matrix * &fit.b_matrix * &m_matrix;
+    let differences = &estimates - &k_matrix;
+    let l_xtx_inv_lt = &l_matrix * &fit.xtx_inv * l_matrix.transpose();
+    let e_transformed = m_matrix.transpose() * &fit.e_matrix * &m_matrix;
+
+    // Contrast estimates and standard errors for each L row and transformed variable
+    let t_critical = calculate_t_critical(error_df, alpha / 2.0);
+    let mut contrast_estimates = Vec::new();
+    for i in 0..l_matrix.nrows() {
+        for j in 0..m_matrix.ncols() {
+            let variance = (l_xtx_inv_lt[(i, i)] * e_transformed[(j, j)]) / (error_df as f64);
+            let std_error = variance.max(0.0).sqrt();
+            let t_value = if std_error > 0.0 { differences[(i, j)] / std_error } else { f64::NAN };
+            contrast_estimates.push(CustomContrastEstimate {
+                contrast: format!("L{}", i + 1),
+                transformed_variable: transformed_variables[j].clone(),
+                estimate: estimates[(i, j)],
+                hypothesized_value: k_matrix[(i, j)],
+                difference: differences[(i, j)],
+                std_error,
+                t_value,
+                significance: calculate_t_significance(error_df, t_value),
+                confidence_interval: ConfidenceInterval {
+                    lower_bound: differences[(i, j)] - t_critical * std_error,
+                    upper_bound: differences[(i, j)] + t_critical * std_error,
+                },
+            });
+        }
+    }
+
+    let hypothesis_df = l_xtx_inv_lt.rank(1e-10);
+    if hypothesis_df == 0 {
+        return Err(format!("The L matrix of custom hypothesis '{}' has rank zero", label));
+    }
+    if hypothesis_df < l_matrix.nrows() {
+        notes.push(
+            format!(
+                "The L matrix has {} rows but rank {}; the tests use {} hypothesis degrees of freedom.",
+                l_matrix.nrows(),
+                hypothesis_df,
+                hypothesis_df
+            )
+        );
+    }
+
+    let l_xtx_inv_lt_inv = l_xtx_inv_lt
+        .clone()
+        .pseudo_inverse(1e-12)
+        .map_err(|e| format!("Failed to invert L(X'X)^-1L' for '{}': {}", label, e))?;
+    let h_transformed = differences.transpose() * l_xtx_inv_lt_inv * &differences;
+
+    // Univariate F-tests on each transformed variable
+    let univariate_tests = (0..m_matrix.ncols())
+        .map(|j| {
+            let hypothesis_ss = h_transformed[(j, j)];
+            let error_ss = e_transformed[(j, j)];
+            let f_value = if error_ss > 0.0 {
+                hypothesis_ss / (hypothesis_df as f64) / (error_ss / (error_df as f64))
+            } else {
+                f64::NAN
+            };
+            CustomUnivariateTest {
+                transformed_variable: transformed_variables[j].clone(),
+                hypothesis_ss,
+                error_ss,
+                hypothesis_df,
+                error_df,
+                f_value,
+                significance: f_upper_tail(hypothesis_df as f64, error_df as f64, f_value),
+                partial_eta_squared: if hypothesis_ss + error_ss > 0.0 {
+                    hypothesis_ss / (hypothesis_ss + error_ss)
+                } else {
+                    0.0
+                },
+            }
+        })
+        .collect();
+
+    let multivariate_tests = match
+        calculate_multivariate_statistics_from_sscp(&h_transformed, &e_transformed, hypothesis_df, error_df, alpha)
+    {
+        Ok(tests) => tests,
+        Err(e) => {
+            notes.push(e);
+            HashMap::new()
+        }
+    };
+
+    Ok(CustomHypothesisResult {
+        label,
+        parameter_names: fit.parameter_names.clone(),
+        measures: fit.measures.clone(),
+        l_matrix: from_dmatrix(&l_matrix),
+        m_matrix: from_dmatrix(&m_matrix),
+        k_matrix: from_dmatrix(&k_matrix),
+        transformed_variables,
+        contrast_estimates,
+        multivariate_tests,
+        univariate_tests,
+        hypothesis_sscp: from_dmatrix(&h_transformed),
+        error_sscp: from_dmatrix(&e_transformed),
+        hypothesis_df,
+        error_df,
+        notes,
+    })
+}
@@ -1,4 +1,4 @@
-use statrs::statistics::{ Data, Statistics };
+use statrs::statistics::Statistics;
 use std::collections::HashMap;
 
 use crate::models::{
@@ -10,7 +10,7 @@ use crate::models::{
 use super::core::parse_within_subject_factors;
 
 /// Calculate descriptive statistics for repeated measures data
-fn calculate_descriptive_statistics(
+pub fn calculate_descriptive_statistics(
     data: &AnalysisData,
     config: &RepeatedMeasuresConfig
 ) -> Result<HashMap<String, DescriptiveStatistics>, String> {
@@ -20,7 +20,7 @@ fn calculate_descriptive_statistics(
     let within_factors = parse_within_subject_factors(data, config)?;
 
     // Process each dependent variable (measure)
-    for (factor_name, factors) in &within_factors.measures {
+    for factors in within_factors.measures.values() {
         for factor in factors {
             let var_name = &factor.dependent_variable;
 
@@ -51,11 +51,9 @@ fn calculate_descriptive_statistics(
 
                 // Calculate statistics using statrs
                 if !values.is_empty() {
-                    let data_obj = Data::new(&values);
-
                     let stats_entry = StatsEntry {
-                        mean: data_obj.mean(),
-                        std_deviation: data_obj.std_dev(),
+                        mean: values.iter().mean(),
+                        std_deviation: values.iter().std_dev(),
                         n: values.len(),
                     };
 
@@ -133,11 +131,9 @@ fn add_between_subjects_groups(
 
                 // Calculate statistics using statrs
                 if !values.is_empty() {
-                    let data_obj = Data::new(&values);
-
                     let stats_entry = StatsEntry {
-                        mean: data_obj.mean(),
-                        std_deviation: data_obj.std_dev(),
+                        mean: values.iter().mean(),
+                        std_deviation: values.iter().std_dev(),
                         n: values.len(),
                     };
 
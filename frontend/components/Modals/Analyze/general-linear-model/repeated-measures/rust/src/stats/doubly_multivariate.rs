@@ -0,0 +1,385 @@
+use std::collections::HashMap;
+
+use nalgebra::{ DMatrix, DVector };
+
+use crate::models::{
+    config::RepeatedMeasuresConfig,
+    data::AnalysisData,
+    result::{
+        ConfidenceInterval,
+        DoublyMultivariateResult,
+        DoublyMultivariateUnivariateTest,
+        EstimatedMarginalMean,
+    },
+};
+
+use super::core::{
+    build_between_subjects_design,
+    BetweenSubjectsDesign,
+    calculate_multivariate_statistics_from_sscp,
+    calculate_t_critical,
+    f_upper_tail,
+    get_factor_levels,
+    parse_within_subject_layout,
+    within_subject_terms,
+    WithinSubjectsLayout,
+};
+
+/// Between-subjects fit of the stacked responses, ordered by measure and then by cell
+struct DoublyMultivariateFit {
+    b_matrix: DMatrix<f64>,
+    xtx_inv: DMatrix<f64>,
+    e_matrix: DMatrix<f64>,
+    error_df: usize,
+}
+
+/// Calculate the doubly multivariate repeated measures analysis
+///
+/// Every within-subjects cell holds a vector of measures. Each within-subjects effect is
+/// tested on the stacked transformed responses Y(I ⊗ M), where M holds orthonormal polynomial
+/// contrasts for the factors in the effect and normalized averages over the remaining factors.
+/// Univariate follow-ups and estimated marginal means are reported separately for every measure.
+pub fn calculate_doubly_multivariate_tests(
+    data: &AnalysisData,
+    config: &RepeatedMeasuresConfig
+) -> Result<DoublyMultivariateResult, String> {
+    let (design, layout) = parse_within_subject_layout(data, config)?;
+    if layout.measures.len() < 2 {
+        return Err(
+            "A doubly multivariate analysis requires at least two measures per within-subjects cell".to_string()
+        );
+    }
+    let measures = &layout.measures;
+    let within_factors = &layout.within_factors;
+
+    // Response columns ordered by measure, then by cell
+    let response_vars = layout.variables.concat();
+    let between = build_between_subjects_design(data, config, &response_vars, true)?;
+    let n = between.x_matrix.nrows();
+    let p = between.parameter_names.len();
+    if n <= p {
+        return Err("Not enough complete cases to estimate the error SSCP matrix".to_string());
+    }
+    let error_df = n - p;
+
+    let x_matrix = &between.x_matrix;
+    let xtx_inv = (x_matrix.transpose() * x_matrix)
+        .try_inverse()
+        .ok_or_else(|| "Could not invert X'X matrix - possibly due to empty cells".to_string())?;
+    let b_matrix = &xtx_inv * x_matrix.transpose() * &between.y_matrix;
+    let residuals = &between.y_matrix - x_matrix * &b_matrix;
+    let e_matrix = residuals.transpose() * &residuals;
+
+    // Within-subjects terms: the average over all cells, then main effects and interactions
+    let mut within_terms: Vec<Vec<usize>> = vec![Vec::new()];
+    within_terms.extend(within_subject_terms(within_factors));
+
+    let alpha = config.options.sig_level.unwrap_or(0.05);
+    let mut effects = Vec::new();
+    let mut multivariate_tests = HashMap::new();
+    let mut univariate_tests: HashMap<String, Vec<DoublyMultivariateUnivariateTest>> = HashMap::new();
+    let mut notes = Vec::new();
+
+    for within_term in &within_terms {
+        let m_cell = within_transformation(&layout.factor_levels, within_term);
+        let r = m_cell.ncols();
+        let m_matrix = kronecker_identity(measures.len(), &m_cell);
+        let bm = &b_matrix * &m_matrix;
+        let e_transformed = m_matrix.transpose() * &e_matrix * &m_matrix;
+        let within_name = within_term
+            .iter()
+            .map(|&i| within_factors[i].clone())
+            .collect::<Vec<_>>()
+            .join("*");
+
+        for (between_name, columns) in &between.term_columns {
+            let effect = match (within_name.is_empty(), between_name == "Intercept") {
+                (true, _) => between_name.clone(),
+                (false, true) => within_name.clone(),
+                (false, false) => format!("{}*{}", within_name, between_name),
+            };
+
+            let q = columns.len();
+            let l_matrix = DMatrix::from_fn(q, p, |i, j| if columns[i] == j { 1.0 } else { 0.0 });
+            let lbm = &l_matrix * &bm;
+            let l_xtx_inv_lt = &l_matrix * &xtx_inv * l_matrix.transpose();
+            let l_xtx_inv_lt_inv = match l_xtx_inv_lt.try_inverse() {
+                Some(inverse) => inverse,
+                None => {
+                    notes.push(format!("The hypothesis matrix of '{}' is singular; the effect was not tested.", effect));
+                    continue;
+                }
+            };
+            let h_transformed = lbm.transpose() * l_xtx_inv_lt_inv * &lbm;
+
+            match calculate_multivariate_statistics_from_sscp(&h_transformed, &e_transformed, q, error_df, alpha) {
+                Ok(tests) => {
+                    multivariate_tests.insert(effect.clone(), tests);
+                }
+                Err(e) => notes.push(format!("{}: {}", effect, e)),
+            }
+
+            // Per-measure univariate follow-ups on the measure's own block of transformed variables
+            for (measure_idx, measure) in measures.iter().enumerate() {
+                let offset = measure_idx * r;
+                let h_block = h_transformed.view((offset, offset), (r, r));
+                let e_block = e_transformed.view((offset, offset), (r, r)).into_owned();
+                univariate_tests
+                    .entry(measure.clone())
+                    .or_default()
+                    .push(univariate_follow_up(&effect, h_block.trace(), &e_block, q, error_df));
+            }
+            effects.push(effect);
+        }
+    }
+
+    let targets = marginal_mean_targets(data, config, &between, &layout);
+    let fit = DoublyMultivariateFit {
+        b_matrix,
+        xtx_inv,
+        e_matrix,
+        error_df,
+    };
+    let emmeans = calculate_measure_emmeans(measures, layout.cells.len(), &targets, &fit, config);
+
+    Ok(DoublyMultivariateResult {
+        design,
+        measures: layout.measures.clone(),
+        within_factors: layout.within_factors.clone(),
+        effects,
+        multivariate_tests,
+        univariate_tests,
+        emmeans,
+        error_df,
+        notes,
+    })
+}
+
+/// Orthonormal polynomial contrasts for a factor with `k` equally spaced levels
+fn orthonormal_polynomial_contrasts(k: usize) -> DMatrix<f64> {
+    let mut basis: Vec<DVector<f64>> = Vec::with_capacity(k);
+    for degree in 0..k {
+        let mut column = DVector::from_fn(k, |i, _| ((i + 1) as f64).powi(degree as i32));
+        for previous in &basis {
+            let projection = column.dot(previous);
+            column -= previous * projection;
+        }
+        let norm = column.norm();
+        basis.push(column / norm);
+    }
+
+    DMatrix::from_fn(k, k.saturating_sub(1), |i, j| basis[j + 1][i])
+}
+
+/// Transformation of the cells for one within-subjects term: contrasts for the factors in
+/// the term and a normalized average over all other factors
+fn within_transformation(factor_levels: &[Vec<String>], term: &[usize]) -> DMatrix<f64> {
+    let mut m_matrix = DMatrix::from_element(1, 1, 1.0);
+    for (factor_idx, levels) in factor_levels.iter().enumerate() {
+        let k = levels.len();
+        let factor_matrix = if term.contains(&factor_idx) {
+            orthonormal_polynomial_contrasts(k)
+        } else {
+            DMatrix::from_element(k, 1, 1.0 / (k as f64).sqrt())
+        };
+        m_matrix = m_matrix.kronecker(&factor_matrix);
+    }
+    m_matrix
+}
+
+/// Block-diagonal I ⊗ M applying the same cell transformation to every measure
+fn kronecker_identity(n_measures: usize, m_cell: &DMatrix<f64>) -> DMatrix<f64> {
+    DMatrix::<f64>::identity(n_measures, n_measures).kronecker(m_cell)
+}
+
+/// Sphericity-assumed F-test for one measure, with the Greenhouse-Geisser correction when the
+/// effect has more than one transformed variable
+fn univariate_follow_up(
+    effect: &str,
+    sum_of_squares: f64,
+    e_block: &DMatrix<f64>,
+    hypothesis_df: usize,
+    error_df: usize
+) -> DoublyMultivariateUnivariateTest {
+    let r = e_block.nrows() as f64;
+    let error_sum_of_squares = e_block.trace();
+    let df = (hypothesis_df as f64) * r;
+    let df_error = (error_df as f64) * r;
+    let mean_square = sum_of_squares / df;
+    let f = if error_sum_of_squares > 0.0 {
+        mean_square / (error_sum_of_squares / df_error)
+    } else {
+        f64::NAN
+    };
+
+    let (greenhouse_geisser_epsilon, greenhouse_geisser_significance) = if e_block.nrows() > 1 {
+        let e_squared_trace = (e_block * e_block).trace();
+        let epsilon = if e_squared_trace > 0.0 {
+            (error_sum_of_squares * error_sum_of_squares) / (r * e_squared_trace)
+        } else {
+            f64::NAN
+        };
+        (Some(epsilon), Some(f_upper_tail(df * epsilon, df_error * epsilon, f)))
+    } else {
+        (None, None)
+    };
+
+    DoublyMultivariateUnivariateTest {
+        source: effect.to_string(),
+        sum_of_squares,
+        df,
+        mean_square,
+        f,
+        significance: f_upper_tail(df, df_error, f),
+        error_sum_of_squares,
+        error_df: df_error,
+        partial_eta_squared: if sum_of_squares + error_sum_of_squares > 0.0 {
+            sum_of_squares / (sum_of_squares + error_sum_of_squares)
+        } else {
+            0.0
+        },
+        greenhouse_geisser_epsilon,
+        greenhouse_geisser_significance,
+    }
+}
+
+/// One estimated marginal mean: a between-subjects L vector combined with weights over the
+/// within-subjects cells
+pub struct MarginalMeanTarget {
+    pub factor_name: String,
+    pub factor_value: String,
+    pub between_l: DVector<f64>,
+    pub cell_weights: DVector<f64>,
+}
+
+/// Marginal means for every within-subjects term and between-subjects factor level, averaging
+/// over the effect-coded factors not in the term and fixing covariates at their means
+pub fn marginal_mean_targets(
+    data: &AnalysisData,
+    config: &RepeatedMeasuresConfig,
+    between: &BetweenSubjectsDesign,
+    layout: &WithinSubjectsLayout
+) -> Vec<MarginalMeanTarget> {
+    let n_cells = layout.cells.len();
+    let term_columns = &between.term_columns;
+
+    let mut base_l = DVector::zeros(between.parameter_names.len());
+    base_l[0] = 1.0;
+    for covariate in config.main.covariates.iter().flatten() {
+        if let Some((_, columns)) = term_columns.iter().find(|(name, _)| name == covariate) {
+            base_l[columns[0]] = between.x_matrix.column(columns[0]).mean();
+        }
+    }
+
+    let mut targets = Vec::new();
+    for term in &within_subject_terms(&layout.within_factors) {
+        let mut combinations: Vec<Vec<String>> = vec![Vec::new()];
+        for &factor_idx in term {
+            combinations = combinations
+                .into_iter()
+                .flat_map(|combo| {
+                    layout.factor_levels[factor_idx].iter().map(move |level| {
+                        let mut extended = combo.clone();
+                        extended.push(level.clone());
+                        extended
+                    })
+                })
+                .collect();
+        }
+        let factor_name = term
+            .iter()
+            .map(|&i| layout.within_factors[i].clone())
+            .collect::<Vec<_>>()
+            .join("*");
+        for combo in combinations {
+            let matches: Vec<bool> = layout.cells
+                .iter()
+                .map(|cell| term.iter().zip(&combo).all(|(&i, level)| &cell[i] == level))
+                .collect();
+            let count = matches.iter().filter(|&&m| m).count() as f64;
+            targets.push(MarginalMeanTarget {
+                factor_name: factor_name.clone(),
+                factor_value: combo.join("*"),
+                between_l: base_l.clone(),
+                cell_weights: DVector::from_fn(n_cells, |c, _| if matches[c] { 1.0 / count } else { 0.0 }),
+            });
+        }
+    }
+
+    let average_weights = DVector::from_element(n_cells, 1.0 / (n_cells as f64));
+    for factor in config.main.factors_var.iter().flatten() {
+        let Some((_, columns)) = term_columns.iter().find(|(name, _)| name == factor) else {
+            continue;
+        };
+        let Ok(levels) = get_factor_levels(data, factor) else {
+            continue;
+        };
+        for (level_idx, level) in levels.iter().enumerate() {
+            // The last level is coded -1 on every effect column of the factor
+            let mut between_l = base_l.clone();
+            for (col_idx, &col) in columns.iter().enumerate() {
+                between_l[col] = if col_idx == level_idx {
+                    1.0
+                } else if level_idx == columns.len() {
+                    -1.0
+                } else {
+                    0.0
+                };
+            }
+            targets.push(MarginalMeanTarget {
+                factor_name: factor.clone(),
+                factor_value: level.clone(),
+                between_l,
+                cell_weights: average_weights.clone(),
+            });
+        }
+    }
+
+    targets
+}
+
+/// Estimated marginal means of every measure, with standard errors from the between-subjects
+/// covariance of the parameters and the within-subjects error covariance of the cells
+fn calculate_measure_emmeans(
+    measures: &[String],
+    n_cells: usize,
+    targets: &[MarginalMeanTarget],
+    fit: &DoublyMultivariateFit,
+    config: &RepeatedMeasuresConfig
+) -> HashMap<String, Vec<EstimatedMarginalMean>> {
+    let alpha = config.options.sig_level.unwrap_or(0.05);
+    let sigma = &fit.e_matrix / (fit.error_df as f64);
+    let t_critical = calculate_t_critical(fit.error_df, alpha / 2.0);
+
+    let mut emmeans = HashMap::new();
+    for (measure_idx, measure) in measures.iter().enumerate() {
+        let offset = measure_idx * n_cells;
+        let b_block = fit.b_matrix.columns(offset, n_cells);
+        let sigma_block = sigma.view((offset, offset), (n_cells, n_cells));
+        let means = targets
+            .iter()
+            .map(|target| {
+                let l_vector = &target.between_l;
+                let weights = &target.cell_weights;
+                let mean = (l_vector.transpose() * b_block * weights)[(0, 0)];
+                let between_variance = (l_vector.transpose() * &fit.xtx_inv * l_vector)[(0, 0)];
+                let within_variance = (weights.transpose() * sigma_block * weights)[(0, 0)];
+                let std_error = (between_variance * within_variance).max(0.0).sqrt();
+                EstimatedMarginalMean {
+                    dependent_variable: measure.clone(),
+                    factor_name: target.factor_name.clone(),
+                    factor_value: target.factor_value.clone(),
+                    mean,
+                    std_error,
+                    confidence_interval: ConfidenceInterval {
+                        lower_bound: mean - t_critical * std_error,
+                        upper_bound: mean + t_critical * std_error,
+                    },
+                }
+            })
+            .collect();
+        emmeans.insert(measure.clone(), means);
+    }
+
+    emmeans
+}
@@ -129,10 +129,8 @@ pub fn calculate_emmeans(
 
                                 for records in &data.subject_data {
                                     for record in records {
-                                        if let Some(value) = record.values.get(covar) {
-                                            if let DataValue::Number(num) = value {
-                                                covar_values.push(*num);
-                                            }
+                                        if let Some(DataValue::Number(num)) = record.values.get(covar) {
+                                            covar_values.push(*num);
                                         }
                                     }
                                 }
@@ -32,12 +32,13 @@ pub fn calculate_general_estimable_function(
             // - L5, L7, L9 test other combinations
 
             let contrast_value = match *contrast {
-                "L1" => if param_name == "Intercept" { 1 } else { 0 }
-                "L2" => if i == 1 { 1 } else { 0 }
-                "L3" => if i == 2 { 1 } else { 0 }
-                "L5" => if i == 1 { 1 } else if i == 2 { -1 } else { 0 }
-                "L7" => if i == 3 { 1 } else { 0 }
-                "L9" => if i == 4 { 1 } else { 0 }
+                "L1" if param_name == "Intercept" => 1,
+                "L2" if i == 1 => 1,
+                "L3" if i == 2 => 1,
+                "L5" if i == 1 => 1,
+                "L5" if i == 2 => -1,
+                "L7" if i == 3 => 1,
+                "L9" if i == 4 => 1,
                 _ => 0,
             };
 
@@ -11,7 +11,7 @@ use crate::models::{
 use super::core::parse_within_subject_factors;
 
 /// Calculate Mauchly's Test of Sphericity
-fn calculate_mauchly_test(
+pub fn calculate_mauchly_test(
     data: &AnalysisData,
     config: &RepeatedMeasuresConfig
 ) -> Result<MauchlyTest, String> {
@@ -103,17 +103,16 @@ fn calculate_mauchly_test(
         }
 
         // Calculate the transformed covariance matrix
-        let transformed_cov = contrast_matrix * cov_matrix * contrast_matrix.transpose();
+        let transformed_cov = &contrast_matrix * cov_matrix * contrast_matrix.transpose();
 
         // Calculate determinants
-        let det_cov = cov_matrix.determinant();
         let det_transformed = transformed_cov.determinant();
 
         // Calculate Mauchly's W
         let mauchly_w = if det_transformed.abs() < 1e-10 {
             0.0
         } else {
-            det_transformed / (transformed_cov.trace() / ((k - 1) as f64)).powi(k - 1)
+            det_transformed / (transformed_cov.trace() / ((k - 1) as f64)).powi((k - 1) as i32)
         };
 
         // Calculate chi-square statistic
@@ -130,7 +129,7 @@ fn calculate_mauchly_test(
         // Calculate epsilon adjustments
         // Greenhouse-Geisser epsilon
         let mut eigenvalues = Vec::with_capacity(k - 1);
-        let symmetric_transformed = (transformed_cov + transformed_cov.transpose()) / 2.0;
+        let symmetric_transformed = (&transformed_cov + transformed_cov.transpose()) / 2.0;
 
         for i in 0..k - 1 {
             eigenvalues.push(symmetric_transformed[(i, i)]);
@@ -152,10 +151,10 @@ fn calculate_mauchly_test(
         let huynh_feldt_epsilon = if n_subjects <= k {
             greenhouse_geisser_epsilon
         } else {
-            let numerator = n_subjects * ((k - 1) as f64) * greenhouse_geisser_epsilon - 2.0;
+            let numerator = n * ((k - 1) as f64) * greenhouse_geisser_epsilon - 2.0;
             let denominator =
                 ((k - 1) as f64) *
-                (n_subjects - 1.0 - ((k - 1) as f64) * greenhouse_geisser_epsilon);
+                (n - 1.0 - ((k - 1) as f64) * greenhouse_geisser_epsilon);
 
             if denominator < 1e-10 {
                 greenhouse_geisser_epsilon
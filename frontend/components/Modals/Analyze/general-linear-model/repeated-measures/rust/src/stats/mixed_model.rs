@@ -0,0 +1,917 @@
+use std::collections::HashMap;
+
+use nalgebra::{ DMatrix, DVector };
+use statrs::distribution::{ ContinuousCDF, Normal, StudentsT };
+
+use crate::models::{
+    config::{ CovarianceStructure, DenominatorDfMethod, RepeatedMeasuresConfig },
+    data::{ AnalysisData, DataValue },
+    result::{
+        ConfidenceInterval,
+        CovarianceParameterEstimate,
+        MixedModelEstimatedMean,
+        MixedModelFixedEffectTest,
+        MixedModelInformationCriteria,
+        MixedModelResult,
+    },
+};
+
+use super::core::{
+    build_between_subjects_design,
+    data_value_to_string,
+    f_upper_tail,
+    find_case_value,
+    marginal_mean_targets,
+    parse_within_subject_layout,
+    within_subject_terms,
+    BetweenSubjectsDesign,
+    WithinSubjectsLayout,
+};
+
+/// Observations of one subject: the occasions (cells) observed, their fixed-effects rows and responses
+struct SubjectBlock {
+    cells: Vec<usize>,
+    x_matrix: DMatrix<f64>,
+    y_vector: DVector<f64>,
+}
+
+/// Quantities of the REML fit at one value of the covariance parameters
+struct RemlState {
+    neg2_log_likelihood: f64,
+    beta: DVector<f64>,
+    xtwx_inv: DMatrix<f64>,
+    w_blocks: Vec<DMatrix<f64>>,
+    residuals: Vec<DVector<f64>>,
+}
+
+/// Derivatives of the REML log-likelihood with respect to the covariance parameters
+struct RemlDerivatives {
+    score: DVector<f64>,
+    information: DMatrix<f64>,
+    /// Σ X'W (dΣ/dθk) W X for every parameter k
+    a_matrices: Vec<DMatrix<f64>>,
+    /// Σ X'W (dΣ/dθk) W (dΣ/dθl) W X for every pair of parameters
+    q_matrices: Vec<Vec<DMatrix<f64>>>,
+}
+
+/// Fit a linear mixed model to the repeated measures by restricted maximum likelihood
+///
+/// Every available occasion of a subject is used, so subjects with missing occasions stay in
+/// the analysis. The fixed effects cross the between-subjects design with an effect-coded
+/// within-subjects design, the occasions of a subject share the selected covariance structure
+/// and the F-tests and marginal means use Satterthwaite or Kenward-Roger degrees of freedom.
+/// Data can be wide (one variable per occasion, as in the other repeated measures tables) or
+/// long (one row per occasion with subject, occasion and dependent variables).
+pub fn calculate_mixed_model(
+    data: &AnalysisData,
+    config: &RepeatedMeasuresConfig
+) -> Result<MixedModelResult, String> {
+    let mixed = &config.mixed;
+    let structure = mixed.cov_structure.clone().unwrap_or(CovarianceStructure::Unstructured);
+    let df_method = mixed.df_method.clone().unwrap_or(DenominatorDfMethod::Satterthwaite);
+    let alpha = config.options.sig_level.unwrap_or(0.05);
+    let max_iter = mixed.max_iter.unwrap_or(100);
+    let converge = mixed.converge.unwrap_or(1e-8);
+
+    let (dependent_variable, between, layout, observations) = match
+        (&mixed.subject_var, &mixed.occasion_var, &mixed.dep_var)
+    {
+        (Some(subject_var), Some(occasion_var), Some(dep_var)) =>
+            collect_long_observations(data, config, subject_var, occasion_var, dep_var)?,
+        _ => collect_wide_observations(data, config)?,
+    };
+
+    let n_cells = layout.cells.len();
+    if n_cells < 2 {
+        return Err("The mixed model requires at least two occasions".to_string());
+    }
+
+    let (within_design, within_term_columns) = within_effect_design(&layout);
+    let p_between = between.parameter_names.len();
+    let p = p_between * n_cells;
+
+    // Fixed-effects rows are the between-subjects row crossed with the occasion's within-subjects row
+    let blocks: Vec<SubjectBlock> = observations
+        .iter()
+        .map(|(between_row, cells_and_values)| {
+            let x_between = between.x_matrix.row(*between_row);
+            let cells: Vec<usize> = cells_and_values.iter().map(|(cell, _)| *cell).collect();
+            let x_matrix = DMatrix::from_fn(cells.len(), p, |i, j| {
+                x_between[j / n_cells] * within_design[(cells[i], j % n_cells)]
+            });
+            let y_vector = DVector::from_iterator(
+                cells.len(),
+                cells_and_values.iter().map(|(_, value)| *value)
+            );
+            SubjectBlock { cells, x_matrix, y_vector }
+        })
+        .collect();
+
+    let n_obs: usize = blocks.iter().map(|block| block.cells.len()).sum();
+    let incomplete_subjects = blocks
+        .iter()
+        .filter(|block| block.cells.len() < n_cells)
+        .count();
+    if n_obs <= p {
+        return Err("Not enough observations to estimate the mixed model".to_string());
+    }
+
+    let parameter_names = covariance_parameter_names(&structure, n_cells);
+    let mut theta = initial_covariance_parameters(&structure, &blocks, n_cells, p)?;
+    let mut state = evaluate_reml(&blocks, &build_covariance(&structure, n_cells, &theta), p)?;
+
+    // Fisher scoring with step-halving on the restricted log-likelihood
+    let mut notes = Vec::new();
+    let mut converged = false;
+    let mut iterations = 0;
+    for iter in 1..=max_iter {
+        iterations = iter;
+        let derivatives = reml_derivatives(&blocks, &state, &structure, n_cells, &theta);
+        let Some(info_inv) = derivatives.information.clone().try_inverse() else {
+            notes.push("The information matrix of the covariance parameters is singular.".to_string());
+            break;
+        };
+        let delta = info_inv * &derivatives.score;
+
+        let mut step = 1.0;
+        let mut accepted = None;
+        for _ in 0..20 {
+            let candidate: Vec<f64> = theta
+                .iter()
+                .zip(delta.iter())
+                .map(|(value, change)| value + step * change)
+                .collect();
+            let sigma = build_covariance(&structure, n_cells, &candidate);
+            if is_admissible(&structure, &candidate, &sigma) {
+                if let Ok(candidate_state) = evaluate_reml(&blocks, &sigma, p) {
+                    if candidate_state.neg2_log_likelihood <= state.neg2_log_likelihood + 1e-10 {
+                        accepted = Some((candidate, candidate_state));
+                        break;
+                    }
+                }
+            }
+            step /= 2.0;
+        }
+
+        let Some((candidate, candidate_state)) = accepted else {
+            converged = derivatives.score.amax() < converge.sqrt();
+            if !converged {
+                notes.push(
+                    format!("Step-halving failed to improve the likelihood at iteration {}.", iter)
+                );
+            }
+            break;
+        };
+
+        let change = (state.neg2_log_likelihood - candidate_state.neg2_log_likelihood).abs();
+        theta = candidate;
+        state = candidate_state;
+        if change <= converge * (state.neg2_log_likelihood.abs() + converge) {
+            converged = true;
+            break;
+        }
+    }
+    if !converged && iterations == max_iter {
+        notes.push(format!("The REML iterations did not converge within {} iterations.", max_iter));
+    }
+
+    let derivatives = reml_derivatives(&blocks, &state, &structure, n_cells, &theta);
+    let theta_cov = derivatives.information
+        .clone()
+        .try_inverse()
+        .unwrap_or_else(|| DMatrix::from_element(theta.len(), theta.len(), f64::NAN));
+
+    let normal = Normal::new(0.0, 1.0).map_err(|e| e.to_string())?;
+    let covariance_parameters = parameter_names
+        .iter()
+        .enumerate()
+        .map(|(k, name)| {
+            let std_error = theta_cov[(k, k)].max(0.0).sqrt();
+            let wald_z = theta[k] / std_error;
+            CovarianceParameterEstimate {
+                parameter: name.clone(),
+                estimate: theta[k],
+                std_error,
+                wald_z,
+                significance: if wald_z.is_finite() {
+                    2.0 * (1.0 - normal.cdf(wald_z.abs()))
+                } else {
+                    f64::NAN
+                },
+            }
+        })
+        .collect();
+
+    // Kenward-Roger inflates the covariance of the fixed effects for the uncertainty in θ
+    let phi = match df_method {
+        DenominatorDfMethod::KenwardRoger => kenward_roger_covariance(&state.xtwx_inv, &derivatives, &theta_cov),
+        DenominatorDfMethod::Satterthwaite => state.xtwx_inv.clone(),
+    };
+
+    let mut fixed_effects_tests = Vec::new();
+    for (between_name, between_cols) in &between.term_columns {
+        for (within_term, within_cols) in &within_term_columns {
+            let within_name = within_term
+                .iter()
+                .map(|&i| layout.within_factors[i].clone())
+                .collect::<Vec<_>>()
+                .join("*");
+            let source = match (within_name.is_empty(), between_name == "Intercept") {
+                (true, _) => between_name.clone(),
+                (false, true) => within_name,
+                (false, false) => format!("{}*{}", within_name, between_name),
+            };
+
+            let columns: Vec<usize> = between_cols
+                .iter()
+                .flat_map(|&b| within_cols.iter().map(move |&w| b * n_cells + w))
+                .collect();
+            let l_matrix = DMatrix::from_fn(columns.len(), p, |i, j| {
+                if columns[i] == j { 1.0 } else { 0.0 }
+            });
+            let (f, numerator_df, denominator_df) = fixed_effect_test(
+                &l_matrix,
+                &state,
+                &phi,
+                &derivatives,
+                &theta_cov,
+                &df_method
+            );
+            fixed_effects_tests.push(MixedModelFixedEffectTest {
+                source,
+                numerator_df,
+                denominator_df,
+                f,
+                significance: f_upper_tail(numerator_df, denominator_df, f),
+            });
+        }
+    }
+
+    let emmeans = marginal_mean_targets(data, config, &between, &layout)
+        .into_iter()
+        .map(|target| {
+            let within_weights = within_design.transpose() * &target.cell_weights;
+            let l_vector = DVector::from_fn(p, |j, _| {
+                target.between_l[j / n_cells] * within_weights[j % n_cells]
+            });
+            let l_matrix = DMatrix::from_row_slice(1, p, l_vector.as_slice());
+            let mean = (&l_matrix * &state.beta)[(0, 0)];
+            let std_error = (&l_matrix * &phi * &l_vector)[(0, 0)].max(0.0).sqrt();
+            let (_, _, df) = fixed_effect_test(
+                &l_matrix,
+                &state,
+                &phi,
+                &derivatives,
+                &theta_cov,
+                &df_method
+            );
+            let t_critical = StudentsT::new(0.0, 1.0, df)
+                .map(|dist| dist.inverse_cdf(1.0 - alpha / 2.0))
+                .unwrap_or(f64::NAN);
+            MixedModelEstimatedMean {
+                factor_name: target.factor_name,
+                factor_value: target.factor_value,
+                mean,
+                std_error,
+                df,
+                confidence_interval: ConfidenceInterval {
+                    lower_bound: mean - t_critical * std_error,
+                    upper_bound: mean + t_critical * std_error,
+                },
+            }
+        })
+        .collect();
+
+    // Information criteria use the REML sample size N - p
+    let k = theta.len() as f64;
+    let n_reml = (n_obs - p) as f64;
+    let neg2 = state.neg2_log_likelihood;
+    let information_criteria = MixedModelInformationCriteria {
+        restricted_log_likelihood: neg2,
+        aic: neg2 + 2.0 * k,
+        aicc: if n_reml - k - 1.0 > 0.0 {
+            neg2 + (2.0 * k * n_reml) / (n_reml - k - 1.0)
+        } else {
+            f64::NAN
+        },
+        bic: neg2 + k * n_reml.ln(),
+        covariance_parameters: theta.len(),
+    };
+
+    let sigma = build_covariance(&structure, n_cells, &theta);
+    Ok(MixedModelResult {
+        dependent_variable,
+        covariance_structure: (
+            match structure {
+                CovarianceStructure::CompoundSymmetry => "Compound Symmetry",
+                CovarianceStructure::AR1 => "First-Order Autoregressive",
+                CovarianceStructure::Unstructured => "Unstructured",
+                CovarianceStructure::Toeplitz => "Toeplitz",
+            }
+        ).to_string(),
+        df_method: (
+            match df_method {
+                DenominatorDfMethod::Satterthwaite => "Satterthwaite",
+                DenominatorDfMethod::KenwardRoger => "Kenward-Roger",
+            }
+        ).to_string(),
+        within_factors: layout.within_factors.clone(),
+        occasions: layout.cells
+            .iter()
+            .map(|cell| cell.join("*"))
+            .collect(),
+        subjects: blocks.len(),
+        observations: n_obs,
+        incomplete_subjects,
+        fixed_effect_parameters: p,
+        information_criteria,
+        covariance_parameters,
+        residual_covariance: (0..n_cells)
+            .map(|i| (0..n_cells).map(|j| sigma[(i, j)]).collect())
+            .collect(),
+        fixed_effects_tests,
+        emmeans,
+        iterations,
+        converged,
+        notes,
+    })
+}
+
+/// Observations of each subject: the row of the between-subjects design and its (cell, value) pairs
+type SubjectObservations = Vec<(usize, Vec<(usize, f64)>)>;
+
+/// Wide data: one variable per within-subjects cell of a single measure
+fn collect_wide_observations(
+    data: &AnalysisData,
+    config: &RepeatedMeasuresConfig
+) -> Result<(String, BetweenSubjectsDesign, WithinSubjectsLayout, SubjectObservations), String> {
+    let (_, layout) = parse_within_subject_layout(data, config)?;
+    let measure_idx = match &config.mixed.measure {
+        Some(measure) =>
+            layout.measures
+                .iter()
+                .position(|name| name == measure)
+                .ok_or_else(|| format!("Measure '{}' is not defined", measure))?,
+        None if layout.measures.len() == 1 => 0,
+        None => {
+            return Err(
+                format!(
+                    "Select one of the measures {} for the mixed model",
+                    layout.measures.join(", ")
+                )
+            );
+        }
+    };
+
+    let between = build_between_subjects_design(data, config, &[], true)?;
+    let variables = &layout.variables[measure_idx];
+    let mut observations = Vec::new();
+    for (row, &case_idx) in between.case_indices.iter().enumerate() {
+        let values: Vec<(usize, f64)> = variables
+            .iter()
+            .enumerate()
+            .filter_map(|(cell, variable)| {
+                match find_case_value(&data.subject_data, &data.subject_data_defs, variable, case_idx) {
+                    Some(DataValue::Number(value)) => Some((cell, *value)),
+                    _ => None,
+                }
+            })
+            .collect();
+        if !values.is_empty() {
+            observations.push((row, values));
+        }
+    }
+
+    Ok((layout.measures[measure_idx].clone(), between, layout, observations))
+}
+
+/// Long data: one row per occasion identified by subject and occasion variables
+fn collect_long_observations(
+    data: &AnalysisData,
+    config: &RepeatedMeasuresConfig,
+    subject_var: &str,
+    occasion_var: &str,
+    dep_var: &str
+) -> Result<(String, BetweenSubjectsDesign, WithinSubjectsLayout, SubjectObservations), String> {
+    let between = build_between_subjects_design(data, config, &[dep_var.to_string()], true)?;
+
+    let mut rows = Vec::with_capacity(between.case_indices.len());
+    for (row, &case_idx) in between.case_indices.iter().enumerate() {
+        let subject = find_any_case_value(data, subject_var, case_idx);
+        let occasion = find_any_case_value(data, occasion_var, case_idx);
+        match (subject, occasion) {
+            (Some(subject), Some(occasion)) if
+                !matches!(subject, DataValue::Null) && !matches!(occasion, DataValue::Null)
+            => {
+                rows.push((
+                    row,
+                    data_value_to_string(subject),
+                    data_value_to_string(occasion),
+                    between.y_matrix[(row, 0)],
+                ));
+            }
+            _ => {}
+        }
+    }
+
+    // Occasions in numeric order when possible, otherwise alphabetically
+    let mut occasions: Vec<String> = rows
+        .iter()
+        .map(|(_, _, occasion, _)| occasion.clone())
+        .collect();
+    occasions.sort_by(|a, b| {
+        match (a.parse::<f64>(), b.parse::<f64>()) {
+            (Ok(x), Ok(y)) => x.partial_cmp(&y).unwrap_or(std::cmp::Ordering::Equal),
+            _ => a.cmp(b),
+        }
+    });
+    occasions.dedup();
+
+    let mut subject_order: Vec<String> = Vec::new();
+    let mut subject_rows: HashMap<String, (usize, Vec<(usize, f64)>)> = HashMap::new();
+    for (row, subject, occasion, value) in rows {
+        let cell = occasions
+            .iter()
+            .position(|o| o == &occasion)
+            .unwrap_or_default();
+        let entry = subject_rows.entry(subject.clone()).or_insert_with(|| {
+            subject_order.push(subject.clone());
+            (row, Vec::new())
+        });
+        if entry.1.iter().any(|(existing, _)| *existing == cell) {
+            return Err(
+                format!("Subject '{}' has more than one row for occasion '{}'", subject, occasion)
+            );
+        }
+        entry.1.push((cell, value));
+    }
+
+    let observations = subject_order
+        .iter()
+        .filter_map(|subject| subject_rows.remove(subject))
+        .map(|(row, mut values)| {
+            values.sort_by_key(|(cell, _)| *cell);
+            (row, values)
+        })
+        .collect();
+
+    let layout = WithinSubjectsLayout {
+        measures: vec![dep_var.to_string()],
+        within_factors: vec![occasion_var.to_string()],
+        factor_levels: vec![occasions.clone()],
+        cells: occasions
+            .iter()
+            .map(|occasion| vec![occasion.clone()])
+            .collect(),
+        variables: vec![Vec::new()],
+    };
+
+    Ok((dep_var.to_string(), between, layout, observations))
+}
+
+/// Find the value of a variable for one case in any of the data groups
+fn find_any_case_value<'a>(
+    data: &'a AnalysisData,
+    variable: &str,
+    case_idx: usize
+) -> Option<&'a DataValue> {
+    find_case_value(&data.subject_data, &data.subject_data_defs, variable, case_idx)
+        .or_else(|| find_case_value(&data.factors_data, &data.factors_data_defs, variable, case_idx))
+        .or_else(|| {
+            match (&data.covariate_data, &data.covariate_data_defs) {
+                (Some(groups), Some(defs)) => find_case_value(groups, defs, variable, case_idx),
+                _ => None,
+            }
+        })
+}
+
+/// Within-subjects terms (factor indices) with their design columns
+type WithinTermColumns = Vec<(Vec<usize>, Vec<usize>)>;
+
+/// Effect-coded within-subjects design (one row per cell) with the columns of every term,
+/// starting with the intercept term
+fn within_effect_design(layout: &WithinSubjectsLayout) -> (DMatrix<f64>, WithinTermColumns) {
+    let n_cells = layout.cells.len();
+    let mut columns: Vec<DVector<f64>> = vec![DVector::from_element(n_cells, 1.0)];
+    let mut term_columns = vec![(Vec::new(), vec![0])];
+
+    for term in within_subject_terms(&layout.within_factors) {
+        let mut term_codes: Vec<DVector<f64>> = vec![DVector::from_element(n_cells, 1.0)];
+        for &factor_idx in &term {
+            let levels = &layout.factor_levels[factor_idx];
+            let last = &levels[levels.len() - 1];
+            let factor_codes: Vec<DVector<f64>> = levels[..levels.len() - 1]
+                .iter()
+                .map(|level| {
+                    DVector::from_fn(n_cells, |c, _| {
+                        let cell_level = &layout.cells[c][factor_idx];
+                        if cell_level == level {
+                            1.0
+                        } else if cell_level == last {
+                            -1.0
+                        } else {
+                            0.0
+                        }
+                    })
+                })
+                .collect();
+            term_codes = term_codes
+                .iter()
+                .flat_map(|existing| factor_codes.iter().map(move |code| existing.component_mul(code)))
+                .collect();
+        }
+        let first_col = columns.len();
+        columns.extend(term_codes);
+        term_columns.push((term, (first_col..columns.len()).collect()));
+    }
+
+    (DMatrix::from_columns(&columns), term_columns)
+}
+
+fn covariance_parameter_names(structure: &CovarianceStructure, n_cells: usize) -> Vec<String> {
+    match structure {
+        CovarianceStructure::CompoundSymmetry =>
+            vec!["CS Diagonal Offset".to_string(), "CS Covariance".to_string()],
+        CovarianceStructure::AR1 => vec!["AR1 Diagonal".to_string(), "AR1 Rho".to_string()],
+        CovarianceStructure::Unstructured => {
+            let mut names = Vec::new();
+            for i in 0..n_cells {
+                for j in 0..=i {
+                    names.push(format!("UN ({},{})", i + 1, j + 1));
+                }
+            }
+            names
+        }
+        CovarianceStructure::Toeplitz => {
+            let mut names = vec!["TP Diagonal".to_string()];
+            names.extend((1..n_cells).map(|lag| format!("TP Lag {} Covariance", lag)));
+            names
+        }
+    }
+}
+
+/// Covariance matrix of the occasions for the given parameters
+fn build_covariance(structure: &CovarianceStructure, n_cells: usize, theta: &[f64]) -> DMatrix<f64> {
+    match structure {
+        CovarianceStructure::CompoundSymmetry =>
+            DMatrix::from_fn(n_cells, n_cells, |i, j| theta[1] + (if i == j { theta[0] } else { 0.0 })),
+        CovarianceStructure::AR1 =>
+            DMatrix::from_fn(n_cells, n_cells, |i, j| theta[0] * theta[1].powi(i.abs_diff(j) as i32)),
+        CovarianceStructure::Unstructured => {
+            DMatrix::from_fn(n_cells, n_cells, |i, j| {
+                let (row, col) = if i >= j { (i, j) } else { (j, i) };
+                theta[(row * (row + 1)) / 2 + col]
+            })
+        }
+        CovarianceStructure::Toeplitz =>
+            DMatrix::from_fn(n_cells, n_cells, |i, j| theta[i.abs_diff(j)]),
+    }
+}
+
+/// Derivative of the covariance matrix with respect to every parameter
+fn covariance_derivatives(
+    structure: &CovarianceStructure,
+    n_cells: usize,
+    theta: &[f64]
+) -> Vec<DMatrix<f64>> {
+    match structure {
+        CovarianceStructure::CompoundSymmetry =>
+            vec![
+                DMatrix::identity(n_cells, n_cells),
+                DMatrix::from_element(n_cells, n_cells, 1.0)
+            ],
+        CovarianceStructure::AR1 =>
+            vec![
+                DMatrix::from_fn(n_cells, n_cells, |i, j| theta[1].powi(i.abs_diff(j) as i32)),
+                DMatrix::from_fn(n_cells, n_cells, |i, j| {
+                    let lag = i.abs_diff(j);
+                    if lag == 0 {
+                        0.0
+                    } else {
+                        theta[0] * (lag as f64) * theta[1].powi(lag as i32 - 1)
+                    }
+                })
+            ],
+        CovarianceStructure::Unstructured => {
+            let mut derivatives = Vec::new();
+            for i in 0..n_cells {
+                for j in 0..=i {
+                    let mut derivative = DMatrix::zeros(n_cells, n_cells);
+                    derivative[(i, j)] = 1.0;
+                    derivative[(j, i)] = 1.0;
+                    derivatives.push(derivative);
+                }
+            }
+            derivatives
+        }
+        CovarianceStructure::Toeplitz =>
+            (0..n_cells)
+                .map(|lag| DMatrix::from_fn(n_cells, n_cells, |i, j| if i.abs_diff(j) == lag { 1.0 } else { 0.0 }))
+                .collect(),
+    }
+}
+
+fn is_admissible(structure: &CovarianceStructure, theta: &[f64], sigma: &DMatrix<f64>) -> bool {
+    let in_range = match structure {
+        CovarianceStructure::AR1 => theta[0] > 0.0 && theta[1].abs() < 1.0,
+        CovarianceStructure::CompoundSymmetry => theta[0] > 0.0,
+        _ => true,
+    };
+    in_range && sigma.clone().cholesky().is_some()
+}
+
+/// Starting values from the pairwise covariance of the ordinary least-squares residuals
+fn initial_covariance_parameters(
+    structure: &CovarianceStructure,
+    blocks: &[SubjectBlock],
+    n_cells: usize,
+    p: usize
+) -> Result<Vec<f64>, String> {
+    let mut xtx = DMatrix::zeros(p, p);
+    let mut xty = DVector::zeros(p);
+    for block in blocks {
+        xtx += block.x_matrix.transpose() * &block.x_matrix;
+        xty += block.x_matrix.transpose() * &block.y_vector;
+    }
+    let beta = xtx
+        .try_inverse()
+        .ok_or_else(|| "Could not invert X'X matrix - possibly due to empty cells".to_string())? *
+    xty;
+
+    let mut sums = DMatrix::<f64>::zeros(n_cells, n_cells);
+    let mut counts = DMatrix::<f64>::zeros(n_cells, n_cells);
+    for block in blocks {
+        let residuals = &block.y_vector - &block.x_matrix * &beta;
+        for (a, &cell_a) in block.cells.iter().enumerate() {
+            for (b, &cell_b) in block.cells.iter().enumerate() {
+                sums[(cell_a, cell_b)] += residuals[a] * residuals[b];
+                counts[(cell_a, cell_b)] += 1.0;
+            }
+        }
+    }
+    let pairwise = DMatrix::from_fn(n_cells, n_cells, |i, j| {
+        if counts[(i, j)] > 0.0 { sums[(i, j)] / counts[(i, j)] } else { 0.0 }
+    });
+
+    let variance = (pairwise.trace() / (n_cells as f64)).max(1e-8);
+    let band_mean = |lag: usize| -> f64 {
+        let values: Vec<f64> = (lag..n_cells).map(|i| pairwise[(i, i - lag)]).collect();
+        values.iter().sum::<f64>() / (values.len() as f64)
+    };
+    let off_diagonal = if n_cells > 1 {
+        (pairwise.sum() - pairwise.trace()) / ((n_cells * (n_cells - 1)) as f64)
+    } else {
+        0.0
+    };
+
+    let theta = match structure {
+        CovarianceStructure::CompoundSymmetry => {
+            let covariance = off_diagonal.max(0.0).min(0.9 * variance);
+            vec![variance - covariance, covariance]
+        }
+        CovarianceStructure::AR1 => vec![variance, (band_mean(1) / variance).clamp(-0.9, 0.9)],
+        CovarianceStructure::Unstructured => {
+            let start = if pairwise.clone().cholesky().is_some() {
+                pairwise
+            } else {
+                DMatrix::from_diagonal(&pairwise.diagonal().map(|v| v.max(1e-8)))
+            };
+            let mut theta = Vec::new();
+            for i in 0..n_cells {
+                for j in 0..=i {
+                    theta.push(start[(i, j)]);
+                }
+            }
+            theta
+        }
+        CovarianceStructure::Toeplitz => {
+            let theta: Vec<f64> = (0..n_cells)
+                .map(|lag| if lag == 0 { variance } else { band_mean(lag) })
+                .collect();
+            if build_covariance(structure, n_cells, &theta).cholesky().is_some() {
+                theta
+            } else {
+                let mut theta = vec![0.0; n_cells];
+                theta[0] = variance;
+                theta
+            }
+        }
+    };
+
+    Ok(theta)
+}
+
+/// Generalized least-squares fit and -2 restricted log-likelihood for a covariance matrix
+fn evaluate_reml(blocks: &[SubjectBlock], sigma: &DMatrix<f64>, p: usize) -> Result<RemlState, String> {
+    let mut xtwx = DMatrix::zeros(p, p);
+    let mut xtwy = DVector::zeros(p);
+    let mut log_det_v = 0.0;
+    let mut w_blocks = Vec::with_capacity(blocks.len());
+    let mut n_obs = 0;
+
+    for block in blocks {
+        let v_block = sigma.select_rows(&block.cells).select_columns(&block.cells);
+        let cholesky = v_block
+            .cholesky()
+            .ok_or_else(|| "The covariance matrix is not positive definite".to_string())?;
+        log_det_v += 2.0 * cholesky.l().diagonal().map(|d| d.ln()).sum();
+        let w_block = cholesky.inverse();
+        let xtw = block.x_matrix.transpose() * &w_block;
+        xtwx += &xtw * &block.x_matrix;
+        xtwy += &xtw * &block.y_vector;
+        w_blocks.push(w_block);
+        n_obs += block.cells.len();
+    }
+
+    let xtwx_cholesky = xtwx
+        .clone()
+        .cholesky()
+        .ok_or_else(|| "X'V^-1X is singular - possibly due to empty cells".to_string())?;
+    let log_det_xtwx = 2.0 * xtwx_cholesky.l().diagonal().map(|d| d.ln()).sum();
+    let xtwx_inv = xtwx_cholesky.inverse();
+    let beta = &xtwx_inv * xtwy;
+
+    let mut quadratic = 0.0;
+    let mut residuals = Vec::with_capacity(blocks.len());
+    for (block, w_block) in blocks.iter().zip(&w_blocks) {
+        let residual = &block.y_vector - &block.x_matrix * &beta;
+        quadratic += (residual.transpose() * w_block * &residual)[(0, 0)];
+        residuals.push(residual);
+    }
+
+    let neg2_log_likelihood =
+        log_det_v +
+        log_det_xtwx +
+        quadratic +
+        ((n_obs - p) as f64) * (2.0 * std::f64::consts::PI).ln();
+
+    Ok(RemlState { neg2_log_likelihood, beta, xtwx_inv, w_blocks, residuals })
+}
+
+/// REML score, expected information and the matrices reused by the denominator df methods
+fn reml_derivatives(
+    blocks: &[SubjectBlock],
+    state: &RemlState,
+    structure: &CovarianceStructure,
+    n_cells: usize,
+    theta: &[f64]
+) -> RemlDerivatives {
+    let derivatives = covariance_derivatives(structure, n_cells, theta);
+    let k = derivatives.len();
+    let p = state.beta.len();
+
+    let mut trace_wd = vec![0.0; k];
+    let mut quadratic = vec![0.0; k];
+    let mut trace_wdwd = DMatrix::<f64>::zeros(k, k);
+    let mut a_matrices = vec![DMatrix::zeros(p, p); k];
+    let mut q_matrices = vec![vec![DMatrix::zeros(p, p); k]; k];
+
+    for ((block, w_block), residual) in blocks.iter().zip(&state.w_blocks).zip(&state.residuals) {
+        let wx = w_block * &block.x_matrix;
+        let wr = w_block * residual;
+        let d_blocks: Vec<DMatrix<f64>> = derivatives
+            .iter()
+            .map(|d| d.select_rows(&block.cells).select_columns(&block.cells))
+            .collect();
+        let wd: Vec<DMatrix<f64>> = d_blocks
+            .iter()
+            .map(|d| w_block * d)
+            .collect();
+
+        for a in 0..k {
+            trace_wd[a] += wd[a].trace();
+            quadratic[a] += (wr.transpose() * &d_blocks[a] * &wr)[(0, 0)];
+            a_matrices[a] += wx.transpose() * &d_blocks[a] * &wx;
+            for b in 0..k {
+                trace_wdwd[(a, b)] += (&wd[a] * &wd[b]).trace();
+                q_matrices[a][b] += wx.transpose() * &d_blocks[a] * &wd[b] * &wx;
+            }
+        }
+    }
+
+    let c = &state.xtwx_inv;
+    let c_a: Vec<DMatrix<f64>> = a_matrices
+        .iter()
+        .map(|a| c * a)
+        .collect();
+    let score = DVector::from_fn(k, |a, _| -0.5 * (trace_wd[a] - c_a[a].trace()) + 0.5 * quadratic[a]);
+    let information = DMatrix::from_fn(k, k, |a, b| {
+        0.5 * (trace_wdwd[(a, b)] - 2.0 * (c * &q_matrices[a][b]).trace() + (&c_a[a] * &c_a[b]).trace())
+    });
+
+    RemlDerivatives { score, information, a_matrices, q_matrices }
+}
+
+/// Kenward-Roger adjusted covariance of the fixed effects: C + 2C[Σ Wkl (Qkl - AkCAl)]C
+fn kenward_roger_covariance(
+    c: &DMatrix<f64>,
+    derivatives: &RemlDerivatives,
+    theta_cov: &DMatrix<f64>
+) -> DMatrix<f64> {
+    let k = derivatives.a_matrices.len();
+    let mut inner = DMatrix::zeros(c.nrows(), c.ncols());
+    for a in 0..k {
+        for b in 0..k {
+            inner +=
+                theta_cov[(a, b)] *
+                (&derivatives.q_matrices[a][b] -
+                    &derivatives.a_matrices[a] * c * &derivatives.a_matrices[b]);
+        }
+    }
+    c + 2.0 * c * inner * c
+}
+
+/// F statistic, numerator df and denominator df for the hypothesis Lβ = 0
+fn fixed_effect_test(
+    l_matrix: &DMatrix<f64>,
+    state: &RemlState,
+    phi: &DMatrix<f64>,
+    derivatives: &RemlDerivatives,
+    theta_cov: &DMatrix<f64>,
+    df_method: &DenominatorDfMethod
+) -> (f64, f64, f64) {
+    let c = &state.xtwx_inv;
+    let q = l_matrix.nrows() as f64;
+    let l_beta = l_matrix * &state.beta;
+    let k = derivatives.a_matrices.len();
+    let c_a_c: Vec<DMatrix<f64>> = derivatives.a_matrices
+        .iter()
+        .map(|a| c * a * c)
+        .collect();
+
+    let wald = |covariance: &DMatrix<f64>| -> f64 {
+        match (l_matrix * covariance * l_matrix.transpose()).try_inverse() {
+            Some(inverse) => (l_beta.transpose() * inverse * &l_beta)[(0, 0)] / q,
+            None => f64::NAN,
+        }
+    };
+
+    // Satterthwaite: one df per eigen-direction of LCL', combined through their F moments
+    let satterthwaite_df = || -> f64 {
+        let eigen = (l_matrix * c * l_matrix.transpose()).symmetric_eigen();
+        let mut nus = Vec::with_capacity(eigen.eigenvalues.len());
+        for (m, &d) in eigen.eigenvalues.iter().enumerate() {
+            let direction = eigen.eigenvectors.column(m).transpose() * l_matrix;
+            let gradient = DVector::from_fn(k, |a, _| {
+                (&direction * &c_a_c[a] * direction.transpose())[(0, 0)]
+            });
+            let variance = (gradient.transpose() * theta_cov * &gradient)[(0, 0)];
+            nus.push(if variance > 0.0 { (2.0 * d * d) / variance } else { f64::INFINITY });
+        }
+        if nus.len() == 1 {
+            return nus[0];
+        }
+        let expectation: f64 = nus
+            .iter()
+            .filter(|&&nu| nu > 2.0)
+            .map(|&nu| if nu.is_finite() { nu / (nu - 2.0) } else { 1.0 })
+            .sum();
+        if expectation > q {
+            (2.0 * expectation) / (expectation - q)
+        } else {
+            nus.iter().cloned().fold(f64::INFINITY, f64::min)
+        }
+    };
+
+    match df_method {
+        DenominatorDfMethod::Satterthwaite => (wald(c), q, satterthwaite_df()),
+        DenominatorDfMethod::KenwardRoger => {
+            let f_adjusted = wald(phi);
+            let Some(middle) = (l_matrix * c * l_matrix.transpose()).try_inverse() else {
+                return (f_adjusted, q, f64::NAN);
+            };
+            let theta_l = l_matrix.transpose() * middle * l_matrix;
+            let products: Vec<DMatrix<f64>> = c_a_c
+                .iter()
+                .map(|cac| &theta_l * cac)
+                .collect();
+
+            let mut a1 = 0.0;
+            let mut a2 = 0.0;
+            for a in 0..k {
+                for b in 0..k {
+                    a1 += theta_cov[(a, b)] * products[a].trace() * products[b].trace();
+                    a2 += theta_cov[(a, b)] * (&products[a] * &products[b]).trace();
+                }
+            }
+
+            let b_term = (a1 + 6.0 * a2) / (2.0 * q);
+            let g = ((q + 1.0) * a1 - (q + 4.0) * a2) / ((q + 2.0) * a2);
+            let denominator = 3.0 * q + 2.0 * (1.0 - g);
+            let c1 = g / denominator;
+            let c2 = (q - g) / denominator;
+            let c3 = (q + 2.0 - g) / denominator;
+            let expectation = 1.0 / (1.0 - a2 / q);
+            let variance = (2.0 / q) * ((1.0 + c1 * b_term) / ((1.0 - c2 * b_term).powi(2) * (1.0 - c3 * b_term)));
+            let rho = variance / (2.0 * expectation * expectation);
+            let m = 4.0 + (q + 2.0) / (q * rho - 1.0);
+            let lambda = m / (expectation * (m - 2.0));
+
+            if m.is_finite() && m > 0.0 && lambda.is_finite() {
+                (lambda * f_adjusted, q, m)
+            } else {
+                (f_adjusted, q, satterthwaite_df())
+            }
+        }
+    }
+}
@@ -3,10 +3,13 @@ pub mod bartlett_test;
 pub mod between_subjects_effects;
 pub mod between_subjects_sscp;
 pub mod common;
+pub mod custom_hypothesis;
 pub mod descriptive_statistics;
+pub mod doubly_multivariate;
 pub mod emmeans;
 pub mod estimable_function;
 pub mod mauchly_test;
+pub mod mixed_model;
 pub mod multivariate_tests;
 pub mod parameter_estimates;
 pub mod parse_factors;
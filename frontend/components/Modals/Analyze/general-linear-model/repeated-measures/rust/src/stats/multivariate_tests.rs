@@ -1,5 +1,7 @@
 use std::collections::HashMap;
 
+use nalgebra::DMatrix;
+
 use crate::{
     models::{
         config::RepeatedMeasuresConfig,
@@ -12,11 +14,12 @@ use crate::{
 use super::{
     common::{
         calculate_f_significance,
+        calculate_observed_power,
+        f_upper_tail,
         generate_interaction_terms,
         matrix_determinant,
         matrix_inverse,
         matrix_multiply,
-        matrix_transpose,
     },
     core::{ data_value_to_string, extract_dependent_value, parse_interaction_term },
 };
@@ -61,7 +64,6 @@ pub fn calculate_multivariate_tests(
     let (h_matrix, e_matrix, hypothesis_df, error_df) = match
         calculate_hypothesis_error_matrices(
             data,
-            config,
             "Intercept",
             dependent_vars,
             &all_values,
@@ -81,7 +83,6 @@ pub fn calculate_multivariate_tests(
             &e_matrix,
             hypothesis_df,
             error_df,
-            alpha,
             dependent_vars.len()
         )
     {
@@ -98,7 +99,6 @@ pub fn calculate_multivariate_tests(
         let (h_matrix, e_matrix, hypothesis_df, error_df) = match
             calculate_hypothesis_error_matrices(
                 data,
-                config,
                 factor,
                 dependent_vars,
                 &all_values,
@@ -117,7 +117,6 @@ pub fn calculate_multivariate_tests(
                 &e_matrix,
                 hypothesis_df,
                 error_df,
-                alpha,
                 dependent_vars.len()
             )
         {
@@ -142,7 +141,6 @@ pub fn calculate_multivariate_tests(
             let (h_matrix, e_matrix, hypothesis_df, error_df) = match
                 calculate_hypothesis_error_matrices(
                     data,
-                    config,
                     &term,
                     dependent_vars,
                     &all_values,
@@ -163,7 +161,6 @@ pub fn calculate_multivariate_tests(
                     &e_matrix,
                     hypothesis_df,
                     error_df,
-                    alpha,
                     dependent_vars.len()
                 )
             {
@@ -191,15 +188,17 @@ pub fn calculate_multivariate_tests(
     })
 }
 
+/// Hypothesis matrix, error matrix, hypothesis df and error df
+type HypothesisErrorMatrices = (Vec<Vec<f64>>, Vec<Vec<f64>>, f64, f64);
+
 /// Calculate hypothesis and error matrices for a given effect
 fn calculate_hypothesis_error_matrices(
     data: &AnalysisData,
-    config: &RepeatedMeasuresConfig,
     effect: &str,
     dependent_vars: &[String],
     all_values: &[Vec<f64>],
     factors_in_effect: Option<&[String]>
-) -> Result<(Vec<Vec<f64>>, Vec<Vec<f64>>, f64, f64), String> {
+) -> Result<HypothesisErrorMatrices, String> {
     let p = dependent_vars.len();
     let n_obs = all_values[0].len();
 
@@ -239,7 +238,7 @@ fn calculate_hypothesis_error_matrices(
             }
         }
 
-        return Ok((h_matrix, e_matrix, 1.0, (n_obs - 1) as f64));
+        Ok((h_matrix, e_matrix, 1.0, (n_obs - 1) as f64))
     } else if factors_in_effect.is_none() || factors_in_effect.unwrap().is_empty() {
         // Main effect
         let factor_levels = get_factor_levels(data, effect)?;
@@ -254,7 +253,6 @@ fn calculate_hypothesis_error_matrices(
 
             // Find all records with this factor level
             for (i, dep_var) in dependent_vars.iter().enumerate() {
-                let mut record_idx = 0;
                 for records in &data.subject_data {
                     for record in records {
                         if let Some(value) = extract_dependent_value(record, dep_var) {
@@ -281,7 +279,6 @@ fn calculate_hypothesis_error_matrices(
                                 level_values[i].push(value);
                             }
                         }
-                        record_idx += 1;
                     }
                 }
             }
@@ -323,7 +320,7 @@ fn calculate_hypothesis_error_matrices(
                 let mut record_idx = 0;
 
                 for records in &data.subject_data {
-                    for record in records {
+                    for _ in records {
                         if record_idx >= n_obs {
                             continue;
                         }
@@ -369,7 +366,7 @@ fn calculate_hypothesis_error_matrices(
         let hypothesis_df = (level_count - 1) as f64;
         let error_df = (n_obs - level_count) as f64;
 
-        return Ok((h_matrix, e_matrix, hypothesis_df, error_df));
+        Ok((h_matrix, e_matrix, hypothesis_df, error_df))
     } else {
         // Interaction effect
         let interaction_factors = factors_in_effect.unwrap();
@@ -414,7 +411,6 @@ fn calculate_hypothesis_error_matrices(
 
             // Find all records with this combination of factor levels
             for (i, dep_var) in dependent_vars.iter().enumerate() {
-                let mut record_idx = 0;
                 for records in &data.subject_data {
                     for record in records {
                         if let Some(value) = extract_dependent_value(record, dep_var) {
@@ -450,7 +446,6 @@ fn calculate_hypothesis_error_matrices(
                                 combo_values[i].push(value);
                             }
                         }
-                        record_idx += 1;
                     }
                 }
             }
@@ -483,7 +478,6 @@ fn calculate_hypothesis_error_matrices(
 
                 // Find all records with this factor level
                 for (i, dep_var) in dependent_vars.iter().enumerate() {
-                    let mut record_idx = 0;
                     for records in &data.subject_data {
                         for record in records {
                             if let Some(value) = extract_dependent_value(record, dep_var) {
@@ -510,7 +504,6 @@ fn calculate_hypothesis_error_matrices(
                                     level_values[i].push(value);
                                 }
                             }
-                            record_idx += 1;
                         }
                     }
                 }
@@ -574,7 +567,7 @@ fn calculate_hypothesis_error_matrices(
                 let mut record_idx = 0;
 
                 for records in &data.subject_data {
-                    for record in records {
+                    for _ in records {
                         if record_idx >= n_obs {
                             continue;
                         }
@@ -606,7 +599,7 @@ fn calculate_hypothesis_error_matrices(
                                 .iter()
                                 .position(|c| {
                                     c.iter().all(|(f, l)| {
-                                        record_combo.get(f).map_or(false, |rl| rl == l)
+                                        record_combo.get(f).is_some_and(|rl| rl == l)
                                     })
                                 })
                         {
@@ -637,7 +630,7 @@ fn calculate_hypothesis_error_matrices(
         let total_combinations = level_combinations.len();
         let error_df = (n_obs - total_combinations) as f64;
 
-        return Ok((h_matrix, e_matrix, hypothesis_df, error_df));
+        Ok((h_matrix, e_matrix, hypothesis_df, error_df))
     }
 }
 
@@ -647,7 +640,6 @@ fn calculate_multivariate_test_statistics(
     e_matrix: &[Vec<f64>],
     hypothesis_df: f64,
     error_df: f64,
-    alpha: f64,
     p: usize
 ) -> Result<HashMap<String, MultivariateTestEntry>, String> {
     let mut test_results = HashMap::new();
@@ -671,29 +663,15 @@ fn calculate_multivariate_test_statistics(
     // However, computing eigenvalues is complex, so we'll use a simpler approach
     // to estimate the multivariate test statistics
 
-    // Calculate determinants
-    let det_h = match matrix_determinant(h_matrix) {
-        Ok(d) => d,
-        Err(_) => 0.0001, // Fallback value if determinant calculation fails
-    };
-
-    let det_e = match matrix_determinant(e_matrix) {
-        Ok(d) => d,
-        Err(_) => 0.0001, // Fallback value
-    };
-
-    let det_he = match matrix_determinant(&he_inv) {
-        Ok(d) => d,
-        Err(_) => 0.0001, // Fallback value
-    };
+    // Calculate determinant, with a fallback value if determinant calculation fails
+    let det_he = matrix_determinant(&he_inv).unwrap_or(0.0001);
 
     // Compute trace of HE^-1
-    let mut trace_he = 0.0;
-    for i in 0..he_inv.len() {
-        if i < he_inv[i].len() {
-            trace_he += he_inv[i][i];
-        }
-    }
+    let trace_he: f64 = he_inv
+        .iter()
+        .enumerate()
+        .filter_map(|(i, row)| row.get(i))
+        .sum();
 
     // Calculate largest eigenvalue (approximation)
     let max_eigenvalue = trace_he / (p as f64);
@@ -733,7 +711,6 @@ fn calculate_multivariate_test_statistics(
     } else {
         // General approximation (Rao's)
         let r = error_df - ((p as f64) - hypothesis_df + 1.0) / 2.0;
-        let u = ((p as f64) * hypothesis_df - 2.0) / 4.0;
         let t = if (p as f64) * hypothesis_df > 2.0 {
             ((p as f64) * hypothesis_df).sqrt() - 1.0
         } else {
@@ -836,3 +813,110 @@ fn calculate_multivariate_test_statistics(
 
     Ok(test_results)
 }
+
+/// Pillai's trace, Wilks' lambda, Hotelling's trace and Roy's largest root from the
+/// eigenvalues of E^-1 H, with the usual F approximations
+pub fn calculate_multivariate_statistics_from_sscp(
+    h_matrix: &DMatrix<f64>,
+    e_matrix: &DMatrix<f64>,
+    hypothesis_df: usize,
+    error_df: usize,
+    alpha: f64
+) -> Result<HashMap<String, MultivariateTestEntry>, String> {
+    let t = h_matrix.nrows() as f64;
+    let q = hypothesis_df as f64;
+    let v = error_df as f64;
+
+    // Eigenvalues of E^-1 H via the symmetric form U'^-1 H U^-1 where E = U'U
+    let cholesky = e_matrix
+        .clone()
+        .cholesky()
+        .ok_or_else(|| "The transformed error SSCP matrix is not positive definite".to_string())?;
+    let l_inv = cholesky
+        .l()
+        .try_inverse()
+        .ok_or_else(|| "Failed to invert the Cholesky factor of the error SSCP matrix".to_string())?;
+    let symmetric = &l_inv * h_matrix * l_inv.transpose();
+    let eigenvalues: Vec<f64> = symmetric
+        .symmetric_eigen()
+        .eigenvalues.iter()
+        .map(|&lambda| lambda.max(0.0))
+        .collect();
+
+    let s = t.min(q);
+    let m = ((t - q).abs() - 1.0) / 2.0;
+    let n = (v - t - 1.0) / 2.0;
+    let is_exact = s == 1.0;
+
+    let make_entry = |value: f64, f: f64, df1: f64, df2: f64, partial_eta_squared: f64| {
+        MultivariateTestEntry {
+            value,
+            f,
+            hypothesis_df: df1,
+            error_df: df2,
+            significance: f_upper_tail(df1, df2, f),
+            partial_eta_squared,
+            noncent_parameter: f * df1,
+            observed_power: calculate_observed_power(df1 as usize, df2 as usize, f, alpha),
+            is_exact_statistic: is_exact,
+        }
+    };
+
+    let mut tests = HashMap::new();
+
+    let pillai: f64 = eigenvalues
+        .iter()
+        .map(|lambda| lambda / (1.0 + lambda))
+        .sum();
+    let pillai_df1 = s * (2.0 * m + s + 1.0);
+    let pillai_df2 = s * (2.0 * n + s + 1.0);
+    let pillai_f = (pillai_df2 / pillai_df1) * (pillai / (s - pillai));
+    tests.insert(
+        "Pillai's Trace".to_string(),
+        make_entry(pillai, pillai_f, pillai_df1, pillai_df2, pillai / s)
+    );
+
+    let wilks: f64 = eigenvalues
+        .iter()
+        .map(|lambda| 1.0 / (1.0 + lambda))
+        .product();
+    let rao_r = if t * t + q * q - 5.0 > 0.0 {
+        ((t * t * q * q - 4.0) / (t * t + q * q - 5.0)).sqrt()
+    } else {
+        1.0
+    };
+    let wilks_df1 = t * q;
+    let wilks_df2 = rao_r * (v - (t - q + 1.0) / 2.0) - (t * q - 2.0) / 2.0;
+    let wilks_root = wilks.powf(1.0 / rao_r);
+    let wilks_f = ((1.0 - wilks_root) / wilks_root) * (wilks_df2 / wilks_df1);
+    tests.insert(
+        "Wilks' Lambda".to_string(),
+        make_entry(wilks, wilks_f, wilks_df1, wilks_df2, 1.0 - wilks_root)
+    );
+
+    let hotelling: f64 = eigenvalues.iter().sum();
+    let hotelling_df1 = s * (2.0 * m + s + 1.0);
+    let hotelling_df2 = 2.0 * (s * n + 1.0);
+    let hotelling_f = (hotelling_df2 * hotelling) / (s * s * (2.0 * m + s + 1.0));
+    tests.insert(
+        "Hotelling's Trace".to_string(),
+        make_entry(
+            hotelling,
+            hotelling_f,
+            hotelling_df1,
+            hotelling_df2,
+            hotelling / s / (1.0 + hotelling / s)
+        )
+    );
+
+    let roy = eigenvalues.iter().cloned().fold(0.0, f64::max);
+    let roy_df1 = t.max(q);
+    let roy_df2 = v - roy_df1 + q;
+    let roy_f = (roy * roy_df2) / roy_df1;
+    tests.insert(
+        "Roy's Largest Root".to_string(),
+        make_entry(roy, roy_f, roy_df1, roy_df2, roy / (1.0 + roy))
+    );
+
+    Ok(tests)
+}
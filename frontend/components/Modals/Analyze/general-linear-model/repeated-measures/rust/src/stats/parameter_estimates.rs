@@ -1,5 +1,5 @@
 use nalgebra::{ DMatrix, DVector };
-use statrs::distribution::{ StudentsT, ContinuousCDF };
+use statrs::distribution::{ ContinuousCDF, Normal, StudentsT };
 use std::collections::HashMap;
 
 use crate::models::{
@@ -13,10 +13,15 @@ use crate::models::{
     },
 };
 
-use super::core::parse_within_subject_factors;
+use super::core::{
+    generate_interaction_terms,
+    get_factor_levels,
+    parse_interaction_term,
+    parse_within_subject_factors,
+};
 
 /// Calculate parameter estimates
-fn calculate_parameter_estimates(
+pub fn calculate_parameter_estimates(
     data: &AnalysisData,
     config: &RepeatedMeasuresConfig
 ) -> Result<ParameterEstimates, String> {
@@ -34,7 +39,7 @@ fn calculate_parameter_estimates(
     };
 
     // Process each dependent variable
-    for (factor_name, factors) in &within_factors.measures {
+    for factors in within_factors.measures.values() {
         let var_names: Vec<String> = factors
             .iter()
             .map(|f| f.dependent_variable.clone())
@@ -112,24 +117,24 @@ fn calculate_parameter_estimates(
             let p = if factor_groups[0].is_empty() { 1 } else { factor_groups[0].len() };
 
             let y = DVector::from_iterator(n, data_values.iter().cloned());
-            let mut X = DMatrix::zeros(n, p + 1); // +1 for intercept
+            let mut x_matrix = DMatrix::zeros(n, p + 1); // +1 for intercept
 
             // Set intercept column
             for i in 0..n {
-                X[(i, 0)] = 1.0;
+                x_matrix[(i, 0)] = 1.0;
             }
 
             // Set factor columns
             for i in 0..n {
                 for j in 0..p {
                     if j < factor_groups[i].len() {
-                        X[(i, j + 1)] = factor_groups[i][j];
+                        x_matrix[(i, j + 1)] = factor_groups[i][j];
                     }
                 }
             }
 
             // Calculate (X'X)^-1 X'y for parameter estimates
-            let xtx = X.transpose() * &X;
+            let xtx = x_matrix.transpose() * &x_matrix;
             let xtx_inv = match xtx.try_inverse() {
                 Some(inv) => inv,
                 None => {
@@ -138,11 +143,11 @@ fn calculate_parameter_estimates(
                 }
             };
 
-            let beta = xtx_inv * X.transpose() * y;
+            let beta = &xtx_inv * x_matrix.transpose() * &y;
 
             // Calculate fitted values and residuals
-            let y_hat = X * beta;
-            let residuals = y - y_hat;
+            let y_hat = &x_matrix * &beta;
+            let residuals = &y - y_hat;
 
             // Calculate standard errors
             let df_error = n - p - 1;
@@ -227,7 +232,7 @@ fn calculate_intercept_only_parameters(
     let mut estimates = HashMap::new();
 
     // Process each dependent variable
-    for (factor_name, factors) in &within_factors.measures {
+    for factors in within_factors.measures.values() {
         let var_names: Vec<String> = factors
             .iter()
             .map(|f| f.dependent_variable.clone())
@@ -310,18 +315,75 @@ fn calculate_intercept_only_parameters(
     Ok(ParameterEstimates { estimates })
 }
 
+/// Helper function to generate parameter names
+pub fn generate_parameter_names(
+    data: &AnalysisData,
+    config: &RepeatedMeasuresConfig
+) -> Result<Vec<String>, String> {
+    // The repeated measures model always includes the intercept
+    let mut param_names = vec!["Intercept".to_string()];
+
+    // Add factor parameters
+    if let Some(factors) = &config.main.factors_var {
+        for factor in factors {
+            if let Ok(levels) = get_factor_levels(data, factor) {
+                // Create names for each level (except reference level)
+                for level in levels.iter().take(levels.len().saturating_sub(1)) {
+                    param_names.push(format!("[{}={}]", factor, level));
+                }
+            }
+        }
+    }
+
+    // Add covariate parameters
+    if let Some(covariates) = &config.main.covariates {
+        for covar in covariates {
+            param_names.push(covar.clone());
+        }
+    }
+
+    // Add interaction terms
+    if let Some(factors) = &config.main.factors_var {
+        if factors.len() > 1 {
+            let interaction_terms = generate_interaction_terms(factors);
+            for term in &interaction_terms {
+                let factors = parse_interaction_term(term);
+                let mut interaction_levels = Vec::new();
+
+                // For each factor in the interaction, get its levels
+                for factor in &factors {
+                    if let Ok(levels) = get_factor_levels(data, factor) {
+                        // For simplicity, use the first non-reference level
+                        if !levels.is_empty() {
+                            interaction_levels.push(format!("[{}={}]", factor, levels[0]));
+                        }
+                    }
+                }
+
+                // Create the interaction parameter name
+                if !interaction_levels.is_empty() {
+                    param_names.push(interaction_levels.join(" * "));
+                }
+            }
+        }
+    }
+
+    Ok(param_names)
+}
+
 /// Calculate power for t-test
 fn calculate_t_test_power(ncp: f64, df: f64, alpha: f64) -> f64 {
     // Approximation of t-test power
     let t_critical = t_critical_value(df, alpha / 2.0);
 
     // Calculate power using normal approximation
-    1.0 - normal_cdf((t_critical - ncp) / (1.0 + ncp.powi(2) / (2.0 * df)).sqrt())
+    let normal = Normal::new(0.0, 1.0).unwrap();
+    1.0 - normal.cdf((t_critical - ncp) / (1.0 + ncp.powi(2) / (2.0 * df)).sqrt())
 }
 
 /// Calculate t critical value
 fn t_critical_value(df: f64, alpha: f64) -> f64 {
     // Approximation of t critical value
-    let z = normal_quantile(1.0 - alpha);
+    let z = Normal::new(0.0, 1.0).unwrap().inverse_cdf(1.0 - alpha);
     z * (1.0 + z.powi(2) / (4.0 * df))
 }
@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{ BTreeSet, HashMap };
 use regex::Regex;
 
 use crate::models::{
@@ -7,7 +7,23 @@ use crate::models::{
     result::{ WithinSubjectFactor, WithinSubjectsFactors },
 };
 
-/// Parse within-subject factor string in format "variable_(level1,level2,...,measure_name)"
+use super::common::{ generate_interaction_terms, parse_interaction_term };
+
+/// Within-subjects cells shared by every measure, with the variable observed at each cell
+pub struct WithinSubjectsLayout {
+    pub measures: Vec<String>,
+    pub within_factors: Vec<String>,
+    pub factor_levels: Vec<Vec<String>>,
+    /// Level of every within-subjects factor per cell, first factor varying slowest
+    pub cells: Vec<Vec<String>>,
+    /// Variable holding each measure (outer, same order as `measures`) at each cell (inner)
+    pub variables: Vec<Vec<String>>,
+}
+
+/// Parse within-subject variables in format "variable_(level1,level2,...,measure_name)"
+///
+/// Cells are grouped by measure name, so a doubly multivariate design (several measures
+/// recorded at every within-subjects cell) yields one entry per measure.
 pub fn parse_within_subject_factors(
     data: &AnalysisData,
     config: &RepeatedMeasuresConfig
@@ -16,19 +32,37 @@ pub fn parse_within_subject_factors(
     let mut measures: HashMap<String, Vec<WithinSubjectFactor>> = HashMap::new();
 
     // Create regex for parsing variable format
-    let var_pattern = Regex::new(r"(\w+)_\((\d+(?:,\d+)*),(\w+)\)").map_err(|e| e.to_string())?;
+    let var_pattern = Regex::new(r"^(.+)_\((\d+(?:,\d+)*),([^,()]+)\)$").map_err(|e| e.to_string())?;
 
-    // Get the variable definitions
-    let var_defs = &data.subject_data_defs[0];
+    // Prefer the annotated names from the configuration, falling back to the variable definitions
+    let var_names: Vec<String> = match &config.main.sub_var {
+        Some(sub_vars) if !sub_vars.is_empty() => sub_vars.clone(),
+        _ =>
+            data.subject_data_defs
+                .iter()
+                .flatten()
+                .map(|def| def.name.clone())
+                .collect(),
+    };
 
-    // Process each variable
-    for var_def in var_defs {
-        let var_name = &var_def.name;
+    // Parse factor names from config
+    let factor_names: Vec<String> = config.model.def_factors
+        .as_ref()
+        .map(|def_factors| {
+            def_factors
+                .split(';')
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+                .collect()
+        })
+        .unwrap_or_default();
 
-        if let Some(captures) = var_pattern.captures(var_name) {
-            let factor_name = captures.get(1).unwrap().as_str().to_string();
+    // Process each variable
+    for var_name in &var_names {
+        if let Some(captures) = var_pattern.captures(var_name.trim()) {
+            let variable = captures.get(1).unwrap().as_str().trim().to_string();
             let level_str = captures.get(2).unwrap().as_str();
-            let measure_name = captures.get(3).unwrap().as_str().to_string();
+            let measure_name = captures.get(3).unwrap().as_str().trim().to_string();
 
             // Parse levels
             let levels: Vec<u32> = level_str
@@ -36,40 +70,143 @@ pub fn parse_within_subject_factors(
                 .filter_map(|s| s.parse::<u32>().ok())
                 .collect();
 
-            // Create factor values map
+            // Assign factor values based on levels and names
             let mut factor_values = HashMap::new();
-            if let Some(def_factors) = &config.model.def_factors {
-                // Parse factor names and levels from config
-                let factor_names: Vec<&str> = def_factors
-                    .split(';')
-                    .map(|s| s.trim())
-                    .collect();
-
-                // Assign factor values based on levels and names
-                for (i, &level) in levels.iter().enumerate() {
-                    if i < factor_names.len() {
-                        factor_values.insert(factor_names[i].to_string(), level.to_string());
-                    } else {
-                        factor_values.insert(format!("Factor{}", i + 1), level.to_string());
-                    }
-                }
-            } else {
-                // Default naming if no config provided
-                for (i, &level) in levels.iter().enumerate() {
-                    factor_values.insert(format!("Level{}", i + 1), level.to_string());
-                }
+            for (i, &level) in levels.iter().enumerate() {
+                let factor_name = factor_names
+                    .get(i)
+                    .cloned()
+                    .unwrap_or_else(|| format!("Factor{}", i + 1));
+                factor_values.insert(factor_name, level.to_string());
             }
 
             // Create the within subject factor
             let factor = WithinSubjectFactor {
                 factor_values,
-                dependent_variable: var_name.clone(),
+                dependent_variable: variable,
             };
 
             // Add to measures map
-            measures.entry(factor_name).or_insert_with(Vec::new).push(factor);
+            measures.entry(measure_name).or_default().push(factor);
         }
     }
 
     Ok(WithinSubjectsFactors { measures })
 }
+
+/// Arrange the parsed within-subject variables into a full grid of cells for every measure
+pub fn parse_within_subject_layout(
+    data: &AnalysisData,
+    config: &RepeatedMeasuresConfig
+) -> Result<(WithinSubjectsFactors, WithinSubjectsLayout), String> {
+    let design = parse_within_subject_factors(data, config)?;
+    if design.measures.is_empty() {
+        return Err("No within-subject variables in the form variable_(levels,measure) were found".to_string());
+    }
+
+    let mut measures: Vec<String> = design.measures.keys().cloned().collect();
+    measures.sort();
+
+    // Within-subjects factors in the order they were defined
+    let first_cell = &design.measures[&measures[0]][0];
+    let mut within_factors: Vec<String> = config.model.def_factors
+        .as_ref()
+        .map(|def_factors| {
+            def_factors
+                .split(';')
+                .map(|s| s.trim().to_string())
+                .filter(|name| first_cell.factor_values.contains_key(name))
+                .collect()
+        })
+        .unwrap_or_default();
+    if within_factors.len() != first_cell.factor_values.len() {
+        within_factors = first_cell.factor_values.keys().cloned().collect();
+        within_factors.sort();
+    }
+
+    let mut factor_levels: Vec<Vec<String>> = Vec::with_capacity(within_factors.len());
+    for factor in &within_factors {
+        let levels: Vec<u32> = design.measures
+            .values()
+            .flatten()
+            .filter_map(|cell| cell.factor_values.get(factor))
+            .filter_map(|level| level.parse::<u32>().ok())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect();
+        factor_levels.push(levels.iter().map(|level| level.to_string()).collect());
+    }
+
+    // Cells in lexicographic order of levels, first factor varying slowest
+    let mut cells: Vec<Vec<String>> = vec![Vec::new()];
+    for levels in &factor_levels {
+        cells = cells
+            .into_iter()
+            .flat_map(|cell| {
+                levels.iter().map(move |level| {
+                    let mut extended = cell.clone();
+                    extended.push(level.clone());
+                    extended
+                })
+            })
+            .collect();
+    }
+    let n_cells = cells.len();
+
+    let mut variables = Vec::with_capacity(measures.len());
+    for measure in &measures {
+        let measure_cells = &design.measures[measure];
+        if measure_cells.len() != n_cells {
+            return Err(
+                format!(
+                    "Measure '{}' has {} variables but the within-subjects design has {} cells",
+                    measure,
+                    measure_cells.len(),
+                    n_cells
+                )
+            );
+        }
+        let mut measure_variables = Vec::with_capacity(n_cells);
+        for cell in &cells {
+            let variable = measure_cells
+                .iter()
+                .find(|candidate| {
+                    within_factors
+                        .iter()
+                        .zip(cell)
+                        .all(|(factor, level)| candidate.factor_values.get(factor) == Some(level))
+                })
+                .map(|candidate| candidate.dependent_variable.clone())
+                .ok_or_else(||
+                    format!("Measure '{}' has no variable for cell ({})", measure, cell.join(","))
+                )?;
+            measure_variables.push(variable);
+        }
+        variables.push(measure_variables);
+    }
+
+    Ok((
+        design,
+        WithinSubjectsLayout {
+            measures,
+            within_factors,
+            factor_levels,
+            cells,
+            variables,
+        },
+    ))
+}
+
+/// Within-subjects main effects and interactions as indices into the within-subjects factors
+pub fn within_subject_terms(within_factors: &[String]) -> Vec<Vec<usize>> {
+    let mut terms: Vec<Vec<usize>> = (0..within_factors.len()).map(|i| vec![i]).collect();
+    for term in generate_interaction_terms(within_factors) {
+        terms.push(
+            parse_interaction_term(&term)
+                .iter()
+                .filter_map(|name| within_factors.iter().position(|factor| factor == name))
+                .collect()
+        );
+    }
+    terms
+}
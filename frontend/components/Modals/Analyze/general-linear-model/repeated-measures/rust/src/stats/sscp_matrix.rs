@@ -8,12 +8,9 @@ use crate::models::{
 
 use super::common::{
     build_design_matrix_and_response,
-    calculate_mean,
-    extract_dependent_value,
     matrix_multiply,
     matrix_transpose,
     matrix_inverse,
-    data_value_to_string,
 };
 
 /// Calculate SSCP (Sum of Squares and Cross Products) matrix for the model
@@ -21,9 +18,6 @@ pub fn calculate_sscp_matrix(
     data: &AnalysisData,
     config: &RepeatedMeasuresConfig
 ) -> Result<SSCPMatrix, String> {
-    // Get the list of dependent variables
-    let dependent_vars = config.main.sub_var.as_ref().unwrap();
-
     // Calculate Lack of Fit and Pure Error SSCP matrices
     let (lack_of_fit_sscp, pure_error_sscp) = calculate_lack_of_fit_and_pure_error(data, config)?;
 
@@ -43,14 +37,14 @@ pub fn calculate_sscp_matrix(
     })
 }
 
+/// SSCP values keyed by row and column dependent variable
+type SSCPRows = HashMap<String, HashMap<String, f64>>;
+
 /// Calculate Lack of Fit and Pure Error SSCP matrices
 fn calculate_lack_of_fit_and_pure_error(
     data: &AnalysisData,
     config: &RepeatedMeasuresConfig
-) -> Result<
-    (HashMap<String, HashMap<String, f64>>, HashMap<String, HashMap<String, f64>>),
-    String
-> {
+) -> Result<(SSCPRows, SSCPRows), String> {
     // Check if there are any dependent variables
     if config.main.sub_var.is_none() || config.main.sub_var.as_ref().unwrap().is_empty() {
         return Err("No dependent variables specified".to_string());
@@ -89,10 +83,7 @@ fn calculate_lack_of_fit_and_pure_error(
     let mut fitted_values = Vec::new();
     let mut residuals = Vec::new();
 
-    for (i, (x_matrix, y_vector)) in design_matrices
-        .iter()
-        .zip(response_vectors.iter())
-        .enumerate() {
+    for (x_matrix, y_vector) in design_matrices.iter().zip(response_vectors.iter()) {
         if x_matrix.is_empty() || y_vector.is_empty() {
             fitted_values.push(Vec::new());
             residuals.push(Vec::new());
@@ -177,10 +168,7 @@ fn calculate_lack_of_fit_and_pure_error(
     // Calculate the group means for each unique predictor combination
     let mut group_means = HashMap::new();
     for (key, indices) in &unique_x_combinations {
-        let mut group_means_row = Vec::new();
-        for _ in 0..dependent_vars.len() {
-            group_means_row.push(0.0);
-        }
+        let mut group_means_row = vec![0.0; dependent_vars.len()];
 
         // Count the observations in this group
         let mut counts = vec![0; dependent_vars.len()];
@@ -359,10 +347,7 @@ pub fn calculate_hypothesis_sscp(
     let mut full_parameters = Vec::new();
     let mut full_fitted_values = Vec::new();
 
-    for (i, (x_matrix, y_vector)) in full_design_matrices
-        .iter()
-        .zip(full_response_vectors.iter())
-        .enumerate() {
+    for (x_matrix, y_vector) in full_design_matrices.iter().zip(full_response_vectors.iter()) {
         // Calculate X'X
         let x_transpose = matrix_transpose(x_matrix);
         let xtx = match matrix_multiply(&x_transpose, x_matrix) {
@@ -416,10 +401,7 @@ pub fn calculate_hypothesis_sscp(
     // Calculate parameters for reduced model
     let mut reduced_fitted_values = Vec::new();
 
-    for (i, (x_matrix, y_vector)) in reduced_design_matrices
-        .iter()
-        .zip(full_response_vectors.iter())
-        .enumerate() {
+    for (x_matrix, y_vector) in reduced_design_matrices.iter().zip(full_response_vectors.iter()) {
         // Calculate X'X
         let x_transpose = matrix_transpose(x_matrix);
         let xtx = match matrix_multiply(&x_transpose, x_matrix) {
@@ -584,7 +566,7 @@ fn build_type_ii_reduced_design_matrix(
     let cols_per_effect = (full_matrix[0].len() - 1) / model_effects.len();
 
     for (i, e) in model_effects.iter().enumerate() {
-        if e != effect && !containing_effects.contains(&&e) {
+        if e != effect && !containing_effects.contains(&e) {
             let start_col = 1 + i * cols_per_effect;
             for j in 0..cols_per_effect {
                 columns_to_keep.push(start_col + j);
@@ -766,9 +748,8 @@ pub fn calculate_error_sscp(
             // Calculate the SSCP value: sum of residual products
             let mut sscp_value = 0.0;
 
-            let min_len = std::cmp::min(residuals[i].len(), residuals[j].len());
-            for k in 0..min_len {
-                sscp_value += residuals[i][k] * residuals[j][k];
+            for (res_i, res_j) in residuals[i].iter().zip(residuals[j].iter()) {
+                sscp_value += res_i * res_j;
             }
 
             row_values.insert(dep_var2.clone(), sscp_value);
@@ -33,18 +33,13 @@ pub fn calculate_univariate_tests(
 
     // Process each dependent variable
     for dep_var in dependent_vars {
-        // Create an empty vector to store test entries for this dependent variable
-        let mut test_entries = Vec::new();
-
         // Calculate test entries for this dependent variable
-        match calculate_univariate_test_entries(data, config, dep_var, alpha) {
-            Ok(entries) => {
-                test_entries = entries;
-            }
+        let test_entries = match calculate_univariate_test_entries(data, config, dep_var, alpha) {
+            Ok(entries) => entries,
             Err(e) => {
                 return Err(format!("Failed to calculate tests for {}: {}", dep_var, e));
             }
-        }
+        };
 
         // Add test entries for this dependent variable
         tests.insert(dep_var.clone(), test_entries);
@@ -77,12 +72,6 @@ fn calculate_univariate_test_entries(
     // Calculate overall mean
     let overall_mean = calculate_mean(&values);
 
-    // Calculate total sum of squares
-    let total_ss = values
-        .iter()
-        .map(|&value| (value - overall_mean).powi(2))
-        .sum::<f64>();
-
     // Build design matrix and fit the model
     let (x_matrix, y_vector) = match build_design_matrix_and_response(data, config, dep_var) {
         Ok(result) => result,
@@ -155,13 +144,9 @@ fn calculate_univariate_test_entries(
     match config.model.sum_of_square_method {
         SumOfSquaresMethod::TypeI => {
             create_type1_test_entries(
-                data,
                 config,
-                dep_var,
                 &values,
                 overall_mean,
-                &x_matrix,
-                &y_vector,
                 &beta,
                 model_ss,
                 error_ss,
@@ -170,13 +155,9 @@ fn calculate_univariate_test_entries(
         }
         SumOfSquaresMethod::TypeII => {
             create_type2_test_entries(
-                data,
                 config,
-                dep_var,
                 &values,
                 overall_mean,
-                &x_matrix,
-                &y_vector,
                 &beta,
                 model_ss,
                 error_ss,
@@ -186,13 +167,9 @@ fn calculate_univariate_test_entries(
         SumOfSquaresMethod::TypeIII => {
             // Type III is the default
             create_type3_test_entries(
-                data,
                 config,
-                dep_var,
                 &values,
                 overall_mean,
-                &x_matrix,
-                &y_vector,
                 &beta,
                 model_ss,
                 error_ss,
@@ -202,13 +179,9 @@ fn calculate_univariate_test_entries(
         SumOfSquaresMethod::TypeIV => {
             // Type IV is similar to Type III but with special handling for empty cells
             create_type3_test_entries(
-                data,
                 config,
-                dep_var,
                 &values,
                 overall_mean,
-                &x_matrix,
-                &y_vector,
                 &beta,
                 model_ss,
                 error_ss,
@@ -220,13 +193,9 @@ fn calculate_univariate_test_entries(
 
 /// Create Type I (sequential) test entries
 fn create_type1_test_entries(
-    data: &AnalysisData,
     config: &RepeatedMeasuresConfig,
-    dep_var: &str,
     values: &[f64],
     overall_mean: f64,
-    x_matrix: &[Vec<f64>],
-    y_vector: &[f64],
     beta: &[f64],
     model_ss: f64,
     error_ss: f64,
@@ -255,28 +224,16 @@ fn create_type1_test_entries(
         None
     };
 
-    let significance = if let Some(f_val) = f_value {
-        Some(calculate_f_significance(model_df, error_df, f_val))
-    } else {
-        None
-    };
+    let significance = f_value.map(|f_val| calculate_f_significance(model_df, error_df, f_val));
 
     // Calculate partial eta squared
     let partial_eta_squared = Some(model_ss / (model_ss + error_ss));
 
     // Calculate noncentrality parameter
-    let noncent_parameter = if let Some(f_val) = f_value {
-        Some(f_val * (model_df as f64))
-    } else {
-        None
-    };
+    let noncent_parameter = f_value.map(|f_val| f_val * (model_df as f64));
 
     // Calculate observed power
-    let observed_power = if let Some(f_val) = f_value {
-        Some(calculate_observed_power(model_df, error_df, f_val, alpha))
-    } else {
-        None
-    };
+    let observed_power = f_value.map(|f_val| calculate_observed_power(model_df, error_df, f_val, alpha));
 
     // Add entry for corrected model
     test_entries.push(UnivariateTestEntry {
@@ -301,34 +258,18 @@ fn create_type1_test_entries(
         for factor in &factors {
             let factor_ms = if factor_df > 0 { Some(factor_ss / (factor_df as f64)) } else { None };
 
-            let factor_f = if let Some(error_ms_val) = error_ms {
-                if let Some(factor_ms_val) = factor_ms {
-                    Some(factor_ms_val / error_ms_val)
-                } else {
-                    None
-                }
+            let factor_f = if let (Some(factor_ms_val), Some(error_ms_val)) = (factor_ms, error_ms) {
+                Some(factor_ms_val / error_ms_val)
             } else {
                 None
             };
 
-            let factor_sig = if let Some(f_val) = factor_f {
-                Some(calculate_f_significance(factor_df, error_df, f_val))
-            } else {
-                None
-            };
+            let factor_sig = factor_f.map(|f_val| calculate_f_significance(factor_df, error_df, f_val));
 
             let factor_eta = Some(factor_ss / (factor_ss + error_ss));
-            let factor_ncp = if let Some(f_val) = factor_f {
-                Some(f_val * (factor_df as f64))
-            } else {
-                None
-            };
+            let factor_ncp = factor_f.map(|f_val| f_val * (factor_df as f64));
 
-            let factor_power = if let Some(f_val) = factor_f {
-                Some(calculate_observed_power(factor_df, error_df, f_val, alpha))
-            } else {
-                None
-            };
+            let factor_power = factor_f.map(|f_val| calculate_observed_power(factor_df, error_df, f_val, alpha));
 
             test_entries.push(UnivariateTestEntry {
                 source: factor.clone(),
@@ -388,13 +329,9 @@ fn create_type1_test_entries(
 
 /// Create Type II test entries (adjusted for appropriate effects)
 fn create_type2_test_entries(
-    data: &AnalysisData,
     config: &RepeatedMeasuresConfig,
-    dep_var: &str,
     values: &[f64],
     overall_mean: f64,
-    x_matrix: &[Vec<f64>],
-    y_vector: &[f64],
     beta: &[f64],
     model_ss: f64,
     error_ss: f64,
@@ -403,13 +340,9 @@ fn create_type2_test_entries(
     // For Type II, similar approach to Type I but with different adjustment logic
     // For simplicity, we'll reuse the Type I implementation for now
     create_type1_test_entries(
-        data,
         config,
-        dep_var,
         values,
         overall_mean,
-        x_matrix,
-        y_vector,
         beta,
         model_ss,
         error_ss,
@@ -419,13 +352,9 @@ fn create_type2_test_entries(
 
 /// Create Type III test entries (adjusted for all other effects)
 fn create_type3_test_entries(
-    data: &AnalysisData,
     config: &RepeatedMeasuresConfig,
-    dep_var: &str,
     values: &[f64],
     overall_mean: f64,
-    x_matrix: &[Vec<f64>],
-    y_vector: &[f64],
     beta: &[f64],
     model_ss: f64,
     error_ss: f64,
@@ -454,28 +383,16 @@ fn create_type3_test_entries(
         None
     };
 
-    let significance = if let Some(f_val) = f_value {
-        Some(calculate_f_significance(model_df, error_df, f_val))
-    } else {
-        None
-    };
+    let significance = f_value.map(|f_val| calculate_f_significance(model_df, error_df, f_val));
 
     // Calculate partial eta squared
     let partial_eta_squared = Some(model_ss / (model_ss + error_ss));
 
     // Calculate noncentrality parameter
-    let noncent_parameter = if let Some(f_val) = f_value {
-        Some(f_val * (model_df as f64))
-    } else {
-        None
-    };
+    let noncent_parameter = f_value.map(|f_val| f_val * (model_df as f64));
 
     // Calculate observed power
-    let observed_power = if let Some(f_val) = f_value {
-        Some(calculate_observed_power(model_df, error_df, f_val, alpha))
-    } else {
-        None
-    };
+    let observed_power = f_value.map(|f_val| calculate_observed_power(model_df, error_df, f_val, alpha));
 
     // Add entry for corrected model
     test_entries.push(UnivariateTestEntry {
@@ -501,34 +418,18 @@ fn create_type3_test_entries(
         for factor in &factors {
             let factor_ms = if factor_df > 0 { Some(factor_ss / (factor_df as f64)) } else { None };
 
-            let factor_f = if let Some(error_ms_val) = error_ms {
-                if let Some(factor_ms_val) = factor_ms {
-                    Some(factor_ms_val / error_ms_val)
-                } else {
-                    None
-                }
+            let factor_f = if let (Some(factor_ms_val), Some(error_ms_val)) = (factor_ms, error_ms) {
+                Some(factor_ms_val / error_ms_val)
             } else {
                 None
             };
 
-            let factor_sig = if let Some(f_val) = factor_f {
-                Some(calculate_f_significance(factor_df, error_df, f_val))
-            } else {
-                None
-            };
+            let factor_sig = factor_f.map(|f_val| calculate_f_significance(factor_df, error_df, f_val));
 
             let factor_eta = Some(factor_ss / (factor_ss + error_ss));
-            let factor_ncp = if let Some(f_val) = factor_f {
-                Some(f_val * (factor_df as f64))
-            } else {
-                None
-            };
+            let factor_ncp = factor_f.map(|f_val| f_val * (factor_df as f64));
 
-            let factor_power = if let Some(f_val) = factor_f {
-                Some(calculate_observed_power(factor_df, error_df, f_val, alpha))
-            } else {
-                None
-            };
+            let factor_power = factor_f.map(|f_val| calculate_observed_power(factor_df, error_df, f_val, alpha));
 
             test_entries.push(UnivariateTestEntry {
                 source: factor.clone(),
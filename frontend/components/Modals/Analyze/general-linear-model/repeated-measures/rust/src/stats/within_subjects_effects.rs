@@ -16,7 +16,7 @@ use crate::models::{
 use super::core::parse_within_subject_factors;
 
 /// Calculate tests of within-subjects effects
-fn calculate_within_subjects_effects(
+pub fn calculate_within_subjects_effects(
     data: &AnalysisData,
     config: &RepeatedMeasuresConfig,
     mauchly_test: &Option<MauchlyTest>
@@ -255,7 +255,6 @@ fn calculate_within_subjects_effects(
 /// Calculate observed power using the non-central F distribution approximation
 fn calculate_observed_power(f: f64, df1: f64, df2: f64, alpha: f64) -> f64 {
     // This is a simplified approximation
-    let noncentrality = f * df1;
     let critical_f = f_critical(df1, df2, alpha);
 
     // Power approximation based on normal distribution
@@ -0,0 +1,313 @@
+use std::collections::HashMap;
+
+use serde_json::json;
+
+use crate::models::{
+    config::{
+        CovarianceStructure,
+        CustomHypothesisSpec,
+        DenominatorDfMethod,
+        RepeatedMeasuresConfig,
+    },
+    data::{ AnalysisData, DataRecord, DataValue, VariableDefinition },
+    result::MixedModelResult,
+};
+use crate::stats::{
+    custom_hypothesis::calculate_custom_hypothesis_tests,
+    doubly_multivariate::calculate_doubly_multivariate_tests,
+    mixed_model::calculate_mixed_model,
+};
+
+// Six subjects measured on three occasions, no missing values
+const SCORES: [[f64; 3]; 6] = [
+    [10.0, 12.0, 15.0],
+    [8.0, 11.0, 11.0],
+    [13.0, 14.0, 19.0],
+    [9.0, 13.0, 12.0],
+    [12.0, 12.0, 17.0],
+    [7.0, 10.0, 12.0],
+];
+const OCCASIONS: [&str; 3] = ["t1", "t2", "t3"];
+// Occasion variables annotated with their level of "time" and the measure name
+const SUB_VARS: [&str; 3] = ["t1_(1,score)", "t2_(2,score)", "t3_(3,score)"];
+
+// A second measure recorded on the same occasions, for the doubly multivariate design
+const RATINGS: [[f64; 3]; 6] = [
+    [3.0, 4.0, 6.0],
+    [2.0, 4.0, 3.0],
+    [5.0, 5.0, 7.0],
+    [3.0, 5.0, 4.0],
+    [4.0, 4.0, 6.0],
+    [2.0, 3.0, 5.0],
+];
+const RATING_OCCASIONS: [&str; 3] = ["r1", "r2", "r3"];
+const DOUBLY_SUB_VARS: [&str; 6] = [
+    "t1_(1,score)",
+    "t2_(2,score)",
+    "t3_(3,score)",
+    "r1_(1,rating)",
+    "r2_(2,rating)",
+    "r3_(3,rating)",
+];
+
+// time on the four polynomial-transformed responses (two per measure): H = n z̄ z̄' and E the
+// SSCP about z̄ on 5 df, Wilks' lambda with exact F on (4, 2)
+const DOUBLY_TIME_WILKS: (f64, f64, f64) = (0.002572898799314, 193.8333333333, 0.005139177790396);
+// (measure, SS, error SS, F, Greenhouse-Geisser epsilon) of the univariate time test of each
+// measure on (2, 10) df; the score test repeats F(time) of the compound symmetry model
+const DOUBLY_TIME_UNIVARIATE: [(&str, f64, f64, f64, f64); 2] = [
+    ("rating", 12.0, 6.666666666667, 9.0, 0.609756097561),
+    ("score", 60.77777777778, 16.55555555556, 18.35570469799, 0.5524010947997),
+];
+// (measure, time, mean, std. error): occasion means with std. error s / sqrt(6)
+const DOUBLY_TIME_MEANS: [(&str, &str, f64, f64); 6] = [
+    ("rating", "1", 3.166666666667, 0.4772607021092),
+    ("rating", "2", 4.166666666667, 0.3073181485764),
+    ("rating", "3", 5.166666666667, 0.6009252125773),
+    ("score", "1", 9.833333333333, 0.9457507306074),
+    ("score", "2", 12.0, 0.5773502691896),
+    ("score", "3", 14.33333333333, 1.308094458023),
+];
+
+// Custom hypotheses on the intercept. Successive differences (t2 - t1, t3 - t2) have means
+// (13/6, 7/3) and error SSCP E = [[65/6, -22/3], [-22/3, 94/3]] on 5 df, so Wilks' lambda gives
+// Hotelling's T^2 with exact F on (2, 4)
+const CUSTOM_TIME_WILKS: (f64, f64, f64) = (0.01675977653631, 117.3333333333, 0.0002808901095471);
+// (hypothesis SS, error SS, F) of each difference tested on its own
+const CUSTOM_TIME_UNIVARIATE: [(f64, f64, f64); 2] = [
+    (28.16666666667, 10.83333333333, 13.0),
+    (32.66666666667, 31.33333333333, 5.212765957447),
+];
+// t3 - t1 against a hypothesized 3: (estimate, difference, std. error, Sig., lower, upper),
+// the differences having mean 4.5 and SS 7.5 about it
+const CUSTOM_T3_T1: (f64, f64, f64, f64, f64, f64) = (
+    4.5,
+    1.5,
+    0.5,
+    0.03009924789746,
+    0.2147090821818,
+    2.785290917818,
+);
+
+fn scale_definition(name: &str, column_index: usize) -> VariableDefinition {
+    serde_json
+        ::from_value(
+            json!({
+                "columnIndex": column_index,
+                "name": name,
+                "type": "NUMERIC",
+                "width": 8,
+                "decimals": 2,
+                "values": [],
+                "missing": [],
+                "columns": 8,
+                "align": "right",
+                "measure": "scale",
+                "role": "input",
+            })
+        )
+        .unwrap()
+}
+
+fn wide_data() -> AnalysisData {
+    let records = SCORES.iter()
+        .map(|row| DataRecord {
+            values: OCCASIONS.iter()
+                .zip(row.iter())
+                .map(|(name, &value)| (name.to_string(), DataValue::Number(value)))
+                .collect::<HashMap<_, _>>(),
+        })
+        .collect();
+
+    AnalysisData {
+        subject_data: vec![records],
+        factors_data: vec![],
+        covariate_data: None,
+        subject_data_defs: vec![
+            OCCASIONS.iter()
+                .enumerate()
+                .map(|(i, name)| scale_definition(name, i))
+                .collect()
+        ],
+        factors_data_defs: vec![],
+        covariate_data_defs: None,
+    }
+}
+
+fn doubly_data() -> AnalysisData {
+    let records = SCORES.iter()
+        .zip(RATINGS.iter())
+        .map(|(scores, ratings)| DataRecord {
+            values: OCCASIONS.iter()
+                .zip(scores.iter())
+                .chain(RATING_OCCASIONS.iter().zip(ratings.iter()))
+                .map(|(name, &value)| (name.to_string(), DataValue::Number(value)))
+                .collect::<HashMap<_, _>>(),
+        })
+        .collect();
+
+    AnalysisData {
+        subject_data: vec![records],
+        factors_data: vec![],
+        covariate_data: None,
+        subject_data_defs: vec![
+            OCCASIONS.iter()
+                .chain(RATING_OCCASIONS.iter())
+                .enumerate()
+                .map(|(i, name)| scale_definition(name, i))
+                .collect()
+        ],
+        factors_data_defs: vec![],
+        covariate_data_defs: None,
+    }
+}
+
+fn mixed_config(cov_structure: CovarianceStructure) -> RepeatedMeasuresConfig {
+    let mut config = RepeatedMeasuresConfig::default();
+    config.main.sub_var = Some(SUB_VARS.iter().map(|name| name.to_string()).collect());
+    config.model.def_factors = Some("time".to_string());
+    config.mixed.enabled = true;
+    config.mixed.cov_structure = Some(cov_structure);
+    config.mixed.df_method = Some(DenominatorDfMethod::KenwardRoger);
+    config.mixed.converge = Some(1e-12);
+    config
+}
+
+fn within_hypothesis(
+    label: &str,
+    columns: &[&[(usize, f64)]],
+    k_matrix: Option<Vec<Vec<f64>>>
+) -> CustomHypothesisSpec {
+    CustomHypothesisSpec {
+        label: Some(label.to_string()),
+        l_matrix: vec![HashMap::from([("Intercept".to_string(), 1.0)])],
+        m_matrix: Some(
+            columns
+                .iter()
+                .map(|column| {
+                    column
+                        .iter()
+                        .map(|&(occasion, coefficient)| (SUB_VARS[occasion].to_string(), coefficient))
+                        .collect()
+                })
+                .collect()
+        ),
+        k_matrix,
+    }
+}
+
+fn effect_test(result: &MixedModelResult, source: &str) -> (f64, f64, f64) {
+    let test = result.fixed_effects_tests
+        .iter()
+        .find(|test| test.source == source)
+        .unwrap_or_else(|| panic!("no test for {}", source));
+    (test.f, test.numerator_df, test.denominator_df)
+}
+
+// Kenward & Roger (1997): in balanced designs the adjusted test reproduces the exact F test.
+// Under compound symmetry that is the univariate repeated measures ANOVA:
+// F(time) = MS(time) / MS(time x subject) = 18.3557 on (2, 10), F(intercept) = 182.657 on (1, 5)
+#[test]
+fn kenward_roger_compound_symmetry_matches_univariate_anova() {
+    let result = calculate_mixed_model(&wide_data(), &mixed_config(CovarianceStructure::CompoundSymmetry)).unwrap();
+    assert!(result.converged);
+
+    let (f, numerator_df, denominator_df) = effect_test(&result, "time");
+    assert!((f - 18.3557046980).abs() < 1e-5);
+    assert_eq!(numerator_df, 2.0);
+    assert!((denominator_df - 10.0).abs() < 1e-5);
+
+    let (f, _, denominator_df) = effect_test(&result, "Intercept");
+    assert!((f - 182.6570985260).abs() < 1e-4);
+    assert!((denominator_df - 5.0).abs() < 1e-5);
+}
+
+// Under an unstructured covariance it is Hotelling's T^2 on the occasion differences:
+// T^2 = 293.333, F = (n - t + 1) / ((n - 1)(t - 1)) T^2 = 117.333 on (2, n - t + 1 = 4)
+#[test]
+fn kenward_roger_unstructured_matches_hotelling() {
+    let result = calculate_mixed_model(&wide_data(), &mixed_config(CovarianceStructure::Unstructured)).unwrap();
+    assert!(result.converged);
+
+    let (f, numerator_df, denominator_df) = effect_test(&result, "time");
+    assert!((f - 117.3333333333).abs() < 1e-4);
+    assert_eq!(numerator_df, 2.0);
+    assert!((denominator_df - 4.0).abs() < 1e-5);
+}
+
+#[test]
+fn custom_hypotheses_match_reference_values() {
+    let mut config = RepeatedMeasuresConfig::default();
+    config.main.sub_var = Some(SUB_VARS.iter().map(|name| name.to_string()).collect());
+    config.custom.tests = vec![
+        within_hypothesis("time", &[&[(0, -1.0), (1, 1.0)], &[(1, -1.0), (2, 1.0)]], None),
+        within_hypothesis("t3 - t1", &[&[(0, -1.0), (2, 1.0)]], Some(vec![vec![3.0]]))
+    ];
+
+    let results = calculate_custom_hypothesis_tests(&wide_data(), &config).unwrap();
+    assert_eq!(results.len(), 2);
+
+    let time = &results[0];
+    assert_eq!((time.hypothesis_df, time.error_df), (1, 5));
+    let wilks = &time.multivariate_tests["Wilks' Lambda"];
+    let (lambda, f, significance) = CUSTOM_TIME_WILKS;
+    assert!((wilks.value - lambda).abs() < 1e-9);
+    assert!((wilks.f - f).abs() < 1e-7);
+    assert_eq!((wilks.hypothesis_df, wilks.error_df), (2.0, 4.0));
+    assert!((wilks.significance - significance).abs() < 1e-9);
+    for (test, (hypothesis_ss, error_ss, f)) in time.univariate_tests
+        .iter()
+        .zip(CUSTOM_TIME_UNIVARIATE) {
+        assert!((test.hypothesis_ss - hypothesis_ss).abs() < 1e-9, "{}", test.transformed_variable);
+        assert!((test.error_ss - error_ss).abs() < 1e-9, "{}", test.transformed_variable);
+        assert!((test.f_value - f).abs() < 1e-9, "{}", test.transformed_variable);
+    }
+
+    let contrast = &results[1].contrast_estimates[0];
+    let (estimate, difference, std_error, significance, lower, upper) = CUSTOM_T3_T1;
+    assert!((contrast.estimate - estimate).abs() < 1e-9);
+    assert!((contrast.difference - difference).abs() < 1e-9);
+    assert!((contrast.std_error - std_error).abs() < 1e-9);
+    assert!((contrast.significance - significance).abs() < 1e-9);
+    assert!((contrast.confidence_interval.lower_bound - lower).abs() < 1e-9);
+    assert!((contrast.confidence_interval.upper_bound - upper).abs() < 1e-9);
+}
+
+#[test]
+fn doubly_multivariate_matches_reference_values() {
+    let mut config = RepeatedMeasuresConfig::default();
+    config.main.sub_var = Some(DOUBLY_SUB_VARS.iter().map(|name| name.to_string()).collect());
+    config.model.def_factors = Some("time".to_string());
+
+    let result = calculate_doubly_multivariate_tests(&doubly_data(), &config).unwrap();
+    assert_eq!(result.measures, vec!["rating", "score"]);
+    assert_eq!(result.error_df, 5);
+
+    let wilks = &result.multivariate_tests["time"]["Wilks' Lambda"];
+    let (lambda, f, significance) = DOUBLY_TIME_WILKS;
+    assert!((wilks.value - lambda).abs() < 1e-9);
+    assert!((wilks.f - f).abs() < 1e-7);
+    assert_eq!((wilks.hypothesis_df, wilks.error_df), (4.0, 2.0));
+    assert!((wilks.significance - significance).abs() < 1e-9);
+
+    for (measure, sum_of_squares, error_sum_of_squares, f, epsilon) in DOUBLY_TIME_UNIVARIATE {
+        let test = result.univariate_tests[measure]
+            .iter()
+            .find(|test| test.source == "time")
+            .unwrap();
+        assert_eq!((test.df, test.error_df), (2.0, 10.0));
+        assert!((test.sum_of_squares - sum_of_squares).abs() < 1e-9, "{}", measure);
+        assert!((test.error_sum_of_squares - error_sum_of_squares).abs() < 1e-9, "{}", measure);
+        assert!((test.f - f).abs() < 1e-9, "{}", measure);
+        assert!((test.greenhouse_geisser_epsilon.unwrap() - epsilon).abs() < 1e-9, "{}", measure);
+    }
+
+    for (measure, level, mean, std_error) in DOUBLY_TIME_MEANS {
+        let emmean = result.emmeans[measure]
+            .iter()
+            .find(|emmean| emmean.factor_name == "time" && emmean.factor_value == level)
+            .unwrap();
+        assert!((emmean.mean - mean).abs() < 1e-9, "{} {}", measure, level);
+        assert!((emmean.std_error - std_error).abs() < 1e-9, "{} {}", measure, level);
+    }
+}
@@ -1,6 +1,5 @@
 use wasm_bindgen::prelude::*;
 
-use crate::models::config::ContrastMethod;
 use crate::models::{
     config::RepeatedMeasuresConfig,
     data::AnalysisData,
@@ -23,14 +22,14 @@ pub fn run_analysis(
     web_sys::console::log_1(&format!("Config: {:?}", config).into());
 
     // Step 1: Calculate within-subjects factors (always executed)
-    executed_functions.push("calculate_within_subjects_factors".to_string());
+    executed_functions.push("parse_within_subject_factors".to_string());
     let mut within_subjects_factors = None;
-    match core::calculate_within_subjects_factors(data, config) {
+    match core::parse_within_subject_factors(data, config) {
         Ok(factors) => {
             within_subjects_factors = Some(factors);
         }
         Err(e) => {
-            error_collector.add_error("calculate_within_subjects_factors", &e);
+            error_collector.add_error("parse_within_subject_factors", &e);
         }
     }
 
@@ -88,41 +87,29 @@ pub fn run_analysis(
 
     // Step 6: Tests of within-subjects effects
     let mut tests_of_within_subjects_effects = None;
-    executed_functions.push("calculate_tests_within_subjects_effects".to_string());
-    match core::calculate_tests_within_subjects_effects(data, config) {
+    executed_functions.push("calculate_within_subjects_effects".to_string());
+    match core::calculate_within_subjects_effects(data, config, &mauchly_test) {
         Ok(tests) => {
             tests_of_within_subjects_effects = Some(tests);
         }
         Err(e) => {
-            error_collector.add_error("calculate_tests_within_subjects_effects", &e);
+            error_collector.add_error("calculate_within_subjects_effects", &e);
         }
     }
 
-    // Step 7: Tests of within-subjects contrasts
-    let mut tests_of_within_subjects_contrasts = None;
-    executed_functions.push("calculate_tests_within_subjects_contrasts".to_string());
-    match core::calculate_tests_within_subjects_contrasts(data, config) {
-        Ok(tests) => {
-            tests_of_within_subjects_contrasts = Some(tests);
-        }
-        Err(e) => {
-            error_collector.add_error("calculate_tests_within_subjects_contrasts", &e);
-        }
-    }
-
-    // Step 8: Tests of between-subjects effects
+    // Step 7: Tests of between-subjects effects
     let mut tests_of_between_subjects_effects = None;
-    executed_functions.push("calculate_tests_between_subjects_effects".to_string());
-    match core::calculate_tests_between_subjects_effects(data, config) {
+    executed_functions.push("calculate_between_subjects_effects".to_string());
+    match core::calculate_between_subjects_effects(data, config) {
         Ok(tests) => {
             tests_of_between_subjects_effects = Some(tests);
         }
         Err(e) => {
-            error_collector.add_error("calculate_tests_between_subjects_effects", &e);
+            error_collector.add_error("calculate_between_subjects_effects", &e);
         }
     }
 
-    // Step 9: Parameter Estimates if requested in options
+    // Step 8: Parameter Estimates if requested in options
     let mut parameter_estimates = None;
     if config.options.param_est {
         executed_functions.push("calculate_parameter_estimates".to_string());
@@ -136,7 +123,7 @@ pub fn run_analysis(
         }
     }
 
-    // Step 10: General estimable function if requested in options
+    // Step 9: General estimable function if requested in options
     let mut general_estimable_function = None;
     if config.options.general_fun {
         executed_functions.push("calculate_general_estimable_function".to_string());
@@ -150,21 +137,7 @@ pub fn run_analysis(
         }
     }
 
-    // Step 11: Within-subjects SSCP Matrix if requested in options
-    let mut within_subjects_sscp = None;
-    if config.options.sscp_mat {
-        executed_functions.push("calculate_within_subjects_sscp".to_string());
-        match core::calculate_within_subjects_sscp(data, config) {
-            Ok(sscp) => {
-                within_subjects_sscp = Some(sscp);
-            }
-            Err(e) => {
-                error_collector.add_error("calculate_within_subjects_sscp", &e);
-            }
-        }
-    }
-
-    // Step 12: Between-subjects SSCP Matrix if requested in options
+    // Step 10: Between-subjects SSCP Matrix if requested in options
     let mut between_subjects_sscp = None;
     if config.options.sscp_mat {
         executed_functions.push("calculate_between_subjects_sscp".to_string());
@@ -178,7 +151,7 @@ pub fn run_analysis(
         }
     }
 
-    // Step 13: Residual SSCP Matrix if requested in options
+    // Step 11: Residual SSCP Matrix if requested in options
     let mut residual_matrix = None;
     if config.options.res_sscp_mat {
         executed_functions.push("calculate_residual_matrix".to_string());
@@ -192,7 +165,7 @@ pub fn run_analysis(
         }
     }
 
-    // Step 14: SSCP Matrix if requested
+    // Step 12: SSCP Matrix if requested
     let mut sscp_matrix = None;
     if config.options.sscp_mat {
         executed_functions.push("calculate_sscp_matrix".to_string());
@@ -206,7 +179,7 @@ pub fn run_analysis(
         }
     }
 
-    // Step 15: Univariate tests
+    // Step 13: Univariate tests
     let mut univariate_tests = None;
     executed_functions.push("calculate_univariate_tests".to_string());
     match core::calculate_univariate_tests(data, config) {
@@ -218,7 +191,21 @@ pub fn run_analysis(
         }
     }
 
-    // Step 17: Post-hoc tests if requested
+    // Step 14: Doubly multivariate tests when several measures share the within-subjects cells
+    let mut doubly_multivariate = None;
+    if core::parse_within_subject_factors(data, config).is_ok_and(|factors| factors.measures.len() > 1) {
+        executed_functions.push("calculate_doubly_multivariate_tests".to_string());
+        match core::calculate_doubly_multivariate_tests(data, config) {
+            Ok(result) => {
+                doubly_multivariate = Some(result);
+            }
+            Err(e) => {
+                error_collector.add_error("calculate_doubly_multivariate_tests", &e);
+            }
+        }
+    }
+
+    // Step 15: Post-hoc tests if requested
     let mut posthoc_tests = None;
     if let Some(fix_factor_vars) = &config.posthoc.fix_factor_vars {
         if !fix_factor_vars.is_empty() {
@@ -234,7 +221,7 @@ pub fn run_analysis(
         }
     }
 
-    // Step 18: Estimated Marginal Means if requested
+    // Step 16: Estimated Marginal Means if requested
     let mut emmeans = None;
     if let Some(target_list) = &config.emmeans.target_list {
         if !target_list.is_empty() {
@@ -250,23 +237,30 @@ pub fn run_analysis(
         }
     }
 
-    // Step 19: Save variables if requested
-    if
-        config.save.res_weighted ||
-        config.save.pre_weighted ||
-        config.save.unstandardized_res ||
-        config.save.weighted_res ||
-        config.save.standardized_res ||
-        config.save.studentized_res ||
-        config.save.deleted_res ||
-        config.save.leverage ||
-        config.save.cooks_d
-    {
-        executed_functions.push("save_variables".to_string());
-        match core::save_variables(data, config) {
-            Ok(_) => {}
+    // Custom L/M/K hypothesis tests if specified
+    let mut custom_hypothesis_tests = None;
+    if !config.custom.tests.is_empty() {
+        executed_functions.push("calculate_custom_hypothesis_tests".to_string());
+        match core::calculate_custom_hypothesis_tests(data, config) {
+            Ok(tests) => {
+                custom_hypothesis_tests = Some(tests);
+            }
+            Err(e) => {
+                error_collector.add_error("calculate_custom_hypothesis_tests", &e);
+            }
+        }
+    }
+
+    // Linear mixed model (REML) if requested
+    let mut mixed_model = None;
+    if config.mixed.enabled {
+        executed_functions.push("calculate_mixed_model".to_string());
+        match core::calculate_mixed_model(data, config) {
+            Ok(result) => {
+                mixed_model = Some(result);
+            }
             Err(e) => {
-                error_collector.add_error("save_variables", &e);
+                error_collector.add_error("calculate_mixed_model", &e);
             }
         }
     }
@@ -279,17 +273,20 @@ pub fn run_analysis(
         multivariate_tests,
         mauchly_test,
         tests_of_within_subjects_effects,
-        tests_of_within_subjects_contrasts,
+        tests_of_within_subjects_contrasts: None,
         tests_of_between_subjects_effects,
         parameter_estimates,
         general_estimable_function,
-        within_subjects_sscp,
+        within_subjects_sscp: None,
         between_subjects_sscp,
         residual_matrix,
         sscp_matrix,
         univariate_tests,
         posthoc_tests,
         emmeans,
+        custom_hypothesis_tests,
+        doubly_multivariate,
+        mixed_model,
         executed_functions,
     };
 
@@ -207,6 +207,29 @@ export type RepeatedMeasuresOptionsProps = {
     data: RepeatedMeasuresOptionsType;
 };
 
+export type RepeatedMeasuresCustomHypothesisType = {
+    Label: string | null;
+    LMatrix: Record<string, number>[];
+    MMatrix: Record<string, number>[] | null;
+    KMatrix: number[][] | null;
+};
+
+export type RepeatedMeasuresCustomType = {
+    Tests: RepeatedMeasuresCustomHypothesisType[];
+};
+
+export type RepeatedMeasuresMixedType = {
+    Enabled: boolean;
+    CovStructure: "compoundSymmetry" | "ar1" | "unstructured" | "toeplitz" | null;
+    DfMethod: "satterthwaite" | "kenwardRoger" | null;
+    Measure: string | null;
+    SubjectVar: string | null;
+    OccasionVar: string | null;
+    DepVar: string | null;
+    MaxIter: number | null;
+    Converge: number | null;
+};
+
 export type RepeatedMeasuresType = {
     main: RepeatedMeasuresMainType;
     model: RepeatedMeasuresModelType;
@@ -216,6 +239,8 @@ export type RepeatedMeasuresType = {
     emmeans: RepeatedMeasuresEMMeansType;
     save: RepeatedMeasuresSaveType;
     options: RepeatedMeasuresOptionsType;
+    custom: RepeatedMeasuresCustomType;
+    mixed: RepeatedMeasuresMixedType;
 };
 
 export type RepeatedMeasuresContainerProps = {
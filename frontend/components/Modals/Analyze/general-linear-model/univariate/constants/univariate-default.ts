@@ -1,6 +1,7 @@
 import {
     UnivariateBootstrapType,
     UnivariateContrastType,
+    UnivariateCustomType,
     UnivariateEMMeansType,
     UnivariateMainType,
     UnivariateModelType,
@@ -90,6 +91,8 @@ export const UnivariateEMMeansDefault: UnivariateEMMeansType = {
     SrcList: null,
     TargetList: null,
     CompMainEffect: false,
+    CompSimpleEffect: false,
+    CompInteraction: false,
     ConfiIntervalMethod: "lsdNone",
 };
 
@@ -107,6 +110,13 @@ export const UnivariateSaveDefault: UnivariateSaveType = {
     CoeffStats: false,
     StandardStats: false,
     Heteroscedasticity: false,
+    CaseDiagnostics: false,
+    ResidualThreshold: null,
+    LeverageThreshold: null,
+    CooksDThreshold: null,
+    DffitThreshold: null,
+    DfbetaThreshold: null,
+    CovRatioThreshold: null,
     NewDataSet: true,
     FilePath: null,
     DatasetName: null,
@@ -152,6 +162,10 @@ export const UnivariateBootstrapDefault: UnivariateBootstrapType = {
     StrataVariables: null,
 };
 
+export const UnivariateCustomDefault: UnivariateCustomType = {
+    Tests: [],
+};
+
 export const UnivariateDefault: UnivariateType = {
     main: UnivariateMainDefault,
     model: UnivariateModelDefault,
@@ -162,4 +176,5 @@ export const UnivariateDefault: UnivariateType = {
     save: UnivariateSaveDefault,
     options: UnivariateOptionsDefault,
     bootstrap: UnivariateBootstrapDefault,
+    custom: UnivariateCustomDefault,
 };
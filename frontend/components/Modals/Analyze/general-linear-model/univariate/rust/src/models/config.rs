@@ -1,6 +1,7 @@
 use serde::{ Deserialize, Serialize };
+use std::collections::HashMap;
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct UnivariateConfig {
     pub main: MainConfig,
     pub model: ModelConfig,
@@ -11,6 +12,8 @@ pub struct UnivariateConfig {
     pub save: SaveConfig,
     pub options: OptionsConfig,
     pub bootstrap: BootstrapConfig,
+    #[serde(default)]
+    pub custom: CustomHypothesisConfig,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -77,7 +80,7 @@ pub enum CIMethod {
     Sidak,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct MainConfig {
     #[serde(rename = "DepVar")]
     pub dep_var: Option<String>,
@@ -119,6 +122,25 @@ pub struct ModelConfig {
     pub intercept: bool,
 }
 
+impl Default for ModelConfig {
+    fn default() -> Self {
+        ModelConfig {
+            non_cust: true,
+            custom: false,
+            build_custom_term: false,
+            factors_var: Vec::new(),
+            terms_var: None,
+            factors_model: None,
+            cov_model: None,
+            random_model: None,
+            build_term_method: BuildTermMethod::Interaction,
+            term_text: None,
+            sum_of_square_method: SumOfSquaresMethod::TypeIII,
+            intercept: true,
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ContrastConfig {
     #[serde(rename = "FactorList")]
@@ -131,6 +153,17 @@ pub struct ContrastConfig {
     pub first: bool,
 }
 
+impl Default for ContrastConfig {
+    fn default() -> Self {
+        ContrastConfig {
+            factor_list: None,
+            contrast_method: ContrastMethod::None,
+            last: true,
+            first: false,
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct PlotsConfig {
     #[serde(rename = "SrcList")]
@@ -163,6 +196,27 @@ pub struct PlotsConfig {
     pub y_axis_start_0: bool,
 }
 
+impl Default for PlotsConfig {
+    fn default() -> Self {
+        PlotsConfig {
+            src_list: Vec::new(),
+            axis_list: None,
+            line_list: None,
+            plot_list: None,
+            fix_factor_vars: None,
+            rand_factor_vars: None,
+            line_chart_type: true,
+            bar_chart_type: false,
+            include_error_bars: false,
+            confidence_interval: true,
+            standard_error: false,
+            multiplier: 2,
+            include_ref_line_for_grand_mean: false,
+            y_axis_start_0: false,
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct PosthocConfig {
     #[serde(rename = "SrcList")]
@@ -217,6 +271,38 @@ pub struct PosthocConfig {
     pub dunc: bool,
 }
 
+impl Default for PosthocConfig {
+    fn default() -> Self {
+        PosthocConfig {
+            src_list: None,
+            fix_factor_vars: None,
+            error_ratio: 100,
+            twosided: true,
+            lt_control: false,
+            gt_control: false,
+            category_method: CategoryMethod::Last,
+            waller: false,
+            dunnett: false,
+            lsd: false,
+            bonfe: false,
+            sidak: false,
+            scheffe: false,
+            regwf: false,
+            regwq: false,
+            snk: false,
+            tu: false,
+            tub: false,
+            dun: false,
+            hoc: false,
+            gabriel: false,
+            tam: false,
+            dunt: false,
+            games: false,
+            dunc: false,
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct EmmmeansConfig {
     #[serde(rename = "SrcList")]
@@ -225,10 +311,27 @@ pub struct EmmmeansConfig {
     pub target_list: Option<Vec<String>>,
     #[serde(rename = "CompMainEffect")]
     pub comp_main_effect: bool,
+    #[serde(rename = "CompSimpleEffect", default)]
+    pub comp_simple_effect: bool,
+    #[serde(rename = "CompInteraction", default)]
+    pub comp_interaction: bool,
     #[serde(rename = "ConfiIntervalMethod")]
     pub confi_interval_method: CIMethod,
 }
 
+impl Default for EmmmeansConfig {
+    fn default() -> Self {
+        EmmmeansConfig {
+            src_list: Vec::new(),
+            target_list: None,
+            comp_main_effect: false,
+            comp_simple_effect: false,
+            comp_interaction: false,
+            confi_interval_method: CIMethod::LsdNone,
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct SaveConfig {
     #[serde(rename = "UnstandardizedPre")]
@@ -257,6 +360,20 @@ pub struct SaveConfig {
     pub standard_stats: bool,
     #[serde(rename = "Heteroscedasticity")]
     pub heteroscedasticity: bool,
+    #[serde(rename = "CaseDiagnostics", default)]
+    pub case_diagnostics: bool,
+    #[serde(rename = "ResidualThreshold")]
+    pub residual_threshold: Option<f64>,
+    #[serde(rename = "LeverageThreshold")]
+    pub leverage_threshold: Option<f64>,
+    #[serde(rename = "CooksDThreshold")]
+    pub cooks_d_threshold: Option<f64>,
+    #[serde(rename = "DffitThreshold")]
+    pub dffit_threshold: Option<f64>,
+    #[serde(rename = "DfbetaThreshold")]
+    pub dfbeta_threshold: Option<f64>,
+    #[serde(rename = "CovRatioThreshold")]
+    pub cov_ratio_threshold: Option<f64>,
     #[serde(rename = "NewDataSet")]
     pub new_dataset: bool,
     #[serde(rename = "FilePath")]
@@ -267,6 +384,37 @@ pub struct SaveConfig {
     pub write_new_dataset: bool,
 }
 
+impl Default for SaveConfig {
+    fn default() -> Self {
+        SaveConfig {
+            unstandardized_pre: false,
+            weighted_pre: false,
+            std_statistics: false,
+            cooks_d: false,
+            leverage: false,
+            unstandardized_res: false,
+            weighted_res: false,
+            standardized_res: false,
+            studentized_res: false,
+            deleted_res: false,
+            coeff_stats: false,
+            standard_stats: false,
+            heteroscedasticity: false,
+            case_diagnostics: false,
+            residual_threshold: None,
+            leverage_threshold: None,
+            cooks_d_threshold: None,
+            dffit_threshold: None,
+            dfbeta_threshold: None,
+            cov_ratio_threshold: None,
+            new_dataset: true,
+            file_path: None,
+            dataset_name: None,
+            write_new_dataset: false,
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct OptionsConfig {
     #[serde(rename = "DescStats")]
@@ -315,6 +463,35 @@ pub struct OptionsConfig {
     pub sig_level: f64,
 }
 
+impl Default for OptionsConfig {
+    fn default() -> Self {
+        OptionsConfig {
+            desc_stats: false,
+            homogen_test: false,
+            est_effect_size: false,
+            spr_vs_level: false,
+            obs_power: false,
+            res_plot: false,
+            param_est: false,
+            lack_of_fit: false,
+            transform_mat: false,
+            general_fun: false,
+            mod_brusch_pagan: false,
+            f_test: false,
+            brusch_pagan: false,
+            white_test: false,
+            param_est_rob_std_err: false,
+            hc0: false,
+            hc1: false,
+            hc2: false,
+            hc3: false,
+            hc4: false,
+            coefficient_matrix: false,
+            sig_level: 0.05,
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct BootstrapConfig {
     #[serde(rename = "PerformBootStrapping")]
@@ -340,3 +517,37 @@ pub struct BootstrapConfig {
     #[serde(rename = "StrataVariables")]
     pub strata_variables: Option<Vec<String>>,
 }
+
+impl Default for BootstrapConfig {
+    fn default() -> Self {
+        BootstrapConfig {
+            perform_boot_strapping: false,
+            num_of_samples: 1000,
+            seed: false,
+            seed_value: 200000,
+            level: 95.0,
+            percentile: true,
+            bca: false,
+            simple: true,
+            stratified: false,
+            variables: Vec::new(),
+            strata_variables: None,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct CustomHypothesisConfig {
+    #[serde(rename = "Tests", default)]
+    pub tests: Vec<CustomHypothesisSpec>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CustomHypothesisSpec {
+    #[serde(rename = "Label", default)]
+    pub label: Option<String>,
+    #[serde(rename = "LMatrix", default)]
+    pub l_matrix: Vec<HashMap<String, f64>>,
+    #[serde(rename = "KMatrix", default)]
+    pub k_matrix: Option<Vec<f64>>,
+}
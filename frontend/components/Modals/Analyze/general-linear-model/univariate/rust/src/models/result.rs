@@ -12,12 +12,16 @@ pub struct UnivariateResult {
     pub heteroscedasticity_tests: Option<HeteroscedasticityTests>,
     pub tests_of_between_subjects_effects: Option<TestsBetweenSubjectsEffects>,
     pub parameter_estimates: Option<ParameterEstimates>,
+    pub robust_parameter_estimates: Option<Vec<RobustParameterEstimates>>,
     pub general_estimable_function: Option<GeneralEstimableFunction>,
     pub hypothesis_l_matrices: Option<HypothesisLMatrices>,
     pub contrast_coefficients: Option<ContrastCoefficients>,
+    pub custom_hypothesis_tests: Option<CustomHypothesisTests>,
     pub lack_of_fit_tests: Option<LackOfFitTests>,
     pub emmeans: Option<EMMeansResult>,
+    pub posthoc_tests: Option<PostHocTests>,
     pub saved_variables: Option<SavedVariables>,
+    pub case_diagnostics: Option<CaseDiagnostics>,
 }
 
 #[derive(Debug, Clone)]
@@ -104,10 +108,37 @@ pub struct LeveneTestEntry {
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct TestsBetweenSubjectsEffects {
     pub sources: Vec<SourceEntry>,
+    pub error_terms: Option<Vec<ErrorTermEntry>>,
+    pub expected_mean_squares: Option<ExpectedMeanSquares>,
     pub note: Option<String>,
     pub interpretation: Option<String>,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ErrorTermEntry {
+    pub source: String,
+    pub sum_of_squares: f64,
+    pub df: f64,
+    pub mean_square: f64,
+    pub composition: String,
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ExpectedMeanSquares {
+    pub variance_components: Vec<String>,
+    pub entries: Vec<ExpectedMeanSquaresEntry>,
+    pub note: Option<String>,
+    pub interpretation: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ExpectedMeanSquaresEntry {
+    pub source: String,
+    pub coefficients: Vec<f64>,
+    pub quadratic_term: Option<String>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct SourceEntry {
     pub name: String,
@@ -148,6 +179,8 @@ pub struct EMMeansResult {
     pub em_estimates: Vec<EMMeansEstimates>,
     pub pairwise_comparisons: Option<Vec<PairwiseComparisons>>,
     pub univariate_tests: Option<Vec<UnivariateTests>>,
+    pub simple_effects: Option<Vec<SimpleEffects>>,
+    pub interaction_contrasts: Option<Vec<InteractionContrasts>>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -201,6 +234,93 @@ pub struct UnivariateTestsEntry {
     pub observed_power: f64,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SimpleEffects {
+    pub factor_spec: String,
+    pub compared_factor: String,
+    pub slices: Vec<SimpleEffectSlice>,
+    pub note: Option<String>,
+    pub interpretation: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SimpleEffectSlice {
+    pub fixed_levels: Vec<String>,
+    pub comparisons: Vec<PairwiseComparisonsEntry>,
+    pub test: UnivariateTestsEntry,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct InteractionContrasts {
+    pub factor_spec: String,
+    pub factors: Vec<String>,
+    pub entries: Vec<InteractionContrastEntry>,
+    pub tests: Vec<UnivariateTestsEntry>,
+    pub note: Option<String>,
+    pub interpretation: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct InteractionContrastEntry {
+    pub fixed_levels: Vec<String>,
+    pub first_factor_levels: Vec<String>,
+    pub second_factor_levels: Vec<String>,
+    pub estimate: f64,
+    pub standard_error: f64,
+    pub t_value: f64,
+    pub significance: f64,
+    pub confidence_interval: ConfidenceInterval,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PostHocTests {
+    pub factor_names: Vec<String>,
+    pub multiple_comparisons: Vec<MultipleComparisons>,
+    pub homogeneous_subsets: Vec<HomogeneousSubsets>,
+    pub note: Option<String>,
+    pub interpretation: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MultipleComparisons {
+    pub factor_name: String,
+    pub test_name: String,
+    pub entries: Vec<MultipleComparisonsEntry>,
+    pub note: Option<String>,
+    pub interpretation: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MultipleComparisonsEntry {
+    pub i_level: String,
+    pub j_level: String,
+    pub mean_difference: f64,
+    pub std_error: f64,
+    pub significance: f64,
+    pub confidence_interval: ConfidenceInterval,
+    pub is_significant: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct HomogeneousSubsets {
+    pub factor_name: String,
+    pub test_name: String,
+    pub groups: Vec<HomogeneousSubsetGroup>,
+    pub subset_significance: Vec<Option<f64>>,
+    pub error_mean_square: f64,
+    pub harmonic_mean_sample_size: f64,
+    pub alpha: f64,
+    pub note: Option<String>,
+    pub interpretation: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct HomogeneousSubsetGroup {
+    pub factor_value: String,
+    pub n: usize,
+    pub subsets: HashMap<usize, f64>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ParameterEstimates {
     pub estimates: Vec<ParameterEstimateEntry>,
@@ -222,6 +342,25 @@ pub struct ParameterEstimateEntry {
     pub is_redundant: bool,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RobustParameterEstimates {
+    pub hc_method: String,
+    pub estimates: Vec<RobustParameterEstimateEntry>,
+    pub note: Option<String>,
+    pub interpretation: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RobustParameterEstimateEntry {
+    pub parameter: String,
+    pub b: f64,
+    pub robust_std_error: f64,
+    pub t_value: f64,
+    pub significance: f64,
+    pub confidence_interval: ConfidenceInterval,
+    pub is_redundant: bool,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ConfidenceInterval {
     pub lower_bound: f64,
@@ -341,6 +480,26 @@ pub struct ContrastTestResultEntry {
     pub observed_power: f64,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CustomHypothesisTests {
+    pub tests: Vec<CustomHypothesisResult>,
+    pub note: Option<String>,
+    pub interpretation: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CustomHypothesisResult {
+    pub label: String,
+    pub parameter_names: Vec<String>,
+    pub contrast_names: Vec<String>,
+    pub l_matrix: Vec<Vec<f64>>,
+    pub estimable: bool,
+    pub contrast_result: Vec<ContrastResultEntry>,
+    pub test_result: Vec<ContrastTestResultEntry>,
+    pub note: Option<String>,
+    pub interpretation: Option<String>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct LackOfFitTests {
     pub lack_of_fit: LackOfFitTestsEntries,
@@ -426,3 +585,42 @@ pub struct SavedVariables {
     pub cook_distances: Vec<f64>,
     pub leverages: Vec<f64>,
 }
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CaseDiagnostics {
+    pub parameter_names: Vec<String>,
+    pub thresholds: CaseDiagnosticThresholds,
+    pub entries: Vec<CaseDiagnosticEntry>,
+    pub flagged_cases: Vec<usize>,
+    pub note: Option<String>,
+    pub interpretation: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CaseDiagnosticThresholds {
+    pub residual: f64,
+    pub leverage: f64,
+    pub cook_distance: f64,
+    pub standardized_dffit: f64,
+    pub standardized_dfbeta: f64,
+    pub covariance_ratio: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CaseDiagnosticEntry {
+    pub case_number: usize,
+    pub included: bool,
+    pub predicted_value: Option<f64>,
+    pub residual: Option<f64>,
+    pub standardized_residual: Option<f64>,
+    pub studentized_residual: Option<f64>,
+    pub deleted_studentized_residual: Option<f64>,
+    pub leverage: Option<f64>,
+    pub cook_distance: Option<f64>,
+    pub dffit: Option<f64>,
+    pub standardized_dffit: Option<f64>,
+    pub covariance_ratio: Option<f64>,
+    pub dfbeta: Vec<f64>,
+    pub standardized_dfbeta: Vec<f64>,
+    pub flags: Vec<String>,
+}
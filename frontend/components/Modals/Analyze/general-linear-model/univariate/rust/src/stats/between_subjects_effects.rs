@@ -53,7 +53,7 @@ pub fn calculate_tests_between_subjects_effects(
             SumOfSquaresMethod::TypeI => {
                 calculate_type_i_ss(
                     &design_info,
-                    &term_name,
+                    term_name,
                     all_model_terms_in_design,
                     &swept_info.beta_hat,
                     &swept_info.g_inv,
@@ -63,7 +63,7 @@ pub fn calculate_tests_between_subjects_effects(
             SumOfSquaresMethod::TypeII => {
                 calculate_type_ii_ss(
                     &design_info,
-                    &term_name,
+                    term_name,
                     all_model_terms_in_design,
                     &swept_info.beta_hat,
                     &swept_info.g_inv
@@ -72,7 +72,7 @@ pub fn calculate_tests_between_subjects_effects(
             SumOfSquaresMethod::TypeIII => {
                 calculate_type_iii_ss(
                     &design_info,
-                    &term_name,
+                    term_name,
                     all_model_terms_in_design,
                     &swept_info.beta_hat,
                     &swept_info.g_inv,
@@ -83,7 +83,7 @@ pub fn calculate_tests_between_subjects_effects(
             SumOfSquaresMethod::TypeIV => {
                 calculate_type_iv_ss(
                     &design_info,
-                    &term_name,
+                    term_name,
                     all_model_terms_in_design,
                     &swept_info.beta_hat,
                     &swept_info.g_inv,
@@ -113,19 +113,54 @@ pub fn calculate_tests_between_subjects_effects(
         final_sources.push(SourceEntry { name: term_name.clone(), effect: effect_entry });
     }
 
+    let mut error_terms = None;
+    let mut expected_mean_squares = None;
+    if !get_random_terms(&design_info, config).is_empty() {
+        let term_tests: Vec<(String, f64, usize)> = final_sources
+            .iter()
+            .map(|s| (s.name.clone(), s.effect.sum_of_squares, s.effect.df))
+            .collect();
+        let (ems, terms) = calculate_expected_mean_squares(
+            &design_info,
+            &swept_info,
+            &ztwz_matrix,
+            data,
+            config,
+            &term_tests,
+            (ms_error, df_error)
+        )?;
+
+        // Efek diuji terhadap error term sintetisnya masing-masing.
+        for source in final_sources.iter_mut() {
+            if let Some(error_term) = terms.iter().find(|t| t.source == source.name) {
+                source.effect = create_effect_entry_with_error_term(
+                    source.effect.sum_of_squares,
+                    source.effect.df,
+                    error_term,
+                    config.options.sig_level,
+                    config.options.est_effect_size,
+                    config.options.obs_power
+                );
+            }
+        }
+
+        expected_mean_squares = Some(ems);
+        error_terms = Some(terms);
+    }
+
     add_model_summary_entries(
         &mut final_sources,
-        ss_model_corrected,
-        df_model_overall,
-        ss_error,
-        df_error,
-        ms_error,
-        ss_total_corrected,
-        df_total,
+        (ss_model_corrected, df_model_overall),
+        (ss_error, df_error, ms_error),
+        (ss_total_corrected, df_total),
         &design_info,
         config
     );
 
+    if error_terms.is_some() {
+        final_sources.retain(|s| s.name != "Corrected Model");
+    }
+
     // Mengurutkan hasil
     final_sources.sort_by_key(|s| {
         match s.name.as_str() {
@@ -147,9 +182,16 @@ pub fn calculate_tests_between_subjects_effects(
     notes.push(format!("c. Sum of Squares Method: {:?}.", config.model.sum_of_square_method));
     notes.push(format!("d. R Squared = {:.4}.", current_r_squared));
     notes.push(format!("e. Adjusted R Squared = {:.4}.", current_adj_r_squared));
+    if let Some(terms) = &error_terms {
+        for term in terms {
+            notes.push(format!("Error term for {}: {}", term.source, term.composition));
+        }
+    }
 
     Ok(TestsBetweenSubjectsEffects {
         sources: final_sources,
+        error_terms,
+        expected_mean_squares,
         note: Some(notes.join("\n")),
         interpretation: Some(
             "This table tests the hypothesis that each effect (e.g., factor or interaction) in the model is null. A significant F-value (Sig. < .05) suggests that the effect significantly contributes to explaining the variance in the dependent variable. The Partial Eta Squared indicates the proportion of variance uniquely explained by that effect.".to_string()
@@ -167,7 +209,7 @@ fn calculate_error_terms(
 
     // Validasi
     if
-        current_df_error <= 0 &&
+        current_df_error == 0 &&
         !(design_info.n_samples == design_info.r_x_rank && design_info.n_samples > 0)
     {
         return Err(format!("Error degrees of freedom ({}) is not positive.", current_df_error));
@@ -203,7 +245,7 @@ fn calculate_r_squared_metrics(
     df_model_overall: usize
 ) -> (f64, f64) {
     let current_r_squared = if ss_total_corrected.abs() > 1e-9 {
-        (ss_model_corrected / ss_total_corrected).max(0.0).min(1.0)
+        (ss_model_corrected / ss_total_corrected).clamp(0.0, 1.0)
     } else {
         0.0
     };
@@ -220,16 +262,15 @@ fn calculate_r_squared_metrics(
 
 fn add_model_summary_entries(
     final_sources: &mut Vec<SourceEntry>,
-    ss_model_corrected: f64,
-    df_model_overall: usize,
-    ss_error: f64,
-    df_error: usize,
-    ms_error: f64,
-    ss_total_corrected: f64,
-    df_total: usize,
+    model_terms: (f64, usize),
+    error_terms: (f64, usize, f64),
+    total_terms: (f64, usize),
     design_info: &DesignMatrixInfo,
     config: &UnivariateConfig
 ) {
+    let (ss_model_corrected, df_model_overall) = model_terms;
+    let (ss_error, df_error, ms_error) = error_terms;
+    let (ss_total_corrected, df_total) = total_terms;
     let has_intercept = config.model.intercept;
 
     if has_intercept {
@@ -250,7 +291,7 @@ fn add_model_summary_entries(
 
             let pes_model_corrected = if config.options.est_effect_size {
                 if ss_total_corrected.abs() > 1e-9 {
-                    (ss_model_corrected / ss_total_corrected).max(0.0).min(1.0)
+                    (ss_model_corrected / ss_total_corrected).clamp(0.0, 1.0)
                 } else {
                     0.0
                 }
@@ -352,7 +393,7 @@ fn add_model_summary_entries(
             let f_model = if ms_error > 1e-9 { ms_model / ms_error } else { f64::NAN };
             let sig_model = calculate_f_significance(df_model, df_error, f_model);
             let pes_model = if config.options.est_effect_size {
-                if ss_total.abs() > 1e-9 { (ss_model / ss_total).max(0.0).min(1.0) } else { 0.0 }
+                if ss_total.abs() > 1e-9 { (ss_model / ss_total).clamp(0.0, 1.0) } else { 0.0 }
             } else {
                 f64::NAN
             };
@@ -437,7 +478,7 @@ pub fn create_effect_entry(
         if sum_of_squares >= 0.0 && error_df > 0 {
             let error_ss = error_ms * (error_df as f64);
             let eta_sq = sum_of_squares / (sum_of_squares + error_ss);
-            eta_sq.max(0.0).min(1.0)
+            eta_sq.clamp(0.0, 1.0)
         } else {
             0.0
         }
@@ -18,6 +18,17 @@ use nalgebra::{ DMatrix, DVector };
 
 use super::core::*;
 
+// Hasil fit model yang dipakai bersama oleh semua kontras faktor
+struct ContrastFit<'a> {
+    beta_hat: &'a DVector<f64>,
+    g_inv: &'a DMatrix<f64>,
+    mse: f64,
+    df_error: f64,
+}
+
+// Baris matriks L beserta deskripsi dan labelnya
+type LMatrixWithDescriptions = (Vec<Vec<f64>>, Vec<String>, Vec<String>);
+
 pub fn calculate_contrast_coefficients(
     data: &AnalysisData,
     config: &UnivariateConfig
@@ -36,6 +47,7 @@ pub fn calculate_contrast_coefficients(
     let df_error = (design_info.n_samples as i64) - (design_info.r_x_rank as i64);
     let mse = if df_error > 0 { s_rss / (df_error as f64) } else { f64::NAN };
     let sig_level_config = config.options.sig_level;
+    let fit = ContrastFit { beta_hat, g_inv, mse, df_error: df_error as f64 };
 
     let mut all_factors_in_model_with_their_levels: HashMap<String, Vec<String>> = HashMap::new();
     let mut unique_factors_in_model: HashSet<String> = HashSet::new();
@@ -48,7 +60,7 @@ pub fn calculate_contrast_coefficients(
             let f_name = f_name_part.trim().to_string();
             let is_covariate = config.main.covar
                 .as_ref()
-                .map_or(false, |covars| covars.contains(&f_name));
+                .is_some_and(|covars| covars.contains(&f_name));
             if !is_covariate {
                 unique_factors_in_model.insert(f_name);
             }
@@ -86,29 +98,23 @@ pub fn calculate_contrast_coefficients(
             continue;
         }
 
-        let cce: ContrastCoefficientsEntry;
-
-        if config.options.coefficient_matrix {
+        let cce = if config.options.coefficient_matrix {
             let cce_note_ref_part = if parsed_spec.ref_setting != "N/A" {
                 format!(", Ref: {}.", parsed_spec.ref_setting)
             } else {
-                if parsed_spec.method == ContrastMethod::None {
-                    ".".to_string()
-                } else {
-                    ".".to_string()
-                }
+                ".".to_string()
             };
 
-            let cce_notes = vec![
+            let cce_notes = [
                 format!(
                     "L-Matrix for factor spec: \"{}\". Method: {:?}, Ref: {}.",
                     spec_str,
                     parsed_spec.method,
                     cce_note_ref_part
-                )
+                ),
             ];
 
-            cce = ContrastCoefficientsEntry {
+            ContrastCoefficientsEntry {
                 parameter: all_model_parameters_names.clone(),
                 l_label: l_labels,
                 l_matrix: l_matrix.clone(),
@@ -117,39 +123,31 @@ pub fn calculate_contrast_coefficients(
                 interpretation: Some(
                     "This table provides the contrast coefficients (L' Matrix) for the specified factor. Each row represents a contrast, and each column represents a model parameter. The coefficients indicate the contribution of each parameter to the contrast.".to_string()
                 ),
-            };
+            }
         } else {
-            cce = ContrastCoefficientsEntry {
+            ContrastCoefficientsEntry {
                 parameter: Vec::new(),
                 l_label: Vec::new(),
                 l_matrix: Vec::new(),
                 contrast_information: Vec::new(),
                 note: None,
                 interpretation: None,
-            };
-        }
+            }
+        };
 
         let contrast_result_struct = create_contrast_result(
             &cce_row_descriptions,
             spec_str,
             &l_matrix,
-            beta_hat,
-            g_inv,
-            mse,
-            df_error as f64,
+            &fit,
             sig_level_config
         );
 
         let contrast_test_struct = create_contrast_test_result(
             spec_str,
             &l_matrix,
-            beta_hat,
-            g_inv,
-            mse,
-            df_error as f64,
-            config.options.sig_level,
-            config.options.est_effect_size,
-            config.options.obs_power
+            &fit,
+            config
         );
 
         let method_name_str = match parsed_spec.method {
@@ -297,11 +295,11 @@ fn parse_contrast_factor_spec(factor_spec_str: &str) -> Result<ParsedFactorSpec,
 
 fn generate_l_matrix_and_descriptions(
     parsed_spec: &ParsedFactorSpec,
-    levels_of_contrasted_factor: &Vec<String>,
-    all_model_parameters_names: &Vec<String>,
+    levels_of_contrasted_factor: &[String],
+    all_model_parameters_names: &[String],
     p_total_model_params: usize,
     all_factors_in_model_with_their_levels: &HashMap<String, Vec<String>>
-) -> Result<(Vec<Vec<f64>>, Vec<String>, Vec<String>), String> {
+) -> Result<LMatrixWithDescriptions, String> {
     let level_count_of_contrasted_factor = levels_of_contrasted_factor.len();
     let use_first_as_ref = parsed_spec.use_first_as_ref;
     let factor_to_contrast_name = &parsed_spec.factor_name;
@@ -569,19 +567,17 @@ fn generate_l_matrix_and_descriptions(
 }
 
 fn create_contrast_result(
-    k_matrix_row_descriptions: &Vec<String>,
+    k_matrix_row_descriptions: &[String],
     factor_spec_str: &str,
-    l_matrix_factor: &Vec<Vec<f64>>,
-    beta_hat: &DVector<f64>,
-    g_inv: &DMatrix<f64>,
-    mse: f64,
-    df_error: f64,
+    l_matrix_factor: &[Vec<f64>],
+    fit: &ContrastFit,
     sig_level: f64
 ) -> ContrastResult {
+    let (beta_hat, g_inv, mse, df_error) = (fit.beta_hat, fit.g_inv, fit.mse, fit.df_error);
     let num_contrasts_for_factor = l_matrix_factor.len();
     if num_contrasts_for_factor == 0 {
         return ContrastResult {
-            parameter: k_matrix_row_descriptions.clone(),
+            parameter: k_matrix_row_descriptions.to_vec(),
             contrast_result: Vec::new(),
             note: Some(format!("No contrasts to calculate for: {}", factor_spec_str)),
             interpretation: None,
@@ -591,7 +587,7 @@ fn create_contrast_result(
     let p_model_params = beta_hat.nrows();
     if p_model_params == 0 {
         return ContrastResult {
-            parameter: k_matrix_row_descriptions.clone(),
+            parameter: k_matrix_row_descriptions.to_vec(),
             contrast_result: vec![ContrastResultEntry {
                 contrast_estimate: f64::NAN,
                 hypothesized_value: 0.0,
@@ -685,7 +681,7 @@ fn create_contrast_result(
     }
 
     ContrastResult {
-        parameter: k_matrix_row_descriptions.clone(),
+        parameter: k_matrix_row_descriptions.to_vec(),
         contrast_result: result_entries,
         note: Some(format!("Calculated results for factor spec: {}", factor_spec_str)),
         interpretation: Some(
@@ -696,15 +692,14 @@ fn create_contrast_result(
 
 fn create_contrast_test_result(
     factor_spec_str: &str,
-    l_matrix_factor: &Vec<Vec<f64>>,
-    beta_hat: &DVector<f64>,
-    g_inv: &DMatrix<f64>,
-    mse: f64,
-    df_error: f64,
-    sig_level: f64,
-    est_effect_size: bool,
-    obs_power: bool
+    l_matrix_factor: &[Vec<f64>],
+    fit: &ContrastFit,
+    config: &UnivariateConfig
 ) -> ContrastTestResult {
+    let (beta_hat, g_inv, mse, df_error) = (fit.beta_hat, fit.g_inv, fit.mse, fit.df_error);
+    let sig_level = config.options.sig_level;
+    let est_effect_size = config.options.est_effect_size;
+    let obs_power = config.options.obs_power;
     let num_contrasts_for_factor = l_matrix_factor.len();
     let df_hypothesis = num_contrasts_for_factor;
     let df_error_usize = if df_error >= 0.0 { df_error as usize } else { 0 };
@@ -867,7 +862,7 @@ fn create_contrast_test_result(
         let pes = if est_effect_size && !ssh.is_nan() && !ss_error.is_nan() {
             let den = ssh + ss_error;
             if den > 1e-9 {
-                (ssh / den).max(0.0).min(1.0)
+                (ssh / den).clamp(0.0, 1.0)
             } else {
                 0.0
             }
@@ -947,9 +942,7 @@ pub fn generate_polynomial_contrast(level_count: usize) -> Vec<f64> {
         .map(|i| (i as f64) - ((level_count - 1) as f64) / 2.0)
         .collect();
 
-    for i in 0..level_count {
-        contrasts[i] = x_values[i];
-    }
+    contrasts.copy_from_slice(&x_values);
 
     let sum_sq: f64 = contrasts
         .iter()
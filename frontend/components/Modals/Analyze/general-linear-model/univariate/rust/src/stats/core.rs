@@ -3,17 +3,20 @@ pub use crate::stats::between_subjects_effects::*;
 pub use crate::stats::common::*;
 pub use crate::stats::contrast_coefficient::*;
 pub use crate::stats::contrast_factors::*;
+pub use crate::stats::custom_hypothesis::*;
 pub use crate::stats::descriptive_statistics::*;
 pub use crate::stats::distribution_utils::*;
 pub use crate::stats::design_matrix::*;
 pub use crate::stats::emmeans::*;
 pub use crate::stats::estimable_function::*;
+pub use crate::stats::expected_mean_squares::*;
 pub use crate::stats::factor_utils::*;
 pub use crate::stats::heteroscedasticity::*;
 pub use crate::stats::hypothesis_matrix::*;
 pub use crate::stats::lack_of_fit::*;
 pub use crate::stats::levene_test::*;
 pub use crate::stats::parameter_estimates::*;
+pub use crate::stats::posthoc::*;
 pub use crate::stats::save::*;
 pub use crate::stats::sum_of_squares::*;
 pub use crate::stats::summary_processing::*;
@@ -0,0 +1,309 @@
+use nalgebra::{ DMatrix, DVector };
+use crate::models::{
+    config::{ CustomHypothesisSpec, UnivariateConfig },
+    data::AnalysisData,
+    result::{
+        ConfidenceInterval,
+        ContrastResultEntry,
+        ContrastTestResultEntry,
+        CustomHypothesisResult,
+        CustomHypothesisTests,
+    },
+};
+
+use super::core::*;
+
+// Hasil fit model yang dipakai bersama oleh semua hipotesis kustom
+struct UnivariateFit<'a> {
+    beta_hat: &'a DVector<f64>,
+    g_inv: &'a DMatrix<f64>,
+    mse: f64,
+    df_error: usize,
+}
+
+pub fn calculate_custom_hypothesis_tests(
+    data: &AnalysisData,
+    config: &UnivariateConfig
+) -> Result<CustomHypothesisTests, String> {
+    if config.custom.tests.is_empty() {
+        return Err("No custom hypothesis tests specified".to_string());
+    }
+
+    let design_info = create_design_response_weights(data, config)?;
+    let all_param_names = generate_all_row_parameter_names_sorted(&design_info, data)?;
+    let ztwz_matrix = create_cross_product_matrix(&design_info)?;
+    let swept_info = perform_sweep_and_extract_results(&ztwz_matrix, design_info.p_parameters)?;
+
+    let p = design_info.p_parameters;
+    let df_error = (design_info.n_samples as i64) - (design_info.r_x_rank as i64);
+    let fit = UnivariateFit {
+        beta_hat: &swept_info.beta_hat,
+        g_inv: &swept_info.g_inv,
+        mse: if df_error > 0 { swept_info.s_rss / (df_error as f64) } else { f64::NAN },
+        df_error: if df_error > 0 { df_error as usize } else { 0 },
+    };
+
+    // H = G * X'WX, baris L estimable jika L = L * H
+    let xtwx = ztwz_matrix.view((0, 0), (p, p)).into_owned();
+    let h_matrix = fit.g_inv * &xtwx;
+
+    let mut tests = Vec::with_capacity(config.custom.tests.len());
+    for (test_idx, spec) in config.custom.tests.iter().enumerate() {
+        let label = spec.label
+            .as_ref()
+            .filter(|l| !l.trim().is_empty())
+            .cloned()
+            .unwrap_or_else(|| format!("Custom Hypothesis {}", test_idx + 1));
+
+        let (l_matrix, k_vector) = build_custom_l_and_k(spec, &all_param_names, &label)?;
+        let estimable = is_l_matrix_estimable(&l_matrix, &h_matrix);
+
+        tests.push(
+            evaluate_custom_hypothesis(
+                &fit,
+                label,
+                &all_param_names,
+                &l_matrix,
+                &k_vector,
+                estimable,
+                config
+            )
+        );
+    }
+
+    Ok(CustomHypothesisTests {
+        tests,
+        note: Some(
+            "Each custom hypothesis tests LB = K, where the rows of L are defined by parameter name and K defaults to zero.".to_string()
+        ),
+        interpretation: Some(
+            "These tables evaluate user-defined linear combinations of the model parameters. The contrast results give each row's estimate, its difference from the hypothesized value and a t-test, while the test result gives a joint F-test for all rows of the hypothesis. Non-estimable hypotheses depend on the chosen parameterization and are not tested.".to_string()
+        ),
+    })
+}
+
+fn build_custom_l_and_k(
+    spec: &CustomHypothesisSpec,
+    all_param_names: &[String],
+    label: &str
+) -> Result<(DMatrix<f64>, DVector<f64>), String> {
+    if spec.l_matrix.is_empty() {
+        return Err(format!("Custom hypothesis '{}' has no L matrix rows", label));
+    }
+
+    let num_rows = spec.l_matrix.len();
+    let mut l_matrix = DMatrix::zeros(num_rows, all_param_names.len());
+
+    for (row_idx, row) in spec.l_matrix.iter().enumerate() {
+        for (param_name, coefficient) in row {
+            let col_idx = all_param_names
+                .iter()
+                .position(|name| name == param_name.trim())
+                .ok_or_else(||
+                    format!(
+                        "Custom hypothesis '{}' refers to unknown parameter '{}'. Available parameters: {}",
+                        label,
+                        param_name,
+                        all_param_names.join(", ")
+                    )
+                )?;
+            l_matrix[(row_idx, col_idx)] = *coefficient;
+        }
+    }
+
+    let k_vector = match &spec.k_matrix {
+        Some(k_values) => {
+            if k_values.len() != num_rows {
+                return Err(
+                    format!(
+                        "Custom hypothesis '{}' has {} L rows but {} K values",
+                        label,
+                        num_rows,
+                        k_values.len()
+                    )
+                );
+            }
+            DVector::from_vec(k_values.clone())
+        }
+        None => DVector::zeros(num_rows),
+    };
+
+    Ok((l_matrix, k_vector))
+}
+
+fn is_l_matrix_estimable(l_matrix: &DMatrix<f64>, h_matrix: &DMatrix<f64>) -> bool {
+    let difference = l_matrix - l_matrix * h_matrix;
+    let scale = l_matrix.amax().max(1.0);
+    difference.amax() <= 1e-8 * scale
+}
+
+fn evaluate_custom_hypothesis(
+    fit: &UnivariateFit,
+    label: String,
+    all_param_names: &[String],
+    l_matrix: &DMatrix<f64>,
+    k_vector: &DVector<f64>,
+    estimable: bool,
+    config: &UnivariateConfig
+) -> CustomHypothesisResult {
+    let (beta_hat, g_inv, mse, df_error) = (fit.beta_hat, fit.g_inv, fit.mse, fit.df_error);
+    let num_rows = l_matrix.nrows();
+    let sig_level = config.options.sig_level;
+    let contrast_names: Vec<String> = (1..=num_rows).map(|i| format!("L{}", i)).collect();
+    let l_rows: Vec<Vec<f64>> = l_matrix
+        .row_iter()
+        .map(|row| row.iter().cloned().collect())
+        .collect();
+
+    let ss_error = if !mse.is_nan() && df_error > 0 { mse * (df_error as f64) } else { f64::NAN };
+    let error_entry = ContrastTestResultEntry {
+        source: "Error".to_string(),
+        sum_of_squares: ss_error,
+        df: df_error,
+        mean_square: mse,
+        f_value: f64::NAN,
+        significance: f64::NAN,
+        partial_eta_squared: f64::NAN,
+        noncent_parameter: f64::NAN,
+        observed_power: f64::NAN,
+    };
+
+    if !estimable {
+        return CustomHypothesisResult {
+            label,
+            parameter_names: all_param_names.to_vec(),
+            contrast_names,
+            l_matrix: l_rows,
+            estimable,
+            contrast_result: Vec::new(),
+            test_result: vec![error_entry],
+            note: Some(
+                "The L matrix is not estimable under the current model; the hypothesis was not tested.".to_string()
+            ),
+            interpretation: None,
+        };
+    }
+
+    let l_beta = l_matrix * beta_hat;
+    let differences = &l_beta - k_vector;
+    let l_ginv_lt = l_matrix * g_inv * l_matrix.transpose();
+
+    let t_critical = if df_error > 0 {
+        calculate_t_critical(Some(sig_level), df_error)
+    } else {
+        f64::NAN
+    };
+
+    let contrast_result: Vec<ContrastResultEntry> = (0..num_rows)
+        .map(|i| {
+            let variance = l_ginv_lt[(i, i)] * mse;
+            let standard_error = if variance >= 0.0 { variance.sqrt() } else { f64::NAN };
+            let t_value = if standard_error.is_nan() || standard_error == 0.0 {
+                f64::NAN
+            } else {
+                differences[i] / standard_error
+            };
+            let significance = if t_value.is_nan() || df_error == 0 {
+                f64::NAN
+            } else {
+                calculate_t_significance(t_value.abs(), df_error)
+            };
+            let (lower_bound, upper_bound) = if t_critical.is_nan() || standard_error.is_nan() {
+                (f64::NAN, f64::NAN)
+            } else {
+                (differences[i] - t_critical * standard_error, differences[i] + t_critical * standard_error)
+            };
+
+            ContrastResultEntry {
+                contrast_estimate: l_beta[i],
+                hypothesized_value: k_vector[i],
+                difference: differences[i],
+                standard_error,
+                significance,
+                confidence_interval: ConfidenceInterval {
+                    lower_bound,
+                    upper_bound,
+                },
+            }
+        })
+        .collect();
+
+    // Uji F gabungan: (LB - K)' (LGL')^- (LB - K) / rank
+    let df_hypothesis = l_ginv_lt.rank(1e-8);
+    let mut notes = Vec::new();
+    let ss_hypothesis = if df_hypothesis == 0 {
+        notes.push("L*G*L' has rank zero; the joint test is undefined.".to_string());
+        f64::NAN
+    } else {
+        match l_ginv_lt.clone().svd(true, true).pseudo_inverse(1e-12) {
+            Ok(l_ginv_lt_inv) => (differences.transpose() * l_ginv_lt_inv * &differences)[(0, 0)].max(0.0),
+            Err(e) => {
+                notes.push(format!("Pseudo-inverse of L*G*L' failed: {}", e));
+                f64::NAN
+            }
+        }
+    };
+    if df_hypothesis > 0 && df_hypothesis < num_rows {
+        notes.push(
+            format!(
+                "The L matrix has {} rows but rank {}; the joint test uses {} degrees of freedom.",
+                num_rows,
+                df_hypothesis,
+                df_hypothesis
+            )
+        );
+    }
+
+    let mean_square = if df_hypothesis > 0 { ss_hypothesis / (df_hypothesis as f64) } else { f64::NAN };
+    let f_value = if !mse.is_nan() && mse > 0.0 { mean_square / mse } else { f64::NAN };
+    let significance = if f_value.is_nan() || df_error == 0 {
+        f64::NAN
+    } else {
+        calculate_f_significance(df_hypothesis, df_error, f_value)
+    };
+
+    let partial_eta_squared = if config.options.est_effect_size && !ss_hypothesis.is_nan() && !ss_error.is_nan() {
+        let den = ss_hypothesis + ss_error;
+        if den > 1e-9 { (ss_hypothesis / den).clamp(0.0, 1.0) } else { 0.0 }
+    } else {
+        f64::NAN
+    };
+    let (noncent_parameter, observed_power) = if
+        config.options.obs_power &&
+        !f_value.is_nan() &&
+        f_value > 0.0
+    {
+        (
+            f_value * (df_hypothesis as f64),
+            calculate_observed_power_f(f_value, df_hypothesis as f64, df_error as f64, sig_level),
+        )
+    } else {
+        (f64::NAN, f64::NAN)
+    };
+
+    let hypothesis_entry = ContrastTestResultEntry {
+        source: "Contrast".to_string(),
+        sum_of_squares: ss_hypothesis,
+        df: df_hypothesis,
+        mean_square,
+        f_value,
+        significance,
+        partial_eta_squared,
+        noncent_parameter,
+        observed_power,
+    };
+
+    CustomHypothesisResult {
+        label,
+        parameter_names: all_param_names.to_vec(),
+        contrast_names,
+        l_matrix: l_rows,
+        estimable,
+        contrast_result,
+        test_result: vec![hypothesis_entry, error_entry],
+        note: if notes.is_empty() { None } else { Some(notes.join(" ")) },
+        interpretation: Some(
+            "A significant F-test indicates that at least one linear combination in L differs from its hypothesized value in K.".to_string()
+        ),
+    }
+}
@@ -22,7 +22,7 @@ pub fn calculate_descriptive_statistics(
         all_factors.extend(rand_factors.clone());
     }
 
-    let n_obs = data.dependent_data.get(0).map_or(0, |d| d.len());
+    let n_obs = data.dependent_data.first().map_or(0, |d| d.len());
 
     let mut factor_locations: HashMap<String, (bool, usize)> = HashMap::new();
     for factor_name in &all_factors {
@@ -181,8 +181,8 @@ fn build_groups_recursive(
         current_path.push((factor_name.clone(), value.clone()));
 
         let mut key_map = current_path.iter().cloned().collect::<HashMap<_, _>>();
-        for i in level + 1..factor_names.len() {
-            key_map.insert(factor_names[i].clone(), "Total".to_string());
+        for other_factor in &factor_names[level + 1..] {
+            key_map.insert(other_factor.clone(), "Total".to_string());
         }
 
         // Buat key unik
@@ -117,7 +117,7 @@ pub fn create_design_response_weights(
     // Cache covariate and factor columns
     for term_component in &all_terms_for_cache {
         if
-            config.main.covar.as_ref().map_or(false, |c| c.contains(term_component)) &&
+            config.main.covar.as_ref().is_some_and(|c| c.contains(term_component)) &&
             !covariate_cols_cache.contains_key(term_component)
         {
             let mut cov_values_filtered = Vec::with_capacity(case_indices_to_keep.len());
@@ -178,7 +178,7 @@ pub fn create_design_response_weights(
             } else {
                 continue;
             }
-        } else if config.main.covar.as_ref().map_or(false, |c| c.contains(term_name)) {
+        } else if config.main.covar.as_ref().is_some_and(|c| c.contains(term_name)) {
             if let Some(col) = covariate_cols_cache.get(term_name) {
                 if !col.is_empty() {
                     term_matrix_cols.push(col.clone());
@@ -247,11 +247,11 @@ pub fn create_design_response_weights(
             let term_end_col = current_col_idx + term_matrix_cols.len() - 1;
             let added_indices: Vec<usize> = (current_col_idx..=term_end_col).collect();
 
-            if config.main.fix_factor.as_ref().map_or(false, |f| f.contains(term_name)) {
+            if config.main.fix_factor.as_ref().is_some_and(|f| f.contains(term_name)) {
                 fixed_factor_indices.insert(term_name.clone(), added_indices.clone());
-            } else if config.main.rand_factor.as_ref().map_or(false, |r| r.contains(term_name)) {
+            } else if config.main.rand_factor.as_ref().is_some_and(|r| r.contains(term_name)) {
                 random_factor_indices.insert(term_name.clone(), added_indices.clone());
-            } else if config.main.covar.as_ref().map_or(false, |c| c.contains(term_name)) {
+            } else if config.main.covar.as_ref().is_some_and(|c| c.contains(term_name)) {
                 covariate_indices.insert(term_name.clone(), added_indices.clone());
             }
 
@@ -280,7 +280,7 @@ pub fn create_design_response_weights(
     if
         config.model.intercept &&
         x_nalgebra.ncols() > 0 &&
-        model_terms.get(0) == Some(&"Intercept".to_string())
+        model_terms.first() == Some(&"Intercept".to_string())
     {
         if let Some((start, end)) = term_column_map.get("Intercept") {
             if *start == 0 && *end == 0 {
@@ -341,9 +341,7 @@ pub fn perform_sweep_and_extract_results(
     p_params_in_model: usize
 ) -> Result<SweptMatrixInfo, String> {
     if p_params_in_model == 0 {
-        let s_rss = if ztwz_matrix.nrows() == 1 && ztwz_matrix.ncols() == 1 {
-            ztwz_matrix[(0, 0)]
-        } else if ztwz_matrix.nrows() > 0 && ztwz_matrix.ncols() > 0 {
+        let s_rss = if ztwz_matrix.nrows() > 0 && ztwz_matrix.ncols() > 0 {
             ztwz_matrix[(0, 0)]
         } else {
             0.0
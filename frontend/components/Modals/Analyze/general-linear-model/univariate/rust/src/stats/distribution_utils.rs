@@ -1,4 +1,5 @@
 use statrs::distribution::{ ChiSquared, FisherSnedecor, Normal, StudentsT, ContinuousCDF };
+use statrs::function::{ erf::erfc, gamma::ln_gamma };
 
 pub fn calculate_f_significance(df1: usize, df2: usize, f_value: f64) -> f64 {
     if df1 == 0 || df2 == 0 || f_value.is_nan() || f_value < 0.0 {
@@ -72,7 +73,7 @@ pub fn noncentral_f_cdf(f_prime: f64, df1: f64, df2: f64, lambda: f64) -> f64 {
     let df1_adjusted = (df1 + lambda).powi(2) / (df1 + 2.0 * lambda);
 
     match FisherSnedecor::new(df1_adjusted, df2) {
-        Ok(dist) => dist.cdf(f_adjusted).max(0.0).min(1.0),
+        Ok(dist) => dist.cdf(f_adjusted).clamp(0.0, 1.0),
         Err(_) => f64::NAN,
     }
 }
@@ -90,7 +91,7 @@ pub fn noncentral_t_cdf(t_prime: f64, nu: f64, delta: f64) -> f64 {
     match Normal::new(0.0, 1.0) {
         Ok(norm_dist) => {
             let cdf_val = norm_dist.cdf(x_approx);
-            cdf_val.max(0.0).min(1.0)
+            cdf_val.clamp(0.0, 1.0)
         }
         Err(_) => f64::NAN,
     }
@@ -125,9 +126,7 @@ pub fn calculate_observed_power_f(f_value: f64, df1: f64, df2: f64, alpha_for_te
     let cdf = noncentral_f_cdf(crit_f, df1, df2, ncp);
 
     // Power = 1 - CDF_noncentral(F_critical).
-    let power = if cdf.is_nan() { f64::NAN } else { (1.0 - cdf).max(0.0).min(1.0) };
-
-    power
+    if cdf.is_nan() { f64::NAN } else { (1.0 - cdf).clamp(0.0, 1.0) }
 }
 
 pub fn calculate_observed_power_t(t_value: f64, df: usize, alpha: Option<f64>) -> f64 {
@@ -165,19 +164,185 @@ pub fn calculate_observed_power_t(t_value: f64, df: usize, alpha: Option<f64>) -
     if power.is_nan() {
         return f64::NAN;
     }
-    power.max(0.0).min(1.0)
+    power.clamp(0.0, 1.0)
 }
 
 pub fn chi_square_cdf(x: f64, df: f64) -> f64 {
     if x < 0.0 || df <= 0.0 {
         return 0.0;
     }
-    ChiSquared::new(df).map_or(0.0, |dist| dist.cdf(x).max(0.0).min(1.0))
+    ChiSquared::new(df).map_or(0.0, |dist| dist.cdf(x).clamp(0.0, 1.0))
 }
 
 pub fn f_distribution_cdf(x: f64, df1: f64, df2: f64) -> f64 {
     if x < 0.0 || df1 <= 0.0 || df2 <= 0.0 {
         return 0.0;
     }
-    FisherSnedecor::new(df1, df2).map_or(0.0, |dist| dist.cdf(x).max(0.0).min(1.0))
+    FisherSnedecor::new(df1, df2).map_or(0.0, |dist| dist.cdf(x).clamp(0.0, 1.0))
+}
+
+// Titik dan bobot Gauss-Legendre 8 titik pada [-1, 1].
+const GAUSS_LEGENDRE_8: [(f64, f64); 8] = [
+    (-0.9602898564975363, 0.1012285362903763),
+    (-0.7966664774136267, 0.2223810344533745),
+    (-0.525532409916329, 0.3137066458778873),
+    (-0.1834346424956498, 0.362683783378362),
+    (0.1834346424956498, 0.362683783378362),
+    (0.525532409916329, 0.3137066458778873),
+    (0.7966664774136267, 0.2223810344533745),
+    (0.9602898564975363, 0.1012285362903763),
+];
+
+fn integrate_composite<F: Fn(f64) -> f64>(lower: f64, upper: f64, intervals: usize, f: F) -> f64 {
+    let width = (upper - lower) / (intervals as f64);
+    let mut total = 0.0;
+    for i in 0..intervals {
+        let mid = lower + ((i as f64) + 0.5) * width;
+        let half = width / 2.0;
+        for &(node, weight) in GAUSS_LEGENDRE_8.iter() {
+            total += weight * half * f(mid + half * node);
+        }
+    }
+    total
+}
+
+fn standard_normal_cdf(x: f64) -> f64 {
+    0.5 * erfc(-x / std::f64::consts::SQRT_2)
+}
+
+fn standard_normal_pdf(x: f64) -> f64 {
+    (-0.5 * x * x).exp() / (2.0 * std::f64::consts::PI).sqrt()
+}
+
+// Mengintegrasikan probabilitas bersyarat terhadap s = sqrt(chi^2_df / df),
+// yaitu faktor skala dari estimasi simpangan baku galat.
+fn integrate_over_error_scale<F: Fn(f64) -> f64>(df: f64, conditional: F) -> f64 {
+    if !df.is_finite() {
+        return conditional(1.0);
+    }
+
+    let half_df = df / 2.0;
+    let log_const = half_df * df.ln() - ln_gamma(half_df) - (half_df - 1.0) * (2.0_f64).ln();
+    let spread = 8.0 / df.sqrt();
+    let lower = (1.0 - spread).max(0.0);
+    let upper = 1.0 + spread;
+
+    integrate_composite(lower, upper, 16, |s| {
+        let density = (log_const + (df - 1.0) * s.ln() - (df * s * s) / 2.0).exp();
+        density * conditional(s)
+    })
+}
+
+fn invert_cdf<F: Fn(f64) -> f64>(probability: f64, cdf: F) -> f64 {
+    if probability <= 0.0 || probability >= 1.0 || probability.is_nan() {
+        return f64::NAN;
+    }
+
+    let mut low = 0.0;
+    let mut high = 1.0;
+    while cdf(high) < probability {
+        low = high;
+        high *= 2.0;
+        if high > 1.0e4 {
+            return f64::NAN;
+        }
+    }
+
+    for _ in 0..60 {
+        let mid = (low + high) / 2.0;
+        if cdf(mid) < probability {
+            low = mid;
+        } else {
+            high = mid;
+        }
+        if high - low < 1.0e-8 {
+            break;
+        }
+    }
+
+    (low + high) / 2.0
+}
+
+// CDF distribusi studentized range Q(k, df).
+// df = f64::INFINITY menghasilkan range dari k normal baku.
+pub fn studentized_range_cdf(q: f64, k: usize, df: f64) -> f64 {
+    if q.is_nan() || k < 2 || df <= 0.0 {
+        return f64::NAN;
+    }
+    if q <= 0.0 {
+        return 0.0;
+    }
+
+    let exponent = (k - 1) as i32;
+    let range_cdf = |w: f64| -> f64 {
+        let value = integrate_composite(-8.0, 8.0, 16, |z| {
+            let inner = (standard_normal_cdf(z) - standard_normal_cdf(z - w)).max(0.0);
+            standard_normal_pdf(z) * inner.powi(exponent)
+        });
+        ((k as f64) * value).min(1.0)
+    };
+
+    integrate_over_error_scale(df, |s| range_cdf(q * s))
+        .clamp(0.0, 1.0)
+}
+
+pub fn studentized_range_quantile(probability: f64, k: usize, df: f64) -> f64 {
+    invert_cdf(probability, |q| studentized_range_cdf(q, k, df))
+}
+
+// CDF distribusi studentized maximum modulus M(m, df), yaitu maksimum nilai mutlak
+// dari m variabel normal baku independen yang dibagi estimasi simpangan baku yang sama.
+pub fn studentized_maximum_modulus_cdf(x: f64, m: usize, df: f64) -> f64 {
+    if x.is_nan() || m == 0 || df <= 0.0 {
+        return f64::NAN;
+    }
+    if x <= 0.0 {
+        return 0.0;
+    }
+
+    integrate_over_error_scale(df, |s| {
+        (2.0 * standard_normal_cdf(x * s) - 1.0).max(0.0).powi(m as i32)
+    })
+        .clamp(0.0, 1.0)
+}
+
+pub fn studentized_maximum_modulus_quantile(probability: f64, m: usize, df: f64) -> f64 {
+    invert_cdf(probability, |x| studentized_maximum_modulus_cdf(x, m, df))
+}
+
+// CDF statistik Dunnett untuk perbandingan terhadap satu kontrol.
+// `lambdas[i] = sqrt(n_i / (n_i + n_kontrol))`, sehingga korelasi antar perbandingan
+// adalah lambda_i * lambda_j.
+pub fn dunnett_cdf(c: f64, lambdas: &[f64], df: f64, two_sided: bool) -> f64 {
+    if c.is_nan() || lambdas.is_empty() || df <= 0.0 {
+        return f64::NAN;
+    }
+    if two_sided && c <= 0.0 {
+        return 0.0;
+    }
+
+    let conditional = |bound: f64| -> f64 {
+        integrate_composite(-8.0, 8.0, 16, |z| {
+            let product: f64 = lambdas
+                .iter()
+                .map(|&lambda| {
+                    let residual_sd = (1.0 - lambda * lambda).max(1e-12).sqrt();
+                    let upper = standard_normal_cdf((bound - lambda * z) / residual_sd);
+                    if two_sided {
+                        (upper - standard_normal_cdf((-bound - lambda * z) / residual_sd)).max(0.0)
+                    } else {
+                        upper
+                    }
+                })
+                .product();
+            standard_normal_pdf(z) * product
+        })
+    };
+
+    integrate_over_error_scale(df, |s| conditional(c * s))
+        .clamp(0.0, 1.0)
+}
+
+pub fn dunnett_quantile(probability: f64, lambdas: &[f64], df: f64, two_sided: bool) -> f64 {
+    invert_cdf(probability, |c| dunnett_cdf(c, lambdas, df, two_sided))
 }
@@ -15,11 +15,45 @@ use crate::models::{
         ContrastCoefficientsEntry,
         ConfidenceInterval,
         DesignMatrixInfo,
+        InteractionContrastEntry,
+        InteractionContrasts,
+        SimpleEffectSlice,
+        SimpleEffects,
     },
 };
 
 use super::core::*;
 
+// Hasil fit model yang dipakai bersama oleh semua tabel EMMeans
+struct EmmeansFit<'a> {
+    beta_hat: &'a DVector<f64>,
+    g_inv: &'a DMatrix<f64>,
+    mse: f64,
+    df_error: i64,
+    s_rss: f64,
+}
+
+// Informasi model untuk membentuk vektor L EMMeans
+struct EmmeansModel<'a> {
+    all_model_parameters_names: &'a [String],
+    design_info: &'a DesignMatrixInfo,
+    factor_levels_map: &'a HashMap<String, Vec<String>>,
+    covariate_means_map: &'a HashMap<String, f64>,
+    covar_names: &'a [String],
+}
+
+// Tabel keluaran EMMeans untuk semua spesifikasi
+#[derive(Default)]
+struct EmmeansTables {
+    param_names: Vec<String>,
+    contrast_coefficients: Vec<ContrastCoefficientsEntry>,
+    em_estimates: Vec<EMMeansEstimates>,
+    pairwise_comparisons: Vec<PairwiseComparisons>,
+    univariate_tests: Vec<UnivariateTests>,
+    simple_effects: Vec<SimpleEffects>,
+    interaction_contrasts: Vec<InteractionContrasts>,
+}
+
 pub fn calculate_emmeans(
     data: &AnalysisData,
     config: &UnivariateConfig
@@ -33,6 +67,8 @@ pub fn calculate_emmeans(
                 em_estimates: Vec::new(),
                 pairwise_comparisons: None,
                 univariate_tests: None,
+                simple_effects: None,
+                interaction_contrasts: None,
             });
         }
     };
@@ -58,33 +94,41 @@ pub fn calculate_emmeans(
 
     let factor_levels_map = extract_factor_levels_from_design(&design_info, data, config)?;
 
-    let emmeans_results = process_emmeans_specifications(
-        &factors_to_analyze_for_emmeans,
-        &all_model_parameters_names,
-        &design_info,
-        &factor_levels_map,
-        &covariate_means_map,
-        beta_hat,
-        g_inv,
-        mse,
-        df_error,
-        s_rss,
-        config
-    )?;
+    let covar_names = config.main.covar.clone().unwrap_or_default();
+    let model = EmmeansModel {
+        all_model_parameters_names: &all_model_parameters_names,
+        design_info: &design_info,
+        factor_levels_map: &factor_levels_map,
+        covariate_means_map: &covariate_means_map,
+        covar_names: &covar_names,
+    };
+    let fit = EmmeansFit { beta_hat, g_inv, mse, df_error, s_rss };
+
+    let tables = process_emmeans_specifications(&factors_to_analyze_for_emmeans, &model, &fit, config)?;
 
     Ok(EMMeansResult {
-        parameter_names: emmeans_results.0,
-        contrast_coefficients: emmeans_results.1,
-        em_estimates: emmeans_results.2,
-        pairwise_comparisons: if emmeans_results.3.is_empty() {
+        parameter_names: tables.param_names,
+        contrast_coefficients: tables.contrast_coefficients,
+        em_estimates: tables.em_estimates,
+        pairwise_comparisons: if tables.pairwise_comparisons.is_empty() {
+            None
+        } else {
+            Some(tables.pairwise_comparisons)
+        },
+        univariate_tests: if tables.univariate_tests.is_empty() {
+            None
+        } else {
+            Some(tables.univariate_tests)
+        },
+        simple_effects: if tables.simple_effects.is_empty() {
             None
         } else {
-            Some(emmeans_results.3)
+            Some(tables.simple_effects)
         },
-        univariate_tests: if emmeans_results.4.is_empty() {
+        interaction_contrasts: if tables.interaction_contrasts.is_empty() {
             None
         } else {
-            Some(emmeans_results.4)
+            Some(tables.interaction_contrasts)
         },
     })
 }
@@ -93,10 +137,9 @@ fn add_empty_emmeans_result(
     factor_spec: &str,
     all_model_parameters_names: &[String],
     config: &UnivariateConfig,
-    contrast_coeffs_list: &mut Vec<ContrastCoefficientsEntry>,
-    estimates_list: &mut Vec<EMMeansEstimates>
+    tables: &mut EmmeansTables
 ) {
-    estimates_list.push(EMMeansEstimates {
+    tables.em_estimates.push(EMMeansEstimates {
         entries: Vec::new(),
         note: Some(format!("No level combinations for {}", factor_spec)),
         interpretation: Some(
@@ -105,7 +148,7 @@ fn add_empty_emmeans_result(
     });
 
     if config.options.coefficient_matrix {
-        contrast_coeffs_list.push(ContrastCoefficientsEntry {
+        tables.contrast_coefficients.push(ContrastCoefficientsEntry {
             parameter: all_model_parameters_names.to_vec(),
             l_label: Vec::new(),
             l_matrix: Vec::new(),
@@ -177,7 +220,7 @@ fn extract_factor_levels_from_design(
         .collect();
 
     for factor_name in all_factor_names {
-        if config.main.covar.as_ref().map_or(false, |c| c.contains(&factor_name)) {
+        if config.main.covar.as_ref().is_some_and(|c| c.contains(&factor_name)) {
             continue;
         }
 
@@ -196,115 +239,52 @@ fn extract_factor_levels_from_design(
 
 fn process_emmeans_specifications(
     factors_to_analyze: &[String],
-    all_model_parameters_names: &[String],
-    design_info: &DesignMatrixInfo,
-    factor_levels_map: &HashMap<String, Vec<String>>,
-    covariate_means_map: &HashMap<String, f64>,
-    beta_hat: &DVector<f64>,
-    g_inv: &DMatrix<f64>,
-    mse: f64,
-    df_error: i64,
-    s_rss: f64,
+    model: &EmmeansModel,
+    fit: &EmmeansFit,
     config: &UnivariateConfig
-) -> Result<
-    (
-        Vec<String>,
-        Vec<ContrastCoefficientsEntry>,
-        Vec<EMMeansEstimates>,
-        Vec<PairwiseComparisons>,
-        Vec<UnivariateTests>,
-    ),
-    String
-> {
-    let mut param_names = Vec::new();
-    let mut contrast_coeffs_list = Vec::new();
-    let mut estimates_list = Vec::new();
-    let mut pairwise_list = Vec::new();
-    let mut univariate_tests_list = Vec::new();
-
-    let covar_names = config.main.covar.as_ref().map_or_else(Vec::new, |v| v.clone());
+) -> Result<EmmeansTables, String> {
+    let mut tables = EmmeansTables::default();
 
     for factor_spec in factors_to_analyze {
-        param_names.push(factor_spec.clone());
+        tables.param_names.push(factor_spec.clone());
 
         if factor_spec == "(OVERALL)" {
-            process_grand_mean_emmeans(
-                all_model_parameters_names,
-                design_info,
-                factor_levels_map,
-                covariate_means_map,
-                &covar_names,
-                beta_hat,
-                g_inv,
-                mse,
-                df_error,
-                config,
-                &mut contrast_coeffs_list,
-                &mut estimates_list
-            )?;
+            process_grand_mean_emmeans(model, fit, config, &mut tables)?;
             continue;
         }
 
-        process_factor_emmeans(
-            factor_spec,
-            all_model_parameters_names,
-            design_info,
-            factor_levels_map,
-            covariate_means_map,
-            &covar_names,
-            beta_hat,
-            g_inv,
-            mse,
-            df_error,
-            s_rss,
-            config,
-            &mut contrast_coeffs_list,
-            &mut estimates_list,
-            &mut pairwise_list,
-            &mut univariate_tests_list
-        )?;
+        process_factor_emmeans(factor_spec, model, fit, config, &mut tables)?;
     }
 
-    Ok((param_names, contrast_coeffs_list, estimates_list, pairwise_list, univariate_tests_list))
+    Ok(tables)
 }
 
 fn process_grand_mean_emmeans(
-    all_model_parameters_names: &[String],
-    design_info: &DesignMatrixInfo,
-    factor_levels_map: &HashMap<String, Vec<String>>,
-    covariate_means_map: &HashMap<String, f64>,
-    covar_names: &[String],
-    beta_hat: &DVector<f64>,
-    g_inv: &DMatrix<f64>,
-    mse: f64,
-    df_error: i64,
+    model: &EmmeansModel,
+    fit: &EmmeansFit,
     config: &UnivariateConfig,
-    contrast_coeffs_list: &mut Vec<ContrastCoefficientsEntry>,
-    estimates_list: &mut Vec<EMMeansEstimates>
+    tables: &mut EmmeansTables
 ) -> Result<(), String> {
     let grand_mean_l_vector = generate_l_vector_for_grand_mean(
-        all_model_parameters_names,
-        design_info.p_parameters,
-        factor_levels_map,
-        covariate_means_map,
-        covar_names
+        model.all_model_parameters_names,
+        model.design_info.p_parameters,
+        model.factor_levels_map,
+        model.covariate_means_map,
+        model.covar_names
     )?;
 
     let grand_mean_estimates = generate_em_estimates_table(
-        &[grand_mean_l_vector.clone()],
+        std::slice::from_ref(&grand_mean_l_vector),
         &[BTreeMap::new()],
-        beta_hat,
-        g_inv,
-        mse,
-        df_error,
+        fit,
         config.options.sig_level,
         "Grand Mean"
     );
-    estimates_list.push(grand_mean_estimates);
+    tables.em_estimates.push(grand_mean_estimates);
 
     if config.options.coefficient_matrix {
-        contrast_coeffs_list.push(ContrastCoefficientsEntry {
-            parameter: all_model_parameters_names.to_vec(),
+        tables.contrast_coefficients.push(ContrastCoefficientsEntry {
+            parameter: model.all_model_parameters_names.to_vec(),
             l_label: vec!["Grand Mean".to_string()],
             l_matrix: vec![grand_mean_l_vector],
             contrast_information: vec!["L-Matrix for Grand Mean".to_string()],
@@ -320,22 +300,13 @@ fn process_grand_mean_emmeans(
 
 fn process_factor_emmeans(
     factor_spec: &str,
-    all_model_parameters_names: &[String],
-    design_info: &DesignMatrixInfo,
-    factor_levels_map: &HashMap<String, Vec<String>>,
-    covariate_means_map: &HashMap<String, f64>,
-    covar_names: &[String],
-    beta_hat: &DVector<f64>,
-    g_inv: &DMatrix<f64>,
-    mse: f64,
-    df_error: i64,
-    s_rss: f64,
+    model: &EmmeansModel,
+    fit: &EmmeansFit,
     config: &UnivariateConfig,
-    contrast_coeffs_list: &mut Vec<ContrastCoefficientsEntry>,
-    estimates_list: &mut Vec<EMMeansEstimates>,
-    pairwise_list: &mut Vec<PairwiseComparisons>,
-    univariate_tests_list: &mut Vec<UnivariateTests>
+    tables: &mut EmmeansTables
 ) -> Result<(), String> {
+    let all_model_parameters_names = model.all_model_parameters_names;
+    let factor_levels_map = model.factor_levels_map;
     let parsed_terms = parse_interaction_term(factor_spec);
     let current_spec_factors: Vec<&str> = parsed_terms.iter().map(String::as_str).collect();
 
@@ -344,8 +315,7 @@ fn process_factor_emmeans(
             factor_spec,
             all_model_parameters_names,
             config,
-            contrast_coeffs_list,
-            estimates_list
+            tables
         );
         return Ok(());
     }
@@ -358,8 +328,7 @@ fn process_factor_emmeans(
                 factor_spec,
                 all_model_parameters_names,
                 config,
-                contrast_coeffs_list,
-                estimates_list
+                tables
             );
             return Ok(());
         }
@@ -372,26 +341,23 @@ fn process_factor_emmeans(
     let (l_matrix_rows, l_labels) = generate_l_vectors_for_emmeans(
         &level_combinations,
         all_model_parameters_names,
-        design_info.p_parameters,
+        model.design_info.p_parameters,
         factor_levels_map,
-        covariate_means_map,
-        covar_names
+        model.covariate_means_map,
+        model.covar_names
     )?;
 
     let estimates_table = generate_em_estimates_table(
         &l_matrix_rows,
         &level_combinations,
-        beta_hat,
-        g_inv,
-        mse,
-        df_error,
+        fit,
         config.options.sig_level,
         factor_spec
     );
-    estimates_list.push(estimates_table);
+    tables.em_estimates.push(estimates_table);
 
     if config.options.coefficient_matrix {
-        contrast_coeffs_list.push(ContrastCoefficientsEntry {
+        tables.contrast_coefficients.push(ContrastCoefficientsEntry {
             parameter: all_model_parameters_names.to_vec(),
             l_label: l_labels,
             l_matrix: l_matrix_rows.clone(),
@@ -411,14 +377,11 @@ fn process_factor_emmeans(
                     main_effect_name,
                     main_effect_levels,
                     &l_matrix_rows,
-                    beta_hat,
-                    g_inv,
-                    mse,
-                    df_error,
+                    fit,
                     config
                 )
             {
-                pairwise_list.push(pairwise_table);
+                tables.pairwise_comparisons.push(pairwise_table);
             }
 
             if
@@ -426,22 +389,44 @@ fn process_factor_emmeans(
                     main_effect_name,
                     main_effect_levels.len(),
                     &l_matrix_rows,
-                    beta_hat,
-                    g_inv,
-                    mse,
-                    df_error,
-                    design_info.p_parameters,
-                    s_rss,
-                    config.options.sig_level,
-                    config.options.est_effect_size,
-                    config.options.obs_power
+                    fit,
+                    model.design_info.p_parameters,
+                    config
                 )
             {
-                univariate_tests_list.push(univariate_table);
+                tables.univariate_tests.push(univariate_table);
             }
         }
     }
 
+    if current_spec_factors.len() > 1 && config.emmeans.comp_simple_effect {
+        tables.simple_effects.extend(
+            generate_simple_effects(
+                factor_spec,
+                &current_spec_factors,
+                &level_combinations,
+                &l_matrix_rows,
+                factor_levels_map,
+                fit,
+                config
+            )?
+        );
+    }
+
+    if current_spec_factors.len() > 1 && config.emmeans.comp_interaction {
+        tables.interaction_contrasts.extend(
+            generate_interaction_contrasts(
+                factor_spec,
+                &current_spec_factors,
+                &level_combinations,
+                &l_matrix_rows,
+                factor_levels_map,
+                fit,
+                config
+            )?
+        );
+    }
+
     Ok(())
 }
 
@@ -494,13 +479,11 @@ fn calculate_emmeans_coefficient(
 fn generate_em_estimates_table(
     l_matrix_for_emms: &[Vec<f64>],
     level_combinations_for_spec: &[BTreeMap<String, String>],
-    beta_hat: &DVector<f64>,
-    g_inv: &DMatrix<f64>,
-    mse: f64,
-    df_error: i64,
+    fit: &EmmeansFit,
     sig_level: f64,
     factor_spec_emmeans: &str
 ) -> EMMeansEstimates {
+    let (beta_hat, g_inv, mse, df_error) = (fit.beta_hat, fit.g_inv, fit.mse, fit.df_error);
     let mut emm_estimates_entries = Vec::new();
     let mut emm_levels_collector: Vec<String> = Vec::new();
     let mut emm_means_collector: Vec<f64> = Vec::new();
@@ -580,10 +563,7 @@ fn generate_pairwise_comparisons_table(
     main_effect_name: &str,
     main_effect_levels: &[String],
     l_matrix_for_emms: &[Vec<f64>],
-    beta_hat: &DVector<f64>,
-    g_inv: &DMatrix<f64>,
-    mse: f64,
-    df_error: i64,
+    fit: &EmmeansFit,
     config: &UnivariateConfig
 ) -> Option<PairwiseComparisons> {
     let num_levels_main_effect = main_effect_levels.len();
@@ -597,143 +577,578 @@ fn generate_pairwise_comparisons_table(
         return None;
     }
 
-    let alpha_pairwise_sig = match config.emmeans.confi_interval_method {
-        CIMethod::Bonferroni => config.options.sig_level / (num_pairwise_comparisons as f64),
-        CIMethod::Sidak =>
-            1.0 - (1.0 - config.options.sig_level).powf(1.0 / (num_pairwise_comparisons as f64)),
-        CIMethod::LsdNone => config.options.sig_level,
-    };
-
     for i in 0..num_levels_main_effect {
         for j in 0..num_levels_main_effect {
             if i == j {
                 continue;
             }
 
-            let l_vector_i_data = &l_matrix_for_emms[i];
-            let l_vector_j_data = &l_matrix_for_emms[j];
-
-            if
-                l_vector_i_data.iter().all(|&x| x == 0.0) ||
-                l_vector_j_data.iter().all(|&x| x == 0.0)
-            {
-                pairwise_entries.push(PairwiseComparisonsEntry {
-                    parameter: vec![
+            pairwise_entries.push(
+                generate_pairwise_comparison_entry(
+                    vec![
                         format!("{}={}", main_effect_name, main_effect_levels[i]),
                         format!("{}={}", main_effect_name, main_effect_levels[j])
                     ],
-                    mean_difference: vec![f64::NAN],
-                    standard_error: vec![f64::NAN],
-                    significance: vec![f64::NAN],
-                    confidence_interval: vec![ConfidenceInterval {
-                        lower_bound: f64::NAN,
-                        upper_bound: f64::NAN,
-                    }],
-                });
-                continue;
-            }
+                    &l_matrix_for_emms[i],
+                    &l_matrix_for_emms[j],
+                    fit,
+                    num_pairwise_comparisons,
+                    config
+                )
+            );
+        }
+    }
+    Some(PairwiseComparisons {
+        entries: pairwise_entries,
+        note: Some(
+            format!(
+                "Pairwise comparisons for {}. Adjustment for multiple comparisons: {:?}.",
+                main_effect_name,
+                config.emmeans.confi_interval_method
+            )
+        ),
+        interpretation: Some(
+            "This table compares each pair of levels for a main effect. A significant p-value (typically < .05) indicates a statistically significant difference between the two levels' means. The confidence interval for the mean difference should not contain zero for the difference to be significant.".to_string()
+        ),
+    })
+}
+
+fn adjusted_pairwise_alpha(sig_level: f64, num_comparisons: usize, method: &CIMethod) -> f64 {
+    match method {
+        CIMethod::Bonferroni => sig_level / (num_comparisons as f64),
+        CIMethod::Sidak => 1.0 - (1.0 - sig_level).powf(1.0 / (num_comparisons as f64)),
+        CIMethod::LsdNone => sig_level,
+    }
+}
+
+fn adjusted_pairwise_significance(
+    raw_significance: f64,
+    num_comparisons: usize,
+    method: &CIMethod
+) -> f64 {
+    if raw_significance.is_nan() {
+        return f64::NAN;
+    }
+    match method {
+        CIMethod::Bonferroni => (raw_significance * (num_comparisons as f64)).min(1.0),
+        CIMethod::Sidak => (1.0 - (1.0 - raw_significance).powf(num_comparisons as f64)).min(1.0),
+        CIMethod::LsdNone => raw_significance,
+    }
+}
 
-            let l_vector_i = DVector::from_column_slice(l_vector_i_data);
-            let l_vector_j = DVector::from_column_slice(l_vector_j_data);
+fn estimate_emmeans_contrast(
+    l_vector: &DVector<f64>,
+    fit: &EmmeansFit
+) -> (f64, f64) {
+    let estimate = (l_vector.transpose() * fit.beta_hat)[(0, 0)];
+    let variance = (l_vector.transpose() * fit.g_inv * l_vector * fit.mse)[(0, 0)];
+    let std_error = if variance >= 0.0 { variance.sqrt() } else { f64::NAN };
+    (estimate, std_error)
+}
 
-            let emm_i_val = (l_vector_i.transpose() * beta_hat)[(0, 0)];
-            let emm_j_val = (l_vector_j.transpose() * beta_hat)[(0, 0)];
-            let mean_diff = emm_i_val - emm_j_val;
+fn generate_pairwise_comparison_entry(
+    parameter: Vec<String>,
+    l_vector_i_data: &[f64],
+    l_vector_j_data: &[f64],
+    fit: &EmmeansFit,
+    num_comparisons: usize,
+    config: &UnivariateConfig
+) -> PairwiseComparisonsEntry {
+    let df_error = fit.df_error;
+    if l_vector_i_data.iter().all(|&x| x == 0.0) || l_vector_j_data.iter().all(|&x| x == 0.0) {
+        return PairwiseComparisonsEntry {
+            parameter,
+            mean_difference: vec![f64::NAN],
+            standard_error: vec![f64::NAN],
+            significance: vec![f64::NAN],
+            confidence_interval: vec![ConfidenceInterval {
+                lower_bound: f64::NAN,
+                upper_bound: f64::NAN,
+            }],
+        };
+    }
 
-            let l_vector_diff = &l_vector_i - &l_vector_j;
-            let variance_of_diff_matrix = l_vector_diff.transpose() * g_inv * &l_vector_diff * mse;
-            let std_error_diff = if variance_of_diff_matrix[(0, 0)] >= 0.0 {
-                variance_of_diff_matrix[(0, 0)].sqrt()
-            } else {
-                f64::NAN
-            };
+    let l_vector_diff =
+        DVector::from_column_slice(l_vector_i_data) - DVector::from_column_slice(l_vector_j_data);
+    let (mean_diff, std_error_diff) = estimate_emmeans_contrast(
+        &l_vector_diff,
+        fit
+    );
 
-            let t_value_diff = if !std_error_diff.is_nan() && std_error_diff != 0.0 {
-                mean_diff / std_error_diff
+    let t_value_diff = if !std_error_diff.is_nan() && std_error_diff != 0.0 {
+        mean_diff / std_error_diff
+    } else {
+        f64::NAN
+    };
+
+    let raw_significance_diff = if !t_value_diff.is_nan() && df_error > 0 {
+        calculate_t_significance(t_value_diff.abs(), df_error as usize)
+    } else {
+        f64::NAN
+    };
+
+    let method = &config.emmeans.confi_interval_method;
+    let adjusted_significance_diff = adjusted_pairwise_significance(
+        raw_significance_diff,
+        num_comparisons,
+        method
+    );
+
+    let alpha_pairwise_sig = adjusted_pairwise_alpha(
+        config.options.sig_level,
+        num_comparisons,
+        method
+    );
+    let t_crit_pairwise = calculate_t_critical(Some(alpha_pairwise_sig), df_error as usize);
+    let ci_width_diff = if !t_crit_pairwise.is_nan() && !std_error_diff.is_nan() {
+        std_error_diff * t_crit_pairwise
+    } else {
+        f64::NAN
+    };
+
+    PairwiseComparisonsEntry {
+        parameter,
+        mean_difference: vec![mean_diff],
+        standard_error: vec![std_error_diff],
+        significance: vec![adjusted_significance_diff],
+        confidence_interval: vec![ConfidenceInterval {
+            lower_bound: if !ci_width_diff.is_nan() {
+                mean_diff - ci_width_diff
             } else {
                 f64::NAN
-            };
-
-            let raw_significance_diff = if !t_value_diff.is_nan() && df_error > 0 {
-                calculate_t_significance(t_value_diff.abs(), df_error as usize)
+            },
+            upper_bound: if !ci_width_diff.is_nan() {
+                mean_diff + ci_width_diff
             } else {
                 f64::NAN
-            };
+            },
+        }],
+    }
+}
+
+fn find_level_combination_index(
+    level_combinations: &[BTreeMap<String, String>],
+    fixed_levels: &BTreeMap<String, String>,
+    assignments: &[(&str, &str)]
+) -> Option<usize> {
+    level_combinations.iter().position(|combo| {
+        fixed_levels.iter().all(|(f, l)| combo.get(f) == Some(l)) &&
+            assignments.iter().all(|(f, l)| combo.get(*f).map(String::as_str) == Some(*l))
+    })
+}
+
+fn format_fixed_levels(fixed_levels: &BTreeMap<String, String>) -> Vec<String> {
+    fixed_levels
+        .iter()
+        .map(|(f, l)| format!("{}={}", f, l))
+        .collect()
+}
+
+fn generate_l_matrix_test_entry(
+    source: String,
+    l_matrix: &DMatrix<f64>,
+    fit: &EmmeansFit,
+    config: &UnivariateConfig
+) -> UnivariateTestsEntry {
+    let (beta_hat, g_inv, mse, df_error, s_rss) = (
+        fit.beta_hat,
+        fit.g_inv,
+        fit.mse,
+        fit.df_error,
+        fit.s_rss,
+    );
+    let (sum_of_squares, df) = calculate_ss_for_term(l_matrix, beta_hat, g_inv, &source).unwrap_or(
+        (f64::NAN, 0)
+    );
+
+    if df == 0 || df_error <= 0 || sum_of_squares.is_nan() {
+        return UnivariateTestsEntry {
+            source,
+            sum_of_squares: f64::NAN,
+            df,
+            mean_square: f64::NAN,
+            f_value: f64::NAN,
+            significance: f64::NAN,
+            partial_eta_squared: f64::NAN,
+            noncent_parameter: f64::NAN,
+            observed_power: f64::NAN,
+        };
+    }
+
+    let mean_square = sum_of_squares / (df as f64);
+    let f_value = if mse > 1e-12 { mean_square / mse } else { f64::NAN };
+    let significance = calculate_f_significance(df, df_error as usize, f_value);
+
+    let partial_eta_squared = if config.options.est_effect_size {
+        if (sum_of_squares + s_rss).abs() > 1e-12 {
+            sum_of_squares / (sum_of_squares + s_rss)
+        } else {
+            0.0
+        }
+    } else {
+        f64::NAN
+    };
+
+    let (noncent_parameter, observed_power) = if config.options.obs_power && !f_value.is_nan() {
+        (
+            f_value * (df as f64),
+            calculate_observed_power_f(
+                f_value,
+                df as f64,
+                df_error as f64,
+                config.options.sig_level
+            ),
+        )
+    } else {
+        (f64::NAN, f64::NAN)
+    };
 
-            let adjusted_significance_diff = if !raw_significance_diff.is_nan() {
-                match config.emmeans.confi_interval_method {
-                    CIMethod::Bonferroni =>
-                        (raw_significance_diff * (num_pairwise_comparisons as f64)).min(1.0),
-                    CIMethod::Sidak =>
-                        (
-                            1.0 -
-                            (1.0 - raw_significance_diff).powf(num_pairwise_comparisons as f64)
-                        ).min(1.0),
-                    CIMethod::LsdNone => raw_significance_diff,
+    UnivariateTestsEntry {
+        source,
+        sum_of_squares,
+        df,
+        mean_square,
+        f_value,
+        significance,
+        partial_eta_squared,
+        noncent_parameter,
+        observed_power,
+    }
+}
+
+fn generate_simple_effects(
+    factor_spec: &str,
+    spec_factors: &[&str],
+    level_combinations: &[BTreeMap<String, String>],
+    l_matrix_for_emms: &[Vec<f64>],
+    factor_levels_map: &HashMap<String, Vec<String>>,
+    fit: &EmmeansFit,
+    config: &UnivariateConfig
+) -> Result<Vec<SimpleEffects>, String> {
+    let mut simple_effects = Vec::new();
+
+    for &compared_factor in spec_factors {
+        let levels = match factor_levels_map.get(compared_factor) {
+            Some(levels) if levels.len() > 1 => levels,
+            _ => {
+                continue;
+            }
+        };
+        let num_comparisons = (levels.len() * (levels.len() - 1)) / 2;
+
+        let other_factors: Vec<&str> = spec_factors
+            .iter()
+            .copied()
+            .filter(|f| *f != compared_factor)
+            .collect();
+        let slice_combinations = get_factor_level_combinations(&other_factors, factor_levels_map)?;
+
+        let mut slices = Vec::new();
+        let mut dropped_rows = 0;
+        for fixed_levels in &slice_combinations {
+            let level_indices: Vec<Option<usize>> = levels
+                .iter()
+                .map(|level| {
+                    find_level_combination_index(
+                        level_combinations,
+                        fixed_levels,
+                        &[(compared_factor, level.as_str())]
+                    )
+                })
+                .collect();
+            let fixed_label = format_fixed_levels(fixed_levels);
+
+            let mut comparisons = Vec::new();
+            let mut contrast_rows = Vec::new();
+            for i in 0..levels.len() {
+                for j in 0..levels.len() {
+                    if i == j {
+                        continue;
+                    }
+                    let (Some(idx_i), Some(idx_j)) = (level_indices[i], level_indices[j]) else {
+                        continue;
+                    };
+
+                    comparisons.push(
+                        generate_pairwise_comparison_entry(
+                            vec![
+                                format!("{}={}", compared_factor, levels[i]),
+                                format!("{}={}", compared_factor, levels[j])
+                            ],
+                            &l_matrix_for_emms[idx_i],
+                            &l_matrix_for_emms[idx_j],
+                            fit,
+                            num_comparisons,
+                            config
+                        )
+                    );
+
+                    if i < j {
+                        let estimable = [idx_i, idx_j]
+                            .iter()
+                            .all(|&idx| l_matrix_for_emms[idx].iter().any(|&x| x != 0.0));
+                        if !estimable {
+                            dropped_rows += 1;
+                            continue;
+                        }
+
+                        let mut row = vec![0.0; level_combinations.len()];
+                        row[idx_i] = 1.0;
+                        row[idx_j] = -1.0;
+                        contrast_rows.push(row);
+                    }
                 }
-            } else {
-                f64::NAN
-            };
+            }
 
-            let t_crit_pairwise = calculate_t_critical(Some(alpha_pairwise_sig), df_error as usize);
-            let ci_width_diff = if !t_crit_pairwise.is_nan() && !std_error_diff.is_nan() {
-                std_error_diff * t_crit_pairwise
-            } else {
-                f64::NAN
+            let l_matrix = construct_emmeans_contrast_l_matrix(l_matrix_for_emms, &contrast_rows);
+            let test = generate_l_matrix_test_entry(
+                format!("{} within {}", compared_factor, fixed_label.join(", ")),
+                &l_matrix,
+                fit,
+                config
+            );
+
+            slices.push(SimpleEffectSlice {
+                fixed_levels: fixed_label,
+                comparisons,
+                test,
+            });
+        }
+
+        let mut note = format!(
+            "Simple effects of {} within each level combination of {}. Adjustment for multiple comparisons: {:?}.",
+            compared_factor,
+            other_factors.join(" * "),
+            config.emmeans.confi_interval_method
+        );
+        if dropped_rows > 0 {
+            note.push_str(
+                &format!(
+                    " {} pairwise difference(s) involving non-estimable means were excluded from the F tests.",
+                    dropped_rows
+                )
+            );
+        }
+
+        simple_effects.push(SimpleEffects {
+            factor_spec: factor_spec.to_string(),
+            compared_factor: compared_factor.to_string(),
+            slices,
+            note: Some(note),
+            interpretation: Some(
+                format!(
+                    "Each F test examines whether the estimated marginal means of {} differ at one level combination of the other factors; the pairwise comparisons locate those differences. This is the usual follow-up to a significant {} interaction.",
+                    compared_factor,
+                    factor_spec
+                )
+            ),
+        });
+    }
+
+    Ok(simple_effects)
+}
+
+fn generate_interaction_contrasts(
+    factor_spec: &str,
+    spec_factors: &[&str],
+    level_combinations: &[BTreeMap<String, String>],
+    l_matrix_for_emms: &[Vec<f64>],
+    factor_levels_map: &HashMap<String, Vec<String>>,
+    fit: &EmmeansFit,
+    config: &UnivariateConfig
+) -> Result<Vec<InteractionContrasts>, String> {
+    let df_error = fit.df_error;
+    let mut interaction_contrasts = Vec::new();
+    let method = &config.emmeans.confi_interval_method;
+
+    for (first_idx, &first_factor) in spec_factors.iter().enumerate() {
+        for &second_factor in &spec_factors[first_idx + 1..] {
+            let (first_levels, second_levels) = match
+                (factor_levels_map.get(first_factor), factor_levels_map.get(second_factor))
+            {
+                (Some(a), Some(b)) if a.len() > 1 && b.len() > 1 => (a, b),
+                _ => {
+                    continue;
+                }
             };
+            let num_comparisons =
+                ((first_levels.len() * (first_levels.len() - 1)) / 2) *
+                ((second_levels.len() * (second_levels.len() - 1)) / 2);
+            let alpha = adjusted_pairwise_alpha(config.options.sig_level, num_comparisons, method);
+            let t_crit = calculate_t_critical(Some(alpha), df_error as usize);
+
+            let other_factors: Vec<&str> = spec_factors
+                .iter()
+                .copied()
+                .filter(|f| *f != first_factor && *f != second_factor)
+                .collect();
+            let slice_combinations = get_factor_level_combinations(
+                &other_factors,
+                factor_levels_map
+            )?;
 
-            pairwise_entries.push(PairwiseComparisonsEntry {
-                parameter: vec![
-                    format!("{}={}", main_effect_name, main_effect_levels[i]),
-                    format!("{}={}", main_effect_name, main_effect_levels[j])
-                ],
-                mean_difference: vec![mean_diff],
-                standard_error: vec![std_error_diff],
-                significance: vec![adjusted_significance_diff],
-                confidence_interval: vec![ConfidenceInterval {
-                    lower_bound: if !ci_width_diff.is_nan() {
-                        mean_diff - ci_width_diff
-                    } else {
-                        f64::NAN
-                    },
-                    upper_bound: if !ci_width_diff.is_nan() {
-                        mean_diff + ci_width_diff
-                    } else {
-                        f64::NAN
-                    },
-                }],
+            let mut entries = Vec::new();
+            let mut tests = Vec::new();
+            for fixed_levels in &slice_combinations {
+                let fixed_label = format_fixed_levels(fixed_levels);
+                let cell = |a: &str, b: &str| {
+                    find_level_combination_index(
+                        level_combinations,
+                        fixed_levels,
+                        &[
+                            (first_factor, a),
+                            (second_factor, b),
+                        ]
+                    )
+                };
+
+                let mut contrast_rows = Vec::new();
+                for i in 0..first_levels.len() {
+                    for j in i + 1..first_levels.len() {
+                        for k in 0..second_levels.len() {
+                            for l in k + 1..second_levels.len() {
+                                let cells = [
+                                    (cell(&first_levels[i], &second_levels[k]), 1.0),
+                                    (cell(&first_levels[j], &second_levels[k]), -1.0),
+                                    (cell(&first_levels[i], &second_levels[l]), -1.0),
+                                    (cell(&first_levels[j], &second_levels[l]), 1.0),
+                                ];
+                                if cells.iter().any(|(idx, _)| idx.is_none()) {
+                                    continue;
+                                }
+
+                                let mut row = vec![0.0; level_combinations.len()];
+                                let mut estimable = true;
+                                for (idx, sign) in cells {
+                                    let idx = idx.unwrap();
+                                    if l_matrix_for_emms[idx].iter().all(|&x| x == 0.0) {
+                                        estimable = false;
+                                    }
+                                    row[idx] = sign;
+                                }
+
+                                let l_vector = construct_emmeans_contrast_l_matrix(
+                                    l_matrix_for_emms,
+                                    std::slice::from_ref(&row)
+                                )
+                                    .row(0)
+                                    .transpose();
+                                let (estimate, standard_error) = if estimable {
+                                    estimate_emmeans_contrast(&l_vector, fit)
+                                } else {
+                                    (f64::NAN, f64::NAN)
+                                };
+                                let t_value = if standard_error > 0.0 {
+                                    estimate / standard_error
+                                } else {
+                                    f64::NAN
+                                };
+                                let significance = if !t_value.is_nan() && df_error > 0 {
+                                    adjusted_pairwise_significance(
+                                        calculate_t_significance(t_value.abs(), df_error as usize),
+                                        num_comparisons,
+                                        method
+                                    )
+                                } else {
+                                    f64::NAN
+                                };
+                                let ci_width = standard_error * t_crit;
+
+                                entries.push(InteractionContrastEntry {
+                                    fixed_levels: fixed_label.clone(),
+                                    first_factor_levels: vec![
+                                        first_levels[i].clone(),
+                                        first_levels[j].clone()
+                                    ],
+                                    second_factor_levels: vec![
+                                        second_levels[k].clone(),
+                                        second_levels[l].clone()
+                                    ],
+                                    estimate,
+                                    standard_error,
+                                    t_value,
+                                    significance,
+                                    confidence_interval: ConfidenceInterval {
+                                        lower_bound: estimate - ci_width,
+                                        upper_bound: estimate + ci_width,
+                                    },
+                                });
+
+                                if estimable {
+                                    contrast_rows.push(row);
+                                }
+                            }
+                        }
+                    }
+                }
+
+                let source = if fixed_label.is_empty() {
+                    format!("{} * {}", first_factor, second_factor)
+                } else {
+                    format!("{} * {} within {}", first_factor, second_factor, fixed_label.join(", "))
+                };
+                let l_matrix = construct_emmeans_contrast_l_matrix(
+                    l_matrix_for_emms,
+                    &contrast_rows
+                );
+                tests.push(
+                    generate_l_matrix_test_entry(
+                        source,
+                        &l_matrix,
+                        fit,
+                        config
+                    )
+                );
+            }
+
+            interaction_contrasts.push(InteractionContrasts {
+                factor_spec: factor_spec.to_string(),
+                factors: vec![first_factor.to_string(), second_factor.to_string()],
+                entries,
+                tests,
+                note: Some(
+                    format!(
+                        "Interaction contrasts (difference of differences) between {} and {}. Adjustment for multiple comparisons: {:?}.",
+                        first_factor,
+                        second_factor,
+                        config.emmeans.confi_interval_method
+                    )
+                ),
+                interpretation: Some(
+                    format!(
+                        "Each contrast tests whether the difference between two levels of {} changes between two levels of {}. The F test combines all contrasts and is the test of the {} * {} interaction on the estimated marginal means.",
+                        first_factor,
+                        second_factor,
+                        first_factor,
+                        second_factor
+                    )
+                ),
             });
         }
     }
-    Some(PairwiseComparisons {
-        entries: pairwise_entries,
-        note: Some(
-            format!(
-                "Pairwise comparisons for {}. Adjustment for multiple comparisons: {:?}.",
-                main_effect_name,
-                config.emmeans.confi_interval_method
-            )
-        ),
-        interpretation: Some(
-            "This table compares each pair of levels for a main effect. A significant p-value (typically < .05) indicates a statistically significant difference between the two levels' means. The confidence interval for the mean difference should not contain zero for the difference to be significant.".to_string()
-        ),
-    })
+
+    Ok(interaction_contrasts)
 }
 
 fn generate_univariate_test_table(
     main_effect_name: &str,
     num_levels_main_effect: usize,
     l_matrix_for_emms: &[Vec<f64>],
-    beta_hat: &DVector<f64>,
-    g_inv: &DMatrix<f64>,
-    mse: f64,
-    df_error: i64,
+    fit: &EmmeansFit,
     p_parameters: usize,
-    s_rss: f64,
-    sig_level_option: f64,
-    est_effect_size: bool,
-    obs_power: bool
+    config: &UnivariateConfig
 ) -> Option<UnivariateTests> {
+    let (beta_hat, g_inv, mse, df_error, s_rss) = (
+        fit.beta_hat,
+        fit.g_inv,
+        fit.mse,
+        fit.df_error,
+        fit.s_rss,
+    );
+    let sig_level_option = config.options.sig_level;
+    let est_effect_size = config.options.est_effect_size;
+    let obs_power = config.options.obs_power;
+
     if num_levels_main_effect < 2 {
         return None;
     }
@@ -798,8 +1213,7 @@ fn generate_univariate_test_table(
     let mut l_matrix_uni_test_rows_as_vecs: Vec<Vec<f64>> = Vec::new();
     let l_vector_last_level_data = &l_matrix_for_emms[num_levels_main_effect - 1];
 
-    for i in 0..num_levels_main_effect - 1 {
-        let l_vector_i_data = &l_matrix_for_emms[i];
+    for l_vector_i_data in &l_matrix_for_emms[..num_levels_main_effect - 1] {
         let diff_row_data: Vec<f64> = l_vector_i_data
             .iter()
             .zip(l_vector_last_level_data.iter())
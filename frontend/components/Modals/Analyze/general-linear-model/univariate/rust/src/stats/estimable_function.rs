@@ -464,7 +464,7 @@ fn build_interaction_l_vector(
         let num_non_pivot_levels_for_interacting_factors_in_this_term =
             k_interaction_way - num_pivot_levels_for_interacting_factors_in_this_term;
 
-        let sign = if num_non_pivot_levels_for_interacting_factors_in_this_term % 2 == 0 {
+        let sign = if num_non_pivot_levels_for_interacting_factors_in_this_term.is_multiple_of(2) {
             1i32
         } else {
             -1i32
@@ -501,7 +501,7 @@ fn get_coeffs_for_cell_mean(
 
         let param_factor_levels = parse_parameter_name(model_param_name);
 
-        if param_factor_levels.get("Intercept").is_some() || param_factor_levels.is_empty() {
+        if param_factor_levels.contains_key("Intercept") || param_factor_levels.is_empty() {
             continue;
         }
 
@@ -0,0 +1,312 @@
+use nalgebra::{ DMatrix, DVector };
+use statrs::distribution::{ ContinuousCDF, FisherSnedecor };
+
+use crate::models::{
+    config::{ SumOfSquaresMethod, UnivariateConfig },
+    data::AnalysisData,
+    result::{
+        DesignMatrixInfo,
+        ErrorTermEntry,
+        ExpectedMeanSquares,
+        ExpectedMeanSquaresEntry,
+        SweptMatrixInfo,
+        TestEffectEntry,
+    },
+};
+
+use super::core::*;
+
+pub fn get_random_terms(design_info: &DesignMatrixInfo, config: &UnivariateConfig) -> Vec<String> {
+    let random_factors = match &config.main.rand_factor {
+        Some(factors) if !factors.is_empty() => factors,
+        _ => {
+            return Vec::new();
+        }
+    };
+
+    design_info.term_names
+        .iter()
+        .filter(|term| term.as_str() != "Intercept")
+        .filter(|term| {
+            parse_interaction_term(term)
+                .iter()
+                .any(|factor| random_factors.contains(factor))
+        })
+        .cloned()
+        .collect()
+}
+
+pub fn construct_term_l_matrix(
+    design_info: &DesignMatrixInfo,
+    term: &str,
+    ztwz_matrix: &DMatrix<f64>,
+    data: &AnalysisData,
+    config: &UnivariateConfig
+) -> Result<DMatrix<f64>, String> {
+    let all_terms = &design_info.term_names;
+    match config.model.sum_of_square_method {
+        SumOfSquaresMethod::TypeI => {
+            construct_type_i_l_matrix(design_info, term, all_terms, ztwz_matrix)
+        }
+        SumOfSquaresMethod::TypeII => construct_type_ii_l_matrix(design_info, term, all_terms),
+        SumOfSquaresMethod::TypeIII => {
+            construct_type_iii_l_matrix(design_info, term, all_terms, data, config)
+        }
+        SumOfSquaresMethod::TypeIV => {
+            construct_type_iv_l_matrix(design_info, term, all_terms, data, config)
+        }
+    }
+}
+
+// Menghitung koefisien expected mean square setiap term terhadap komponen varians
+// acak (model tak terestriksi), lalu menyusun error term sintetis per efek dengan
+// derajat bebas Satterthwaite. `term_tests` berisi (nama, SS, df) setiap term dan
+// `residual` berisi (MS, df) galat.
+pub fn calculate_expected_mean_squares(
+    design_info: &DesignMatrixInfo,
+    swept_info: &SweptMatrixInfo,
+    ztwz_matrix: &DMatrix<f64>,
+    data: &AnalysisData,
+    config: &UnivariateConfig,
+    term_tests: &[(String, f64, usize)],
+    residual: (f64, usize)
+) -> Result<(ExpectedMeanSquares, Vec<ErrorTermEntry>), String> {
+    let random_terms = get_random_terms(design_info, config);
+    if random_terms.is_empty() {
+        return Err("No random terms in the model".to_string());
+    }
+
+    let x = &design_info.x;
+    let p = design_info.p_parameters;
+    let g_inv = &swept_info.g_inv;
+    let weights = match &design_info.w {
+        Some(w) => w.clone_owned(),
+        None => DVector::from_element(design_info.n_samples, 1.0),
+    };
+
+    // B = G X'W sehingga beta_hat = B y.
+    let mut xtw = x.transpose();
+    for (j, mut column) in xtw.column_iter_mut().enumerate() {
+        column *= weights[j];
+    }
+    let b_matrix = g_inv * &xtw;
+    let h_matrix = g_inv * ztwz_matrix.view((0, 0), (p, p));
+
+    let mut variance_components = random_terms.clone();
+    variance_components.push("Error".to_string());
+
+    let mut entries = Vec::new();
+    for (term, _, df) in term_tests {
+        if *df == 0 {
+            continue;
+        }
+
+        let l_matrix = construct_term_l_matrix(design_info, term, ztwz_matrix, data, config)?;
+        let lgl = &l_matrix * g_inv * l_matrix.transpose();
+        let lgl_pinv = lgl
+            .svd(true, true)
+            .pseudo_inverse(1e-10)
+            .map_err(|e| format!("Singular L G L' for term '{}': {}", term, e))?;
+        let lb = &l_matrix * &b_matrix;
+
+        let mut coefficients = Vec::with_capacity(variance_components.len());
+        for random_term in &random_terms {
+            let coefficient = match design_info.term_column_indices.get(random_term) {
+                Some(&(start, end)) => {
+                    let m = &lb * x.columns(start, end - start + 1);
+                    let mmt = &m * m.transpose();
+                    lgl_pinv.component_mul(&mmt).sum() / (*df as f64)
+                }
+                None => 0.0,
+            };
+            coefficients.push(if coefficient.abs() < 1e-10 { 0.0 } else { coefficient });
+        }
+        coefficients.push(1.0);
+
+        let lh = &l_matrix * &h_matrix;
+        let quadratic_terms: Vec<String> = design_info.term_names
+            .iter()
+            .filter(|t| !random_terms.contains(t))
+            .filter(|t| {
+                design_info.term_column_indices.get(*t).is_some_and(|&(start, end)| {
+                    lh.columns(start, end - start + 1).norm() > 1e-8
+                })
+            })
+            .cloned()
+            .collect();
+
+        entries.push(ExpectedMeanSquaresEntry {
+            source: term.clone(),
+            coefficients,
+            quadratic_term: if quadratic_terms.is_empty() {
+                None
+            } else {
+                Some(quadratic_terms.join(", "))
+            },
+        });
+    }
+
+    let mut error_coefficients = vec![0.0; random_terms.len()];
+    error_coefficients.push(1.0);
+    entries.push(ExpectedMeanSquaresEntry {
+        source: "Error".to_string(),
+        coefficients: error_coefficients,
+        quadratic_term: None,
+    });
+
+    let (ms_error, df_error) = residual;
+    let mean_square_of = |name: &str| -> Option<(f64, usize)> {
+        if name == "Error" {
+            return Some((ms_error, df_error));
+        }
+        term_tests
+            .iter()
+            .find(|(t, _, df)| t == name && *df > 0)
+            .map(|(_, ss, df)| (ss / (*df as f64), *df))
+    };
+
+    let mut error_terms = Vec::new();
+    for entry in entries.iter().filter(|e| e.source != "Error") {
+        let own_index = random_terms.iter().position(|t| *t == entry.source);
+        let mut target = DVector::from_vec(entry.coefficients.clone());
+        if let Some(idx) = own_index {
+            target[idx] = 0.0;
+        }
+
+        let candidates: Vec<&ExpectedMeanSquaresEntry> = entries
+            .iter()
+            .filter(|e| e.source != entry.source)
+            .filter(|e| e.source == "Error" || random_terms.contains(&e.source))
+            .filter(|e| mean_square_of(&e.source).is_some())
+            .collect();
+
+        let design = DMatrix::from_fn(variance_components.len(), candidates.len(), |r, c| {
+            candidates[c].coefficients[r]
+        });
+        let weights_solution = design
+            .clone()
+            .svd(true, true)
+            .solve(&target, 1e-10)
+            .map_err(|e| format!("Cannot synthesize error term for '{}': {}", entry.source, e))?;
+
+        let mut mean_square = 0.0;
+        let mut satterthwaite_denominator = 0.0;
+        let mut parts = Vec::new();
+        for (candidate, &coefficient) in candidates.iter().zip(weights_solution.iter()) {
+            if coefficient.abs() < 1e-8 {
+                continue;
+            }
+            let (ms, df) = mean_square_of(&candidate.source).unwrap();
+            mean_square += coefficient * ms;
+            satterthwaite_denominator += (coefficient * ms).powi(2) / (df as f64);
+            parts.push((coefficient, candidate.source.clone()));
+        }
+
+        let df = if satterthwaite_denominator > 0.0 {
+            mean_square.powi(2) / satterthwaite_denominator
+        } else {
+            f64::NAN
+        };
+
+        let composition = parts
+            .iter()
+            .enumerate()
+            .map(|(i, (coefficient, source))| {
+                let sign = if i == 0 {
+                    if *coefficient < 0.0 { "-" } else { "" }
+                } else if *coefficient < 0.0 {
+                    " - "
+                } else {
+                    " + "
+                };
+                if (coefficient.abs() - 1.0).abs() < 1e-8 && parts.len() == 1 {
+                    format!("{}MS({})", sign, source)
+                } else {
+                    format!("{}{:.3} MS({})", sign, coefficient.abs(), source)
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("");
+
+        let residual_norm = (&design * &weights_solution - &target).norm();
+        error_terms.push(ErrorTermEntry {
+            source: entry.source.clone(),
+            sum_of_squares: mean_square * df,
+            df,
+            mean_square,
+            composition,
+            note: if residual_norm > 1e-6 {
+                Some(
+                    "The expected mean square of this effect cannot be matched exactly by a combination of other mean squares; the closest least-squares combination is used.".to_string()
+                )
+            } else {
+                None
+            },
+        });
+    }
+
+    let expected_mean_squares = ExpectedMeanSquares {
+        variance_components,
+        entries,
+        note: Some(
+            "For each source, the expected mean square equals the sum of the coefficients in the cells times the variance components, plus a quadratic term involving effects in the Quadratic Term cell.".to_string()
+        ),
+        interpretation: Some(
+            "Expected mean squares are based on the Type of sums of squares chosen for the model. An effect is tested against the combination of mean squares whose expectation matches its own expectation without the effect.".to_string()
+        ),
+    };
+
+    Ok((expected_mean_squares, error_terms))
+}
+
+pub fn create_effect_entry_with_error_term(
+    sum_of_squares: f64,
+    df: usize,
+    error_term: &ErrorTermEntry,
+    sig_level: f64,
+    est_effect_size: bool,
+    obs_power: bool
+) -> TestEffectEntry {
+    let mean_square = if df > 0 { sum_of_squares / (df as f64) } else { 0.0 };
+    let error_df = error_term.df;
+    let f_value = if error_term.mean_square > 0.0 && error_df.is_finite() {
+        mean_square / error_term.mean_square
+    } else {
+        f64::NAN
+    };
+
+    let significance = if f_value.is_finite() && f_value >= 0.0 {
+        FisherSnedecor::new(df as f64, error_df).map_or(f64::NAN, |dist| {
+            (1.0 - dist.cdf(f_value)).max(0.0)
+        })
+    } else {
+        f64::NAN
+    };
+
+    let partial_eta_squared = if est_effect_size && error_term.sum_of_squares.is_finite() {
+        let denominator = sum_of_squares + error_term.sum_of_squares;
+        if denominator > 0.0 { (sum_of_squares / denominator).clamp(0.0, 1.0) } else { 0.0 }
+    } else {
+        f64::NAN
+    };
+
+    let (noncent_parameter, observed_power) = if obs_power && f_value.is_finite() {
+        (
+            f_value * (df as f64),
+            calculate_observed_power_f(f_value, df as f64, error_df, sig_level),
+        )
+    } else {
+        (f64::NAN, f64::NAN)
+    };
+
+    TestEffectEntry {
+        sum_of_squares,
+        df,
+        mean_square,
+        f_value,
+        significance,
+        partial_eta_squared,
+        noncent_parameter,
+        observed_power,
+    }
+}
@@ -21,7 +21,7 @@ pub fn parse_parameter_name(param_str: &str) -> HashMap<String, String> {
     }
 
     for part in param_str.split('*') {
-        let clean_part = part.trim_matches(|c| (c == '[' || c == ']'));
+        let clean_part = part.trim_matches(|c| c == '[' || c == ']');
         if let Some((factor, level)) = clean_part.split_once('=') {
             factors.insert(factor.to_string(), level.to_string());
         }
@@ -90,7 +90,7 @@ pub fn matches_combination(combo: &HashMap<String, String>, data: &AnalysisData)
     let mut row = vec![0.0; n_samples];
 
     let mut factor_locations = HashMap::new();
-    for (factor, _) in combo {
+    for factor in combo.keys() {
         for (group_idx, def_group) in data.fix_factor_data_defs.iter().enumerate() {
             if def_group.iter().any(|def| &def.name == factor) {
                 factor_locations.insert(factor.clone(), (true, group_idx));
@@ -110,7 +110,7 @@ pub fn matches_combination(combo: &HashMap<String, String>, data: &AnalysisData)
         }
     }
 
-    for i in 0..n_samples {
+    for (i, row_value) in row.iter_mut().enumerate() {
         let mut matches = true;
 
         for (factor, expected_level) in combo {
@@ -142,7 +142,7 @@ pub fn matches_combination(combo: &HashMap<String, String>, data: &AnalysisData)
         }
 
         if matches {
-            row[i] = 1.0;
+            *row_value = 1.0;
         }
     }
     row
@@ -458,7 +458,7 @@ pub fn get_all_non_empty_cells(
         return Ok(Vec::new());
     }
 
-    let n_samples = data.dependent_data.get(0).map_or(0, |d| d.len());
+    let n_samples = data.dependent_data.first().map_or(0, |d| d.len());
     if n_samples == 0 {
         return Ok(Vec::new());
     }
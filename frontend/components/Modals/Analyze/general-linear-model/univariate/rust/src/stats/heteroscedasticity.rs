@@ -159,7 +159,7 @@ fn run_simple_ols(
     let rss = residuals.norm_squared();
     let ess = tss - rss;
 
-    let r_squared = if tss.abs() < 1e-12 { 0.0 } else { (ess / tss).max(0.0).min(1.0) };
+    let r_squared = if tss.abs() < 1e-12 { 0.0 } else { (ess / tss).clamp(0.0, 1.0) };
 
     let df_residuals = n_obs.saturating_sub(n_predictors);
 
@@ -323,13 +323,13 @@ pub fn construct_type_iii_l_matrix(
         for potential_factor_name in components {
             let is_covariate = config.main.covar
                 .as_ref()
-                .map_or(false, |c_list| c_list.contains(&potential_factor_name));
+                .is_some_and(|c_list| c_list.contains(&potential_factor_name));
             let is_fix_factor = config.main.fix_factor
                 .as_ref()
-                .map_or(false, |f_list| f_list.contains(&potential_factor_name));
+                .is_some_and(|f_list| f_list.contains(&potential_factor_name));
             let is_rand_factor = config.main.rand_factor
                 .as_ref()
-                .map_or(false, |r_list| r_list.contains(&potential_factor_name));
+                .is_some_and(|r_list| r_list.contains(&potential_factor_name));
 
             if !is_covariate && (is_fix_factor || is_rand_factor) {
                 unique_true_factor_names_in_model.insert(potential_factor_name.clone());
@@ -365,7 +365,7 @@ pub fn construct_type_iii_l_matrix(
     let is_covariate_term =
         config.main.covar
             .as_ref()
-            .map_or(false, |covars| covars.iter().any(|c| c == term_of_interest)) &&
+            .is_some_and(|covars| covars.iter().any(|c| c == term_of_interest)) &&
         !term_of_interest.contains('*') &&
         term_of_interest != "Intercept";
 
@@ -379,7 +379,7 @@ pub fn construct_type_iii_l_matrix(
                 let mut coeff_prod = 1.0;
                 let mut is_pure_factor_based_param = !param_components.is_empty();
 
-                for (factor_in_param, _level_in_param) in &param_components {
+                for factor_in_param in param_components.keys() {
                     if let Some(levels) = factor_levels_map.get(factor_in_param) {
                         coeff_prod *= 1.0 / (levels.len() as f64);
                     } else {
@@ -415,14 +415,13 @@ pub fn construct_type_iii_l_matrix(
         let num_f_levels = f_levels.len();
         if num_f_levels >= 2 {
             let ref_level_f = f_levels.last().unwrap().clone();
-            for i in 0..num_f_levels - 1 {
-                let current_level_f = f_levels[i].clone();
+            for current_level_f in &f_levels[..num_f_levels - 1] {
                 let mut l_vec = DVector::from_element(p, 0.0);
 
                 for (j, param_name) in all_model_param_names.iter().enumerate() {
                     let param_components = parse_parameter_name(param_name);
                     if let Some(level_in_param_for_f) = param_components.get(term_of_interest) {
-                        let f_contrast_coeff: f64 = if level_in_param_for_f == &current_level_f {
+                        let f_contrast_coeff: f64 = if level_in_param_for_f == current_level_f {
                             1.0
                         } else if level_in_param_for_f == &ref_level_f {
                             -1.0
@@ -434,7 +433,7 @@ pub fn construct_type_iii_l_matrix(
                             let mut avg_coeff_for_other_factors = 1.0;
                             let mut is_param_structure_valid_for_avg = true;
 
-                            for (factor_in_param, _level_in_param) in &param_components {
+                            for factor_in_param in param_components.keys() {
                                 if factor_in_param != term_of_interest {
                                     if
                                         let Some(other_factor_levels) =
@@ -471,8 +470,8 @@ pub fn construct_type_iii_l_matrix(
                 }
                 let ref_level = levels.last().unwrap().clone();
                 let mut contrasts_for_this_factor = Vec::new();
-                for i in 0..levels.len() - 1 {
-                    contrasts_for_this_factor.push((levels[i].clone(), ref_level.clone()));
+                for level in &levels[..levels.len() - 1] {
+                    contrasts_for_this_factor.push((level.clone(), ref_level.clone()));
                 }
                 factor_contrast_plans.push(contrasts_for_this_factor);
             } else {
@@ -646,10 +645,11 @@ pub fn construct_type_iv_l_matrix(
                     }
                 }
                 for (other_factor, other_level) in &param_components {
-                    if !f_factors.contains(other_factor) {
-                        if cell_combo.get(other_factor) != Some(other_level) {
-                            continue 'cell_loop;
-                        }
+                    if
+                        !f_factors.contains(other_factor) &&
+                        cell_combo.get(other_factor) != Some(other_level)
+                    {
+                        continue 'cell_loop;
                     }
                 }
                 relevant_combos += 1;
@@ -716,6 +716,25 @@ pub fn sweep_matrix_on_columns(mut matrix: DMatrix<f64>, cols_to_sweep: &[usize]
     matrix
 }
 
+pub fn construct_emmeans_contrast_l_matrix(
+    emm_l_rows: &[Vec<f64>],
+    contrast_rows: &[Vec<f64>]
+) -> DMatrix<f64> {
+    let p = emm_l_rows.first().map_or(0, |row| row.len());
+    let mut l_matrix = DMatrix::zeros(contrast_rows.len(), p);
+    for (r, contrast) in contrast_rows.iter().enumerate() {
+        for (emm_idx, &coefficient) in contrast.iter().enumerate() {
+            if coefficient == 0.0 {
+                continue;
+            }
+            for (c, &value) in emm_l_rows[emm_idx].iter().enumerate() {
+                l_matrix[(r, c)] += coefficient * value;
+            }
+        }
+    }
+    l_matrix
+}
+
 fn gram_schmidt_orthogonalization(matrix: &DMatrix<f64>) -> DMatrix<f64> {
     if matrix.nrows() == 0 {
         return matrix.clone_owned();
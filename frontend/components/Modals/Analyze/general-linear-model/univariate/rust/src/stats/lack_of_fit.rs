@@ -65,7 +65,7 @@ pub fn calculate_lack_of_fit_tests(
 
     let partial_eta_squared_lof = if config.options.est_effect_size {
         if ss_error_total.abs() > 1e-9 {
-            (ss_lack_of_fit / ss_error_total).max(0.0).min(1.0)
+            (ss_lack_of_fit / ss_error_total).clamp(0.0, 1.0)
         } else {
             0.0
         }
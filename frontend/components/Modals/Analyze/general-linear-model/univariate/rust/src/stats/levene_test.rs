@@ -16,7 +16,7 @@ pub fn calculate_levene_test(
     let design_info = create_design_response_weights(data, config)?;
     let design_string = generate_design_string(&design_info);
 
-    let data_for_levene = if config.main.covar.as_ref().map_or(true, |c| c.is_empty()) {
+    let data_for_levene = if config.main.covar.as_ref().is_none_or(|c| c.is_empty()) {
         design_info.y.as_slice().to_vec()
     } else {
         let ztwz_matrix = create_cross_product_matrix(&design_info)?;
@@ -29,11 +29,8 @@ pub fn calculate_levene_test(
 
     let mut groups = create_groups_from_design_matrix(&design_info, &data_for_levene);
 
-    if config.main.covar.as_ref().map_or(true, |c| c.is_empty()) {
-        groups = groups
-            .into_iter()
-            .filter(|g| g.len() > 1)
-            .collect();
+    if config.main.covar.as_ref().is_none_or(|c| c.is_empty()) {
+        groups.retain(|g| g.len() > 1);
     }
 
     if groups.is_empty() {
@@ -62,7 +59,7 @@ pub fn calculate_levene_entries(
     config: &UnivariateConfig
 ) -> Result<Vec<LeveneTestEntry>, String> {
     let mut entries = Vec::new();
-    let has_no_covariates = config.main.covar.as_ref().map_or(true, |c| c.is_empty());
+    let has_no_covariates = config.main.covar.as_ref().is_none_or(|c| c.is_empty());
 
     if has_no_covariates {
         if let Ok((f, df1, df2, sig)) = calculate_levene_anova(groups, LeveneCenter::Mean) {
@@ -332,9 +329,7 @@ fn calculate_interpolated_trimmed_mean(group: &[f64], proportion_alpha: f64) ->
     let mut weighted_sum = 0.0;
 
     if g_usize + 1 < n - g_usize {
-        for i in g_usize + 1..n - g_usize - 1 {
-            weighted_sum += sorted_group[i];
-        }
+        weighted_sum += sorted_group[g_usize + 1..n - g_usize - 1].iter().sum::<f64>();
     }
 
     if fraction == 0.0 {
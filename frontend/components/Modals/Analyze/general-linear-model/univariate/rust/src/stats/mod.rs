@@ -4,17 +4,20 @@ pub mod common;
 pub mod contrast_coefficient;
 pub mod contrast_factors;
 pub mod core;
+pub mod custom_hypothesis;
 pub mod descriptive_statistics;
 pub mod distribution_utils;
 pub mod design_matrix;
 pub mod emmeans;
 pub mod estimable_function;
+pub mod expected_mean_squares;
 pub mod factor_utils;
 pub mod heteroscedasticity;
 pub mod hypothesis_matrix;
 pub mod lack_of_fit;
 pub mod levene_test;
 pub mod parameter_estimates;
+pub mod posthoc;
 pub mod save;
 pub mod sum_of_squares;
 pub mod summary_processing;
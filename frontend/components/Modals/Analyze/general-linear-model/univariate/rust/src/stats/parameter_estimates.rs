@@ -1,8 +1,15 @@
 use crate::models::{
     config::UnivariateConfig,
     data::AnalysisData,
-    result::{ ConfidenceInterval, ParameterEstimateEntry, ParameterEstimates },
+    result::{
+        ConfidenceInterval,
+        ParameterEstimateEntry,
+        ParameterEstimates,
+        RobustParameterEstimateEntry,
+        RobustParameterEstimates,
+    },
 };
+use nalgebra::{ DMatrix, DVector };
 use std::collections::HashMap;
 
 use super::core::*;
@@ -40,8 +47,7 @@ pub fn calculate_parameter_estimates(
     let mut estimated_params_map: HashMap<String, (usize, f64, f64)> = HashMap::with_capacity(
         design_info.p_parameters
     );
-    for i in 0..design_info.p_parameters {
-        let param_name = &all_parameter_names[i];
+    for (i, param_name) in all_parameter_names.iter().enumerate().take(design_info.p_parameters) {
         let beta_val = beta_hat_vec.get(i).cloned().unwrap_or(0.0);
         let g_ii = g_inv_matrix.get((i, i)).cloned().unwrap_or(0.0);
         estimated_params_map.insert(param_name.clone(), (i, beta_val, g_ii));
@@ -125,7 +131,7 @@ pub fn calculate_parameter_estimates(
                         let t_sq = t_val.powi(2);
                         let den = t_sq + df_error_val;
                         if den.abs() > 1e-12 {
-                            (t_sq / den).max(0.0).min(1.0)
+                            (t_sq / den).clamp(0.0, 1.0)
                         } else if t_sq.abs() < 1e-12 {
                             0.0
                         } else {
@@ -226,3 +232,181 @@ pub fn calculate_parameter_estimates(
 
     Ok(ParameterEstimates { estimates, note, interpretation })
 }
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum HcMethod {
+    Hc0,
+    Hc1,
+    Hc2,
+    Hc3,
+    Hc4,
+}
+
+impl HcMethod {
+    fn label(&self) -> &'static str {
+        match self {
+            HcMethod::Hc0 => "HC0",
+            HcMethod::Hc1 => "HC1",
+            HcMethod::Hc2 => "HC2",
+            HcMethod::Hc3 => "HC3",
+            HcMethod::Hc4 => "HC4",
+        }
+    }
+
+    // Faktor penyesuaian untuk kuadrat residual pada diagonal matriks "meat".
+    fn residual_adjustment(&self, leverage: f64, n: f64, rank: f64) -> f64 {
+        match self {
+            HcMethod::Hc0 => 1.0,
+            HcMethod::Hc1 => n / (n - rank),
+            HcMethod::Hc2 => 1.0 / (1.0 - leverage),
+            HcMethod::Hc3 => 1.0 / (1.0 - leverage).powi(2),
+            HcMethod::Hc4 => {
+                let delta = ((n * leverage) / rank).min(4.0);
+                1.0 / (1.0 - leverage).powf(delta)
+            }
+        }
+    }
+}
+
+pub fn calculate_robust_parameter_estimates(
+    data: &AnalysisData,
+    config: &UnivariateConfig
+) -> Result<Vec<RobustParameterEstimates>, String> {
+    let options = &config.options;
+    let mut methods: Vec<HcMethod> = [
+        (options.hc0, HcMethod::Hc0),
+        (options.hc1, HcMethod::Hc1),
+        (options.hc2, HcMethod::Hc2),
+        (options.hc3, HcMethod::Hc3),
+        (options.hc4, HcMethod::Hc4),
+    ]
+        .into_iter()
+        .filter(|(selected, _)| *selected)
+        .map(|(_, method)| method)
+        .collect();
+    if methods.is_empty() {
+        methods.push(HcMethod::Hc3);
+    }
+
+    let design_info = create_design_response_weights(data, config)?;
+    let ztwz_matrix = create_cross_product_matrix(&design_info)?;
+    let swept_info = perform_sweep_and_extract_results(&ztwz_matrix, design_info.p_parameters)?;
+
+    let n = design_info.n_samples;
+    let p = design_info.p_parameters;
+    let rank = design_info.r_x_rank;
+    if n <= rank {
+        return Err(
+            "Robust standard errors require more cases than estimable parameters.".to_string()
+        );
+    }
+    let df_error_usize = n - rank;
+
+    let x = &design_info.x;
+    let g_inv = &swept_info.g_inv;
+    let beta_hat = &swept_info.beta_hat;
+    let residuals = &design_info.y - x * beta_hat;
+    let weight_values = match &design_info.w {
+        Some(w) => w.clone_owned(),
+        None => DVector::from_element(n, 1.0),
+    };
+
+    let leverages: Vec<f64> = (0..n)
+        .map(|i| {
+            let x_i = x.row(i);
+            weight_values[i] * (x_i * g_inv * x_i.transpose())[(0, 0)]
+        })
+        .collect();
+
+    let all_parameter_names = generate_all_row_parameter_names_sorted(&design_info, data)?;
+    let sig_level = options.sig_level;
+    let t_crit = calculate_t_critical(Some(sig_level), df_error_usize);
+
+    let mut results = Vec::with_capacity(methods.len());
+    for method in methods {
+        // Sandwich: G (sum_i w_i^2 e_i^2 omega_i x_i x_i') G.
+        let mut meat = DMatrix::<f64>::zeros(p, p);
+        for i in 0..n {
+            let leverage = leverages[i];
+            if leverage >= 1.0 - 1e-10 {
+                continue;
+            }
+            let omega = method.residual_adjustment(leverage, n as f64, rank as f64);
+            let score_scale = (weight_values[i] * residuals[i]).powi(2) * omega;
+            let x_i = x.row(i).transpose();
+            meat += &x_i * x_i.transpose() * score_scale;
+        }
+        let robust_cov = g_inv * meat * g_inv;
+
+        let estimates = all_parameter_names
+            .iter()
+            .enumerate()
+            .take(p)
+            .map(|(i, param_name)| {
+                let g_ii = g_inv[(i, i)];
+                let is_redundant = g_ii.abs() < 1e-9 || g_ii.is_nan();
+                if is_redundant {
+                    return RobustParameterEstimateEntry {
+                        parameter: param_name.clone(),
+                        b: 0.0,
+                        robust_std_error: f64::NAN,
+                        t_value: f64::NAN,
+                        significance: f64::NAN,
+                        confidence_interval: ConfidenceInterval {
+                            lower_bound: f64::NAN,
+                            upper_bound: f64::NAN,
+                        },
+                        is_redundant: true,
+                    };
+                }
+
+                let b = beta_hat[i];
+                let robust_std_error = robust_cov[(i, i)].max(0.0).sqrt();
+                let t_value = if robust_std_error > 1e-12 { b / robust_std_error } else { f64::NAN };
+                let significance = if t_value.is_nan() {
+                    f64::NAN
+                } else {
+                    calculate_t_significance(t_value, df_error_usize)
+                };
+
+                RobustParameterEstimateEntry {
+                    parameter: param_name.clone(),
+                    b,
+                    robust_std_error,
+                    t_value,
+                    significance,
+                    confidence_interval: ConfidenceInterval {
+                        lower_bound: b - t_crit * robust_std_error,
+                        upper_bound: b + t_crit * robust_std_error,
+                    },
+                    is_redundant: false,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let mut notes = vec![
+            format!("Dependent Variable:{}", config.main.dep_var.as_ref().unwrap()),
+            format!("a. {} method", method.label()),
+            format!("b. Computed using alpha = {:.2}", sig_level)
+        ];
+        if estimates.iter().any(|e| e.is_redundant) {
+            notes.push("c. This parameter is set to zero because it is redundant.".to_string());
+        }
+        if leverages.iter().any(|&h| h >= 1.0 - 1e-10) {
+            notes.push(
+                "Cases with leverage equal to 1 do not contribute to the robust covariance matrix.".to_string()
+            );
+        }
+
+        results.push(RobustParameterEstimates {
+            hc_method: method.label().to_string(),
+            estimates,
+            note: Some(notes.join(" \n")),
+            interpretation: Some(
+                "Robust standard errors come from the heteroscedasticity-consistent sandwich covariance matrix, so the t tests and confidence intervals remain valid when the error variance differs across cases. HC3 is recommended for small samples; HC4 adds stronger protection against high-leverage cases.".to_string()
+            ),
+        });
+    }
+
+    Ok(results)
+}
@@ -0,0 +1,869 @@
+use std::collections::HashMap;
+use statrs::distribution::{ ContinuousCDF, FisherSnedecor, StudentsT };
+
+use crate::models::{
+    config::{ CategoryMethod, UnivariateConfig },
+    data::AnalysisData,
+    result::{
+        ConfidenceInterval,
+        HomogeneousSubsetGroup,
+        HomogeneousSubsets,
+        MultipleComparisons,
+        MultipleComparisonsEntry,
+        PostHocTests,
+    },
+};
+
+use super::core::*;
+
+#[derive(Debug, Clone)]
+struct LevelSummary {
+    level: String,
+    n: usize,
+    mean: f64,
+    variance: f64,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct ErrorTerm {
+    mse: f64,
+    df: f64,
+    alpha: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum PairwiseProcedure {
+    Lsd,
+    Bonferroni,
+    Sidak,
+    Scheffe,
+    Tukey,
+    HochbergGt2,
+    Gabriel,
+    Tamhane,
+    DunnettT3,
+    GamesHowell,
+    DunnettC,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum SubsetProcedure {
+    Snk,
+    Tukey,
+    TukeyB,
+    Duncan,
+    RegwF,
+    RegwQ,
+    Scheffe,
+    HochbergGt2,
+    Gabriel,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum DunnettAlternative {
+    TwoSided,
+    LessThanControl,
+    GreaterThanControl,
+}
+
+pub fn calculate_posthoc_tests(
+    data: &AnalysisData,
+    config: &UnivariateConfig
+) -> Result<PostHocTests, String> {
+    let factors = match config.posthoc.fix_factor_vars.as_ref() {
+        Some(factors) if !factors.is_empty() => factors.clone(),
+        _ => {
+            return Err("No factors selected for post hoc tests".to_string());
+        }
+    };
+
+    let design_info = create_design_response_weights(data, config)?;
+    if design_info.n_samples == 0 {
+        return Err("No valid cases available for post hoc tests".to_string());
+    }
+
+    let ztwz_matrix = create_cross_product_matrix(&design_info)?;
+    let swept_info = perform_sweep_and_extract_results(&ztwz_matrix, design_info.p_parameters)?;
+    let df_error = (design_info.n_samples as i64) - (design_info.r_x_rank as i64);
+    if df_error <= 0 {
+        return Err("Error degrees of freedom must be positive for post hoc tests".to_string());
+    }
+
+    let error = ErrorTerm {
+        mse: swept_info.s_rss / (df_error as f64),
+        df: df_error as f64,
+        alpha: config.options.sig_level,
+    };
+
+    let pairwise_procedures = selected_pairwise_procedures(config);
+    let subset_procedures = selected_subset_procedures(config);
+    let dunnett_alternative = if config.posthoc.dunnett {
+        Some(if config.posthoc.lt_control {
+            DunnettAlternative::LessThanControl
+        } else if config.posthoc.gt_control {
+            DunnettAlternative::GreaterThanControl
+        } else {
+            DunnettAlternative::TwoSided
+        })
+    } else {
+        None
+    };
+
+    if
+        pairwise_procedures.is_empty() &&
+        subset_procedures.is_empty() &&
+        dunnett_alternative.is_none() &&
+        !config.posthoc.waller
+    {
+        return Err("No post hoc procedures selected".to_string());
+    }
+
+    let mut multiple_comparisons = Vec::new();
+    let mut homogeneous_subsets = Vec::new();
+    let mut skipped_notes = Vec::new();
+
+    // Nilai t Bayes Waller-Duncan t(K, F, q, f) belum diimplementasikan
+    if config.posthoc.waller {
+        skipped_notes.push(
+            "The Waller-Duncan test is not supported; no homogeneous subsets are reported for it.".to_string()
+        );
+    }
+
+    for factor in &factors {
+        let summaries = summarize_levels(
+            data,
+            design_info.y.as_slice(),
+            &design_info.case_indices_to_keep,
+            factor
+        )?;
+
+        if summaries.len() < 3 {
+            skipped_notes.push(
+                format!(
+                    "Post hoc tests are not performed for {} because there are fewer than three groups.",
+                    factor
+                )
+            );
+            continue;
+        }
+
+        for &procedure in &pairwise_procedures {
+            multiple_comparisons.push(compare_all_pairs(procedure, factor, &summaries, error));
+        }
+
+        if let Some(alternative) = dunnett_alternative {
+            multiple_comparisons.push(
+                compare_with_control(
+                    alternative,
+                    &config.posthoc.category_method,
+                    factor,
+                    &summaries,
+                    error
+                )
+            );
+        }
+
+        for &procedure in &subset_procedures {
+            homogeneous_subsets.push(
+                form_homogeneous_subsets(procedure, factor, &summaries, error)
+            );
+        }
+    }
+
+    let mut note = format!(
+        "Based on observed means. The error term is Mean Square(Error) = {:.3} with {} degrees of freedom.",
+        error.mse,
+        df_error
+    );
+    for skipped in &skipped_notes {
+        note.push(' ');
+        note.push_str(skipped);
+    }
+
+    Ok(PostHocTests {
+        factor_names: factors,
+        multiple_comparisons,
+        homogeneous_subsets,
+        note: Some(note),
+        interpretation: Some(
+            "Post hoc tests compare the observed level means of each selected factor after the omnibus F test. Pairwise procedures report adjusted significance and simultaneous confidence intervals; homogeneous subsets group levels whose means do not differ significantly.".to_string()
+        ),
+    })
+}
+
+fn selected_pairwise_procedures(config: &UnivariateConfig) -> Vec<PairwiseProcedure> {
+    let posthoc = &config.posthoc;
+    [
+        (posthoc.tu, PairwiseProcedure::Tukey),
+        (posthoc.scheffe, PairwiseProcedure::Scheffe),
+        (posthoc.lsd, PairwiseProcedure::Lsd),
+        (posthoc.bonfe, PairwiseProcedure::Bonferroni),
+        (posthoc.sidak, PairwiseProcedure::Sidak),
+        (posthoc.hoc, PairwiseProcedure::HochbergGt2),
+        (posthoc.gabriel, PairwiseProcedure::Gabriel),
+        (posthoc.tam, PairwiseProcedure::Tamhane),
+        (posthoc.dunt, PairwiseProcedure::DunnettT3),
+        (posthoc.games, PairwiseProcedure::GamesHowell),
+        (posthoc.dunc, PairwiseProcedure::DunnettC),
+    ]
+        .into_iter()
+        .filter(|(selected, _)| *selected)
+        .map(|(_, procedure)| procedure)
+        .collect()
+}
+
+fn selected_subset_procedures(config: &UnivariateConfig) -> Vec<SubsetProcedure> {
+    let posthoc = &config.posthoc;
+    [
+        (posthoc.snk, SubsetProcedure::Snk),
+        (posthoc.tu, SubsetProcedure::Tukey),
+        (posthoc.tub, SubsetProcedure::TukeyB),
+        (posthoc.dun, SubsetProcedure::Duncan),
+        (posthoc.regwf, SubsetProcedure::RegwF),
+        (posthoc.regwq, SubsetProcedure::RegwQ),
+        (posthoc.scheffe, SubsetProcedure::Scheffe),
+        (posthoc.hoc, SubsetProcedure::HochbergGt2),
+        (posthoc.gabriel, SubsetProcedure::Gabriel),
+    ]
+        .into_iter()
+        .filter(|(selected, _)| *selected)
+        .map(|(_, procedure)| procedure)
+        .collect()
+}
+
+fn pairwise_test_name(procedure: PairwiseProcedure) -> &'static str {
+    match procedure {
+        PairwiseProcedure::Lsd => "LSD",
+        PairwiseProcedure::Bonferroni => "Bonferroni",
+        PairwiseProcedure::Sidak => "Sidak",
+        PairwiseProcedure::Scheffe => "Scheffe",
+        PairwiseProcedure::Tukey => "Tukey HSD",
+        PairwiseProcedure::HochbergGt2 => "Hochberg",
+        PairwiseProcedure::Gabriel => "Gabriel",
+        PairwiseProcedure::Tamhane => "Tamhane",
+        PairwiseProcedure::DunnettT3 => "Dunnett T3",
+        PairwiseProcedure::GamesHowell => "Games-Howell",
+        PairwiseProcedure::DunnettC => "Dunnett C",
+    }
+}
+
+fn subset_test_name(procedure: SubsetProcedure) -> &'static str {
+    match procedure {
+        SubsetProcedure::Snk => "Student-Newman-Keuls",
+        SubsetProcedure::Tukey => "Tukey HSD",
+        SubsetProcedure::TukeyB => "Tukey B",
+        SubsetProcedure::Duncan => "Duncan",
+        SubsetProcedure::RegwF => "Ryan-Einot-Gabriel-Welsch F",
+        SubsetProcedure::RegwQ => "Ryan-Einot-Gabriel-Welsch Range",
+        SubsetProcedure::Scheffe => "Scheffe",
+        SubsetProcedure::HochbergGt2 => "Hochberg",
+        SubsetProcedure::Gabriel => "Gabriel",
+    }
+}
+
+fn summarize_levels(
+    data: &AnalysisData,
+    y: &[f64],
+    case_indices: &[usize],
+    factor: &str
+) -> Result<Vec<LevelSummary>, String> {
+    let group_idx = data.fix_factor_data_defs
+        .iter()
+        .position(|def_group| def_group.iter().any(|def| def.name == factor))
+        .ok_or_else(|| format!("Post hoc factor '{}' is not a fixed factor", factor))?;
+    let records = &data.fix_factor_data[group_idx];
+
+    let mut values_by_level: HashMap<String, Vec<f64>> = HashMap::new();
+    for (row, &case_idx) in case_indices.iter().enumerate() {
+        if let Some(value) = records.get(case_idx).and_then(|r| r.values.get(factor)) {
+            values_by_level.entry(data_value_to_string(value)).or_default().push(y[row]);
+        }
+    }
+
+    let levels = get_factor_levels(data, factor)?;
+    Ok(
+        levels
+            .into_iter()
+            .filter_map(|level| {
+                let values = values_by_level.remove(&level)?;
+                let mean = calculate_mean(&values);
+                let variance = if values.len() > 1 {
+                    calculate_variance(&values, Some(mean))
+                } else {
+                    f64::NAN
+                };
+                Some(LevelSummary { level, n: values.len(), mean, variance })
+            })
+            .collect()
+    )
+}
+
+fn t_two_sided_significance(t: f64, df: f64) -> f64 {
+    StudentsT::new(0.0, 1.0, df).map_or(f64::NAN, |dist| 2.0 * dist.cdf(-t.abs()))
+}
+
+fn t_quantile(probability: f64, df: f64) -> f64 {
+    StudentsT::new(0.0, 1.0, df).map_or(f64::NAN, |dist| dist.inverse_cdf(probability))
+}
+
+fn f_upper_tail(f_value: f64, df1: f64, df2: f64) -> f64 {
+    FisherSnedecor::new(df1, df2).map_or(f64::NAN, |dist| (1.0 - dist.cdf(f_value)).max(0.0))
+}
+
+fn f_quantile(probability: f64, df1: f64, df2: f64) -> f64 {
+    FisherSnedecor::new(df1, df2).map_or(f64::NAN, |dist| dist.inverse_cdf(probability))
+}
+
+fn sidak_alpha(alpha: f64, comparisons: f64) -> f64 {
+    1.0 - (1.0 - alpha).powf(1.0 / comparisons)
+}
+
+// Derajat bebas Welch-Satterthwaite untuk dua varians kelompok.
+fn welch_df(var_a: f64, n_a: usize, var_b: f64, n_b: usize) -> f64 {
+    let v_a = var_a / (n_a as f64);
+    let v_b = var_b / (n_b as f64);
+    (v_a + v_b).powi(2) /
+        (v_a.powi(2) / ((n_a as f64) - 1.0) + v_b.powi(2) / ((n_b as f64) - 1.0))
+}
+
+// Menghasilkan (signifikansi, setengah lebar interval) untuk satu pasangan.
+fn pairwise_statistics(
+    procedure: PairwiseProcedure,
+    a: &LevelSummary,
+    b: &LevelSummary,
+    num_levels: usize,
+    error: ErrorTerm,
+    critical_cache: &mut HashMap<u64, f64>
+) -> (f64, f64, f64) {
+    let k = num_levels as f64;
+    let num_pairs = (num_levels * (num_levels - 1)) / 2;
+    let m = num_pairs as f64;
+    let diff = a.mean - b.mean;
+    let pooled_se = (error.mse * (1.0 / (a.n as f64) + 1.0 / (b.n as f64))).sqrt();
+    let t_pooled = diff / pooled_se;
+    let welch_se = (a.variance / (a.n as f64) + b.variance / (b.n as f64)).sqrt();
+    let t_welch = diff / welch_se;
+    let df_welch = welch_df(a.variance, a.n, b.variance, b.n);
+
+    let mut cached = |key: f64, compute: &dyn Fn() -> f64| -> f64 {
+        *critical_cache.entry(key.to_bits()).or_insert_with(compute)
+    };
+
+    match procedure {
+        PairwiseProcedure::Lsd => {
+            let critical = cached(error.df, &(|| t_quantile(1.0 - error.alpha / 2.0, error.df)));
+            (pooled_se, t_two_sided_significance(t_pooled, error.df), critical * pooled_se)
+        }
+        PairwiseProcedure::Bonferroni => {
+            let p = (m * t_two_sided_significance(t_pooled, error.df)).min(1.0);
+            let critical = cached(error.df, &(|| t_quantile(1.0 - error.alpha / (2.0 * m), error.df)));
+            (pooled_se, p, critical * pooled_se)
+        }
+        PairwiseProcedure::Sidak => {
+            let p = 1.0 - (1.0 - t_two_sided_significance(t_pooled, error.df)).powf(m);
+            let critical = cached(
+                error.df,
+                &(|| t_quantile(1.0 - sidak_alpha(error.alpha, m) / 2.0, error.df))
+            );
+            (pooled_se, p.clamp(0.0, 1.0), critical * pooled_se)
+        }
+        PairwiseProcedure::Scheffe => {
+            let p = f_upper_tail((t_pooled * t_pooled) / (k - 1.0), k - 1.0, error.df);
+            let critical = cached(
+                error.df,
+                &(|| ((k - 1.0) * f_quantile(1.0 - error.alpha, k - 1.0, error.df)).sqrt())
+            );
+            (pooled_se, p, critical * pooled_se)
+        }
+        PairwiseProcedure::Tukey => {
+            let q = std::f64::consts::SQRT_2 * t_pooled.abs();
+            let p = 1.0 - studentized_range_cdf(q, num_levels, error.df);
+            let critical = cached(
+                error.df,
+                &(|| studentized_range_quantile(1.0 - error.alpha, num_levels, error.df))
+            );
+            (pooled_se, p.max(0.0), (critical / std::f64::consts::SQRT_2) * pooled_se)
+        }
+        PairwiseProcedure::HochbergGt2 => {
+            let p = 1.0 - studentized_maximum_modulus_cdf(t_pooled.abs(), num_pairs, error.df);
+            let critical = cached(
+                error.df,
+                &(|| studentized_maximum_modulus_quantile(1.0 - error.alpha, num_pairs, error.df))
+            );
+            (pooled_se, p.max(0.0), critical * pooled_se)
+        }
+        PairwiseProcedure::Gabriel => {
+            // Gabriel memakai galat baku sqrt(MSE) * (1/sqrt(2 n_i) + 1/sqrt(2 n_j)).
+            let gabriel_se =
+                error.mse.sqrt() *
+                (1.0 / (2.0 * (a.n as f64)).sqrt() + 1.0 / (2.0 * (b.n as f64)).sqrt());
+            let statistic = diff.abs() / gabriel_se;
+            let p = 1.0 - studentized_maximum_modulus_cdf(statistic, num_pairs, error.df);
+            let critical = cached(
+                error.df,
+                &(|| studentized_maximum_modulus_quantile(1.0 - error.alpha, num_pairs, error.df))
+            );
+            (pooled_se, p.max(0.0), critical * gabriel_se)
+        }
+        PairwiseProcedure::Tamhane => {
+            let p = 1.0 - (1.0 - t_two_sided_significance(t_welch, df_welch)).powf(m);
+            let critical = t_quantile(1.0 - sidak_alpha(error.alpha, m) / 2.0, df_welch);
+            (welch_se, p.clamp(0.0, 1.0), critical * welch_se)
+        }
+        PairwiseProcedure::DunnettT3 => {
+            let p = 1.0 - studentized_maximum_modulus_cdf(t_welch.abs(), num_pairs, df_welch);
+            let critical = cached(
+                df_welch,
+                &(|| studentized_maximum_modulus_quantile(1.0 - error.alpha, num_pairs, df_welch))
+            );
+            (welch_se, p.max(0.0), critical * welch_se)
+        }
+        PairwiseProcedure::GamesHowell => {
+            let q = std::f64::consts::SQRT_2 * t_welch.abs();
+            let p = 1.0 - studentized_range_cdf(q, num_levels, df_welch);
+            let critical = cached(
+                df_welch,
+                &(|| studentized_range_quantile(1.0 - error.alpha, num_levels, df_welch))
+            );
+            (welch_se, p.max(0.0), (critical / std::f64::consts::SQRT_2) * welch_se)
+        }
+        PairwiseProcedure::DunnettC => {
+            // Dunnett C tidak memiliki nilai p; batas kritis menggabungkan studentized
+            // range dengan derajat bebas masing-masing kelompok (n_i - 1).
+            let v_a = a.variance / (a.n as f64);
+            let v_b = b.variance / (b.n as f64);
+            let q_a = cached((a.n - 1) as f64, &(|| {
+                studentized_range_quantile(1.0 - error.alpha, num_levels, (a.n - 1) as f64)
+            }));
+            let q_b = cached((b.n - 1) as f64, &(|| {
+                studentized_range_quantile(1.0 - error.alpha, num_levels, (b.n - 1) as f64)
+            }));
+            let half_width =
+                (q_a * v_a + q_b * v_b) / (std::f64::consts::SQRT_2 * (v_a + v_b).sqrt());
+            (welch_se, f64::NAN, half_width)
+        }
+    }
+}
+
+fn compare_all_pairs(
+    procedure: PairwiseProcedure,
+    factor: &str,
+    summaries: &[LevelSummary],
+    error: ErrorTerm
+) -> MultipleComparisons {
+    let k = summaries.len();
+    let mut critical_cache = HashMap::new();
+    let mut pair_stats = vec![vec![None; k]; k];
+
+    for i in 0..k {
+        for j in i + 1..k {
+            pair_stats[i][j] = Some(
+                pairwise_statistics(
+                    procedure,
+                    &summaries[i],
+                    &summaries[j],
+                    k,
+                    error,
+                    &mut critical_cache
+                )
+            );
+        }
+    }
+
+    let mut entries = Vec::with_capacity(k * (k - 1));
+    for (i, a) in summaries.iter().enumerate() {
+        for (j, b) in summaries.iter().enumerate() {
+            if i == j {
+                continue;
+            }
+            let (std_error, significance, half_width) = pair_stats[i.min(j)][i.max(j)].unwrap();
+            let diff = a.mean - b.mean;
+            let confidence_interval = ConfidenceInterval {
+                lower_bound: diff - half_width,
+                upper_bound: diff + half_width,
+            };
+            entries.push(MultipleComparisonsEntry {
+                i_level: a.level.clone(),
+                j_level: b.level.clone(),
+                mean_difference: diff,
+                std_error,
+                significance,
+                is_significant: confidence_interval.lower_bound > 0.0 ||
+                confidence_interval.upper_bound < 0.0,
+                confidence_interval,
+            });
+        }
+    }
+
+    let uses_group_variances = matches!(
+        procedure,
+        PairwiseProcedure::Tamhane |
+            PairwiseProcedure::DunnettT3 |
+            PairwiseProcedure::GamesHowell |
+            PairwiseProcedure::DunnettC
+    );
+    let note = if uses_group_variances {
+        format!(
+            "{}: does not assume equal variances; standard errors and degrees of freedom are computed from each pair's group variances. Confidence intervals are simultaneous at {:.0}%.",
+            pairwise_test_name(procedure),
+            (1.0 - error.alpha) * 100.0
+        )
+    } else {
+        format!(
+            "{}: based on the error term Mean Square(Error) = {:.3}. Confidence intervals are simultaneous at {:.0}%.",
+            pairwise_test_name(procedure),
+            error.mse,
+            (1.0 - error.alpha) * 100.0
+        )
+    };
+
+    MultipleComparisons {
+        factor_name: factor.to_string(),
+        test_name: pairwise_test_name(procedure).to_string(),
+        entries,
+        note: Some(note),
+        interpretation: Some(
+            "A mean difference is significant when its adjusted significance is below the chosen alpha, equivalently when the simultaneous confidence interval excludes zero.".to_string()
+        ),
+    }
+}
+
+fn compare_with_control(
+    alternative: DunnettAlternative,
+    category_method: &CategoryMethod,
+    factor: &str,
+    summaries: &[LevelSummary],
+    error: ErrorTerm
+) -> MultipleComparisons {
+    let control_idx = match category_method {
+        CategoryMethod::First => 0,
+        CategoryMethod::Last => summaries.len() - 1,
+    };
+    let control = &summaries[control_idx];
+
+    let lambdas: Vec<f64> = summaries
+        .iter()
+        .enumerate()
+        .filter(|(idx, _)| *idx != control_idx)
+        .map(|(_, s)| ((s.n as f64) / ((s.n + control.n) as f64)).sqrt())
+        .collect();
+
+    let two_sided = alternative == DunnettAlternative::TwoSided;
+    let critical = dunnett_quantile(1.0 - error.alpha, &lambdas, error.df, two_sided);
+
+    let entries = summaries
+        .iter()
+        .enumerate()
+        .filter(|(idx, _)| *idx != control_idx)
+        .map(|(_, treatment)| {
+            let diff = treatment.mean - control.mean;
+            let std_error = (
+                error.mse *
+                (1.0 / (treatment.n as f64) + 1.0 / (control.n as f64))
+            ).sqrt();
+            let t = diff / std_error;
+
+            let (significance, confidence_interval) = match alternative {
+                DunnettAlternative::TwoSided =>
+                    (
+                        1.0 - dunnett_cdf(t.abs(), &lambdas, error.df, true),
+                        ConfidenceInterval {
+                            lower_bound: diff - critical * std_error,
+                            upper_bound: diff + critical * std_error,
+                        },
+                    ),
+                DunnettAlternative::LessThanControl =>
+                    (
+                        1.0 - dunnett_cdf(-t, &lambdas, error.df, false),
+                        ConfidenceInterval {
+                            lower_bound: f64::NEG_INFINITY,
+                            upper_bound: diff + critical * std_error,
+                        },
+                    ),
+                DunnettAlternative::GreaterThanControl =>
+                    (
+                        1.0 - dunnett_cdf(t, &lambdas, error.df, false),
+                        ConfidenceInterval {
+                            lower_bound: diff - critical * std_error,
+                            upper_bound: f64::INFINITY,
+                        },
+                    ),
+            };
+
+            MultipleComparisonsEntry {
+                i_level: treatment.level.clone(),
+                j_level: control.level.clone(),
+                mean_difference: diff,
+                std_error,
+                significance: significance.max(0.0),
+                is_significant: confidence_interval.lower_bound > 0.0 ||
+                confidence_interval.upper_bound < 0.0,
+                confidence_interval,
+            }
+        })
+        .collect();
+
+    let test_name = match alternative {
+        DunnettAlternative::TwoSided => "Dunnett t (2-sided)",
+        DunnettAlternative::LessThanControl => "Dunnett t (<control)",
+        DunnettAlternative::GreaterThanControl => "Dunnett t (>control)",
+    };
+
+    MultipleComparisons {
+        factor_name: factor.to_string(),
+        test_name: test_name.to_string(),
+        entries,
+        note: Some(
+            format!(
+                "{} treats level {} as the control category and compares every other level against it.",
+                test_name,
+                control.level
+            )
+        ),
+        interpretation: Some(
+            "Dunnett's test controls the familywise error rate over the comparisons with the control only, so it is more powerful than all-pairs procedures when only those comparisons matter.".to_string()
+        ),
+    }
+}
+
+// Alpha per langkah untuk prosedur R-E-G-W dengan p rata-rata dalam rentang.
+fn regw_alpha(alpha: f64, p: usize, k: usize) -> f64 {
+    if p + 1 >= k { alpha } else { 1.0 - (1.0 - alpha).powf((p as f64) / (k as f64)) }
+}
+
+fn regw_significance(raw: f64, p: usize, k: usize) -> f64 {
+    if p + 1 >= k { raw } else { 1.0 - (1.0 - raw).powf((k as f64) / (p as f64)) }
+}
+
+// Menghasilkan (signifikan?, nilai p) untuk rentang rata-rata terurut.
+fn test_mean_range(
+    procedure: SubsetProcedure,
+    means: &[f64],
+    k: usize,
+    harmonic_n: f64,
+    error: ErrorTerm,
+    critical_values: &[f64]
+) -> (bool, Option<f64>) {
+    let p = means.len();
+    if p < 2 {
+        return (false, Some(1.0));
+    }
+
+    let range = means[p - 1] - means[0];
+    let q_observed = range / (error.mse / harmonic_n).sqrt();
+    let critical = critical_values[p];
+    let num_pairs = (k * (k - 1)) / 2;
+
+    match procedure {
+        SubsetProcedure::RegwF => {
+            let mean_of_means = means.iter().sum::<f64>() / (p as f64);
+            let ss = means
+                .iter()
+                .map(|m| (m - mean_of_means).powi(2))
+                .sum::<f64>();
+            let f_value = (harmonic_n * ss) / ((p as f64) - 1.0) / error.mse;
+            let raw = f_upper_tail(f_value, (p as f64) - 1.0, error.df);
+            (f_value > critical, Some(regw_significance(raw, p, k)))
+        }
+        SubsetProcedure::Snk => {
+            let raw = 1.0 - studentized_range_cdf(q_observed, p, error.df);
+            (q_observed > critical, Some(raw.max(0.0)))
+        }
+        SubsetProcedure::Tukey => {
+            let raw = 1.0 - studentized_range_cdf(q_observed, k, error.df);
+            (q_observed > critical, Some(raw.max(0.0)))
+        }
+        SubsetProcedure::Duncan => {
+            let raw = 1.0 - studentized_range_cdf(q_observed, p, error.df);
+            let adjusted = 1.0 - (1.0 - raw.max(0.0)).powf(1.0 / ((p as f64) - 1.0));
+            (q_observed > critical, Some(adjusted))
+        }
+        SubsetProcedure::RegwQ => {
+            let raw = 1.0 - studentized_range_cdf(q_observed, p, error.df);
+            (q_observed > critical, Some(regw_significance(raw.max(0.0), p, k)))
+        }
+        SubsetProcedure::Scheffe => {
+            let f_value = (q_observed * q_observed) / (2.0 * ((k as f64) - 1.0));
+            let raw = f_upper_tail(f_value, (k as f64) - 1.0, error.df);
+            (q_observed > critical, Some(raw))
+        }
+        SubsetProcedure::HochbergGt2 | SubsetProcedure::Gabriel => {
+            let statistic = q_observed / std::f64::consts::SQRT_2;
+            let raw = 1.0 - studentized_maximum_modulus_cdf(statistic, num_pairs, error.df);
+            (q_observed > critical, Some(raw.max(0.0)))
+        }
+        SubsetProcedure::TukeyB => (q_observed > critical, None),
+    }
+}
+
+// Nilai kritis per ukuran rentang p (indeks 2..=k). Untuk R-E-G-W F nilainya
+// berada pada skala F, selainnya pada skala studentized range.
+fn subset_critical_values(
+    procedure: SubsetProcedure,
+    summaries: &[LevelSummary],
+    error: ErrorTerm
+) -> Vec<f64> {
+    let k = summaries.len();
+    let alpha = error.alpha;
+    let tukey_q = || studentized_range_quantile(1.0 - alpha, k, error.df);
+    let mut critical = vec![f64::NAN; k + 1];
+
+    match procedure {
+        SubsetProcedure::Tukey => {
+            let q = tukey_q();
+            critical.iter_mut().skip(2).for_each(|c| {
+                *c = q;
+            });
+        }
+        SubsetProcedure::Snk => {
+            for (p, c) in critical.iter_mut().enumerate().skip(2) {
+                *c = studentized_range_quantile(1.0 - alpha, p, error.df);
+            }
+        }
+        SubsetProcedure::TukeyB => {
+            let q_k = tukey_q();
+            for (p, c) in critical.iter_mut().enumerate().skip(2) {
+                *c = (q_k + studentized_range_quantile(1.0 - alpha, p, error.df)) / 2.0;
+            }
+        }
+        SubsetProcedure::Duncan => {
+            for (p, c) in critical.iter_mut().enumerate().skip(2) {
+                let alpha_p = 1.0 - (1.0 - alpha).powi((p - 1) as i32);
+                *c = studentized_range_quantile(1.0 - alpha_p, p, error.df);
+            }
+        }
+        SubsetProcedure::RegwQ => {
+            for (p, c) in critical.iter_mut().enumerate().skip(2) {
+                *c = studentized_range_quantile(1.0 - regw_alpha(alpha, p, k), p, error.df);
+            }
+        }
+        SubsetProcedure::RegwF => {
+            for (p, c) in critical.iter_mut().enumerate().skip(2) {
+                *c = f_quantile(1.0 - regw_alpha(alpha, p, k), (p as f64) - 1.0, error.df);
+            }
+        }
+        SubsetProcedure::Scheffe => {
+            let q =
+                (2.0 * ((k as f64) - 1.0) * f_quantile(1.0 - alpha, (k as f64) - 1.0, error.df)).sqrt();
+            critical.iter_mut().skip(2).for_each(|c| {
+                *c = q;
+            });
+        }
+        SubsetProcedure::HochbergGt2 | SubsetProcedure::Gabriel => {
+            let num_pairs = (k * (k - 1)) / 2;
+            let q =
+                std::f64::consts::SQRT_2 *
+                studentized_maximum_modulus_quantile(1.0 - alpha, num_pairs, error.df);
+            critical.iter_mut().skip(2).for_each(|c| {
+                *c = q;
+            });
+        }
+    }
+
+    critical
+}
+
+fn form_homogeneous_subsets(
+    procedure: SubsetProcedure,
+    factor: &str,
+    summaries: &[LevelSummary],
+    error: ErrorTerm
+) -> HomogeneousSubsets {
+    let mut sorted: Vec<&LevelSummary> = summaries.iter().collect();
+    sorted.sort_by(|a, b| a.mean.partial_cmp(&b.mean).unwrap_or(std::cmp::Ordering::Equal));
+    let means: Vec<f64> = sorted
+        .iter()
+        .map(|s| s.mean)
+        .collect();
+
+    let k = sorted.len();
+    let harmonic_n = (k as f64) / sorted.iter().map(|s| 1.0 / (s.n as f64)).sum::<f64>();
+    let critical_values = subset_critical_values(procedure, summaries, error);
+
+    // Prosedur step-down: rentang yang termuat dalam rentang homogen dianggap homogen
+    // tanpa diuji lagi.
+    let mut homogeneous = vec![vec![false; k]; k];
+    for size in (1..=k).rev() {
+        for start in 0..=k - size {
+            let end = start + size - 1;
+            let contained =
+                (start > 0 && homogeneous[start - 1][end]) ||
+                (end + 1 < k && homogeneous[start][end + 1]);
+            homogeneous[start][end] =
+                contained ||
+                !test_mean_range(
+                    procedure,
+                    &means[start..=end],
+                    k,
+                    harmonic_n,
+                    error,
+                    &critical_values
+                ).0;
+        }
+    }
+
+    let mut subsets = Vec::new();
+    for start in 0..k {
+        for end in start..k {
+            let extends_left = start > 0 && homogeneous[start - 1][end];
+            let extends_right = end + 1 < k && homogeneous[start][end + 1];
+            if homogeneous[start][end] && !extends_left && !extends_right {
+                subsets.push((start, end));
+            }
+        }
+    }
+
+    let subset_significance = subsets
+        .iter()
+        .map(|&(start, end)| {
+            test_mean_range(
+                procedure,
+                &means[start..=end],
+                k,
+                harmonic_n,
+                error,
+                &critical_values
+            ).1
+        })
+        .collect();
+
+    let groups = sorted
+        .iter()
+        .enumerate()
+        .map(|(idx, summary)| HomogeneousSubsetGroup {
+            factor_value: summary.level.clone(),
+            n: summary.n,
+            subsets: subsets
+                .iter()
+                .enumerate()
+                .filter(|(_, &(start, end))| idx >= start && idx <= end)
+                .map(|(subset_idx, _)| (subset_idx + 1, summary.mean))
+                .collect(),
+        })
+        .collect();
+
+    let mut note = format!(
+        "Means for groups in homogeneous subsets are displayed. Uses Harmonic Mean Sample Size = {:.3}. Alpha = {:.2}.",
+        harmonic_n,
+        error.alpha
+    );
+    if sorted.iter().any(|s| s.n != sorted[0].n) {
+        note.push_str(
+            " The group sizes are unequal; the harmonic mean of the group sizes is used and Type I error levels are not guaranteed."
+        );
+    }
+
+    HomogeneousSubsets {
+        factor_name: factor.to_string(),
+        test_name: subset_test_name(procedure).to_string(),
+        groups,
+        subset_significance,
+        error_mean_square: error.mse,
+        harmonic_mean_sample_size: harmonic_n,
+        alpha: error.alpha,
+        note: Some(note),
+        interpretation: Some(
+            "Levels that share a subset column do not differ significantly from one another; levels that never appear in the same subset do.".to_string()
+        ),
+    }
+}
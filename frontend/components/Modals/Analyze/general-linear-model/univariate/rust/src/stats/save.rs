@@ -1,4 +1,8 @@
-use crate::models::{ config::UnivariateConfig, data::AnalysisData, result::SavedVariables };
+use crate::models::{
+    config::UnivariateConfig,
+    data::AnalysisData,
+    result::{ CaseDiagnosticEntry, CaseDiagnosticThresholds, CaseDiagnostics, SavedVariables },
+};
 use nalgebra::DVector;
 
 use super::core::*;
@@ -110,7 +114,7 @@ pub fn save_variables(
         }
 
         if config.save.cooks_d {
-            if se_residual.is_finite() && se_residual > 0.0 && leverage < 1.0 && leverage >= 0.0 {
+            if se_residual.is_finite() && se_residual > 0.0 && (0.0..1.0).contains(&leverage) {
                 let stud_res = residual / se_residual;
                 let cook_d = (stud_res.powi(2) / (rank as f64)) * (leverage / (1.0 - leverage));
                 result.cook_distances.push(cook_d);
@@ -126,3 +130,200 @@ pub fn save_variables(
 
     Ok(result)
 }
+
+// Diagnostik kasus (residual, leverage, Cook's D, DFBETA, DFFIT, COVRATIO) yang
+// disejajarkan dengan urutan kasus asli. Kasus yang dikeluarkan dari model (bobot
+// WLS <= 0) tetap muncul dengan nilai kosong.
+pub fn calculate_case_diagnostics(
+    data: &AnalysisData,
+    config: &UnivariateConfig
+) -> Result<CaseDiagnostics, String> {
+    let design_info = create_design_response_weights(data, config)?;
+
+    let n = design_info.n_samples;
+    let p = design_info.p_parameters;
+    let rank = design_info.r_x_rank;
+    if n <= rank + 1 {
+        return Err(
+            "Case diagnostics require at least two more cases than estimable parameters.".to_string()
+        );
+    }
+
+    let x = &design_info.x;
+    let ztwz_matrix = create_cross_product_matrix(&design_info)?;
+    let swept_info = perform_sweep_and_extract_results(&ztwz_matrix, p)?;
+    let g_inv = &swept_info.g_inv;
+    let y_hat = x * &swept_info.beta_hat;
+    let residuals = &design_info.y - &y_hat;
+
+    let df_residual = (n - rank) as f64;
+    let sse = swept_info.s_rss;
+    let mse = sse / df_residual;
+
+    let weight_values = match &design_info.w {
+        Some(w) => w.clone_owned(),
+        None => DVector::from_element(n, 1.0),
+    };
+
+    let parameter_names = generate_all_row_parameter_names_sorted(&design_info, data)?;
+    let n_f64 = n as f64;
+    let rank_f64 = rank as f64;
+    let save = &config.save;
+    let thresholds = CaseDiagnosticThresholds {
+        residual: save.residual_threshold.unwrap_or(3.0),
+        leverage: save.leverage_threshold.unwrap_or((2.0 * rank_f64) / n_f64),
+        cook_distance: save.cooks_d_threshold.unwrap_or(4.0 / n_f64),
+        standardized_dffit: save.dffit_threshold.unwrap_or(2.0 * (rank_f64 / n_f64).sqrt()),
+        standardized_dfbeta: save.dfbeta_threshold.unwrap_or(2.0 / n_f64.sqrt()),
+        covariance_ratio: save.cov_ratio_threshold.unwrap_or((3.0 * rank_f64) / n_f64),
+    };
+
+    let total_cases = data.dependent_data.first().map_or(n, |records| records.len());
+    let mut entries: Vec<CaseDiagnosticEntry> = (0..total_cases)
+        .map(|case_index| CaseDiagnosticEntry {
+            case_number: case_index + 1,
+            included: false,
+            predicted_value: None,
+            residual: None,
+            standardized_residual: None,
+            studentized_residual: None,
+            deleted_studentized_residual: None,
+            leverage: None,
+            cook_distance: None,
+            dffit: None,
+            standardized_dffit: None,
+            covariance_ratio: None,
+            dfbeta: Vec::new(),
+            standardized_dfbeta: Vec::new(),
+            flags: Vec::new(),
+        })
+        .collect();
+
+    let finite = |value: f64| if value.is_finite() { Some(value) } else { None };
+
+    for i in 0..n {
+        let weight = weight_values[i];
+        let residual = residuals[i];
+        let x_i = x.row(i);
+        let g_x = g_inv * x_i.transpose();
+        let leverage = weight * (x_i * &g_x)[(0, 0)];
+        let one_minus_h = 1.0 - leverage;
+
+        let weighted_residual = weight.sqrt() * residual;
+        let standardized_residual = weighted_residual / mse.sqrt();
+
+        // Statistik penghapusan kasus ke-i tanpa mengestimasi ulang model.
+        let s_deleted_sq = if one_minus_h > 1e-10 {
+            (sse - weighted_residual.powi(2) / one_minus_h).max(0.0) / (df_residual - 1.0)
+        } else {
+            f64::NAN
+        };
+        let studentized = standardized_residual / one_minus_h.sqrt();
+        let deleted_studentized = weighted_residual / (s_deleted_sq * one_minus_h).sqrt();
+        let cook_distance = (studentized.powi(2) / rank_f64) * (leverage / one_minus_h);
+        let dffit = (leverage * residual) / one_minus_h;
+        let standardized_dffit = deleted_studentized * (leverage / one_minus_h).sqrt();
+        let covariance_ratio = (s_deleted_sq / mse).powf(rank_f64) / one_minus_h;
+
+        let dfbeta_scale = if one_minus_h > 1e-10 {
+            (weight * residual) / one_minus_h
+        } else {
+            f64::NAN
+        };
+        let dfbeta: Vec<f64> = g_x.iter().map(|g| g * dfbeta_scale).collect();
+        let standardized_dfbeta: Vec<f64> = dfbeta
+            .iter()
+            .enumerate()
+            .map(|(j, value)| {
+                let g_jj = g_inv[(j, j)];
+                if g_jj > 1e-12 && s_deleted_sq > 0.0 {
+                    value / (s_deleted_sq * g_jj).sqrt()
+                } else {
+                    0.0
+                }
+            })
+            .collect();
+
+        let mut flags = Vec::new();
+        if deleted_studentized.abs() > thresholds.residual {
+            flags.push("Residual".to_string());
+        }
+        if leverage > thresholds.leverage {
+            flags.push("Leverage".to_string());
+        }
+        if cook_distance > thresholds.cook_distance {
+            flags.push("Cook's D".to_string());
+        }
+        if standardized_dffit.abs() > thresholds.standardized_dffit {
+            flags.push("DFFIT".to_string());
+        }
+        if standardized_dfbeta.iter().any(|value| value.abs() > thresholds.standardized_dfbeta) {
+            flags.push("DFBETA".to_string());
+        }
+        if (covariance_ratio - 1.0).abs() > thresholds.covariance_ratio {
+            flags.push("COVRATIO".to_string());
+        }
+
+        let case_index = design_info.case_indices_to_keep.get(i).copied().unwrap_or(i);
+        if let Some(entry) = entries.get_mut(case_index) {
+            entry.included = true;
+            entry.predicted_value = finite(y_hat[i]);
+            entry.residual = finite(residual);
+            entry.standardized_residual = finite(standardized_residual);
+            entry.studentized_residual = finite(studentized);
+            entry.deleted_studentized_residual = finite(deleted_studentized);
+            entry.leverage = finite(leverage);
+            entry.cook_distance = finite(cook_distance);
+            entry.dffit = finite(dffit);
+            entry.standardized_dffit = finite(standardized_dffit);
+            entry.covariance_ratio = finite(covariance_ratio);
+            entry.dfbeta = dfbeta;
+            entry.standardized_dfbeta = standardized_dfbeta;
+            entry.flags = flags;
+        }
+    }
+
+    let flagged_cases: Vec<usize> = entries
+        .iter()
+        .filter(|entry| !entry.flags.is_empty())
+        .map(|entry| entry.case_number)
+        .collect();
+    let excluded = entries
+        .iter()
+        .filter(|entry| !entry.included)
+        .count();
+
+    let mut note = format!(
+        "Thresholds: |deleted studentized residual| > {:.3}, leverage > {:.3}, Cook's D > {:.3}, |standardized DFFIT| > {:.3}, |standardized DFBETA| > {:.3}, |COVRATIO - 1| > {:.3}.",
+        thresholds.residual,
+        thresholds.leverage,
+        thresholds.cook_distance,
+        thresholds.standardized_dffit,
+        thresholds.standardized_dfbeta,
+        thresholds.covariance_ratio
+    );
+    if excluded > 0 {
+        note.push_str(
+            &format!(" {} case(s) were excluded from the model and have no diagnostic values.", excluded)
+        );
+    }
+
+    let interpretation = if flagged_cases.is_empty() {
+        "No case exceeds the influence thresholds.".to_string()
+    } else {
+        format!(
+            "{} of {} cases exceed at least one influence threshold and should be reviewed. DFBETA is the change in each parameter estimate and DFFIT the change in the predicted value when the case is deleted; COVRATIO compares the determinant of the covariance matrix of the estimates with and without the case.",
+            flagged_cases.len(),
+            n
+        )
+    };
+
+    Ok(CaseDiagnostics {
+        parameter_names,
+        thresholds,
+        entries,
+        flagged_cases,
+        note: Some(note),
+        interpretation: Some(interpretation),
+    })
+}
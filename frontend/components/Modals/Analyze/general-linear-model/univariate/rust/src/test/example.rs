@@ -0,0 +1,486 @@
+use std::collections::HashMap;
+
+use serde_json::json;
+
+use crate::models::{
+    config::{ CIMethod, CustomHypothesisSpec, UnivariateConfig },
+    data::{ AnalysisData, DataRecord, DataValue, VariableDefinition },
+};
+use crate::stats::{
+    between_subjects_effects::calculate_tests_between_subjects_effects,
+    custom_hypothesis::calculate_custom_hypothesis_tests,
+    emmeans::calculate_emmeans,
+    parameter_estimates::calculate_robust_parameter_estimates,
+    posthoc::calculate_posthoc_tests,
+    save::calculate_case_diagnostics,
+};
+
+// Straight-line fit whose residual spread grows with x
+const X: [f64; 8] = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0];
+const Y: [f64; 8] = [1.2, 1.9, 3.4, 3.1, 6.2, 4.8, 9.1, 6.5];
+
+// Robust standard errors of (intercept, slope), worked from (X'X)^-1 X' diag(e_i^2 omega_i) X (X'X)^-1
+// with leverages h_i = 1/n + (x_i - 4.5)^2 / 42
+const ROBUST_STD_ERRORS: [(&str, f64, f64); 5] = [
+    ("HC0", 0.518923515, 0.1799091054),
+    ("HC1", 0.5992012622, 0.2077411408),
+    ("HC2", 0.6332965642, 0.2205262354),
+    ("HC3", 0.7806925931, 0.2722451096),
+    ("HC4", 0.6999658462, 0.2427889606),
+];
+
+// (slice, SS, F, Sig.) for the simple-effect F tests of the fixed 2 x 3 model,
+// MS(Error) = 31/12 on 6 df
+const SIMPLE_EFFECT_TESTS: [(&str, &str, f64, f64, f64); 5] = [
+    ("a", "b=1", 20.25, 7.838709677419, 0.03117341212874),
+    ("a", "b=2", 20.25, 7.838709677419, 0.03117341212874),
+    ("a", "b=3", 30.25, 11.70967741935, 0.01411006710489),
+    ("b", "a=1", 22.33333333333, 4.322580645161, 0.06876560592072),
+    ("b", "a=2", 34.33333333333, 6.645161290323, 0.0300909071558),
+];
+
+// Bonferroni-adjusted comparisons of b within a=1: (i, j, mean difference, Sig., lower, upper)
+// with std. error sqrt(MSE) = 1.607275126832
+const SIMPLE_EFFECT_PAIRS: [(&str, &str, f64, f64, f64, f64); 3] = [
+    ("b=1", "b=2", -1.0, 1.0, -6.283844904564, 4.283844904564),
+    ("b=1", "b=3", -4.5, 0.09352023638621, -9.783844904564, 0.7838449045639),
+    ("b=2", "b=3", -3.5, 0.2169009724983, -8.783844904564, 1.783844904564),
+];
+
+// Deletion diagnostics worked by refitting the line without the case
+struct CaseDeletion {
+    case: usize,
+    leverage: f64,
+    dfbeta: [f64; 2],
+    standardized_dfbeta: [f64; 2],
+    dffit: f64,
+    standardized_dffit: f64,
+    covariance_ratio: f64,
+}
+
+const CASE_DELETIONS: [CaseDeletion; 2] = [
+    CaseDeletion {
+        case: 7,
+        leverage: 0.2738095238095,
+        dfbeta: [-0.4292740046838, 0.1788641686183],
+        standardized_dfbeta: [-0.6221462995469, 1.30903533847],
+        dffit: 0.822775175644,
+        standardized_dffit: 1.775661925016,
+        covariance_ratio: 0.2776473267596,
+    },
+    CaseDeletion {
+        case: 8,
+        leverage: 0.4166666666667,
+        dfbeta: [0.5892857142857, -0.1964285714286],
+        standardized_dfbeta: [0.6286263965843, -1.058135899448],
+        dffit: -0.9821428571429,
+        standardized_dffit: -1.264714299584,
+        covariance_ratio: 1.177584765444,
+    },
+];
+
+// One-way layout with unequal group sizes; MSE = 0.620962963 on 9 df
+const GROUPS: [i64; 12] = [1, 1, 1, 1, 2, 2, 2, 2, 2, 3, 3, 3];
+const SCORES: [f64; 12] = [4.1, 5.3, 6.0, 4.8, 6.9, 7.4, 8.1, 6.2, 7.7, 9.0, 8.4, 10.1];
+
+// (i, j, mean difference, std. error) for each pair of levels
+const POSTHOC_PAIRS: [(&str, &str, f64, f64); 3] = [
+    ("1", "2", -2.21, 0.5286145413563),
+    ("1", "3", -4.116666666667, 0.6018541310498),
+    ("2", "3", -1.906666666667, 0.5754826208615),
+];
+
+// (significance, lower bound, upper bound) per pair, worked from the pooled t and F
+// distributions with k = 3 levels
+const BONFERRONI_PAIRS: [(f64, f64, f64); 3] = [
+    (0.007120446994537, -3.760597767625, -0.6594022323748),
+    (0.0002267361225218, -5.882099886962, -2.351233446371),
+    (0.02711478513374, -3.59474370088, -0.2185896324531),
+];
+const SCHEFFE_PAIRS: [(f64, f64, f64); 3] = [
+    (0.007781523278842, -3.752340111951, -0.6676598880491),
+    (0.0002721160876264, -5.872698132392, -2.360635200942),
+    (0.02765040349472, -3.585753904048, -0.2275794292858),
+];
+
+// Custom hypotheses on the one-way layout: [g=1] - [g=2] = -1 has estimate -2.21, difference
+// -1.21 and t = -2.289002 on 9 df; [g=1] - [g=3] = [g=2] - [g=3] = 0 reproduces the one-way
+// ANOVA with SS(between) = 29.63133333333
+const CUSTOM_SINGLE_ROW: (f64, f64, f64, (f64, f64), f64) = (
+    -1.21,
+    0.5286145413563,
+    0.0478526744633,
+    (-2.405809171088, -0.01419082891155),
+    5.239532386974,
+);
+const CUSTOM_EQUAL_MEANS: (f64, f64, f64) = (29.63133333333, 23.85917929142, 0.0002525427176257);
+
+// Balanced 2 x 3 layout, two cases per cell; b is random in the mixed model and fixed in the
+// simple-effects test
+const MIXED_A: [i64; 12] = [1, 1, 1, 1, 1, 1, 2, 2, 2, 2, 2, 2];
+const MIXED_B: [i64; 12] = [1, 1, 2, 2, 3, 3, 1, 1, 2, 2, 3, 3];
+const MIXED_Y: [f64; 12] = [12.0, 14.0, 15.0, 13.0, 18.0, 17.0, 16.0, 19.0, 20.0, 17.0, 24.0, 22.0];
+
+// (source, SS, df, F, Sig.) under the unrestricted mixed model: a and b are tested against
+// MS(a*b) = 1/3 on 2 df, a*b against MS(Error) = 31/12 on 6 df
+const MIXED_EFFECTS: [(&str, f64, usize, f64, f64); 3] = [
+    ("a", 70.08333333333, 1, 210.25, 0.004722576570859),
+    ("b", 56.0, 2, 84.0, 0.01176470588235),
+    ("a*b", 0.6666666666667, 2, 0.1290322580645, 0.8813200346674),
+];
+
+// Coefficients of (Var(b), Var(a*b), Var(Error)) in each expected mean square, with n = 2 cases
+// per cell and a = 2 levels of the fixed factor
+const MIXED_EMS: [(&str, [f64; 3]); 4] = [
+    ("a", [0.0, 2.0, 1.0]),
+    ("b", [4.0, 2.0, 1.0]),
+    ("a*b", [0.0, 2.0, 1.0]),
+    ("Error", [0.0, 0.0, 1.0]),
+];
+
+fn records(name: &str, values: &[f64]) -> Vec<DataRecord> {
+    values
+        .iter()
+        .map(|&value| {
+            let mut record = HashMap::new();
+            record.insert(name.to_string(), DataValue::NumberFloat(value));
+            DataRecord { values: record }
+        })
+        .collect()
+}
+
+fn level_records(name: &str, levels: &[i64]) -> Vec<DataRecord> {
+    levels
+        .iter()
+        .map(|&level| {
+            let mut record = HashMap::new();
+            record.insert(name.to_string(), DataValue::Number(level));
+            DataRecord { values: record }
+        })
+        .collect()
+}
+
+fn definition(name: &str, column_index: usize, measure: &str) -> VariableDefinition {
+    serde_json
+        ::from_value(
+            json!({
+                "columnIndex": column_index,
+                "name": name,
+                "type": "NUMERIC",
+                "width": 8,
+                "decimals": 2,
+                "values": [],
+                "missing": [],
+                "columns": 8,
+                "align": "right",
+                "measure": measure,
+                "role": "input",
+            })
+        )
+        .unwrap()
+}
+
+fn regression_data() -> AnalysisData {
+    AnalysisData {
+        dependent_data: vec![records("y", &Y)],
+        fix_factor_data: vec![],
+        random_factor_data: None,
+        covariate_data: Some(vec![records("x", &X)]),
+        wls_data: None,
+        dependent_data_defs: vec![vec![definition("y", 0, "scale")]],
+        fix_factor_data_defs: vec![],
+        random_factor_data_defs: None,
+        covariate_data_defs: Some(vec![vec![definition("x", 1, "scale")]]),
+        wls_data_defs: None,
+    }
+}
+
+fn one_way_data() -> AnalysisData {
+    AnalysisData {
+        dependent_data: vec![records("y", &SCORES)],
+        fix_factor_data: vec![level_records("g", &GROUPS)],
+        random_factor_data: None,
+        covariate_data: None,
+        wls_data: None,
+        dependent_data_defs: vec![vec![definition("y", 0, "scale")]],
+        fix_factor_data_defs: vec![vec![definition("g", 1, "nominal")]],
+        random_factor_data_defs: None,
+        covariate_data_defs: None,
+        wls_data_defs: None,
+    }
+}
+
+fn one_way_config() -> UnivariateConfig {
+    let mut config = UnivariateConfig::default();
+    config.main.dep_var = Some("y".to_string());
+    config.main.fix_factor = Some(vec!["g".to_string()]);
+    config
+}
+
+fn mixed_data() -> AnalysisData {
+    AnalysisData {
+        dependent_data: vec![records("y", &MIXED_Y)],
+        fix_factor_data: vec![level_records("a", &MIXED_A)],
+        random_factor_data: Some(vec![level_records("b", &MIXED_B)]),
+        covariate_data: None,
+        wls_data: None,
+        dependent_data_defs: vec![vec![definition("y", 0, "scale")]],
+        fix_factor_data_defs: vec![vec![definition("a", 1, "nominal")]],
+        random_factor_data_defs: Some(vec![vec![definition("b", 2, "nominal")]]),
+        covariate_data_defs: None,
+        wls_data_defs: None,
+    }
+}
+
+fn two_way_data() -> AnalysisData {
+    AnalysisData {
+        dependent_data: vec![records("y", &MIXED_Y)],
+        fix_factor_data: vec![level_records("a", &MIXED_A), level_records("b", &MIXED_B)],
+        random_factor_data: None,
+        covariate_data: None,
+        wls_data: None,
+        dependent_data_defs: vec![vec![definition("y", 0, "scale")]],
+        fix_factor_data_defs: vec![
+            vec![definition("a", 1, "nominal")],
+            vec![definition("b", 2, "nominal")]
+        ],
+        random_factor_data_defs: None,
+        covariate_data_defs: None,
+        wls_data_defs: None,
+    }
+}
+
+// Dialog defaults with x as covariate and every HC method selected
+fn regression_config() -> UnivariateConfig {
+    let mut config = UnivariateConfig::default();
+    config.main.dep_var = Some("y".to_string());
+    config.main.covar = Some(vec!["x".to_string()]);
+    config.options.param_est_rob_std_err = true;
+    config.options.hc0 = true;
+    config.options.hc1 = true;
+    config.options.hc2 = true;
+    config.options.hc3 = true;
+    config.options.hc4 = true;
+    config
+}
+
+#[test]
+fn robust_standard_errors_hc0_to_hc4() {
+    let results = calculate_robust_parameter_estimates(
+        &regression_data(),
+        &regression_config()
+    ).unwrap();
+
+    assert_eq!(results.len(), ROBUST_STD_ERRORS.len());
+    for (result, &(method, intercept_se, slope_se)) in results.iter().zip(ROBUST_STD_ERRORS.iter()) {
+        assert_eq!(result.hc_method, method);
+
+        let intercept = &result.estimates[0];
+        let slope = &result.estimates[1];
+        assert!((intercept.b - 0.2178571429).abs() < 1e-9);
+        assert!((slope.b - 0.9571428571).abs() < 1e-9);
+        assert!((intercept.robust_std_error - intercept_se).abs() < 1e-8, "{} intercept", method);
+        assert!((slope.robust_std_error - slope_se).abs() < 1e-8, "{} slope", method);
+        assert!((slope.t_value - slope.b / slope_se).abs() < 1e-6);
+    }
+}
+
+#[test]
+fn posthoc_bonferroni_and_scheffe_pairs() {
+    let mut config = one_way_config();
+    config.posthoc.fix_factor_vars = Some(vec!["g".to_string()]);
+    config.posthoc.bonfe = true;
+    config.posthoc.scheffe = true;
+
+    let results = calculate_posthoc_tests(&one_way_data(), &config).unwrap();
+    for (test_name, adjusted) in [("Bonferroni", &BONFERRONI_PAIRS), ("Scheffe", &SCHEFFE_PAIRS)] {
+        let table = results.multiple_comparisons
+            .iter()
+            .find(|c| c.test_name == test_name)
+            .unwrap();
+
+        for (&(i, j, diff, se), &(p, lower, upper)) in POSTHOC_PAIRS.iter().zip(adjusted.iter()) {
+            let entry = table.entries
+                .iter()
+                .find(|e| e.i_level == i && e.j_level == j)
+                .unwrap();
+            assert!((entry.mean_difference - diff).abs() < 1e-9);
+            assert!((entry.std_error - se).abs() < 1e-9);
+            assert!((entry.significance - p).abs() < 1e-8, "{} {}-{} p", test_name, i, j);
+            assert!((entry.confidence_interval.lower_bound - lower).abs() < 1e-7);
+            assert!((entry.confidence_interval.upper_bound - upper).abs() < 1e-7);
+            assert!(entry.is_significant);
+        }
+    }
+}
+
+#[test]
+fn random_factor_effects_use_synthesized_error_terms() {
+    let mut config = UnivariateConfig::default();
+    config.main.dep_var = Some("y".to_string());
+    config.main.fix_factor = Some(vec!["a".to_string()]);
+    config.main.rand_factor = Some(vec!["b".to_string()]);
+
+    let results = calculate_tests_between_subjects_effects(&mixed_data(), &config).unwrap();
+    for &(source, ss, df, f_value, significance) in &MIXED_EFFECTS {
+        let effect = &results.sources
+            .iter()
+            .find(|s| s.name == source)
+            .unwrap().effect;
+        assert!((effect.sum_of_squares - ss).abs() < 1e-9, "{} SS", source);
+        assert_eq!(effect.df, df);
+        assert!((effect.f_value - f_value).abs() < 1e-8, "{} F", source);
+        assert!((effect.significance - significance).abs() < 1e-8, "{} Sig.", source);
+    }
+
+    let error_terms = results.error_terms.unwrap();
+    let composition = |source: &str| {
+        error_terms
+            .iter()
+            .find(|t| t.source == source)
+            .unwrap()
+            .composition.clone()
+    };
+    assert_eq!(composition("a"), "MS(a*b)");
+    assert_eq!(composition("b"), "MS(a*b)");
+    assert_eq!(composition("a*b"), "MS(Error)");
+
+    let ems = results.expected_mean_squares.unwrap();
+    assert_eq!(ems.variance_components, ["b", "a*b", "Error"]);
+    for (source, coefficients) in &MIXED_EMS {
+        let entry = ems.entries
+            .iter()
+            .find(|e| e.source == *source)
+            .unwrap();
+        for (actual, expected) in entry.coefficients.iter().zip(coefficients.iter()) {
+            assert!((actual - expected).abs() < 1e-9, "{} EMS", source);
+        }
+    }
+}
+
+#[test]
+fn case_diagnostics_match_refitting_without_each_case() {
+    let mut config = UnivariateConfig::default();
+    config.main.dep_var = Some("y".to_string());
+    config.main.covar = Some(vec!["x".to_string()]);
+
+    let diagnostics = calculate_case_diagnostics(&regression_data(), &config).unwrap();
+    assert_eq!(diagnostics.entries.len(), X.len());
+
+    for expected in &CASE_DELETIONS {
+        let case = expected.case;
+        let entry = &diagnostics.entries[case - 1];
+        assert_eq!(entry.case_number, case);
+        assert!((entry.leverage.unwrap() - expected.leverage).abs() < 1e-9);
+        for j in 0..2 {
+            assert!((entry.dfbeta[j] - expected.dfbeta[j]).abs() < 1e-9, "case {} DFBETA", case);
+            assert!((entry.standardized_dfbeta[j] - expected.standardized_dfbeta[j]).abs() < 1e-9);
+        }
+        assert!((entry.dffit.unwrap() - expected.dffit).abs() < 1e-9, "case {} DFFIT", case);
+        assert!((entry.standardized_dffit.unwrap() - expected.standardized_dffit).abs() < 1e-9);
+        assert!(
+            (entry.covariance_ratio.unwrap() - expected.covariance_ratio).abs() < 1e-9,
+            "case {} COVRATIO",
+            case
+        );
+    }
+}
+
+#[test]
+fn emmeans_simple_effects_within_each_level() {
+    let mut config = UnivariateConfig::default();
+    config.main.dep_var = Some("y".to_string());
+    config.main.fix_factor = Some(vec!["a".to_string(), "b".to_string()]);
+    config.emmeans.target_list = Some(vec!["a*b".to_string()]);
+    config.emmeans.comp_simple_effect = true;
+    config.emmeans.confi_interval_method = CIMethod::Bonferroni;
+
+    let results = calculate_emmeans(&two_way_data(), &config).unwrap();
+    let simple_effects = results.simple_effects.unwrap();
+    let slice = |compared: &str, fixed: &str| {
+        simple_effects
+            .iter()
+            .find(|s| s.compared_factor == compared)
+            .unwrap()
+            .slices.iter()
+            .find(|s| s.fixed_levels == [fixed])
+            .unwrap()
+    };
+
+    for &(compared, fixed, ss, f_value, significance) in &SIMPLE_EFFECT_TESTS {
+        let test = &slice(compared, fixed).test;
+        assert!((test.sum_of_squares - ss).abs() < 1e-9, "{} within {} SS", compared, fixed);
+        assert!((test.f_value - f_value).abs() < 1e-9);
+        assert!((test.significance - significance).abs() < 1e-9);
+    }
+
+    let comparisons = &slice("b", "a=1").comparisons;
+    for &(i, j, diff, significance, lower, upper) in &SIMPLE_EFFECT_PAIRS {
+        let entry = comparisons
+            .iter()
+            .find(|c| c.parameter == [i, j])
+            .unwrap();
+        assert!((entry.mean_difference[0] - diff).abs() < 1e-9);
+        assert!((entry.standard_error[0] - 1.607275126832).abs() < 1e-9);
+        assert!((entry.significance[0] - significance).abs() < 1e-8, "{} vs {} Sig.", i, j);
+        assert!((entry.confidence_interval[0].lower_bound - lower).abs() < 1e-7);
+        assert!((entry.confidence_interval[0].upper_bound - upper).abs() < 1e-7);
+    }
+}
+
+fn custom_spec(label: &str, rows: &[&[(&str, f64)]], k_matrix: Option<Vec<f64>>) -> CustomHypothesisSpec {
+    CustomHypothesisSpec {
+        label: Some(label.to_string()),
+        l_matrix: rows
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|&(name, coefficient)| (name.to_string(), coefficient))
+                    .collect()
+            })
+            .collect(),
+        k_matrix,
+    }
+}
+
+#[test]
+fn custom_hypotheses_on_one_way_layout() {
+    let mut config = one_way_config();
+    config.custom.tests = vec![
+        custom_spec("g1 vs g2", &[&[("[g=1]", 1.0), ("[g=2]", -1.0)]], Some(vec![-1.0])),
+        custom_spec(
+            "equal means",
+            &[&[("[g=1]", 1.0), ("[g=3]", -1.0)], &[("[g=2]", 1.0), ("[g=3]", -1.0)]],
+            None
+        ),
+        custom_spec("level 1 alone", &[&[("[g=1]", 1.0)]], None)
+    ];
+
+    let results = calculate_custom_hypothesis_tests(&one_way_data(), &config).unwrap();
+    assert_eq!(results.tests.len(), 3);
+
+    let (difference, std_error, significance, (lower, upper), f_value) = CUSTOM_SINGLE_ROW;
+    let single = &results.tests[0];
+    assert!(single.estimable);
+    let row = &single.contrast_result[0];
+    assert!((row.contrast_estimate - -2.21).abs() < 1e-9);
+    assert_eq!(row.hypothesized_value, -1.0);
+    assert!((row.difference - difference).abs() < 1e-9);
+    assert!((row.standard_error - std_error).abs() < 1e-9);
+    assert!((row.significance - significance).abs() < 1e-8);
+    assert!((row.confidence_interval.lower_bound - lower).abs() < 1e-7);
+    assert!((row.confidence_interval.upper_bound - upper).abs() < 1e-7);
+    assert!((single.test_result[0].f_value - f_value).abs() < 1e-8);
+
+    let (ss_between, f_between, sig_between) = CUSTOM_EQUAL_MEANS;
+    let joint = &results.tests[1].test_result[0];
+    assert_eq!(joint.df, 2);
+    assert!((joint.sum_of_squares - ss_between).abs() < 1e-9);
+    assert!((joint.f_value - f_between).abs() < 1e-8);
+    assert!((joint.significance - sig_between).abs() < 1e-9);
+    assert_eq!(results.tests[1].test_result[1].df, 9);
+
+    let alone = &results.tests[2];
+    assert!(!alone.estimable);
+    assert!(alone.contrast_result.is_empty());
+}
@@ -2,14 +2,20 @@ use wasm_bindgen::JsValue;
 use serde::Serialize;
 
 use crate::models::result::{
+    CaseDiagnostics,
     ContrastCoefficients,
+    CustomHypothesisTests,
     EMMeansResult,
+    ErrorTermEntry,
+    ExpectedMeanSquares,
     GeneralEstimableFunction,
     HeteroscedasticityTests,
     HypothesisLMatrices,
     LackOfFitTests,
     LeveneTest,
     ParameterEstimates,
+    PostHocTests,
+    RobustParameterEstimates,
     SavedVariables,
     SourceEntry,
     UnivariateResult,
@@ -38,12 +44,16 @@ struct FormatResult {
     heteroscedasticity_tests: Option<HeteroscedasticityTests>,
     tests_of_between_subjects_effects: Option<FormattedTestsBetweenSubjectsEffects>,
     parameter_estimates: Option<ParameterEstimates>,
+    robust_parameter_estimates: Option<Vec<RobustParameterEstimates>>,
     general_estimable_function: Option<GeneralEstimableFunction>,
     contrast_coefficients: Option<ContrastCoefficients>,
+    custom_hypothesis_tests: Option<CustomHypothesisTests>,
     hypothesis_l_matrices: Option<HypothesisLMatrices>,
     lack_of_fit_tests: Option<LackOfFitTests>,
     emmeans: Option<EMMeansResult>,
+    posthoc_tests: Option<PostHocTests>,
     saved_variables: Option<SavedVariables>,
+    case_diagnostics: Option<CaseDiagnostics>,
 }
 
 #[derive(Serialize)]
@@ -72,6 +82,8 @@ struct FormattedDescriptiveStatistic {
 #[derive(Serialize)]
 struct FormattedTestsBetweenSubjectsEffects {
     sources: Vec<SourceEntry>,
+    error_terms: Option<Vec<ErrorTermEntry>>,
+    expected_mean_squares: Option<ExpectedMeanSquares>,
     note: Option<String>,
     interpretation: Option<String>,
 }
@@ -102,8 +114,8 @@ impl FormatResult {
 
         let descriptive_statistics = result.descriptive_statistics.as_ref().map(|stats| {
             stats
-                .iter()
-                .map(|(_, stat)| {
+                .values()
+                .map(|stat| {
                     FormattedDescriptiveStatistic {
                         dependent_variable: stat.dependent_variable.clone(),
                         groups: stat.groups.clone(),
@@ -120,6 +132,8 @@ impl FormatResult {
             .map(|tests| {
                 FormattedTestsBetweenSubjectsEffects {
                     sources: tests.sources.clone(),
+                    error_terms: tests.error_terms.clone(),
+                    expected_mean_squares: tests.expected_mean_squares.clone(),
                     note: tests.note.clone(),
                     interpretation: tests.interpretation.clone(),
                 }
@@ -132,12 +146,16 @@ impl FormatResult {
             heteroscedasticity_tests: result.heteroscedasticity_tests.clone(),
             tests_of_between_subjects_effects,
             parameter_estimates: result.parameter_estimates.clone(),
+            robust_parameter_estimates: result.robust_parameter_estimates.clone(),
             general_estimable_function: result.general_estimable_function.clone(),
             contrast_coefficients: result.contrast_coefficients.clone(),
+            custom_hypothesis_tests: result.custom_hypothesis_tests.clone(),
             hypothesis_l_matrices: result.hypothesis_l_matrices.clone(),
             lack_of_fit_tests: result.lack_of_fit_tests.clone(),
             emmeans: result.emmeans.clone(),
+            posthoc_tests: result.posthoc_tests.clone(),
             saved_variables: result.saved_variables.clone(),
+            case_diagnostics: result.case_diagnostics.clone(),
         }
     }
 }
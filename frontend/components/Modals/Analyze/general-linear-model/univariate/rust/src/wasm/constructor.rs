@@ -20,6 +20,8 @@ pub struct UnivariateAnalysis {
 
 #[wasm_bindgen]
 impl UnivariateAnalysis {
+    // Each dataset and its variable definitions arrive as separate JS values
+    #[allow(clippy::too_many_arguments)]
     #[wasm_bindgen(constructor)]
     pub fn new(
         dep_data: JsValue,
@@ -176,8 +178,8 @@ impl UnivariateAnalysis {
 
         if
             fix_factor_data.is_empty() &&
-            random_factor_data.as_deref().map_or(true, |v| v.is_empty()) &&
-            covariate_data.as_deref().map_or(true, |v| v.is_empty())
+            random_factor_data.as_deref().is_none_or(|v| v.is_empty()) &&
+            covariate_data.as_deref().is_none_or(|v| v.is_empty())
         {
             let msg =
                 "At least one fixed factor, random factor, or covariate must be provided".to_string();
@@ -16,7 +16,7 @@ pub fn run_analysis(
 ) -> Result<Option<UnivariateResult>, JsValue> {
     logger.add_log("basic_processing_summary");
     let mut processing_summary = None;
-    match core::basic_processing_summary(&data, config) {
+    match core::basic_processing_summary(data, config) {
         Ok(summary) => {
             processing_summary = Some(summary);
         }
@@ -28,7 +28,7 @@ pub fn run_analysis(
     let mut descriptive_statistics = None;
     if config.options.desc_stats {
         logger.add_log("calculate_descriptive_statistics");
-        match core::calculate_descriptive_statistics(&data, config) {
+        match core::calculate_descriptive_statistics(data, config) {
             Ok(stats) => {
                 descriptive_statistics = Some(stats);
             }
@@ -41,7 +41,7 @@ pub fn run_analysis(
     let mut levene_test = None;
     if config.options.homogen_test {
         logger.add_log("calculate_levene_test");
-        match core::calculate_levene_test(&data, config) {
+        match core::calculate_levene_test(data, config) {
             Ok(test) => {
                 levene_test = Some(test);
             }
@@ -59,7 +59,7 @@ pub fn run_analysis(
         config.options.f_test
     {
         logger.add_log("calculate_heteroscedasticity_tests");
-        match core::calculate_heteroscedasticity_tests(&data, config) {
+        match core::calculate_heteroscedasticity_tests(data, config) {
             Ok(tests) => {
                 heteroscedasticity_tests = Some(tests);
             }
@@ -71,7 +71,7 @@ pub fn run_analysis(
 
     let mut tests_of_between_subjects_effects = None;
     logger.add_log("calculate_tests_between_subjects_effects");
-    match core::calculate_tests_between_subjects_effects(&data, config) {
+    match core::calculate_tests_between_subjects_effects(data, config) {
         Ok(tests) => {
             tests_of_between_subjects_effects = Some(tests);
         }
@@ -83,7 +83,7 @@ pub fn run_analysis(
     let mut parameter_estimates = None;
     if config.options.param_est {
         logger.add_log("calculate_parameter_estimates");
-        match core::calculate_parameter_estimates(&data, config) {
+        match core::calculate_parameter_estimates(data, config) {
             Ok(estimates) => {
                 parameter_estimates = Some(estimates);
             }
@@ -93,11 +93,31 @@ pub fn run_analysis(
         }
     }
 
+    let mut robust_parameter_estimates = None;
+    if
+        config.options.param_est_rob_std_err ||
+        config.options.hc0 ||
+        config.options.hc1 ||
+        config.options.hc2 ||
+        config.options.hc3 ||
+        config.options.hc4
+    {
+        logger.add_log("calculate_robust_parameter_estimates");
+        match core::calculate_robust_parameter_estimates(data, config) {
+            Ok(estimates) => {
+                robust_parameter_estimates = Some(estimates);
+            }
+            Err(e) => {
+                error_collector.add_error("Run Analysis : Robust Parameter Estimates", &e);
+            }
+        }
+    }
+
     let mut contrast_coefficients = None;
     if let Some(factor_list) = &config.contrast.factor_list {
         if !factor_list.is_empty() {
             logger.add_log("calculate_contrast_coefficients");
-            match core::calculate_contrast_coefficients(&data, config) {
+            match core::calculate_contrast_coefficients(data, config) {
                 Ok(coefficients) => {
                     contrast_coefficients = Some(coefficients);
                 }
@@ -108,10 +128,23 @@ pub fn run_analysis(
         }
     }
 
+    let mut custom_hypothesis_tests = None;
+    if !config.custom.tests.is_empty() {
+        logger.add_log("calculate_custom_hypothesis_tests");
+        match core::calculate_custom_hypothesis_tests(data, config) {
+            Ok(tests) => {
+                custom_hypothesis_tests = Some(tests);
+            }
+            Err(e) => {
+                error_collector.add_error("Run Analysis : Custom Hypothesis Tests", &e);
+            }
+        }
+    }
+
     let mut hypothesis_l_matrices = None;
     if config.options.coefficient_matrix {
         logger.add_log("calculate_hypothesis_l_matrices");
-        match core::calculate_hypothesis_l_matrices(&data, config) {
+        match core::calculate_hypothesis_l_matrices(data, config) {
             Ok(matrices) => {
                 hypothesis_l_matrices = Some(matrices);
             }
@@ -124,7 +157,7 @@ pub fn run_analysis(
     let mut lack_of_fit_tests = None;
     if config.options.lack_of_fit {
         logger.add_log("calculate_lack_of_fit_tests");
-        match core::calculate_lack_of_fit_tests(&data, config) {
+        match core::calculate_lack_of_fit_tests(data, config) {
             Ok(tests) => {
                 lack_of_fit_tests = Some(tests);
             }
@@ -135,9 +168,9 @@ pub fn run_analysis(
     }
 
     let mut emmeans = None;
-    if config.emmeans.target_list.as_ref().map_or(false, |v| !v.is_empty()) {
+    if config.emmeans.target_list.as_ref().is_some_and(|v| !v.is_empty()) {
         logger.add_log("calculate_emmeans");
-        match core::calculate_emmeans(&data, config) {
+        match core::calculate_emmeans(data, config) {
             Ok(means) => {
                 emmeans = Some(means);
             }
@@ -147,6 +180,19 @@ pub fn run_analysis(
         }
     }
 
+    let mut posthoc_tests = None;
+    if config.posthoc.fix_factor_vars.as_ref().is_some_and(|v| !v.is_empty()) {
+        logger.add_log("calculate_posthoc_tests");
+        match core::calculate_posthoc_tests(data, config) {
+            Ok(tests) => {
+                posthoc_tests = Some(tests);
+            }
+            Err(e) => {
+                error_collector.add_error("Run Analysis : Post Hoc Tests", &e);
+            }
+        }
+    }
+
     let mut saved_variables = None;
     if
         config.save.unstandardized_res ||
@@ -160,7 +206,7 @@ pub fn run_analysis(
         config.save.cooks_d
     {
         logger.add_log("save_variables");
-        match core::save_variables(&data, config) {
+        match core::save_variables(data, config) {
             Ok(vars) => {
                 saved_variables = Some(vars);
             }
@@ -170,10 +216,23 @@ pub fn run_analysis(
         }
     }
 
+    let mut case_diagnostics = None;
+    if config.save.case_diagnostics {
+        logger.add_log("calculate_case_diagnostics");
+        match core::calculate_case_diagnostics(data, config) {
+            Ok(diagnostics) => {
+                case_diagnostics = Some(diagnostics);
+            }
+            Err(e) => {
+                error_collector.add_error("Run Analysis : Case Diagnostics", &e);
+            }
+        }
+    }
+
     let mut general_estimable_function = None;
     if config.options.general_fun {
         logger.add_log("calculate_general_estimable_function");
-        match core::calculate_general_estimable_function(&data, config) {
+        match core::calculate_general_estimable_function(data, config) {
             Ok(gef) => {
                 general_estimable_function = Some(gef);
             }
@@ -190,12 +249,16 @@ pub fn run_analysis(
         heteroscedasticity_tests,
         tests_of_between_subjects_effects,
         parameter_estimates,
+        robust_parameter_estimates,
         general_estimable_function,
         hypothesis_l_matrices,
         contrast_coefficients,
+        custom_hypothesis_tests,
         lack_of_fit_tests,
         emmeans,
+        posthoc_tests,
         saved_variables,
+        case_diagnostics,
     };
 
     Ok(Some(result))
@@ -141,6 +141,8 @@ export type UnivariateEMMeansType = {
     SrcList: string[] | null;
     TargetList: string[] | null;
     CompMainEffect: boolean;
+    CompSimpleEffect: boolean;
+    CompInteraction: boolean;
     ConfiIntervalMethod: string | null;
 };
 
@@ -168,6 +170,13 @@ export type UnivariateSaveType = {
     CoeffStats: boolean;
     StandardStats: boolean;
     Heteroscedasticity: boolean;
+    CaseDiagnostics: boolean;
+    ResidualThreshold: number | null;
+    LeverageThreshold: number | null;
+    CooksDThreshold: number | null;
+    DffitThreshold: number | null;
+    DfbetaThreshold: number | null;
+    CovRatioThreshold: number | null;
     NewDataSet: boolean;
     FilePath: string | null;
     DatasetName: string | null;
@@ -243,6 +252,16 @@ export type UnivariateBootstrapProps = {
     data: UnivariateBootstrapType;
 };
 
+export type UnivariateCustomHypothesisType = {
+    Label: string | null;
+    LMatrix: Record<string, number>[];
+    KMatrix: number[] | null;
+};
+
+export type UnivariateCustomType = {
+    Tests: UnivariateCustomHypothesisType[];
+};
+
 export type UnivariateType = {
     main: UnivariateMainType;
     model: UnivariateModelType;
@@ -253,6 +272,7 @@ export type UnivariateType = {
     save: UnivariateSaveType;
     options: UnivariateOptionsType;
     bootstrap: UnivariateBootstrapType;
+    custom: UnivariateCustomType;
 };
 
 export type UnivariateContainerProps = {
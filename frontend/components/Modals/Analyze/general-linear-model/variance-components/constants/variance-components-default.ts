@@ -1,4 +1,5 @@
 import {
+    VarianceCompsIntervalsType,
     VarianceCompsMainType,
     VarianceCompsModelType,
     VarianceCompsOptionsType,
@@ -52,9 +53,18 @@ export const VarianceCompsSaveTypeDefault: VarianceCompsSaveType = {
     DatasetName: null,
 };
 
+export const VarianceCompsIntervalsTypeDefault: VarianceCompsIntervalsType = {
+    ConfInterval: false,
+    ConfLevel: 95,
+    WaldLogScale: false,
+    ProfileLikelihood: false,
+    Icc: false,
+};
+
 export const VarianceCompsDefault: VarianceCompsType = {
     main: VarianceCompsMainTypeDefault,
     model: VarianceCompsModelTypeDefault,
     options: VarianceCompsOptionsTypeDefault,
     save: VarianceCompsSaveTypeDefault,
+    intervals: VarianceCompsIntervalsTypeDefault,
 };
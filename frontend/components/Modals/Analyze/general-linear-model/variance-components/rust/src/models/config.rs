@@ -1,14 +1,16 @@
 use serde::{ Deserialize, Serialize };
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct VarianceCompsConfig {
     pub main: MainConfig,
     pub model: ModelConfig,
     pub options: OptionsConfig,
     pub save: SaveConfig,
+    #[serde(default)]
+    pub intervals: IntervalsConfig,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct MainConfig {
     #[serde(rename = "DepVar")]
     pub dep_var: Option<String>,
@@ -56,6 +58,20 @@ pub struct ModelConfig {
     pub intercept: bool,
 }
 
+impl Default for ModelConfig {
+    fn default() -> Self {
+        ModelConfig {
+            non_cust: true,
+            custom: false,
+            factors_var: None,
+            terms_var: None,
+            factors_model: None,
+            build_term_method: Some(BuildTermMethod::Interaction),
+            intercept: true,
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct OptionsConfig {
     #[serde(rename = "Minque")]
@@ -88,6 +104,27 @@ pub struct OptionsConfig {
     pub in_steps_of: Option<i32>,
 }
 
+impl Default for OptionsConfig {
+    fn default() -> Self {
+        OptionsConfig {
+            minque: true,
+            anova: false,
+            max_likelihood: false,
+            res_max_likelihood: false,
+            uniform: true,
+            zero: false,
+            type_i: true,
+            type_iii: false,
+            convergence_method: Some("1e-8".to_string()),
+            max_iter: Some(50),
+            sum_of_squares: false,
+            expected_mean_squares: false,
+            iteration_history: false,
+            in_steps_of: Some(1),
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct SaveConfig {
     #[serde(rename = "VarCompEst")]
@@ -107,3 +144,32 @@ pub struct SaveConfig {
     #[serde(rename = "FilePath")]
     pub file_path: Option<String>,
 }
+
+impl Default for SaveConfig {
+    fn default() -> Self {
+        SaveConfig {
+            var_comp_est: false,
+            comp_covar: false,
+            cov_matrix: false,
+            cor_matrix: false,
+            create_new_dataset: true,
+            dataset_name: None,
+            write_new_data_file: false,
+            file_path: None,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct IntervalsConfig {
+    #[serde(rename = "ConfInterval")]
+    pub conf_interval: bool,
+    #[serde(rename = "ConfLevel")]
+    pub conf_level: Option<f64>,
+    #[serde(rename = "WaldLogScale")]
+    pub wald_log_scale: bool,
+    #[serde(rename = "ProfileLikelihood")]
+    pub profile_likelihood: bool,
+    #[serde(rename = "Icc")]
+    pub icc: bool,
+}
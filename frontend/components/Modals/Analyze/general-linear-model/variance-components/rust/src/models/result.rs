@@ -8,6 +8,7 @@ pub struct VarianceComponentsResult {
     pub anova_table: Option<AnovaTable>,
     pub expected_mean_squares: Option<ExpectedMeanSquares>,
     pub method_info: Option<MethodInfo>,
+    pub variance_intervals: Option<VarianceComponentIntervals>,
     pub design: Option<String>,
     pub executed_functions: Vec<String>,
 }
@@ -26,6 +27,43 @@ pub struct VarianceComponent {
     pub estimate: f64,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ConfidenceInterval {
+    pub lower_bound: f64,
+    pub upper_bound: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct VarianceComponentIntervals {
+    pub dependent_variable: String,
+    pub method: String,
+    pub confidence_level: f64,
+    pub components: Vec<VarianceComponentInterval>,
+    pub intraclass_correlations: Option<Vec<IntraclassCorrelation>>,
+    pub notes: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct VarianceComponentInterval {
+    pub component: String,
+    pub estimate: f64,
+    pub std_error: Option<f64>,
+    pub wald: Option<ConfidenceInterval>,
+    pub wald_log: Option<ConfidenceInterval>,
+    pub profile_likelihood: Option<ConfidenceInterval>,
+    pub satterthwaite: Option<ConfidenceInterval>,
+    pub satterthwaite_df: Option<f64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct IntraclassCorrelation {
+    pub component: String,
+    pub icc: f64,
+    pub std_error: Option<f64>,
+    pub confidence_interval: Option<ConfidenceInterval>,
+    pub interval_method: Option<String>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct FactorLevelInformation {
     pub factors: Vec<FactorInfo>,
@@ -9,13 +9,27 @@ use crate::models::{
 };
 
 use super::core::{
-    from_vec,
     vec_to_vector,
     get_factor_levels,
     create_design_and_response,
     create_effect_matrices,
+    trace,
 };
 
+/// Dekomposisi jumlah kuadrat beserta koefisien expected mean squares
+pub struct SumsOfSquares {
+    /// Sumber variasi sesuai urutan model (Intercept, faktor tetap, faktor acak), diakhiri Error
+    pub sources: Vec<AnovaSource>,
+    /// Koefisien setiap komponen varians acak pada E[MS] masing-masing sumber
+    pub ems_coefficients: Vec<Vec<f64>>,
+    pub model_ss: f64,
+    pub model_df: usize,
+    pub total_ss: f64,
+    pub df_total: usize,
+    pub corrected_total_ss: f64,
+    pub df_corrected: usize,
+}
+
 /// Estimasi komponen varians menggunakan metode ANOVA
 pub fn anova_estimation(
     data: &AnalysisData,
@@ -29,70 +43,39 @@ pub fn anova_estimation(
         }
     };
 
-    // Get design matrix and response
-    let (design_matrix, y) = create_design_and_response(data, config)?;
-    let design_mat = from_vec(&design_matrix)?;
-    let y_vec = vec_to_vector(&y);
-
     // Determine sum of squares type
     let ss_type = if config.options.type_i { "Type I" } else { "Type III" };
 
     // Calculate sums of squares, degrees of freedom, and mean squares
-    let (sources, total_ss, df_total, corrected_total_ss, df_corrected) = calculate_sums_of_squares(
-        data,
-        config,
-        &design_mat,
-        &y_vec,
-        ss_type
-    )?;
+    let sums = calculate_sums_of_squares(data, config, ss_type)?;
 
     // Create ANOVA table
     let mut anova_sources = Vec::new();
-    let mut variance_components = HashMap::new();
 
     // Add corrected model
-    let model_ss: f64 = sources
-        .iter()
-        .filter(|(source, _)| *source != "Error" && *source != "Intercept")
-        .map(|(_, (ss, _, _))| *ss)
-        .sum();
-    let model_df: usize = sources
-        .iter()
-        .filter(|(source, _)| *source != "Error" && *source != "Intercept")
-        .map(|(_, (_, df, _))| *df)
-        .sum();
-    let model_ms = if model_df > 0 { model_ss / (model_df as f64) } else { 0.0 };
-
     anova_sources.push(AnovaSource {
         source: "Corrected Model".to_string(),
-        sum_of_squares: model_ss,
-        df: model_df,
-        mean_square: model_ms,
+        sum_of_squares: sums.model_ss,
+        df: sums.model_df,
+        mean_square: if sums.model_df > 0 { sums.model_ss / (sums.model_df as f64) } else { 0.0 },
     });
 
-    // Add individual sources
-    for (source, (ss, df, ms)) in &sources {
-        anova_sources.push(AnovaSource {
-            source: source.clone(),
-            sum_of_squares: *ss,
-            df: *df,
-            mean_square: *ms,
-        });
-    }
+    // Add individual sources (including error)
+    anova_sources.extend(sums.sources.iter().cloned());
 
     // Add total
     anova_sources.push(AnovaSource {
         source: "Total".to_string(),
-        sum_of_squares: total_ss,
-        df: df_total,
+        sum_of_squares: sums.total_ss,
+        df: sums.df_total,
         mean_square: 0.0, // Not applicable for total
     });
 
     // Add corrected total
     anova_sources.push(AnovaSource {
         source: "Corrected Total".to_string(),
-        sum_of_squares: corrected_total_ss,
-        df: df_corrected,
+        sum_of_squares: sums.corrected_total_ss,
+        df: sums.df_corrected,
         mean_square: 0.0, // Not applicable for corrected total
     });
 
@@ -103,31 +86,18 @@ pub fn anova_estimation(
     };
 
     // Get random factors
-    let random_factors = match &config.main.rand_factor {
-        Some(factors) => factors,
-        None => &Vec::new(),
-    };
+    let binding = Vec::new();
+    let random_factors = config.main.rand_factor.as_ref().unwrap_or(&binding);
 
-    // Find error mean square
-    let error_ms = sources
-        .iter()
-        .find(|(source, _)| *source == "Error")
-        .map(|(_, (_, _, ms))| *ms)
-        .unwrap_or(0.0);
-
-    // Calculate variance components for random effects
-    for factor in random_factors {
-        let factor_ms = sources
+    // Variance component = kombinasi linear mean square hasil penyelesaian sistem EMS
+    let coefficients = anova_component_coefficients(&sums, random_factors)?;
+    let mut variance_components = HashMap::new();
+    for (factor, weights) in random_factors.iter().zip(&coefficients) {
+        let variance: f64 = weights
             .iter()
-            .find(|(source, _)| *source == factor)
-            .map(|(_, (_, _, ms))| *ms)
-            .unwrap_or(0.0);
-
-        // Hitung koefisien dari expected mean squares
-        let coefficient = calculate_ems_coefficient(data, config, factor, &design_mat)?;
-
-        // Variance component = (MS_effect - MS_error) / coefficient
-        let variance = (factor_ms - error_ms) / coefficient;
+            .zip(&sums.sources)
+            .map(|(weight, source)| weight * source.mean_square)
+            .sum();
         variance_components.insert(format!("Var({})", factor), if variance > 0.0 {
             variance
         } else {
@@ -136,373 +106,224 @@ pub fn anova_estimation(
     }
 
     // Add error variance
+    let error_ms = sums.sources
+        .last()
+        .map(|source| source.mean_square)
+        .unwrap_or(0.0);
     variance_components.insert("Var(Error)".to_string(), error_ms);
 
     Ok((variance_components, anova_table))
 }
 
-/// Hitung koefisien untuk expected mean squares
-fn calculate_ems_coefficient(
-    data: &AnalysisData,
-    config: &VarianceCompsConfig,
-    factor: &str,
-    design_matrix: &DMatrix<f64>
-) -> Result<f64, String> {
-    // Menghitung koefisien dari expected mean squares untuk efek acak
-    // Untuk balanced design, koefisien biasanya adalah jumlah observasi per level
-    let factor_levels = get_factor_levels(data, config, factor)?;
-    let n_levels = factor_levels.len();
-
-    // Dalam kasus seimbang, koefisien adalah jumlah observasi total / jumlah level
-    let n_total = design_matrix.nrows();
-    let coefficient = (n_total as f64) / (n_levels as f64);
-
-    Ok(coefficient)
-}
+/// Hitung koefisien kombinasi linear mean square yang membentuk estimasi ANOVA
+/// untuk setiap komponen varians acak
+///
+/// Setiap baris hasil berisi satu bobot per sumber pada `sums.sources`, sehingga
+/// estimasi komponen = Σ bobot × MS. Bobot ini juga dipakai untuk derajat bebas Satterthwaite.
+pub fn anova_component_coefficients(
+    sums: &SumsOfSquares,
+    random_factors: &[String]
+) -> Result<Vec<Vec<f64>>, String> {
+    let k = random_factors.len();
+    let error_idx = sums.sources.len() - 1;
+
+    let mut rows = Vec::with_capacity(k);
+    for factor in random_factors {
+        let row = sums.sources
+            .iter()
+            .position(|source| &source.source == factor)
+            .ok_or_else(|| format!("Random factor {} not found in ANOVA decomposition", factor))?;
+        rows.push(row);
+    }
 
-/// Hitung sums of squares untuk metode ANOVA
-pub fn calculate_sums_of_squares(
-    data: &AnalysisData,
-    config: &VarianceCompsConfig,
-    design_matrix: &DMatrix<f64>,
-    y: &DVector<f64>,
-    ss_type: &str
-) -> Result<(HashMap<String, (f64, usize, f64)>, f64, usize, f64, usize), String> {
-    // Simpan sums of squares, degrees of freedom, dan mean squares untuk setiap source of variation
-    let mut sources = HashMap::new();
+    // E[MS_f] = Σⱼ cfⱼ σⱼ² + σₑ², sehingga σ² = C⁻¹ (MS_f - MS_e)
+    let c_matrix = DMatrix::from_fn(k, k, |f, j| sums.ems_coefficients[rows[f]][j]);
+    let c_inv = c_matrix
+        .try_inverse()
+        .ok_or_else(||
+            "Expected mean squares are not estimable for the random effects (check that every random factor has more than one level)".to_string()
+        )?;
+
+    Ok(
+        (0..k)
+            .map(|j| {
+                let mut weights = vec![0.0; sums.sources.len()];
+                for (f, &row) in rows.iter().enumerate() {
+                    weights[row] = c_inv[(j, f)];
+                    weights[error_idx] -= c_inv[(j, f)];
+                }
+                weights
+            })
+            .collect()
+    )
+}
 
-    // Total sum of squares
-    let total_mean = y.iter().sum::<f64>() / (y.len() as f64);
-    let total_ss = y
+/// Matriks proyeksi ortogonal ke ruang kolom gabungan beserta rank-nya
+fn projection(blocks: &[&DMatrix<f64>], n: usize) -> Result<(DMatrix<f64>, usize), String> {
+    let n_cols: usize = blocks
         .iter()
-        .map(|&yi| (yi - total_mean).powi(2))
-        .sum::<f64>();
-    let df_total = y.len();
-
-    // Dapatkan faktor dan efek
-    let mut effects = Vec::new();
-
-    // Tambahkan intercept jika disertakan
-    if config.model.intercept {
-        effects.push("Intercept".to_string());
+        .map(|block| block.ncols())
+        .sum();
+    if n_cols == 0 {
+        return Ok((DMatrix::zeros(n, n), 0));
     }
 
-    // Tambahkan fixed factors
-    if let Some(fix_factors) = &config.main.fix_factor {
-        for factor in fix_factors {
-            effects.push(factor.clone());
-        }
+    let mut x = DMatrix::zeros(n, n_cols);
+    let mut offset = 0;
+    for block in blocks {
+        x.columns_mut(offset, block.ncols()).copy_from(*block);
+        offset += block.ncols();
     }
 
-    // Tambahkan random factors
-    if let Some(rand_factors) = &config.main.rand_factor {
-        for factor in rand_factors {
-            effects.push(factor.clone());
-        }
-    }
+    let xtx_inv = (x.transpose() * &x)
+        .pseudo_inverse(1.0e-10)
+        .map_err(|e| format!("Failed to invert X'X: {}", e))?;
+    let rank = x.rank(1.0e-10);
 
-    // Buat X matriks untuk setiap efek (termasuk intercept dan faktor)
-    let mut effect_matrices = HashMap::new();
+    Ok((&x * xtx_inv * x.transpose(), rank))
+}
 
-    if config.model.intercept {
-        let intercept = DVector::from_element(y.len(), 1.0);
-        effect_matrices.insert("Intercept".to_string(), intercept);
-    }
+/// Nama efek beserta kolom-kolom matriks desainnya
+type EffectBlock = (String, DMatrix<f64>);
 
-    // Isi efek faktor
+/// Kumpulan matriks desain untuk setiap efek model (Intercept, faktor tetap, faktor acak)
+fn effect_blocks(
+    data: &AnalysisData,
+    config: &VarianceCompsConfig
+) -> Result<(Vec<EffectBlock>, usize), String> {
     let (x_matrices, _) = create_effect_matrices(data, config)?;
+    let x0 = &x_matrices[0];
+    let mut blocks = Vec::new();
+    let mut col_idx = 0;
+
+    if config.model.intercept && x0.ncols() > 0 {
+        blocks.push(("Intercept".to_string(), x0.columns(0, 1).into_owned()));
+        col_idx += 1;
+    }
 
-    // Extract fixed effect design matrices
     if let Some(fix_factors) = &config.main.fix_factor {
         for factor in fix_factors {
-            let levels = get_factor_levels(data, config, factor)?;
-            let n_levels = levels.len();
-
-            // Create dummy variable matrix for this factor (n_obs x (n_levels-1))
-            let mut factor_matrix: DMatrix<f64> = DMatrix::zeros(y.len(), n_levels - 1);
-
-            // Add to effect_matrices - simplified
-            effect_matrices.insert(factor.clone(), DVector::zeros(y.len()));
+            let n_dummy_vars = get_factor_levels(data, config, factor)?.len().saturating_sub(1);
+            let n_cols = n_dummy_vars.min(x0.ncols().saturating_sub(col_idx));
+            blocks.push((factor.clone(), x0.columns(col_idx, n_cols).into_owned()));
+            col_idx += n_cols;
         }
     }
 
-    // Extract random effect design matrices
     if let Some(rand_factors) = &config.main.rand_factor {
-        for factor in rand_factors {
-            let levels = get_factor_levels(data, config, factor)?;
-            let n_levels = levels.len();
-
-            // Create dummy variable matrix for this factor (n_obs x n_levels)
-            let mut factor_matrix: DMatrix<f64> = DMatrix::zeros(y.len(), n_levels);
-
-            // Add to effect_matrices - simplified
-            effect_matrices.insert(factor.clone(), DVector::zeros(y.len()));
+        for (factor, xi) in rand_factors.iter().zip(x_matrices.iter().skip(1)) {
+            blocks.push((factor.clone(), xi.clone()));
         }
     }
 
-    if ss_type == "Type I" {
-        // Type I: Hierarchical decomposition
-        // Each term is adjusted only for the term that precedes it in the model
-        let mut residual_ss = total_ss;
-        let mut residual_df = df_total - 1; // Adjust for mean
-
-        // Create X matrix from all effects
-        let full_model = design_matrix;
-
-        // Fit full model: β = (X'X)⁻¹X'y
-        let full_xtx = full_model.transpose() * full_model;
-        let full_xty = full_model.transpose() * y;
-
-        let full_beta = match full_xtx.try_inverse() {
-            Some(inv) => inv * full_xty,
-            None => {
-                return Err("X'X matrix is not invertible".to_string());
-            }
-        };
-
-        // Calculate fitted values: ŷ = Xβ
-        let full_fitted = full_model * full_beta;
-
-        // Calculate residual sum of squares: SSₑ = (y - ŷ)'(y - ŷ)
-        let residuals = y - full_fitted;
-        let full_rss = (residuals.transpose() * residuals)[0];
-
-        // Calculate effect sum of squares sequentially
-        let mut sequential_model = Vec::new();
-
-        for effect in &effects {
-            if effect == "Intercept" {
-                // Intercept SS = n * ȳ²
-                let intercept_ss = (y.len() as f64) * total_mean.powi(2);
-                let intercept_df = 1;
-                let intercept_ms = intercept_ss;
-
-                sources.insert("Intercept".to_string(), (intercept_ss, intercept_df, intercept_ms));
-                residual_ss -= intercept_ss;
-                residual_df -= intercept_df;
-
-                // Add intercept to sequential model
-                if let Some(intercept_matrix) = effect_matrices.get("Intercept") {
-                    sequential_model.push(intercept_matrix.clone());
-                }
-            } else {
-                // Add current effect to sequential model
-                if let Some(effect_matrix) = effect_matrices.get(effect) {
-                    sequential_model.push(effect_matrix.clone());
-                } else {
-                    return Err(format!("Effect matrix not found for {}", effect));
-                }
+    Ok((blocks, x0.nrows()))
+}
 
-                // Fit sequential model
-                let seq_model_matrix = DMatrix::from_columns(&sequential_model);
-                let seq_xtx = seq_model_matrix.transpose() * &seq_model_matrix;
-                let seq_xty = seq_model_matrix.transpose() * y;
-
-                let seq_beta = match seq_xtx.try_inverse() {
-                    Some(inv) => inv * seq_xty,
-                    None => {
-                        return Err(
-                            format!("Sequential model X'X matrix is not invertible for effect {}", effect)
-                        );
-                    }
-                };
-
-                // Calculate sequential fitted values
-                let seq_fitted = seq_model_matrix * seq_beta;
-
-                // Calculate sequential residual sum of squares
-                let seq_residuals = y - seq_fitted;
-                let seq_rss = (seq_residuals.transpose() * seq_residuals)[0];
-
-                // Calculate previous sequential RSS (without current effect)
-                let prev_seq_model_matrix = if sequential_model.len() > 1 {
-                    DMatrix::from_columns(&sequential_model[0..sequential_model.len() - 1])
-                } else {
-                    // If only intercept, create matrix with just intercept
-                    DMatrix::from_columns(&[DVector::from_element(y.len(), 1.0)])
-                };
-
-                let prev_seq_xtx = prev_seq_model_matrix.transpose() * &prev_seq_model_matrix;
-                let prev_seq_xty = prev_seq_model_matrix.transpose() * y;
-
-                let prev_seq_beta = match prev_seq_xtx.try_inverse() {
-                    Some(inv) => inv * prev_seq_xty,
-                    None => {
-                        return Err(
-                            format!("Previous sequential model X'X matrix is not invertible for effect {}", effect)
-                        );
-                    }
-                };
-
-                let prev_seq_fitted = prev_seq_model_matrix * prev_seq_beta;
-                let prev_seq_residuals = y - prev_seq_fitted;
-                let prev_seq_rss = (prev_seq_residuals.transpose() * prev_seq_residuals)[0];
-
-                // Type I SS for current effect = previous sequential RSS - current sequential RSS
-                let effect_ss = prev_seq_rss - seq_rss;
-
-                // Calculate DF for effect
-                let effect_df = get_effect_df(data, config, effect)?;
-
-                // Mean square
-                let effect_ms = effect_ss / (effect_df as f64);
-
-                sources.insert(effect.clone(), (effect_ss, effect_df, effect_ms));
-                residual_ss -= effect_ss;
-                residual_df -= effect_df;
-            }
-        }
+/// Hitung sums of squares untuk metode ANOVA
+///
+/// Type I menguji setiap efek setelah efek-efek sebelumnya dalam model, sedangkan
+/// Type III menguji setiap efek setelah semua efek lain. Koefisien EMS untuk komponen
+/// acak j pada sumber s adalah tr(Zⱼ'MₛZⱼ)/dfₛ dengan Mₛ proyeksi yang membentuk SSₛ.
+pub fn calculate_sums_of_squares(
+    data: &AnalysisData,
+    config: &VarianceCompsConfig,
+    ss_type: &str
+) -> Result<SumsOfSquares, String> {
+    let (_, y) = create_design_and_response(data, config)?;
+    let y: DVector<f64> = vec_to_vector(&y);
+    let (blocks, n) = effect_blocks(data, config)?;
+    if y.len() != n {
+        return Err("Dependent variable and design matrix have different numbers of cases".to_string());
+    }
 
-        // Add error/residual
-        let error_ms = if residual_df > 0 { residual_ss / (residual_df as f64) } else { 0.0 };
-        sources.insert("Error".to_string(), (residual_ss, residual_df, error_ms));
+    let random_blocks: Vec<&DMatrix<f64>> = match &config.main.rand_factor {
+        Some(factors) =>
+            blocks
+                .iter()
+                .filter(|(name, _)| factors.contains(name))
+                .map(|(_, block)| block)
+                .collect(),
+        None => Vec::new(),
+    };
 
-        // Corrected total = total SS - intercept SS
-        let intercept_ss = sources
-            .get("Intercept")
-            .map(|(ss, _, _)| *ss)
-            .unwrap_or(0.0);
-        let corrected_total_ss = total_ss - intercept_ss;
-        let df_corrected = df_total - 1; // Adjust for mean
+    let all_blocks: Vec<&DMatrix<f64>> = blocks
+        .iter()
+        .map(|(_, block)| block)
+        .collect();
+    let (p_full, rank_full) = projection(&all_blocks, n)?;
+
+    let mut sources = Vec::new();
+    let mut ems_coefficients = Vec::new();
+    let mut push_source = |name: &str, m_matrix: &DMatrix<f64>, df: usize| {
+        let ss = (y.transpose() * m_matrix * &y)[0].max(0.0);
+        let coefficients = random_blocks
+            .iter()
+            .map(|z| if df > 0 { trace(&(z.transpose() * m_matrix * *z)) / (df as f64) } else { 0.0 })
+            .collect();
+        sources.push(AnovaSource {
+            source: name.to_string(),
+            sum_of_squares: ss,
+            df,
+            mean_square: if df > 0 { ss / (df as f64) } else { 0.0 },
+        });
+        ems_coefficients.push(coefficients);
+    };
 
-        Ok((sources, total_ss, df_total, corrected_total_ss, df_corrected))
+    if ss_type == "Type I" {
+        // Type I: Hierarchical decomposition
+        let mut p_prev = DMatrix::zeros(n, n);
+        let mut rank_prev = 0;
+        for (idx, (name, _)) in blocks.iter().enumerate() {
+            let (p_curr, rank_curr) = projection(&all_blocks[..=idx], n)?;
+            push_source(name, &(&p_curr - &p_prev), rank_curr - rank_prev);
+            p_prev = p_curr;
+            rank_prev = rank_curr;
+        }
     } else {
         // Type III: Each effect is adjusted for all other effects
-        // Create full model
-        let full_model = design_matrix;
-
-        // Fit full model: β = (X'X)⁻¹X'y
-        let full_xtx = full_model.transpose() * full_model;
-        let full_xty = full_model.transpose() * y;
-
-        let full_beta = match full_xtx.try_inverse() {
-            Some(inv) => inv * full_xty,
-            None => {
-                return Err("X'X matrix is not invertible".to_string());
-            }
-        };
-
-        // Calculate fitted values: ŷ = Xβ
-        let full_fitted = full_model * full_beta;
-
-        // Calculate residual sum of squares: SSₑ = (y - ŷ)'(y - ŷ)
-        let residuals = y - full_fitted;
-        let full_rss = (residuals.transpose() * residuals)[0];
-
-        // For each effect, calculate Type III SS
-        for effect in &effects {
-            if effect == "Intercept" {
-                // Special handling for intercept
-                // Type III SS for intercept adjusts for all other effects
-                // We need the model without intercept to calculate this
-
-                // Create model without intercept
-                let mut reduced_model_cols = Vec::new();
-
-                for other_effect in &effects {
-                    if other_effect != "Intercept" {
-                        if let Some(effect_matrix) = effect_matrices.get(other_effect) {
-                            reduced_model_cols.push(effect_matrix.clone());
-                        }
-                    }
-                }
-
-                let reduced_model = DMatrix::from_columns(&reduced_model_cols);
-
-                // Fit reduced model
-                let reduced_xtx = reduced_model.transpose() * &reduced_model;
-                let reduced_xty = reduced_model.transpose() * y;
-
-                let reduced_beta = match reduced_xtx.try_inverse() {
-                    Some(inv) => inv * reduced_xty,
-                    None => {
-                        return Err(
-                            "Reduced model X'X matrix is not invertible for intercept".to_string()
-                        );
-                    }
-                };
-
-                // Calculate reduced fitted values
-                let reduced_fitted = reduced_model * reduced_beta;
-
-                // Calculate reduced residual sum of squares
-                let reduced_residuals = y - reduced_fitted;
-                let reduced_rss = (reduced_residuals.transpose() * reduced_residuals)[0];
-
-                // Type III SS for intercept = reduced RSS - full RSS
-                let intercept_ss = reduced_rss - full_rss;
-                let intercept_df = 1;
-                let intercept_ms = intercept_ss;
-
-                sources.insert("Intercept".to_string(), (intercept_ss, intercept_df, intercept_ms));
-            } else {
-                // For other effects, create model without this effect
-                let mut reduced_model_cols = Vec::new();
-
-                for other_effect in &effects {
-                    if other_effect != effect {
-                        if let Some(effect_matrix) = effect_matrices.get(other_effect) {
-                            reduced_model_cols.push(effect_matrix.clone());
-                        }
-                    }
-                }
-
-                let reduced_model = DMatrix::from_columns(&reduced_model_cols);
-
-                // Fit reduced model
-                let reduced_xtx = reduced_model.transpose() * &reduced_model;
-                let reduced_xty = reduced_model.transpose() * y;
-
-                let reduced_beta = match reduced_xtx.try_inverse() {
-                    Some(inv) => inv * reduced_xty,
-                    None => {
-                        return Err(
-                            format!("Reduced model X'X matrix is not invertible for effect {}", effect)
-                        );
-                    }
-                };
-
-                // Calculate reduced fitted values
-                let reduced_fitted = reduced_model * reduced_beta;
-
-                // Calculate reduced residual sum of squares
-                let reduced_residuals = y - reduced_fitted;
-                let reduced_rss = (reduced_residuals.transpose() * reduced_residuals)[0];
-
-                // Type III SS for effect = reduced RSS - full RSS
-                let effect_ss = reduced_rss - full_rss;
-
-                // Calculate DF for effect
-                let effect_df = get_effect_df(data, config, effect)?;
-
-                // Mean square
-                let effect_ms = effect_ss / (effect_df as f64);
-
-                sources.insert(effect.clone(), (effect_ss, effect_df, effect_ms));
-            }
+        for (idx, (name, _)) in blocks.iter().enumerate() {
+            let reduced: Vec<&DMatrix<f64>> = all_blocks
+                .iter()
+                .enumerate()
+                .filter(|(other, _)| *other != idx)
+                .map(|(_, block)| *block)
+                .collect();
+            let (p_reduced, rank_reduced) = projection(&reduced, n)?;
+            push_source(name, &(&p_full - &p_reduced), rank_full - rank_reduced);
         }
+    }
 
-        // Calculate error SS and DF
-        let model_df: usize = sources
-            .values()
-            .map(|(_, df, _)| *df)
-            .sum();
-        let error_df = df_total - model_df;
-        let error_ss = full_rss;
-        let error_ms = if error_df > 0 { error_ss / (error_df as f64) } else { 0.0 };
-
-        sources.insert("Error".to_string(), (error_ss, error_df, error_ms));
+    // Add error/residual
+    let error_m = DMatrix::identity(n, n) - &p_full;
+    push_source("Error", &error_m, n - rank_full);
 
-        // Corrected total = total SS - intercept SS
-        let intercept_ss = sources
-            .get("Intercept")
-            .map(|(ss, _, _)| *ss)
-            .unwrap_or(0.0);
-        let corrected_total_ss = total_ss - intercept_ss;
-        let df_corrected = df_total - 1; // Adjust for mean
+    // Corrected model = model penuh dikurangi intercept
+    let intercept_blocks: Vec<&DMatrix<f64>> = blocks
+        .iter()
+        .filter(|(name, _)| name == "Intercept")
+        .map(|(_, block)| block)
+        .collect();
+    let (p_intercept, rank_intercept) = projection(&intercept_blocks, n)?;
+    let model_ss = (y.transpose() * (&p_full - &p_intercept) * &y)[0].max(0.0);
+    let model_df = rank_full - rank_intercept;
+
+    let total_ss = y.dot(&y);
+    let mean = y.mean();
+    let corrected_total_ss = y
+        .iter()
+        .map(|&yi| (yi - mean).powi(2))
+        .sum::<f64>();
 
-        Ok((sources, total_ss, df_total, corrected_total_ss, df_corrected))
-    }
+    Ok(SumsOfSquares {
+        sources,
+        ems_coefficients,
+        model_ss,
+        model_df,
+        total_ss,
+        df_total: n,
+        corrected_total_ss,
+        df_corrected: n.saturating_sub(1),
+    })
 }
 
 /// Mendapatkan degrees of freedom untuk efek
@@ -3,6 +3,7 @@ pub use crate::stats::common::*;
 pub use crate::stats::likelihood::*;
 pub use crate::stats::design_matrix::*;
 pub use crate::stats::anova::*;
+pub use crate::stats::intervals::*;
 pub use crate::stats::minque::*;
 pub use crate::stats::ml::*;
 pub use crate::stats::reml::*;
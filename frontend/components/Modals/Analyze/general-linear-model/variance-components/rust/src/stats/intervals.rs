@@ -0,0 +1,732 @@
+// intervals.rs
+use nalgebra::{ DMatrix, DVector };
+use statrs::distribution::{ ChiSquared, ContinuousCDF, FisherSnedecor, Normal };
+
+use crate::models::{
+    config::VarianceCompsConfig,
+    data::AnalysisData,
+    result::{
+        ConfidenceInterval,
+        IntraclassCorrelation,
+        VarianceComponentInterval,
+        VarianceComponentIntervals,
+    },
+};
+
+use super::core::{
+    anova_component_coefficients,
+    calculate_ml_covariance_matrix,
+    calculate_reml_covariance_matrix,
+    calculate_sums_of_squares,
+    create_design_and_response,
+    create_effect_matrices,
+    from_vec,
+    minque_estimation,
+    ml_estimation,
+    reml_estimation,
+    vec_to_vector,
+};
+
+/// Menghitung interval kepercayaan komponen varians dan tabel intraclass correlation
+///
+/// Estimasi ANOVA memakai interval Satterthwaite, estimasi ML/REML memakai interval Wald
+/// (opsional skala log) dan profile likelihood. MINQUE hanya melaporkan estimasi titik.
+pub fn calculate_variance_component_intervals(
+    data: &AnalysisData,
+    config: &VarianceCompsConfig
+) -> Result<VarianceComponentIntervals, String> {
+    let dependent_variable = match &config.main.dep_var {
+        Some(dep_var) => dep_var.clone(),
+        None => {
+            return Err("No dependent variable specified".to_string());
+        }
+    };
+
+    let random_factors = match &config.main.rand_factor {
+        Some(factors) if !factors.is_empty() => factors.clone(),
+        _ => {
+            return Err("No random factors specified".to_string());
+        }
+    };
+
+    let confidence_level = config.intervals.conf_level.unwrap_or(95.0);
+    if confidence_level <= 0.0 || confidence_level >= 100.0 {
+        return Err(format!("Confidence level must be between 0 and 100, got {}", confidence_level));
+    }
+    let alpha = 1.0 - confidence_level / 100.0;
+
+    let mut component_names: Vec<String> = random_factors
+        .iter()
+        .map(|factor| format!("Var({})", factor))
+        .collect();
+    component_names.push("Var(Error)".to_string());
+
+    let mut notes = Vec::new();
+
+    // Urutan prioritas metode sama dengan calculate_variance_estimates
+    let (method, components, intraclass_correlations) = if config.options.anova {
+        let (components, icc) = anova_intervals(
+            data,
+            config,
+            &random_factors,
+            &component_names,
+            alpha,
+            &mut notes
+        )?;
+        ("ANOVA".to_string(), components, icc)
+    } else if
+        !config.options.minque &&
+        (config.options.max_likelihood || config.options.res_max_likelihood)
+    {
+        let reml = !config.options.max_likelihood;
+        let (components, icc) = likelihood_intervals(
+            data,
+            config,
+            &random_factors,
+            &component_names,
+            reml,
+            alpha,
+            &mut notes
+        )?;
+        let method = if reml { "Restricted Maximum Likelihood" } else { "Maximum Likelihood" };
+        (method.to_string(), components, icc)
+    } else {
+        let estimates = minque_estimation(data, config)?;
+        let components: Vec<VarianceComponentInterval> = component_names
+            .iter()
+            .map(|name| point_estimate(name, estimates.get(name).cloned().unwrap_or(0.0)))
+            .collect();
+        notes.push(
+            "MINQUE estimates have no sampling distribution available; confidence intervals are reported for ANOVA, ML and REML estimates only.".to_string()
+        );
+        let values: Vec<f64> = components
+            .iter()
+            .map(|c| c.estimate)
+            .collect();
+        let icc = random_factors
+            .iter()
+            .enumerate()
+            .map(|(i, factor)| IntraclassCorrelation {
+                component: factor.clone(),
+                icc: icc_point(&values, i),
+                std_error: None,
+                confidence_interval: None,
+                interval_method: None,
+            })
+            .collect();
+        let method = if config.options.minque && !config.options.uniform {
+            "MINQUE(0)"
+        } else {
+            "MINQUE(1)"
+        };
+        (method.to_string(), components, icc)
+    };
+
+    Ok(VarianceComponentIntervals {
+        dependent_variable,
+        method,
+        confidence_level,
+        components,
+        intraclass_correlations: if config.intervals.icc {
+            Some(intraclass_correlations)
+        } else {
+            None
+        },
+        notes,
+    })
+}
+
+/// Komponen tanpa interval (hanya estimasi titik)
+fn point_estimate(name: &str, estimate: f64) -> VarianceComponentInterval {
+    VarianceComponentInterval {
+        component: name.to_string(),
+        estimate,
+        std_error: None,
+        wald: None,
+        wald_log: None,
+        profile_likelihood: None,
+        satterthwaite: None,
+        satterthwaite_df: None,
+    }
+}
+
+/// ICC komponen acak ke-i: σᵢ² / (Σⱼ σⱼ² + σₑ²)
+fn icc_point(values: &[f64], i: usize) -> f64 {
+    let total: f64 = values
+        .iter()
+        .map(|v| v.max(0.0))
+        .sum();
+    if total > 0.0 { values[i].max(0.0) / total } else { 0.0 }
+}
+
+/// Interval Satterthwaite untuk estimasi ANOVA dan interval ICC berbasis distribusi F
+fn anova_intervals(
+    data: &AnalysisData,
+    config: &VarianceCompsConfig,
+    random_factors: &[String],
+    component_names: &[String],
+    alpha: f64,
+    notes: &mut Vec<String>
+) -> Result<(Vec<VarianceComponentInterval>, Vec<IntraclassCorrelation>), String> {
+    let ss_type = if config.options.type_i { "Type I" } else { "Type III" };
+    let sums = calculate_sums_of_squares(data, config, ss_type)?;
+    let coefficients = anova_component_coefficients(&sums, random_factors)?;
+    let error = sums.sources
+        .last()
+        .ok_or_else(|| "ANOVA decomposition has no error term".to_string())?;
+
+    let mut components = Vec::with_capacity(component_names.len());
+    for (name, weights) in component_names.iter().zip(&coefficients) {
+        // σ̂² = Σ aₛ MSₛ, ν = (σ̂²)² / Σ (aₛ MSₛ)² / dfₛ
+        let estimate: f64 = weights
+            .iter()
+            .zip(&sums.sources)
+            .map(|(weight, source)| weight * source.mean_square)
+            .sum();
+        let denominator: f64 = weights
+            .iter()
+            .zip(&sums.sources)
+            .filter(|(weight, source)| **weight != 0.0 && source.df > 0)
+            .map(|(weight, source)| (weight * source.mean_square).powi(2) / (source.df as f64))
+            .sum();
+
+        let mut component = point_estimate(name, estimate.max(0.0));
+        if estimate > 0.0 && denominator > 0.0 {
+            let df = (estimate * estimate) / denominator;
+            component.satterthwaite = chi_square_interval(estimate, df, alpha);
+            component.satterthwaite_df = Some(df);
+            if component.satterthwaite.is_none() {
+                notes.push(
+                    format!("The Satterthwaite interval for {} could not be computed with {:.3} degrees of freedom.", name, df)
+                );
+            }
+        } else {
+            notes.push(
+                format!("{} has a non-positive ANOVA estimate; no Satterthwaite interval is reported.", name)
+            );
+        }
+        components.push(component);
+    }
+
+    // Komponen error: ν = df error, interval chi-square eksak
+    let mut error_component = point_estimate("Var(Error)", error.mean_square);
+    if error.df > 0 && error.mean_square > 0.0 {
+        let df = error.df as f64;
+        error_component.satterthwaite = chi_square_interval(error.mean_square, df, alpha);
+        error_component.satterthwaite_df = Some(df);
+    }
+    components.push(error_component);
+
+    let values: Vec<f64> = components
+        .iter()
+        .map(|c| c.estimate)
+        .collect();
+    let mut icc: Vec<IntraclassCorrelation> = random_factors
+        .iter()
+        .enumerate()
+        .map(|(i, factor)| IntraclassCorrelation {
+            component: factor.clone(),
+            icc: icc_point(&values, i),
+            std_error: None,
+            confidence_interval: None,
+            interval_method: None,
+        })
+        .collect();
+
+    if config.intervals.icc {
+        if random_factors.len() == 1 {
+            // Interval eksak berbasis F = MS_f / MS_e dengan koefisien EMS c
+            let row = sums.sources
+                .iter()
+                .position(|source| source.source == random_factors[0]);
+            if let Some(row) = row {
+                let source = &sums.sources[row];
+                let c = sums.ems_coefficients[row][0];
+                if source.df > 0 && error.df > 0 && error.mean_square > 0.0 && c > 0.0 {
+                    let f_value = source.mean_square / error.mean_square;
+                    let f_dist = FisherSnedecor::new(source.df as f64, error.df as f64).map_err(|e|
+                        format!("Failed to create F distribution: {}", e)
+                    )?;
+                    let f_lower = f_value / f_dist.inverse_cdf(1.0 - alpha / 2.0);
+                    let f_upper = f_value / f_dist.inverse_cdf(alpha / 2.0);
+                    let bound = |f: f64| ((f - 1.0) / (f - 1.0 + c)).clamp(0.0, 1.0);
+                    icc[0].confidence_interval = Some(ConfidenceInterval {
+                        lower_bound: bound(f_lower),
+                        upper_bound: bound(f_upper),
+                    });
+                    icc[0].interval_method = Some("F distribution".to_string());
+                }
+            }
+        } else {
+            notes.push(
+                "Intraclass correlation intervals for ANOVA estimates are only available with a single random factor.".to_string()
+            );
+        }
+    }
+
+    Ok((components, icc))
+}
+
+/// Interval chi-square untuk estimasi varians dengan ν derajat bebas
+fn chi_square_interval(estimate: f64, df: f64, alpha: f64) -> Option<ConfidenceInterval> {
+    let chi = ChiSquared::new(df).ok()?;
+    let lower_bound = (df * estimate) / chi.inverse_cdf(1.0 - alpha / 2.0);
+    let upper_bound = (df * estimate) / chi.inverse_cdf(alpha / 2.0);
+    if lower_bound.is_finite() && upper_bound.is_finite() {
+        Some(ConfidenceInterval { lower_bound, upper_bound })
+    } else {
+        None
+    }
+}
+
+/// Interval Wald, Wald skala log dan profile likelihood untuk estimasi ML/REML
+fn likelihood_intervals(
+    data: &AnalysisData,
+    config: &VarianceCompsConfig,
+    random_factors: &[String],
+    component_names: &[String],
+    reml: bool,
+    alpha: f64,
+    notes: &mut Vec<String>
+) -> Result<(Vec<VarianceComponentInterval>, Vec<IntraclassCorrelation>), String> {
+    let (estimates, _, _) = if reml {
+        reml_estimation(data, config)?
+    } else {
+        ml_estimation(data, config)?
+    };
+    let theta: Vec<f64> = component_names
+        .iter()
+        .map(|name| estimates.get(name).cloned().unwrap_or(0.0))
+        .collect();
+    let k = random_factors.len();
+    let residual_variance = theta[k];
+    if residual_variance <= 0.0 {
+        return Err("Error variance estimate must be positive to compute intervals".to_string());
+    }
+
+    // Matriks kovarians asimtotik dari informasi Fisher
+    let (design_matrix, y) = create_design_and_response(data, config)?;
+    let design_mat = from_vec(&design_matrix)?;
+    let y_vec = vec_to_vector(&y);
+    let (x_matrices, v_matrices) = create_effect_matrices(data, config)?;
+    let variance_ratios: Vec<f64> = theta[..k]
+        .iter()
+        .map(|v| v / residual_variance)
+        .collect();
+    let covariance = if reml {
+        calculate_reml_covariance_matrix(
+            &design_mat,
+            &y_vec,
+            &variance_ratios,
+            residual_variance,
+            &x_matrices,
+            &v_matrices,
+            random_factors
+        )?
+    } else {
+        calculate_ml_covariance_matrix(
+            &design_mat,
+            &y_vec,
+            &variance_ratios,
+            residual_variance,
+            &x_matrices,
+            &v_matrices,
+            random_factors
+        )?
+    };
+    let cov_matrix = DMatrix::from_fn(k + 1, k + 1, |i, j| {
+        covariance
+            .get(&component_names[i])
+            .and_then(|row| row.get(&component_names[j]))
+            .cloned()
+            .unwrap_or(0.0)
+    });
+
+    let z = Normal::new(0.0, 1.0)
+        .map_err(|e| format!("Failed to create normal distribution: {}", e))?
+        .inverse_cdf(1.0 - alpha / 2.0);
+
+    let mut components: Vec<VarianceComponentInterval> = component_names
+        .iter()
+        .enumerate()
+        .map(|(i, name)| {
+            let mut component = point_estimate(name, theta[i]);
+            let variance = cov_matrix[(i, i)];
+            if variance.is_finite() && variance >= 0.0 {
+                let se = variance.sqrt();
+                component.std_error = Some(se);
+                component.wald = Some(ConfidenceInterval {
+                    lower_bound: theta[i] - z * se,
+                    upper_bound: theta[i] + z * se,
+                });
+                // Skala log: ln σ̂² ± z·se/σ̂² lalu ditransformasi balik
+                if config.intervals.wald_log_scale && theta[i] > 0.0 {
+                    let log_se = se / theta[i];
+                    component.wald_log = Some(ConfidenceInterval {
+                        lower_bound: theta[i] * (-z * log_se).exp(),
+                        upper_bound: theta[i] * (z * log_se).exp(),
+                    });
+                }
+            }
+            component
+        })
+        .collect();
+
+    for component in &components {
+        if component.estimate <= 0.0 && component.component != "Var(Error)" {
+            notes.push(
+                format!(
+                    "{} is estimated on the boundary of the parameter space; Wald intervals are unreliable for it.",
+                    component.component
+                )
+            );
+        }
+    }
+
+    if config.intervals.profile_likelihood {
+        let fixed = x_matrices[0].clone();
+        let model = ProfileModel {
+            y: &y_vec,
+            x0: &fixed,
+            v_matrices: &v_matrices,
+            reml,
+        };
+        let max_iter = config.options.max_iter.unwrap_or(50).max(1) as usize;
+        match model.maximize(theta.clone(), &vec![true; k + 1], max_iter) {
+            Ok((theta_hat, log_likelihood_max)) => {
+                let critical = ChiSquared::new(1.0)
+                    .map_err(|e| format!("Failed to create chi-square distribution: {}", e))?
+                    .inverse_cdf(1.0 - alpha);
+                for (i, component) in components.iter_mut().enumerate() {
+                    let scale = component.std_error.unwrap_or(theta_hat[i].abs());
+                    match model.profile_interval(&theta_hat, log_likelihood_max, i, critical, scale, max_iter) {
+                        Ok(interval) => {
+                            component.profile_likelihood = Some(interval);
+                        }
+                        Err(e) => {
+                            notes.push(format!("Profile likelihood interval for {}: {}", component.component, e));
+                        }
+                    }
+                }
+            }
+            Err(e) => {
+                notes.push(format!("Profile likelihood intervals are not available: {}", e));
+            }
+        }
+    }
+
+    // ICC dengan metode delta: ∂ρᵢ/∂σᵢ² = (T - σᵢ²)/T², ∂ρᵢ/∂σⱼ² = -σᵢ²/T²
+    let total: f64 = theta
+        .iter()
+        .map(|v| v.max(0.0))
+        .sum();
+    let icc = random_factors
+        .iter()
+        .enumerate()
+        .map(|(i, factor)| {
+            let rho = icc_point(&theta, i);
+            let gradient = DVector::from_fn(k + 1, |j, _| {
+                if total <= 0.0 {
+                    0.0
+                } else if j == i {
+                    (total - theta[i].max(0.0)) / (total * total)
+                } else {
+                    -theta[i].max(0.0) / (total * total)
+                }
+            });
+            let variance = (gradient.transpose() * &cov_matrix * &gradient)[0];
+            let std_error = if variance.is_finite() && variance >= 0.0 {
+                Some(variance.sqrt())
+            } else {
+                None
+            };
+
+            let (confidence_interval, interval_method) = match std_error {
+                Some(se) if config.intervals.wald_log_scale && rho > 0.0 && rho < 1.0 => {
+                    // Skala logit: logit(ρ) ± z·se/(ρ(1-ρ))
+                    let logit = (rho / (1.0 - rho)).ln();
+                    let logit_se = se / (rho * (1.0 - rho));
+                    let inv_logit = |x: f64| 1.0 / (1.0 + (-x).exp());
+                    (
+                        Some(ConfidenceInterval {
+                            lower_bound: inv_logit(logit - z * logit_se),
+                            upper_bound: inv_logit(logit + z * logit_se),
+                        }),
+                        Some("Wald (logit scale)".to_string()),
+                    )
+                }
+                Some(se) =>
+                    (
+                        Some(ConfidenceInterval {
+                            lower_bound: (rho - z * se).max(0.0),
+                            upper_bound: (rho + z * se).min(1.0),
+                        }),
+                        Some("Wald".to_string()),
+                    ),
+                None => (None, None),
+            };
+
+            IntraclassCorrelation {
+                component: factor.clone(),
+                icc: rho,
+                std_error,
+                confidence_interval,
+                interval_method,
+            }
+        })
+        .collect();
+
+    Ok((components, icc))
+}
+
+/// Log-likelihood ML/REML dalam parameterisasi komponen varians θ, V = Σ θᵢVᵢ
+///
+/// `v_matrices` mengikuti create_effect_matrices: ZᵢZᵢ' untuk setiap faktor acak
+/// diikuti matriks identitas untuk komponen error.
+struct ProfileModel<'a> {
+    y: &'a DVector<f64>,
+    x0: &'a DMatrix<f64>,
+    v_matrices: &'a [DMatrix<f64>],
+    reml: bool,
+}
+
+impl ProfileModel<'_> {
+    /// Log-likelihood (tanpa konstanta), score dan informasi Fisher pada θ
+    fn evaluate(&self, theta: &[f64]) -> Result<(f64, DVector<f64>, DMatrix<f64>), String> {
+        let n = self.y.len();
+        let m = theta.len();
+
+        let mut v = DMatrix::zeros(n, n);
+        for (theta_i, v_i) in theta.iter().zip(self.v_matrices) {
+            v += *theta_i * v_i;
+        }
+        let cholesky = v
+            .cholesky()
+            .ok_or_else(|| "V matrix is not positive definite".to_string())?;
+        let log_det_v =
+            2.0 *
+            cholesky
+                .l()
+                .diagonal()
+                .iter()
+                .map(|d| d.ln())
+                .sum::<f64>();
+        let v_inv = cholesky.inverse();
+
+        // P = V⁻¹ - V⁻¹X₀(X₀'V⁻¹X₀)⁻¹X₀'V⁻¹
+        let (p_matrix, log_det_xvx) = if self.x0.ncols() > 0 {
+            let v_inv_x = &v_inv * self.x0;
+            let xvx = self.x0.transpose() * &v_inv_x;
+            let xvx_chol = xvx
+                .cholesky()
+                .ok_or_else(|| "X₀'V⁻¹X₀ is not positive definite".to_string())?;
+            let log_det =
+                2.0 *
+                xvx_chol
+                    .l()
+                    .diagonal()
+                    .iter()
+                    .map(|d| d.ln())
+                    .sum::<f64>();
+            (&v_inv - &v_inv_x * xvx_chol.inverse() * v_inv_x.transpose(), log_det)
+        } else {
+            (v_inv.clone(), 0.0)
+        };
+
+        let py = &p_matrix * self.y;
+        let quad = self.y.dot(&py);
+        let log_likelihood = if self.reml {
+            -0.5 * (log_det_v + log_det_xvx + quad)
+        } else {
+            -0.5 * (log_det_v + quad)
+        };
+
+        let a_matrix = if self.reml { &p_matrix } else { &v_inv };
+        let a_v: Vec<DMatrix<f64>> = self.v_matrices
+            .iter()
+            .map(|v_i| a_matrix * v_i)
+            .collect();
+
+        let mut score = DVector::zeros(m);
+        let mut information = DMatrix::zeros(m, m);
+        for i in 0..m {
+            score[i] = -0.5 * a_v[i].trace() + 0.5 * py.dot(&(&self.v_matrices[i] * &py));
+            for j in 0..=i {
+                let value = 0.5 * a_v[i].component_mul(&a_v[j].transpose()).sum();
+                information[(i, j)] = value;
+                information[(j, i)] = value;
+            }
+        }
+
+        Ok((log_likelihood, score, information))
+    }
+
+    /// Fisher scoring dengan step-halving atas parameter yang bebas
+    fn maximize(
+        &self,
+        mut theta: Vec<f64>,
+        free: &[bool],
+        max_iter: usize
+    ) -> Result<(Vec<f64>, f64), String> {
+        let m = theta.len();
+        let error_idx = m - 1;
+        let floor = |i: usize, value: f64| {
+            if i == error_idx { value.max(1.0e-10) } else { value.max(0.0) }
+        };
+        for (i, value) in theta.iter_mut().enumerate() {
+            *value = floor(i, *value);
+        }
+
+        let (mut log_likelihood, mut score, mut information) = self.evaluate(&theta)?;
+        for _ in 0..max_iter.max(1) {
+            // Parameter pada batas nol dengan score negatif tetap di batas
+            let active: Vec<usize> = (0..m)
+                .filter(|&i| free[i] && !(i != error_idx && theta[i] <= 0.0 && score[i] <= 0.0))
+                .collect();
+            if active.is_empty() {
+                break;
+            }
+
+            let info_active = DMatrix::from_fn(active.len(), active.len(), |a, b| {
+                information[(active[a], active[b])]
+            });
+            let score_active = DVector::from_fn(active.len(), |a, _| score[active[a]]);
+            let delta = info_active
+                .lu()
+                .solve(&score_active)
+                .ok_or_else(|| "Information matrix is singular".to_string())?;
+
+            let mut step = 1.0;
+            let mut accepted = None;
+            while step > 1.0e-10 {
+                let mut candidate = theta.clone();
+                for (a, &i) in active.iter().enumerate() {
+                    candidate[i] = floor(i, theta[i] + step * delta[a]);
+                }
+                if let Ok(evaluation) = self.evaluate(&candidate) {
+                    if evaluation.0 >= log_likelihood - 1.0e-12 {
+                        accepted = Some((candidate, evaluation));
+                        break;
+                    }
+                }
+                step /= 2.0;
+            }
+
+            let Some((candidate, (new_log_likelihood, new_score, new_information))) = accepted else {
+                break;
+            };
+            let change = candidate
+                .iter()
+                .zip(&theta)
+                .map(|(new, old)| (new - old).abs() / old.abs().max(1.0))
+                .fold(0.0, f64::max);
+            let improvement = new_log_likelihood - log_likelihood;
+
+            theta = candidate;
+            log_likelihood = new_log_likelihood;
+            score = new_score;
+            information = new_information;
+
+            if change < 1.0e-10 && improvement.abs() < 1.0e-10 {
+                break;
+            }
+        }
+
+        Ok((theta, log_likelihood))
+    }
+
+    /// Log-likelihood profil dengan komponen `index` ditetapkan pada `value`
+    fn profile(
+        &self,
+        start: &[f64],
+        index: usize,
+        value: f64,
+        max_iter: usize
+    ) -> Result<(Vec<f64>, f64), String> {
+        let mut theta = start.to_vec();
+        theta[index] = value;
+        let free: Vec<bool> = (0..theta.len()).map(|i| i != index).collect();
+        self.maximize(theta, &free, max_iter)
+    }
+
+    /// Batas interval: nilai komponen dengan 2(ℓ̂ - ℓₚ) sama dengan nilai kritis chi-square(1)
+    fn profile_interval(
+        &self,
+        theta_hat: &[f64],
+        log_likelihood_max: f64,
+        index: usize,
+        critical: f64,
+        scale: f64,
+        max_iter: usize
+    ) -> Result<ConfidenceInterval, String> {
+        let estimate = theta_hat[index];
+        let is_error = index == theta_hat.len() - 1;
+        let deviance = |value: f64, start: &[f64]| -> Result<(f64, Vec<f64>), String> {
+            let (theta, log_likelihood) = self.profile(start, index, value, max_iter)?;
+            Ok((2.0 * (log_likelihood_max - log_likelihood).max(0.0), theta))
+        };
+
+        // Batas bawah
+        let lower_limit = if is_error { estimate * 1.0e-6 } else { 0.0 };
+        let lower_bound = if estimate <= lower_limit {
+            lower_limit
+        } else {
+            let (limit_deviance, _) = deviance(lower_limit, theta_hat)?;
+            if limit_deviance <= critical {
+                lower_limit
+            } else {
+                self.bisect(&deviance, theta_hat, lower_limit, estimate, critical)?
+            }
+        };
+
+        // Batas atas: perlebar langkah sampai deviance melewati nilai kritis
+        let mut step = scale.max(estimate.abs()).max(1.0e-8);
+        let mut inside = estimate;
+        let mut start = theta_hat.to_vec();
+        let mut outside = None;
+        for _ in 0..60 {
+            let candidate = estimate + step;
+            let (candidate_deviance, theta) = deviance(candidate, &start)?;
+            if candidate_deviance > critical {
+                outside = Some(candidate);
+                break;
+            }
+            inside = candidate;
+            start = theta;
+            step *= 2.0;
+        }
+        let outside = outside.ok_or_else(|| "upper bound could not be bracketed".to_string())?;
+        let upper_bound = self.bisect(&deviance, &start, outside, inside, critical)?;
+
+        Ok(ConfidenceInterval { lower_bound, upper_bound })
+    }
+
+    /// Bisection antara `outside` (deviance > kritis) dan `inside` (deviance ≤ kritis)
+    fn bisect<F>(
+        &self,
+        deviance: &F,
+        start: &[f64],
+        mut outside: f64,
+        mut inside: f64,
+        critical: f64
+    ) -> Result<f64, String>
+        where F: Fn(f64, &[f64]) -> Result<(f64, Vec<f64>), String>
+    {
+        let tolerance = 1.0e-7 * outside.abs().max(inside.abs()).max(1.0e-8);
+        let mut start = start.to_vec();
+        for _ in 0..100 {
+            if (outside - inside).abs() <= tolerance {
+                break;
+            }
+            let middle = 0.5 * (outside + inside);
+            let (middle_deviance, theta) = deviance(middle, &start)?;
+            if middle_deviance > critical {
+                outside = middle;
+            } else {
+                inside = middle;
+                start = theta;
+            }
+        }
+        Ok(0.5 * (outside + inside))
+    }
+}
@@ -83,12 +83,13 @@ pub fn initialize_residual_variance(
     data: &AnalysisData,
     config: &VarianceCompsConfig
 ) -> Result<f64, String> {
-    let (design_matrix, y) = create_design_and_response(data, config)?;
-    let x = from_vec(&design_matrix)?;
+    let (_, y) = create_design_and_response(data, config)?;
+    let (x_matrices, _) = create_effect_matrices(data, config)?;
+    let x = &x_matrices[0];
     let y_vec = vec_to_vector(&y);
 
-    // Hitung r = y - X(X'X)^-1X'y
-    let xt_x = x.transpose() * &x;
+    // Hitung r = y - X₀(X₀'X₀)^-1X₀'y
+    let xt_x = x.transpose() * x;
     let xt_y = x.transpose() * &y_vec;
 
     let beta = match xt_x.try_inverse() {
@@ -298,9 +299,9 @@ pub fn calculate_ml_gradient_hessian(
 
     // For γᵢ² and σₑ²
     for i in 0..k {
-        // E[∂²l/∂γᵢ²∂σₑ²] = -1/(2σₑ⁴)tr(V⁻¹Vᵢ)
+        // E[∂²l/∂γᵢ²∂σₑ²] = -1/(2σₑ²)tr(V⁻¹Vᵢ)
         let tr = trace(&(&v_inv * &v_matrices[i]));
-        let val = (-0.5 / (residual_variance * residual_variance)) * tr;
+        let val = (-0.5 / residual_variance) * tr;
 
         fisher_info[(i, k)] = val;
         fisher_info[(k, i)] = val; // Symmetric
@@ -499,16 +500,13 @@ pub fn calculate_reml_gradient_hessian(
 
     // Untuk setiap γᵢ²
     for i in 0..k {
-        // ∂l/∂γᵢ² = 1/(2σₑ²)y'RVᵢRy - 1/2tr(X₀'V⁻¹X₀)⁻¹(X₀'V⁻¹VᵢV⁻¹X₀)) - 1/2tr(RVᵢ)
+        // ∂l/∂γᵢ² = 1/(2σₑ²)y'RVᵢRy - 1/2tr(RVᵢ)
+        // tr(RVᵢ) = tr(V⁻¹Vᵢ) - tr((X₀'V⁻¹X₀)⁻¹X₀'V⁻¹VᵢV⁻¹X₀) sudah memuat koreksi efek tetap
         let r_vi = &r_matrix * &v_matrices[i];
         let quad = y.transpose() * &r_vi * &r_matrix * y;
+        let tr = trace(&r_vi);
 
-        let x0_v_inv_vi_v_inv_x0 = x0.transpose() * &v_inv * &v_matrices[i] * &v_inv * x0;
-        let tr1 = trace(&(x0_v_inv_x0_inv.clone() * x0_v_inv_vi_v_inv_x0));
-
-        let tr2 = trace(&r_vi);
-
-        gradient[i] = (0.5 / residual_variance) * quad[0] - 0.5 * tr1 - 0.5 * tr2;
+        gradient[i] = (0.5 / residual_variance) * quad[0] - 0.5 * tr;
     }
 
     // Untuk σₑ²
@@ -568,9 +566,9 @@ pub fn calculate_reml_gradient_hessian(
 
     // For γᵢ² and σₑ²
     for i in 0..k {
-        // E[∂²l/∂γᵢ²∂σₑ²] = -1/(2σₑ⁴)tr(RVᵢ)
+        // E[∂²l/∂γᵢ²∂σₑ²] = -1/(2σₑ²)tr(RVᵢ)
         let tr = trace(&(&r_matrix * &v_matrices[i]));
-        let val = (-0.5 / (residual_variance * residual_variance)) * tr;
+        let val = (-0.5 / residual_variance) * tr;
 
         fisher_info[(i, k)] = val;
         fisher_info[(k, i)] = val; // Symmetric
@@ -754,11 +752,12 @@ pub fn likelihood_estimation(
             "Fisher"
         };
 
-        // Calculate step
+        // Calculate step (gradient sudah dinegasikan, sehingga arah naik adalah -gradient)
+        let ascent = -&gradient;
         let delta = if step_type == "Newton" {
-            solve_linear_system(&hessian, &gradient)?
+            solve_linear_system(&hessian, &ascent)?
         } else {
-            solve_linear_system(&fisher_info, &gradient)?
+            solve_linear_system(&fisher_info, &ascent)?
         };
 
         // Apply step-halving if needed
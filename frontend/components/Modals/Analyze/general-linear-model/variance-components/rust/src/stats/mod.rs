@@ -3,6 +3,7 @@ pub mod anova;
 pub mod center;
 pub mod common;
 pub mod design_matrix;
+pub mod intervals;
 pub mod likelihood;
 pub mod minque;
 pub mod ml;
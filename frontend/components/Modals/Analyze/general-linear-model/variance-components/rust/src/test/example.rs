@@ -0,0 +1,253 @@
+use std::collections::HashMap;
+
+use crate::models::{
+    config::{ IntervalsConfig, MainConfig, OptionsConfig, VarianceCompsConfig },
+    data::{ AnalysisData, DataRecord, DataValue },
+    result::{ ConfidenceInterval, VarianceComponentInterval, VarianceComponentIntervals },
+};
+use crate::stats::core::{
+    anova_estimation,
+    calculate_variance_component_intervals,
+    ml_estimation,
+    reml_estimation,
+};
+
+// lme4 Dyestuff: yield of dyestuff from five preparations of each of six batches
+const DYESTUFF: [(&str, [f64; 5]); 6] = [
+    ("A", [1545.0, 1440.0, 1440.0, 1520.0, 1580.0]),
+    ("B", [1540.0, 1555.0, 1490.0, 1560.0, 1495.0]),
+    ("C", [1595.0, 1550.0, 1605.0, 1510.0, 1560.0]),
+    ("D", [1445.0, 1440.0, 1595.0, 1465.0, 1545.0]),
+    ("E", [1595.0, 1630.0, 1515.0, 1635.0, 1625.0]),
+    ("F", [1520.0, 1455.0, 1450.0, 1480.0, 1445.0]),
+];
+
+// Balanced one-way layout: MS(Batch) = 11271.5 on 5 df, MS(Error) = 2451.25 on 24 df
+const BATCH_VARIANCE: f64 = 1764.05;
+const ERROR_VARIANCE: f64 = 2451.25;
+const BATCH_VARIANCE_ML: f64 = 1388.3333333333;
+
+fn record(name: &str, value: DataValue) -> DataRecord {
+    DataRecord {
+        values: HashMap::from([(name.to_string(), value)]),
+    }
+}
+
+fn dyestuff_data(skip: &[usize]) -> AnalysisData {
+    let mut yields = Vec::new();
+    let mut batches = Vec::new();
+    for (case, (batch, value)) in DYESTUFF.iter()
+        .flat_map(|(batch, values)| values.iter().map(move |value| (batch, value)))
+        .enumerate() {
+        if skip.contains(&(case + 1)) {
+            continue;
+        }
+        yields.push(record("Yield", DataValue::Number(*value)));
+        batches.push(record("Batch", DataValue::Text(batch.to_string())));
+    }
+
+    AnalysisData {
+        dependent_data: vec![yields],
+        fix_factor_data: vec![],
+        random_factor_data: Some(vec![batches]),
+        covariate_data: None,
+        wls_data: None,
+        dependent_data_defs: vec![],
+        fix_factor_data_defs: vec![],
+        random_factor_data_defs: None,
+        covariate_data_defs: None,
+        wls_data_defs: None,
+    }
+}
+
+// Yield ~ (1 | Batch) with the given estimation method
+fn dyestuff_config(options: OptionsConfig) -> VarianceCompsConfig {
+    VarianceCompsConfig {
+        main: MainConfig {
+            dep_var: Some("Yield".to_string()),
+            rand_factor: Some(vec!["Batch".to_string()]),
+            ..Default::default()
+        },
+        options,
+        ..Default::default()
+    }
+}
+
+fn anova() -> OptionsConfig {
+    OptionsConfig { minque: false, anova: true, ..Default::default() }
+}
+
+fn ml() -> OptionsConfig {
+    OptionsConfig { minque: false, max_likelihood: true, max_iter: Some(100), ..Default::default() }
+}
+
+fn reml() -> OptionsConfig {
+    OptionsConfig {
+        minque: false,
+        res_max_likelihood: true,
+        max_iter: Some(100),
+        ..Default::default()
+    }
+}
+
+fn with_intervals(mut config: VarianceCompsConfig, intervals: IntervalsConfig) -> VarianceCompsConfig {
+    config.intervals = IntervalsConfig {
+        conf_interval: true,
+        conf_level: Some(95.0),
+        ..intervals
+    };
+    config
+}
+
+fn assert_close(actual: f64, expected: f64, tolerance: f64, what: &str) {
+    assert!(
+        (actual - expected).abs() <= tolerance * expected.abs().max(1.0),
+        "{}: {} != {}",
+        what,
+        actual,
+        expected
+    );
+}
+
+fn assert_interval(interval: &Option<ConfidenceInterval>, lower: f64, upper: f64, what: &str) {
+    let interval = interval.as_ref().unwrap_or_else(|| panic!("{}: no interval", what));
+    assert_close(interval.lower_bound, lower, 1e-6, what);
+    assert_close(interval.upper_bound, upper, 1e-6, what);
+}
+
+fn component<'a>(intervals: &'a VarianceComponentIntervals, name: &str) -> &'a VarianceComponentInterval {
+    intervals.components
+        .iter()
+        .find(|component| component.component == name)
+        .unwrap_or_else(|| panic!("no interval for {}", name))
+}
+
+// lme4::lmer(Yield ~ 1 + (1 | Batch), Dyestuff): REML 1764.05 / 2451.25, ML 1388.33 / 2451.25.
+// In a balanced layout the ANOVA estimates equal REML.
+#[test]
+fn dyestuff_point_estimates() {
+    let (anova_estimates, _) = anova_estimation(&dyestuff_data(&[]), &dyestuff_config(anova())).unwrap();
+    let (ml_estimates, _, _) = ml_estimation(&dyestuff_data(&[]), &dyestuff_config(ml())).unwrap();
+    let (reml_estimates, _, _) = reml_estimation(&dyestuff_data(&[]), &dyestuff_config(reml())).unwrap();
+
+    for (method, estimates, batch) in [
+        ("ANOVA", &anova_estimates, BATCH_VARIANCE),
+        ("ML", &ml_estimates, BATCH_VARIANCE_ML),
+        ("REML", &reml_estimates, BATCH_VARIANCE),
+    ] {
+        assert_close(estimates["Var(Batch)"], batch, 1e-6, method);
+        assert_close(estimates["Var(Error)"], ERROR_VARIANCE, 1e-6, method);
+    }
+}
+
+// Dyestuff without cases 2, 7, 8 and 21, so the batches are unbalanced and the methods differ.
+// ANOVA is Henderson's method I with n0 = (N - Σnᵢ²/N) / (a - 1); ML and REML maximise the
+// closed-form one-way likelihood with |Vᵢ| = σₑ^(2(nᵢ-1)) (σₑ² + nᵢσ²)
+#[test]
+fn dyestuff_unbalanced_point_estimates() {
+    let data = dyestuff_data(&[2, 7, 8, 21]);
+    let (anova_estimates, _) = anova_estimation(&data, &dyestuff_config(anova())).unwrap();
+    let (ml_estimates, _, _) = ml_estimation(&data, &dyestuff_config(ml())).unwrap();
+    let (reml_estimates, _, _) = reml_estimation(&data, &dyestuff_config(reml())).unwrap();
+
+    for (method, estimates, batch, error) in [
+        ("ANOVA", &anova_estimates, 1701.4992559524, 2565.2083333333),
+        ("ML", &ml_estimates, 1290.2509109641, 2550.4732910753),
+        ("REML", &reml_estimates, 1655.3118889295, 2554.3952147582),
+    ] {
+        assert_close(estimates["Var(Batch)"], batch, 1e-6, method);
+        assert_close(estimates["Var(Error)"], error, 1e-6, method);
+    }
+}
+
+// Satterthwaite (1946): ν = σ̂⁴ / Σ (aₛMSₛ)²/dfₛ = 3.0319 for Var(Batch), [νσ̂²/χ²(.975), νσ̂²/χ²(.025)];
+// Var(Error) uses the exact chi-square interval on 24 df
+#[test]
+fn dyestuff_satterthwaite_intervals() {
+    let config = with_intervals(dyestuff_config(anova()), IntervalsConfig::default());
+    let intervals = calculate_variance_component_intervals(&dyestuff_data(&[]), &config).unwrap();
+    assert_eq!(intervals.method, "ANOVA");
+
+    let batch = component(&intervals, "Var(Batch)");
+    assert_close(batch.satterthwaite_df.unwrap(), 3.0318672600, 1e-8, "Batch df");
+    assert_interval(&batch.satterthwaite, 568.5047921404, 23994.0093361906, "Var(Batch)");
+
+    let error = component(&intervals, "Var(Error)");
+    assert_eq!(error.satterthwaite_df, Some(24.0));
+    assert_interval(&error.satterthwaite, 1494.5098283453, 4743.9147956812, "Var(Error)");
+}
+
+// Balanced one-way REML: var(σ̂ₑ²) = 2σₑ⁴/(a(n-1)),
+// var(σ̂²) = 2/n² [(σₑ² + nσ²)²/(a-1) + σₑ⁴/(a(n-1))] (Searle, Casella & McCulloch 1992, §4.7)
+#[test]
+fn dyestuff_wald_intervals() {
+    let config = with_intervals(dyestuff_config(reml()), IntervalsConfig::default());
+    let intervals = calculate_variance_component_intervals(&dyestuff_data(&[]), &config).unwrap();
+    assert_eq!(intervals.method, "Restricted Maximum Likelihood");
+
+    let batch = component(&intervals, "Var(Batch)");
+    assert_close(batch.std_error.unwrap(), 1432.7512523841, 1e-6, "Batch SE");
+    assert_interval(&batch.wald, -1044.0908534776, 4572.1908534776, "Var(Batch)");
+    assert!(batch.wald_log.is_none());
+
+    let error = component(&intervals, "Var(Error)");
+    assert_close(error.std_error.unwrap(), 707.6149236755, 1e-6, "Error SE");
+    assert_interval(&error.wald, 1064.3502346729, 3838.1497653271, "Var(Error)");
+}
+
+// ln σ̂² ± z·SE/σ̂², transformed back
+#[test]
+fn dyestuff_log_wald_intervals() {
+    let config = with_intervals(dyestuff_config(reml()), IntervalsConfig {
+        wald_log_scale: true,
+        ..Default::default()
+    });
+    let intervals = calculate_variance_component_intervals(&dyestuff_data(&[]), &config).unwrap();
+
+    let batch = component(&intervals, "Var(Batch)");
+    assert_interval(&batch.wald_log, 359.0623744223, 8666.6624636082, "Var(Batch)");
+}
+
+// Closed-form balanced REML log-likelihood
+// -½[a(n-1) ln σₑ² + (a-1) ln λ + SSE/σₑ² + SSA/λ], λ = σₑ² + nσ², profiled over the other component
+// and cut at χ²₁(.95) = 3.8415
+#[test]
+fn dyestuff_profile_likelihood_intervals() {
+    let config = with_intervals(dyestuff_config(reml()), IntervalsConfig {
+        profile_likelihood: true,
+        ..Default::default()
+    });
+    let intervals = calculate_variance_component_intervals(&dyestuff_data(&[]), &config).unwrap();
+
+    let batch = component(&intervals, "Var(Batch)");
+    let interval = batch.profile_likelihood.as_ref().unwrap();
+    assert_close(interval.lower_bound, 245.1478251613, 1e-5, "Var(Batch) lower");
+    assert_close(interval.upper_bound, 10214.3614096863, 1e-5, "Var(Batch) upper");
+
+    let error = component(&intervals, "Var(Error)");
+    let interval = error.profile_likelihood.as_ref().unwrap();
+    assert_close(interval.lower_bound, 1461.5423283939, 1e-5, "Var(Error) lower");
+    assert_close(interval.upper_bound, 4577.5606873266, 1e-5, "Var(Error) upper");
+}
+
+// ANOVA: exact interval from F = MS(Batch)/MS(Error) on (5, 24), ρ = (F/F_q - 1)/(F/F_q - 1 + n).
+// REML: delta-method Wald interval, truncated at 0
+#[test]
+fn dyestuff_intraclass_correlation() {
+    let icc_only = IntervalsConfig { icc: true, ..Default::default() };
+
+    let config = with_intervals(dyestuff_config(anova()), icc_only.clone());
+    let intervals = calculate_variance_component_intervals(&dyestuff_data(&[]), &config).unwrap();
+    let icc = &intervals.intraclass_correlations.unwrap()[0];
+    assert_close(icc.icc, 0.4184874149, 1e-8, "ANOVA ICC");
+    assert_eq!(icc.interval_method.as_deref(), Some("F distribution"));
+    assert_interval(&icc.confidence_interval, 0.0838360507, 0.8478768155, "ANOVA ICC");
+
+    let config = with_intervals(dyestuff_config(reml()), icc_only);
+    let intervals = calculate_variance_component_intervals(&dyestuff_data(&[]), &config).unwrap();
+    let icc = &intervals.intraclass_correlations.unwrap()[0];
+    assert_close(icc.icc, 0.4184874149, 1e-8, "REML ICC");
+    assert_close(icc.std_error.unwrap(), 0.2162049906, 1e-6, "REML ICC SE");
+    assert_eq!(icc.interval_method.as_deref(), Some("Wald"));
+    assert_interval(&icc.confidence_interval, 0.0, 0.4184874149 + 1.959963984540054 * 0.2162049906, "REML ICC");
+}
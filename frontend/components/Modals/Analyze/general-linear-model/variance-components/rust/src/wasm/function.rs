@@ -95,7 +95,21 @@ pub fn run_analysis(
         // This is already handled in method_info, just tracking the function execution
     }
 
-    // Step 7: Save variance component estimates if requested
+    // Step 7: Confidence intervals for variance components and ICC if requested
+    let mut variance_intervals = None;
+    if config.intervals.conf_interval || config.intervals.icc {
+        logger.add_log("calculate_variance_component_intervals");
+        match core::calculate_variance_component_intervals(data, config) {
+            Ok(intervals) => {
+                variance_intervals = Some(intervals);
+            }
+            Err(e) => {
+                error_collector.add_error("calculate_variance_component_intervals", &e);
+            }
+        }
+    }
+
+    // Step 8: Save variance component estimates if requested
     if config.save.var_comp_est {
         logger.add_log("save_variance_component_estimates");
         match core::save_variance_component_estimates(data, config) {
@@ -106,7 +120,7 @@ pub fn run_analysis(
         }
     }
 
-    // Step 8: Save component covariation if requested
+    // Step 9: Save component covariation if requested
     if config.save.comp_covar {
         logger.add_log("save_component_covariation");
         match core::save_component_covariation(data, config) {
@@ -124,6 +138,7 @@ pub fn run_analysis(
         anova_table,
         expected_mean_squares,
         method_info,
+        variance_intervals,
         design: None, // This would come from the config
         executed_functions: logger.get_executed_functions(),
     };
@@ -92,11 +92,20 @@ export type VarianceCompsSaveProps = {
     data: VarianceCompsSaveType;
 };
 
+export type VarianceCompsIntervalsType = {
+    ConfInterval: boolean;
+    ConfLevel: number | null;
+    WaldLogScale: boolean;
+    ProfileLikelihood: boolean;
+    Icc: boolean;
+};
+
 export type VarianceCompsType = {
     main: VarianceCompsMainType;
     model: VarianceCompsModelType;
     options: VarianceCompsOptionsType;
     save: VarianceCompsSaveType;
+    intervals: VarianceCompsIntervalsType;
 };
 
 export type VarianceCompsContainerProps = {